@@ -0,0 +1,238 @@
+use inject::{CommandRunner, DisplayServer};
+use serde_json::Value;
+use shared_types::{PasteProfile, PasteShortcut};
+
+pub fn paste_shortcut_for(profiles: &[PasteProfile], window_class: Option<&str>) -> PasteShortcut {
+    let Some(window_class) = window_class
+        .map(str::trim)
+        .filter(|class| !class.is_empty())
+    else {
+        return PasteShortcut::default();
+    };
+    profiles
+        .iter()
+        .find(|profile| class_matches(&profile.window_class, window_class))
+        .map(|profile| profile.shortcut)
+        .unwrap_or_default()
+}
+
+fn class_matches(pattern: &str, window_class: &str) -> bool {
+    let pattern: Vec<char> = pattern.trim().to_lowercase().chars().collect();
+    let class: Vec<char> = window_class.to_lowercase().chars().collect();
+    if pattern.is_empty() {
+        return false;
+    }
+    let (mut p, mut c) = (0, 0);
+    let mut backtrack: Option<(usize, usize)> = None;
+    while c < class.len() {
+        if p < pattern.len() && pattern[p] == '*' {
+            backtrack = Some((p, c));
+            p += 1;
+        } else if p < pattern.len() && pattern[p] == class[c] {
+            p += 1;
+            c += 1;
+        } else if let Some((star, matched)) = backtrack {
+            p = star + 1;
+            c = matched + 1;
+            backtrack = Some((star, matched + 1));
+        } else {
+            return false;
+        }
+    }
+    pattern[p..].iter().all(|ch| *ch == '*')
+}
+
+pub fn active_window_class<R: CommandRunner>(
+    runner: &mut R,
+    display: DisplayServer,
+) -> Option<String> {
+    if display == DisplayServer::Wayland {
+        if let Some(class) = run_probe(runner, "swaymsg", &["-t", "get_tree"])
+            .and_then(|tree| serde_json::from_str(&tree).ok())
+            .and_then(|tree| focused_sway_class(&tree))
+        {
+            return Some(class);
+        }
+        if let Some(class) = run_probe(runner, "hyprctl", &["activewindow", "-j"])
+            .and_then(|window| serde_json::from_str::<Value>(&window).ok())
+            .and_then(|window| json_class(&window, "class"))
+        {
+            return Some(class);
+        }
+    }
+    run_probe(
+        runner,
+        "xdotool",
+        &["getactivewindow", "getwindowclassname"],
+    )
+}
+
+fn run_probe<R: CommandRunner>(runner: &mut R, program: &str, args: &[&str]) -> Option<String> {
+    let args: Vec<String> = args.iter().map(|arg| arg.to_string()).collect();
+    let output = runner.run(program, &args, None).ok()?;
+    let stdout = output.stdout.trim();
+    (output.success && !stdout.is_empty()).then(|| stdout.to_string())
+}
+
+fn focused_sway_class(node: &Value) -> Option<String> {
+    if node["focused"].as_bool() == Some(true) {
+        return json_class(node, "app_id")
+            .or_else(|| json_class(&node["window_properties"], "class"));
+    }
+    ["nodes", "floating_nodes"]
+        .iter()
+        .filter_map(|key| node[key].as_array())
+        .flatten()
+        .find_map(focused_sway_class)
+}
+
+fn json_class(value: &Value, key: &str) -> Option<String> {
+    value[key]
+        .as_str()
+        .map(str::trim)
+        .filter(|class| !class.is_empty())
+        .map(str::to_string)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use inject::CommandOutput;
+    use shared_types::default_paste_profiles;
+    use std::collections::HashMap;
+    use std::io;
+
+    #[derive(Default)]
+    struct ProbeRunner {
+        outputs: HashMap<String, String>,
+        calls: Vec<String>,
+    }
+
+    impl ProbeRunner {
+        fn with(mut self, command: &str, stdout: &str) -> Self {
+            self.outputs.insert(command.to_string(), stdout.to_string());
+            self
+        }
+    }
+
+    impl CommandRunner for ProbeRunner {
+        fn run(
+            &mut self,
+            program: &str,
+            args: &[String],
+            _stdin: Option<&str>,
+        ) -> io::Result<CommandOutput> {
+            let command = format!("{program} {}", args.join(" "));
+            self.calls.push(command.clone());
+            let stdout = self
+                .outputs
+                .get(&command)
+                .cloned()
+                .ok_or_else(|| io::Error::from(io::ErrorKind::NotFound))?;
+            Ok(CommandOutput {
+                success: true,
+                status: "exit status: 0".to_string(),
+                stdout,
+                stderr: String::new(),
+            })
+        }
+    }
+
+    #[test]
+    fn terminals_resolve_to_terminal_paste_shortcuts() {
+        let profiles = default_paste_profiles();
+        let shortcut = |class| paste_shortcut_for(&profiles, Some(class));
+
+        assert_eq!(shortcut("gnome-terminal-server"), PasteShortcut::CtrlShiftV);
+        assert_eq!(shortcut("org.gnome.Terminal"), PasteShortcut::CtrlShiftV);
+        assert_eq!(shortcut("kitty"), PasteShortcut::CtrlShiftV);
+        assert_eq!(shortcut("Alacritty"), PasteShortcut::CtrlShiftV);
+        assert_eq!(shortcut("org.kde.konsole"), PasteShortcut::CtrlShiftV);
+        assert_eq!(shortcut("XTerm"), PasteShortcut::ShiftInsert);
+        assert_eq!(shortcut("firefox"), PasteShortcut::CtrlV);
+        assert_eq!(shortcut("kitty-launcher"), PasteShortcut::CtrlV);
+        assert_eq!(paste_shortcut_for(&profiles, None), PasteShortcut::CtrlV);
+    }
+
+    #[test]
+    fn first_matching_profile_wins() {
+        let profiles = vec![
+            PasteProfile::new("code*", PasteShortcut::ShiftInsert),
+            PasteProfile::new("*", PasteShortcut::CtrlShiftV),
+        ];
+
+        assert_eq!(
+            paste_shortcut_for(&profiles, Some("Code - OSS")),
+            PasteShortcut::ShiftInsert
+        );
+        assert_eq!(
+            paste_shortcut_for(&profiles, Some("gedit")),
+            PasteShortcut::CtrlShiftV
+        );
+        assert_eq!(
+            paste_shortcut_for(&profiles, Some(" ")),
+            PasteShortcut::CtrlV
+        );
+    }
+
+    #[test]
+    fn class_patterns_support_wildcards() {
+        assert!(class_matches("*term*", "xfce4-terminal"));
+        assert!(class_matches("a*b*c", "aXXbYYc"));
+        assert!(class_matches("a*c", "abcbc"));
+        assert!(!class_matches("a*c", "abcb"));
+        assert!(!class_matches(" ", "kitty"));
+    }
+
+    #[test]
+    fn x11_reads_the_active_window_class_with_xdotool() {
+        let mut runner =
+            ProbeRunner::default().with("xdotool getactivewindow getwindowclassname", "kitty\n");
+
+        assert_eq!(
+            active_window_class(&mut runner, DisplayServer::X11).as_deref(),
+            Some("kitty")
+        );
+        assert_eq!(runner.calls.len(), 1);
+    }
+
+    #[test]
+    fn sway_reports_the_focused_window_class() {
+        let tree = r#"{"focused": false, "nodes": [
+            {"focused": false, "app_id": "firefox", "nodes": []},
+            {"focused": false, "nodes": [], "floating_nodes": [
+                {"focused": true, "app_id": null, "window_properties": {"class": "Alacritty"}}
+            ]}
+        ]}"#;
+        let mut runner = ProbeRunner::default().with("swaymsg -t get_tree", tree);
+
+        assert_eq!(
+            active_window_class(&mut runner, DisplayServer::Wayland).as_deref(),
+            Some("Alacritty")
+        );
+    }
+
+    #[test]
+    fn wayland_falls_back_to_hyprctl_then_xwayland() {
+        let mut runner =
+            ProbeRunner::default().with("hyprctl activewindow -j", r#"{"class": "foot"}"#);
+        assert_eq!(
+            active_window_class(&mut runner, DisplayServer::Wayland).as_deref(),
+            Some("foot")
+        );
+
+        let mut runner = ProbeRunner::default();
+        assert_eq!(
+            active_window_class(&mut runner, DisplayServer::Wayland),
+            None
+        );
+        assert_eq!(
+            runner.calls,
+            vec![
+                "swaymsg -t get_tree",
+                "hyprctl activewindow -j",
+                "xdotool getactivewindow getwindowclassname",
+            ]
+        );
+    }
+}
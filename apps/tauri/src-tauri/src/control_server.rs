@@ -35,6 +35,17 @@ pub fn start(handle: PttHandle) {
                     .respond(tiny_http::Response::from_string(body).with_status_code(status));
                 continue;
             }
+            if url.starts_with("/cancel") {
+                let result = handle.cancel();
+                let status = if result.is_ok() { 200 } else { 500 };
+                let body = match result {
+                    Ok(state) => format!("ok {state:?}"),
+                    Err(err) => format!("error {err}"),
+                };
+                let _ = request
+                    .respond(tiny_http::Response::from_string(body).with_status_code(status));
+                continue;
+            }
             if url.starts_with("/ping") {
                 let _ = request.respond(tiny_http::Response::from_string("pong"));
                 continue;
@@ -0,0 +1,201 @@
+use crate::logging::app_identifier;
+use core_input::{CpalTonePlayer, FeedbackTone, TonePlayer};
+use log::warn;
+use shared_types::AppSettings;
+use std::sync::Arc;
+use tauri::api::notification::Notification;
+
+pub const COMPLETE_NOTIFICATION_TITLE: &str = "Transcription complete";
+const PREVIEW_CHARS: usize = 120;
+
+pub trait Notifier: Send + Sync {
+    fn notify(&self, title: &str, body: &str) -> Result<(), String>;
+}
+
+pub struct DesktopNotifier;
+
+impl Notifier for DesktopNotifier {
+    fn notify(&self, title: &str, body: &str) -> Result<(), String> {
+        let identifier =
+            app_identifier().ok_or_else(|| "no app window to notify from".to_string())?;
+        Notification::new(identifier)
+            .title(title)
+            .body(body)
+            .show()
+            .map_err(|err| err.to_string())
+    }
+}
+
+#[derive(Clone)]
+pub struct Feedback {
+    tones: Arc<dyn TonePlayer>,
+    notifier: Arc<dyn Notifier>,
+}
+
+impl Default for Feedback {
+    fn default() -> Self {
+        Self::new(Arc::new(CpalTonePlayer), Arc::new(DesktopNotifier))
+    }
+}
+
+impl Feedback {
+    pub fn new(tones: Arc<dyn TonePlayer>, notifier: Arc<dyn Notifier>) -> Self {
+        Self { tones, notifier }
+    }
+
+    pub fn play(&self, settings: &AppSettings, tone: FeedbackTone) {
+        if !settings.sound_feedback {
+            return;
+        }
+        let tones = Arc::clone(&self.tones);
+        std::thread::spawn(move || {
+            if let Err(err) = tones.play(tone) {
+                warn!("failed to play {tone:?} feedback tone: {err}");
+            }
+        });
+    }
+
+    pub fn transcript_ready(&self, settings: &AppSettings, text: &str) {
+        if !settings.notify_on_complete {
+            return;
+        }
+        let preview = transcript_preview(text);
+        if preview.is_empty() {
+            return;
+        }
+        if let Err(err) = self.notifier.notify(COMPLETE_NOTIFICATION_TITLE, &preview) {
+            warn!("failed to show completion notification: {err}");
+        }
+    }
+}
+
+fn transcript_preview(text: &str) -> String {
+    let text = text.split_whitespace().collect::<Vec<_>>().join(" ");
+    if text.chars().count() <= PREVIEW_CHARS {
+        return text;
+    }
+    let mut preview: String = text.chars().take(PREVIEW_CHARS - 1).collect();
+    preview.truncate(preview.trim_end().len());
+    preview.push('…');
+    preview
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use core_input::AudioError;
+    use std::sync::{mpsc, Mutex};
+    use std::time::Duration;
+
+    struct MockTones {
+        played: Mutex<mpsc::Sender<FeedbackTone>>,
+        fail: bool,
+    }
+
+    impl TonePlayer for MockTones {
+        fn play(&self, tone: FeedbackTone) -> Result<(), AudioError> {
+            let _ = self.played.lock().unwrap().send(tone);
+            if self.fail {
+                return Err(AudioError::NoOutputDevice);
+            }
+            Ok(())
+        }
+    }
+
+    #[derive(Default)]
+    struct MockNotifier {
+        calls: Mutex<Vec<(String, String)>>,
+        fail: bool,
+    }
+
+    impl Notifier for MockNotifier {
+        fn notify(&self, title: &str, body: &str) -> Result<(), String> {
+            self.calls
+                .lock()
+                .unwrap()
+                .push((title.to_string(), body.to_string()));
+            if self.fail {
+                return Err("notification daemon unavailable".to_string());
+            }
+            Ok(())
+        }
+    }
+
+    fn feedback(fail: bool) -> (Feedback, mpsc::Receiver<FeedbackTone>, Arc<MockNotifier>) {
+        let (sender, receiver) = mpsc::channel();
+        let notifier = Arc::new(MockNotifier {
+            fail,
+            ..MockNotifier::default()
+        });
+        let tones = Arc::new(MockTones {
+            played: Mutex::new(sender),
+            fail,
+        });
+        (Feedback::new(tones, notifier.clone()), receiver, notifier)
+    }
+
+    fn settings(sound_feedback: bool, notify_on_complete: bool) -> AppSettings {
+        AppSettings {
+            sound_feedback,
+            notify_on_complete,
+            ..AppSettings::default()
+        }
+    }
+
+    #[test]
+    fn tones_only_play_when_sound_feedback_is_enabled() {
+        let (feedback, played, _) = feedback(false);
+
+        feedback.play(&settings(false, true), FeedbackTone::Start);
+        assert!(played.recv_timeout(Duration::from_millis(100)).is_err());
+
+        feedback.play(&settings(true, false), FeedbackTone::Error);
+        assert_eq!(
+            played.recv_timeout(Duration::from_secs(1)),
+            Ok(FeedbackTone::Error)
+        );
+    }
+
+    #[test]
+    fn notifications_only_fire_when_enabled() {
+        let (feedback, _, notifier) = feedback(false);
+
+        feedback.transcript_ready(&settings(true, false), "hello world");
+        assert!(notifier.calls.lock().unwrap().is_empty());
+
+        feedback.transcript_ready(&settings(false, true), "  hello\n world ");
+        feedback.transcript_ready(&settings(false, true), " ");
+        assert_eq!(
+            *notifier.calls.lock().unwrap(),
+            vec![(
+                COMPLETE_NOTIFICATION_TITLE.to_string(),
+                "hello world".to_string()
+            )]
+        );
+    }
+
+    #[test]
+    fn long_transcripts_are_shortened_for_the_preview() {
+        let text = "word ".repeat(40);
+
+        let preview = transcript_preview(&text);
+
+        assert_eq!(preview.chars().count(), PREVIEW_CHARS);
+        assert!(preview.ends_with("word…"));
+    }
+
+    #[test]
+    fn feedback_failures_are_only_logged() {
+        let (feedback, played, notifier) = feedback(true);
+        let enabled = settings(true, true);
+
+        feedback.play(&enabled, FeedbackTone::Stop);
+        feedback.transcript_ready(&enabled, "hello");
+
+        assert_eq!(
+            played.recv_timeout(Duration::from_secs(1)),
+            Ok(FeedbackTone::Stop)
+        );
+        assert_eq!(notifier.calls.lock().unwrap().len(), 1);
+    }
+}
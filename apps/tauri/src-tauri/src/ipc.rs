@@ -1,18 +1,83 @@
-use crate::logging::{logger, LogEntry};
+use crate::logging::{emit_app_event, event_seq, logger, LogEntry};
 use crate::ptt::{
-    build_model_status_payload, model_id_from_name, register_standard_models, PttHotkeyPayload,
+    build_model_status_payload, delete_model_files, hotkey_restart_required, import_custom_model,
+    model_disk_usage, model_id_from_name, register_known_models, settings_transcribe_options,
+    transcribe_audio_file, verify_installed_model, FileTranscriptionPayload,
+    FileTranscriptionStage, LocalTranscriber, ModelDiskUsagePayload, ModelVerificationPayload,
+    PttHotkeyPayload, FILE_TRANSCRIPTION_EVENT, HOTKEY_CAPTURE_TIMEOUT,
 };
-use crate::state::AppState;
+use crate::state::{AppState, SettingsError};
 use shared_types::{
-    AppSettings, BackendEvent, BackendState, ModelInstallStatus, ModelStatusPayload, PttState,
-    SettingsUpdate,
+    format_bytes, AppSettings, BackendEvent, BackendState, HotkeyConfig, ModelInstallStatus,
+    ModelStatusPayload, PttLevel, PttState, SettingsUpdate, TranscriptEntry, TranscriptionStats,
+};
+use std::{
+    path::{Path, PathBuf},
+    sync::{atomic::Ordering, Arc},
+    thread,
+    time::{Duration, Instant},
+};
+use transcribe_engine::{
+    decode_audio_file, DownloadProgress, HttpDownloader, ModelError, ModelManager, Segment,
+    TranscriptionResult,
 };
-use std::thread;
-use tauri::Manager;
-use transcribe_engine::{HttpDownloader, ModelManager};
 
 pub const BACKEND_STATE_EVENT: &str = "backend-state";
 pub const MODEL_STATUS_EVENT: &str = "model-download-status";
+const MODEL_PROGRESS_INTERVAL: Duration = Duration::from_millis(250);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct DownloadSample {
+    downloaded_bytes: u64,
+    total_bytes: u64,
+    speed_bytes_per_sec: u64,
+    eta_seconds: u64,
+}
+
+struct DownloadProgressReporter {
+    started: Instant,
+    last_emit: Option<Instant>,
+}
+
+impl DownloadProgressReporter {
+    fn new(started: Instant) -> Self {
+        Self {
+            started,
+            last_emit: None,
+        }
+    }
+
+    fn observe(&mut self, progress: DownloadProgress, now: Instant) -> Option<DownloadSample> {
+        let total_bytes = progress.total_bytes.unwrap_or(0);
+        let finished = total_bytes > 0 && progress.downloaded_bytes >= total_bytes;
+        let due = self
+            .last_emit
+            .is_none_or(|last| now.duration_since(last) >= MODEL_PROGRESS_INTERVAL);
+        if !due && !finished {
+            return None;
+        }
+        self.last_emit = Some(now);
+
+        let elapsed = now.duration_since(self.started).as_secs_f64();
+        let speed_bytes_per_sec = if elapsed > 0.0 {
+            (progress.downloaded_bytes as f64 / elapsed) as u64
+        } else {
+            0
+        };
+        let remaining = total_bytes.saturating_sub(progress.downloaded_bytes);
+        let eta_seconds = if speed_bytes_per_sec > 0 {
+            remaining.div_ceil(speed_bytes_per_sec)
+        } else {
+            0
+        };
+        Some(DownloadSample {
+            downloaded_bytes: progress.downloaded_bytes,
+            total_bytes,
+            speed_bytes_per_sec,
+            eta_seconds,
+        })
+    }
+}
 
 #[tauri::command]
 pub fn ipc_get_state(state: tauri::State<AppState>) -> BackendState {
@@ -23,7 +88,6 @@ pub fn ipc_get_state(state: tauri::State<AppState>) -> BackendState {
 #[tauri::command]
 pub fn ipc_send_event(
     event: BackendEvent,
-    app: tauri::AppHandle,
     state: tauri::State<AppState>,
 ) -> Result<BackendState, String> {
     let next = {
@@ -31,7 +95,6 @@ pub fn ipc_send_event(
         orchestrator.apply_event(event.clone())?
     };
     log::info!("state transition: {:?} -> {:?}", event, next);
-    let _ = app.emit_all(BACKEND_STATE_EVENT, next.clone());
     Ok(next)
 }
 
@@ -45,7 +108,7 @@ pub fn ipc_get_settings(state: tauri::State<AppState>) -> AppSettings {
 pub fn ipc_update_settings(
     update: SettingsUpdate,
     state: tauri::State<AppState>,
-) -> Result<AppSettings, String> {
+) -> Result<AppSettings, SettingsError> {
     let mut orchestrator = state.lock_orchestrator();
     let next = orchestrator.update_settings(update)?;
     state.ptt_handle().update_settings(next.clone());
@@ -57,7 +120,7 @@ pub fn ipc_update_settings(
 pub fn ipc_set_settings(
     settings: AppSettings,
     state: tauri::State<AppState>,
-) -> Result<AppSettings, String> {
+) -> Result<AppSettings, SettingsError> {
     let mut orchestrator = state.lock_orchestrator();
     let next = orchestrator.set_settings(settings)?;
     state.ptt_handle().update_settings(next.clone());
@@ -70,6 +133,11 @@ pub fn ipc_get_logs() -> Vec<LogEntry> {
     logger().entries()
 }
 
+#[tauri::command]
+pub fn ipc_get_event_seq() -> u64 {
+    event_seq()
+}
+
 #[tauri::command]
 pub fn ipc_get_models(state: tauri::State<AppState>) -> ModelStatusPayload {
     let models = state.lock_models();
@@ -82,10 +150,21 @@ pub fn ipc_get_last_transcript(state: tauri::State<AppState>) -> Option<String>
     models.last_transcript()
 }
 
+#[tauri::command]
+pub fn ipc_get_transcript_history(state: tauri::State<AppState>) -> Vec<TranscriptEntry> {
+    let models = state.lock_models();
+    models.transcript_history()
+}
+
+#[tauri::command]
+pub fn ipc_get_transcription_stats(state: tauri::State<AppState>) -> Vec<TranscriptionStats> {
+    let models = state.lock_models();
+    models.transcription_stats()
+}
+
 #[tauri::command]
 pub fn ipc_model_select(
     model: String,
-    app: tauri::AppHandle,
     state: tauri::State<AppState>,
 ) -> Result<ModelStatusPayload, String> {
     let model_name = model.trim().to_string();
@@ -106,14 +185,19 @@ pub fn ipc_model_select(
     state
         .ptt_handle()
         .set_active_model(payload.active_model.clone());
-    let _ = app.emit_all(MODEL_STATUS_EVENT, payload.clone());
+    if let Err(err) = state.lock_orchestrator().update_settings(SettingsUpdate {
+        active_model: Some(model_name),
+        ..SettingsUpdate::default()
+    }) {
+        log::warn!("failed to persist active model: {err}");
+    }
+    emit_app_event(MODEL_STATUS_EVENT, &payload);
     Ok(payload)
 }
 
 #[tauri::command]
 pub fn ipc_model_download(
     model: String,
-    app: tauri::AppHandle,
     state: tauri::State<AppState>,
 ) -> Result<ModelStatusPayload, String> {
     let model_name = model.trim().to_string();
@@ -122,63 +206,311 @@ pub fn ipc_model_download(
     }
     let model_root = state.model_root();
     let models_handle = state.models.clone();
-    let payload = {
+    let (cancel, payload) = {
         let mut models = state.lock_models();
+        if models.overrides_snapshot().get(&model_name) == Some(&ModelInstallStatus::Downloading) {
+            return Ok(models.snapshot());
+        }
+        let Some(cancel) = models.begin_download(&model_name) else {
+            return Ok(models.snapshot());
+        };
         models.set_override(model_name.clone(), ModelInstallStatus::Downloading);
         let overrides = models.overrides_snapshot();
         let active = models.active_model();
         let payload = build_model_status_payload(&model_root, active.as_deref(), &overrides);
         let _ = models.set_models(payload.models.clone());
         let _ = models.set_active_model(payload.active_model.clone());
-        payload
+        (cancel, payload)
     };
-    let _ = app.emit_all(MODEL_STATUS_EVENT, payload.clone());
+    emit_app_event(MODEL_STATUS_EVENT, &payload);
 
-    let app_handle = app.clone();
     thread::spawn(move || {
-        let result = (|| {
-            let mut manager = ModelManager::new(model_root.clone());
-            register_standard_models(&mut manager);
+        let result = {
+            let mut manager =
+                ModelManager::new(model_root.clone()).with_cancellation(Arc::clone(&cancel));
+            register_known_models(&mut manager, &model_root);
             let model_id = model_id_from_name(Some(&model_name));
-            if matches!(model_id, transcribe_engine::ModelId::Custom(_)) {
-                return Err("custom model download not supported".to_string());
-            }
-            let downloader = HttpDownloader;
+            let downloader = HttpDownloader::from_env();
+            let mut reporter = DownloadProgressReporter::new(Instant::now());
             manager
-                .ensure_model_cached(&model_id, &downloader)
+                .ensure_model_cached_with_progress(&model_id, &downloader, &mut |progress| {
+                    let Some(sample) = reporter.observe(progress, Instant::now()) else {
+                        return;
+                    };
+                    let payload = models_handle
+                        .lock()
+                        .unwrap_or_else(|poisoned| poisoned.into_inner())
+                        .apply_progress(
+                            &model_name,
+                            sample.downloaded_bytes,
+                            sample.total_bytes,
+                            sample.speed_bytes_per_sec,
+                            sample.eta_seconds,
+                        );
+                    if let Some(payload) = payload {
+                        emit_app_event(MODEL_STATUS_EVENT, &payload);
+                    }
+                })
                 .map(|_| ())
-                .map_err(|err| err.to_string())
-        })();
+                .map_err(|err| download_error_message(&err))
+        };
+        let cancelled = cancel.load(Ordering::SeqCst);
 
         let payload = {
             let mut models = models_handle
                 .lock()
                 .unwrap_or_else(|poisoned| poisoned.into_inner());
+            models.finish_download(&model_name);
             match result {
                 Ok(()) => models.clear_override(&model_name),
+                Err(_) if cancelled => {
+                    models.set_override(model_name.clone(), ModelInstallStatus::Pending)
+                }
                 Err(_) => models.set_override(model_name.clone(), ModelInstallStatus::Failed),
             }
             let overrides = models.overrides_snapshot();
             let active = models.active_model();
+            let rebuilt = build_model_status_payload(&model_root, active.as_deref(), &overrides);
+            match rebuilt
+                .models
+                .into_iter()
+                .find(|item| item.id == model_name)
+            {
+                Some(item) => models.merge_model(item),
+                None => models.snapshot(),
+            }
+        };
+
+        match &result {
+            Err(_) if cancelled => log::info!("model download cancelled: {model_name}"),
+            Err(err) => log::warn!("model download failed: {err}"),
+            Ok(()) => {}
+        }
+        emit_app_event(MODEL_STATUS_EVENT, &payload);
+    });
+
+    Ok(payload)
+}
+
+fn download_error_message(err: &ModelError) -> String {
+    match err {
+        ModelError::InsufficientDiskSpace {
+            required,
+            available,
+        } => format!(
+            "not enough disk space for this model: {} needed, {} free",
+            format_bytes(*required),
+            format_bytes(*available)
+        ),
+        other => other.to_string(),
+    }
+}
+
+#[tauri::command]
+pub fn ipc_model_download_cancel(
+    model: String,
+    state: tauri::State<AppState>,
+) -> Result<(), String> {
+    let model_name = model.trim().to_string();
+    if !state.lock_models().cancel_download(&model_name) {
+        return Err(format!("no download in progress for model '{model_name}'"));
+    }
+    log::info!("cancelling model download: {model_name}");
+    Ok(())
+}
+
+#[tauri::command]
+pub fn ipc_model_delete(
+    model: String,
+    state: tauri::State<AppState>,
+) -> Result<ModelStatusPayload, String> {
+    let model_name = model.trim().to_string();
+    let model_root = state.model_root();
+    let payload = {
+        let mut models = state.lock_models();
+        let overrides = models.overrides_snapshot();
+        if overrides
+            .get(&model_name)
+            .is_some_and(ModelInstallStatus::is_pending)
+        {
+            return Err(format!("model '{model_name}' is still downloading"));
+        }
+        let active = models.active_model();
+        let freed = delete_model_files(&model_root, &model_name, active.as_deref())?;
+        log::info!("deleted model {model_name} ({freed} bytes freed)");
+        models.clear_override(&model_name);
+        let overrides = models.overrides_snapshot();
+        let payload = build_model_status_payload(&model_root, active.as_deref(), &overrides);
+        let _ = models.set_models(payload.models.clone());
+        payload
+    };
+    emit_app_event(MODEL_STATUS_EVENT, &payload);
+    Ok(payload)
+}
+
+#[tauri::command(async)]
+pub fn ipc_model_verify(
+    model: String,
+    state: tauri::State<AppState>,
+) -> Result<ModelVerificationPayload, String> {
+    let model_name = model.trim().to_string();
+    let model_root = state.model_root();
+    let report = verify_installed_model(&model_root, &model_name)?;
+    if !report.valid {
+        log::warn!("model {model_name} failed verification");
+        let payload = {
+            let mut models = state.lock_models();
+            models.set_override(model_name.clone(), ModelInstallStatus::Failed);
+            let overrides = models.overrides_snapshot();
+            let active = models.active_model();
             let payload = build_model_status_payload(&model_root, active.as_deref(), &overrides);
             let _ = models.set_models(payload.models.clone());
-            let _ = models.set_active_model(payload.active_model.clone());
             payload
         };
+        emit_app_event(MODEL_STATUS_EVENT, &payload);
+    }
+    Ok(report)
+}
 
-        if let Err(err) = &result {
-            log::warn!("model download failed: {err}");
-        }
-        let _ = app_handle.emit_all(MODEL_STATUS_EVENT, payload);
+#[tauri::command(async)]
+pub fn ipc_import_custom_model(
+    path: String,
+    name: String,
+    state: tauri::State<AppState>,
+) -> Result<ModelStatusPayload, String> {
+    let model_name = name.trim().to_string();
+    let model_root = state.model_root();
+    let installed = import_custom_model(&model_root, Path::new(path.trim()), &model_name)?;
+    log::info!("imported model {model_name} to {}", installed.display());
+    let payload = {
+        let mut models = state.lock_models();
+        models.clear_override(&model_name);
+        let overrides = models.overrides_snapshot();
+        let active = models.active_model();
+        let payload = build_model_status_payload(&model_root, active.as_deref(), &overrides);
+        let _ = models.set_models(payload.models.clone());
+        payload
+    };
+    emit_app_event(MODEL_STATUS_EVENT, &payload);
+    Ok(payload)
+}
+
+#[tauri::command]
+pub fn ipc_transcribe_file(
+    path: String,
+    model: Option<String>,
+    force: Option<bool>,
+    state: tauri::State<AppState>,
+) -> Result<(), String> {
+    let path = PathBuf::from(path.trim());
+    if !path.is_file() {
+        return Err(format!("audio file not found: {}", path.display()));
+    }
+    let model_name = model
+        .map(|name| name.trim().to_string())
+        .filter(|name| !name.is_empty())
+        .or_else(|| state.lock_models().active_model());
+    let model_id = model_id_from_name(model_name.as_deref());
+    let options = settings_transcribe_options(&state.lock_orchestrator().settings(), false);
+    let model_root = state.model_root();
+    let models_handle = state.models.clone();
+    let force = force.unwrap_or(false);
+
+    thread::spawn(move || {
+        emit_app_event(
+            FILE_TRANSCRIPTION_EVENT,
+            &FileTranscriptionPayload::new(&path, FileTranscriptionStage::Decoding),
+        );
+        let result = decode_audio_file(&path, force)
+            .map_err(|err| err.to_string())
+            .and_then(|audio| {
+                emit_app_event(
+                    FILE_TRANSCRIPTION_EVENT,
+                    &FileTranscriptionPayload::new(&path, FileTranscriptionStage::Transcribing),
+                );
+                let model = model_id.display_name();
+                let transcriber = LocalTranscriber::new(model_root, model_id);
+                transcribe_audio_file(&transcriber, &audio, &options, Some(model))
+            });
+        let payload = match result {
+            Ok(entry) => {
+                let entry = models_handle
+                    .lock()
+                    .unwrap_or_else(|poisoned| poisoned.into_inner())
+                    .record_transcript(entry);
+                log::info!("transcribed {} ({} chars)", path.display(), entry.chars);
+                FileTranscriptionPayload {
+                    entry: Some(entry),
+                    ..FileTranscriptionPayload::new(&path, FileTranscriptionStage::Completed)
+                }
+            }
+            Err(err) => {
+                log::warn!("file transcription failed for {}: {err}", path.display());
+                FileTranscriptionPayload {
+                    error: Some(err),
+                    ..FileTranscriptionPayload::new(&path, FileTranscriptionStage::Failed)
+                }
+            }
+        };
+        emit_app_event(FILE_TRANSCRIPTION_EVENT, &payload);
     });
+    Ok(())
+}
 
-    Ok(payload)
+#[tauri::command]
+pub fn ipc_export_transcript(
+    id: u64,
+    format: String,
+    path: String,
+    state: tauri::State<AppState>,
+) -> Result<(), String> {
+    let entry = state
+        .lock_models()
+        .transcript_history()
+        .into_iter()
+        .find(|entry| entry.id == id)
+        .ok_or_else(|| format!("transcript {id} not found"))?;
+    let contents = export_transcript(&entry, &format)?;
+    std::fs::write(path.trim(), contents)
+        .map_err(|err| format!("failed to write {}: {err}", path.trim()))
+}
+
+fn export_transcript(entry: &TranscriptEntry, format: &str) -> Result<String, String> {
+    let segments = if entry.segments.is_empty() {
+        vec![Segment {
+            start_ms: 0,
+            end_ms: entry.duration_ms,
+            text: entry.text.clone(),
+        }]
+    } else {
+        entry
+            .segments
+            .iter()
+            .map(|segment| Segment {
+                start_ms: segment.start_ms,
+                end_ms: segment.end_ms,
+                text: segment.text.clone(),
+            })
+            .collect()
+    };
+    let result = TranscriptionResult::from_segments(segments);
+    match format.trim().to_ascii_lowercase().as_str() {
+        "srt" => Ok(result.to_srt()),
+        "vtt" => Ok(result.to_vtt()),
+        other => Err(format!("unsupported export format: {other}")),
+    }
+}
+
+#[tauri::command(async)]
+pub fn ipc_get_model_disk_usage(
+    state: tauri::State<AppState>,
+) -> Result<ModelDiskUsagePayload, String> {
+    model_disk_usage(&state.model_root())
 }
 
 #[tauri::command]
 pub fn ipc_set_models(
     payload: ModelStatusPayload,
-    app: tauri::AppHandle,
     state: tauri::State<AppState>,
 ) -> Result<ModelStatusPayload, String> {
     let next = {
@@ -188,7 +520,7 @@ pub fn ipc_set_models(
     state
         .ptt_handle()
         .set_active_model(next.active_model.clone());
-    let _ = app.emit_all(MODEL_STATUS_EVENT, next.clone());
+    emit_app_event(MODEL_STATUS_EVENT, &next);
     Ok(next)
 }
 
@@ -228,12 +560,35 @@ pub fn ipc_ptt_toggle_recording(state: tauri::State<AppState>) -> Result<PttStat
     result
 }
 
+#[tauri::command]
+pub fn ipc_ptt_cancel(state: tauri::State<AppState>) -> Result<PttState, String> {
+    let result = state.ptt_handle().cancel();
+    if let Ok(next) = &result {
+        log::info!("ptt cancel -> {next:?}");
+    } else if let Err(err) = &result {
+        log::warn!("ptt cancel failed: {err}");
+    }
+    result
+}
+
 #[tauri::command]
 pub fn ipc_ptt_set_hotkey(
     payload: PttHotkeyPayload,
+    replace: Option<bool>,
     state: tauri::State<AppState>,
 ) -> Result<PttHotkeyPayload, String> {
-    state.ptt_handle().set_hotkey(payload)
+    let payload = state
+        .ptt_handle()
+        .set_hotkey(payload, replace.unwrap_or(false))?;
+    let next = state
+        .lock_orchestrator()
+        .update_settings(SettingsUpdate {
+            hotkey: Some(HotkeyConfig::from(&payload)),
+            ..SettingsUpdate::default()
+        })
+        .map_err(|err| format!("hotkey applied but not saved: {err}"))?;
+    state.ptt_handle().update_settings(next);
+    Ok(payload)
 }
 
 #[tauri::command]
@@ -241,6 +596,31 @@ pub fn ipc_ptt_get_state(state: tauri::State<AppState>) -> PttState {
     state.ptt_state()
 }
 
+#[tauri::command(async)]
+pub fn ipc_ptt_capture_hotkey(
+    timeout_ms: Option<u64>,
+    state: tauri::State<AppState>,
+) -> Result<PttHotkeyPayload, String> {
+    let timeout = timeout_ms.map_or(HOTKEY_CAPTURE_TIMEOUT, Duration::from_millis);
+    state.ptt_handle().capture_hotkey(timeout)
+}
+
+#[tauri::command]
+pub fn ipc_hotkey_set_enabled(enabled: bool, state: tauri::State<AppState>) -> Result<(), String> {
+    state.ptt_handle().set_hotkey_enabled(enabled)
+}
+
+#[tauri::command]
+pub fn ipc_hotkey_restart_required(state: tauri::State<AppState>) -> bool {
+    let orchestrator = state.lock_orchestrator();
+    hotkey_restart_required(&orchestrator.settings())
+}
+
+#[tauri::command]
+pub fn ipc_get_level_history(state: tauri::State<AppState>) -> Result<Vec<PttLevel>, String> {
+    state.ptt_handle().level_history()
+}
+
 #[tauri::command]
 pub fn ipc_hello() -> String {
     println!("hello from UI");
@@ -257,4 +637,77 @@ mod tests {
     fn ipc_hello_returns_message() {
         assert_eq!(ipc_hello(), "hello from backend");
     }
+
+    #[test]
+    fn disk_space_errors_are_readable() {
+        let message = download_error_message(&ModelError::InsufficientDiskSpace {
+            required: 3 * 1024 * 1024 * 1024,
+            available: 512 * 1024 * 1024,
+        });
+        assert_eq!(
+            message,
+            "not enough disk space for this model: 3.0 GiB needed, 512.0 MiB free"
+        );
+    }
+
+    #[test]
+    fn download_progress_is_throttled_and_monotonic() {
+        let start = Instant::now();
+        let mut reporter = DownloadProgressReporter::new(start);
+        let progress = |downloaded_bytes| DownloadProgress {
+            downloaded_bytes,
+            total_bytes: Some(1_000),
+        };
+
+        let samples = [(100, 100), (200, 200), (400, 400), (600, 500), (1_000, 550)]
+            .into_iter()
+            .filter_map(|(bytes, at_ms)| {
+                reporter.observe(progress(bytes), start + Duration::from_millis(at_ms))
+            })
+            .collect::<Vec<_>>();
+
+        let downloaded = samples
+            .iter()
+            .map(|sample| sample.downloaded_bytes)
+            .collect::<Vec<_>>();
+        assert_eq!(downloaded, vec![100, 400, 1_000]);
+        assert_eq!(samples[0].speed_bytes_per_sec, 1_000);
+        assert_eq!(samples[0].eta_seconds, 1);
+        assert_eq!(samples[2].eta_seconds, 0);
+        assert!(samples
+            .windows(2)
+            .all(|pair| pair[0].downloaded_bytes <= pair[1].downloaded_bytes));
+    }
+
+    #[test]
+    fn transcripts_export_segments_as_subtitles() {
+        let entry = TranscriptEntry::new("hi there", 0, 2_000, None, Default::default())
+            .with_segments(vec![
+                shared_types::TranscriptSegment {
+                    start_ms: 0,
+                    end_ms: 900,
+                    text: "hi".to_string(),
+                },
+                shared_types::TranscriptSegment {
+                    start_ms: 900,
+                    end_ms: 2_000,
+                    text: "there".to_string(),
+                },
+            ]);
+
+        let srt = export_transcript(&entry, "SRT").expect("export srt");
+        assert!(srt.starts_with("1\n00:00:00,000 --> 00:00:00,900\nhi\n\n2\n"));
+        let vtt = export_transcript(&entry, "vtt").expect("export vtt");
+        assert!(vtt.starts_with("WEBVTT\n\n00:00:00.000 --> 00:00:00.900\nhi\n"));
+        assert!(export_transcript(&entry, "docx").is_err());
+    }
+
+    #[test]
+    fn transcripts_without_segments_export_one_cue() {
+        let entry = TranscriptEntry::new("whole note", 0, 61_500, None, Default::default());
+        assert_eq!(
+            export_transcript(&entry, "srt").expect("export srt"),
+            "1\n00:00:00,000 --> 00:01:01,500\nwhole note\n"
+        );
+    }
 }
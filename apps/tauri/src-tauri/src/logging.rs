@@ -1,4 +1,5 @@
 use serde::{Deserialize, Serialize};
+use shared_types::EventSequencer;
 use std::{
     collections::VecDeque,
     sync::{Mutex, Once, OnceLock, RwLock},
@@ -60,6 +61,7 @@ impl LogStore {
 pub struct BridgeLogger {
     store: Mutex<LogStore>,
     handle: RwLock<Option<AppHandle>>,
+    sequencer: EventSequencer,
 }
 
 impl BridgeLogger {
@@ -67,6 +69,7 @@ impl BridgeLogger {
         Self {
             store: Mutex::new(LogStore::new(capacity)),
             handle: RwLock::new(None),
+            sequencer: EventSequencer::new(),
         }
     }
 
@@ -80,6 +83,18 @@ impl BridgeLogger {
         guard.entries()
     }
 
+    pub fn event_seq(&self) -> u64 {
+        self.sequencer.current()
+    }
+
+    pub fn app_identifier(&self) -> Option<String> {
+        self.handle
+            .read()
+            .expect("log handle lock poisoned")
+            .as_ref()
+            .map(|handle| handle.config().tauri.bundle.identifier.clone())
+    }
+
     pub fn emit_event<T: Serialize>(&self, event: &str, payload: &T) {
         if let Some(handle) = self
             .handle
@@ -87,7 +102,7 @@ impl BridgeLogger {
             .expect("log handle lock poisoned")
             .as_ref()
         {
-            let _ = handle.emit_all(event, payload);
+            let _ = handle.emit_all(event, self.sequencer.wrap(payload));
         }
     }
 
@@ -147,6 +162,14 @@ pub fn emit_app_event<T: Serialize>(event: &str, payload: &T) {
     }
 }
 
+pub fn app_identifier() -> Option<String> {
+    LOGGER.get().and_then(|logger| logger.app_identifier())
+}
+
+pub fn event_seq() -> u64 {
+    LOGGER.get().map_or(0, |logger| logger.event_seq())
+}
+
 pub fn attach_app_handle(handle: AppHandle) {
     logger().set_app_handle(handle);
 }
@@ -1,27 +1,36 @@
+mod active_window;
 mod control_server;
+mod feedback;
 mod ipc;
 mod logging;
+mod output;
+mod postprocess;
 mod ptt;
+mod recordings;
 mod state;
 mod ui_server;
 mod whisper_cli;
 
 use ipc::{
-    ipc_get_last_transcript, ipc_get_logs, ipc_get_models, ipc_get_settings, ipc_get_state,
-    ipc_hello, ipc_model_download, ipc_model_select, ipc_ptt_get_state, ipc_ptt_set_hotkey,
-    ipc_ptt_start, ipc_ptt_stop, ipc_ptt_toggle_recording, ipc_send_event, ipc_set_models,
-    ipc_set_settings, ipc_update_settings, BACKEND_STATE_EVENT, MODEL_STATUS_EVENT,
+    ipc_export_transcript, ipc_get_event_seq, ipc_get_last_transcript, ipc_get_level_history,
+    ipc_get_logs, ipc_get_model_disk_usage, ipc_get_models, ipc_get_settings, ipc_get_state,
+    ipc_get_transcript_history, ipc_get_transcription_stats, ipc_hello,
+    ipc_hotkey_restart_required, ipc_hotkey_set_enabled, ipc_import_custom_model, ipc_model_delete,
+    ipc_model_download, ipc_model_download_cancel, ipc_model_select, ipc_model_verify,
+    ipc_ptt_cancel, ipc_ptt_capture_hotkey, ipc_ptt_get_state, ipc_ptt_set_hotkey, ipc_ptt_start,
+    ipc_ptt_stop, ipc_ptt_toggle_recording, ipc_send_event, ipc_set_models, ipc_set_settings,
+    ipc_transcribe_file, ipc_update_settings, BACKEND_STATE_EVENT, MODEL_STATUS_EVENT,
 };
-use logging::{attach_app_handle, init_logging};
-use ptt::PTT_STATE_EVENT;
+use logging::{attach_app_handle, emit_app_event, init_logging};
+use ptt::{PTT_ERROR_EVENT, PTT_STATE_EVENT};
 use signal_hook::consts::signal::SIGUSR1;
 use signal_hook::iterator::Signals;
 use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
 use std::thread;
 use std::time::{SystemTime, UNIX_EPOCH};
 use tauri::{
-    CustomMenuItem, Icon, Manager, SystemTray, SystemTrayEvent, SystemTrayMenu, WindowBuilder,
-    WindowEvent, WindowUrl,
+    CustomMenuItem, Icon, Manager, RunEvent, SystemTray, SystemTrayEvent, SystemTrayMenu,
+    WindowBuilder, WindowEvent, WindowUrl,
 };
 
 fn main() {
@@ -52,6 +61,8 @@ fn main() {
                 .unwrap_or_else(|| std::env::temp_dir())
                 .join("models");
             log::info!("model root: {}", model_root.display());
+            ptt::log_manifest_models(&model_root);
+            ptt::cleanup_stale_downloads(&model_root);
             if let Some(app_data_dir) = app.path_resolver().app_data_dir() {
                 whisper_cli::ensure_whisper_cli(app_data_dir);
             } else {
@@ -83,12 +94,15 @@ fn main() {
             } else {
                 log::info!("ptt auto-start disabled");
             }
-            let backend_state = app_state.lock_orchestrator().current_state();
+            let backend_state = app_state.lock_orchestrator().current_timed_state();
             let models = app_state.lock_models().snapshot();
             let ptt_state = app_state.ptt_state();
-            let _ = app.emit_all(BACKEND_STATE_EVENT, backend_state);
-            let _ = app.emit_all(MODEL_STATUS_EVENT, models);
-            let _ = app.emit_all(PTT_STATE_EVENT, ptt_state);
+            emit_app_event(BACKEND_STATE_EVENT, &backend_state);
+            emit_app_event(MODEL_STATUS_EVENT, &models);
+            emit_app_event(PTT_STATE_EVENT, &ptt_state);
+            if let Some(warning) = app_state.startup_warning() {
+                emit_app_event(PTT_ERROR_EVENT, &warning);
+            }
             log::info!("tauri backend initialized");
             Ok(())
         })
@@ -126,20 +140,43 @@ fn main() {
             ipc_update_settings,
             ipc_set_settings,
             ipc_get_logs,
+            ipc_get_event_seq,
             ipc_get_models,
             ipc_get_last_transcript,
+            ipc_get_transcript_history,
+            ipc_get_transcription_stats,
+            ipc_export_transcript,
             ipc_set_models,
             ipc_model_select,
             ipc_model_download,
+            ipc_model_download_cancel,
+            ipc_model_delete,
+            ipc_model_verify,
+            ipc_get_model_disk_usage,
+            ipc_import_custom_model,
+            ipc_transcribe_file,
             ipc_ptt_start,
             ipc_ptt_stop,
             ipc_ptt_toggle_recording,
+            ipc_ptt_cancel,
             ipc_ptt_set_hotkey,
+            ipc_ptt_capture_hotkey,
+            ipc_hotkey_set_enabled,
+            ipc_hotkey_restart_required,
             ipc_ptt_get_state,
+            ipc_get_level_history,
             ipc_hello
         ])
-        .run(context)
-        .expect("error while running tauri application");
+        .build(context)
+        .expect("error while building tauri application")
+        .run(|app, event| {
+            if let RunEvent::Exit = event {
+                let app_state = app.state::<state::AppState>();
+                if let Err(err) = app_state.ptt_handle().stop() {
+                    log::warn!("failed to stop ptt on exit: {err}");
+                }
+            }
+        });
 }
 
 fn spawn_indicator_window(app: &tauri::App, ptt_handle: ptt::PttHandle) {
@@ -172,7 +209,7 @@ fn spawn_indicator_window(app: &tauri::App, ptt_handle: ptt::PttHandle) {
             let state = ptt_handle.state();
             let is_capturing = matches!(
                 state,
-                shared_types::PttState::Capturing | shared_types::PttState::Processing
+                shared_types::PttState::Capturing | shared_types::PttState::Processing { .. }
             );
             if is_capturing != last_visible {
                 last_visible = is_capturing;
@@ -214,6 +251,8 @@ fn run_headless() {
     let settings_path = state::default_settings_path(Some(config_dir));
     let model_root = app_data_dir.join("models");
     log::info!("headless model root: {}", model_root.display());
+    ptt::log_manifest_models(&model_root);
+    ptt::cleanup_stale_downloads(&model_root);
 
     whisper_cli::ensure_whisper_cli(app_data_dir.clone());
     write_pid_file(&app_data_dir);
@@ -0,0 +1,469 @@
+use crate::active_window::{active_window_class, paste_shortcut_for};
+use inject::{
+    wl_copy, ArboardClipboard, Clipboard, ClipboardError, ClipboardRestore, CommandRunner,
+    CommandTyper, DisplayServer, InjectOutcome, Injector, RestorePolicy, SystemCommandRunner,
+    Typer,
+};
+use log::{debug, info};
+use shared_types::{AppSettings, OutputMode, PasteProfile, PasteShortcut};
+use std::time::Duration;
+
+const PASTE_RESTORE_DELAY: Duration = Duration::from_millis(150);
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum OutputStrategy {
+    UiOnly,
+    Clipboard,
+    Type,
+    Paste {
+        restore: RestorePolicy,
+        profiles: Vec<PasteProfile>,
+    },
+}
+
+impl OutputStrategy {
+    pub fn from_settings(settings: &AppSettings) -> Self {
+        match settings.output_mode {
+            OutputMode::UiOnly => Self::UiOnly,
+            OutputMode::Clipboard => Self::Clipboard,
+            OutputMode::DirectWrite => Self::Type,
+            OutputMode::Paste => Self::Paste {
+                restore: if settings.restore_clipboard {
+                    RestorePolicy::OnlyIfUnchanged
+                } else {
+                    RestorePolicy::Never
+                },
+                profiles: settings.paste_profiles.clone(),
+            },
+        }
+    }
+}
+
+fn resolve_paste_shortcut<R: CommandRunner>(
+    profiles: &[PasteProfile],
+    runner: &mut R,
+    display: DisplayServer,
+) -> PasteShortcut {
+    if profiles.is_empty() || !cfg!(target_os = "linux") {
+        return PasteShortcut::default();
+    }
+    let window_class = active_window_class(runner, display);
+    let shortcut = paste_shortcut_for(profiles, window_class.as_deref());
+    info!(
+        "pasting with {shortcut:?} into window class {}",
+        window_class.as_deref().unwrap_or("<unknown>")
+    );
+    shortcut
+}
+
+struct SystemClipboard {
+    inner: Result<ArboardClipboard, ClipboardError>,
+    display: DisplayServer,
+}
+
+impl SystemClipboard {
+    fn open(paste_shortcut: PasteShortcut) -> Self {
+        Self {
+            inner: ArboardClipboard::new()
+                .map(|clipboard| clipboard.with_paste_shortcut(paste_shortcut)),
+            display: DisplayServer::detect(),
+        }
+    }
+
+    fn inner(&mut self) -> Result<&mut ArboardClipboard, ClipboardError> {
+        self.inner.as_mut().map_err(|err| err.clone())
+    }
+}
+
+fn set_with_wl_copy_fallback<R: CommandRunner>(
+    result: Result<(), ClipboardError>,
+    display: DisplayServer,
+    runner: &mut R,
+    text: &str,
+) -> Result<(), ClipboardError> {
+    match result {
+        Err(err) if display == DisplayServer::Wayland => {
+            wl_copy(runner, text).map_err(|fallback| {
+                ClipboardError::new(format!("{}; {}", err.message(), fallback.message()))
+            })?;
+            info!("clipboard set through wl-copy");
+            Ok(())
+        }
+        result => result,
+    }
+}
+
+impl Clipboard for SystemClipboard {
+    fn get_text(&mut self) -> Result<Option<String>, ClipboardError> {
+        self.inner()?.get_text()
+    }
+
+    fn set_text(&mut self, text: &str) -> Result<(), ClipboardError> {
+        let result = self.inner().and_then(|clipboard| clipboard.set_text(text));
+        set_with_wl_copy_fallback(result, self.display, &mut SystemCommandRunner, text)
+    }
+
+    fn clear(&mut self) -> Result<(), ClipboardError> {
+        self.inner()?.clear()
+    }
+
+    fn paste(&mut self) -> Result<(), ClipboardError> {
+        self.inner()?.paste()
+    }
+}
+
+pub fn deliver_output(strategy: &OutputStrategy, text: &str) -> Result<(), String> {
+    if *strategy == OutputStrategy::UiOnly || text.is_empty() {
+        return Ok(());
+    }
+    let paste_shortcut = match strategy {
+        OutputStrategy::Paste { profiles, .. } => {
+            resolve_paste_shortcut(profiles, &mut SystemCommandRunner, DisplayServer::detect())
+        }
+        _ => PasteShortcut::default(),
+    };
+    inject_output(
+        strategy,
+        SystemClipboard::open(paste_shortcut),
+        CommandTyper::new(),
+        text,
+    )
+}
+
+fn inject_output<C: Clipboard, T: Typer>(
+    strategy: &OutputStrategy,
+    mut clipboard: C,
+    mut typer: T,
+    text: &str,
+) -> Result<(), String> {
+    match strategy {
+        OutputStrategy::UiOnly => Ok(()),
+        OutputStrategy::Clipboard => clipboard
+            .set_text(text)
+            .map_err(|err| err.message().to_string()),
+        OutputStrategy::Type => match typer.type_text(text) {
+            Ok(()) => {
+                info!("direct write succeeded");
+                Ok(())
+            }
+            Err(err) => {
+                let _ = clipboard.set_text(text);
+                Err(format!(
+                    "direct write failed; copied to clipboard: {}",
+                    err.message()
+                ))
+            }
+        },
+        OutputStrategy::Paste { restore, .. } => {
+            let result = Injector::builder(clipboard, typer)
+                .restore_policy(*restore)
+                .restore_delay(PASTE_RESTORE_DELAY)
+                .build()
+                .inject_text(text)
+                .map_err(|err| err.to_string())?;
+            debug!(
+                "paste injection: set {:?} ({} attempts), paste {:?}, typing {:?}",
+                result.set_duration, result.attempts, result.paste_duration, result.typing_duration
+            );
+            if result.outcome == InjectOutcome::TypedFallback {
+                info!("paste failed; typed the transcript instead");
+            }
+            match result.restore {
+                ClipboardRestore::Failed(err) => Err(format!(
+                    "transcript pasted but the previous clipboard could not be restored: {}",
+                    err.message()
+                )),
+                ClipboardRestore::SkippedExternalChange => {
+                    info!("clipboard changed during paste; leaving it untouched");
+                    Ok(())
+                }
+                _ => Ok(()),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use inject::{CommandOutput, TypingError};
+    use shared_types::default_paste_profiles;
+    use std::io;
+    use std::sync::{Arc, Mutex};
+
+    #[derive(Default)]
+    struct ClipboardState {
+        text: Option<String>,
+        sets: Vec<String>,
+        pastes: usize,
+        fail_paste: bool,
+        fail_set_after: Option<usize>,
+    }
+
+    #[derive(Clone, Default)]
+    struct MockClipboard {
+        state: Arc<Mutex<ClipboardState>>,
+    }
+
+    impl MockClipboard {
+        fn holding(text: &str) -> Self {
+            let clipboard = Self::default();
+            clipboard.state.lock().unwrap().text = Some(text.to_string());
+            clipboard
+        }
+
+        fn text(&self) -> Option<String> {
+            self.state.lock().unwrap().text.clone()
+        }
+
+        fn pastes(&self) -> usize {
+            self.state.lock().unwrap().pastes
+        }
+    }
+
+    impl Clipboard for MockClipboard {
+        fn get_text(&mut self) -> Result<Option<String>, ClipboardError> {
+            Ok(self.text())
+        }
+
+        fn set_text(&mut self, text: &str) -> Result<(), ClipboardError> {
+            let mut state = self.state.lock().unwrap();
+            if state.fail_set_after == Some(state.sets.len()) {
+                return Err(ClipboardError::new("clipboard owner went away"));
+            }
+            state.sets.push(text.to_string());
+            state.text = Some(text.to_string());
+            Ok(())
+        }
+
+        fn clear(&mut self) -> Result<(), ClipboardError> {
+            self.state.lock().unwrap().text = None;
+            Ok(())
+        }
+
+        fn paste(&mut self) -> Result<(), ClipboardError> {
+            let mut state = self.state.lock().unwrap();
+            if state.fail_paste {
+                return Err(ClipboardError::new("missing paste helper"));
+            }
+            state.pastes += 1;
+            Ok(())
+        }
+    }
+
+    #[derive(Clone, Default)]
+    struct MockTyper {
+        typed: Arc<Mutex<Vec<String>>>,
+        fail: bool,
+    }
+
+    impl Typer for MockTyper {
+        fn type_text(&mut self, text: &str) -> Result<(), TypingError> {
+            if self.fail {
+                return Err(TypingError::new("missing typing helper"));
+            }
+            self.typed.lock().unwrap().push(text.to_string());
+            Ok(())
+        }
+    }
+
+    #[derive(Default)]
+    struct MockRunner {
+        copied: Vec<String>,
+        missing: bool,
+    }
+
+    impl CommandRunner for MockRunner {
+        fn run(
+            &mut self,
+            program: &str,
+            _args: &[String],
+            stdin: Option<&str>,
+        ) -> io::Result<CommandOutput> {
+            assert_eq!(program, "wl-copy");
+            if self.missing {
+                return Err(io::Error::from(io::ErrorKind::NotFound));
+            }
+            self.copied.push(stdin.unwrap_or_default().to_string());
+            Ok(CommandOutput {
+                success: true,
+                status: "exit status: 0".to_string(),
+                stdout: String::new(),
+                stderr: String::new(),
+            })
+        }
+    }
+
+    fn paste(restore: RestorePolicy) -> OutputStrategy {
+        OutputStrategy::Paste {
+            restore,
+            profiles: default_paste_profiles(),
+        }
+    }
+
+    #[test]
+    fn output_modes_map_to_strategies() {
+        let settings = |output_mode, restore_clipboard| AppSettings {
+            output_mode,
+            restore_clipboard,
+            ..AppSettings::default()
+        };
+        assert_eq!(
+            OutputStrategy::from_settings(&settings(OutputMode::UiOnly, true)),
+            OutputStrategy::UiOnly
+        );
+        assert_eq!(
+            OutputStrategy::from_settings(&settings(OutputMode::Clipboard, true)),
+            OutputStrategy::Clipboard
+        );
+        assert_eq!(
+            OutputStrategy::from_settings(&settings(OutputMode::DirectWrite, true)),
+            OutputStrategy::Type
+        );
+        assert_eq!(
+            OutputStrategy::from_settings(&settings(OutputMode::Paste, true)),
+            paste(RestorePolicy::OnlyIfUnchanged)
+        );
+        assert_eq!(
+            OutputStrategy::from_settings(&settings(OutputMode::Paste, false)),
+            paste(RestorePolicy::Never)
+        );
+    }
+
+    #[test]
+    fn clipboard_mode_sets_without_pasting_or_restoring() {
+        let clipboard = MockClipboard::holding("previous");
+
+        inject_output(
+            &OutputStrategy::Clipboard,
+            clipboard.clone(),
+            MockTyper::default(),
+            "hello",
+        )
+        .unwrap();
+
+        assert_eq!(clipboard.text().as_deref(), Some("hello"));
+        assert_eq!(clipboard.pastes(), 0);
+    }
+
+    #[test]
+    fn paste_mode_restores_previous_clipboard() {
+        let clipboard = MockClipboard::holding("previous");
+
+        inject_output(
+            &paste(RestorePolicy::OnlyIfUnchanged),
+            clipboard.clone(),
+            MockTyper::default(),
+            "hello",
+        )
+        .unwrap();
+
+        assert_eq!(clipboard.pastes(), 1);
+        assert_eq!(clipboard.text().as_deref(), Some("previous"));
+        assert_eq!(
+            clipboard.state.lock().unwrap().sets,
+            vec!["hello".to_string(), "previous".to_string()]
+        );
+    }
+
+    #[test]
+    fn paste_mode_can_leave_transcript_on_clipboard() {
+        let clipboard = MockClipboard::holding("previous");
+
+        inject_output(
+            &paste(RestorePolicy::Never),
+            clipboard.clone(),
+            MockTyper::default(),
+            "hello",
+        )
+        .unwrap();
+
+        assert_eq!(clipboard.pastes(), 1);
+        assert_eq!(clipboard.text().as_deref(), Some("hello"));
+    }
+
+    #[test]
+    fn failed_paste_types_transcript_and_restores() {
+        let clipboard = MockClipboard::holding("previous");
+        clipboard.state.lock().unwrap().fail_paste = true;
+        let typer = MockTyper::default();
+
+        inject_output(
+            &paste(RestorePolicy::OnlyIfUnchanged),
+            clipboard.clone(),
+            typer.clone(),
+            "hello",
+        )
+        .unwrap();
+
+        assert_eq!(*typer.typed.lock().unwrap(), vec!["hello".to_string()]);
+        assert_eq!(clipboard.text().as_deref(), Some("previous"));
+    }
+
+    #[test]
+    fn failed_restore_is_reported() {
+        let clipboard = MockClipboard::holding("previous");
+        clipboard.state.lock().unwrap().fail_set_after = Some(1);
+
+        let err = inject_output(
+            &paste(RestorePolicy::OnlyIfUnchanged),
+            clipboard.clone(),
+            MockTyper::default(),
+            "hello",
+        )
+        .unwrap_err();
+
+        assert_eq!(clipboard.pastes(), 1);
+        assert_eq!(
+            err,
+            "transcript pasted but the previous clipboard could not be restored: \
+             clipboard owner went away"
+        );
+    }
+
+    #[test]
+    fn wayland_clipboard_failures_fall_back_to_wl_copy() {
+        let failed = || {
+            Err(ClipboardError::new(
+                "clipboard is not supported in this environment",
+            ))
+        };
+        let mut runner = MockRunner::default();
+
+        set_with_wl_copy_fallback(Ok(()), DisplayServer::Wayland, &mut runner, "hello").unwrap();
+        assert!(runner.copied.is_empty());
+
+        set_with_wl_copy_fallback(failed(), DisplayServer::X11, &mut runner, "hello").unwrap_err();
+        assert!(runner.copied.is_empty());
+
+        set_with_wl_copy_fallback(failed(), DisplayServer::Wayland, &mut runner, "hello").unwrap();
+        assert_eq!(runner.copied, vec!["hello".to_string()]);
+
+        runner.missing = true;
+        let err = set_with_wl_copy_fallback(failed(), DisplayServer::Wayland, &mut runner, "hello")
+            .unwrap_err();
+        assert_eq!(
+            err.message(),
+            "clipboard is not supported in this environment; \
+             missing clipboard helper: install wl-clipboard to copy on Wayland"
+        );
+    }
+
+    #[test]
+    fn direct_write_copies_to_clipboard_when_typing_fails() {
+        let clipboard = MockClipboard::holding("previous");
+        let typer = MockTyper {
+            fail: true,
+            ..MockTyper::default()
+        };
+
+        let err =
+            inject_output(&OutputStrategy::Type, clipboard.clone(), typer, "hello").unwrap_err();
+
+        assert_eq!(
+            err,
+            "direct write failed; copied to clipboard: missing typing helper"
+        );
+        assert_eq!(clipboard.text().as_deref(), Some("hello"));
+    }
+}
@@ -0,0 +1,351 @@
+use shared_types::{AppSettings, WordReplacement};
+use transcribe_engine::TranscriptionResult;
+
+const SPOKEN_PUNCTUATION: &[(&str, &str, RuleKind)] = &[
+    ("new paragraph", "\n\n", RuleKind::LineBreak),
+    ("new line", "\n", RuleKind::LineBreak),
+    ("question mark", "?", RuleKind::Punctuation),
+    ("exclamation mark", "!", RuleKind::Punctuation),
+    ("exclamation point", "!", RuleKind::Punctuation),
+    ("full stop", ".", RuleKind::Punctuation),
+    ("period", ".", RuleKind::Punctuation),
+    ("comma", ",", RuleKind::Punctuation),
+    ("semicolon", ";", RuleKind::Punctuation),
+    ("colon", ":", RuleKind::Punctuation),
+];
+const ABSORBED_PUNCTUATION: &[char] = &[',', '.', ';', ':', '!', '?'];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum RuleKind {
+    Punctuation,
+    LineBreak,
+    Replace,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Rule {
+    words: Vec<String>,
+    replacement: String,
+    kind: RuleKind,
+}
+
+impl Rule {
+    fn new(phrase: &str, replacement: &str, kind: RuleKind) -> Option<Self> {
+        let words: Vec<String> = phrase.split_whitespace().map(str::to_lowercase).collect();
+        if words.is_empty() {
+            return None;
+        }
+        Some(Self {
+            words,
+            replacement: replacement.to_string(),
+            kind,
+        })
+    }
+
+    fn apply(&self, text: &str) -> String {
+        let mut output = String::with_capacity(text.len());
+        let mut rest = text;
+        while let Some((start, end)) = find_phrase(rest, &self.words) {
+            output.push_str(&rest[..start]);
+            let after = &rest[end..];
+            rest = match self.kind {
+                RuleKind::Replace => {
+                    output.push_str(&self.replacement);
+                    after
+                }
+                RuleKind::Punctuation => {
+                    let kept = output
+                        .trim_end_matches(|ch: char| {
+                            ch.is_whitespace() || ABSORBED_PUNCTUATION.contains(&ch)
+                        })
+                        .len();
+                    output.truncate(kept);
+                    output.push_str(&self.replacement);
+                    after.trim_start_matches(ABSORBED_PUNCTUATION)
+                }
+                RuleKind::LineBreak => {
+                    let kept = output.trim_end().len();
+                    output.truncate(kept);
+                    output.push_str(&self.replacement);
+                    after.trim_start_matches(|ch: char| {
+                        ch.is_whitespace() || ABSORBED_PUNCTUATION.contains(&ch)
+                    })
+                }
+            };
+        }
+        output.push_str(rest);
+        output
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PostProcessor {
+    rules: Vec<Rule>,
+}
+
+impl PostProcessor {
+    pub fn from_settings(settings: &AppSettings) -> Self {
+        let mut processor = Self::default();
+        if settings.spoken_punctuation {
+            processor = processor.with_spoken_punctuation();
+        }
+        processor.with_replacements(&settings.replacements)
+    }
+
+    pub fn with_spoken_punctuation(mut self) -> Self {
+        self.rules.extend(
+            SPOKEN_PUNCTUATION
+                .iter()
+                .filter_map(|(phrase, replacement, kind)| Rule::new(phrase, replacement, *kind)),
+        );
+        self
+    }
+
+    pub fn with_replacements(mut self, replacements: &[WordReplacement]) -> Self {
+        self.rules
+            .extend(replacements.iter().filter_map(|replacement| {
+                Rule::new(&replacement.from, &replacement.to, RuleKind::Replace)
+            }));
+        self
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.rules.is_empty()
+    }
+
+    pub fn process(&self, text: &str) -> String {
+        self.rules
+            .iter()
+            .fold(text.to_string(), |text, rule| rule.apply(&text))
+    }
+
+    pub fn process_result(&self, mut result: TranscriptionResult) -> TranscriptionResult {
+        if self.is_empty() {
+            return result;
+        }
+        result.text = self.process(&result.text);
+        for segment in &mut result.segments {
+            segment.text = self.process(&segment.text);
+        }
+        result
+    }
+}
+
+fn find_phrase(text: &str, words: &[String]) -> Option<(usize, usize)> {
+    let mut previous: Option<char> = None;
+    for (start, ch) in text.char_indices() {
+        let at_boundary = previous.is_none_or(|prev| !is_word_char(prev));
+        previous = Some(ch);
+        if !at_boundary || !ch.is_alphanumeric() {
+            continue;
+        }
+        if let Some(end) = match_words(text, start, words) {
+            return Some((start, end));
+        }
+    }
+    None
+}
+
+fn match_words(text: &str, start: usize, words: &[String]) -> Option<usize> {
+    let mut position = start;
+    for (index, word) in words.iter().enumerate() {
+        if index > 0 {
+            let gap = text[position..]
+                .find(|ch: char| !ch.is_whitespace())
+                .unwrap_or(text.len() - position);
+            if gap == 0 {
+                return None;
+            }
+            position += gap;
+        }
+        position = match_word(text, position, word)?;
+    }
+    let at_boundary = text[position..]
+        .chars()
+        .next()
+        .is_none_or(|ch| !is_word_char(ch));
+    at_boundary.then_some(position)
+}
+
+fn is_word_char(ch: char) -> bool {
+    ch.is_alphanumeric() || ch == '\'' || ch == '-'
+}
+
+fn match_word(text: &str, start: usize, word: &str) -> Option<usize> {
+    let mut expected = word.chars().peekable();
+    let mut position = start;
+    for ch in text[start..].chars() {
+        if expected.peek().is_none() {
+            break;
+        }
+        for lower in ch.to_lowercase() {
+            if expected.next() != Some(lower) {
+                return None;
+            }
+        }
+        position += ch.len_utf8();
+    }
+    expected.peek().is_none().then_some(position)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use transcribe_engine::Segment;
+
+    fn punctuation() -> PostProcessor {
+        PostProcessor::default().with_spoken_punctuation()
+    }
+
+    fn replacement(from: &str, to: &str) -> WordReplacement {
+        WordReplacement {
+            from: from.to_string(),
+            to: to.to_string(),
+        }
+    }
+
+    #[test]
+    fn spoken_punctuation_attaches_to_previous_word() {
+        assert_eq!(
+            punctuation().process("hello comma world period"),
+            "hello, world."
+        );
+        assert_eq!(
+            punctuation().process("is it ready question mark yes exclamation point"),
+            "is it ready? yes!"
+        );
+        assert_eq!(
+            punctuation().process("items colon apples semicolon pears"),
+            "items: apples; pears"
+        );
+    }
+
+    #[test]
+    fn spoken_punctuation_absorbs_whisper_punctuation() {
+        assert_eq!(
+            punctuation().process("Hello, comma, world."),
+            "Hello, world."
+        );
+        assert_eq!(punctuation().process("That's it period."), "That's it.");
+        assert_eq!(punctuation().process("Really? Question mark?"), "Really?");
+    }
+
+    #[test]
+    fn line_breaks_trim_surrounding_spaces() {
+        assert_eq!(
+            punctuation().process("Dear team, new line. Thanks."),
+            "Dear team,\nThanks."
+        );
+        assert_eq!(
+            punctuation().process("first new paragraph second"),
+            "first\n\nsecond"
+        );
+        assert_eq!(punctuation().process("one New  Line two"), "one\ntwo");
+    }
+
+    #[test]
+    fn matching_ignores_case_but_preserves_surrounding_text() {
+        assert_eq!(
+            punctuation().process("Send It To Bob COMMA Then Stop Period"),
+            "Send It To Bob, Then Stop."
+        );
+    }
+
+    #[test]
+    fn matching_respects_word_boundaries() {
+        let processor = punctuation();
+        assert_eq!(
+            processor.process("the commander reads periodicals"),
+            "the commander reads periodicals"
+        );
+        assert_eq!(processor.process("semicolons"), "semicolons");
+        assert_eq!(
+            processor.process("a newline character"),
+            "a newline character"
+        );
+        assert_eq!(processor.process("comma-separated"), "comma-separated");
+        assert_eq!(processor.process("the period's end"), "the period's end");
+    }
+
+    #[test]
+    fn colon_does_not_match_inside_semicolon() {
+        assert_eq!(punctuation().process("a semicolon b"), "a; b");
+    }
+
+    #[test]
+    fn replacements_apply_case_insensitively() {
+        let processor = PostProcessor::default()
+            .with_replacements(&[replacement("open whisper", "OpenWhisperAI")]);
+        assert_eq!(
+            processor.process("I use Open Whisper daily, open   whisper rocks"),
+            "I use OpenWhisperAI daily, OpenWhisperAI rocks"
+        );
+        assert_eq!(processor.process("reopen whisperer"), "reopen whisperer");
+    }
+
+    #[test]
+    fn replacements_run_in_order() {
+        let processor = PostProcessor::default()
+            .with_replacements(&[replacement("alpha", "beta"), replacement("beta", "gamma")]);
+        assert_eq!(processor.process("alpha beta"), "gamma gamma");
+    }
+
+    #[test]
+    fn blank_replacements_are_ignored() {
+        let processor = PostProcessor::default().with_replacements(&[replacement(" ", "x")]);
+        assert!(processor.is_empty());
+        assert_eq!(processor.process("unchanged"), "unchanged");
+    }
+
+    #[test]
+    fn replacements_handle_unicode_case() {
+        let processor =
+            PostProcessor::default().with_replacements(&[replacement("émile", "Émile Zola")]);
+        assert_eq!(processor.process("ÉMILE wrote"), "Émile Zola wrote");
+    }
+
+    #[test]
+    fn settings_gate_spoken_punctuation() {
+        let manual = AppSettings {
+            auto_punctuation: true,
+            spoken_punctuation: false,
+            replacements: vec![replacement("gpu", "GPU")],
+            ..AppSettings::default()
+        };
+        assert_eq!(
+            PostProcessor::from_settings(&manual).process("gpu comma cpu"),
+            "GPU comma cpu"
+        );
+
+        let spoken = AppSettings {
+            spoken_punctuation: true,
+            ..manual
+        };
+        assert_eq!(
+            PostProcessor::from_settings(&spoken).process("gpu comma cpu"),
+            "GPU, cpu"
+        );
+    }
+
+    #[test]
+    fn results_process_text_and_segments() {
+        let result = TranscriptionResult::from_segments(vec![
+            Segment {
+                start_ms: 0,
+                end_ms: 1_000,
+                text: "hello comma".to_string(),
+            },
+            Segment {
+                start_ms: 1_000,
+                end_ms: 2_000,
+                text: "world period".to_string(),
+            },
+        ]);
+
+        let processed = punctuation().process_result(result);
+
+        assert_eq!(processed.text, "hello, world.");
+        assert_eq!(processed.segments[0].text, "hello,");
+        assert_eq!(processed.segments[1].text, "world.");
+    }
+}
@@ -1,33 +1,79 @@
+use crate::feedback::Feedback;
 use crate::logging::emit_app_event;
+use crate::output::{deliver_output, OutputStrategy};
+use crate::postprocess::PostProcessor;
+use crate::recordings::RecordingStore;
 use core_input::{
-    AudioBackend, CpalAudioBackend, GlobalHotkeyListener, Hotkey, HotkeyActionEvent, HotkeyKey,
-    HotkeyListenerHandle, HotkeyManager, HotkeyModifiers, HotkeyState, HotkeyTrigger, LevelReading,
-    PttCaptureService,
+    is_monitor_device, resample_to_16k_mono, AudioBackend, AudioDevice, AudioError,
+    CaptureAutoStopped, CaptureChunk, CaptureLimits, ChunkConfig, ConflictError, CpalAudioBackend,
+    DeviceChangeEvent, DeviceWatcherHandle, FeedbackTone, GainMode, GlobalHotkeyListener, Hotkey,
+    HotkeyActionEvent, HotkeyCaptureSession, HotkeyError, HotkeyKey, HotkeyListenerHandle,
+    HotkeyManager, HotkeyModifiers, HotkeyState, HotkeyTrigger, LevelReading, ListenerMode,
+    MixInput, MultiDeviceCapture, OverflowPolicy, PttCaptureError, PttCaptureService,
+    MIX_DEVICE_PREFIX,
 };
+#[cfg(all(target_os = "linux", feature = "wayland-portal"))]
+use core_input::{DbusShortcutPortal, PortalHotkeyListener};
+#[cfg(all(target_os = "linux", feature = "evdev"))]
+use core_input::{EvdevHotkeyListener, EVDEV_INPUT_DIR};
 use log::{info, warn};
 use serde::{Deserialize, Serialize};
 use shared_types::{
-    AppSettings, ModelInstallStatus, ModelStatusItem, ModelStatusPayload, OutputMode, PttLevel,
-    PttState,
+    AppSettings, AudioSource, HotkeyConfig, ModelInstallStatus, ModelStatusItem,
+    ModelStatusPayload, OutputMode, PttLevel, PttMode, PttState, TranscriptEntry,
+    TranscriptMetrics, TranscriptSegment,
 };
 use std::{
     collections::HashMap,
-    io::ErrorKind,
+    fmt,
     path::{Path, PathBuf},
-    process::Command,
-    sync::{mpsc, Arc, Mutex},
-    time::Duration,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        mpsc, Arc, Mutex,
+    },
+    time::{Duration, Instant, SystemTime, UNIX_EPOCH},
 };
 use transcribe_engine::{
-    BindingError, ModelError, ModelId, ModelManager, ModelSpec, WhisperBindings, WhisperCppBindings,
+    standard_model_checksum, BindingError, ModelError, ModelId, ModelManager, ModelSpec,
+    StandardModel, TranscribeOptions, TranscriptionMetrics, TranscriptionResult, WhisperBindings,
+    WhisperCppBindings,
 };
+#[cfg(feature = "whisper-native")]
+use transcribe_engine::{WhisperNativeBindings, WhisperNativeContext};
 
 pub const PTT_STATE_EVENT: &str = "ptt_state";
 pub const PTT_LEVEL_EVENT: &str = "ptt_level";
 pub const PTT_TRANSCRIPTION_EVENT: &str = "ptt_transcription";
+pub const PTT_TRANSCRIPTION_PARTIAL_EVENT: &str = "ptt_transcription_partial";
 pub const PTT_ERROR_EVENT: &str = "ptt_error";
+pub const PTT_DEVICES_EVENT: &str = "ptt_devices";
+pub const PTT_LANGUAGE_DETECTED_EVENT: &str = "ptt_language_detected";
+pub const PTT_CANCELLED_EVENT: &str = "ptt_cancelled";
+pub const FILE_TRANSCRIPTION_EVENT: &str = "file_transcription";
 const MODEL_STATUS_EVENT: &str = "model-download-status";
 const TARGET_SAMPLE_RATE: u32 = 16_000;
+const LANGUAGE_DETECT_SAMPLES: usize = TARGET_SAMPLE_RATE as usize * 3;
+const DEVICE_POLL_INTERVAL: Duration = Duration::from_secs(2);
+pub const HOTKEY_CAPTURE_TIMEOUT: Duration = Duration::from_secs(10);
+const PTT_ACTION: &str = "ptt";
+const PTT_TOGGLE_ACTION: &str = "ptt-toggle";
+const PTT_TRANSLATE_ACTION: &str = "ptt-translate";
+const PTT_CANCEL_ACTION: &str = "ptt-cancel";
+const PTT_HOLD_THRESHOLD_MS: u64 = 300;
+const PTT_STUCK_KEY_TIMEOUT: Duration = Duration::from_secs(30);
+const STREAM_RESTART_WINDOW: Duration = Duration::from_secs(30);
+const PARTIAL_SEAM_WORDS: usize = 8;
+const PTT_HOLD_BINDINGS: [(HotkeyTrigger, &str); 2] = [
+    (HotkeyTrigger::Released, PTT_TOGGLE_ACTION),
+    (
+        HotkeyTrigger::Held {
+            min_ms: PTT_HOLD_THRESHOLD_MS,
+        },
+        PTT_ACTION,
+    ),
+];
+const PTT_TOGGLE_BINDINGS: [(HotkeyTrigger, &str); 1] =
+    [(HotkeyTrigger::Pressed, PTT_TOGGLE_ACTION)];
 
 #[derive(Clone)]
 pub struct PttHandle {
@@ -46,6 +92,7 @@ enum PttRuntimeCommand {
     },
     SetHotkey {
         payload: PttHotkeyPayload,
+        replace: bool,
         respond: mpsc::Sender<Result<PttHotkeyPayload, String>>,
     },
     UpdateSettings {
@@ -57,17 +104,37 @@ enum PttRuntimeCommand {
     ManualToggle {
         respond: mpsc::Sender<Result<PttState, String>>,
     },
+    Cancel {
+        respond: mpsc::Sender<Result<PttState, String>>,
+    },
+    LevelHistory {
+        respond: mpsc::Sender<Vec<PttLevel>>,
+    },
+    SetHotkeyEnabled {
+        enabled: bool,
+    },
+    BeginHotkeyCapture {
+        timeout: Duration,
+        respond: mpsc::Sender<Result<HotkeyCaptureSession, String>>,
+    },
 }
 
 impl PttHandle {
     pub fn new(model_root: PathBuf, models: Arc<Mutex<crate::state::ModelStore>>) -> Self {
+        Self::spawn(move || SystemPttController::new(model_root, models))
+    }
+
+    fn spawn<B, F>(make_controller: F) -> Self
+    where
+        B: AudioBackend,
+        F: FnOnce() -> PttController<B> + Send + 'static,
+    {
         let (sender, receiver) = mpsc::channel();
         let state = Arc::new(Mutex::new(PttState::Idle));
         let state_handle = Arc::clone(&state);
-        let models_handle = Arc::clone(&models);
 
         std::thread::spawn(move || {
-            let mut controller = SystemPttController::new(model_root, Arc::clone(&models_handle));
+            let mut controller = make_controller();
             controller.attach_state_store(Arc::clone(&state_handle));
 
             loop {
@@ -85,8 +152,12 @@ impl PttHandle {
                             let result = controller.stop();
                             let _ = respond.send(result);
                         }
-                        PttRuntimeCommand::SetHotkey { payload, respond } => {
-                            let result = controller.set_hotkey(payload);
+                        PttRuntimeCommand::SetHotkey {
+                            payload,
+                            replace,
+                            respond,
+                        } => {
+                            let result = controller.set_hotkey(payload, replace);
                             let _ = respond.send(result);
                         }
                         PttRuntimeCommand::UpdateSettings { settings } => {
@@ -99,13 +170,31 @@ impl PttHandle {
                             let result = controller.manual_toggle_recording();
                             let _ = respond.send(result);
                         }
+                        PttRuntimeCommand::Cancel { respond } => {
+                            let _ = respond.send(controller.cancel_capture());
+                        }
+                        PttRuntimeCommand::LevelHistory { respond } => {
+                            let _ = respond.send(controller.level_history());
+                        }
+                        PttRuntimeCommand::SetHotkeyEnabled { enabled } => {
+                            controller.set_hotkey_enabled(enabled);
+                        }
+                        PttRuntimeCommand::BeginHotkeyCapture { timeout, respond } => {
+                            let _ = respond.send(controller.begin_hotkey_capture(timeout));
+                        }
                     },
                     Err(mpsc::RecvTimeoutError::Timeout) => {}
                     Err(mpsc::RecvTimeoutError::Disconnected) => break,
                 }
 
                 controller.poll_hotkey_events();
+                controller.poll_capture_capacity();
+                controller.poll_auto_stop();
+                controller.poll_capture_chunks();
+                controller.poll_stream_errors();
                 controller.poll_level_readings();
+                controller.poll_device_events();
+                controller.poll_transcription();
             }
         });
 
@@ -136,14 +225,40 @@ impl PttHandle {
         receiver.recv().map_err(|err| err.to_string())?
     }
 
-    pub fn set_hotkey(&self, payload: PttHotkeyPayload) -> Result<PttHotkeyPayload, String> {
+    pub fn set_hotkey(
+        &self,
+        payload: PttHotkeyPayload,
+        replace: bool,
+    ) -> Result<PttHotkeyPayload, String> {
         let (respond, receiver) = mpsc::channel();
         self.sender
-            .send(PttRuntimeCommand::SetHotkey { payload, respond })
+            .send(PttRuntimeCommand::SetHotkey {
+                payload,
+                replace,
+                respond,
+            })
             .map_err(|err| err.to_string())?;
         receiver.recv().map_err(|err| err.to_string())?
     }
 
+    pub fn set_hotkey_enabled(&self, enabled: bool) -> Result<(), String> {
+        self.sender
+            .send(PttRuntimeCommand::SetHotkeyEnabled { enabled })
+            .map_err(|err| err.to_string())
+    }
+
+    pub fn capture_hotkey(&self, timeout: Duration) -> Result<PttHotkeyPayload, String> {
+        let (respond, receiver) = mpsc::channel();
+        self.sender
+            .send(PttRuntimeCommand::BeginHotkeyCapture { timeout, respond })
+            .map_err(|err| err.to_string())?;
+        let session = receiver.recv().map_err(|err| err.to_string())??;
+        session
+            .wait()
+            .map(PttHotkeyPayload::from)
+            .map_err(|err| err.to_string())
+    }
+
     pub fn update_settings(&self, settings: AppSettings) {
         let _ = self
             .sender
@@ -164,6 +279,22 @@ impl PttHandle {
         receiver.recv().map_err(|err| err.to_string())?
     }
 
+    pub fn cancel(&self) -> Result<PttState, String> {
+        let (respond, receiver) = mpsc::channel();
+        self.sender
+            .send(PttRuntimeCommand::Cancel { respond })
+            .map_err(|err| err.to_string())?;
+        receiver.recv().map_err(|err| err.to_string())?
+    }
+
+    pub fn level_history(&self) -> Result<Vec<PttLevel>, String> {
+        let (respond, receiver) = mpsc::channel();
+        self.sender
+            .send(PttRuntimeCommand::LevelHistory { respond })
+            .map_err(|err| err.to_string())?;
+        receiver.recv().map_err(|err| err.to_string())
+    }
+
     pub fn state(&self) -> PttState {
         self.state
             .lock()
@@ -172,6 +303,69 @@ impl PttHandle {
     }
 }
 
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+pub struct TranscriptionPayload {
+    pub text: String,
+    pub segments: Vec<TranscriptSegment>,
+}
+
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+pub struct PartialTranscriptionPayload {
+    pub text: String,
+    pub chunks: usize,
+}
+
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct LanguageDetectedPayload {
+    pub language: String,
+    pub probability: f32,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum FileTranscriptionStage {
+    Decoding,
+    Transcribing,
+    Completed,
+    Failed,
+}
+
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct FileTranscriptionPayload {
+    pub path: String,
+    pub stage: FileTranscriptionStage,
+    pub entry: Option<TranscriptEntry>,
+    pub error: Option<String>,
+}
+
+impl FileTranscriptionPayload {
+    pub fn new(path: &Path, stage: FileTranscriptionStage) -> Self {
+        Self {
+            path: path.display().to_string(),
+            stage,
+            entry: None,
+            error: None,
+        }
+    }
+}
+
+impl From<&TranscriptionResult> for TranscriptionPayload {
+    fn from(result: &TranscriptionResult) -> Self {
+        Self {
+            text: result.text.clone(),
+            segments: result
+                .segments
+                .iter()
+                .map(|segment| TranscriptSegment {
+                    start_ms: segment.start_ms,
+                    end_ms: segment.end_ms,
+                    text: segment.text.clone(),
+                })
+                .collect(),
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 pub struct PttHotkeyPayload {
     pub key: String,
@@ -190,15 +384,66 @@ pub struct PttHotkeyModifiers {
     pub meta: bool,
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct PttDevicePayload {
+    pub id: String,
+    pub name: String,
+    pub sample_rate: u32,
+    pub channels: u16,
+}
+
+impl From<&AudioDevice> for PttDevicePayload {
+    fn from(device: &AudioDevice) -> Self {
+        Self {
+            id: device.id.clone(),
+            name: device.name.clone(),
+            sample_rate: device.sample_rate,
+            channels: device.channels,
+        }
+    }
+}
+
 impl Default for PttHotkeyPayload {
     fn default() -> Self {
+        Self::from(&HotkeyConfig::default())
+    }
+}
+
+impl From<&HotkeyConfig> for PttHotkeyPayload {
+    fn from(config: &HotkeyConfig) -> Self {
         Self {
-            key: "space".to_string(),
+            key: config.key.clone(),
             modifiers: PttHotkeyModifiers {
-                ctrl: true,
-                alt: true,
-                shift: false,
-                meta: false,
+                ctrl: config.ctrl,
+                alt: config.alt,
+                shift: config.shift,
+                meta: config.meta,
+            },
+        }
+    }
+}
+
+impl From<&PttHotkeyPayload> for HotkeyConfig {
+    fn from(payload: &PttHotkeyPayload) -> Self {
+        Self {
+            key: payload.key.clone(),
+            ctrl: payload.modifiers.ctrl,
+            alt: payload.modifiers.alt,
+            shift: payload.modifiers.shift,
+            meta: payload.modifiers.meta,
+        }
+    }
+}
+
+impl From<Hotkey> for PttHotkeyPayload {
+    fn from(hotkey: Hotkey) -> Self {
+        Self {
+            key: hotkey.key.to_string(),
+            modifiers: PttHotkeyModifiers {
+                ctrl: hotkey.modifiers.ctrl,
+                alt: hotkey.modifiers.alt,
+                shift: hotkey.modifiers.shift,
+                meta: hotkey.modifiers.meta,
             },
         }
     }
@@ -206,285 +451,443 @@ impl Default for PttHotkeyPayload {
 
 impl PttHotkeyPayload {
     pub fn to_hotkey(&self) -> Result<Hotkey, String> {
-        let key = parse_hotkey_key(&self.key)
-            .ok_or_else(|| format!("unsupported hotkey key '{}'", self.key))?;
+        let key = self
+            .key
+            .parse::<HotkeyKey>()
+            .map_err(|err| err.to_string())?;
+        let modifiers = HotkeyModifiers {
+            ctrl: self.modifiers.ctrl,
+            alt: self.modifiers.alt,
+            shift: self.modifiers.shift,
+            meta: self.modifiers.meta,
+        };
         Ok(Hotkey {
             key,
-            modifiers: HotkeyModifiers {
-                ctrl: self.modifiers.ctrl,
-                alt: self.modifiers.alt,
-                shift: self.modifiers.shift,
-                meta: self.modifiers.meta,
-            },
+            modifiers: modifiers.without_key(key),
         })
     }
 }
 
-pub trait TextInjector: Send + Sync {
-    fn inject(&self, text: &str) -> Result<(), String>;
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TranscriberError {
+    ModelUnusable(String),
+    Failed(String),
 }
 
-pub struct ClipboardInjector;
-
-impl TextInjector for ClipboardInjector {
-    fn inject(&self, text: &str) -> Result<(), String> {
-        let mut clipboard = arboard::Clipboard::new().map_err(|err| err.to_string())?;
-        clipboard
-            .set_text(text.to_string())
-            .map_err(|err| err.to_string())?;
-
-        paste_from_clipboard()
+impl TranscriberError {
+    pub fn model_unusable(&self) -> bool {
+        matches!(self, Self::ModelUnusable(_))
     }
 }
 
-pub struct ClipboardOnlyInjector;
-
-impl TextInjector for ClipboardOnlyInjector {
-    fn inject(&self, text: &str) -> Result<(), String> {
-        let mut clipboard = arboard::Clipboard::new().map_err(|err| err.to_string())?;
-        clipboard
-            .set_text(text.to_string())
-            .map_err(|err| err.to_string())?;
-        Ok(())
+impl fmt::Display for TranscriberError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::ModelUnusable(message) | Self::Failed(message) => f.write_str(message),
+        }
     }
 }
 
-pub struct DirectWriteInjector;
-
-impl TextInjector for DirectWriteInjector {
-    fn inject(&self, text: &str) -> Result<(), String> {
-        type_text(text)
+impl From<String> for TranscriberError {
+    fn from(message: String) -> Self {
+        Self::Failed(message)
     }
 }
 
-enum PasteCommandError {
-    NotFound,
-    Failed(String),
-}
-
-fn paste_from_clipboard() -> Result<(), String> {
-    #[cfg(target_os = "linux")]
-    {
-        let wayland = std::env::var_os("WAYLAND_DISPLAY").is_some();
-        let mut missing = Vec::new();
-
-        for (cmd, args) in paste_command_candidates(wayland) {
-            match run_paste_command(cmd, args) {
-                Ok(()) => return Ok(()),
-                Err(PasteCommandError::NotFound) => missing.push(cmd),
-                Err(PasteCommandError::Failed(message)) => return Err(message),
+impl From<BindingError> for TranscriberError {
+    fn from(err: BindingError) -> Self {
+        match err {
+            BindingError::ModelLoadFailed { .. } | BindingError::UnsupportedModel { .. } => {
+                Self::ModelUnusable(binding_error_message(err))
             }
+            other => Self::Failed(binding_error_message(other)),
         }
-
-        let helper_hint = if wayland {
-            "wtype (Wayland) or xdotool (X11)"
-        } else {
-            "xdotool (X11) or wtype (Wayland)"
-        };
-        Err(format!(
-            "missing paste helper: install {} to enable text injection",
-            helper_hint
-        ))
-    }
-
-    #[cfg(not(target_os = "linux"))]
-    {
-        Err("text injection is only supported on Linux via xdotool or wtype".to_string())
     }
 }
 
-fn type_text(text: &str) -> Result<(), String> {
-    #[cfg(target_os = "linux")]
-    {
-        let wayland = std::env::var_os("WAYLAND_DISPLAY").is_some();
-        let mut missing = Vec::new();
-        for (cmd, args) in type_command_candidates(wayland, text) {
-            log::info!("direct write using {cmd}");
-            match run_type_command(cmd, args) {
-                Ok(()) => return Ok(()),
-                Err(PasteCommandError::NotFound) => missing.push(cmd),
-                Err(PasteCommandError::Failed(message)) => return Err(message),
-            }
-        }
-        let helper_hint = if wayland {
-            "wtype (Wayland) or xdotool (X11)"
-        } else {
-            "xdotool (X11) or wtype (Wayland)"
-        };
-        Err(format!(
-            "missing typing helper: install {} to enable direct write",
-            helper_hint
-        ))
-    }
-
-    #[cfg(not(target_os = "linux"))]
-    {
-        Err("direct write is only supported on Linux via xdotool or wtype".to_string())
-    }
-}
-
-#[cfg(target_os = "linux")]
-fn type_command_candidates(wayland: bool, text: &str) -> Vec<(&'static str, Vec<String>)> {
-    if wayland {
-        vec![
-            ("wtype", vec!["--".to_string(), text.to_string()]),
-            (
-                "xdotool",
-                vec![
-                    "type".to_string(),
-                    "--clearmodifiers".to_string(),
-                    text.to_string(),
-                ],
-            ),
-        ]
-    } else {
-        vec![
-            (
-                "xdotool",
-                vec![
-                    "type".to_string(),
-                    "--clearmodifiers".to_string(),
-                    text.to_string(),
-                ],
-            ),
-            ("wtype", vec!["--".to_string(), text.to_string()]),
-        ]
-    }
-}
-
-#[cfg(target_os = "linux")]
-fn run_type_command(cmd: &str, args: Vec<String>) -> Result<(), PasteCommandError> {
-    let output = Command::new(cmd).args(&args).output().map_err(|err| {
-        if err.kind() == ErrorKind::NotFound {
-            return PasteCommandError::NotFound;
-        }
-        PasteCommandError::Failed(err.to_string())
-    })?;
+pub trait Transcriber: Send + Sync {
+    fn transcribe(
+        &self,
+        audio: &[f32],
+        options: &TranscribeOptions,
+    ) -> Result<TranscriptionResult, TranscriberError>;
 
-    if output.status.success() {
-        return Ok(());
+    fn detect_language(&self, audio: &[f32]) -> Result<(String, f32), String> {
+        let _ = audio;
+        Err("language detection unavailable".to_string())
     }
 
-    let stderr = String::from_utf8_lossy(&output.stderr);
-    Err(PasteCommandError::Failed(format!(
-        "command `{}` exited with {}: {}",
-        cmd,
-        output.status,
-        stderr.trim()
-    )))
+    fn warm_up(&self) {}
 }
 
-#[cfg(target_os = "linux")]
-fn paste_command_candidates(wayland: bool) -> Vec<(&'static str, &'static [&'static str])> {
-    if wayland {
-        vec![
-            ("wtype", &["-M", "ctrl", "-k", "v", "-m", "ctrl"]),
-            ("xdotool", &["key", "--clearmodifiers", "ctrl+v"]),
-        ]
-    } else {
-        vec![
-            ("xdotool", &["key", "--clearmodifiers", "ctrl+v"]),
-            ("wtype", &["-M", "ctrl", "-k", "v", "-m", "ctrl"]),
-        ]
-    }
+struct CachedContext<C> {
+    model_path: PathBuf,
+    modified: Option<SystemTime>,
+    context: C,
 }
 
-fn run_paste_command(cmd: &str, args: &[&str]) -> Result<(), PasteCommandError> {
-    let output = Command::new(cmd).args(args).output().map_err(|err| {
-        if err.kind() == ErrorKind::NotFound {
-            PasteCommandError::NotFound
-        } else {
-            PasteCommandError::Failed(format!("failed to run `{}`: {}", cmd, err))
-        }
-    })?;
-
-    if output.status.success() {
-        return Ok(());
-    }
+// The lock only guards loading; transcriptions share the loaded context so a
+// final transcription never waits behind an in-flight partial.
+type ContextSlot<C> = Mutex<Option<Arc<CachedContext<C>>>>;
 
-    let stderr = String::from_utf8_lossy(&output.stderr);
-    let message = stderr.trim();
-    let details = if message.is_empty() {
-        format!("command `{}` exited with {}", cmd, output.status)
-    } else {
-        format!("command `{}` failed: {}", cmd, message)
-    };
-    Err(PasteCommandError::Failed(details))
+fn model_modified(path: &Path) -> Option<SystemTime> {
+    std::fs::metadata(path)
+        .and_then(|metadata| metadata.modified())
+        .ok()
 }
 
-pub trait Transcriber: Send + Sync {
-    fn transcribe(&self, audio: &[f32]) -> Result<String, String>;
+fn binding_error_message(err: BindingError) -> String {
+    match err {
+        BindingError::Unavailable => "whisper.cpp CLI not found; set WHISPER_CPP_BIN".to_string(),
+        BindingError::Timeout { millis } => {
+            format!("transcription timed out after {millis}ms and was cancelled")
+        }
+        BindingError::ModelLoadFailed { stderr } => {
+            format!("model could not be loaded; try downloading it again ({stderr})")
+        }
+        BindingError::UnsupportedModel { stderr } => {
+            format!("model format is not supported by this whisper.cpp build; try downloading it again ({stderr})")
+        }
+        BindingError::OutOfMemory { stderr } => {
+            format!("not enough memory to run this model; try a smaller one ({stderr})")
+        }
+        other => other.to_string(),
+    }
 }
 
-pub struct LocalTranscriber {
+pub struct LocalTranscriber<W: WhisperBindings = WhisperCppBindings> {
     manager: ModelManager,
     model_id: ModelId,
+    context: ContextSlot<W::Context>,
+    #[cfg(feature = "whisper-native")]
+    native: ContextSlot<WhisperNativeContext>,
 }
 
 impl LocalTranscriber {
     pub fn new(model_root: PathBuf, model_id: ModelId) -> Self {
+        Self::with_bindings(model_root, model_id)
+    }
+}
+
+impl<W: WhisperBindings> LocalTranscriber<W> {
+    pub fn with_bindings(model_root: PathBuf, model_id: ModelId) -> Self {
         let mut manager = ModelManager::new(model_root.clone());
-        register_standard_models(&mut manager);
+        register_known_models(&mut manager, &model_root);
         if let ModelId::Custom(name) = &model_id {
-            register_custom_model(&mut manager, &model_root, name);
+            if manager.model_path(&model_id).is_err() {
+                register_custom_model(&mut manager, &model_root, name);
+            }
+        }
+        Self {
+            manager,
+            model_id,
+            context: Mutex::new(None),
+            #[cfg(feature = "whisper-native")]
+            native: Mutex::new(None),
         }
-        Self { manager, model_id }
     }
-}
 
-impl Transcriber for LocalTranscriber {
-    fn transcribe(&self, audio: &[f32]) -> Result<String, String> {
+    fn load_context<C>(
+        &self,
+        init: impl FnOnce(&Path) -> Result<C, BindingError>,
+    ) -> Result<CachedContext<C>, TranscriberError> {
         let model_path = self
             .manager
             .ensure_model_available(&self.model_id)
-            .map_err(|err| match err {
-                ModelError::MissingFile(_) => {
-                    format!("model not downloaded: {}", self.model_id.display_name())
-                }
-                other => other.to_string(),
+            .map_err(|err| {
+                TranscriberError::Failed(match err {
+                    ModelError::MissingFile(_) => {
+                        format!("model not downloaded: {}", self.model_id.display_name())
+                    }
+                    other => other.to_string(),
+                })
             })?;
         info!(
-            "transcribe using model '{}' at {}",
+            "loading model '{}' from {}",
             self.model_id.display_name(),
             model_path.display()
         );
-        let context = WhisperCppBindings::init_from_file(&model_path).map_err(|err| match err {
-            BindingError::Unavailable => {
-                "whisper.cpp CLI not found; set WHISPER_CPP_BIN".to_string()
-            }
-            other => other.to_string(),
-        })?;
-        WhisperCppBindings::transcribe(&context, audio).map_err(|err| {
-            let message = match err {
-                BindingError::Unavailable => {
-                    "whisper.cpp CLI not found; set WHISPER_CPP_BIN".to_string()
+        let context = init(&model_path)?;
+        Ok(CachedContext {
+            modified: model_modified(&model_path),
+            model_path,
+            context,
+        })
+    }
+
+    fn timed(
+        &self,
+        audio: &[f32],
+        run: impl FnOnce() -> Result<TranscriptionResult, BindingError>,
+    ) -> Result<TranscriptionResult, BindingError> {
+        let started = Instant::now();
+        let result = run()?;
+        let metrics = TranscriptionMetrics::measure(
+            self.model_id.display_name(),
+            audio.len(),
+            started.elapsed(),
+        );
+        Ok(result.with_metrics(metrics))
+    }
+
+    fn with_context<C, T>(
+        &self,
+        slot: &ContextSlot<C>,
+        init: impl FnOnce(&Path) -> Result<C, BindingError>,
+        run: impl FnOnce(&C) -> Result<T, BindingError>,
+    ) -> Result<T, TranscriberError> {
+        let entry = {
+            let mut cached = slot.lock().unwrap_or_else(|err| err.into_inner());
+            let current = self.manager.model_path(&self.model_id).ok();
+            let fresh = match (cached.as_ref(), current.as_deref()) {
+                (Some(entry), Some(path)) => {
+                    entry.model_path == path && entry.modified == model_modified(path)
                 }
-                other => other.to_string(),
+                _ => false,
             };
-            warn!("whisper transcribe failed: {message}");
-            message
+            match cached.take() {
+                Some(entry) if fresh => {
+                    *cached = Some(Arc::clone(&entry));
+                    entry
+                }
+                _ => {
+                    let entry = Arc::new(self.load_context(init)?);
+                    *cached = Some(Arc::clone(&entry));
+                    entry
+                }
+            }
+        };
+        run(&entry.context).map_err(TranscriberError::from)
+    }
+}
+
+impl<W: WhisperBindings> Transcriber for LocalTranscriber<W>
+where
+    W::Context: Send + Sync,
+{
+    fn transcribe(
+        &self,
+        audio: &[f32],
+        options: &TranscribeOptions,
+    ) -> Result<TranscriptionResult, TranscriberError> {
+        #[cfg(feature = "whisper-native")]
+        match self.with_context(
+            &self.native,
+            WhisperNativeBindings::init_from_file,
+            |context| {
+                self.timed(audio, || {
+                    WhisperNativeBindings::transcribe_with_options(context, audio, options)
+                })
+            },
+        ) {
+            Ok(result) => return Ok(result),
+            Err(err) => warn!("native whisper backend failed, falling back to cli: {err}"),
+        }
+        self.with_context(&self.context, W::init_from_file, |context| {
+            self.timed(audio, || {
+                W::transcribe_with_options(context, audio, options)
+            })
+        })
+        .inspect_err(|message| warn!("whisper transcribe failed: {message}"))
+    }
+
+    fn detect_language(&self, audio: &[f32]) -> Result<(String, f32), String> {
+        #[cfg(feature = "whisper-native")]
+        match self.with_context(
+            &self.native,
+            WhisperNativeBindings::init_from_file,
+            |context| WhisperNativeBindings::detect_language(context, audio),
+        ) {
+            Ok(detected) => return Ok(detected),
+            Err(err) => warn!("native language detection failed, falling back to cli: {err}"),
+        }
+        self.with_context(&self.context, W::init_from_file, |context| {
+            W::detect_language(context, audio)
         })
+        .map_err(|err| err.to_string())
+    }
+
+    fn warm_up(&self) {
+        #[cfg(feature = "whisper-native")]
+        let warmed = self.with_context(&self.native, WhisperNativeBindings::init_from_file, |_| {
+            Ok(())
+        });
+        #[cfg(not(feature = "whisper-native"))]
+        let warmed = self.with_context(&self.context, W::init_from_file, |_| Ok(()));
+        if let Err(err) = warmed {
+            info!("model warm-up skipped: {err}");
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum HotkeyBackend {
+    Disabled,
+    Rdev,
+    Portal,
+    Evdev,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum DisplaySession {
+    Desktop,
+    Wayland,
+    Headless,
+}
+
+fn detect_display_session() -> DisplaySession {
+    if matches!(std::env::var("XDG_SESSION_TYPE").as_deref(), Ok("wayland")) {
+        DisplaySession::Wayland
+    } else if cfg!(target_os = "linux")
+        && std::env::var_os("DISPLAY").is_none()
+        && std::env::var_os("WAYLAND_DISPLAY").is_none()
+    {
+        DisplaySession::Headless
+    } else {
+        DisplaySession::Desktop
+    }
+}
+
+fn select_hotkey_backend(
+    disabled: bool,
+    session: DisplaySession,
+    portal_available: impl FnOnce() -> bool,
+    evdev_available: impl FnOnce() -> bool,
+) -> HotkeyBackend {
+    if disabled {
+        HotkeyBackend::Disabled
+    } else if session == DisplaySession::Desktop {
+        HotkeyBackend::Rdev
+    } else if session == DisplaySession::Headless {
+        if evdev_available() {
+            info!("no display detected: reading hotkeys from input devices");
+            HotkeyBackend::Evdev
+        } else {
+            warn!("no display detected: global hotkeys are disabled (use SIGUSR1)");
+            HotkeyBackend::Disabled
+        }
+    } else if portal_available() {
+        info!("Wayland session detected: using the global shortcuts portal");
+        HotkeyBackend::Portal
+    } else {
+        warn!("Wayland session detected: global hotkeys are disabled (use Hyprland binding)");
+        HotkeyBackend::Disabled
+    }
+}
+
+#[cfg(all(target_os = "linux", feature = "wayland-portal"))]
+fn portal_available() -> bool {
+    match DbusShortcutPortal::connect() {
+        Ok(_) => true,
+        Err(err) => {
+            warn!("global shortcuts portal unavailable: {err}");
+            false
+        }
+    }
+}
+
+#[cfg(not(all(target_os = "linux", feature = "wayland-portal")))]
+fn portal_available() -> bool {
+    false
+}
+
+#[cfg(all(target_os = "linux", feature = "evdev"))]
+fn evdev_available() -> bool {
+    Path::new(EVDEV_INPUT_DIR).is_dir()
+}
+
+#[cfg(not(all(target_os = "linux", feature = "evdev")))]
+fn evdev_available() -> bool {
+    false
+}
+
+#[cfg(all(target_os = "linux", feature = "evdev"))]
+fn start_evdev_listener(manager: Arc<Mutex<HotkeyManager>>) -> Result<HotkeyListenerStart, String> {
+    EvdevHotkeyListener::new(manager)
+        .start()
+        .map_err(|err| err.to_string())
+}
+
+#[cfg(not(all(target_os = "linux", feature = "evdev")))]
+fn start_evdev_listener(
+    _manager: Arc<Mutex<HotkeyManager>>,
+) -> Result<HotkeyListenerStart, String> {
+    Err("evdev hotkey support is not enabled in this build".to_string())
+}
+
+#[cfg(all(target_os = "linux", feature = "wayland-portal"))]
+fn start_portal_listener(
+    manager: Arc<Mutex<HotkeyManager>>,
+) -> Result<HotkeyListenerStart, String> {
+    let portal = DbusShortcutPortal::connect().map_err(|err| err.to_string())?;
+    PortalHotkeyListener::new(manager)
+        .start(portal)
+        .map_err(|err| err.to_string())
+}
+
+#[cfg(not(all(target_os = "linux", feature = "wayland-portal")))]
+fn start_portal_listener(
+    _manager: Arc<Mutex<HotkeyManager>>,
+) -> Result<HotkeyListenerStart, String> {
+    Err("global shortcuts portal support is not enabled in this build".to_string())
+}
+
+fn listener_mode(settings: &AppSettings) -> ListenerMode {
+    if settings.consume_hotkey {
+        ListenerMode::Grab
+    } else {
+        ListenerMode::Listen
     }
 }
 
+/// Whether `consume_hotkey` only takes effect after a restart, because the
+/// shared rdev thread is already running in the other mode.
+pub fn hotkey_restart_required(settings: &AppSettings) -> bool {
+    listener_restart_required(GlobalHotkeyListener::running_mode(), settings)
+}
+
+fn listener_restart_required(running: Option<ListenerMode>, settings: &AppSettings) -> bool {
+    running.is_some_and(|running| running != listener_mode(settings))
+}
+
+type HotkeyListenerStart = (HotkeyListenerHandle, mpsc::Receiver<HotkeyActionEvent>);
+
 pub struct PttController<B: AudioBackend> {
     state: PttState,
     armed: bool,
     hotkey: Hotkey,
+    cancel_hotkey: Option<Hotkey>,
+    hotkey_enabled: bool,
     hotkey_manager: Arc<Mutex<HotkeyManager>>,
     hotkey_listener: Option<HotkeyListenerHandle>,
     hotkey_receiver: Option<mpsc::Receiver<HotkeyActionEvent>>,
-    allow_global_hotkeys: bool,
+    hotkey_backend: HotkeyBackend,
+    hotkey_listener_mode: ListenerMode,
+    ptt_mode: PttMode,
+    capture_started: Option<Instant>,
+    translate_capture: bool,
+    language_checked: bool,
     runtime_started: bool,
     level_receiver: Option<mpsc::Receiver<LevelReading>>,
-    capture: PttCaptureService<B>,
+    level_window: LevelWindow,
+    auto_stop_receiver: Option<mpsc::Receiver<CaptureAutoStopped>>,
+    chunk_receiver: Option<mpsc::Receiver<CaptureChunk>>,
+    transcription_receiver: Option<mpsc::Receiver<TranscriptionOutcome>>,
+    partials: Option<PartialWorker>,
+    stream_error_receiver: Option<mpsc::Receiver<AudioError>>,
+    last_stream_restart: Option<Instant>,
+    device_watcher: Option<DeviceWatcherHandle>,
+    device_receiver: Option<mpsc::Receiver<DeviceChangeEvent>>,
+    capture: PttCaptureService<MultiDeviceCapture<B>>,
     transcriber: Arc<dyn Transcriber>,
-    injector: Arc<dyn TextInjector>,
+    transcriber_model: ModelId,
     settings: AppSettings,
     model_root: PathBuf,
+    recordings: RecordingStore,
     active_model: Option<String>,
     state_store: Option<Arc<Mutex<PttState>>>,
     models: Arc<Mutex<crate::state::ModelStore>>,
+    feedback: Feedback,
 }
 
 pub type SystemPttController = PttController<CpalAudioBackend>;
@@ -504,37 +907,63 @@ impl<B: AudioBackend> PttController<B> {
         let disable_hotkeys = std::env::var("OPENWHISPERAI_DISABLE_GLOBAL_HOTKEYS")
             .map(|value| value == "1" || value.eq_ignore_ascii_case("true"))
             .unwrap_or(false);
-        let wayland = matches!(std::env::var("XDG_SESSION_TYPE").as_deref(), Ok("wayland"));
-        let allow_global_hotkeys = !disable_hotkeys && !wayland;
-        if wayland {
-            warn!("Wayland session detected: global hotkeys are disabled (use Hyprland binding)");
-        }
-        let hotkey = PttHotkeyPayload::default().to_hotkey().unwrap_or(Hotkey {
-            key: HotkeyKey::F9,
-            modifiers: HotkeyModifiers::none(),
-        });
+        let hotkey_backend = select_hotkey_backend(
+            disable_hotkeys,
+            detect_display_session(),
+            portal_available,
+            evdev_available,
+        );
+        let settings = AppSettings::default();
+        let hotkey = PttHotkeyPayload::from(&settings.hotkey)
+            .to_hotkey()
+            .unwrap_or(Hotkey {
+                key: HotkeyKey::F9,
+                modifiers: HotkeyModifiers::none(),
+            });
         let mut manager = HotkeyManager::new();
-        register_hotkey_binding(&mut manager, hotkey);
+        let _ = register_hotkey_binding(&mut manager, hotkey, settings.ptt_mode, true);
+        let cancel_hotkey = PttHotkeyPayload::from(&settings.cancel_hotkey)
+            .to_hotkey()
+            .ok()
+            .filter(|cancel| register_cancel_binding(&mut manager, *cancel).is_ok());
         let transcriber = Arc::new(LocalTranscriber::new(model_root.clone(), ModelId::Base));
 
         Self {
             state: PttState::Idle,
             armed: false,
             hotkey,
+            cancel_hotkey,
+            hotkey_enabled: true,
             hotkey_manager: Arc::new(Mutex::new(manager)),
             hotkey_listener: None,
             hotkey_receiver: None,
-            allow_global_hotkeys,
+            hotkey_backend,
+            hotkey_listener_mode: listener_mode(&settings),
+            ptt_mode: settings.ptt_mode,
+            capture_started: None,
+            translate_capture: false,
+            language_checked: false,
             runtime_started: false,
             level_receiver: None,
-            capture: PttCaptureService::new(backend, "ptt"),
+            level_window: LevelWindow::default(),
+            auto_stop_receiver: None,
+            chunk_receiver: None,
+            transcription_receiver: None,
+            partials: None,
+            stream_error_receiver: None,
+            last_stream_restart: None,
+            device_watcher: None,
+            device_receiver: None,
+            capture: PttCaptureService::new(MultiDeviceCapture::new(backend), PTT_ACTION),
             transcriber,
-            injector: Arc::new(ClipboardInjector),
-            settings: AppSettings::default(),
+            transcriber_model: ModelId::Base,
+            settings,
+            recordings: RecordingStore::for_model_root(&model_root),
             model_root,
             active_model: None,
             state_store: None,
             models,
+            feedback: Feedback::default(),
         }
     }
 
@@ -548,53 +977,196 @@ impl<B: AudioBackend> PttController<B> {
         }
     }
 
-    pub fn set_hotkey(&mut self, payload: PttHotkeyPayload) -> Result<PttHotkeyPayload, String> {
+    pub fn set_hotkey(
+        &mut self,
+        payload: PttHotkeyPayload,
+        replace: bool,
+    ) -> Result<PttHotkeyPayload, String> {
         let hotkey = payload.to_hotkey()?;
-        if let Ok(mut manager) = self.hotkey_manager.lock() {
-            manager.unregister(&self.hotkey);
-            register_hotkey_binding(&mut manager, hotkey);
+        if hotkey != self.hotkey {
+            if let Ok(mut manager) = self.hotkey_manager.lock() {
+                let conflict = ptt_bindings(self.ptt_mode)
+                    .iter()
+                    .find_map(|(trigger, _)| manager.would_conflict(&hotkey, *trigger))
+                    .map(str::to_string);
+                if let Some(action) = conflict {
+                    if !replace {
+                        return Err(format!("hotkey '{hotkey}' is already bound to '{action}'"));
+                    }
+                    warn!("replacing hotkey '{hotkey}' bound to '{action}'");
+                    manager.unregister(&hotkey);
+                }
+                manager.unregister(&self.hotkey);
+                if let Some(translate) = translate_hotkey(self.hotkey) {
+                    manager.unregister(&translate);
+                }
+                register_hotkey_binding(&mut manager, hotkey, self.ptt_mode, self.hotkey_enabled)
+                    .map_err(|err| err.to_string())?;
+            }
+            self.hotkey = hotkey;
+            if self.hotkey_backend == HotkeyBackend::Portal && self.hotkey_listener.is_some() {
+                self.stop_hotkey_listener();
+                self.ensure_hotkey_listener()?;
+            }
         }
-        self.hotkey = hotkey;
+        let payload = PttHotkeyPayload::from(hotkey);
+        self.settings.hotkey = HotkeyConfig::from(&payload);
         Ok(payload)
     }
 
-    pub fn set_active_model(&mut self, model_name: Option<String>) {
-        if let Some(active) = self.active_model.as_deref() {
-            if let Ok(mut models) = self.models.lock() {
-                models.clear_override(active);
+    pub fn set_hotkey_enabled(&mut self, enabled: bool) {
+        self.hotkey_enabled = enabled;
+        if let Ok(mut manager) = self.hotkey_manager.lock() {
+            for (trigger, _) in ptt_bindings(self.ptt_mode) {
+                manager.set_enabled(&self.hotkey, *trigger, enabled);
             }
-        }
-        let model_id = model_id_from_name(model_name.as_deref());
-        let display_name = model_id.display_name();
-        self.transcriber = Arc::new(LocalTranscriber::new(self.model_root.clone(), model_id));
-        self.active_model = model_name.or_else(|| Some(display_name));
-        if let Some(active) = self.active_model.as_deref() {
-            if let Ok(mut models) = self.models.lock() {
-                models.clear_override(active);
+            if let Some(translate) = translate_hotkey(self.hotkey) {
+                manager.set_enabled(&translate, translate_trigger(self.ptt_mode), enabled);
             }
         }
-        self.update_model_status_snapshot();
-    }
-
-    pub fn update_settings(&mut self, settings: AppSettings) {
-        self.settings = settings;
+        self.set_cancel_enabled(self.state == PttState::Capturing);
     }
 
-    pub fn start(
-        &mut self,
-        settings: AppSettings,
-        active_model: Option<String>,
-    ) -> Result<PttState, String> {
+    fn apply_cancel_hotkey(&mut self, config: &HotkeyConfig) {
+        let hotkey = match PttHotkeyPayload::from(config).to_hotkey() {
+            Ok(hotkey) => hotkey,
+            Err(err) => {
+                warn!("ignoring configured cancel hotkey: {err}");
+                return;
+            }
+        };
+        if self.cancel_hotkey == Some(hotkey) {
+            return;
+        }
+        if hotkey == self.hotkey {
+            warn!("cancel hotkey '{hotkey}' is already the push-to-talk hotkey");
+            return;
+        }
+        if let Ok(mut manager) = self.hotkey_manager.lock() {
+            if let Some(previous) = self.cancel_hotkey.take() {
+                manager.unregister(&previous);
+            }
+            match register_cancel_binding(&mut manager, hotkey) {
+                Ok(()) => self.cancel_hotkey = Some(hotkey),
+                Err(err) => warn!("cancel hotkey '{hotkey}' unavailable: {err}"),
+            }
+        }
+        self.set_cancel_enabled(self.state == PttState::Capturing);
+    }
+
+    fn set_cancel_enabled(&self, enabled: bool) {
+        let Some(cancel) = self.cancel_hotkey else {
+            return;
+        };
+        if let Ok(mut manager) = self.hotkey_manager.lock() {
+            manager.set_enabled(
+                &cancel,
+                HotkeyTrigger::Pressed,
+                enabled && self.hotkey_enabled,
+            );
+        }
+    }
+
+    fn set_ptt_mode(&mut self, mode: PttMode) {
+        if mode == self.ptt_mode {
+            return;
+        }
+        if let Ok(mut manager) = self.hotkey_manager.lock() {
+            manager.unregister(&self.hotkey);
+            if let Some(translate) = translate_hotkey(self.hotkey) {
+                manager.unregister(&translate);
+            }
+            if let Err(err) =
+                register_hotkey_binding(&mut manager, self.hotkey, mode, self.hotkey_enabled)
+            {
+                warn!("failed to register hotkey for {mode:?}: {err}");
+            }
+        }
+        self.ptt_mode = mode;
+        info!("ptt mode set to {mode:?}");
+        if self.hotkey_backend == HotkeyBackend::Portal && self.hotkey_listener.is_some() {
+            self.stop_hotkey_listener();
+            if let Err(err) = self.ensure_hotkey_listener() {
+                warn!("failed to restart global hotkey listener: {err}");
+            }
+        }
+    }
+
+    pub fn begin_hotkey_capture(
+        &mut self,
+        timeout: Duration,
+    ) -> Result<HotkeyCaptureSession, String> {
+        if self.hotkey_backend == HotkeyBackend::Portal {
+            return Err("hotkey capture is not available through the shortcuts portal".to_string());
+        }
+        if self.hotkey_listener.is_none() {
+            return Err("global hotkey listener is not running".to_string());
+        }
+        let mut manager = self
+            .hotkey_manager
+            .lock()
+            .map_err(|_| HotkeyError::ManagerLockPoisoned.to_string())?;
+        Ok(manager.begin_capture(timeout))
+    }
+
+    fn apply_configured_hotkey(&mut self, config: &HotkeyConfig) {
+        if let Err(err) = self.set_hotkey(PttHotkeyPayload::from(config), true) {
+            warn!("ignoring configured hotkey: {err}");
+        }
+    }
+
+    pub fn set_active_model(&mut self, model_name: Option<String>) {
+        if let Some(active) = self.active_model.as_deref() {
+            if let Ok(mut models) = self.models.lock() {
+                models.clear_override(active);
+            }
+        }
+        let model_id = model_id_from_name(model_name.as_deref());
+        let display_name = model_id.display_name();
+        if model_id != self.transcriber_model {
+            self.transcriber = Arc::new(LocalTranscriber::new(
+                self.model_root.clone(),
+                model_id.clone(),
+            ));
+            self.transcriber_model = model_id;
+        }
+        self.active_model = model_name.or_else(|| Some(display_name));
+        if let Some(active) = self.active_model.as_deref() {
+            if let Ok(mut models) = self.models.lock() {
+                models.clear_override(active);
+            }
+        }
+        self.update_model_status_snapshot();
+    }
+
+    pub fn update_settings(&mut self, settings: AppSettings) {
+        self.set_ptt_mode(settings.ptt_mode);
+        self.apply_configured_hotkey(&settings.hotkey);
+        self.apply_cancel_hotkey(&settings.cancel_hotkey);
+        self.apply_listener_mode(&settings);
+        self.settings = settings;
+    }
+
+    pub fn start(
+        &mut self,
+        settings: AppSettings,
+        active_model: Option<String>,
+    ) -> Result<PttState, String> {
+        self.apply_listener_mode(&settings);
         self.ensure_runtime()?;
         self.arm(settings, active_model)
     }
 
     pub fn stop(&mut self) -> Result<PttState, String> {
         self.armed = false;
+        self.partials = None;
+        self.stop_hotkey_listener();
         if self.capture.audio().is_running() {
-            let _ = self.capture.stop();
+            let _ = self.capture.pause();
+        }
+        if !self.transcription_pending() {
+            self.set_state(PttState::Idle);
         }
-        self.set_state(PttState::Idle);
         Ok(self.state.clone())
     }
 
@@ -603,47 +1175,206 @@ impl<B: AudioBackend> PttController<B> {
         settings: AppSettings,
         active_model: Option<String>,
     ) -> Result<PttState, String> {
+        self.set_ptt_mode(settings.ptt_mode);
+        self.apply_configured_hotkey(&settings.hotkey);
+        self.apply_cancel_hotkey(&settings.cancel_hotkey);
         self.settings = settings.clone();
         self.set_active_model(active_model);
+        self.capture.set_limits(capture_limits(&settings));
+        self.capture.set_gain_mode(gain_mode(&settings));
+        self.capture.set_noise_reduction(settings.noise_reduction);
         self.prepare_audio(&settings)?;
         self.armed = true;
+        let transcriber = Arc::clone(&self.transcriber);
+        std::thread::spawn(move || transcriber.warm_up());
         self.update_model_status_snapshot();
         self.set_state(PttState::Armed);
         Ok(self.state.clone())
     }
 
+    fn capture_plan(
+        &self,
+        settings: &AppSettings,
+    ) -> Result<(Vec<String>, Option<String>), String> {
+        let selection = &settings.input_device;
+        let microphones: Vec<String> = if selection.is_default() {
+            Vec::new()
+        } else {
+            selection
+                .devices()
+                .into_iter()
+                .map(str::to_string)
+                .collect()
+        };
+        if settings.audio_source == AudioSource::Microphone {
+            return Ok(match microphones.len() {
+                0 | 1 => (Vec::new(), microphones.into_iter().next()),
+                _ => (microphones, None),
+            });
+        }
+
+        let backend = self.capture.audio().backend().inner();
+        let monitor = backend
+            .list_loopback_devices()
+            .map_err(|err| err.to_string())?
+            .into_iter()
+            .next()
+            .ok_or_else(|| "no system audio source found".to_string())?;
+        if settings.audio_source == AudioSource::SystemAudio {
+            return Ok((Vec::new(), Some(monitor.id)));
+        }
+
+        let mut inputs = if microphones.is_empty() {
+            backend
+                .default_input_device()
+                .map_err(|err| err.to_string())?
+                .filter(|device| !is_monitor_device(device))
+                .map(|device| device.id)
+                .into_iter()
+                .collect()
+        } else {
+            microphones
+        };
+        if inputs.is_empty() {
+            return Ok((Vec::new(), Some(monitor.id)));
+        }
+        inputs.push(monitor.id);
+        Ok((inputs, None))
+    }
+
     fn prepare_audio(&mut self, settings: &AppSettings) -> Result<(), String> {
+        let (mix_inputs, device) = self.capture_plan(settings)?;
         let audio = self.capture.audio_mut();
-        audio.refresh_devices().map_err(|err| err.to_string())?;
-        if settings.input_device != "default" {
-            let _ = audio.select_device(&settings.input_device);
+        audio
+            .backend()
+            .set_inputs(mix_inputs.into_iter().map(MixInput::new).collect());
+        audio
+            .refresh_devices()
+            .map_err(|err| capture_error_message(err.into()))?;
+        let previous = audio.selected_device().map(|device| device.id.clone());
+        let mut fallback_warning = None;
+        if let Some(mix) = audio.backend().mix_device() {
+            let _ = audio.select_device(&mix.id);
+        } else {
+            let stale = audio.selected_device().is_some_and(|selected| {
+                selected.id.starts_with(MIX_DEVICE_PREFIX)
+                    || (device.is_none() && is_monitor_device(selected))
+            });
+            if stale {
+                audio.clear_selected_device();
+            }
+            if let Some(device) = device {
+                let chosen = audio
+                    .select_device_or_default(&device)
+                    .map_err(|err| capture_error_message(err.into()))?;
+                if chosen.is_fallback() {
+                    fallback_warning = Some(format!(
+                        "device '{device}' not found, using '{}'",
+                        chosen.device().name
+                    ));
+                }
+            }
+        }
+        let device_changed = audio.selected_device().map(|device| device.id.clone()) != previous;
+        if let Some(message) = fallback_warning {
+            emit_warning(&message);
+        }
+        let audio = self.capture.audio();
+        if audio.is_paused() && !device_changed {
+            return self.capture.resume().map_err(capture_error_message);
+        }
+        if device_changed && audio.is_running() {
+            let _ = self.capture.stop();
         }
-        if !audio.is_running() {
-            self.capture.start().map_err(|err| err.to_string())?;
+        if !self.capture.audio().is_running() {
+            self.capture.start().map_err(capture_error_message)?;
         }
         Ok(())
     }
 
-    fn ensure_runtime(&mut self) -> Result<(), String> {
-        if self.runtime_started {
+    fn ensure_hotkey_listener(&mut self) -> Result<(), String> {
+        if self.hotkey_listener.is_some() {
             return Ok(());
         }
+        let manager = Arc::clone(&self.hotkey_manager);
+        let (handle_listener, receiver) = match self.hotkey_backend {
+            HotkeyBackend::Disabled => return Ok(()),
+            HotkeyBackend::Rdev => GlobalHotkeyListener::new(manager)
+                .start(GlobalHotkeyListener::running_mode().unwrap_or(self.hotkey_listener_mode))
+                .map_err(|err| err.to_string())?,
+            HotkeyBackend::Portal => start_portal_listener(manager)?,
+            HotkeyBackend::Evdev => start_evdev_listener(manager)?,
+        };
+        self.hotkey_listener = Some(handle_listener);
+        self.hotkey_receiver = Some(receiver);
+        Ok(())
+    }
+
+    fn apply_listener_mode(&mut self, settings: &AppSettings) {
+        let mode = listener_mode(settings);
+        if mode == self.hotkey_listener_mode {
+            return;
+        }
+        self.hotkey_listener_mode = mode;
+        if self.hotkey_backend == HotkeyBackend::Rdev && hotkey_restart_required(settings) {
+            warn!("consume_hotkey changes take effect after the app restarts");
+        }
+    }
 
-        if self.allow_global_hotkeys && self.hotkey_receiver.is_none() {
-            let listener = GlobalHotkeyListener::new(Arc::clone(&self.hotkey_manager));
-            let (handle_listener, receiver) = listener.start().map_err(|err| err.to_string())?;
-            self.hotkey_listener = Some(handle_listener);
-            self.hotkey_receiver = Some(receiver);
+    fn ensure_runtime(&mut self) -> Result<(), String> {
+        self.ensure_hotkey_listener()?;
+
+        if self.runtime_started {
+            return Ok(());
         }
 
         if self.level_receiver.is_none() {
             self.level_receiver = self.capture.level_feed();
         }
 
+        if self.auto_stop_receiver.is_none() {
+            self.auto_stop_receiver = self.capture.auto_stop_feed();
+        }
+
+        if self.chunk_receiver.is_none() {
+            self.chunk_receiver = self.capture.chunk_feed();
+        }
+
+        if self.stream_error_receiver.is_none() {
+            self.stream_error_receiver = self.capture.error_feed();
+        }
+
+        if self.device_watcher.is_none() {
+            match self.capture.audio().device_watcher() {
+                Ok(watcher) => {
+                    let (handle, receiver) = watcher.start(DEVICE_POLL_INTERVAL);
+                    self.device_watcher = Some(handle);
+                    self.device_receiver = Some(receiver);
+                }
+                Err(err) => warn!("device watcher unavailable: {err}"),
+            }
+        }
+
         self.runtime_started = true;
         Ok(())
     }
 
+    fn stop_hotkey_listener(&mut self) {
+        if let Some(listener) = self.hotkey_listener.take() {
+            listener.stop();
+            // The evdev reader threads wake up to check the stop flag, so wait
+            // for them to close their device handles. The rdev thread is shared
+            // and keeps running until the next start swaps its handler.
+            if self.hotkey_backend == HotkeyBackend::Evdev {
+                if let Err(err) = listener.join() {
+                    warn!("evdev hotkey listener stopped with an error: {err}");
+                }
+            }
+            info!("global hotkey listener stopped");
+        }
+        self.hotkey_receiver = None;
+    }
+
     fn poll_hotkey_events(&mut self) {
         let Some(receiver) = self.hotkey_receiver.take() else {
             return;
@@ -660,13 +1391,7 @@ impl<B: AudioBackend> PttController<B> {
                     };
 
                     if let Some(work) = work {
-                        let transcription = work.transcriber.transcribe(&work.audio);
-                        if let Ok(text) = &transcription {
-                            if let Err(err) = self.handle_output(&work.output_mode, text) {
-                                self.emit_output_warning(&err);
-                            }
-                        }
-                        self.complete_transcription(transcription);
+                        self.run_transcription(work);
                     }
                 }
                 Err(mpsc::TryRecvError::Empty) => break,
@@ -680,166 +1405,584 @@ impl<B: AudioBackend> PttController<B> {
         self.hotkey_receiver = Some(receiver);
     }
 
-    fn poll_level_readings(&mut self) {
-        let Some(receiver) = self.level_receiver.take() else {
+    fn poll_capture_capacity(&mut self) {
+        if self.state != PttState::Capturing {
+            return;
+        }
+        match self.capture.check_capacity() {
+            Ok(()) => {}
+            Err(PttCaptureError::BufferFull) => {
+                warn!(
+                    "recording reached the {}s limit; stopping capture",
+                    self.settings.max_recording_seconds
+                );
+                self.release_capture();
+            }
+            Err(err) => self.emit_error(&err.to_string()),
+        }
+    }
+
+    fn poll_auto_stop(&mut self) {
+        let Some(receiver) = self.auto_stop_receiver.take() else {
             return;
         };
         loop {
             match receiver.try_recv() {
-                Ok(reading) => {
-                    if self.armed {
-                        emit_level(reading);
+                Ok(event) => {
+                    if self.state == PttState::Capturing {
+                        info!("ptt auto-stopped after {:?} of silence", event.silence);
+                        self.release_capture();
                     }
                 }
                 Err(mpsc::TryRecvError::Empty) => break,
                 Err(mpsc::TryRecvError::Disconnected) => {
-                    self.level_receiver = None;
+                    self.auto_stop_receiver = None;
                     return;
                 }
             }
         }
 
-        self.level_receiver = Some(receiver);
+        self.auto_stop_receiver = Some(receiver);
     }
 
-    fn handle_hotkey_action(
-        &mut self,
-        event: &HotkeyActionEvent,
-    ) -> Result<Option<TranscriptionWork>, String> {
-        if !self.armed {
-            return Ok(None);
-        }
-        if event.action != "ptt" {
-            return Ok(None);
-        }
-        let mut effective_state = event.state;
-        if matches!(event.state, HotkeyState::Pressed) && self.state == PttState::Capturing {
-            warn!("ptt release not detected; treating press as release");
-            effective_state = HotkeyState::Released;
-        }
-        info!("ptt hotkey {:?}", effective_state);
-        let effective_event = HotkeyActionEvent {
-            action: event.action.clone(),
-            hotkey: event.hotkey.clone(),
-            state: effective_state,
+    fn poll_capture_chunks(&mut self) {
+        let Some(receiver) = self.chunk_receiver.take() else {
+            return;
         };
-        self.capture
-            .handle_hotkey_action(&effective_event)
-            .map_err(|err| err.to_string())?;
-
-        match effective_state {
-            HotkeyState::Pressed => {
-                self.set_state(PttState::Capturing);
-                Ok(None)
-            }
-            HotkeyState::Released => {
-                self.set_state(PttState::Processing);
-                self.mark_model_downloading();
-                let audio = self.capture.take_audio().map_err(|err| err.to_string())?;
-                let (sample_rate, channels) = self
-                    .capture
-                    .audio()
-                    .selected_device()
-                    .map(|device| (device.sample_rate, device.channels))
-                    .unwrap_or((TARGET_SAMPLE_RATE, 1));
-                let audio = resample_to_16k_mono(audio, sample_rate, channels);
-                Ok(Some(TranscriptionWork {
-                    audio,
-                    transcriber: Arc::clone(&self.transcriber),
-                    injector: Arc::clone(&self.injector),
-                    output_mode: self.settings.output_mode.clone(),
-                }))
+        loop {
+            match receiver.try_recv() {
+                Ok(chunk) => self.forward_chunk(chunk),
+                Err(mpsc::TryRecvError::Empty) => break,
+                Err(mpsc::TryRecvError::Disconnected) => {
+                    self.chunk_receiver = None;
+                    return;
+                }
             }
         }
+
+        self.chunk_receiver = Some(receiver);
     }
 
-    fn manual_toggle_recording(&mut self) -> Result<PttState, String> {
-        log::info!("manual toggle requested (state={:?})", self.state);
-        if self.state == PttState::Processing {
-            return Ok(self.state.clone());
+    fn forward_chunk(&mut self, chunk: CaptureChunk) {
+        if self.state != PttState::Capturing {
+            return;
         }
-        if !self.armed {
-            let settings = self.settings.clone();
-            let active_model = self.active_model.clone();
-            self.arm(settings, active_model)?;
+        let (sample_rate, channels) = self.capture_format();
+        let audio = resample_to_16k_mono(chunk.samples, sample_rate, channels);
+        if chunk.index == 0 {
+            self.detect_capture_language(&audio);
         }
+        if let Some(partials) = self.partials.as_ref() {
+            partials.push(audio);
+        }
+    }
 
-        let next_state = if self.state == PttState::Capturing {
-            HotkeyState::Released
-        } else {
-            HotkeyState::Pressed
-        };
-        let event = HotkeyActionEvent {
-            action: "ptt".to_string(),
-            hotkey: self.hotkey.clone(),
-            state: next_state,
+    fn detect_capture_language(&mut self, audio: &[f32]) {
+        if self.language_checked || !self.settings.auto_language {
+            return;
+        }
+        self.language_checked = true;
+        let settings = self.settings.clone();
+        let transcriber = Arc::clone(&self.transcriber);
+        let audio = audio[..audio.len().min(LANGUAGE_DETECT_SAMPLES)].to_vec();
+        std::thread::spawn(move || {
+            if let Some(payload) = capture_language(&settings, transcriber.as_ref(), &audio) {
+                emit_app_event(PTT_LANGUAGE_DETECTED_EVENT, &payload);
+            }
+        });
+    }
+
+    fn capture_format(&self) -> (u32, u16) {
+        self.capture
+            .audio()
+            .selected_device()
+            .map(|device| (device.sample_rate, device.channels))
+            .unwrap_or((TARGET_SAMPLE_RATE, 1))
+    }
+
+    fn transcribe_options(&self, translate: bool) -> TranscribeOptions {
+        settings_transcribe_options(&self.settings, translate)
+    }
+
+    fn poll_stream_errors(&mut self) {
+        let Some(receiver) = self.stream_error_receiver.take() else {
+            return;
         };
-        let work = self.handle_hotkey_action(&event)?;
-        if let Some(work) = work {
-            let transcription = work.transcriber.transcribe(&work.audio);
-            if let Ok(text) = &transcription {
-                if let Err(err) = self.handle_output(&work.output_mode, text) {
-                    self.emit_output_warning(&err);
+        let mut failure = None;
+        loop {
+            match receiver.try_recv() {
+                Ok(err) => failure = Some(err),
+                Err(mpsc::TryRecvError::Empty) => break,
+                Err(mpsc::TryRecvError::Disconnected) => {
+                    self.stream_error_receiver = None;
+                    return;
                 }
             }
-            self.complete_transcription(transcription);
         }
 
-        log::info!("manual toggle finished (state={:?})", self.state);
-        Ok(self.state.clone())
+        self.stream_error_receiver = Some(receiver);
+        if let Some(err) = failure {
+            self.restart_stream(&err, Instant::now());
+        }
     }
 
-    fn complete_transcription(&mut self, result: Result<String, String>) {
-        match result {
-            Ok(text) => {
-                if text.trim().is_empty() {
-                    emit_app_event(PTT_ERROR_EVENT, &"no speech detected".to_string());
-                    info!("transcription empty");
-                    self.mark_model_ready();
-                    self.set_state(if self.armed {
-                        PttState::Armed
-                    } else {
-                        PttState::Idle
-                    });
-                    return;
-                }
-                if let Ok(mut models) = self.models.lock() {
-                    models.set_last_transcript(text.clone());
+    fn restart_stream(&mut self, err: &AudioError, now: Instant) {
+        warn!("audio stream failed: {err}");
+        if self.state == PttState::Capturing {
+            // Transcribe what was captured before the stream dropped out.
+            self.release_capture();
+        }
+        self.report_stream_error(&err.to_string());
+        if self.capture.audio().is_running() {
+            let _ = self.capture.stop();
+        }
+        if !self.armed {
+            return;
+        }
+        let restarted_recently = self
+            .last_stream_restart
+            .is_some_and(|last| now.saturating_duration_since(last) < STREAM_RESTART_WINDOW);
+        if restarted_recently {
+            self.armed = false;
+            self.last_stream_restart = None;
+            self.report_stream_error(
+                "audio stream failed again after a restart; start again to retry",
+            );
+            return;
+        }
+        self.last_stream_restart = Some(now);
+        let settings = self.settings.clone();
+        match self.prepare_audio(&settings) {
+            Ok(()) => {
+                info!("audio stream restarted after error");
+                if !self.transcription_pending() {
+                    self.set_state(PttState::Armed);
                 }
-                emit_app_event(PTT_TRANSCRIPTION_EVENT, &text);
-                info!("transcription complete ({} chars)", text.len());
-                self.mark_model_ready();
-                self.set_state(if self.armed {
-                    PttState::Armed
-                } else {
-                    PttState::Idle
-                });
             }
             Err(err) => {
-                self.emit_error(&err);
-                warn!("transcription failed: {err}");
-                self.mark_model_failed();
-                self.set_state(if self.armed {
-                    PttState::Armed
-                } else {
-                    PttState::Idle
-                });
+                self.armed = false;
+                self.report_stream_error(&format!("audio stream restart failed: {err}"));
             }
         }
     }
 
-    fn emit_error(&mut self, message: &str) {
-        self.set_state(PttState::Error {
-            message: message.to_string(),
-        });
-        emit_app_event(PTT_ERROR_EVENT, &message.to_string());
-    }
+    fn report_stream_error(&mut self, message: &str) {
+        if self.transcription_pending() {
+            emit_app_event(PTT_ERROR_EVENT, &message.to_string());
+        } else {
+            self.emit_error(message);
+        }
+    }
+
+    fn release_capture(&mut self) {
+        let event = HotkeyActionEvent {
+            action: PTT_ACTION.to_string(),
+            hotkey: self.hotkey,
+            state: HotkeyState::Released,
+        };
+        match self.handle_hotkey_action(&event) {
+            Ok(Some(work)) => self.run_transcription(work),
+            Ok(None) => {}
+            Err(err) => self.emit_error(&err),
+        }
+    }
+
+    fn poll_level_readings(&mut self) {
+        if let Some(level) = self.drain_level_readings(Instant::now()) {
+            emit_app_event(PTT_LEVEL_EVENT, &level);
+        }
+    }
+
+    fn drain_level_readings(&mut self, now: Instant) -> Option<PttLevel> {
+        let receiver = self.level_receiver.take()?;
+        let metering = matches!(self.state, PttState::Armed | PttState::Capturing);
+        loop {
+            match receiver.try_recv() {
+                Ok(reading) if metering => self.level_window.push(reading),
+                Ok(_) => {}
+                Err(mpsc::TryRecvError::Empty) => break,
+                Err(mpsc::TryRecvError::Disconnected) => {
+                    self.level_window = LevelWindow::default();
+                    return None;
+                }
+            }
+        }
+        self.level_receiver = Some(receiver);
+
+        if !metering {
+            self.level_window = LevelWindow::default();
+            return None;
+        }
+        let interval = Duration::from_millis(u64::from(self.settings.level_interval_ms));
+        self.level_window.flush(now, interval)
+    }
+
+    fn poll_device_events(&mut self) {
+        let Some(receiver) = self.device_receiver.take() else {
+            return;
+        };
+        let mut changed = false;
+        loop {
+            match receiver.try_recv() {
+                Ok(event) => {
+                    info!("audio device change: {event:?}");
+                    changed = true;
+                }
+                Err(mpsc::TryRecvError::Empty) => break,
+                Err(mpsc::TryRecvError::Disconnected) => {
+                    self.device_receiver = None;
+                    self.device_watcher = None;
+                    return;
+                }
+            }
+        }
+
+        self.device_receiver = Some(receiver);
+        if changed {
+            self.sync_devices();
+        }
+    }
+
+    fn sync_devices(&mut self) {
+        let audio = self.capture.audio_mut();
+        let devices = match audio.refresh_devices() {
+            Ok(devices) => devices.to_vec(),
+            Err(err) => {
+                warn!("failed to refresh audio devices: {err}");
+                return;
+            }
+        };
+        let payload: Vec<PttDevicePayload> = devices.iter().map(PttDevicePayload::from).collect();
+        emit_app_event(PTT_DEVICES_EVENT, &payload);
+
+        let selected_removed = audio
+            .selected_device()
+            .is_some_and(|selected| !device_present(&devices, selected));
+        if !selected_removed {
+            return;
+        }
+
+        warn!("selected input device was removed; falling back to default device");
+        if audio.is_running() {
+            let _ = self.capture.stop();
+        }
+        self.capture.audio_mut().clear_selected_device();
+        if self.armed {
+            let settings = self.settings.clone();
+            let active_model = self.active_model.clone();
+            if let Err(err) = self.arm(settings, active_model) {
+                self.armed = false;
+                self.emit_error(&err);
+            }
+        }
+    }
+
+    fn handle_hotkey_action(
+        &mut self,
+        event: &HotkeyActionEvent,
+    ) -> Result<Option<TranscriptionWork>, String> {
+        if !self.armed {
+            return Ok(None);
+        }
+        if event.action == PTT_CANCEL_ACTION {
+            if event.state == HotkeyState::Pressed {
+                self.cancel_capture()?;
+            }
+            return Ok(None);
+        }
+        let effective_state = match event.action.as_str() {
+            PTT_ACTION
+                if event.state == HotkeyState::Pressed && self.state == PttState::Capturing =>
+            {
+                if !self.capture_stuck() {
+                    info!("ignoring ptt press while already capturing");
+                    return Ok(None);
+                }
+                warn!(
+                    "ptt release not detected for {}s; stopping capture",
+                    PTT_STUCK_KEY_TIMEOUT.as_secs()
+                );
+                HotkeyState::Released
+            }
+            PTT_ACTION => event.state,
+            PTT_TOGGLE_ACTION | PTT_TRANSLATE_ACTION if self.state == PttState::Capturing => {
+                HotkeyState::Released
+            }
+            PTT_TOGGLE_ACTION | PTT_TRANSLATE_ACTION => HotkeyState::Pressed,
+            _ => return Ok(None),
+        };
+        if self.transcription_pending() {
+            info!("ignoring ptt {effective_state:?} while a transcription is processing");
+            return Ok(None);
+        }
+        if matches!(effective_state, HotkeyState::Released) && self.state != PttState::Capturing {
+            return Ok(None);
+        }
+        info!("ptt hotkey {:?}", effective_state);
+        let effective_event = HotkeyActionEvent {
+            action: PTT_ACTION.to_string(),
+            hotkey: event.hotkey.clone(),
+            state: effective_state,
+        };
+        self.capture
+            .handle_hotkey_action(&effective_event)
+            .map_err(|err| err.to_string())?;
+
+        match effective_state {
+            HotkeyState::Pressed => {
+                self.translate_capture = event.action == PTT_TRANSLATE_ACTION;
+                self.language_checked = false;
+                self.partials = self.settings.partial_transcription.then(|| {
+                    PartialWorker::spawn(
+                        Arc::clone(&self.transcriber),
+                        self.transcribe_options(self.translate_capture)
+                            .with_partial(true),
+                    )
+                });
+                self.capture_started = Some(Instant::now());
+                self.feedback.play(&self.settings, FeedbackTone::Start);
+                self.set_state(PttState::Capturing);
+                Ok(None)
+            }
+            HotkeyState::Released => {
+                self.capture_started = None;
+                self.feedback.play(&self.settings, FeedbackTone::Stop);
+                self.partials = None;
+                if let Some(receiver) = self.chunk_receiver.as_ref() {
+                    receiver.try_iter().for_each(drop);
+                }
+                let audio = self.capture.take_audio().map_err(|err| err.to_string())?;
+                let (sample_rate, channels) = self.capture_format();
+                let captured = captured_duration(audio.len(), sample_rate, channels);
+                let min_duration = self.capture.limits().min_duration;
+                if captured < min_duration {
+                    log::debug!(
+                        "discarding {}ms capture shorter than {}ms",
+                        captured.as_millis(),
+                        min_duration.as_millis()
+                    );
+                    self.translate_capture = false;
+                    self.set_state(PttState::Armed);
+                    return Ok(None);
+                }
+                self.set_state(self.processing_state());
+                let audio = resample_to_16k_mono(audio, sample_rate, channels);
+                // Captures shorter than the first chunk never reach
+                // forward_chunk, so detect on the full take instead.
+                self.detect_capture_language(&audio);
+                let recording_path = if self.settings.save_recordings {
+                    self.save_recording(&audio)
+                } else {
+                    None
+                };
+                let translate = std::mem::take(&mut self.translate_capture);
+                Ok(Some(TranscriptionWork {
+                    audio,
+                    recording_path,
+                    transcriber: Arc::clone(&self.transcriber),
+                    output: OutputStrategy::from_settings(&self.settings),
+                    output_mode: self.settings.output_mode.clone(),
+                    options: self.transcribe_options(translate),
+                    postprocessor: PostProcessor::from_settings(&self.settings),
+                }))
+            }
+        }
+    }
+
+    fn cancel_capture(&mut self) -> Result<PttState, String> {
+        if self.state != PttState::Capturing {
+            return Ok(self.state.clone());
+        }
+        let release = HotkeyActionEvent {
+            action: PTT_ACTION.to_string(),
+            hotkey: self.hotkey,
+            state: HotkeyState::Released,
+        };
+        self.capture
+            .handle_hotkey_action(&release)
+            .map_err(|err| err.to_string())?;
+        self.capture.take_audio().map_err(|err| err.to_string())?;
+        if let Some(receiver) = self.chunk_receiver.as_ref() {
+            receiver.try_iter().for_each(drop);
+        }
+        self.partials = None;
+        self.capture_started = None;
+        self.translate_capture = false;
+        self.feedback.play(&self.settings, FeedbackTone::Stop);
+        self.set_state(PttState::Armed);
+        info!("ptt capture cancelled");
+        emit_app_event(PTT_CANCELLED_EVENT, &self.state);
+        Ok(self.state.clone())
+    }
+
+    fn capture_stuck(&self) -> bool {
+        self.capture_started
+            .is_some_and(|started| started.elapsed() >= PTT_STUCK_KEY_TIMEOUT)
+    }
+
+    fn level_history(&self) -> Vec<PttLevel> {
+        match self.capture.level_history() {
+            Ok(history) => history.into_iter().map(level_payload).collect(),
+            Err(err) => {
+                log::warn!("failed to read level history: {err}");
+                Vec::new()
+            }
+        }
+    }
+
+    fn manual_toggle_recording(&mut self) -> Result<PttState, String> {
+        log::info!("manual toggle requested (state={:?})", self.state);
+        if self.transcription_pending() {
+            return Ok(self.state.clone());
+        }
+        if !self.armed {
+            let settings = self.settings.clone();
+            let active_model = self.active_model.clone();
+            self.arm(settings, active_model)?;
+        }
+
+        let next_state = if self.state == PttState::Capturing {
+            HotkeyState::Released
+        } else {
+            HotkeyState::Pressed
+        };
+        let event = HotkeyActionEvent {
+            action: PTT_ACTION.to_string(),
+            hotkey: self.hotkey.clone(),
+            state: next_state,
+        };
+        let work = self.handle_hotkey_action(&event)?;
+        if let Some(work) = work {
+            self.run_transcription(work);
+        }
+
+        log::info!("manual toggle finished (state={:?})", self.state);
+        Ok(self.state.clone())
+    }
+
+    fn run_transcription(&mut self, work: TranscriptionWork) {
+        let (sender, receiver) = mpsc::channel();
+        std::thread::spawn(move || {
+            let result = work.run();
+            let _ = sender.send((result, work));
+        });
+        self.transcription_receiver = Some(receiver);
+    }
+
+    fn transcription_pending(&self) -> bool {
+        self.transcription_receiver.is_some()
+    }
+
+    fn poll_transcription(&mut self) {
+        let Some(receiver) = self.transcription_receiver.take() else {
+            return;
+        };
+        match receiver.try_recv() {
+            Ok((result, work)) => self.complete_transcription(result, &work),
+            Err(mpsc::TryRecvError::Empty) => self.transcription_receiver = Some(receiver),
+            Err(mpsc::TryRecvError::Disconnected) => {
+                warn!("transcription worker exited without a result");
+                self.emit_error("transcription stopped unexpectedly");
+            }
+        }
+    }
+
+    fn complete_transcription(
+        &mut self,
+        result: Result<TranscriptionResult, TranscriberError>,
+        work: &TranscriptionWork,
+    ) {
+        match result {
+            Ok(result) => {
+                let payload = TranscriptionPayload::from(&result);
+                let text = payload.text.clone();
+                if text.trim().is_empty() {
+                    self.feedback.play(&self.settings, FeedbackTone::Error);
+                    emit_app_event(PTT_ERROR_EVENT, &"no speech detected".to_string());
+                    info!("transcription empty");
+                    self.mark_model_ready();
+                    self.set_state(if self.armed {
+                        PttState::Armed
+                    } else {
+                        PttState::Idle
+                    });
+                    return;
+                }
+                if let Ok(mut models) = self.models.lock() {
+                    let mut entry = TranscriptEntry::new(
+                        text.clone(),
+                        now_ms(),
+                        work.duration_ms(),
+                        self.active_model.clone(),
+                        work.output_mode.clone(),
+                    );
+                    if let Some(path) = &work.recording_path {
+                        entry = entry.with_recording_path(path.clone());
+                    }
+                    if let Some(metrics) = transcript_metrics(&result.metrics) {
+                        info!(
+                            "transcription metrics: model={} audio={:.2}s wall={}ms rtf={:.3}",
+                            metrics.model, metrics.audio_seconds, metrics.wall_ms, metrics.rtf
+                        );
+                        entry = entry.with_metrics(metrics);
+                    }
+                    models.record_transcript(entry.with_segments(payload.segments.clone()));
+                }
+                emit_app_event(PTT_TRANSCRIPTION_EVENT, &payload);
+                info!("transcription complete ({} chars)", text.len());
+                self.feedback.transcript_ready(&self.settings, &text);
+                self.mark_model_ready();
+                self.set_state(if self.armed {
+                    PttState::Armed
+                } else {
+                    PttState::Idle
+                });
+            }
+            Err(err) => {
+                self.emit_error(&err.to_string());
+                warn!("transcription failed: {err}");
+                if err.model_unusable() {
+                    self.mark_model_failed();
+                }
+                self.set_state(if self.armed {
+                    PttState::Armed
+                } else {
+                    PttState::Idle
+                });
+            }
+        }
+    }
+
+    fn save_recording(&self, audio: &[f32]) -> Option<PathBuf> {
+        match self.recordings.save(audio, now_ms()) {
+            Ok(path) => {
+                info!("saved recording to {}", path.display());
+                Some(path)
+            }
+            Err(err) => {
+                warn!("failed to save recording: {err}");
+                None
+            }
+        }
+    }
+
+    fn emit_error(&mut self, message: &str) {
+        self.feedback.play(&self.settings, FeedbackTone::Error);
+        self.set_state(PttState::Error {
+            message: message.to_string(),
+        });
+        emit_app_event(PTT_ERROR_EVENT, &message.to_string());
+    }
 
     fn set_state(&mut self, next: PttState) {
         if self.state == next {
             return;
         }
+        let was_capturing = self.state == PttState::Capturing;
         self.state = next.clone();
+        if was_capturing != (next == PttState::Capturing) {
+            self.set_cancel_enabled(!was_capturing);
+        }
         if let Some(store) = &self.state_store {
             let mut guard = store
                 .lock()
@@ -864,14 +2007,26 @@ impl<B: AudioBackend> PttController<B> {
         emit_app_event(MODEL_STATUS_EVENT, &payload);
     }
 
-    fn mark_model_downloading(&mut self) {
-        self.update_active_override(Some(ModelInstallStatus::Downloading));
-        self.update_model_status_snapshot();
+    fn processing_state(&self) -> PttState {
+        PttState::Processing {
+            model: self
+                .active_model
+                .clone()
+                .unwrap_or_else(|| self.transcriber_model.display_name()),
+            started_ms: now_ms(),
+        }
     }
 
     fn mark_model_ready(&mut self) {
-        self.update_active_override(None);
-        self.update_model_status_snapshot();
+        let overridden = self.active_model.as_ref().is_some_and(|active| {
+            self.models
+                .lock()
+                .is_ok_and(|models| models.overrides_snapshot().contains_key(active))
+        });
+        if overridden {
+            self.update_active_override(None);
+            self.update_model_status_snapshot();
+        }
     }
 
     fn mark_model_failed(&mut self) {
@@ -890,194 +2045,517 @@ impl<B: AudioBackend> PttController<B> {
             }
         }
     }
+}
 
-    fn handle_output(&self, mode: &OutputMode, text: &str) -> Result<(), String> {
-        if text.is_empty() {
-            return Ok(());
-        }
-        match mode {
-            OutputMode::UiOnly => Ok(()),
-            OutputMode::Clipboard => ClipboardOnlyInjector.inject(text),
-            OutputMode::DirectWrite => match DirectWriteInjector.inject(text) {
-                Ok(()) => {
-                    log::info!("direct write succeeded");
-                    Ok(())
-                }
-                Err(err) => {
-                    let _ = ClipboardOnlyInjector.inject(text);
-                    Err(format!("direct write failed; copied to clipboard: {err}"))
-                }
-            },
-        }
-    }
-
-    fn emit_output_warning(&self, message: &str) {
-        warn!("output warning: {message}");
-        emit_app_event(PTT_ERROR_EVENT, &message.to_string());
-    }
+fn emit_warning(message: &str) {
+    warn!("ptt warning: {message}");
+    emit_app_event(PTT_ERROR_EVENT, &message.to_string());
 }
 
 struct TranscriptionWork {
     audio: Vec<f32>,
+    recording_path: Option<PathBuf>,
     transcriber: Arc<dyn Transcriber>,
-    injector: Arc<dyn TextInjector>,
+    output: OutputStrategy,
     output_mode: OutputMode,
+    options: TranscribeOptions,
+    postprocessor: PostProcessor,
 }
 
-fn resample_to_16k_mono(audio: Vec<f32>, sample_rate: u32, channels: u16) -> Vec<f32> {
-    let mono = if channels <= 1 {
-        audio
-    } else {
-        downmix_to_mono(audio, channels)
-    };
+type TranscriptionOutcome = (
+    Result<TranscriptionResult, TranscriberError>,
+    TranscriptionWork,
+);
 
-    if mono.is_empty() || sample_rate == TARGET_SAMPLE_RATE || sample_rate == 0 {
-        return mono;
-    }
-
-    let target_len =
-        ((mono.len() as f64) * TARGET_SAMPLE_RATE as f64 / sample_rate as f64).round() as usize;
-    if target_len == 0 {
-        return Vec::new();
-    }
-
-    let step = sample_rate as f64 / TARGET_SAMPLE_RATE as f64;
-    let mut output = Vec::with_capacity(target_len);
-    for i in 0..target_len {
-        let src_pos = i as f64 * step;
-        let idx = src_pos.floor() as usize;
-        if idx >= mono.len() {
-            break;
-        }
-        let frac = (src_pos - idx as f64) as f32;
-        let next = if idx + 1 < mono.len() { idx + 1 } else { idx };
-        let sample = mono[idx] + (mono[next] - mono[idx]) * frac;
-        output.push(sample);
-    }
-
-    output
-}
-
-fn downmix_to_mono(audio: Vec<f32>, channels: u16) -> Vec<f32> {
-    let channels = channels as usize;
-    if channels == 0 {
-        return Vec::new();
-    }
-    let frames = audio.len() / channels;
-    let mut mono = Vec::with_capacity(frames);
-    for frame in 0..frames {
-        let offset = frame * channels;
-        let mut sum = 0.0;
-        for channel in 0..channels {
-            sum += audio[offset + channel];
-        }
-        mono.push(sum / channels as f32);
-    }
-    mono
-}
-
-fn register_hotkey_binding(manager: &mut HotkeyManager, hotkey: Hotkey) {
-    manager.register_with_trigger(hotkey, HotkeyTrigger::Pressed, "ptt");
-    manager.register_with_trigger(hotkey, HotkeyTrigger::Released, "ptt");
-}
-
-fn parse_hotkey_key(name: &str) -> Option<HotkeyKey> {
-    let key = name.trim().to_ascii_lowercase();
-    if key.len() == 1 {
-        let ch = key.chars().next()?;
-        return match ch {
-            'a' => Some(HotkeyKey::A),
-            'b' => Some(HotkeyKey::B),
-            'c' => Some(HotkeyKey::C),
-            'd' => Some(HotkeyKey::D),
-            'e' => Some(HotkeyKey::E),
-            'f' => Some(HotkeyKey::F),
-            'g' => Some(HotkeyKey::G),
-            'h' => Some(HotkeyKey::H),
-            'i' => Some(HotkeyKey::I),
-            'j' => Some(HotkeyKey::J),
-            'k' => Some(HotkeyKey::K),
-            'l' => Some(HotkeyKey::L),
-            'm' => Some(HotkeyKey::M),
-            'n' => Some(HotkeyKey::N),
-            'o' => Some(HotkeyKey::O),
-            'p' => Some(HotkeyKey::P),
-            'q' => Some(HotkeyKey::Q),
-            'r' => Some(HotkeyKey::R),
-            's' => Some(HotkeyKey::S),
-            't' => Some(HotkeyKey::T),
-            'u' => Some(HotkeyKey::U),
-            'v' => Some(HotkeyKey::V),
-            'w' => Some(HotkeyKey::W),
-            'x' => Some(HotkeyKey::X),
-            'y' => Some(HotkeyKey::Y),
-            'z' => Some(HotkeyKey::Z),
-            _ => None,
-        };
-    }
-
-    match key.as_str() {
-        "f1" => Some(HotkeyKey::F1),
-        "f2" => Some(HotkeyKey::F2),
-        "f3" => Some(HotkeyKey::F3),
-        "f4" => Some(HotkeyKey::F4),
-        "f5" => Some(HotkeyKey::F5),
-        "f6" => Some(HotkeyKey::F6),
-        "f7" => Some(HotkeyKey::F7),
-        "f8" => Some(HotkeyKey::F8),
-        "f9" => Some(HotkeyKey::F9),
-        "f10" => Some(HotkeyKey::F10),
-        "f11" => Some(HotkeyKey::F11),
-        "f12" => Some(HotkeyKey::F12),
-        "space" => Some(HotkeyKey::Space),
-        "enter" => Some(HotkeyKey::Enter),
-        "escape" => Some(HotkeyKey::Escape),
-        "tab" => Some(HotkeyKey::Tab),
-        "backspace" => Some(HotkeyKey::Backspace),
-        "left" => Some(HotkeyKey::Left),
-        "right" => Some(HotkeyKey::Right),
-        "up" => Some(HotkeyKey::Up),
-        "down" => Some(HotkeyKey::Down),
-        _ => None,
+fn transcription_language(settings: &AppSettings) -> Option<String> {
+    if settings.auto_language {
+        return None;
     }
+    settings.language.clone()
 }
 
-pub(crate) fn model_id_from_name(name: Option<&str>) -> ModelId {
-    let Some(name) = name else {
-        return ModelId::Base;
-    };
-    match name.trim().to_ascii_lowercase().as_str() {
-        "tiny" => ModelId::Tiny,
-        "base" => ModelId::Base,
-        "small" => ModelId::Small,
-        "medium" => ModelId::Medium,
-        "large" => ModelId::Large,
-        other => ModelId::Custom(other.to_string()),
+fn vocabulary_prompt(settings: &AppSettings) -> Option<String> {
+    let terms = settings
+        .custom_vocabulary
+        .iter()
+        .map(|term| term.trim())
+        .filter(|term| !term.is_empty())
+        .collect::<Vec<_>>();
+    (!terms.is_empty()).then(|| terms.join(", "))
+}
+
+fn device_present(devices: &[AudioDevice], selected: &AudioDevice) -> bool {
+    devices
+        .iter()
+        .any(|device| device.id == selected.id || device.name == selected.name)
+}
+
+fn capture_error_message(err: PttCaptureError) -> String {
+    match err {
+        PttCaptureError::Audio(AudioError::NoInputDevice) => {
+            "no microphone found; connect an input device and try again".to_string()
+        }
+        other => other.to_string(),
     }
 }
 
-pub(crate) fn build_model_status_payload(
-    root: &Path,
-    active: Option<&str>,
-    overrides: &HashMap<String, ModelInstallStatus>,
-) -> ModelStatusPayload {
-    let mut items = Vec::new();
-    let standard = [
-        ModelId::Tiny,
-        ModelId::Base,
-        ModelId::Small,
-        ModelId::Medium,
-        ModelId::Large,
-    ];
-
-    for model_id in standard {
-        let id = model_id.display_name();
-        let filename = format!("ggml-{}.bin", id);
-        let path = root.join(&filename);
-        let mut status = if path.exists() {
-            ModelInstallStatus::Ready
-        } else {
-            ModelInstallStatus::Pending
+fn gain_mode(settings: &AppSettings) -> GainMode {
+    if settings.auto_gain {
+        GainMode::Automatic
+    } else {
+        GainMode::Fixed {
+            gain_db: settings.input_gain_db,
+        }
+    }
+}
+
+fn captured_duration(samples: usize, sample_rate: u32, channels: u16) -> Duration {
+    let samples_per_second = u64::from(sample_rate.max(1)) * u64::from(channels.max(1));
+    Duration::from_millis(samples as u64 * 1000 / samples_per_second)
+}
+
+fn capture_limits(settings: &AppSettings) -> CaptureLimits {
+    CaptureLimits {
+        max_duration: Duration::from_secs(settings.max_recording_seconds.into()),
+        min_duration: Duration::from_millis(settings.min_recording_ms.into()),
+        silence_auto_stop: settings.silence_auto_stop,
+        silence_threshold_dbfs: settings.silence_threshold_dbfs,
+        overflow_policy: OverflowPolicy::StopCapture,
+        chunking: (settings.partial_transcription || settings.auto_language)
+            .then(ChunkConfig::default),
+        ..CaptureLimits::default()
+    }
+}
+
+fn capture_language(
+    settings: &AppSettings,
+    transcriber: &dyn Transcriber,
+    audio: &[f32],
+) -> Option<LanguageDetectedPayload> {
+    if !settings.auto_language || audio.is_empty() {
+        return None;
+    }
+    let window = &audio[..audio.len().min(LANGUAGE_DETECT_SAMPLES)];
+    match transcriber.detect_language(window) {
+        Ok((language, probability)) => {
+            info!("detected language {language} (p={probability:.2})");
+            Some(LanguageDetectedPayload {
+                language,
+                probability,
+            })
+        }
+        Err(err) => {
+            info!("language detection skipped: {err}");
+            None
+        }
+    }
+}
+
+pub(crate) fn settings_transcribe_options(
+    settings: &AppSettings,
+    translate: bool,
+) -> TranscribeOptions {
+    TranscribeOptions::default()
+        .with_language(transcription_language(settings))
+        .with_translate(settings.translate || translate)
+        .with_initial_prompt(vocabulary_prompt(settings))
+        .with_threads(settings.whisper_threads)
+        .with_extra_args(settings.whisper_extra_args.clone())
+}
+
+pub(crate) fn transcribe_audio_file(
+    transcriber: &dyn Transcriber,
+    audio: &[f32],
+    options: &TranscribeOptions,
+    model: Option<String>,
+) -> Result<TranscriptEntry, String> {
+    let result = transcriber
+        .transcribe(audio, options)
+        .map_err(|err| err.to_string())?;
+    let payload = TranscriptionPayload::from(&result);
+    if payload.text.trim().is_empty() {
+        return Err("no speech detected".to_string());
+    }
+    let duration_ms = audio.len() as u64 * 1000 / TARGET_SAMPLE_RATE as u64;
+    let mut entry = TranscriptEntry::new(
+        payload.text,
+        now_ms(),
+        duration_ms,
+        model,
+        OutputMode::UiOnly,
+    )
+    .with_segments(payload.segments);
+    if let Some(metrics) = transcript_metrics(&result.metrics) {
+        entry = entry.with_metrics(metrics);
+    }
+    Ok(entry)
+}
+
+fn transcript_metrics(metrics: &TranscriptionMetrics) -> Option<TranscriptMetrics> {
+    if metrics.model.is_empty() {
+        return None;
+    }
+    Some(TranscriptMetrics {
+        audio_seconds: metrics.audio_seconds,
+        wall_ms: metrics.wall_ms,
+        rtf: metrics.rtf,
+        model: metrics.model.clone(),
+    })
+}
+
+struct PartialWorker {
+    sender: mpsc::Sender<Vec<f32>>,
+    cancelled: Arc<AtomicBool>,
+}
+
+impl PartialWorker {
+    fn spawn(transcriber: Arc<dyn Transcriber>, options: TranscribeOptions) -> Self {
+        let (sender, receiver) = mpsc::channel::<Vec<f32>>();
+        let cancelled = Arc::new(AtomicBool::new(false));
+        let worker_cancelled = Arc::clone(&cancelled);
+        std::thread::spawn(move || {
+            let mut partial = PartialTranscript::default();
+            for audio in receiver {
+                if worker_cancelled.load(Ordering::SeqCst) {
+                    break;
+                }
+                let payload = partial.transcribe(transcriber.as_ref(), &audio, &options);
+                if let Some(payload) = payload {
+                    if !worker_cancelled.load(Ordering::SeqCst) {
+                        emit_app_event(PTT_TRANSCRIPTION_PARTIAL_EVENT, &payload);
+                    }
+                }
+            }
+        });
+        Self { sender, cancelled }
+    }
+
+    fn push(&self, audio: Vec<f32>) {
+        let _ = self.sender.send(audio);
+    }
+}
+
+impl Drop for PartialWorker {
+    fn drop(&mut self) {
+        self.cancelled.store(true, Ordering::SeqCst);
+    }
+}
+
+#[derive(Debug, Default)]
+struct PartialTranscript {
+    text: String,
+    chunks: usize,
+}
+
+impl PartialTranscript {
+    fn transcribe(
+        &mut self,
+        transcriber: &dyn Transcriber,
+        audio: &[f32],
+        options: &TranscribeOptions,
+    ) -> Option<PartialTranscriptionPayload> {
+        match transcriber.transcribe(audio, options) {
+            Ok(result) => {
+                self.text = merge_partial_text(&self.text, &result.text);
+                self.chunks += 1;
+                Some(PartialTranscriptionPayload {
+                    text: self.text.clone(),
+                    chunks: self.chunks,
+                })
+            }
+            Err(err) => {
+                warn!("partial transcription failed: {err}");
+                None
+            }
+        }
+    }
+}
+
+fn seam_word(word: &str) -> String {
+    word.chars()
+        .filter(|ch| ch.is_alphanumeric())
+        .flat_map(char::to_lowercase)
+        .collect()
+}
+
+fn merge_partial_text(accumulated: &str, chunk: &str) -> String {
+    let previous: Vec<&str> = accumulated.split_whitespace().collect();
+    let next: Vec<&str> = chunk.split_whitespace().collect();
+    let longest = PARTIAL_SEAM_WORDS.min(previous.len()).min(next.len());
+    let seam = (1..=longest)
+        .rev()
+        .find(|&count| {
+            previous[previous.len() - count..]
+                .iter()
+                .zip(&next[..count])
+                .all(|(left, right)| seam_word(left) == seam_word(right))
+        })
+        .unwrap_or(0);
+    previous
+        .into_iter()
+        .chain(next.into_iter().skip(seam))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+impl TranscriptionWork {
+    fn duration_ms(&self) -> u64 {
+        self.audio.len() as u64 * 1000 / TARGET_SAMPLE_RATE as u64
+    }
+
+    fn run(&self) -> Result<TranscriptionResult, TranscriberError> {
+        let result = self.transcriber.transcribe(&self.audio, &self.options)?;
+        let result = self.postprocessor.process_result(result);
+        if !result.text.trim().is_empty() {
+            if let Err(err) = deliver_output(&self.output, &result.text) {
+                emit_warning(&err);
+            }
+        }
+        Ok(result)
+    }
+}
+
+fn now_ms() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|elapsed| elapsed.as_millis() as u64)
+        .unwrap_or_default()
+}
+
+fn register_cancel_binding(
+    manager: &mut HotkeyManager,
+    hotkey: Hotkey,
+) -> Result<(), ConflictError> {
+    manager.try_register_with_trigger(hotkey, HotkeyTrigger::Pressed, PTT_CANCEL_ACTION)?;
+    manager.set_enabled(&hotkey, HotkeyTrigger::Pressed, false);
+    Ok(())
+}
+
+fn ptt_bindings(mode: PttMode) -> &'static [(HotkeyTrigger, &'static str)] {
+    match mode {
+        PttMode::HoldToTalk => &PTT_HOLD_BINDINGS,
+        PttMode::Toggle => &PTT_TOGGLE_BINDINGS,
+    }
+}
+
+fn translate_trigger(mode: PttMode) -> HotkeyTrigger {
+    match mode {
+        PttMode::HoldToTalk => HotkeyTrigger::Released,
+        PttMode::Toggle => HotkeyTrigger::Pressed,
+    }
+}
+
+fn register_hotkey_binding(
+    manager: &mut HotkeyManager,
+    hotkey: Hotkey,
+    mode: PttMode,
+    enabled: bool,
+) -> Result<(), ConflictError> {
+    for (trigger, action) in ptt_bindings(mode) {
+        manager.try_register_with_trigger(hotkey, *trigger, *action)?;
+        manager.set_enabled(&hotkey, *trigger, enabled);
+    }
+    if let Some(translate) = translate_hotkey(hotkey) {
+        let trigger = translate_trigger(mode);
+        match manager.try_register_with_trigger(translate, trigger, PTT_TRANSLATE_ACTION) {
+            Ok(()) => {
+                manager.set_enabled(&translate, trigger, enabled);
+            }
+            Err(err) => warn!("translate hotkey '{translate}' unavailable: {err}"),
+        }
+    }
+    Ok(())
+}
+
+fn translate_hotkey(hotkey: Hotkey) -> Option<Hotkey> {
+    if hotkey.modifiers.shift {
+        return None;
+    }
+    Some(Hotkey {
+        modifiers: HotkeyModifiers {
+            shift: true,
+            ..hotkey.modifiers
+        },
+        ..hotkey
+    })
+}
+
+pub(crate) fn model_id_from_name(name: Option<&str>) -> ModelId {
+    name.map_or(ModelId::Base, ModelId::from_name)
+}
+
+pub(crate) fn restore_active_model(
+    root: &Path,
+    persisted: Option<String>,
+) -> Result<Option<String>, String> {
+    let Some(name) = persisted.filter(|name| !name.trim().is_empty()) else {
+        return Ok(None);
+    };
+    let model_id = model_id_from_name(Some(&name));
+    let mut manager = ModelManager::new(root);
+    register_known_models(&mut manager, root);
+    if matches!(model_id, ModelId::Custom(_)) && manager.model_path(&model_id).is_err() {
+        register_custom_model(&mut manager, root, &name);
+    }
+    match manager.model_path(&model_id) {
+        Ok(path) if path.exists() => Ok(Some(name)),
+        _ => Err(format!(
+            "selected model '{name}' is no longer installed; using base instead"
+        )),
+    }
+}
+
+pub(crate) fn delete_model_files(
+    root: &Path,
+    name: &str,
+    active: Option<&str>,
+) -> Result<u64, String> {
+    if name.is_empty() {
+        return Err("model name required".to_string());
+    }
+    if active.is_some_and(|active| active.eq_ignore_ascii_case(name)) {
+        return Err(format!("cannot delete the active model '{name}'"));
+    }
+    let model_id = model_id_from_name(Some(name));
+    let mut manager = ModelManager::new(root);
+    register_known_models(&mut manager, root);
+    if matches!(model_id, ModelId::Custom(_)) && manager.model_path(&model_id).is_err() {
+        register_custom_model(&mut manager, root, name);
+    }
+    manager
+        .delete_model(&model_id)
+        .map_err(|err| err.to_string())
+}
+
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+pub struct ModelVerificationPayload {
+    pub model: String,
+    pub valid: bool,
+    pub size_bytes: u64,
+    pub expected_size_bytes: Option<u64>,
+    pub sha256: String,
+    pub expected_sha256: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+pub struct ModelDiskUsageEntry {
+    pub model: String,
+    pub size_bytes: u64,
+}
+
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+pub struct ModelDiskUsagePayload {
+    pub models: Vec<ModelDiskUsageEntry>,
+    pub temp_bytes: u64,
+    pub total_bytes: u64,
+}
+
+pub(crate) fn model_disk_usage(root: &Path) -> Result<ModelDiskUsagePayload, String> {
+    let mut manager = ModelManager::new(root);
+    register_known_models(&mut manager, root);
+    let usage = manager.disk_usage().map_err(|err| err.to_string())?;
+    Ok(ModelDiskUsagePayload {
+        models: usage
+            .per_model
+            .into_iter()
+            .map(|(id, size_bytes)| ModelDiskUsageEntry {
+                model: id.display_name(),
+                size_bytes,
+            })
+            .collect(),
+        temp_bytes: usage.temp_bytes,
+        total_bytes: usage.total,
+    })
+}
+
+pub(crate) fn import_custom_model(
+    root: &Path,
+    source: &Path,
+    name: &str,
+) -> Result<PathBuf, String> {
+    if name.is_empty() {
+        return Err("model name required".to_string());
+    }
+    let mut manager = ModelManager::new(root);
+    register_known_models(&mut manager, root);
+    manager
+        .import_model(source, model_id_from_name(Some(name)))
+        .map_err(|err| match err {
+            ModelError::MissingFile(path) => format!("model file not found: {path}"),
+            other => other.to_string(),
+        })
+}
+
+pub(crate) fn cleanup_stale_downloads(root: &Path) {
+    match ModelManager::new(root).cleanup_temp_files() {
+        Ok(0) => {}
+        Ok(reclaimed) => info!("removed stale model downloads: {reclaimed} bytes reclaimed"),
+        Err(err) => warn!("failed to clean up stale model downloads: {err}"),
+    }
+}
+
+pub(crate) fn verify_installed_model(
+    root: &Path,
+    name: &str,
+) -> Result<ModelVerificationPayload, String> {
+    if name.is_empty() {
+        return Err("model name required".to_string());
+    }
+    let model_id = model_id_from_name(Some(name));
+    let mut manager = ModelManager::new(root);
+    register_known_models(&mut manager, root);
+    if matches!(model_id, ModelId::Custom(_)) && manager.model_path(&model_id).is_err() {
+        register_custom_model(&mut manager, root, name);
+    }
+    let report = manager.verify(&model_id).map_err(|err| match err {
+        ModelError::MissingFile(_) => format!("model not downloaded: {name}"),
+        other => other.to_string(),
+    })?;
+    Ok(ModelVerificationPayload {
+        model: name.to_string(),
+        valid: report.is_valid(),
+        size_bytes: report.size_bytes,
+        expected_size_bytes: report.expected_size_bytes,
+        sha256: report.sha256,
+        expected_sha256: report.expected_sha256,
+    })
+}
+
+pub(crate) fn build_model_status_payload(
+    root: &Path,
+    active: Option<&str>,
+    overrides: &HashMap<String, ModelInstallStatus>,
+) -> ModelStatusPayload {
+    let mut items = Vec::new();
+    let mut manager = ModelManager::new(root);
+    register_known_models(&mut manager, root);
+    let standard = StandardModel::ALL.map(ModelId::from);
+    let mut manifest = manager
+        .specs()
+        .filter(|spec| matches!(spec.id, ModelId::Custom(_)))
+        .map(|spec| spec.id.clone())
+        .collect::<Vec<_>>();
+    manifest.sort_by_key(ModelId::display_name);
+    let quantized = ModelId::quantized_variants()
+        .into_iter()
+        .filter(|model_id| {
+            let id = model_id.display_name();
+            active == Some(id.as_str())
+                || overrides.contains_key(&id)
+                || manager
+                    .model_path(model_id)
+                    .is_ok_and(|path| path.exists() || path.with_extension("download").exists())
+        });
+
+    for model_id in standard.into_iter().chain(quantized).chain(manifest) {
+        let id = model_id.display_name();
+        let Ok(path) = manager.model_path(&model_id) else {
+            continue;
+        };
+        let mut status = if path.exists() {
+            ModelInstallStatus::Ready
+        } else {
+            ModelInstallStatus::Pending
         };
         let is_active = active.map_or(false, |name| name == id);
         if let Some(override_status) = overrides.get(&id) {
@@ -1088,12 +2566,21 @@ pub(crate) fn build_model_status_payload(
         } else {
             0.0
         };
+        let downloaded_bytes = if status == ModelInstallStatus::Ready {
+            0
+        } else {
+            manager
+                .partial_download_size(&model_id)
+                .ok()
+                .flatten()
+                .unwrap_or(0)
+        };
         items.push(ModelStatusItem {
             id: id.clone(),
             name: id,
             status,
             total_bytes: 0,
-            downloaded_bytes: 0,
+            downloaded_bytes,
             speed_bytes_per_sec: 0,
             eta_seconds: 0,
             progress,
@@ -1101,6 +2588,38 @@ pub(crate) fn build_model_status_payload(
         });
     }
 
+    for installed in manager.scan_installed() {
+        if installed.registered.is_some() {
+            continue;
+        }
+        let Some(id) = installed.filename.strip_suffix(".bin") else {
+            continue;
+        };
+        if items.iter().any(|item| item.id == id) {
+            continue;
+        }
+        let status = overrides
+            .get(id)
+            .cloned()
+            .unwrap_or(ModelInstallStatus::Ready);
+        let progress = if status == ModelInstallStatus::Ready {
+            100.0
+        } else {
+            0.0
+        };
+        items.push(ModelStatusItem {
+            id: id.to_string(),
+            name: id.to_string(),
+            status,
+            total_bytes: 0,
+            downloaded_bytes: 0,
+            speed_bytes_per_sec: 0,
+            eta_seconds: 0,
+            progress,
+            active: active == Some(id),
+        });
+    }
+
     if let Some(active_name) = active {
         if !items.iter().any(|item| item.id == active_name) {
             let filename = format!("{active_name}.bin");
@@ -1132,289 +2651,2672 @@ pub(crate) fn build_model_status_payload(
         }
     }
 
-    let queue_count = items
-        .iter()
-        .filter(|model| {
-            matches!(
-                model.status,
-                ModelInstallStatus::Downloading
-                    | ModelInstallStatus::Queued
-                    | ModelInstallStatus::Pending
-            )
-        })
-        .count();
-
-    ModelStatusPayload {
+    let mut payload = ModelStatusPayload {
         models: items,
         active_model: active.map(|name| name.to_string()),
-        queue_count,
+        queue_count: 0,
+    };
+    payload.refresh_queue_count();
+    payload
+}
+
+pub(crate) const MODEL_MANIFEST_FILENAME: &str = "manifest.json";
+
+pub(crate) fn register_known_models(manager: &mut ModelManager, root: &Path) -> usize {
+    register_standard_models(manager);
+    let manifest = root.join(MODEL_MANIFEST_FILENAME);
+    if !manifest.exists() {
+        return 0;
+    }
+    match manager.load_manifest(&manifest) {
+        Ok(count) => count,
+        Err(err) => {
+            warn!(
+                "failed to load model manifest {}: {err}",
+                manifest.display()
+            );
+            0
+        }
+    }
+}
+
+pub(crate) fn log_manifest_models(root: &Path) {
+    let mut manager = ModelManager::new(root);
+    let count = register_known_models(&mut manager, root);
+    if count > 0 {
+        info!("loaded {count} model(s) from {MODEL_MANIFEST_FILENAME}");
+    }
+}
+
+pub(crate) fn register_standard_models(manager: &mut ModelManager) {
+    let mirrors = std::env::var("OPENWHISPERAI_MODEL_MIRRORS").ok();
+    let standard = StandardModel::ALL.map(ModelId::from);
+    for model_id in standard.into_iter().chain(ModelId::quantized_variants()) {
+        let Some(filename) = model_id.ggml_filename() else {
+            continue;
+        };
+        let mut spec = ModelSpec::new(model_id, filename.clone())
+            .with_download_urls(model_download_urls(&filename, mirrors.as_deref()));
+        if let Some((size_bytes, sha256)) = standard_model_checksum(&filename) {
+            spec = spec.with_size(size_bytes).with_sha256(sha256);
+        }
+        manager.register_model(spec);
+    }
+}
+
+pub(crate) fn register_custom_model(manager: &mut ModelManager, root: &Path, name: &str) {
+    let filename = format!("{name}.bin");
+    let spec = ModelSpec::new(ModelId::Custom(name.to_string()), filename.clone())
+        .with_download_url(file_url(root.join(&filename)));
+    manager.register_model(spec);
+}
+
+fn file_url(path: PathBuf) -> String {
+    format!("file://{}", path.display())
+}
+
+fn model_download_urls(filename: &str, mirrors: Option<&str>) -> Vec<String> {
+    let mut urls = vec![format!(
+        "https://huggingface.co/ggerganov/whisper.cpp/resolve/main/{filename}"
+    )];
+    urls.extend(
+        mirrors
+            .unwrap_or_default()
+            .split(',')
+            .map(str::trim)
+            .filter(|mirror| !mirror.is_empty())
+            .map(|mirror| format!("{}/{filename}", mirror.trim_end_matches('/'))),
+    );
+    urls
+}
+
+fn level_payload(reading: LevelReading) -> PttLevel {
+    PttLevel {
+        rms: reading.rms,
+        peak: reading.peak,
+        smoothed_rms: reading.smoothed_rms,
+        peak_hold: reading.peak_hold,
+    }
+}
+
+#[derive(Debug, Default)]
+struct LevelWindow {
+    last_emit: Option<Instant>,
+    readings: u32,
+    rms_sum: f32,
+    peak: f32,
+    peak_hold: f32,
+    smoothed_rms: f32,
+}
+
+impl LevelWindow {
+    fn push(&mut self, reading: LevelReading) {
+        self.readings += 1;
+        self.rms_sum += reading.rms;
+        self.peak = self.peak.max(reading.peak);
+        self.peak_hold = self.peak_hold.max(reading.peak_hold);
+        self.smoothed_rms = reading.smoothed_rms;
+    }
+
+    fn flush(&mut self, now: Instant, interval: Duration) -> Option<PttLevel> {
+        if self.readings == 0 {
+            return None;
+        }
+        if let Some(last) = self.last_emit {
+            if now.saturating_duration_since(last) < interval {
+                return None;
+            }
+        }
+        let level = PttLevel {
+            rms: self.rms_sum / self.readings as f32,
+            peak: self.peak,
+            smoothed_rms: self.smoothed_rms,
+            peak_hold: self.peak_hold,
+        };
+        *self = Self {
+            last_emit: Some(now),
+            ..Self::default()
+        };
+        Some(level)
     }
 }
 
-pub(crate) fn register_standard_models(manager: &mut ModelManager) {
-    let standard = [
-        ModelId::Tiny,
-        ModelId::Base,
-        ModelId::Small,
-        ModelId::Medium,
-        ModelId::Large,
-    ];
-    for model_id in standard {
-        let filename = format!("ggml-{}.bin", model_id.display_name());
-        let spec = ModelSpec::new(model_id, filename.clone())
-            .with_download_url(model_download_url(&filename));
-        manager.register_model(spec);
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::feedback::{Notifier, COMPLETE_NOTIFICATION_TITLE};
+    use core_input::{
+        AudioDevice, AudioError, AudioStream, HotkeyEvent, SampleFormat, StreamFormat,
+        StreamRequest, TonePlayer,
+    };
+    use std::sync::{
+        atomic::{AtomicBool, Ordering},
+        Mutex,
+    };
+    use std::time::Duration;
+
+    #[test]
+    fn hotkey_payload_parses_letters() {
+        let payload = PttHotkeyPayload {
+            key: "k".to_string(),
+            modifiers: PttHotkeyModifiers {
+                ctrl: true,
+                alt: false,
+                shift: false,
+                meta: false,
+            },
+        };
+        let hotkey = payload.to_hotkey().expect("hotkey");
+        assert_eq!(hotkey.key, HotkeyKey::K);
+        assert!(hotkey.modifiers.ctrl);
+    }
+
+    #[test]
+    fn set_hotkey_stores_the_canonical_key_name() {
+        let models = Arc::new(Mutex::new(crate::state::ModelStore::new()));
+        let mut controller =
+            PttController::with_backend(MockAudioBackend::new(), std::env::temp_dir(), models);
+        let payload = PttHotkeyPayload {
+            key: " Numpad0 ".to_string(),
+            modifiers: PttHotkeyModifiers {
+                ctrl: true,
+                alt: false,
+                shift: false,
+                meta: false,
+            },
+        };
+
+        let applied = controller.set_hotkey(payload, false).expect("set hotkey");
+        assert_eq!(applied.key, "numpad0");
+        assert_eq!(controller.hotkey.key, HotkeyKey::Numpad0);
+        assert_eq!(controller.settings.hotkey.key, "numpad0");
+        assert!(controller.settings.hotkey.ctrl);
+    }
+
+    #[test]
+    fn hotkey_payload_accepts_bare_modifier_keys() {
+        let payload = PttHotkeyPayload {
+            key: "ctrl_right".to_string(),
+            modifiers: PttHotkeyModifiers {
+                ctrl: true,
+                alt: false,
+                shift: false,
+                meta: false,
+            },
+        };
+        let hotkey = payload.to_hotkey().expect("hotkey");
+        assert_eq!(hotkey.key, HotkeyKey::CtrlRight);
+        assert_eq!(hotkey.modifiers, HotkeyModifiers::none());
+        assert_eq!(PttHotkeyPayload::from(hotkey).key, "ctrl_right");
+    }
+
+    #[test]
+    fn disabled_ptt_hotkey_stays_muted_across_changes() {
+        let models = Arc::new(Mutex::new(crate::state::ModelStore::new()));
+        let mut controller =
+            PttController::with_backend(MockAudioBackend::new(), std::env::temp_dir(), models);
+        controller.set_hotkey_enabled(false);
+        controller
+            .set_hotkey(
+                PttHotkeyPayload {
+                    key: "f8".to_string(),
+                    modifiers: PttHotkeyModifiers {
+                        ctrl: false,
+                        alt: false,
+                        shift: false,
+                        meta: false,
+                    },
+                },
+                false,
+            )
+            .expect("set hotkey");
+
+        let tapped = HotkeyEvent {
+            key: HotkeyKey::F8,
+            modifiers: HotkeyModifiers::none(),
+            state: HotkeyState::Released,
+        };
+        let bindings = controller.hotkey_manager.lock().expect("lock").bindings();
+        assert_eq!(bindings.len(), 4);
+        assert!(bindings.iter().all(|(_, binding)| !binding.enabled));
+        assert_eq!(
+            controller
+                .hotkey_manager
+                .lock()
+                .expect("lock")
+                .resolve(&tapped),
+            None
+        );
+
+        controller.set_hotkey_enabled(true);
+        assert_eq!(
+            controller
+                .hotkey_manager
+                .lock()
+                .expect("lock")
+                .resolve(&tapped),
+            Some(PTT_TOGGLE_ACTION)
+        );
+    }
+
+    #[test]
+    fn set_hotkey_reports_conflicts_unless_replacing() {
+        let models = Arc::new(Mutex::new(crate::state::ModelStore::new()));
+        let mut controller =
+            PttController::with_backend(MockAudioBackend::new(), std::env::temp_dir(), models);
+        let taken = Hotkey {
+            key: HotkeyKey::F8,
+            modifiers: HotkeyModifiers::none(),
+        };
+        controller
+            .hotkey_manager
+            .lock()
+            .expect("lock")
+            .register_with_trigger(taken, HotkeyTrigger::Released, "toggle-overlay");
+        let payload = PttHotkeyPayload::from(taken);
+
+        let err = controller
+            .set_hotkey(payload.clone(), false)
+            .expect_err("conflict");
+        assert_eq!(err, "hotkey 'f8' is already bound to 'toggle-overlay'");
+        assert_ne!(controller.hotkey, taken);
+
+        controller.set_hotkey(payload, true).expect("replace");
+        assert_eq!(controller.hotkey, taken);
+        let manager = controller.hotkey_manager.lock().expect("lock");
+        assert_eq!(
+            manager.would_conflict(&taken, HotkeyTrigger::Released),
+            Some(PTT_TOGGLE_ACTION)
+        );
+    }
+
+    #[test]
+    fn wayland_sessions_prefer_the_shortcuts_portal() {
+        let unprobed = || panic!("not probed");
+        assert_eq!(
+            select_hotkey_backend(false, DisplaySession::Desktop, unprobed, unprobed),
+            HotkeyBackend::Rdev
+        );
+        assert_eq!(
+            select_hotkey_backend(true, DisplaySession::Wayland, unprobed, unprobed),
+            HotkeyBackend::Disabled
+        );
+        assert_eq!(
+            select_hotkey_backend(false, DisplaySession::Wayland, || true, unprobed),
+            HotkeyBackend::Portal
+        );
+        assert_eq!(
+            select_hotkey_backend(false, DisplaySession::Wayland, || false, unprobed),
+            HotkeyBackend::Disabled
+        );
+    }
+
+    #[test]
+    fn model_status_reports_partial_downloads() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let root = dir.path();
+        std::fs::write(root.join("ggml-base.download"), [0u8; 42]).expect("write partial");
+
+        let payload = build_model_status_payload(root, None, &HashMap::new());
+
+        let base = payload
+            .models
+            .iter()
+            .find(|item| item.id == "base")
+            .expect("base model");
+        assert_eq!(base.status, ModelInstallStatus::Pending);
+        assert_eq!(base.downloaded_bytes, 42);
+    }
+
+    #[test]
+    fn deleting_models_frees_files_and_protects_the_active_model() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let root = dir.path();
+        std::fs::write(root.join("ggml-tiny.bin"), [0u8; 16]).expect("write model");
+
+        let active = delete_model_files(root, "tiny", Some("tiny"));
+        let freed = delete_model_files(root, "tiny", Some("base"));
+        let missing = delete_model_files(root, "small", Some("base"));
+        let tiny_exists = root.join("ggml-tiny.bin").exists();
+
+        assert!(active.is_err());
+        assert_eq!(freed, Ok(16));
+        assert_eq!(missing, Ok(0));
+        assert!(!tiny_exists);
+    }
+
+    #[test]
+    fn restoring_active_model_requires_installed_file() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let root = dir.path();
+        std::fs::write(root.join("ggml-small.bin"), [0u8; 16]).expect("write model");
+
+        let installed = restore_active_model(root, Some("small".to_string()));
+        let missing = restore_active_model(root, Some("medium".to_string()));
+        let unset = restore_active_model(root, None);
+
+        assert_eq!(installed, Ok(Some("small".to_string())));
+        assert_eq!(
+            missing,
+            Err("selected model 'medium' is no longer installed; using base instead".to_string())
+        );
+        assert_eq!(unset, Ok(None));
+    }
+
+    #[test]
+    fn verifying_a_truncated_standard_model_reports_mismatch() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let root = dir.path();
+        std::fs::write(root.join("ggml-tiny.bin"), [0u8; 32]).expect("write model");
+
+        let tiny = verify_installed_model(root, "tiny");
+        let missing = verify_installed_model(root, "base");
+
+        let tiny = tiny.expect("verify tiny");
+        assert!(!tiny.valid);
+        assert_eq!(tiny.size_bytes, 32);
+        assert_eq!(
+            tiny.expected_size_bytes,
+            standard_model_checksum("ggml-tiny.bin").map(|(size_bytes, _)| size_bytes)
+        );
+        assert!(missing.is_err());
+    }
+
+    #[test]
+    fn model_status_lists_installed_quantized_variants() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let root = dir.path();
+        std::fs::write(root.join("ggml-base.en-q5_1.bin"), [0u8; 4]).expect("write model");
+
+        let payload = build_model_status_payload(root, Some("small-q8_0"), &HashMap::new());
+
+        let ids = payload
+            .models
+            .iter()
+            .map(|item| item.id.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            ids,
+            [
+                "tiny",
+                "base",
+                "small",
+                "medium",
+                "large",
+                "base.en-q5_1",
+                "small-q8_0"
+            ]
+        );
+        assert_eq!(payload.models[5].status, ModelInstallStatus::Ready);
+        assert_eq!(payload.models[6].status, ModelInstallStatus::Pending);
+        assert!(payload.models[6].active);
+    }
+
+    #[test]
+    fn model_disk_usage_reports_models_by_name() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let root = dir.path();
+        std::fs::write(root.join("ggml-tiny.bin"), vec![0u8; 32]).expect("write model");
+        std::fs::write(root.join("ggml-base.download"), vec![0u8; 4]).expect("write partial");
+
+        let usage = model_disk_usage(root);
+
+        let usage = usage.expect("disk usage");
+        assert_eq!(
+            usage.models,
+            vec![ModelDiskUsageEntry {
+                model: "tiny".to_string(),
+                size_bytes: 32,
+            }]
+        );
+        assert_eq!(usage.temp_bytes, 4);
+        assert_eq!(usage.total_bytes, 36);
+    }
+
+    #[test]
+    fn imported_custom_model_is_listed_as_ready() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let root = dir.path();
+        let source_dir = tempfile::tempdir().expect("tempdir");
+        let source = source_dir.path().join("dialect-src.bin");
+        std::fs::write(&source, b"lmggweights").expect("write source");
+
+        let imported = import_custom_model(root, &source, "dialect");
+        let duplicate = import_custom_model(root, &source, "dialect");
+        let payload = build_model_status_payload(root, None, &HashMap::new());
+
+        assert!(imported.expect("import").ends_with("dialect.bin"));
+        assert_eq!(
+            duplicate.expect_err("duplicate rejected"),
+            "a model named 'dialect' already exists"
+        );
+        let dialect = payload
+            .models
+            .iter()
+            .find(|item| item.id == "dialect")
+            .expect("imported model listed");
+        assert_eq!(dialect.status, ModelInstallStatus::Ready);
+    }
+
+    thread_local! {
+        static INIT_CALLS: std::cell::Cell<usize> = const { std::cell::Cell::new(0) };
+    }
+
+    struct CountingBindings;
+
+    impl WhisperBindings for CountingBindings {
+        type Context = ();
+
+        fn init_from_file(_path: &Path) -> Result<Self::Context, BindingError> {
+            INIT_CALLS.with(|calls| calls.set(calls.get() + 1));
+            Ok(())
+        }
+
+        fn transcribe(_context: &Self::Context, _audio: &[f32]) -> Result<String, BindingError> {
+            Ok("warm".to_string())
+        }
+    }
+
+    fn counting_transcriber(name: &str) -> (tempfile::TempDir, LocalTranscriber<CountingBindings>) {
+        let root = tempfile::tempdir().expect("tempdir");
+        std::fs::write(root.path().join(format!("{name}.bin")), b"lmgg").expect("write model");
+        let transcriber = LocalTranscriber::with_bindings(
+            root.path().to_path_buf(),
+            ModelId::Custom(name.to_string()),
+        );
+        (root, transcriber)
+    }
+
+    #[test]
+    fn local_transcriber_reuses_context_across_transcriptions() {
+        let (root, transcriber) = counting_transcriber("warm");
+        let options = TranscribeOptions::default();
+        for _ in 0..3 {
+            let result = transcriber
+                .transcribe(&[0.0; 16], &options)
+                .expect("transcribe");
+            assert_eq!(result.text, "warm");
+            assert_eq!(result.metrics.model, "warm");
+            assert_eq!(result.metrics.audio_seconds, 0.001);
+        }
+        assert_eq!(INIT_CALLS.with(|calls| calls.get()), 1);
+
+        let model = std::fs::File::options()
+            .write(true)
+            .open(root.path().join("warm.bin"))
+            .expect("open model");
+        model
+            .set_modified(SystemTime::now() + Duration::from_secs(60))
+            .expect("touch model");
+        transcriber
+            .transcribe(&[0.0; 16], &options)
+            .expect("transcribe");
+
+        assert_eq!(INIT_CALLS.with(|calls| calls.get()), 2);
+    }
+
+    static SLOW_STARTED: AtomicBool = AtomicBool::new(false);
+    static SLOW_RELEASED: AtomicBool = AtomicBool::new(false);
+    static SLOW_FINISHED: AtomicBool = AtomicBool::new(false);
+
+    struct SlowPartialBindings;
+
+    impl WhisperBindings for SlowPartialBindings {
+        type Context = ();
+
+        fn init_from_file(_path: &Path) -> Result<Self::Context, BindingError> {
+            Ok(())
+        }
+
+        fn transcribe(_context: &Self::Context, audio: &[f32]) -> Result<String, BindingError> {
+            if audio.first() != Some(&1.0) {
+                return Ok("final".to_string());
+            }
+            SLOW_STARTED.store(true, Ordering::SeqCst);
+            let deadline = Instant::now() + Duration::from_secs(5);
+            while !SLOW_RELEASED.load(Ordering::SeqCst) && Instant::now() < deadline {
+                std::thread::sleep(Duration::from_millis(1));
+            }
+            SLOW_FINISHED.store(true, Ordering::SeqCst);
+            Ok("partial".to_string())
+        }
+    }
+
+    #[test]
+    fn final_transcription_does_not_wait_for_an_in_flight_partial() {
+        let root = tempfile::tempdir().expect("tempdir");
+        std::fs::write(root.path().join("slow.bin"), b"lmgg").expect("write model");
+        let transcriber = Arc::new(LocalTranscriber::<SlowPartialBindings>::with_bindings(
+            root.path().to_path_buf(),
+            ModelId::Custom("slow".to_string()),
+        ));
+        let partial = {
+            let transcriber = Arc::clone(&transcriber);
+            std::thread::spawn(move || {
+                transcriber.transcribe(&[1.0; 16], &TranscribeOptions::default().with_partial(true))
+            })
+        };
+        while !SLOW_STARTED.load(Ordering::SeqCst) {
+            std::thread::sleep(Duration::from_millis(1));
+        }
+
+        let result = transcriber
+            .transcribe(&[0.0; 16], &TranscribeOptions::default())
+            .expect("final transcription");
+        let partial_finished_first = SLOW_FINISHED.load(Ordering::SeqCst);
+        SLOW_RELEASED.store(true, Ordering::SeqCst);
+        let partial = partial.join().expect("partial thread").expect("partial");
+
+        assert_eq!(result.text, "final");
+        assert_eq!(partial.text, "partial");
+        assert!(!partial_finished_first);
+    }
+
+    struct ChunkRecorder {
+        boundaries: Mutex<Vec<(usize, usize)>>,
+    }
+
+    impl Transcriber for ChunkRecorder {
+        fn transcribe(
+            &self,
+            audio: &[f32],
+            options: &TranscribeOptions,
+        ) -> Result<TranscriptionResult, TranscriberError> {
+            assert!(options.partial);
+            let mut words: Vec<usize> = audio.iter().map(|sample| *sample as usize).collect();
+            words.dedup();
+            if let (Some(first), Some(last)) = (words.first(), words.last()) {
+                self.boundaries.lock().unwrap().push((*first, *last));
+            }
+            let text = words
+                .iter()
+                .map(|word| format!("w{word}"))
+                .collect::<Vec<_>>()
+                .join(" ");
+            Ok(TranscriptionResult::from_text(text))
+        }
+    }
+
+    #[test]
+    fn partial_transcripts_do_not_duplicate_words_at_chunk_seams() {
+        let audio: Vec<f32> = (0..12)
+            .flat_map(|word| std::iter::repeat_n(word as f32, 100))
+            .collect();
+        let recorder = ChunkRecorder {
+            boundaries: Mutex::new(Vec::new()),
+        };
+        let options = TranscribeOptions::default().with_partial(true);
+        let mut partial = PartialTranscript::default();
+        let mut payloads = Vec::new();
+        for index in 0..3 {
+            let start = (index * 400usize).saturating_sub(100);
+            let chunk = &audio[start..(index + 1) * 400];
+            payloads.push(
+                partial
+                    .transcribe(&recorder, chunk, &options)
+                    .expect("partial"),
+            );
+        }
+
+        assert_eq!(
+            *recorder.boundaries.lock().unwrap(),
+            vec![(0, 3), (3, 7), (7, 11)]
+        );
+        assert_eq!(payloads[0].text, "w0 w1 w2 w3");
+        assert_eq!(payloads[1].text, "w0 w1 w2 w3 w4 w5 w6 w7");
+        assert_eq!(
+            payloads[2],
+            PartialTranscriptionPayload {
+                text: "w0 w1 w2 w3 w4 w5 w6 w7 w8 w9 w10 w11".to_string(),
+                chunks: 3,
+            }
+        );
+    }
+
+    #[test]
+    fn partial_seams_ignore_case_and_punctuation() {
+        assert_eq!(
+            merge_partial_text("Hello there, world.", "World how are you"),
+            "Hello there, world. how are you"
+        );
+        assert_eq!(merge_partial_text("", "first words"), "first words");
+        assert_eq!(
+            merge_partial_text("no overlap", "at all"),
+            "no overlap at all"
+        );
+    }
+
+    #[test]
+    fn binding_timeout_is_reported_to_the_user() {
+        assert_eq!(
+            binding_error_message(BindingError::Timeout { millis: 120_000 }),
+            "transcription timed out after 120000ms and was cancelled"
+        );
+    }
+
+    #[test]
+    fn binding_failures_surface_whisper_stderr() {
+        assert_eq!(
+            binding_error_message(BindingError::ModelLoadFailed {
+                stderr: "invalid model data (bad magic)".to_string(),
+            }),
+            "model could not be loaded; try downloading it again (invalid model data (bad magic))"
+        );
+        assert_eq!(
+            binding_error_message(BindingError::ProcessFailed {
+                status: "signal: 11 (SIGSEGV)".to_string(),
+                stderr: "ggml_cuda_init: failed".to_string(),
+            }),
+            "whisper.cpp failed (signal: 11 (SIGSEGV)): ggml_cuda_init: failed"
+        );
+    }
+
+    #[test]
+    fn local_transcriber_warm_up_loads_context_once() {
+        let (_root, transcriber) = counting_transcriber("warmup");
+        transcriber.warm_up();
+        transcriber
+            .transcribe(&[0.0; 16], &TranscribeOptions::default())
+            .expect("transcribe");
+
+        assert_eq!(INIT_CALLS.with(|calls| calls.get()), 1);
+    }
+
+    #[test]
+    fn manifest_models_are_listed_and_resolved() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let root = dir.path();
+        std::fs::write(
+            root.join(MODEL_MANIFEST_FILENAME),
+            r#"[{"id": "internal", "filename": "internal-v2.bin", "url": "https://models.invalid/internal-v2.bin"}]"#,
+        )
+        .expect("write manifest");
+
+        let payload = build_model_status_payload(root, None, &HashMap::new());
+        let mut manager = ModelManager::new(root);
+        let count = register_known_models(&mut manager, root);
+
+        assert_eq!(count, 1);
+        let internal = payload
+            .models
+            .iter()
+            .find(|item| item.id == "internal")
+            .expect("manifest model listed");
+        assert_eq!(internal.status, ModelInstallStatus::Pending);
+        assert!(manager
+            .model_path(&model_id_from_name(Some("internal")))
+            .expect("manifest model registered")
+            .ends_with("internal-v2.bin"));
+    }
+
+    #[test]
+    fn model_status_lists_unmanaged_ggml_files() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let root = dir.path();
+        std::fs::write(root.join("finetune.bin"), b"lmgg0000").expect("write model");
+        std::fs::write(root.join("junk.bin"), b"junk").expect("write junk");
+
+        let payload = build_model_status_payload(root, Some("finetune"), &HashMap::new());
+
+        let finetune = payload
+            .models
+            .iter()
+            .find(|item| item.id == "finetune")
+            .expect("unmanaged model listed");
+        assert_eq!(finetune.status, ModelInstallStatus::Ready);
+        assert!(finetune.active);
+        assert_eq!(
+            payload
+                .models
+                .iter()
+                .filter(|item| item.id == "finetune")
+                .count(),
+            1
+        );
+        assert!(payload.models.iter().all(|item| item.id != "junk"));
+    }
+
+    #[test]
+    fn quantized_models_register_hugging_face_urls() {
+        let mut manager = ModelManager::new(std::env::temp_dir());
+        register_standard_models(&mut manager);
+        for quant in transcribe_engine::StandardModel::Base.quantizations() {
+            let model_id = model_id_from_name(Some(&format!("base.en-{}", quant.name())));
+            let path = manager.model_path(&model_id).expect("registered variant");
+            let filename = format!("ggml-base.en-{}.bin", quant.name());
+            assert!(path.ends_with(&filename));
+            assert_eq!(
+                model_download_urls(&filename, None)[0],
+                format!("https://huggingface.co/ggerganov/whisper.cpp/resolve/main/{filename}")
+            );
+        }
+        let unpublished = model_id_from_name(Some("large-q5_0"));
+        assert!(manager.model_path(&unpublished).is_err());
+    }
+
+    #[test]
+    fn model_mirrors_follow_the_primary_url() {
+        assert_eq!(
+            model_download_urls("ggml-base.bin", None),
+            vec!["https://huggingface.co/ggerganov/whisper.cpp/resolve/main/ggml-base.bin"]
+        );
+        assert_eq!(
+            model_download_urls(
+                "ggml-base.bin",
+                Some(" https://mirror.example/models/ , ,https://cdn.example")
+            )[1..],
+            [
+                "https://mirror.example/models/ggml-base.bin",
+                "https://cdn.example/ggml-base.bin",
+            ]
+        );
+    }
+
+    #[test]
+    fn consume_hotkey_setting_selects_grab_mode() {
+        let mut settings = AppSettings::default();
+        assert_eq!(listener_mode(&settings), ListenerMode::Listen);
+        settings.consume_hotkey = true;
+        assert_eq!(listener_mode(&settings), ListenerMode::Grab);
+    }
+
+    #[test]
+    fn consume_hotkey_change_requires_restart_once_listener_runs() {
+        let mut settings = AppSettings::default();
+        assert!(!listener_restart_required(None, &settings));
+        assert!(!listener_restart_required(
+            Some(ListenerMode::Listen),
+            &settings
+        ));
+        settings.consume_hotkey = true;
+        assert!(!listener_restart_required(None, &settings));
+        assert!(listener_restart_required(
+            Some(ListenerMode::Listen),
+            &settings
+        ));
+        assert!(!listener_restart_required(
+            Some(ListenerMode::Grab),
+            &settings
+        ));
+    }
+
+    #[test]
+    fn headless_sessions_fall_back_to_evdev() {
+        let unprobed = || panic!("not probed");
+        assert_eq!(
+            select_hotkey_backend(false, DisplaySession::Headless, unprobed, || true),
+            HotkeyBackend::Evdev
+        );
+        assert_eq!(
+            select_hotkey_backend(false, DisplaySession::Headless, unprobed, || false),
+            HotkeyBackend::Disabled
+        );
+    }
+
+    #[test]
+    fn hotkey_capture_requires_the_global_listener() {
+        let models = Arc::new(Mutex::new(crate::state::ModelStore::new()));
+        let mut controller =
+            PttController::with_backend(MockAudioBackend::new(), std::env::temp_dir(), models);
+
+        let err = controller
+            .begin_hotkey_capture(HOTKEY_CAPTURE_TIMEOUT)
+            .expect_err("no listener");
+        assert!(err.contains("not running"));
+    }
+
+    #[test]
+    fn hotkey_payload_rejects_unknown_key() {
+        let payload = PttHotkeyPayload {
+            key: "unknown".to_string(),
+            modifiers: PttHotkeyModifiers {
+                ctrl: false,
+                alt: false,
+                shift: false,
+                meta: false,
+            },
+        };
+        let result = payload.to_hotkey();
+        assert_eq!(result, Err("unknown hotkey key 'unknown'".to_string()));
+    }
+
+    type SampleCallback = Box<dyn FnMut(&[f32]) + Send>;
+    type ErrorCallback = Box<dyn FnMut(AudioError) + Send>;
+
+    #[derive(Clone)]
+    struct MockStreamController {
+        running: Arc<AtomicBool>,
+        callback: Arc<Mutex<Option<SampleCallback>>>,
+        on_error: Arc<Mutex<Option<ErrorCallback>>>,
+    }
+
+    impl MockStreamController {
+        fn fail(&self, err: AudioError) {
+            if let Ok(mut on_error) = self.on_error.lock() {
+                if let Some(handler) = on_error.as_mut() {
+                    handler(err);
+                }
+            }
+        }
+
+        fn push_samples(&self, samples: &[f32]) {
+            if !self.running.load(Ordering::SeqCst) {
+                return;
+            }
+
+            if let Ok(mut callback) = self.callback.lock() {
+                if let Some(handler) = callback.as_mut() {
+                    handler(samples);
+                }
+            }
+        }
+    }
+
+    struct MockStream {
+        controller: MockStreamController,
+        format: StreamFormat,
+    }
+
+    impl AudioStream for MockStream {
+        fn start(&self) -> Result<(), AudioError> {
+            self.controller.running.store(true, Ordering::SeqCst);
+            Ok(())
+        }
+
+        fn stop(&self) -> Result<(), AudioError> {
+            self.controller.running.store(false, Ordering::SeqCst);
+            Ok(())
+        }
+
+        fn format(&self) -> StreamFormat {
+            self.format
+        }
+    }
+
+    #[derive(Clone)]
+    struct MockAudioBackend {
+        devices: Arc<Mutex<Vec<AudioDevice>>>,
+        controller: Arc<Mutex<Option<MockStreamController>>>,
+    }
+
+    impl MockAudioBackend {
+        fn new() -> Self {
+            Self {
+                devices: Arc::new(Mutex::new(vec![AudioDevice {
+                    id: "0:Mock".to_string(),
+                    name: "Mock".to_string(),
+                    sample_rate: 44_100,
+                    channels: 2,
+                    sample_format: SampleFormat::F32,
+                }])),
+                controller: Arc::new(Mutex::new(None)),
+            }
+        }
+    }
+
+    impl AudioBackend for MockAudioBackend {
+        type Stream = MockStream;
+
+        fn list_input_devices(&self) -> Result<Vec<AudioDevice>, AudioError> {
+            Ok(self.devices.lock().expect("lock").clone())
+        }
+
+        fn default_input_device(&self) -> Result<Option<AudioDevice>, AudioError> {
+            Ok(self.devices.lock().expect("lock").first().cloned())
+        }
+
+        fn build_input_stream(
+            &self,
+            device: &AudioDevice,
+            _request: &StreamRequest,
+            on_samples: Box<dyn FnMut(&[f32]) + Send>,
+            on_error: ErrorCallback,
+        ) -> Result<Self::Stream, AudioError> {
+            let controller = MockStreamController {
+                running: Arc::new(AtomicBool::new(false)),
+                callback: Arc::new(Mutex::new(Some(on_samples))),
+                on_error: Arc::new(Mutex::new(Some(on_error))),
+            };
+
+            *self.controller.lock().expect("lock") = Some(controller.clone());
+            Ok(MockStream {
+                controller,
+                format: StreamFormat::from(device),
+            })
+        }
+    }
+
+    struct MockTranscriber;
+
+    impl Transcriber for MockTranscriber {
+        fn transcribe(
+            &self,
+            _audio: &[f32],
+            _options: &TranscribeOptions,
+        ) -> Result<TranscriptionResult, TranscriberError> {
+            Ok(TranscriptionResult::from_text("hello world"))
+        }
+    }
+
+    fn finish_transcription<B: AudioBackend>(controller: &mut PttController<B>) {
+        let deadline = Instant::now() + Duration::from_secs(5);
+        while controller.transcription_pending() {
+            assert!(Instant::now() < deadline, "transcription did not finish");
+            controller.poll_transcription();
+            std::thread::sleep(Duration::from_millis(5));
+        }
+    }
+
+    #[test]
+    fn ptt_transcribes_and_injects_on_release() {
+        let backend = MockAudioBackend::new();
+        let controller_handle = backend.controller.clone();
+        let models = Arc::new(Mutex::new(crate::state::ModelStore::new()));
+        let mut controller = PttController::with_backend(backend, std::env::temp_dir(), models);
+
+        let settings = AppSettings {
+            language: Some("de".to_string()),
+            output_mode: OutputMode::Paste,
+            restore_clipboard: false,
+            ..AppSettings::default()
+        };
+        controller
+            .arm(settings, Some("base".to_string()))
+            .expect("arm");
+        controller.transcriber = Arc::new(MockTranscriber);
+
+        let event_pressed = HotkeyActionEvent {
+            action: "ptt".to_string(),
+            hotkey: Hotkey {
+                key: HotkeyKey::F9,
+                modifiers: HotkeyModifiers::none(),
+            },
+            state: HotkeyState::Pressed,
+        };
+        let event_released = HotkeyActionEvent {
+            action: "ptt".to_string(),
+            hotkey: Hotkey {
+                key: HotkeyKey::F9,
+                modifiers: HotkeyModifiers::none(),
+            },
+            state: HotkeyState::Released,
+        };
+
+        controller
+            .handle_hotkey_action(&event_pressed)
+            .expect("pressed");
+
+        let stream_controller = controller_handle
+            .lock()
+            .expect("lock")
+            .clone()
+            .expect("controller ready");
+        stream_controller.push_samples(&vec![0.1; 44_100]);
+
+        let work = controller
+            .handle_hotkey_action(&event_released)
+            .expect("released")
+            .expect("work");
+        assert_eq!(work.options.language.as_deref(), Some("de"));
+        assert!(!work.options.translate);
+        assert_eq!(work.output_mode, OutputMode::Paste);
+        assert!(matches!(
+            work.output,
+            OutputStrategy::Paste {
+                restore: inject::RestorePolicy::Never,
+                ..
+            }
+        ));
+        let result = work.run().expect("transcribe");
+        assert_eq!(result.text, "hello world");
+    }
+
+    #[test]
+    fn tapping_ptt_toggles_capture() {
+        let backend = MockAudioBackend::new();
+        let controller_handle = backend.controller.clone();
+        let models = Arc::new(Mutex::new(crate::state::ModelStore::new()));
+        let mut controller = PttController::with_backend(backend, std::env::temp_dir(), models);
+        controller
+            .arm(AppSettings::default(), Some("base".to_string()))
+            .expect("arm");
+        controller.transcriber = Arc::new(MockTranscriber);
+
+        let tap = HotkeyActionEvent {
+            action: PTT_TOGGLE_ACTION.to_string(),
+            hotkey: Hotkey {
+                key: HotkeyKey::F9,
+                modifiers: HotkeyModifiers::none(),
+            },
+            state: HotkeyState::Released,
+        };
+
+        assert!(controller
+            .handle_hotkey_action(&tap)
+            .expect("start")
+            .is_none());
+        assert_eq!(controller.state, PttState::Capturing);
+
+        controller_handle
+            .lock()
+            .expect("lock")
+            .clone()
+            .expect("controller ready")
+            .push_samples(&vec![0.1; 44_100]);
+
+        let work = controller.handle_hotkey_action(&tap).expect("stop");
+        assert!(work.is_some());
+        assert_ne!(controller.state, PttState::Capturing);
+    }
+
+    #[test]
+    fn toggle_mode_flips_capture_on_each_press() {
+        let backend = MockAudioBackend::new();
+        let controller_handle = backend.controller.clone();
+        let models = Arc::new(Mutex::new(crate::state::ModelStore::new()));
+        let mut controller = PttController::with_backend(backend, std::env::temp_dir(), models);
+        let settings = AppSettings {
+            ptt_mode: PttMode::Toggle,
+            ..AppSettings::default()
+        };
+        controller
+            .arm(settings, Some("base".to_string()))
+            .expect("arm");
+        controller.transcriber = Arc::new(MockTranscriber);
+        let press = HotkeyActionEvent {
+            action: PTT_TOGGLE_ACTION.to_string(),
+            hotkey: controller.hotkey,
+            state: HotkeyState::Pressed,
+        };
+
+        assert!(controller
+            .handle_hotkey_action(&press)
+            .expect("start")
+            .is_none());
+        assert_eq!(controller.state, PttState::Capturing);
+
+        controller_handle
+            .lock()
+            .expect("lock")
+            .clone()
+            .expect("controller ready")
+            .push_samples(&vec![0.1; 44_100]);
+
+        let work = controller.handle_hotkey_action(&press).expect("stop");
+        assert!(work.is_some());
+        assert_ne!(controller.state, PttState::Capturing);
+    }
+
+    #[test]
+    fn hold_mode_ignores_repeat_presses_until_the_key_looks_stuck() {
+        let backend = MockAudioBackend::new();
+        let controller_handle = backend.controller.clone();
+        let models = Arc::new(Mutex::new(crate::state::ModelStore::new()));
+        let mut controller = PttController::with_backend(backend, std::env::temp_dir(), models);
+        controller
+            .arm(AppSettings::default(), Some("base".to_string()))
+            .expect("arm");
+        controller.transcriber = Arc::new(MockTranscriber);
+        let press = HotkeyActionEvent {
+            action: PTT_ACTION.to_string(),
+            hotkey: controller.hotkey,
+            state: HotkeyState::Pressed,
+        };
+
+        controller.handle_hotkey_action(&press).expect("start");
+        assert_eq!(controller.state, PttState::Capturing);
+        assert!(controller
+            .handle_hotkey_action(&press)
+            .expect("repeat press")
+            .is_none());
+        assert_eq!(controller.state, PttState::Capturing);
+
+        controller_handle
+            .lock()
+            .expect("lock")
+            .clone()
+            .expect("controller ready")
+            .push_samples(&vec![0.1; 44_100]);
+        controller.capture_started = Instant::now().checked_sub(PTT_STUCK_KEY_TIMEOUT);
+
+        let work = controller.handle_hotkey_action(&press).expect("recover");
+        assert!(work.is_some());
+        assert_ne!(controller.state, PttState::Capturing);
+    }
+
+    #[test]
+    fn changing_ptt_mode_reregisters_hotkey_triggers() {
+        let backend = MockAudioBackend::new();
+        let models = Arc::new(Mutex::new(crate::state::ModelStore::new()));
+        let mut controller = PttController::with_backend(backend, std::env::temp_dir(), models);
+        let hotkey = controller.hotkey;
+        let held = HotkeyTrigger::Held {
+            min_ms: PTT_HOLD_THRESHOLD_MS,
+        };
+        let bound = |controller: &PttController<MockAudioBackend>, trigger| {
+            controller
+                .hotkey_manager
+                .lock()
+                .unwrap()
+                .would_conflict(&hotkey, trigger)
+                .map(str::to_string)
+        };
+
+        controller.update_settings(AppSettings {
+            ptt_mode: PttMode::Toggle,
+            ..AppSettings::default()
+        });
+        assert_eq!(
+            bound(&controller, HotkeyTrigger::Pressed).as_deref(),
+            Some(PTT_TOGGLE_ACTION)
+        );
+        assert_eq!(bound(&controller, HotkeyTrigger::Released), None);
+        assert_eq!(bound(&controller, held), None);
+
+        controller.update_settings(AppSettings::default());
+        assert_eq!(bound(&controller, HotkeyTrigger::Pressed), None);
+        assert_eq!(
+            bound(&controller, HotkeyTrigger::Released).as_deref(),
+            Some(PTT_TOGGLE_ACTION)
+        );
+        assert_eq!(bound(&controller, held).as_deref(), Some(PTT_ACTION));
+    }
+
+    #[test]
+    fn translate_action_captures_with_translation_enabled() {
+        let backend = MockAudioBackend::new();
+        let controller_handle = backend.controller.clone();
+        let models = Arc::new(Mutex::new(crate::state::ModelStore::new()));
+        let mut controller = PttController::with_backend(backend, std::env::temp_dir(), models);
+        controller
+            .arm(AppSettings::default(), Some("base".to_string()))
+            .expect("arm");
+        controller.transcriber = Arc::new(MockTranscriber);
+        let hotkey = Hotkey {
+            key: HotkeyKey::F9,
+            modifiers: HotkeyModifiers::none(),
+        };
+        let event = |action: &str| HotkeyActionEvent {
+            action: action.to_string(),
+            hotkey,
+            state: HotkeyState::Released,
+        };
+        let mut capture = |action: &str| {
+            controller
+                .handle_hotkey_action(&event(action))
+                .expect("start");
+            controller_handle
+                .lock()
+                .expect("lock")
+                .clone()
+                .expect("controller ready")
+                .push_samples(&vec![0.1; 44_100]);
+            controller
+                .handle_hotkey_action(&event(action))
+                .expect("stop")
+                .expect("work")
+        };
+
+        assert!(capture(PTT_TRANSLATE_ACTION).options.translate);
+        assert!(!capture(PTT_TOGGLE_ACTION).options.translate);
+        assert_eq!(
+            controller
+                .hotkey_manager
+                .lock()
+                .unwrap()
+                .resolve(&HotkeyEvent {
+                    key: HotkeyKey::Space,
+                    modifiers: HotkeyModifiers {
+                        ctrl: true,
+                        alt: true,
+                        shift: true,
+                        meta: false,
+                    },
+                    state: HotkeyState::Released,
+                }),
+            Some(PTT_TRANSLATE_ACTION)
+        );
+    }
+
+    #[test]
+    fn arming_applies_recording_limits_from_settings() {
+        let models = Arc::new(Mutex::new(crate::state::ModelStore::new()));
+        let mut controller =
+            PttController::with_backend(MockAudioBackend::new(), std::env::temp_dir(), models);
+        let settings = AppSettings {
+            max_recording_seconds: 45,
+            silence_auto_stop: true,
+            silence_threshold_dbfs: -52.0,
+            min_recording_ms: 200,
+            ..AppSettings::default()
+        };
+
+        controller.arm(settings, None).expect("arm");
+
+        assert_eq!(
+            controller.capture.limits(),
+            CaptureLimits {
+                max_duration: Duration::from_secs(45),
+                min_duration: Duration::from_millis(200),
+                silence_auto_stop: true,
+                silence_threshold_dbfs: -52.0,
+                silence_duration: CaptureLimits::default().silence_duration,
+                overflow_policy: OverflowPolicy::StopCapture,
+                pre_roll: CaptureLimits::default().pre_roll,
+                chunking: None,
+            }
+        );
+    }
+
+    #[test]
+    fn arming_applies_input_gain_from_settings() {
+        let models = Arc::new(Mutex::new(crate::state::ModelStore::new()));
+        let mut controller =
+            PttController::with_backend(MockAudioBackend::new(), std::env::temp_dir(), models);
+
+        let settings = AppSettings {
+            input_gain_db: 6.0,
+            ..AppSettings::default()
+        };
+        controller.arm(settings.clone(), None).expect("arm");
+        assert_eq!(
+            controller.capture.gain_mode(),
+            GainMode::Fixed { gain_db: 6.0 }
+        );
+
+        let settings = AppSettings {
+            auto_gain: true,
+            ..settings
+        };
+        controller.arm(settings, None).expect("re-arm");
+        assert_eq!(controller.capture.gain_mode(), GainMode::Automatic);
+    }
+
+    #[test]
+    fn arming_follows_noise_reduction_setting() {
+        let models = Arc::new(Mutex::new(crate::state::ModelStore::new()));
+        let mut controller =
+            PttController::with_backend(MockAudioBackend::new(), std::env::temp_dir(), models);
+
+        controller.arm(AppSettings::default(), None).expect("arm");
+        assert!(controller.capture.noise_reduction());
+
+        let settings = AppSettings {
+            noise_reduction: false,
+            ..AppSettings::default()
+        };
+        controller.arm(settings, None).expect("re-arm");
+        assert!(!controller.capture.noise_reduction());
+    }
+
+    #[test]
+    fn silence_auto_stop_releases_and_transcribes() {
+        let backend = MockAudioBackend::new();
+        let controller_handle = backend.controller.clone();
+        let models = Arc::new(Mutex::new(crate::state::ModelStore::new()));
+        let mut controller =
+            PttController::with_backend(backend, std::env::temp_dir(), Arc::clone(&models));
+        let settings = AppSettings {
+            silence_auto_stop: true,
+            output_mode: OutputMode::UiOnly,
+            ..AppSettings::default()
+        };
+        controller.arm(settings, None).expect("arm");
+        controller.auto_stop_receiver = controller.capture.auto_stop_feed();
+        controller.transcriber = Arc::new(MockTranscriber);
+        let event = HotkeyActionEvent {
+            action: "ptt".to_string(),
+            hotkey: controller.hotkey,
+            state: HotkeyState::Pressed,
+        };
+        controller.handle_hotkey_action(&event).expect("pressed");
+
+        let stream = controller_handle
+            .lock()
+            .expect("lock")
+            .clone()
+            .expect("controller ready");
+        stream.push_samples(&vec![0.4; 88_200]);
+        controller.poll_auto_stop();
+        assert_eq!(controller.state, PttState::Capturing);
+
+        stream.push_samples(&vec![0.0; 176_400]);
+        controller.poll_auto_stop();
+        finish_transcription(&mut controller);
+
+        assert_eq!(controller.state, PttState::Armed);
+        assert_eq!(models.lock().unwrap().transcript_history().len(), 1);
+    }
+
+    #[test]
+    fn stream_error_surfaces_and_rearms() {
+        let backend = MockAudioBackend::new();
+        let controller_handle = backend.controller.clone();
+        let models = Arc::new(Mutex::new(crate::state::ModelStore::new()));
+        let mut controller = PttController::with_backend(backend, std::env::temp_dir(), models);
+        controller.arm(AppSettings::default(), None).expect("arm");
+        controller.stream_error_receiver = controller.capture.error_feed();
+        let failed = controller_handle
+            .lock()
+            .expect("lock")
+            .clone()
+            .expect("controller ready");
+
+        failed.fail(AudioError::Stream("device disconnected".to_string()));
+        controller.poll_stream_errors();
+
+        assert_eq!(controller.state, PttState::Armed);
+        assert!(controller.armed);
+        assert!(controller.capture.audio().is_running());
+        let restarted = controller_handle
+            .lock()
+            .expect("lock")
+            .clone()
+            .expect("controller ready");
+        assert!(!Arc::ptr_eq(&failed.running, &restarted.running));
+        assert!(restarted.running.load(Ordering::SeqCst));
+    }
+
+    #[test]
+    fn repeated_stream_errors_stop_restarting() {
+        let backend = MockAudioBackend::new();
+        let controller_handle = backend.controller.clone();
+        let models = Arc::new(Mutex::new(crate::state::ModelStore::new()));
+        let mut controller = PttController::with_backend(backend, std::env::temp_dir(), models);
+        controller.arm(AppSettings::default(), None).expect("arm");
+        controller.stream_error_receiver = controller.capture.error_feed();
+        let stream = || {
+            controller_handle
+                .lock()
+                .expect("lock")
+                .clone()
+                .expect("controller ready")
+        };
+
+        stream().fail(AudioError::Stream("device disconnected".to_string()));
+        controller.poll_stream_errors();
+        assert_eq!(controller.state, PttState::Armed);
+
+        stream().fail(AudioError::Stream("device disconnected".to_string()));
+        controller.poll_stream_errors();
+
+        assert!(!controller.armed);
+        assert!(matches!(
+            controller.state,
+            PttState::Error { ref message } if message.contains("failed again")
+        ));
+        assert!(!controller.capture.audio().is_running());
+    }
+
+    #[test]
+    fn stream_restarts_again_after_the_restart_window() {
+        let backend = MockAudioBackend::new();
+        let models = Arc::new(Mutex::new(crate::state::ModelStore::new()));
+        let mut controller = PttController::with_backend(backend, std::env::temp_dir(), models);
+        controller.arm(AppSettings::default(), None).expect("arm");
+        let error = AudioError::Stream("device disconnected".to_string());
+        let start = Instant::now();
+
+        controller.restart_stream(&error, start);
+        controller.restart_stream(&error, start + STREAM_RESTART_WINDOW);
+
+        assert!(controller.armed);
+        assert_eq!(controller.state, PttState::Armed);
+    }
+
+    #[test]
+    fn stream_error_during_capture_transcribes_the_partial_recording() {
+        let backend = MockAudioBackend::new();
+        let controller_handle = backend.controller.clone();
+        let models = Arc::new(Mutex::new(crate::state::ModelStore::new()));
+        let mut controller =
+            PttController::with_backend(backend, std::env::temp_dir(), Arc::clone(&models));
+        let settings = AppSettings {
+            output_mode: OutputMode::UiOnly,
+            ..AppSettings::default()
+        };
+        controller.arm(settings, None).expect("arm");
+        controller.stream_error_receiver = controller.capture.error_feed();
+        controller.transcriber = Arc::new(MockTranscriber);
+        let event = HotkeyActionEvent {
+            action: "ptt".to_string(),
+            hotkey: controller.hotkey,
+            state: HotkeyState::Pressed,
+        };
+        controller.handle_hotkey_action(&event).expect("pressed");
+        let stream = controller_handle
+            .lock()
+            .expect("lock")
+            .clone()
+            .expect("controller ready");
+        stream.push_samples(&vec![0.4; 88_200]);
+
+        stream.fail(AudioError::Stream("device disconnected".to_string()));
+        controller.poll_stream_errors();
+        assert!(controller.transcription_pending());
+        assert!(controller.capture.audio().is_running());
+        finish_transcription(&mut controller);
+
+        assert_eq!(controller.state, PttState::Armed);
+        assert_eq!(models.lock().unwrap().transcript_history().len(), 1);
+    }
+
+    #[test]
+    fn stream_error_while_idle_stays_in_error() {
+        let backend = MockAudioBackend::new();
+        let controller_handle = backend.controller.clone();
+        let models = Arc::new(Mutex::new(crate::state::ModelStore::new()));
+        let mut controller = PttController::with_backend(backend, std::env::temp_dir(), models);
+        controller.arm(AppSettings::default(), None).expect("arm");
+        controller.stream_error_receiver = controller.capture.error_feed();
+        controller.stop().expect("stop");
+
+        controller_handle
+            .lock()
+            .expect("lock")
+            .clone()
+            .expect("controller ready")
+            .fail(AudioError::Stream("device disconnected".to_string()));
+        controller.poll_stream_errors();
+
+        assert!(matches!(
+            controller.state,
+            PttState::Error { ref message } if message.contains("device disconnected")
+        ));
+        assert!(!controller.capture.audio().is_running());
+    }
+
+    #[test]
+    fn level_history_covers_the_last_capture() {
+        let backend = MockAudioBackend::new();
+        let controller_handle = backend.controller.clone();
+        let models = Arc::new(Mutex::new(crate::state::ModelStore::new()));
+        let mut controller = PttController::with_backend(backend, std::env::temp_dir(), models);
+        let settings = AppSettings {
+            output_mode: OutputMode::UiOnly,
+            ..AppSettings::default()
+        };
+        controller.arm(settings, None).expect("arm");
+        controller.transcriber = Arc::new(MockTranscriber);
+        let mut event = HotkeyActionEvent {
+            action: "ptt".to_string(),
+            hotkey: controller.hotkey,
+            state: HotkeyState::Pressed,
+        };
+        controller.handle_hotkey_action(&event).expect("pressed");
+
+        let stream = controller_handle
+            .lock()
+            .expect("lock")
+            .clone()
+            .expect("controller ready");
+        stream.push_samples(&vec![0.4; 88_200]);
+        event.state = HotkeyState::Released;
+        controller.handle_hotkey_action(&event).expect("released");
+
+        let history = controller.level_history();
+        assert_eq!(history.len(), 20);
+        assert!(history.iter().all(|level| level.peak > 0.0));
+    }
+
+    #[test]
+    fn saved_recording_is_attached_to_history() {
+        let backend = MockAudioBackend::new();
+        let controller_handle = backend.controller.clone();
+        let models = Arc::new(Mutex::new(crate::state::ModelStore::new()));
+        let mut controller =
+            PttController::with_backend(backend, std::env::temp_dir(), Arc::clone(&models));
+        let dir = tempfile::tempdir().expect("tempdir");
+        controller.recordings = RecordingStore::new(dir.path());
+        let settings = AppSettings {
+            save_recordings: true,
+            output_mode: OutputMode::UiOnly,
+            ..AppSettings::default()
+        };
+        controller.arm(settings, None).expect("arm");
+        controller.transcriber = Arc::new(MockTranscriber);
+
+        let hotkey = controller.hotkey;
+        let event = |state| HotkeyActionEvent {
+            action: "ptt".to_string(),
+            hotkey,
+            state,
+        };
+        controller
+            .handle_hotkey_action(&event(HotkeyState::Pressed))
+            .expect("pressed");
+        controller_handle
+            .lock()
+            .expect("lock")
+            .clone()
+            .expect("controller ready")
+            .push_samples(&vec![0.25; 44_100]);
+        let work = controller
+            .handle_hotkey_action(&event(HotkeyState::Released))
+            .expect("released")
+            .expect("work");
+        controller.run_transcription(work);
+        finish_transcription(&mut controller);
+
+        let history = models.lock().unwrap().transcript_history();
+        let path = history[0].recording_path.clone().expect("recording path");
+        assert!(path.starts_with(dir.path()));
+        assert_eq!(std::fs::metadata(&path).unwrap().len(), 44 + 8_000 * 2);
+    }
+
+    #[test]
+    fn full_capture_buffer_releases_and_transcribes() {
+        let backend = MockAudioBackend::new();
+        let controller_handle = backend.controller.clone();
+        let models = Arc::new(Mutex::new(crate::state::ModelStore::new()));
+        let mut controller =
+            PttController::with_backend(backend, std::env::temp_dir(), Arc::clone(&models));
+        let settings = AppSettings {
+            max_recording_seconds: 1,
+            output_mode: OutputMode::UiOnly,
+            ..AppSettings::default()
+        };
+        controller.arm(settings, None).expect("arm");
+        controller.transcriber = Arc::new(MockTranscriber);
+        let event = HotkeyActionEvent {
+            action: "ptt".to_string(),
+            hotkey: controller.hotkey,
+            state: HotkeyState::Pressed,
+        };
+        controller.handle_hotkey_action(&event).expect("pressed");
+
+        controller_handle
+            .lock()
+            .expect("lock")
+            .clone()
+            .expect("controller ready")
+            .push_samples(&vec![0.25; 100_000]);
+        controller.poll_capture_capacity();
+        assert!(matches!(controller.state, PttState::Processing { .. }));
+        finish_transcription(&mut controller);
+
+        assert_eq!(controller.state, PttState::Armed);
+        let history = models.lock().unwrap().transcript_history();
+        assert_eq!(history.len(), 1);
+        assert_eq!(history[0].duration_ms, 1_000);
+    }
+
+    #[test]
+    fn released_audio_is_resampled_from_device_rate() {
+        let backend = MockAudioBackend::new();
+        let controller_handle = backend.controller.clone();
+        let models = Arc::new(Mutex::new(crate::state::ModelStore::new()));
+        let mut controller = PttController::with_backend(backend, std::env::temp_dir(), models);
+        controller.arm(AppSettings::default(), None).expect("arm");
+        let device = controller
+            .capture
+            .audio()
+            .selected_device()
+            .cloned()
+            .expect("device selected");
+        assert_eq!((device.sample_rate, device.channels), (44_100, 2));
+
+        let hotkey = controller.hotkey;
+        let event = |state| HotkeyActionEvent {
+            action: "ptt".to_string(),
+            hotkey,
+            state,
+        };
+        controller
+            .handle_hotkey_action(&event(HotkeyState::Pressed))
+            .expect("pressed");
+        controller_handle
+            .lock()
+            .expect("lock")
+            .clone()
+            .expect("controller ready")
+            .push_samples(&vec![0.25; 44_100]);
+        let work = controller
+            .handle_hotkey_action(&event(HotkeyState::Released))
+            .expect("released")
+            .expect("work");
+
+        assert_eq!(work.audio.len(), 8_000);
+        assert_eq!(work.duration_ms(), 500);
+    }
+
+    #[test]
+    fn captures_shorter_than_min_recording_are_discarded() {
+        let backend = MockAudioBackend::new();
+        let controller_handle = backend.controller.clone();
+        let models = Arc::new(Mutex::new(crate::state::ModelStore::new()));
+        let mut controller = PttController::with_backend(backend, std::env::temp_dir(), models);
+        let settings = AppSettings {
+            min_recording_ms: 300,
+            ..AppSettings::default()
+        };
+        controller.arm(settings, None).expect("arm");
+        let stream = controller_handle
+            .lock()
+            .expect("lock")
+            .clone()
+            .expect("controller ready");
+        let hotkey = controller.hotkey;
+        let event = |state| HotkeyActionEvent {
+            action: "ptt".to_string(),
+            hotkey,
+            state,
+        };
+
+        controller
+            .handle_hotkey_action(&event(HotkeyState::Pressed))
+            .expect("pressed");
+        stream.push_samples(&vec![0.25; 26_372]);
+        let tap = controller
+            .handle_hotkey_action(&event(HotkeyState::Released))
+            .expect("released");
+        assert!(tap.is_none());
+        assert_eq!(controller.state, PttState::Armed);
+
+        controller
+            .handle_hotkey_action(&event(HotkeyState::Pressed))
+            .expect("pressed");
+        stream.push_samples(&vec![0.25; 26_460]);
+        let work = controller
+            .handle_hotkey_action(&event(HotkeyState::Released))
+            .expect("released")
+            .expect("work");
+        assert_eq!(work.audio.len(), 4_800);
+        assert!(matches!(controller.state, PttState::Processing { .. }));
+    }
+
+    #[test]
+    fn cancel_during_capture_discards_audio_and_rearms() {
+        let backend = MockAudioBackend::new();
+        let controller_handle = backend.controller.clone();
+        let models = Arc::new(Mutex::new(crate::state::ModelStore::new()));
+        let mut controller = PttController::with_backend(backend, std::env::temp_dir(), models);
+        controller.arm(AppSettings::default(), None).expect("arm");
+        let stream = controller_handle
+            .lock()
+            .expect("lock")
+            .clone()
+            .expect("controller ready");
+        let hotkey = controller.hotkey;
+        let event = |state| HotkeyActionEvent {
+            action: "ptt".to_string(),
+            hotkey,
+            state,
+        };
+        let escape = HotkeyEvent {
+            key: HotkeyKey::Escape,
+            modifiers: HotkeyModifiers::none(),
+            state: HotkeyState::Pressed,
+        };
+        let resolve_escape = |controller: &PttController<MockAudioBackend>| {
+            controller
+                .hotkey_manager
+                .lock()
+                .expect("lock")
+                .resolve(&escape)
+                .map(str::to_string)
+        };
+        assert_eq!(resolve_escape(&controller), None);
+
+        controller
+            .handle_hotkey_action(&event(HotkeyState::Pressed))
+            .expect("pressed");
+        stream.push_samples(&vec![0.25; 44_100]);
+        assert_eq!(
+            resolve_escape(&controller).as_deref(),
+            Some(PTT_CANCEL_ACTION)
+        );
+
+        let cancelled = controller
+            .handle_hotkey_action(&HotkeyActionEvent {
+                action: PTT_CANCEL_ACTION.to_string(),
+                hotkey: controller.cancel_hotkey.expect("cancel hotkey"),
+                state: HotkeyState::Pressed,
+            })
+            .expect("cancel");
+        assert!(cancelled.is_none());
+        assert_eq!(controller.state, PttState::Armed);
+        assert_eq!(resolve_escape(&controller), None);
+
+        controller
+            .handle_hotkey_action(&event(HotkeyState::Pressed))
+            .expect("pressed");
+        stream.push_samples(&vec![0.25; 26_460]);
+        let work = controller
+            .handle_hotkey_action(&event(HotkeyState::Released))
+            .expect("released")
+            .expect("work");
+        assert_eq!(work.audio.len(), 4_800);
+    }
+
+    #[test]
+    fn level_readings_are_coalesced_and_throttled() {
+        let models = Arc::new(Mutex::new(crate::state::ModelStore::new()));
+        let mut controller =
+            PttController::with_backend(MockAudioBackend::new(), std::env::temp_dir(), models);
+        let (sender, receiver) = mpsc::channel();
+        controller.level_receiver = Some(receiver);
+        controller.state = PttState::Armed;
+        let reading = |rms, peak| LevelReading {
+            rms,
+            peak,
+            clipped: false,
+            smoothed_rms: rms,
+            peak_hold: peak,
+        };
+        let start = Instant::now();
+        let at = |ms| start + Duration::from_millis(ms);
+
+        sender.send(reading(0.1, 0.2)).unwrap();
+        sender.send(reading(0.3, 0.9)).unwrap();
+        let first = controller.drain_level_readings(at(0)).expect("first level");
+        assert!((first.rms - 0.2).abs() < 1e-6);
+        assert_eq!(first.peak, 0.9);
+        assert_eq!(first.peak_hold, 0.9);
+        assert_eq!(first.smoothed_rms, 0.3);
+
+        let mut emitted = 0;
+        for ms in (5..=100).step_by(5) {
+            for _ in 0..10 {
+                sender.send(reading(0.1, 0.2)).unwrap();
+            }
+            emitted += usize::from(controller.drain_level_readings(at(ms)).is_some());
+        }
+        assert_eq!(emitted, 2);
+
+        controller.settings.level_interval_ms = 10;
+        let mut emitted = 0;
+        for ms in (105..=200).step_by(5) {
+            sender.send(reading(0.1, 0.2)).unwrap();
+            emitted += usize::from(controller.drain_level_readings(at(ms)).is_some());
+        }
+        assert_eq!(emitted, 10);
+    }
+
+    #[test]
+    fn level_readings_are_dropped_outside_armed_and_capturing() {
+        let models = Arc::new(Mutex::new(crate::state::ModelStore::new()));
+        let mut controller =
+            PttController::with_backend(MockAudioBackend::new(), std::env::temp_dir(), models);
+        let (sender, receiver) = mpsc::channel();
+        controller.level_receiver = Some(receiver);
+        let reading = LevelReading {
+            rms: 0.1,
+            peak: 0.2,
+            clipped: false,
+            smoothed_rms: 0.1,
+            peak_hold: 0.2,
+        };
+        let start = Instant::now();
+
+        controller.armed = true;
+        controller.state = PttState::Processing {
+            model: "base".to_string(),
+            started_ms: 0,
+        };
+        sender.send(reading).unwrap();
+        assert_eq!(controller.drain_level_readings(start), None);
+
+        controller.state = PttState::Capturing;
+        assert_eq!(controller.drain_level_readings(start), None);
+        sender.send(reading).unwrap();
+        assert!(controller.drain_level_readings(start).is_some());
+    }
+
+    #[test]
+    fn cancel_while_idle_is_a_noop() {
+        let models = Arc::new(Mutex::new(crate::state::ModelStore::new()));
+        let mut controller =
+            PttController::with_backend(MockAudioBackend::new(), std::env::temp_dir(), models);
+        assert_eq!(controller.cancel_capture().expect("cancel"), PttState::Idle);
+
+        controller.arm(AppSettings::default(), None).expect("arm");
+        assert_eq!(
+            controller.cancel_capture().expect("cancel"),
+            PttState::Armed
+        );
+        let cancel = controller.cancel_hotkey.expect("cancel hotkey");
+        let bindings = controller.hotkey_manager.lock().expect("lock").bindings();
+        assert!(bindings
+            .iter()
+            .filter(|(hotkey, _)| *hotkey == cancel)
+            .all(|(_, binding)| !binding.enabled));
+    }
+
+    struct ChannelTones(Mutex<mpsc::Sender<FeedbackTone>>);
+
+    impl TonePlayer for ChannelTones {
+        fn play(&self, tone: FeedbackTone) -> Result<(), AudioError> {
+            let _ = self.0.lock().unwrap().send(tone);
+            Ok(())
+        }
+    }
+
+    #[derive(Default)]
+    struct RecordingNotifier(Mutex<Vec<(String, String)>>);
+
+    impl Notifier for RecordingNotifier {
+        fn notify(&self, title: &str, body: &str) -> Result<(), String> {
+            self.0
+                .lock()
+                .unwrap()
+                .push((title.to_string(), body.to_string()));
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn capture_and_completion_trigger_enabled_feedback() {
+        let backend = MockAudioBackend::new();
+        let controller_handle = backend.controller.clone();
+        let models = Arc::new(Mutex::new(crate::state::ModelStore::new()));
+        let mut controller = PttController::with_backend(backend, std::env::temp_dir(), models);
+        let settings = AppSettings {
+            sound_feedback: true,
+            notify_on_complete: true,
+            output_mode: OutputMode::UiOnly,
+            ..AppSettings::default()
+        };
+        controller.arm(settings, None).expect("arm");
+        controller.transcriber = Arc::new(MockTranscriber);
+        let (sender, played) = mpsc::channel();
+        let notifier = Arc::new(RecordingNotifier::default());
+        controller.feedback =
+            Feedback::new(Arc::new(ChannelTones(Mutex::new(sender))), notifier.clone());
+        let hotkey = controller.hotkey;
+        let event = |state| HotkeyActionEvent {
+            action: "ptt".to_string(),
+            hotkey,
+            state,
+        };
+
+        controller
+            .handle_hotkey_action(&event(HotkeyState::Pressed))
+            .expect("pressed");
+        controller_handle
+            .lock()
+            .expect("lock")
+            .clone()
+            .expect("controller ready")
+            .push_samples(&vec![0.25; 44_100]);
+        let work = controller
+            .handle_hotkey_action(&event(HotkeyState::Released))
+            .expect("released")
+            .expect("work");
+        controller.run_transcription(work);
+        finish_transcription(&mut controller);
+
+        let tones: Vec<FeedbackTone> = (0..2)
+            .map(|_| played.recv_timeout(Duration::from_secs(1)).expect("tone"))
+            .collect();
+        assert!(tones.contains(&FeedbackTone::Start));
+        assert!(tones.contains(&FeedbackTone::Stop));
+        assert_eq!(
+            *notifier.0.lock().unwrap(),
+            vec![(
+                COMPLETE_NOTIFICATION_TITLE.to_string(),
+                "hello world".to_string()
+            )]
+        );
+    }
+
+    #[test]
+    fn stop_pauses_capture_and_rearm_resumes_it() {
+        let backend = MockAudioBackend::new();
+        let controller_handle = backend.controller.clone();
+        let models = Arc::new(Mutex::new(crate::state::ModelStore::new()));
+        let mut controller = PttController::with_backend(backend, std::env::temp_dir(), models);
+        controller.arm(AppSettings::default(), None).expect("arm");
+        let stream = controller_handle
+            .lock()
+            .expect("lock")
+            .clone()
+            .expect("controller ready");
+
+        controller.stop().expect("stop");
+
+        assert!(controller.capture.audio().is_paused());
+        assert!(!stream.running.load(Ordering::SeqCst));
+        assert_eq!(controller.state, PttState::Idle);
+
+        controller
+            .arm(AppSettings::default(), None)
+            .expect("re-arm");
+
+        assert!(!controller.capture.audio().is_paused());
+        assert!(stream.running.load(Ordering::SeqCst));
+        assert_eq!(controller.state, PttState::Armed);
+    }
+
+    #[test]
+    fn removed_input_device_falls_back_to_default_and_rearms() {
+        let backend = MockAudioBackend::new();
+        let usb = AudioDevice {
+            id: "1:USB Mic".to_string(),
+            name: "USB Mic".to_string(),
+            sample_rate: 48_000,
+            channels: 1,
+            sample_format: SampleFormat::F32,
+        };
+        backend.devices.lock().expect("lock").push(usb.clone());
+        let devices = Arc::clone(&backend.devices);
+        let models = Arc::new(Mutex::new(crate::state::ModelStore::new()));
+        let mut controller = PttController::with_backend(backend, std::env::temp_dir(), models);
+        let settings = AppSettings {
+            input_device: usb.id.as_str().into(),
+            ..AppSettings::default()
+        };
+        controller.arm(settings, None).expect("arm");
+        assert_eq!(controller.capture.audio().selected_device(), Some(&usb));
+
+        devices
+            .lock()
+            .expect("lock")
+            .retain(|device| device.id != usb.id);
+        controller.sync_devices();
+
+        let audio = controller.capture.audio();
+        assert_eq!(
+            audio.selected_device().map(|device| device.id.as_str()),
+            Some("0:Mock")
+        );
+        assert!(audio.is_running());
+        assert_eq!(controller.state, PttState::Armed);
+    }
+
+    #[test]
+    fn device_list_setting_captures_from_a_mix() {
+        let backend = MockAudioBackend::new();
+        let desk = AudioDevice {
+            id: "Desk Mic".to_string(),
+            name: "Desk Mic".to_string(),
+            sample_rate: 48_000,
+            channels: 1,
+            sample_format: SampleFormat::F32,
+        };
+        backend.devices.lock().expect("lock").push(desk);
+        let models = Arc::new(Mutex::new(crate::state::ModelStore::new()));
+        let mut controller = PttController::with_backend(backend, std::env::temp_dir(), models);
+        let settings = AppSettings {
+            input_device: vec!["Mock".to_string(), "Desk Mic".to_string()].into(),
+            ..AppSettings::default()
+        };
+        controller.arm(settings, None).expect("arm");
+
+        let audio = controller.capture.audio();
+        let selected = audio.selected_device().expect("selected");
+        assert_eq!(selected.id, format!("{MIX_DEVICE_PREFIX}Mock+Desk Mic"));
+        assert_eq!((selected.sample_rate, selected.channels), (16_000, 1));
+        assert!(audio.is_running());
+
+        controller
+            .arm(AppSettings::default(), None)
+            .expect("re-arm");
+        let audio = controller.capture.audio();
+        assert_eq!(
+            audio.selected_device().map(|device| device.id.as_str()),
+            Some("0:Mock")
+        );
+        assert!(audio.is_running());
+    }
+
+    #[test]
+    fn audio_source_selects_monitor_devices() {
+        let backend = MockAudioBackend::new();
+        let monitor = AudioDevice {
+            id: "Speakers.monitor".to_string(),
+            name: "Speakers.monitor".to_string(),
+            sample_rate: 48_000,
+            channels: 2,
+            sample_format: SampleFormat::F32,
+        };
+        backend.devices.lock().expect("lock").push(monitor);
+        let models = Arc::new(Mutex::new(crate::state::ModelStore::new()));
+        let mut controller = PttController::with_backend(backend, std::env::temp_dir(), models);
+
+        let system = AppSettings {
+            audio_source: AudioSource::SystemAudio,
+            ..AppSettings::default()
+        };
+        controller.arm(system, None).expect("arm system audio");
+        let selected = controller
+            .capture
+            .audio()
+            .selected_device()
+            .expect("selected");
+        assert_eq!(selected.id, "Speakers.monitor");
+
+        let both = AppSettings {
+            audio_source: AudioSource::Both,
+            ..AppSettings::default()
+        };
+        controller.arm(both, None).expect("arm both");
+        let selected = controller
+            .capture
+            .audio()
+            .selected_device()
+            .expect("selected");
+        assert_eq!(
+            selected.id,
+            format!("{MIX_DEVICE_PREFIX}0:Mock+Speakers.monitor")
+        );
+
+        controller
+            .arm(AppSettings::default(), None)
+            .expect("arm microphone");
+        let audio = controller.capture.audio();
+        assert_eq!(
+            audio.selected_device().map(|device| device.id.as_str()),
+            Some("0:Mock")
+        );
+        assert!(audio.is_running());
+    }
+
+    #[test]
+    fn system_audio_without_monitor_fails_to_arm() {
+        let models = Arc::new(Mutex::new(crate::state::ModelStore::new()));
+        let mut controller =
+            PttController::with_backend(MockAudioBackend::new(), std::env::temp_dir(), models);
+        let settings = AppSettings {
+            audio_source: AudioSource::SystemAudio,
+            ..AppSettings::default()
+        };
+
+        let err = controller.arm(settings, None).expect_err("no monitor");
+        assert!(err.contains("no system audio source"));
+        assert!(!controller.capture.audio().is_running());
+    }
+
+    #[test]
+    fn missing_configured_device_falls_back_to_default() {
+        let models = Arc::new(Mutex::new(crate::state::ModelStore::new()));
+        let mut controller =
+            PttController::with_backend(MockAudioBackend::new(), std::env::temp_dir(), models);
+        let settings = AppSettings {
+            input_device: "Bluetooth Headset".into(),
+            ..AppSettings::default()
+        };
+
+        let state = controller.arm(settings, None).expect("arm with fallback");
+        assert_eq!(state, PttState::Armed);
+        let audio = controller.capture.audio();
+        assert_eq!(
+            audio.selected_device().map(|device| device.id.as_str()),
+            Some("0:Mock")
+        );
+        assert!(audio.is_running());
     }
-}
 
-pub(crate) fn register_custom_model(manager: &mut ModelManager, root: &Path, name: &str) {
-    let filename = format!("{name}.bin");
-    let spec = ModelSpec::new(ModelId::Custom(name.to_string()), filename.clone())
-        .with_download_url(file_url(root.join(&filename)));
-    manager.register_model(spec);
-}
+    #[test]
+    fn arm_without_input_devices_reports_readable_error() {
+        let backend = MockAudioBackend::new();
+        backend.devices.lock().expect("lock").clear();
+        let models = Arc::new(Mutex::new(crate::state::ModelStore::new()));
+        let mut controller = PttController::with_backend(backend, std::env::temp_dir(), models);
 
-fn file_url(path: PathBuf) -> String {
-    format!("file://{}", path.display())
-}
+        let err = controller
+            .arm(AppSettings::default(), None)
+            .expect_err("no devices");
+        assert!(err.contains("no microphone found"));
 
-fn model_download_url(filename: &str) -> String {
-    format!("https://huggingface.co/ggerganov/whisper.cpp/resolve/main/{filename}")
-}
+        let settings = AppSettings {
+            input_device: "USB Mic".into(),
+            ..AppSettings::default()
+        };
+        let err = controller.arm(settings, None).expect_err("no devices");
+        assert!(err.contains("no microphone found"));
+        assert!(!controller.capture.audio().is_running());
+    }
 
-fn emit_level(reading: LevelReading) {
-    let level = PttLevel {
-        rms: reading.rms,
-        peak: reading.peak,
-    };
-    emit_app_event(PTT_LEVEL_EVENT, &level);
-}
+    #[test]
+    fn transcription_language_follows_settings() {
+        let explicit = AppSettings {
+            language: Some("fr".to_string()),
+            ..AppSettings::default()
+        };
+        let auto = AppSettings {
+            auto_language: true,
+            ..explicit.clone()
+        };
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use core_input::{AudioDevice, AudioError, AudioStream};
-    use std::sync::{
-        atomic::{AtomicBool, Ordering},
-        Mutex,
-    };
-    use std::time::Duration;
+        assert_eq!(transcription_language(&explicit).as_deref(), Some("fr"));
+        assert_eq!(transcription_language(&auto), None);
+        assert_eq!(transcription_language(&AppSettings::default()), None);
+    }
 
     #[test]
-    fn hotkey_payload_parses_letters() {
-        let payload = PttHotkeyPayload {
-            key: "k".to_string(),
-            modifiers: PttHotkeyModifiers {
-                ctrl: true,
-                alt: false,
-                shift: false,
-                meta: false,
-            },
+    fn custom_vocabulary_becomes_initial_prompt() {
+        let settings = AppSettings {
+            custom_vocabulary: vec![
+                "Kubernetes".to_string(),
+                "  ".to_string(),
+                " OpenWhisperAI ".to_string(),
+                "PipeWire".to_string(),
+            ],
+            ..AppSettings::default()
         };
-        let hotkey = payload.to_hotkey().expect("hotkey");
-        assert_eq!(hotkey.key, HotkeyKey::K);
-        assert!(hotkey.modifiers.ctrl);
+
+        let prompt = vocabulary_prompt(&settings).expect("prompt");
+
+        for term in ["Kubernetes", "OpenWhisperAI", "PipeWire"] {
+            assert!(prompt.contains(term), "{prompt}");
+        }
+        assert_eq!(prompt, "Kubernetes, OpenWhisperAI, PipeWire");
+        assert_eq!(vocabulary_prompt(&AppSettings::default()), None);
     }
 
     #[test]
-    fn hotkey_payload_rejects_unknown_key() {
-        let payload = PttHotkeyPayload {
-            key: "unknown".to_string(),
-            modifiers: PttHotkeyModifiers {
-                ctrl: false,
-                alt: false,
-                shift: false,
-                meta: false,
-            },
+    fn successful_transcription_is_recorded_in_history() {
+        let models = Arc::new(Mutex::new(crate::state::ModelStore::new()));
+        let mut controller = PttController::with_backend(
+            MockAudioBackend::new(),
+            std::env::temp_dir(),
+            Arc::clone(&models),
+        );
+        controller.active_model = Some("base".to_string());
+        let work = TranscriptionWork {
+            audio: vec![0.0; TARGET_SAMPLE_RATE as usize / 2],
+            recording_path: None,
+            transcriber: Arc::new(MockTranscriber),
+            output: OutputStrategy::UiOnly,
+            output_mode: OutputMode::UiOnly,
+            options: TranscribeOptions::default(),
+            postprocessor: PostProcessor::default(),
         };
-        let result = payload.to_hotkey();
-        assert!(result.is_err());
+
+        let timed = TranscriptionResult::from_segments(vec![transcribe_engine::Segment {
+            start_ms: 0,
+            end_ms: 900,
+            text: "hello world".to_string(),
+        }]);
+        controller.complete_transcription(Ok(timed), &work);
+        controller.complete_transcription(Ok(TranscriptionResult::from_text("   ")), &work);
+        controller.complete_transcription(Err("boom".to_string().into()), &work);
+
+        let history = models.lock().unwrap().transcript_history();
+        assert_eq!(history.len(), 1);
+        assert_eq!(history[0].text, "hello world");
+        assert_eq!(
+            history[0].segments,
+            vec![TranscriptSegment {
+                start_ms: 0,
+                end_ms: 900,
+                text: "hello world".to_string(),
+            }]
+        );
+        assert_eq!(history[0].duration_ms, 500);
+        assert_eq!(history[0].chars, 11);
+        assert_eq!(history[0].model.as_deref(), Some("base"));
+        assert_eq!(history[0].output_mode, OutputMode::UiOnly);
     }
 
-    #[cfg(target_os = "linux")]
     #[test]
-    fn paste_candidates_prefer_wayland_helper() {
-        let wayland_candidates = paste_command_candidates(true);
-        let x11_candidates = paste_command_candidates(false);
+    fn persisted_hotkey_is_registered_after_restart() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let path = dir.path().join("settings.json");
+        crate::state::SettingsStore::new(path.clone())
+            .update(shared_types::SettingsUpdate {
+                hotkey: Some(HotkeyConfig {
+                    key: "k".to_string(),
+                    ctrl: true,
+                    alt: false,
+                    shift: true,
+                    meta: false,
+                }),
+                ..shared_types::SettingsUpdate::default()
+            })
+            .expect("persist hotkey");
+
+        let settings = crate::state::SettingsStore::new(path.clone()).settings();
+        let models = Arc::new(Mutex::new(crate::state::ModelStore::new()));
+        let mut controller =
+            PttController::with_backend(MockAudioBackend::new(), std::env::temp_dir(), models);
+        controller.update_settings(settings);
 
+        let manager = controller.hotkey_manager.lock().expect("lock");
+        let tapped = |key, modifiers| HotkeyEvent {
+            key,
+            modifiers,
+            state: HotkeyState::Released,
+        };
+        let configured = HotkeyModifiers {
+            ctrl: true,
+            shift: true,
+            ..HotkeyModifiers::none()
+        };
+        let default = HotkeyModifiers {
+            ctrl: true,
+            alt: true,
+            ..HotkeyModifiers::none()
+        };
         assert_eq!(
-            wayland_candidates.first().map(|(cmd, _)| *cmd),
-            Some("wtype")
+            manager.resolve(&tapped(HotkeyKey::K, configured)),
+            Some(PTT_TOGGLE_ACTION)
         );
-        assert_eq!(x11_candidates.first().map(|(cmd, _)| *cmd), Some("xdotool"));
+        assert_eq!(manager.resolve(&tapped(HotkeyKey::Space, default)), None);
     }
 
-    #[derive(Clone)]
-    struct MockStreamController {
-        running: Arc<AtomicBool>,
-        callback: Arc<Mutex<Option<Box<dyn FnMut(&[f32]) + Send>>>>,
+    #[test]
+    fn audio_file_transcripts_become_history_entries() {
+        let audio = vec![0.0; TARGET_SAMPLE_RATE as usize * 3];
+        let entry = transcribe_audio_file(
+            &MockTranscriber,
+            &audio,
+            &TranscribeOptions::default(),
+            Some("base".to_string()),
+        )
+        .expect("transcribe file");
+
+        assert_eq!(entry.text, "hello world");
+        assert_eq!(entry.duration_ms, 3_000);
+        assert_eq!(entry.model.as_deref(), Some("base"));
+        assert_eq!(entry.output_mode, OutputMode::UiOnly);
     }
 
-    impl MockStreamController {
-        fn push_samples(&self, samples: &[f32]) {
-            if !self.running.load(Ordering::SeqCst) {
-                return;
-            }
-
-            if let Ok(mut callback) = self.callback.lock() {
-                if let Some(handler) = callback.as_mut() {
-                    handler(samples);
-                }
+    #[test]
+    fn silent_audio_files_report_no_speech() {
+        struct SilentTranscriber;
+
+        impl Transcriber for SilentTranscriber {
+            fn transcribe(
+                &self,
+                _audio: &[f32],
+                _options: &TranscribeOptions,
+            ) -> Result<TranscriptionResult, TranscriberError> {
+                Ok(TranscriptionResult::default())
             }
         }
+
+        let err = transcribe_audio_file(
+            &SilentTranscriber,
+            &[0.0; 1_600],
+            &TranscribeOptions::default(),
+            None,
+        )
+        .unwrap_err();
+        assert_eq!(err, "no speech detected");
     }
 
-    struct MockStream {
-        controller: MockStreamController,
+    struct LanguageProbe {
+        calls: Mutex<Vec<usize>>,
     }
 
-    impl AudioStream for MockStream {
-        fn start(&self) -> Result<(), AudioError> {
-            self.controller.running.store(true, Ordering::SeqCst);
-            Ok(())
+    #[test]
+    fn short_captures_detect_language_on_release() {
+        let backend = MockAudioBackend::new();
+        let controller_handle = backend.controller.clone();
+        let models = Arc::new(Mutex::new(crate::state::ModelStore::new()));
+        let mut controller = PttController::with_backend(backend, std::env::temp_dir(), models);
+        let settings = AppSettings {
+            auto_language: true,
+            output_mode: OutputMode::UiOnly,
+            ..AppSettings::default()
+        };
+        controller.arm(settings, None).expect("arm");
+        let probe = Arc::new(LanguageProbe {
+            calls: Mutex::new(Vec::new()),
+        });
+        controller.transcriber = probe.clone();
+        let hotkey = controller.hotkey;
+        let event = |state| HotkeyActionEvent {
+            action: PTT_ACTION.to_string(),
+            hotkey,
+            state,
+        };
+
+        controller
+            .handle_hotkey_action(&event(HotkeyState::Pressed))
+            .expect("pressed");
+        controller_handle
+            .lock()
+            .expect("lock")
+            .clone()
+            .expect("controller ready")
+            .push_samples(&vec![0.1; 44_100]);
+        controller
+            .handle_hotkey_action(&event(HotkeyState::Released))
+            .expect("released")
+            .expect("work");
+
+        let deadline = Instant::now() + Duration::from_secs(2);
+        while probe.calls.lock().unwrap().is_empty() {
+            assert!(Instant::now() < deadline, "language detection did not run");
+            std::thread::sleep(Duration::from_millis(5));
         }
+        assert_eq!(probe.calls.lock().unwrap().len(), 1);
+        assert!(controller.language_checked);
+    }
 
-        fn stop(&self) -> Result<(), AudioError> {
-            self.controller.running.store(false, Ordering::SeqCst);
-            Ok(())
+    impl Transcriber for LanguageProbe {
+        fn transcribe(
+            &self,
+            _audio: &[f32],
+            _options: &TranscribeOptions,
+        ) -> Result<TranscriptionResult, TranscriberError> {
+            Ok(TranscriptionResult::default())
+        }
+
+        fn detect_language(&self, audio: &[f32]) -> Result<(String, f32), String> {
+            self.calls.lock().unwrap().push(audio.len());
+            Ok(("fr".to_string(), 0.8))
         }
     }
 
-    #[derive(Clone)]
-    struct MockAudioBackend {
-        devices: Vec<AudioDevice>,
-        controller: Arc<Mutex<Option<MockStreamController>>>,
+    #[test]
+    fn language_detection_only_runs_with_auto_language() {
+        let probe = LanguageProbe {
+            calls: Mutex::new(Vec::new()),
+        };
+        let audio = vec![0.0; TARGET_SAMPLE_RATE as usize * 5];
+        let manual = AppSettings {
+            auto_language: false,
+            ..AppSettings::default()
+        };
+        assert_eq!(capture_language(&manual, &probe, &audio), None);
+        assert!(probe.calls.lock().unwrap().is_empty());
+
+        let auto = AppSettings {
+            auto_language: true,
+            ..AppSettings::default()
+        };
+        let payload = capture_language(&auto, &probe, &audio).expect("language detected");
+        assert_eq!(payload.language, "fr");
+        assert_eq!(payload.probability, 0.8);
+        assert_eq!(*probe.calls.lock().unwrap(), vec![LANGUAGE_DETECT_SAMPLES]);
+        assert!(capture_limits(&auto).chunking.is_some());
     }
 
-    impl MockAudioBackend {
-        fn new() -> Self {
-            Self {
-                devices: vec![AudioDevice {
-                    id: "0:Mock".to_string(),
-                    name: "Mock".to_string(),
-                    sample_rate: 44_100,
-                    channels: 2,
-                }],
-                controller: Arc::new(Mutex::new(None)),
+    #[test]
+    fn language_detection_failures_are_silent() {
+        let auto = AppSettings {
+            auto_language: true,
+            ..AppSettings::default()
+        };
+        assert_eq!(
+            capture_language(&auto, &MockTranscriber, &[0.0; 1_600]),
+            None
+        );
+    }
+
+    #[test]
+    fn transcripts_are_post_processed_before_recording() {
+        struct RawTranscriber;
+
+        impl Transcriber for RawTranscriber {
+            fn transcribe(
+                &self,
+                _audio: &[f32],
+                _options: &TranscribeOptions,
+            ) -> Result<TranscriptionResult, TranscriberError> {
+                Ok(TranscriptionResult::from_text(
+                    "I like open whisper comma a lot",
+                ))
             }
         }
+
+        let models = Arc::new(Mutex::new(crate::state::ModelStore::new()));
+        let mut controller = PttController::with_backend(
+            MockAudioBackend::new(),
+            std::env::temp_dir(),
+            Arc::clone(&models),
+        );
+        let settings = AppSettings {
+            spoken_punctuation: true,
+            replacements: vec![shared_types::WordReplacement {
+                from: "open whisper".to_string(),
+                to: "OpenWhisperAI".to_string(),
+            }],
+            ..AppSettings::default()
+        };
+        let work = TranscriptionWork {
+            audio: vec![0.0; TARGET_SAMPLE_RATE as usize / 2],
+            recording_path: None,
+            transcriber: Arc::new(RawTranscriber),
+            output: OutputStrategy::UiOnly,
+            output_mode: OutputMode::UiOnly,
+            options: TranscribeOptions::default(),
+            postprocessor: PostProcessor::from_settings(&settings),
+        };
+
+        controller.run_transcription(work);
+        finish_transcription(&mut controller);
+
+        let history = models.lock().unwrap().transcript_history();
+        assert_eq!(history[0].text, "I like OpenWhisperAI, a lot");
     }
 
-    impl AudioBackend for MockAudioBackend {
-        type Stream = MockStream;
+    #[test]
+    fn model_load_failures_are_classified_as_unusable_models() {
+        let load = TranscriberError::from(BindingError::ModelLoadFailed {
+            stderr: "failed to load model".to_string(),
+        });
+        assert!(load.model_unusable());
+        assert!(load.to_string().contains("try downloading it again"));
 
-        fn list_input_devices(&self) -> Result<Vec<AudioDevice>, AudioError> {
-            Ok(self.devices.clone())
-        }
+        let unsupported = TranscriberError::from(BindingError::UnsupportedModel {
+            stderr: "bad magic".to_string(),
+        });
+        assert!(unsupported.model_unusable());
 
-        fn default_input_device(&self) -> Result<Option<AudioDevice>, AudioError> {
-            Ok(self.devices.first().cloned())
-        }
+        let memory = TranscriberError::from(BindingError::OutOfMemory {
+            stderr: "failed to allocate buffer".to_string(),
+        });
+        assert!(!memory.model_unusable());
+        assert_eq!(
+            memory.to_string(),
+            "not enough memory to run this model; try a smaller one (failed to allocate buffer)"
+        );
+    }
 
-        fn build_input_stream(
-            &self,
-            _device: &AudioDevice,
-            on_samples: Box<dyn FnMut(&[f32]) + Send>,
-        ) -> Result<Self::Stream, AudioError> {
-            let controller = MockStreamController {
-                running: Arc::new(AtomicBool::new(false)),
-                callback: Arc::new(Mutex::new(Some(on_samples))),
-            };
+    #[test]
+    fn only_unusable_models_are_marked_failed() {
+        let models = Arc::new(Mutex::new(crate::state::ModelStore::new()));
+        let mut controller = PttController::with_backend(
+            MockAudioBackend::new(),
+            std::env::temp_dir(),
+            Arc::clone(&models),
+        );
+        controller.active_model = Some("base".to_string());
+        let work = TranscriptionWork {
+            audio: vec![0.0; TARGET_SAMPLE_RATE as usize / 2],
+            recording_path: None,
+            transcriber: Arc::new(MockTranscriber),
+            output: OutputStrategy::UiOnly,
+            output_mode: OutputMode::UiOnly,
+            options: TranscribeOptions::default(),
+            postprocessor: PostProcessor::default(),
+        };
 
-            *self.controller.lock().expect("lock") = Some(controller.clone());
-            Ok(MockStream { controller })
-        }
+        controller.complete_transcription(
+            Err(TranscriberError::Failed("timed out".to_string())),
+            &work,
+        );
+        assert_eq!(
+            models.lock().unwrap().overrides_snapshot().get("base"),
+            None
+        );
+
+        controller.complete_transcription(
+            Err(BindingError::ModelLoadFailed {
+                stderr: "failed to load model".to_string(),
+            }
+            .into()),
+            &work,
+        );
+        assert_eq!(
+            models.lock().unwrap().overrides_snapshot().get("base"),
+            Some(&ModelInstallStatus::Failed)
+        );
+
+        controller.complete_transcription(
+            Err(TranscriberError::Failed("timed out".to_string())),
+            &work,
+        );
+        assert_eq!(
+            models.lock().unwrap().overrides_snapshot().get("base"),
+            Some(&ModelInstallStatus::Failed)
+        );
     }
 
-    struct MockTranscriber;
+    struct GatedTranscriber {
+        gate: Mutex<mpsc::Receiver<()>>,
+    }
 
-    impl Transcriber for MockTranscriber {
-        fn transcribe(&self, _audio: &[f32]) -> Result<String, String> {
-            Ok("hello world".to_string())
+    impl Transcriber for GatedTranscriber {
+        fn transcribe(
+            &self,
+            _audio: &[f32],
+            _options: &TranscribeOptions,
+        ) -> Result<TranscriptionResult, TranscriberError> {
+            let _ = self
+                .gate
+                .lock()
+                .unwrap()
+                .recv_timeout(Duration::from_secs(10));
+            Ok(TranscriptionResult::from_text("hello world"))
         }
     }
 
-    struct MockInjector {
-        sender: mpsc::Sender<String>,
+    fn wait_for_state(handle: &PttHandle, expected: PttState) {
+        let deadline = Instant::now() + Duration::from_secs(5);
+        while handle.state() != expected {
+            assert!(
+                Instant::now() < deadline,
+                "state stuck at {:?}",
+                handle.state()
+            );
+            std::thread::sleep(Duration::from_millis(5));
+        }
     }
 
-    impl TextInjector for MockInjector {
-        fn inject(&self, text: &str) -> Result<(), String> {
-            let _ = self.sender.send(text.to_string());
-            Ok(())
-        }
+    #[test]
+    fn runtime_answers_commands_while_transcribing() {
+        let backend = MockAudioBackend::new();
+        let stream = backend.controller.clone();
+        let (release, gate) = mpsc::channel();
+        let models = Arc::new(Mutex::new(crate::state::ModelStore::new()));
+        let controller_models = Arc::clone(&models);
+        let handle = PttHandle::spawn(move || {
+            let mut controller =
+                PttController::with_backend(backend, std::env::temp_dir(), controller_models);
+            controller.settings.output_mode = OutputMode::UiOnly;
+            controller.transcriber = Arc::new(GatedTranscriber {
+                gate: Mutex::new(gate),
+            });
+            controller
+        });
+
+        assert_eq!(handle.manual_toggle().unwrap(), PttState::Capturing);
+        stream
+            .lock()
+            .unwrap()
+            .clone()
+            .expect("controller ready")
+            .push_samples(&vec![0.25; 44_100]);
+        let processing = handle.manual_toggle().unwrap();
+        assert!(matches!(processing, PttState::Processing { .. }));
+
+        let payload = PttHotkeyPayload::from(Hotkey {
+            key: HotkeyKey::F10,
+            modifiers: HotkeyModifiers::none(),
+        });
+        assert_eq!(handle.set_hotkey(payload.clone(), true).unwrap(), payload);
+        assert_eq!(handle.state(), processing);
+        assert_eq!(handle.manual_toggle().unwrap(), processing);
+        assert_eq!(handle.stop().unwrap(), processing);
+        assert!(models.lock().unwrap().transcript_history().is_empty());
+
+        release.send(()).unwrap();
+        wait_for_state(&handle, PttState::Idle);
+        let history = models.lock().unwrap().transcript_history();
+        assert_eq!(history.len(), 1);
+        assert_eq!(history[0].text, "hello world");
     }
 
     #[test]
-    fn ptt_transcribes_and_injects_on_release() {
+    fn presses_are_ignored_while_a_transcription_is_pending() {
         let backend = MockAudioBackend::new();
-        let controller_handle = backend.controller.clone();
-        let (inject_tx, inject_rx) = mpsc::channel();
         let models = Arc::new(Mutex::new(crate::state::ModelStore::new()));
         let mut controller = PttController::with_backend(backend, std::env::temp_dir(), models);
+        controller.arm(AppSettings::default(), None).expect("arm");
+        let (release, gate) = mpsc::channel();
+        controller.run_transcription(TranscriptionWork {
+            audio: vec![0.0; TARGET_SAMPLE_RATE as usize / 2],
+            recording_path: None,
+            transcriber: Arc::new(GatedTranscriber {
+                gate: Mutex::new(gate),
+            }),
+            output: OutputStrategy::UiOnly,
+            output_mode: OutputMode::UiOnly,
+            options: TranscribeOptions::default(),
+            postprocessor: PostProcessor::default(),
+        });
+        let processing = controller.processing_state();
+        controller.set_state(processing.clone());
 
-        controller
-            .arm(AppSettings::default(), Some("base".to_string()))
-            .expect("arm");
-        controller.transcriber = Arc::new(MockTranscriber);
-        controller.injector = Arc::new(MockInjector { sender: inject_tx });
-
-        let event_pressed = HotkeyActionEvent {
+        let event = HotkeyActionEvent {
             action: "ptt".to_string(),
-            hotkey: Hotkey {
-                key: HotkeyKey::F9,
-                modifiers: HotkeyModifiers::none(),
-            },
+            hotkey: controller.hotkey,
             state: HotkeyState::Pressed,
         };
-        let event_released = HotkeyActionEvent {
-            action: "ptt".to_string(),
-            hotkey: Hotkey {
-                key: HotkeyKey::F9,
-                modifiers: HotkeyModifiers::none(),
-            },
-            state: HotkeyState::Released,
-        };
-
-        controller
-            .handle_hotkey_action(&event_pressed)
-            .expect("pressed");
-
-        let stream_controller = controller_handle
-            .lock()
-            .expect("lock")
-            .clone()
-            .expect("controller ready");
-        stream_controller.push_samples(&[0.1, 0.2, 0.3]);
-
-        let work = controller
-            .handle_hotkey_action(&event_released)
-            .expect("released")
-            .expect("work");
-        let text = work
-            .transcriber
-            .transcribe(&work.audio)
-            .expect("transcribe");
-        controller.handle_output(&OutputMode::UiOnly, &text);
+        assert!(controller.handle_hotkey_action(&event).unwrap().is_none());
+        assert_eq!(controller.state, processing);
 
-        let injected = inject_rx.recv_timeout(Duration::from_millis(50));
-        assert!(injected.is_err());
+        release.send(()).unwrap();
+        finish_transcription(&mut controller);
+        assert_eq!(controller.state, PttState::Armed);
     }
 
     #[test]
-    fn resample_downmixes_stereo_to_mono() {
-        let audio = vec![1.0, -1.0, 0.5, 0.5];
-        let output = resample_to_16k_mono(audio, TARGET_SAMPLE_RATE, 2);
-        assert_eq!(output, vec![0.0, 0.5]);
-    }
+    fn model_stays_ready_while_transcribing() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let root = dir.path().to_path_buf();
+        let mut manager = ModelManager::new(&root);
+        register_known_models(&mut manager, &root);
+        let path = manager.model_path(&ModelId::Base).unwrap();
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(&path, b"model").unwrap();
 
-    #[test]
-    fn resample_linearly_interpolates() {
-        let audio = vec![0.0, 1.0, 0.0, -1.0, 0.0];
-        let output = resample_to_16k_mono(audio, 44_100, 1);
-        assert_eq!(output.len(), 2);
-        let expected = -0.75625_f32;
-        assert!((output[0] - 0.0).abs() < 1e-6);
-        assert!((output[1] - expected).abs() < 1e-4);
+        let models = Arc::new(Mutex::new(crate::state::ModelStore::new()));
+        let mut controller =
+            PttController::with_backend(MockAudioBackend::new(), root.clone(), Arc::clone(&models));
+        controller.set_active_model(Some("base".to_string()));
+        let base_status = |models: &Arc<Mutex<crate::state::ModelStore>>| {
+            let snapshot = models.lock().unwrap().snapshot();
+            let status = snapshot
+                .models
+                .iter()
+                .find(|item| item.id == "base")
+                .map(|item| item.status.clone());
+            (status, snapshot.queue_count)
+        };
+        let idle = base_status(&models);
+        assert_eq!(idle.0, Some(ModelInstallStatus::Ready));
+
+        let (release, gate) = mpsc::channel();
+        controller.run_transcription(TranscriptionWork {
+            audio: vec![0.0; TARGET_SAMPLE_RATE as usize / 2],
+            recording_path: None,
+            transcriber: Arc::new(GatedTranscriber {
+                gate: Mutex::new(gate),
+            }),
+            output: OutputStrategy::UiOnly,
+            output_mode: OutputMode::UiOnly,
+            options: TranscribeOptions::default(),
+            postprocessor: PostProcessor::default(),
+        });
+        let processing = controller.processing_state();
+        assert!(
+            matches!(&processing, PttState::Processing { model, .. } if model == "base"),
+            "{processing:?}"
+        );
+        controller.set_state(processing);
+        controller.poll_transcription();
+        assert_eq!(base_status(&models), idle);
+
+        release.send(()).unwrap();
+        finish_transcription(&mut controller);
+        assert_eq!(base_status(&models), idle);
+        assert_eq!(models.lock().unwrap().transcript_history().len(), 1);
     }
 }
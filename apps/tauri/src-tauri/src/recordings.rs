@@ -0,0 +1,143 @@
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+pub const MAX_SAVED_RECORDINGS: usize = 20;
+const RECORDING_PREFIX: &str = "recording-";
+const RECORDING_EXTENSION: &str = "wav";
+
+#[derive(Debug, Clone)]
+pub struct RecordingStore {
+    dir: PathBuf,
+    keep: usize,
+}
+
+impl RecordingStore {
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self {
+            dir: dir.into(),
+            keep: MAX_SAVED_RECORDINGS,
+        }
+    }
+
+    pub fn for_model_root(model_root: &Path) -> Self {
+        let base = model_root.parent().unwrap_or(model_root);
+        Self::new(base.join("recordings"))
+    }
+
+    pub fn save(&self, audio: &[f32], timestamp_ms: u64) -> Result<PathBuf, String> {
+        fs::create_dir_all(&self.dir).map_err(|err| {
+            format!(
+                "failed to create recordings dir {}: {err}",
+                self.dir.display()
+            )
+        })?;
+        let path = self.dir.join(format!(
+            "{RECORDING_PREFIX}{timestamp_ms:016}.{RECORDING_EXTENSION}"
+        ));
+        transcribe_engine::write_wav(&path, audio).map_err(|err| err.to_string())?;
+        if let Err(err) = self.prune() {
+            log::warn!("failed to prune recordings: {err}");
+        }
+        Ok(path)
+    }
+
+    pub fn list(&self) -> io::Result<Vec<PathBuf>> {
+        let mut recordings = Vec::new();
+        for entry in fs::read_dir(&self.dir)? {
+            let path = entry?.path();
+            let is_recording = path.is_file()
+                && path.extension().and_then(|ext| ext.to_str()) == Some(RECORDING_EXTENSION)
+                && path
+                    .file_name()
+                    .and_then(|name| name.to_str())
+                    .is_some_and(|name| name.starts_with(RECORDING_PREFIX));
+            if is_recording {
+                recordings.push(path);
+            }
+        }
+        recordings.sort();
+        Ok(recordings)
+    }
+
+    pub fn prune(&self) -> io::Result<usize> {
+        let recordings = self.list()?;
+        let excess = recordings.len().saturating_sub(self.keep);
+        for path in &recordings[..excess] {
+            fs::remove_file(path)?;
+        }
+        Ok(excess)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_store() -> (tempfile::TempDir, RecordingStore) {
+        let dir = tempfile::tempdir().unwrap();
+        let store = RecordingStore::new(dir.path().join("recordings"));
+        (dir, store)
+    }
+
+    #[test]
+    fn saves_16k_mono_wav() {
+        let (_dir, store) = temp_store();
+
+        let path = store.save(&[0.0, 0.5, -0.5], 1_700_000_000_000).unwrap();
+
+        assert_eq!(
+            path.file_name().and_then(|name| name.to_str()),
+            Some("recording-0001700000000000.wav")
+        );
+        let bytes = fs::read(&path).unwrap();
+        assert_eq!(bytes[..4], *b"RIFF");
+        assert_eq!(bytes[8..12], *b"WAVE");
+        assert_eq!(u16::from_le_bytes([bytes[22], bytes[23]]), 1);
+        assert_eq!(
+            u32::from_le_bytes([bytes[24], bytes[25], bytes[26], bytes[27]]),
+            16_000
+        );
+        assert_eq!(bytes[36..40], *b"data");
+        assert_eq!(
+            u32::from_le_bytes([bytes[40], bytes[41], bytes[42], bytes[43]]),
+            3 * 2
+        );
+    }
+
+    #[test]
+    fn keeps_only_newest_recordings() {
+        let (_dir, store) = temp_store();
+        let store = RecordingStore { keep: 2, ..store };
+        fs::create_dir_all(&store.dir).unwrap();
+        let unrelated = store.dir.join("notes.txt");
+        fs::write(&unrelated, "keep me").unwrap();
+
+        for timestamp in [30, 10, 20] {
+            store.save(&[0.1], timestamp).unwrap();
+        }
+
+        let names: Vec<_> = store
+            .list()
+            .unwrap()
+            .iter()
+            .filter_map(|path| path.file_name()?.to_str().map(str::to_string))
+            .collect();
+        assert_eq!(
+            names,
+            vec![
+                "recording-0000000000000020.wav",
+                "recording-0000000000000030.wav"
+            ]
+        );
+        assert!(unrelated.exists());
+    }
+
+    #[test]
+    fn recordings_live_next_to_models() {
+        let store = RecordingStore::for_model_root(Path::new("/data/app/models"));
+
+        assert_eq!(store.dir, Path::new("/data/app/recordings"));
+    }
+}
@@ -1,25 +1,43 @@
-use crate::{logging::emit_app_event, ptt::PttHandle};
+use crate::{
+    logging::emit_app_event,
+    ptt::{PttHandle, PttHotkeyPayload},
+};
+use serde::Serialize;
 use shared_types::{
-    AppSettings, BackendEvent, BackendState, ModelInstallStatus, ModelStatusItem,
-    ModelStatusPayload, PttState, SettingsUpdate,
+    migrate_from_value, settings_schema_version, AppSettings, BackendEvent, BackendState,
+    MigrationError, ModelInstallStatus, ModelStatusItem, ModelStatusPayload, PttState,
+    SettingsUpdate, SettingsValidationError, TimedState, TranscriptEntry, TranscriptHistory,
+    TranscriptionStats, SETTINGS_SCHEMA_VERSION, TRANSCRIPTION_STATS_WINDOW,
 };
 use std::{
     collections::HashMap,
-    fs,
+    fmt, fs,
     path::{Path, PathBuf},
-    sync::{Arc, Mutex, MutexGuard},
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc, Mutex, MutexGuard,
+    },
+    time::{SystemTime, UNIX_EPOCH},
 };
 
 const BACKEND_STATE_EVENT: &str = "backend-state";
 
 pub struct StateMachine {
     state: BackendState,
+    entered_at_ms: u64,
+    clock: fn() -> u64,
 }
 
 impl StateMachine {
     pub fn new() -> Self {
+        Self::with_clock(epoch_ms)
+    }
+
+    pub fn with_clock(clock: fn() -> u64) -> Self {
         Self {
             state: BackendState::Idle,
+            entered_at_ms: clock(),
+            clock,
         }
     }
 
@@ -27,6 +45,13 @@ impl StateMachine {
         self.state.clone()
     }
 
+    pub fn current_timed(&self) -> TimedState {
+        TimedState {
+            state: self.state.clone(),
+            since_ms: self.entered_at_ms,
+        }
+    }
+
     pub fn apply(&mut self, event: BackendEvent) -> Result<BackendState, String> {
         let next = match (&self.state, event) {
             (BackendState::Idle, BackendEvent::StartRecording) => BackendState::Recording,
@@ -44,51 +69,124 @@ impl StateMachine {
         };
 
         self.state = next;
+        self.entered_at_ms = (self.clock)();
         Ok(self.state.clone())
     }
 }
 
+fn epoch_ms() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|elapsed| elapsed.as_millis() as u64)
+        .unwrap_or_default()
+}
+
 pub trait BackendStateEmitter: Send + Sync {
-    fn emit_state(&self, state: &BackendState);
+    fn emit_state(&self, state: &TimedState);
 }
 
 #[derive(Clone, Default)]
 pub struct AppStateEmitter;
 
 impl BackendStateEmitter for AppStateEmitter {
-    fn emit_state(&self, state: &BackendState) {
+    fn emit_state(&self, state: &TimedState) {
         emit_app_event(BACKEND_STATE_EVENT, state);
     }
 }
 
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum SettingsError {
+    Invalid {
+        errors: Vec<SettingsValidationError>,
+    },
+    Storage {
+        message: String,
+    },
+}
+
+impl fmt::Display for SettingsError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Invalid { errors } => {
+                let messages: Vec<String> = errors.iter().map(ToString::to_string).collect();
+                write!(f, "invalid settings: {}", messages.join("; "))
+            }
+            Self::Storage { message } => write!(f, "failed to save settings: {message}"),
+        }
+    }
+}
+
+impl From<Vec<SettingsValidationError>> for SettingsError {
+    fn from(errors: Vec<SettingsValidationError>) -> Self {
+        Self::Invalid { errors }
+    }
+}
+
+impl From<String> for SettingsError {
+    fn from(message: String) -> Self {
+        Self::Storage { message }
+    }
+}
+
 pub struct SettingsStore {
     path: PathBuf,
     settings: AppSettings,
+    load_error: Option<MigrationError>,
 }
 
 impl SettingsStore {
     pub fn new(path: PathBuf) -> Self {
-        let settings = load_settings(&path).unwrap_or_default();
-        Self { path, settings }
+        let mut store = Self {
+            path,
+            settings: AppSettings::default(),
+            load_error: None,
+        };
+        match load_settings(&store.path) {
+            Ok(None) => {}
+            Ok(Some(LoadedSettings { settings, migrated })) => {
+                store.settings = settings;
+                if migrated {
+                    if let Err(err) = store.persist() {
+                        log::warn!("failed to write migrated settings: {err}");
+                    }
+                }
+            }
+            Err(err) => {
+                log::error!(
+                    "failed to load settings from {}: {err}",
+                    store.path.display()
+                );
+                store.load_error = Some(err);
+            }
+        }
+        store
     }
 
     pub fn settings(&self) -> AppSettings {
         self.settings.clone()
     }
 
-    pub fn set(&mut self, settings: AppSettings) -> Result<AppSettings, String> {
+    pub fn set(&mut self, settings: AppSettings) -> Result<AppSettings, SettingsError> {
+        settings.validate()?;
+        validate_cancel_hotkey(&settings)?;
         self.settings = settings;
         self.persist()?;
         Ok(self.settings.clone())
     }
 
-    pub fn update(&mut self, update: SettingsUpdate) -> Result<AppSettings, String> {
-        self.settings = self.settings.apply_update(update);
+    pub fn update(&mut self, update: SettingsUpdate) -> Result<AppSettings, SettingsError> {
+        let settings = update.validated_apply(&self.settings)?;
+        validate_cancel_hotkey(&settings)?;
+        self.settings = settings;
         self.persist()?;
         Ok(self.settings.clone())
     }
 
     fn persist(&self) -> Result<(), String> {
+        if let Some(err @ MigrationError::UnsupportedVersion { .. }) = &self.load_error {
+            return Err(format!("refusing to overwrite settings file: {err}"));
+        }
         if let Some(parent) = self.path.parent() {
             fs::create_dir_all(parent).map_err(|err| err.to_string())?;
         }
@@ -97,9 +195,34 @@ impl SettingsStore {
     }
 }
 
-fn load_settings(path: &Path) -> Result<AppSettings, String> {
-    let payload = fs::read_to_string(path).map_err(|err| err.to_string())?;
-    serde_json::from_str(&payload).map_err(|err| err.to_string())
+// AppSettings::validate cannot resolve key names, so this catches unknown
+// keys and aliases like "esc" and "escape" that only collide once parsed.
+fn validate_cancel_hotkey(settings: &AppSettings) -> Result<(), SettingsError> {
+    let parse = |config| PttHotkeyPayload::from(config).to_hotkey().ok();
+    let Some(cancel) = parse(&settings.cancel_hotkey) else {
+        let value = settings.cancel_hotkey.key.trim().to_string();
+        return Err(vec![SettingsValidationError::UnknownCancelHotkey { value }].into());
+    };
+    if parse(&settings.hotkey) == Some(cancel) {
+        return Err(vec![SettingsValidationError::CancelHotkey].into());
+    }
+    Ok(())
+}
+
+struct LoadedSettings {
+    settings: AppSettings,
+    migrated: bool,
+}
+
+fn load_settings(path: &Path) -> Result<Option<LoadedSettings>, MigrationError> {
+    let Ok(payload) = fs::read_to_string(path) else {
+        return Ok(None);
+    };
+    let value: serde_json::Value =
+        serde_json::from_str(&payload).map_err(|err| MigrationError::Invalid(err.to_string()))?;
+    let migrated = settings_schema_version(&value)? != SETTINGS_SCHEMA_VERSION;
+    let settings = migrate_from_value(value)?;
+    Ok(Some(LoadedSettings { settings, migrated }))
 }
 
 pub struct BackendOrchestrator {
@@ -113,10 +236,11 @@ impl BackendOrchestrator {
         Self {
             machine: StateMachine::new(),
             settings: SettingsStore::new(settings_path),
-            emitter: Some(Arc::new(AppStateEmitter::default())),
+            emitter: Some(Arc::new(AppStateEmitter)),
         }
     }
 
+    #[cfg(test)]
     pub fn with_emitter(settings_path: PathBuf, emitter: Arc<dyn BackendStateEmitter>) -> Self {
         Self {
             machine: StateMachine::new(),
@@ -129,10 +253,14 @@ impl BackendOrchestrator {
         self.machine.current()
     }
 
+    pub fn current_timed_state(&self) -> TimedState {
+        self.machine.current_timed()
+    }
+
     pub fn apply_event(&mut self, event: BackendEvent) -> Result<BackendState, String> {
         let next = self.machine.apply(event)?;
         if let Some(emitter) = &self.emitter {
-            emitter.emit_state(&next);
+            emitter.emit_state(&self.machine.current_timed());
         }
         Ok(next)
     }
@@ -141,11 +269,14 @@ impl BackendOrchestrator {
         self.settings.settings()
     }
 
-    pub fn update_settings(&mut self, update: SettingsUpdate) -> Result<AppSettings, String> {
+    pub fn update_settings(
+        &mut self,
+        update: SettingsUpdate,
+    ) -> Result<AppSettings, SettingsError> {
         self.settings.update(update)
     }
 
-    pub fn set_settings(&mut self, settings: AppSettings) -> Result<AppSettings, String> {
+    pub fn set_settings(&mut self, settings: AppSettings) -> Result<AppSettings, SettingsError> {
         self.settings.set(settings)
     }
 }
@@ -155,22 +286,42 @@ pub struct AppState {
     pub models: Arc<Mutex<ModelStore>>,
     pub ptt: PttHandle,
     model_root: PathBuf,
+    startup_warning: Option<String>,
 }
 
 impl AppState {
     pub fn new(settings_path: PathBuf, model_root: PathBuf) -> Self {
+        let orchestrator = BackendOrchestrator::new(settings_path);
+        let (active_model, startup_warning) = match crate::ptt::restore_active_model(
+            &model_root,
+            orchestrator.settings().active_model,
+        ) {
+            Ok(active_model) => (active_model, None),
+            Err(warning) => {
+                log::warn!("{warning}");
+                (None, Some(warning))
+            }
+        };
         let models = Arc::new(Mutex::new(ModelStore::new()));
         if let Ok(mut store) = models.lock() {
-            let payload =
-                crate::ptt::build_model_status_payload(&model_root, None, &HashMap::new());
+            let payload = crate::ptt::build_model_status_payload(
+                &model_root,
+                active_model.as_deref(),
+                &HashMap::new(),
+            );
             let _ = store.set_models(payload.models);
             let _ = store.set_active_model(payload.active_model);
         }
+        let ptt = PttHandle::new(model_root.clone(), Arc::clone(&models));
+        if active_model.is_some() {
+            ptt.set_active_model(active_model);
+        }
         Self {
-            orchestrator: Mutex::new(BackendOrchestrator::new(settings_path)),
-            models: Arc::clone(&models),
-            ptt: PttHandle::new(model_root.clone(), models),
+            orchestrator: Mutex::new(orchestrator),
+            models,
+            ptt,
             model_root,
+            startup_warning,
         }
     }
 
@@ -197,13 +348,18 @@ impl AppState {
     pub fn model_root(&self) -> PathBuf {
         self.model_root.clone()
     }
+
+    pub fn startup_warning(&self) -> Option<String> {
+        self.startup_warning.clone()
+    }
 }
 
 pub struct ModelStore {
     models: Vec<ModelStatusItem>,
     active_model: Option<String>,
     overrides: HashMap<String, ModelInstallStatus>,
-    last_transcript: Option<String>,
+    downloads: HashMap<String, Arc<AtomicBool>>,
+    transcripts: TranscriptHistory,
 }
 
 impl ModelStore {
@@ -212,16 +368,19 @@ impl ModelStore {
             models: Vec::new(),
             active_model: None,
             overrides: HashMap::new(),
-            last_transcript: None,
+            downloads: HashMap::new(),
+            transcripts: TranscriptHistory::default(),
         }
     }
 
     pub fn snapshot(&self) -> ModelStatusPayload {
-        ModelStatusPayload {
+        let mut payload = ModelStatusPayload {
             models: self.models.clone(),
             active_model: self.active_model.clone(),
-            queue_count: queue_count(&self.models),
-        }
+            queue_count: 0,
+        };
+        payload.refresh_queue_count();
+        payload
     }
 
     pub fn set(&mut self, payload: ModelStatusPayload) -> ModelStatusPayload {
@@ -235,6 +394,35 @@ impl ModelStore {
         self.snapshot()
     }
 
+    pub fn merge_model(&mut self, item: ModelStatusItem) -> ModelStatusPayload {
+        let mut payload = self.snapshot();
+        payload.merge(item);
+        self.models = payload.models.clone();
+        payload
+    }
+
+    pub fn apply_progress(
+        &mut self,
+        id: &str,
+        downloaded_bytes: u64,
+        total_bytes: u64,
+        speed_bytes_per_sec: u64,
+        eta_seconds: u64,
+    ) -> Option<ModelStatusPayload> {
+        let mut payload = self.snapshot();
+        if !payload.apply_progress(
+            id,
+            downloaded_bytes,
+            total_bytes,
+            speed_bytes_per_sec,
+            eta_seconds,
+        ) {
+            return None;
+        }
+        self.models = payload.models.clone();
+        Some(payload)
+    }
+
     pub fn set_active_model(&mut self, active_model: Option<String>) -> ModelStatusPayload {
         self.active_model = active_model;
         self.snapshot()
@@ -248,6 +436,29 @@ impl ModelStore {
         self.overrides.remove(id);
     }
 
+    pub fn begin_download(&mut self, id: &str) -> Option<Arc<AtomicBool>> {
+        if self.downloads.contains_key(id) {
+            return None;
+        }
+        let cancel = Arc::new(AtomicBool::new(false));
+        self.downloads.insert(id.to_string(), Arc::clone(&cancel));
+        Some(cancel)
+    }
+
+    pub fn cancel_download(&mut self, id: &str) -> bool {
+        match self.downloads.get(id) {
+            Some(cancel) => {
+                cancel.store(true, Ordering::SeqCst);
+                true
+            }
+            None => false,
+        }
+    }
+
+    pub fn finish_download(&mut self, id: &str) {
+        self.downloads.remove(id);
+    }
+
     pub fn overrides_snapshot(&self) -> HashMap<String, ModelInstallStatus> {
         self.overrides.clone()
     }
@@ -256,27 +467,21 @@ impl ModelStore {
         self.active_model.clone()
     }
 
-    pub fn set_last_transcript(&mut self, text: String) {
-        self.last_transcript = Some(text);
+    pub fn record_transcript(&mut self, entry: TranscriptEntry) -> TranscriptEntry {
+        self.transcripts.push(entry).clone()
     }
 
     pub fn last_transcript(&self) -> Option<String> {
-        self.last_transcript.clone()
+        self.transcripts.latest().map(|entry| entry.text.clone())
     }
-}
 
-fn queue_count(models: &[ModelStatusItem]) -> usize {
-    models
-        .iter()
-        .filter(|model| {
-            matches!(
-                model.status,
-                ModelInstallStatus::Downloading
-                    | ModelInstallStatus::Queued
-                    | ModelInstallStatus::Pending
-            )
-        })
-        .count()
+    pub fn transcript_history(&self) -> Vec<TranscriptEntry> {
+        self.transcripts.list()
+    }
+
+    pub fn transcription_stats(&self) -> Vec<TranscriptionStats> {
+        self.transcripts.stats(TRANSCRIPTION_STATS_WINDOW)
+    }
 }
 
 pub fn default_settings_path(config_dir: Option<PathBuf>) -> PathBuf {
@@ -288,6 +493,7 @@ pub fn default_settings_path(config_dir: Option<PathBuf>) -> PathBuf {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use shared_types::HotkeyConfig;
     use std::sync::{Arc, Mutex};
 
     fn temp_settings_path() -> PathBuf {
@@ -300,11 +506,11 @@ mod tests {
 
     #[derive(Default)]
     struct TestEmitter {
-        states: Mutex<Vec<BackendState>>,
+        states: Mutex<Vec<TimedState>>,
     }
 
     impl TestEmitter {
-        fn states(&self) -> Vec<BackendState> {
+        fn states(&self) -> Vec<TimedState> {
             self.states
                 .lock()
                 .unwrap_or_else(|poisoned| poisoned.into_inner())
@@ -313,7 +519,7 @@ mod tests {
     }
 
     impl BackendStateEmitter for TestEmitter {
-        fn emit_state(&self, state: &BackendState) {
+        fn emit_state(&self, state: &TimedState) {
             let mut guard = self
                 .states
                 .lock()
@@ -381,6 +587,43 @@ mod tests {
         );
     }
 
+    fn ticking_clock() -> u64 {
+        thread_local! {
+            static NOW: std::cell::Cell<u64> = const { std::cell::Cell::new(1_000) };
+        }
+        NOW.with(|now| {
+            now.set(now.get() + 250);
+            now.get()
+        })
+    }
+
+    #[test]
+    fn transitions_stamp_entered_at() {
+        let mut machine = StateMachine::with_clock(ticking_clock);
+        let idle = machine.current_timed();
+
+        machine.apply(BackendEvent::StartRecording).unwrap();
+        let recording = machine.current_timed();
+        machine.apply(BackendEvent::StopRecording).unwrap();
+        let processing = machine.current_timed();
+
+        assert_eq!(recording.state, BackendState::Recording);
+        assert!(recording.since_ms > idle.since_ms);
+        assert_eq!(processing.state, BackendState::Processing);
+        assert!(processing.since_ms > recording.since_ms);
+    }
+
+    #[test]
+    fn rejected_transition_keeps_timestamp() {
+        let mut machine = StateMachine::with_clock(ticking_clock);
+        machine.apply(BackendEvent::StartRecording).unwrap();
+        let before = machine.current_timed();
+
+        assert!(machine.apply(BackendEvent::FinishProcessing).is_err());
+
+        assert_eq!(machine.current_timed(), before);
+    }
+
     #[test]
     fn invalid_transition_keeps_state() {
         let mut machine = StateMachine::new();
@@ -404,7 +647,8 @@ mod tests {
             .unwrap();
 
         assert_eq!(next, BackendState::Recording);
-        assert_eq!(emitter.states(), vec![BackendState::Recording]);
+        assert_eq!(emitter.states(), vec![orchestrator.current_timed_state()]);
+        assert_eq!(emitter.states()[0].state, BackendState::Recording);
     }
 
     #[test]
@@ -424,6 +668,106 @@ mod tests {
         assert_eq!(guard.current_state(), BackendState::Idle);
     }
 
+    #[test]
+    fn app_state_restores_persisted_active_model() {
+        let path = temp_settings_path();
+        let dir = tempfile::tempdir().expect("tempdir");
+        let root = dir.path().to_path_buf();
+        std::fs::write(root.join("ggml-small.bin"), [0u8; 16]).expect("write model");
+        SettingsStore::new(path.clone())
+            .update(SettingsUpdate {
+                active_model: Some("small".to_string()),
+                ..SettingsUpdate::default()
+            })
+            .expect("persist active model");
+
+        let restored = AppState::new(path.clone(), root.clone());
+        let restored_model = restored.lock_models().active_model();
+        let restored_warning = restored.startup_warning();
+        drop(restored);
+
+        std::fs::remove_file(root.join("ggml-small.bin")).expect("remove model");
+        let fallback = AppState::new(path.clone(), root.clone());
+        let fallback_model = fallback.lock_models().active_model();
+        let fallback_warning = fallback.startup_warning();
+        drop(fallback);
+        let _ = std::fs::remove_file(&path);
+
+        assert_eq!(restored_model.as_deref(), Some("small"));
+        assert_eq!(restored_warning, None);
+        assert_eq!(fallback_model, None);
+        assert!(fallback_warning.is_some_and(|warning| warning.contains("'small'")));
+    }
+
+    #[test]
+    fn model_store_merge_keeps_other_models() {
+        let item = |id: &str, status| ModelStatusItem {
+            id: id.to_string(),
+            name: id.to_string(),
+            status,
+            total_bytes: 0,
+            downloaded_bytes: 0,
+            speed_bytes_per_sec: 0,
+            eta_seconds: 0,
+            progress: 0.0,
+            active: false,
+        };
+        let mut store = ModelStore::new();
+        store.set_models(vec![
+            item("tiny", ModelInstallStatus::Ready),
+            item("base", ModelInstallStatus::Downloading),
+        ]);
+        store.set_active_model(Some("tiny".to_string()));
+
+        let payload = store.merge_model(item("base", ModelInstallStatus::Ready));
+
+        assert_eq!(payload.queue_count, 0);
+        assert_eq!(payload.active_model.as_deref(), Some("tiny"));
+        assert_eq!(store.snapshot(), payload);
+        assert_eq!(payload.models[0].id, "tiny");
+        assert_eq!(payload.models[1].status, ModelInstallStatus::Ready);
+    }
+
+    #[test]
+    fn model_store_tracks_download_cancellation() {
+        let mut store = ModelStore::new();
+        assert!(!store.cancel_download("base"));
+
+        let cancel = store.begin_download("base").expect("first download");
+        assert!(store.begin_download("base").is_none());
+        assert!(store.cancel_download("base"));
+        assert!(cancel.load(Ordering::SeqCst));
+
+        store.finish_download("base");
+        assert!(!store.cancel_download("base"));
+        assert!(store.begin_download("base").is_some());
+    }
+
+    #[test]
+    fn model_store_applies_download_progress() {
+        let mut store = ModelStore::new();
+        store.set_models(vec![ModelStatusItem {
+            id: "base".to_string(),
+            name: "base".to_string(),
+            status: ModelInstallStatus::Downloading,
+            total_bytes: 0,
+            downloaded_bytes: 0,
+            speed_bytes_per_sec: 0,
+            eta_seconds: 0,
+            progress: 0.0,
+            active: false,
+        }]);
+
+        let payload = store
+            .apply_progress("base", 400, 1_000, 200, 3)
+            .expect("known model");
+
+        assert_eq!(payload.models[0].downloaded_bytes, 400);
+        assert_eq!(payload.models[0].eta_seconds, 3);
+        assert_eq!(store.snapshot(), payload);
+        assert!(store.apply_progress("missing", 1, 2, 3, 4).is_none());
+    }
+
     #[test]
     fn settings_store_persists_updates() {
         let path = temp_settings_path();
@@ -441,4 +785,100 @@ mod tests {
         assert_eq!(updated, reloaded);
         let _ = fs::remove_file(&path);
     }
+
+    #[test]
+    fn settings_store_rejects_invalid_update_atomically() {
+        let path = temp_settings_path().with_extension("invalid.json");
+        let mut store = SettingsStore::new(path.clone());
+
+        let result = store.update(SettingsUpdate {
+            auto_export: Some(false),
+            latency_ms: Some(0),
+            ..SettingsUpdate::default()
+        });
+
+        assert!(matches!(
+            result,
+            Err(SettingsError::Invalid { ref errors }) if errors.len() == 1
+        ));
+        assert_eq!(store.settings(), AppSettings::default());
+        assert!(!path.exists());
+    }
+
+    #[test]
+    fn settings_store_rejects_cancel_hotkey_aliasing_the_ptt_hotkey() {
+        let path = temp_settings_path().with_extension("cancel.json");
+        let mut store = SettingsStore::new(path.clone());
+        let hotkey = |key: &str| HotkeyConfig {
+            key: key.to_string(),
+            ctrl: false,
+            alt: false,
+            shift: false,
+            meta: false,
+        };
+
+        let result = store.update(SettingsUpdate {
+            hotkey: Some(hotkey("esc")),
+            ..SettingsUpdate::default()
+        });
+        assert_eq!(
+            result,
+            Err(SettingsError::Invalid {
+                errors: vec![SettingsValidationError::CancelHotkey]
+            })
+        );
+
+        let result = store.update(SettingsUpdate {
+            cancel_hotkey: Some(hotkey("bogus")),
+            ..SettingsUpdate::default()
+        });
+        assert_eq!(
+            result,
+            Err(SettingsError::Invalid {
+                errors: vec![SettingsValidationError::UnknownCancelHotkey {
+                    value: "bogus".to_string()
+                }]
+            })
+        );
+        assert_eq!(store.settings(), AppSettings::default());
+        assert!(!path.exists());
+    }
+
+    #[test]
+    fn settings_store_upgrades_legacy_file() {
+        let path = temp_settings_path().with_extension("v0.json");
+        fs::write(&path, r#"{ "inputDevice": "USB Mic", "latency_ms": 900 }"#).unwrap();
+
+        let store = SettingsStore::new(path.clone());
+
+        assert!(store.load_error.is_none());
+        assert_eq!(store.settings().input_device, "USB Mic");
+        assert_eq!(store.settings().latency_ms, 900);
+        let written: serde_json::Value =
+            serde_json::from_str(&fs::read_to_string(&path).unwrap()).unwrap();
+        assert_eq!(written["schema_version"], SETTINGS_SCHEMA_VERSION);
+        assert_eq!(written["input_device"], "USB Mic");
+        let _ = fs::remove_file(&path);
+    }
+
+    #[test]
+    fn settings_store_keeps_future_version_file_untouched() {
+        let path = temp_settings_path().with_extension("future.json");
+        let payload = format!(
+            r#"{{ "schema_version": {}, "latency_ms": 900 }}"#,
+            SETTINGS_SCHEMA_VERSION + 1
+        );
+        fs::write(&path, &payload).unwrap();
+
+        let mut store = SettingsStore::new(path.clone());
+
+        assert!(matches!(
+            store.load_error.as_ref(),
+            Some(MigrationError::UnsupportedVersion { .. })
+        ));
+        assert_eq!(store.settings(), AppSettings::default());
+        assert!(store.set(AppSettings::default()).is_err());
+        assert_eq!(fs::read_to_string(&path).unwrap(), payload);
+        let _ = fs::remove_file(&path);
+    }
 }
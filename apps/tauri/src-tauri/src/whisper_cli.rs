@@ -1,9 +1,11 @@
 use log::{info, warn};
 use std::env;
-use std::io::Read;
+use std::fs::File;
+use std::io::{Read, Seek};
 use std::path::{Path, PathBuf};
 use std::process::Command;
 use std::thread;
+use transcribe_engine::{HttpDownloader, ModelDownloader, PartialDownload};
 
 const WHISPER_CPP_VERSION: &str = "v1.8.3";
 
@@ -57,8 +59,11 @@ fn ensure_whisper_cli_sync(app_data_dir: &Path) -> Result<(), String> {
     );
     info!("downloading whisper cli from {url}");
 
-    let bytes = download_bytes(&url)?;
-    let extracted = extract_cli(&bytes, &bin_path)?;
+    let archive_path = bin_path.with_extension("zip.part");
+    let extracted =
+        download_archive(&url, &archive_path).and_then(|archive| extract_cli(archive, &bin_path));
+    let _ = std::fs::remove_file(&archive_path);
+    let extracted = extracted?;
     if !extracted {
         return Err("whisper cli not found in archive".to_string());
     }
@@ -218,20 +223,20 @@ fn command_exists(cmd: &str) -> bool {
     Command::new(cmd).arg("--version").status().is_ok()
 }
 
-fn download_bytes(url: &str) -> Result<Vec<u8>, String> {
-    let response = ureq::get(url)
-        .call()
-        .map_err(|err| format!("download failed: {err}"))?;
-    let mut reader = response.into_reader();
-    let mut bytes = Vec::new();
-    reader
-        .read_to_end(&mut bytes)
+fn download_archive(url: &str, path: &Path) -> Result<File, String> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent).map_err(|err| err.to_string())?;
+    }
+    let mut partial = PartialDownload::open(path).map_err(|err| err.to_string())?;
+    partial.restart().map_err(|err| err.to_string())?;
+    HttpDownloader::from_env()
+        .download_to(url, &mut partial, &mut |_| {})
         .map_err(|err| format!("download failed: {err}"))?;
-    Ok(bytes)
+    drop(partial);
+    File::open(path).map_err(|err| err.to_string())
 }
 
-fn extract_cli(bytes: &[u8], bin_path: &Path) -> Result<bool, String> {
-    let reader = std::io::Cursor::new(bytes);
+fn extract_cli(reader: impl Read + Seek, bin_path: &Path) -> Result<bool, String> {
     let mut archive = zip::ZipArchive::new(reader).map_err(|err| err.to_string())?;
     let mut candidate = None;
 
@@ -263,14 +268,12 @@ fn extract_cli(bytes: &[u8], bin_path: &Path) -> Result<bool, String> {
         return Err("downloaded windows whisper.exe; no linux binary in release".to_string());
     }
     let mut file = archive.by_index(index).map_err(|err| err.to_string())?;
-    let mut buffer = Vec::new();
-    file.read_to_end(&mut buffer)
-        .map_err(|err| err.to_string())?;
 
     if let Some(parent) = bin_path.parent() {
         std::fs::create_dir_all(parent).map_err(|err| err.to_string())?;
     }
-    std::fs::write(bin_path, buffer).map_err(|err| err.to_string())?;
+    let mut output = File::create(bin_path).map_err(|err| err.to_string())?;
+    std::io::copy(&mut file, &mut output).map_err(|err| err.to_string())?;
 
     #[cfg(unix)]
     {
@@ -0,0 +1,250 @@
+use std::f64::consts::PI;
+
+pub const SPEECH_SAMPLE_RATE: u32 = 16_000;
+const ZERO_CROSSINGS: f64 = 32.0;
+const CUTOFF_SCALE: f64 = 0.97;
+const PHASES: usize = 256;
+const MAX_SINC_RATIO: u32 = 16;
+
+#[derive(Debug, Clone)]
+pub struct Resampler {
+    src_rate: u32,
+    dst_rate: u32,
+    taps: usize,
+    table: Vec<f32>,
+}
+
+impl Resampler {
+    pub fn new(src_rate: u32, dst_rate: u32) -> Self {
+        let src_rate = src_rate.max(1);
+        let dst_rate = dst_rate.max(1);
+        let cutoff = (dst_rate as f64 / src_rate as f64).min(1.0) * CUTOFF_SCALE;
+        let half = (ZERO_CROSSINGS / cutoff).ceil() as usize;
+        let taps = half * 2;
+        let mut table = Vec::with_capacity(PHASES * taps);
+        for phase in 0..PHASES {
+            let frac = phase as f64 / PHASES as f64;
+            let row: Vec<f64> = (0..taps)
+                .map(|tap| {
+                    let x = tap as f64 - (half - 1) as f64 - frac;
+                    cutoff * sinc(cutoff * x) * blackman(x, half as f64)
+                })
+                .collect();
+            let sum: f64 = row.iter().sum();
+            table.extend(row.iter().map(|value| (value / sum) as f32));
+        }
+        Self {
+            src_rate,
+            dst_rate,
+            taps,
+            table,
+        }
+    }
+
+    pub fn src_rate(&self) -> u32 {
+        self.src_rate
+    }
+
+    pub fn dst_rate(&self) -> u32 {
+        self.dst_rate
+    }
+
+    pub fn output_len(&self, input_len: usize) -> usize {
+        (input_len as f64 * self.dst_rate as f64 / self.src_rate as f64).round() as usize
+    }
+
+    pub fn process(&self, input: &[f32]) -> Vec<f32> {
+        if self.src_rate == self.dst_rate {
+            return input.to_vec();
+        }
+        let half = self.taps / 2;
+        let step = self.src_rate as f64 / self.dst_rate as f64;
+        (0..self.output_len(input.len()))
+            .map(|index| {
+                let position = index as f64 * step;
+                let mut base = position.floor() as isize;
+                let mut phase = ((position - base as f64) * PHASES as f64).round() as usize;
+                if phase == PHASES {
+                    base += 1;
+                    phase = 0;
+                }
+                let row = &self.table[phase * self.taps..(phase + 1) * self.taps];
+                let start = base - (half as isize - 1);
+                row.iter()
+                    .enumerate()
+                    .filter_map(|(tap, weight)| {
+                        let source = usize::try_from(start + tap as isize).ok()?;
+                        input.get(source).map(|sample| sample * weight)
+                    })
+                    .sum()
+            })
+            .collect()
+    }
+}
+
+pub fn resample_to_16k_mono(samples: Vec<f32>, src_rate: u32, channels: u16) -> Vec<f32> {
+    let mono = downmix_to_mono(samples, channels);
+    if mono.is_empty() || src_rate == SPEECH_SAMPLE_RATE || src_rate == 0 {
+        return mono;
+    }
+    if src_rate > SPEECH_SAMPLE_RATE * MAX_SINC_RATIO {
+        return resample_linear(&mono, src_rate, SPEECH_SAMPLE_RATE);
+    }
+    Resampler::new(src_rate, SPEECH_SAMPLE_RATE).process(&mono)
+}
+
+pub fn resample_linear(input: &[f32], src_rate: u32, dst_rate: u32) -> Vec<f32> {
+    if input.is_empty() || src_rate == dst_rate || src_rate == 0 || dst_rate == 0 {
+        return input.to_vec();
+    }
+
+    let target_len = ((input.len() as f64) * dst_rate as f64 / src_rate as f64).round() as usize;
+    let step = src_rate as f64 / dst_rate as f64;
+    let mut output = Vec::with_capacity(target_len);
+    for i in 0..target_len {
+        let src_pos = i as f64 * step;
+        let idx = src_pos.floor() as usize;
+        if idx >= input.len() {
+            break;
+        }
+        let frac = (src_pos - idx as f64) as f32;
+        let next = if idx + 1 < input.len() { idx + 1 } else { idx };
+        output.push(input[idx] + (input[next] - input[idx]) * frac);
+    }
+    output
+}
+
+pub fn downmix_to_mono(audio: Vec<f32>, channels: u16) -> Vec<f32> {
+    let channels = channels as usize;
+    match channels {
+        0 | 1 => audio,
+        _ => audio
+            .chunks_exact(channels)
+            .map(|frame| frame.iter().sum::<f32>() / channels as f32)
+            .collect(),
+    }
+}
+
+fn sinc(x: f64) -> f64 {
+    if x.abs() < 1e-9 {
+        1.0
+    } else {
+        (PI * x).sin() / (PI * x)
+    }
+}
+
+fn blackman(x: f64, half: f64) -> f64 {
+    if x.abs() >= half {
+        return 0.0;
+    }
+    let ratio = x / half;
+    0.42 + 0.5 * (PI * ratio).cos() + 0.08 * (2.0 * PI * ratio).cos()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        downmix_to_mono, resample_linear, resample_to_16k_mono, Resampler, SPEECH_SAMPLE_RATE,
+    };
+    use std::f64::consts::PI;
+
+    fn tone(frequency: f64, rate: u32, seconds: f64) -> Vec<f32> {
+        (0..(rate as f64 * seconds) as usize)
+            .map(|index| (2.0 * PI * frequency * index as f64 / rate as f64).sin() as f32)
+            .collect()
+    }
+
+    fn amplitude_at(samples: &[f32], frequency: f64, rate: u32) -> f64 {
+        let (mut re, mut im) = (0.0, 0.0);
+        for (index, sample) in samples.iter().enumerate() {
+            let angle = 2.0 * PI * frequency * index as f64 / rate as f64;
+            re += *sample as f64 * angle.cos();
+            im -= *sample as f64 * angle.sin();
+        }
+        2.0 * (re * re + im * im).sqrt() / samples.len() as f64
+    }
+
+    fn steady(samples: &[f32]) -> &[f32] {
+        &samples[200..samples.len() - 200]
+    }
+
+    #[test]
+    fn output_length_follows_rate_ratio() {
+        assert_eq!(
+            Resampler::new(48_000, 16_000).process(&[0.0; 4_800]).len(),
+            1_600
+        );
+        assert_eq!(
+            Resampler::new(44_100, 16_000).process(&[0.0; 4_410]).len(),
+            1_600
+        );
+        assert_eq!(
+            Resampler::new(8_000, 16_000).process(&[0.0; 800]).len(),
+            1_600
+        );
+        assert_eq!(
+            resample_to_16k_mono(vec![0.25; 8_820], 44_100, 2).len(),
+            1_600
+        );
+    }
+
+    #[test]
+    fn passes_through_matching_rate() {
+        let audio = vec![0.1, -0.2, 0.3];
+        assert_eq!(
+            resample_to_16k_mono(audio.clone(), SPEECH_SAMPLE_RATE, 1),
+            audio
+        );
+    }
+
+    #[test]
+    fn preserves_dc_level() {
+        let output = Resampler::new(48_000, 16_000).process(&[0.5; 4_800]);
+
+        assert!(steady(&output)
+            .iter()
+            .all(|sample| (sample - 0.5).abs() < 1e-3));
+    }
+
+    #[test]
+    fn keeps_in_band_tone_without_aliasing() {
+        let input = tone(7_000.0, 48_000, 0.5);
+        let output = Resampler::new(48_000, 16_000).process(&input);
+        let output = steady(&output);
+
+        let kept = amplitude_at(output, 7_000.0, SPEECH_SAMPLE_RATE);
+        assert!(kept > 0.5, "7 kHz amplitude {kept}");
+        for spurious in [1_000.0, 3_000.0, 5_000.0, 6_000.0] {
+            let amplitude = amplitude_at(output, spurious, SPEECH_SAMPLE_RATE);
+            assert!(amplitude < 0.01, "{spurious} Hz amplitude {amplitude}");
+        }
+    }
+
+    #[test]
+    fn rejects_tones_above_target_nyquist() {
+        let input = tone(10_000.0, 48_000, 0.5);
+
+        let sinc = Resampler::new(48_000, 16_000).process(&input);
+        let linear = resample_linear(&input, 48_000, 16_000);
+
+        let sinc_alias = amplitude_at(steady(&sinc), 6_000.0, SPEECH_SAMPLE_RATE);
+        let linear_alias = amplitude_at(steady(&linear), 6_000.0, SPEECH_SAMPLE_RATE);
+        assert!(sinc_alias < 0.01, "sinc alias {sinc_alias}");
+        assert!(linear_alias > 0.1, "linear alias {linear_alias}");
+    }
+
+    #[test]
+    fn downmixes_stereo_to_mono() {
+        let output = resample_to_16k_mono(vec![1.0, -1.0, 0.5, 0.5], SPEECH_SAMPLE_RATE, 2);
+        assert_eq!(output, vec![0.0, 0.5]);
+        assert_eq!(downmix_to_mono(vec![0.5, 0.25, 1.0], 2), vec![0.375]);
+    }
+
+    #[test]
+    fn linear_fallback_interpolates() {
+        let output = resample_linear(&[0.0, 1.0, 0.0, -1.0, 0.0], 44_100, 16_000);
+        assert_eq!(output.len(), 2);
+        assert!((output[0] - 0.0).abs() < 1e-6);
+        assert!((output[1] - -0.75625).abs() < 1e-4);
+    }
+}
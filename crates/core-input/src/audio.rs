@@ -1,6 +1,12 @@
 use crate::meter::{LevelMeter, LevelReading};
 use cpal::traits::{DeviceTrait, HostTrait, StreamTrait};
-use std::sync::{Arc, Mutex};
+use log::{info, warn};
+use std::sync::{
+    atomic::{AtomicBool, Ordering},
+    mpsc, Arc, Mutex,
+};
+use std::thread::JoinHandle;
+use std::time::Duration;
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct AudioDevice {
@@ -8,6 +14,78 @@ pub struct AudioDevice {
     pub name: String,
     pub sample_rate: u32,
     pub channels: u16,
+    pub sample_format: SampleFormat,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SampleFormat {
+    F32,
+    I16,
+    U16,
+    Unsupported,
+}
+
+impl From<cpal::SampleFormat> for SampleFormat {
+    fn from(format: cpal::SampleFormat) -> Self {
+        match format {
+            cpal::SampleFormat::F32 => Self::F32,
+            cpal::SampleFormat::I16 => Self::I16,
+            cpal::SampleFormat::U16 => Self::U16,
+            _ => Self::Unsupported,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct StreamRequest {
+    pub sample_rate: Option<u32>,
+    pub channels: Option<u16>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct StreamFormat {
+    pub sample_rate: u32,
+    pub channels: u16,
+    pub sample_format: SampleFormat,
+}
+
+impl StreamFormat {
+    pub fn satisfies(&self, request: &StreamRequest) -> bool {
+        request
+            .sample_rate
+            .is_none_or(|rate| rate == self.sample_rate)
+            && request
+                .channels
+                .is_none_or(|channels| channels == self.channels)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SelectedDevice {
+    Requested(AudioDevice),
+    Fallback(AudioDevice),
+}
+
+impl SelectedDevice {
+    pub fn device(&self) -> &AudioDevice {
+        match self {
+            Self::Requested(device) | Self::Fallback(device) => device,
+        }
+    }
+
+    pub fn is_fallback(&self) -> bool {
+        matches!(self, Self::Fallback(_))
+    }
+}
+
+impl From<&AudioDevice> for StreamFormat {
+    fn from(device: &AudioDevice) -> Self {
+        Self {
+            sample_rate: device.sample_rate,
+            channels: device.channels,
+            sample_format: device.sample_format,
+        }
+    }
 }
 
 #[derive(Debug, thiserror::Error)]
@@ -16,6 +94,8 @@ pub enum AudioError {
     Backend(String),
     #[error("no input devices available")]
     NoInputDevice,
+    #[error("no output devices available")]
+    NoOutputDevice,
     #[error("input device not found")]
     DeviceNotFound,
     #[error("audio capture already running")]
@@ -24,11 +104,14 @@ pub enum AudioError {
     NotRunning,
     #[error("level meter lock was poisoned")]
     MeterLockPoisoned,
+    #[error("audio input stream error: {0}")]
+    Stream(String),
 }
 
 pub trait AudioStream {
     fn start(&self) -> Result<(), AudioError>;
     fn stop(&self) -> Result<(), AudioError>;
+    fn format(&self) -> StreamFormat;
 }
 
 pub trait AudioBackend: Send + Sync + 'static {
@@ -36,37 +119,227 @@ pub trait AudioBackend: Send + Sync + 'static {
 
     fn list_input_devices(&self) -> Result<Vec<AudioDevice>, AudioError>;
     fn default_input_device(&self) -> Result<Option<AudioDevice>, AudioError>;
+    fn list_loopback_devices(&self) -> Result<Vec<AudioDevice>, AudioError> {
+        Ok(self
+            .list_input_devices()?
+            .into_iter()
+            .filter(is_monitor_device)
+            .collect())
+    }
     fn build_input_stream(
         &self,
         device: &AudioDevice,
+        request: &StreamRequest,
         on_samples: Box<dyn FnMut(&[f32]) + Send>,
+        on_error: Box<dyn FnMut(AudioError) + Send>,
     ) -> Result<Self::Stream, AudioError>;
 }
 
+const DUPLICATE_ID_SEPARATOR: char = '#';
+const MONITOR_SUFFIX: &str = ".monitor";
+
+pub fn is_monitor_device(device: &AudioDevice) -> bool {
+    device.name.to_lowercase().contains(MONITOR_SUFFIX)
+}
+
+fn assign_device_ids(names: &[String]) -> Vec<String> {
+    names
+        .iter()
+        .enumerate()
+        .map(|(index, name)| {
+            let ordinal = names[..index].iter().filter(|seen| *seen == name).count();
+            if ordinal == 0 {
+                name.clone()
+            } else {
+                format!("{name}{DUPLICATE_ID_SEPARATOR}{}", ordinal + 1)
+            }
+        })
+        .collect()
+}
+
+fn device_id_name(id: &str) -> &str {
+    let id = id.strip_prefix("default:").unwrap_or(id);
+    let id = match id.split_once(':') {
+        Some((index, name)) if index.parse::<usize>().is_ok() => name,
+        _ => id,
+    };
+    match id.rsplit_once(DUPLICATE_ID_SEPARATOR) {
+        Some((name, ordinal)) if ordinal.parse::<usize>().is_ok() => name,
+        _ => id,
+    }
+}
+
+fn normalize_device_name(name: &str) -> String {
+    name.chars()
+        .filter(|c| c.is_alphanumeric())
+        .flat_map(char::to_lowercase)
+        .collect()
+}
+
+pub(crate) fn resolve_device_index(ids: &[String], wanted: &str) -> Option<usize> {
+    if let Some(index) = ids.iter().position(|id| id == wanted) {
+        return Some(index);
+    }
+    let name = device_id_name(wanted);
+    if let Some(index) = ids.iter().position(|id| device_id_name(id) == name) {
+        return Some(index);
+    }
+    let needle = normalize_device_name(name);
+    if needle.is_empty() {
+        return None;
+    }
+    let normalized: Vec<String> = ids
+        .iter()
+        .map(|id| normalize_device_name(device_id_name(id)))
+        .collect();
+    if let Some(index) = normalized.iter().position(|candidate| *candidate == needle) {
+        return Some(index);
+    }
+    let mut fuzzy = normalized.iter().enumerate().filter(|(_, candidate)| {
+        !candidate.is_empty()
+            && (candidate.contains(&needle) || needle.contains(candidate.as_str()))
+    });
+    let (index, matched) = fuzzy.next()?;
+    // Duplicates of one device share a name; anything else is ambiguous.
+    fuzzy
+        .all(|(_, candidate)| candidate == matched)
+        .then_some(index)
+}
+
 fn normalize_u16_sample(value: u16) -> f32 {
     let midpoint = (u16::MAX as f32 + 1.0) / 2.0;
     (value as f32 - midpoint) / midpoint
 }
 
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DeviceChangeEvent {
+    Added(AudioDevice),
+    Removed(AudioDevice),
+    DefaultChanged(Option<AudioDevice>),
+}
+
+pub struct DeviceWatcher<B: AudioBackend> {
+    backend: Arc<B>,
+    devices: Vec<AudioDevice>,
+    default_device: Option<AudioDevice>,
+}
+
+impl<B: AudioBackend> DeviceWatcher<B> {
+    pub fn new(backend: Arc<B>) -> Result<Self, AudioError> {
+        let devices = backend.list_input_devices()?;
+        let default_device = backend.default_input_device()?;
+        Ok(Self {
+            backend,
+            devices,
+            default_device,
+        })
+    }
+
+    pub fn devices(&self) -> &[AudioDevice] {
+        &self.devices
+    }
+
+    pub fn poll(&mut self) -> Result<Vec<DeviceChangeEvent>, AudioError> {
+        let devices = self.backend.list_input_devices()?;
+        let default_device = self.backend.default_input_device()?;
+
+        let mut events: Vec<DeviceChangeEvent> = self
+            .devices
+            .iter()
+            .filter(|known| !devices.iter().any(|device| device.id == known.id))
+            .cloned()
+            .map(DeviceChangeEvent::Removed)
+            .collect();
+        events.extend(
+            devices
+                .iter()
+                .filter(|device| !self.devices.iter().any(|known| known.id == device.id))
+                .cloned()
+                .map(DeviceChangeEvent::Added),
+        );
+        if default_device != self.default_device {
+            events.push(DeviceChangeEvent::DefaultChanged(default_device.clone()));
+        }
+
+        self.devices = devices;
+        self.default_device = default_device;
+        Ok(events)
+    }
+
+    pub fn start(
+        mut self,
+        interval: Duration,
+    ) -> (DeviceWatcherHandle, mpsc::Receiver<DeviceChangeEvent>) {
+        let (sender, receiver) = mpsc::channel();
+        let stop = Arc::new(AtomicBool::new(false));
+        let stop_flag = Arc::clone(&stop);
+
+        let join_handle = std::thread::spawn(move || {
+            while !stop_flag.load(Ordering::SeqCst) {
+                std::thread::sleep(interval);
+                let events = match self.poll() {
+                    Ok(events) => events,
+                    Err(err) => {
+                        warn!("device poll failed: {err}");
+                        continue;
+                    }
+                };
+                for event in events {
+                    if sender.send(event).is_err() {
+                        return;
+                    }
+                }
+            }
+        });
+
+        (DeviceWatcherHandle { stop, join_handle }, receiver)
+    }
+}
+
+pub struct DeviceWatcherHandle {
+    stop: Arc<AtomicBool>,
+    join_handle: JoinHandle<()>,
+}
+
+impl DeviceWatcherHandle {
+    pub fn stop(self) {
+        self.stop.store(true, Ordering::SeqCst);
+        let _ = self.join_handle.join();
+    }
+}
+
 pub struct AudioCaptureService<B: AudioBackend> {
-    backend: B,
+    backend: Arc<B>,
     devices: Vec<AudioDevice>,
     selected_device: Option<AudioDevice>,
+    stream_request: StreamRequest,
     meter: Arc<Mutex<LevelMeter>>,
     stream: Option<B::Stream>,
+    paused: bool,
+    error_sender: mpsc::Sender<AudioError>,
+    error_receiver: Option<mpsc::Receiver<AudioError>>,
 }
 
 impl<B: AudioBackend> AudioCaptureService<B> {
     pub fn new(backend: B) -> Self {
+        let (error_sender, error_receiver) = mpsc::channel();
         Self {
-            backend,
+            backend: Arc::new(backend),
             devices: Vec::new(),
             selected_device: None,
+            stream_request: StreamRequest::default(),
             meter: Arc::new(Mutex::new(LevelMeter::new())),
             stream: None,
+            paused: false,
+            error_sender,
+            error_receiver: Some(error_receiver),
         }
     }
 
+    pub fn error_feed(&mut self) -> Option<mpsc::Receiver<AudioError>> {
+        self.error_receiver.take()
+    }
+
     pub fn refresh_devices(&mut self) -> Result<&[AudioDevice], AudioError> {
         self.devices = self.backend.list_input_devices()?;
         Ok(&self.devices)
@@ -76,28 +349,110 @@ impl<B: AudioBackend> AudioCaptureService<B> {
         &self.devices
     }
 
+    pub fn backend(&self) -> &B {
+        &self.backend
+    }
+
     pub fn select_device(&mut self, device_id: &str) -> Result<(), AudioError> {
-        if let Some(device) = self
-            .devices
-            .iter()
-            .find(|device| device.id == device_id)
-            .cloned()
+        if self
+            .selected_device
+            .as_ref()
+            .is_some_and(|selected| selected.id == device_id)
         {
-            self.selected_device = Some(device);
             return Ok(());
         }
+        let ids: Vec<String> = self
+            .devices
+            .iter()
+            .map(|device| device.id.clone())
+            .collect();
+        let index = resolve_device_index(&ids, device_id).ok_or(AudioError::DeviceNotFound)?;
+        if self.devices[index].id != device_id {
+            info!(
+                "resolved input device {device_id:?} to {:?}",
+                self.devices[index].id
+            );
+        }
+        self.selected_device = Some(self.devices[index].clone());
+        Ok(())
+    }
 
-        Err(AudioError::DeviceNotFound)
+    pub fn select_device_or_default(
+        &mut self,
+        device_id: &str,
+    ) -> Result<SelectedDevice, AudioError> {
+        match self.select_device(device_id) {
+            Ok(()) => self
+                .selected_device
+                .clone()
+                .map(SelectedDevice::Requested)
+                .ok_or(AudioError::DeviceNotFound),
+            Err(AudioError::DeviceNotFound) => {
+                let fallback = match self.backend.default_input_device()? {
+                    Some(device) => device,
+                    None => self
+                        .devices
+                        .first()
+                        .cloned()
+                        .ok_or(AudioError::NoInputDevice)?,
+                };
+                warn!(
+                    "input device {device_id:?} not found, falling back to {:?}",
+                    fallback.id
+                );
+                self.selected_device = Some(fallback.clone());
+                Ok(SelectedDevice::Fallback(fallback))
+            }
+            Err(err) => Err(err),
+        }
     }
 
     pub fn selected_device(&self) -> Option<&AudioDevice> {
         self.selected_device.as_ref()
     }
 
+    pub fn clear_selected_device(&mut self) {
+        self.selected_device = None;
+    }
+
+    pub fn device_watcher(&self) -> Result<DeviceWatcher<B>, AudioError> {
+        DeviceWatcher::new(Arc::clone(&self.backend))
+    }
+
+    pub fn stream_request(&self) -> StreamRequest {
+        self.stream_request
+    }
+
+    pub fn set_stream_request(&mut self, request: StreamRequest) {
+        self.stream_request = request;
+    }
+
     pub fn is_running(&self) -> bool {
         self.stream.is_some()
     }
 
+    pub fn is_paused(&self) -> bool {
+        self.paused
+    }
+
+    pub fn pause(&mut self) -> Result<(), AudioError> {
+        let stream = self.stream.as_ref().ok_or(AudioError::NotRunning)?;
+        if !self.paused {
+            stream.stop()?;
+            self.paused = true;
+        }
+        Ok(())
+    }
+
+    pub fn resume(&mut self) -> Result<(), AudioError> {
+        let stream = self.stream.as_ref().ok_or(AudioError::NotRunning)?;
+        if self.paused {
+            stream.start()?;
+            self.paused = false;
+        }
+        Ok(())
+    }
+
     pub fn start(&mut self) -> Result<(), AudioError> {
         self.start_internal(None)
     }
@@ -135,20 +490,43 @@ impl<B: AudioBackend> AudioCaptureService<B> {
             }
         };
 
-        let stream = self
-            .backend
-            .build_input_stream(&device, Box::new(move |samples| on_samples(samples)))?;
+        let error_sender = self.error_sender.clone();
+        let on_error = move |err: AudioError| {
+            warn!("audio input stream error: {err}");
+            let _ = error_sender.send(err);
+        };
+
+        let stream = self.backend.build_input_stream(
+            &device,
+            &self.stream_request,
+            Box::new(move |samples| on_samples(samples)),
+            Box::new(on_error),
+        )?;
+        let format = stream.format();
         if let Ok(mut meter) = self.meter.lock() {
+            meter.set_sample_rate(format.sample_rate, format.channels);
             meter.reset();
         }
         stream.start()?;
-        self.selected_device = Some(device.clone());
+        if !format.satisfies(&self.stream_request) {
+            info!(
+                "stream request {:?} not honored by {}; using {:?}",
+                self.stream_request, device.name, format
+            );
+        }
+        self.selected_device = Some(AudioDevice {
+            sample_rate: format.sample_rate,
+            channels: format.channels,
+            sample_format: format.sample_format,
+            ..device
+        });
         self.stream = Some(stream);
         Ok(())
     }
 
     pub fn stop(&mut self) -> Result<(), AudioError> {
         let stream = self.stream.take().ok_or(AudioError::NotRunning)?;
+        self.paused = false;
         stream.stop()?;
         Ok(())
     }
@@ -176,6 +554,7 @@ impl Default for CpalAudioBackend {
 
 pub struct CpalAudioStream {
     stream: cpal::Stream,
+    format: StreamFormat,
 }
 
 impl AudioStream for CpalAudioStream {
@@ -190,6 +569,47 @@ impl AudioStream for CpalAudioStream {
             .pause()
             .map_err(|err| AudioError::Backend(err.to_string()))
     }
+
+    fn format(&self) -> StreamFormat {
+        self.format
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct SupportedRange {
+    channels: u16,
+    min_sample_rate: u32,
+    max_sample_rate: u32,
+    sample_format: SampleFormat,
+}
+
+fn pick_stream_format(
+    ranges: &[SupportedRange],
+    request: &StreamRequest,
+    fallback: StreamFormat,
+) -> StreamFormat {
+    if *request == StreamRequest::default() {
+        return fallback;
+    }
+    let wanted_rate = request.sample_rate.unwrap_or(fallback.sample_rate);
+    let wanted_channels = request.channels.unwrap_or(fallback.channels);
+
+    ranges
+        .iter()
+        .filter(|range| range.sample_format != SampleFormat::Unsupported)
+        .map(|range| StreamFormat {
+            sample_rate: wanted_rate.clamp(range.min_sample_rate, range.max_sample_rate),
+            channels: range.channels,
+            sample_format: range.sample_format,
+        })
+        .min_by_key(|format| {
+            (
+                format.sample_rate.abs_diff(wanted_rate),
+                format.channels.abs_diff(wanted_channels),
+                format.sample_format != fallback.sample_format,
+            )
+        })
+        .unwrap_or(fallback)
 }
 
 impl CpalAudioBackend {
@@ -201,17 +621,21 @@ impl CpalAudioBackend {
                 .ok_or(AudioError::NoInputDevice);
         }
 
-        let mut parts = device.id.splitn(2, ':');
-        let index = parts
-            .next()
-            .and_then(|value| value.parse::<usize>().ok())
-            .ok_or(AudioError::DeviceNotFound)?;
-
-        let mut devices = self
+        let devices: Vec<cpal::Device> = self
             .host
             .input_devices()
-            .map_err(|err| AudioError::Backend(err.to_string()))?;
-        devices.nth(index).ok_or(AudioError::DeviceNotFound)
+            .map_err(|err| AudioError::Backend(err.to_string()))?
+            .collect();
+        let names: Vec<String> = devices
+            .iter()
+            .map(|device| device.name().unwrap_or_default())
+            .collect();
+        let index = resolve_device_index(&assign_device_ids(&names), &device.id)
+            .ok_or(AudioError::DeviceNotFound)?;
+        devices
+            .into_iter()
+            .nth(index)
+            .ok_or(AudioError::DeviceNotFound)
     }
 }
 
@@ -220,11 +644,10 @@ impl AudioBackend for CpalAudioBackend {
 
     fn list_input_devices(&self) -> Result<Vec<AudioDevice>, AudioError> {
         let mut devices = Vec::new();
-        for (index, device) in self
+        for device in self
             .host
             .input_devices()
             .map_err(|err| AudioError::Backend(err.to_string()))?
-            .enumerate()
         {
             let name = device
                 .name()
@@ -233,15 +656,31 @@ impl AudioBackend for CpalAudioBackend {
                 .default_input_config()
                 .map_err(|err| AudioError::Backend(err.to_string()))?;
             devices.push(AudioDevice {
-                id: format!("{}:{}", index, name),
+                id: String::new(),
                 name,
                 sample_rate: default_config.sample_rate().0,
                 channels: default_config.channels(),
+                sample_format: default_config.sample_format().into(),
             });
         }
+        let names: Vec<String> = devices.iter().map(|device| device.name.clone()).collect();
+        for (device, id) in devices.iter_mut().zip(assign_device_ids(&names)) {
+            device.id = id;
+        }
         Ok(devices)
     }
 
+    fn list_loopback_devices(&self) -> Result<Vec<AudioDevice>, AudioError> {
+        if !cfg!(target_os = "linux") {
+            return Ok(Vec::new());
+        }
+        Ok(self
+            .list_input_devices()?
+            .into_iter()
+            .filter(is_monitor_device)
+            .collect())
+    }
+
     fn default_input_device(&self) -> Result<Option<AudioDevice>, AudioError> {
         let device = match self.host.default_input_device() {
             Some(device) => device,
@@ -260,26 +699,52 @@ impl AudioBackend for CpalAudioBackend {
             name,
             sample_rate: default_config.sample_rate().0,
             channels: default_config.channels(),
+            sample_format: default_config.sample_format().into(),
         }))
     }
 
     fn build_input_stream(
         &self,
         device: &AudioDevice,
+        request: &StreamRequest,
         mut on_samples: Box<dyn FnMut(&[f32]) + Send>,
+        mut on_error: Box<dyn FnMut(AudioError) + Send>,
     ) -> Result<Self::Stream, AudioError> {
         let device = self.device_from_id(device)?;
         let default_config = device
             .default_input_config()
             .map_err(|err| AudioError::Backend(err.to_string()))?;
-        let stream_config: cpal::StreamConfig = default_config.clone().into();
+        let fallback = StreamFormat {
+            sample_rate: default_config.sample_rate().0,
+            channels: default_config.channels(),
+            sample_format: default_config.sample_format().into(),
+        };
+        let ranges: Vec<SupportedRange> = device
+            .supported_input_configs()
+            .map(|configs| {
+                configs
+                    .map(|range| SupportedRange {
+                        channels: range.channels(),
+                        min_sample_rate: range.min_sample_rate().0,
+                        max_sample_rate: range.max_sample_rate().0,
+                        sample_format: range.sample_format().into(),
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+        let format = pick_stream_format(&ranges, request, fallback);
+        let stream_config = cpal::StreamConfig {
+            channels: format.channels,
+            sample_rate: cpal::SampleRate(format.sample_rate),
+            buffer_size: cpal::BufferSize::Default,
+        };
 
-        let error_callback = |err| {
-            eprintln!("audio input stream error: {err}");
+        let error_callback = move |err: cpal::StreamError| {
+            on_error(AudioError::Stream(err.to_string()));
         };
 
-        let stream = match default_config.sample_format() {
-            cpal::SampleFormat::F32 => device
+        let stream = match format.sample_format {
+            SampleFormat::F32 => device
                 .build_input_stream(
                     &stream_config,
                     move |data: &[f32], _| on_samples(data),
@@ -287,7 +752,7 @@ impl AudioBackend for CpalAudioBackend {
                     None,
                 )
                 .map_err(|err| AudioError::Backend(err.to_string()))?,
-            cpal::SampleFormat::I16 => device
+            SampleFormat::I16 => device
                 .build_input_stream(
                     &stream_config,
                     move |data: &[i16], _| {
@@ -301,7 +766,7 @@ impl AudioBackend for CpalAudioBackend {
                     None,
                 )
                 .map_err(|err| AudioError::Backend(err.to_string()))?,
-            cpal::SampleFormat::U16 => device
+            SampleFormat::U16 => device
                 .build_input_stream(
                     &stream_config,
                     move |data: &[u16], _| {
@@ -315,32 +780,48 @@ impl AudioBackend for CpalAudioBackend {
                     None,
                 )
                 .map_err(|err| AudioError::Backend(err.to_string()))?,
-            _ => return Err(AudioError::Backend("unsupported sample format".to_string())),
+            SampleFormat::Unsupported => {
+                return Err(AudioError::Backend("unsupported sample format".to_string()))
+            }
         };
 
-        Ok(CpalAudioStream { stream })
+        Ok(CpalAudioStream { stream, format })
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::{
-        normalize_u16_sample, AudioBackend, AudioCaptureService, AudioDevice, AudioError,
-        AudioStream,
+        assign_device_ids, device_id_name, is_monitor_device, normalize_u16_sample,
+        pick_stream_format, resolve_device_index, AudioBackend, AudioCaptureService, AudioDevice,
+        AudioError, AudioStream, DeviceChangeEvent, SampleFormat, SelectedDevice, StreamFormat,
+        StreamRequest, SupportedRange,
     };
     use crate::meter::LevelReading;
     use std::sync::{
         atomic::{AtomicBool, Ordering},
         Arc, Mutex,
     };
+    use std::time::Duration;
+
+    type ErrorCallback = Box<dyn FnMut(AudioError) + Send>;
 
     #[derive(Clone)]
     struct MockStreamController {
         running: Arc<AtomicBool>,
         callback: Arc<Mutex<Option<Box<dyn FnMut(&[f32]) + Send>>>>,
+        on_error: Arc<Mutex<Option<ErrorCallback>>>,
     }
 
     impl MockStreamController {
+        fn fail(&self, err: AudioError) {
+            if let Ok(mut on_error) = self.on_error.lock() {
+                if let Some(handler) = on_error.as_mut() {
+                    handler(err);
+                }
+            }
+        }
+
         fn push_samples(&self, samples: &[f32]) {
             if !self.running.load(Ordering::SeqCst) {
                 return;
@@ -356,6 +837,7 @@ mod tests {
 
     struct MockStream {
         controller: MockStreamController,
+        format: StreamFormat,
     }
 
     impl AudioStream for MockStream {
@@ -368,22 +850,37 @@ mod tests {
             self.controller.running.store(false, Ordering::SeqCst);
             Ok(())
         }
+
+        fn format(&self) -> StreamFormat {
+            self.format
+        }
     }
 
     #[derive(Clone)]
     struct MockAudioBackend {
-        devices: Vec<AudioDevice>,
+        devices: Arc<Mutex<Vec<AudioDevice>>>,
         controller: Arc<Mutex<Option<MockStreamController>>>,
+        honor_requests: bool,
     }
 
     impl MockAudioBackend {
         fn new(devices: Vec<AudioDevice>) -> Self {
             Self {
-                devices,
+                devices: Arc::new(Mutex::new(devices)),
                 controller: Arc::new(Mutex::new(None)),
+                honor_requests: false,
             }
         }
 
+        fn honoring_requests(mut self) -> Self {
+            self.honor_requests = true;
+            self
+        }
+
+        fn set_devices(&self, devices: Vec<AudioDevice>) {
+            *self.devices.lock().expect("devices lock") = devices;
+        }
+
         fn controller(&self) -> Option<MockStreamController> {
             self.controller.lock().ok()?.clone()
         }
@@ -393,28 +890,36 @@ mod tests {
         type Stream = MockStream;
 
         fn list_input_devices(&self) -> Result<Vec<AudioDevice>, AudioError> {
-            Ok(self.devices.clone())
+            Ok(self.devices.lock().expect("devices lock").clone())
         }
 
         fn default_input_device(&self) -> Result<Option<AudioDevice>, AudioError> {
-            Ok(self.devices.first().cloned())
+            Ok(self.devices.lock().expect("devices lock").first().cloned())
         }
 
         fn build_input_stream(
             &self,
-            _device: &AudioDevice,
+            device: &AudioDevice,
+            request: &StreamRequest,
             on_samples: Box<dyn FnMut(&[f32]) + Send>,
+            on_error: ErrorCallback,
         ) -> Result<Self::Stream, AudioError> {
             let controller = MockStreamController {
                 running: Arc::new(AtomicBool::new(false)),
                 callback: Arc::new(Mutex::new(Some(on_samples))),
+                on_error: Arc::new(Mutex::new(Some(on_error))),
             };
 
             if let Ok(mut stored) = self.controller.lock() {
                 *stored = Some(controller.clone());
             }
 
-            Ok(MockStream { controller })
+            let mut format = StreamFormat::from(device);
+            if self.honor_requests {
+                format.sample_rate = request.sample_rate.unwrap_or(format.sample_rate);
+                format.channels = request.channels.unwrap_or(format.channels);
+            }
+            Ok(MockStream { controller, format })
         }
     }
 
@@ -425,6 +930,7 @@ mod tests {
             name: "Mock".to_string(),
             sample_rate: 48_000,
             channels: 2,
+            sample_format: SampleFormat::F32,
         }]);
         let controller_handle = backend.controller.clone();
         let mut service = AudioCaptureService::new(backend);
@@ -443,6 +949,25 @@ mod tests {
         assert!(reading.peak > 0.0);
     }
 
+    #[test]
+    fn capture_service_forwards_stream_errors() {
+        let backend = MockAudioBackend::new(vec![device("0:Mock")]);
+        let mut service = AudioCaptureService::new(backend.clone());
+        let errors = service.error_feed().expect("error feed");
+        assert!(service.error_feed().is_none());
+        service.start().expect("start capture");
+
+        backend
+            .controller()
+            .expect("controller ready")
+            .fail(AudioError::Stream("device unplugged".to_string()));
+
+        let err = errors
+            .recv_timeout(Duration::from_millis(50))
+            .expect("stream error");
+        assert!(matches!(err, AudioError::Stream(message) if message == "device unplugged"));
+    }
+
     #[test]
     fn capture_service_returns_silence_before_audio() {
         let backend = MockAudioBackend::new(vec![AudioDevice {
@@ -450,6 +975,7 @@ mod tests {
             name: "Mock".to_string(),
             sample_rate: 48_000,
             channels: 2,
+            sample_format: SampleFormat::F32,
         }]);
         let service = AudioCaptureService::new(backend);
         let reading = service.level().expect("meter");
@@ -463,6 +989,7 @@ mod tests {
             name: "Mock".to_string(),
             sample_rate: 48_000,
             channels: 2,
+            sample_format: SampleFormat::F32,
         }]);
         let mut service = AudioCaptureService::new(backend);
         assert!(!service.is_running());
@@ -479,6 +1006,7 @@ mod tests {
             name: "Mock".to_string(),
             sample_rate: 48_000,
             channels: 2,
+            sample_format: SampleFormat::F32,
         }]);
         let mut service = AudioCaptureService::new(backend);
         service.start().expect("start capture");
@@ -496,6 +1024,7 @@ mod tests {
             name: "Mock".to_string(),
             sample_rate: 48_000,
             channels: 2,
+            sample_format: SampleFormat::F32,
         }]);
         let controller_handle = backend.controller.clone();
         let mut service = AudioCaptureService::new(backend);
@@ -516,6 +1045,320 @@ mod tests {
         assert_eq!(reading, LevelReading::silence());
     }
 
+    fn device(id: &str) -> AudioDevice {
+        AudioDevice {
+            id: id.to_string(),
+            name: id.to_string(),
+            sample_rate: 48_000,
+            channels: 1,
+            sample_format: SampleFormat::F32,
+        }
+    }
+
+    #[test]
+    fn selected_device_reports_backend_stream_format() {
+        let usb = AudioDevice {
+            id: "1:USB".to_string(),
+            name: "USB".to_string(),
+            sample_rate: 44_100,
+            channels: 1,
+            sample_format: SampleFormat::I16,
+        };
+        let backend = MockAudioBackend::new(vec![device("0:Built-in"), usb.clone()]);
+        let mut service = AudioCaptureService::new(backend);
+        service.refresh_devices().expect("devices");
+        service.select_device("1:USB").expect("select device");
+        service.start().expect("start capture");
+
+        assert_eq!(service.selected_device(), Some(&usb));
+    }
+
+    fn host_devices(names: &[&str]) -> Vec<AudioDevice> {
+        let names: Vec<String> = names.iter().map(|name| name.to_string()).collect();
+        assign_device_ids(&names)
+            .into_iter()
+            .zip(&names)
+            .map(|(id, name)| AudioDevice {
+                name: name.clone(),
+                ..device(&id)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn device_ids_are_name_based_with_duplicate_suffix() {
+        let ids: Vec<String> = host_devices(&["USB Mic", "Built-in", "USB Mic"])
+            .into_iter()
+            .map(|device| device.id)
+            .collect();
+
+        assert_eq!(ids, vec!["USB Mic", "Built-in", "USB Mic#2"]);
+        assert_eq!(device_id_name("USB Mic#2"), "USB Mic");
+        assert_eq!(device_id_name("3:USB Mic"), "USB Mic");
+        assert_eq!(device_id_name("default:hw:1,0"), "hw:1,0");
+    }
+
+    #[test]
+    fn device_ids_resolve_by_name_and_fuzzy_match() {
+        let ids: Vec<String> = host_devices(&["Webcam", "USB Mic", "USB Mic"])
+            .into_iter()
+            .map(|device| device.id)
+            .collect();
+
+        assert_eq!(resolve_device_index(&ids, "USB Mic#2"), Some(2));
+        assert_eq!(resolve_device_index(&ids, "0:USB Mic"), Some(1));
+        assert_eq!(resolve_device_index(&ids, "usb-mic (analog)"), Some(1));
+        assert_eq!(resolve_device_index(&ids, "usb mic"), Some(1));
+        assert_eq!(resolve_device_index(&ids, "Webcam Pro"), Some(0));
+        assert_eq!(resolve_device_index(&ids, "Bluetooth"), None);
+    }
+
+    #[test]
+    fn device_ids_prefer_exact_names_and_reject_ambiguous_fuzzy_matches() {
+        let ids: Vec<String> = host_devices(&["USB Mic Pro", "USB Mic", "Webcam"])
+            .into_iter()
+            .map(|device| device.id)
+            .collect();
+
+        assert_eq!(resolve_device_index(&ids, "usb-mic"), Some(1));
+        assert_eq!(resolve_device_index(&ids, "USB"), None);
+        assert_eq!(resolve_device_index(&ids, "usb mic pro (analog)"), None);
+        assert_eq!(resolve_device_index(&ids, "Webcam (front)"), Some(2));
+    }
+
+    #[test]
+    fn loopback_devices_are_monitor_sources() {
+        let backend = MockAudioBackend::new(host_devices(&[
+            "Built-in",
+            "alsa_output.pci-0000_00_1f.3.analog-stereo.monitor",
+            "USB Mic",
+        ]));
+
+        let loopback = backend.list_loopback_devices().expect("loopback devices");
+        assert_eq!(loopback.len(), 1);
+        assert_eq!(
+            loopback[0].id,
+            "alsa_output.pci-0000_00_1f.3.analog-stereo.monitor"
+        );
+        assert!(is_monitor_device(&loopback[0]));
+        assert!(!is_monitor_device(&host_devices(&["USB Mic"])[0]));
+    }
+
+    #[test]
+    fn selection_survives_device_reorder() {
+        let backend = MockAudioBackend::new(host_devices(&["Built-in", "USB Mic"]));
+        let handle = backend.clone();
+        let mut service = AudioCaptureService::new(backend);
+        service.refresh_devices().expect("devices");
+        service.select_device("USB Mic").expect("select device");
+        let saved = service.selected_device().expect("selected").id.clone();
+
+        handle.set_devices(host_devices(&["Webcam", "Built-in", "USB Mic"]));
+        let mut service = AudioCaptureService::new(handle.clone());
+        service.refresh_devices().expect("devices");
+        service.select_device(&saved).expect("select saved device");
+        assert_eq!(service.selected_device().expect("selected").name, "USB Mic");
+
+        service
+            .select_device("1:USB Mic")
+            .expect("select legacy id");
+        assert_eq!(service.selected_device().expect("selected").name, "USB Mic");
+        assert!(matches!(
+            service.select_device("Bluetooth"),
+            Err(AudioError::DeviceNotFound)
+        ));
+    }
+
+    #[test]
+    fn missing_device_falls_back_to_default() {
+        let backend = MockAudioBackend::new(host_devices(&["Built-in", "USB Mic"]));
+        let handle = backend.clone();
+        let mut service = AudioCaptureService::new(backend);
+        service.refresh_devices().expect("devices");
+
+        let chosen = service
+            .select_device_or_default("USB Mic")
+            .expect("configured device");
+        assert!(!chosen.is_fallback());
+        assert_eq!(chosen.device().name, "USB Mic");
+
+        let chosen = service
+            .select_device_or_default("Bluetooth Headset")
+            .expect("fallback device");
+        assert!(matches!(&chosen, SelectedDevice::Fallback(device) if device.name == "Built-in"));
+        assert_eq!(service.selected_device(), Some(chosen.device()));
+
+        handle.set_devices(Vec::new());
+        service.refresh_devices().expect("devices");
+        assert!(matches!(
+            service.select_device_or_default("Bluetooth Headset"),
+            Err(AudioError::NoInputDevice)
+        ));
+    }
+
+    const SPEECH: StreamRequest = StreamRequest {
+        sample_rate: Some(16_000),
+        channels: Some(1),
+    };
+
+    #[test]
+    fn honored_stream_request_updates_selected_device() {
+        let backend = MockAudioBackend::new(vec![device("0:Built-in")]).honoring_requests();
+        let mut service = AudioCaptureService::new(backend);
+        service.set_stream_request(SPEECH);
+        service.start().expect("start capture");
+
+        let selected = service.selected_device().expect("device selected");
+        assert_eq!((selected.sample_rate, selected.channels), (16_000, 1));
+        assert_eq!(selected.id, "0:Built-in");
+    }
+
+    #[test]
+    fn rejected_stream_request_keeps_device_format() {
+        let backend = MockAudioBackend::new(vec![device("0:Built-in")]);
+        let mut service = AudioCaptureService::new(backend);
+        service.set_stream_request(SPEECH);
+        service.start().expect("start capture");
+
+        let selected = service.selected_device().expect("device selected");
+        assert_eq!((selected.sample_rate, selected.channels), (48_000, 1));
+        assert!(!StreamFormat::from(selected).satisfies(&SPEECH));
+    }
+
+    #[test]
+    fn picks_closest_supported_stream_format() {
+        let fallback = StreamFormat {
+            sample_rate: 48_000,
+            channels: 2,
+            sample_format: SampleFormat::F32,
+        };
+        let range = |channels, min_sample_rate, max_sample_rate, sample_format| SupportedRange {
+            channels,
+            min_sample_rate,
+            max_sample_rate,
+            sample_format,
+        };
+
+        let ranges = [
+            range(2, 8_000, 96_000, SampleFormat::F32),
+            range(1, 8_000, 96_000, SampleFormat::I16),
+        ];
+        assert_eq!(
+            pick_stream_format(&ranges, &SPEECH, fallback),
+            StreamFormat {
+                sample_rate: 16_000,
+                channels: 1,
+                sample_format: SampleFormat::I16,
+            }
+        );
+
+        let ranges = [range(2, 44_100, 48_000, SampleFormat::F32)];
+        assert_eq!(
+            pick_stream_format(&ranges, &SPEECH, fallback),
+            StreamFormat {
+                sample_rate: 44_100,
+                channels: 2,
+                sample_format: SampleFormat::F32,
+            }
+        );
+
+        let ranges = [range(1, 16_000, 16_000, SampleFormat::Unsupported)];
+        assert_eq!(pick_stream_format(&ranges, &SPEECH, fallback), fallback);
+        assert_eq!(
+            pick_stream_format(&ranges, &StreamRequest::default(), fallback),
+            fallback
+        );
+    }
+
+    #[test]
+    fn device_watcher_reports_added_removed_and_default_changes() {
+        let backend = MockAudioBackend::new(vec![device("0:Built-in"), device("1:USB")]);
+        let service = AudioCaptureService::new(backend.clone());
+        let mut watcher = service.device_watcher().expect("watcher");
+        assert_eq!(watcher.poll().expect("poll"), Vec::new());
+
+        backend.set_devices(vec![device("1:USB"), device("2:Headset")]);
+
+        assert_eq!(
+            watcher.poll().expect("poll"),
+            vec![
+                DeviceChangeEvent::Removed(device("0:Built-in")),
+                DeviceChangeEvent::Added(device("2:Headset")),
+                DeviceChangeEvent::DefaultChanged(Some(device("1:USB"))),
+            ]
+        );
+        assert_eq!(watcher.devices(), &[device("1:USB"), device("2:Headset")]);
+        assert_eq!(watcher.poll().expect("poll"), Vec::new());
+
+        backend.set_devices(Vec::new());
+        let events = watcher.poll().expect("poll");
+        assert_eq!(events.len(), 3);
+        assert_eq!(events[2], DeviceChangeEvent::DefaultChanged(None));
+    }
+
+    #[test]
+    fn device_watcher_thread_sends_events() {
+        let backend = MockAudioBackend::new(vec![device("0:Built-in")]);
+        let service = AudioCaptureService::new(backend.clone());
+        let (handle, receiver) = service
+            .device_watcher()
+            .expect("watcher")
+            .start(Duration::from_millis(5));
+
+        backend.set_devices(vec![device("0:Built-in"), device("1:USB")]);
+
+        let event = receiver
+            .recv_timeout(Duration::from_secs(1))
+            .expect("device event");
+        assert_eq!(event, DeviceChangeEvent::Added(device("1:USB")));
+        handle.stop();
+    }
+
+    #[test]
+    fn paused_stream_does_not_deliver_samples() {
+        let backend = MockAudioBackend::new(vec![device("0:Built-in"), device("1:USB")]);
+        let controller_handle = backend.controller.clone();
+        let mut service = AudioCaptureService::new(backend);
+        service.refresh_devices().expect("devices");
+        service.select_device("1:USB").expect("select device");
+        service.start().expect("start capture");
+        let controller = controller_handle
+            .lock()
+            .ok()
+            .and_then(|value| value.clone())
+            .expect("controller ready");
+
+        service.pause().expect("pause");
+        controller.push_samples(&[0.5, -0.5]);
+
+        assert!(service.is_paused());
+        assert!(service.is_running());
+        assert_eq!(service.level().expect("meter"), LevelReading::silence());
+
+        service.resume().expect("resume");
+        controller.push_samples(&[0.5, -0.5]);
+
+        assert!(!service.is_paused());
+        assert!(service.level().expect("meter").peak > 0.0);
+        assert_eq!(
+            service.selected_device().map(|device| device.id.as_str()),
+            Some("1:USB")
+        );
+    }
+
+    #[test]
+    fn pause_requires_running_stream() {
+        let mut service = AudioCaptureService::new(MockAudioBackend::new(vec![device("0:Mic")]));
+
+        assert!(matches!(service.pause(), Err(AudioError::NotRunning)));
+        assert!(matches!(service.resume(), Err(AudioError::NotRunning)));
+
+        service.start().expect("start capture");
+        service.pause().expect("pause");
+        service.stop().expect("stop");
+        assert!(!service.is_paused());
+    }
+
     #[test]
     fn u16_normalization_centers_at_zero() {
         let min = normalize_u16_sample(u16::MIN);
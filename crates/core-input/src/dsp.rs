@@ -0,0 +1,283 @@
+use std::f32::consts::{FRAC_1_SQRT_2, PI};
+
+pub const HIGH_PASS_CUTOFF_HZ: f32 = 80.0;
+const GATE_FRAME: f32 = 0.01;
+const GATE_FLOOR_RISE: f32 = 0.0005;
+const GATE_OPEN_RATIO: f32 = 3.0;
+const GATE_MIN_GAIN: f32 = 0.1;
+const GATE_SMOOTHING: f32 = 0.5;
+
+pub trait SampleProcessor: Send {
+    fn process(&mut self, samples: &mut [f32]);
+    fn reset(&mut self);
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+struct BiquadState {
+    x1: f32,
+    x2: f32,
+    y1: f32,
+    y2: f32,
+}
+
+#[derive(Debug, Clone)]
+pub struct HighPassFilter {
+    b0: f32,
+    b1: f32,
+    b2: f32,
+    a1: f32,
+    a2: f32,
+    states: Vec<BiquadState>,
+}
+
+impl HighPassFilter {
+    pub fn new(cutoff_hz: f32, sample_rate: u32, channels: u16) -> Self {
+        let sample_rate = sample_rate.max(1) as f32;
+        let cutoff_hz = cutoff_hz.clamp(1.0, sample_rate * 0.45);
+        let w0 = 2.0 * PI * cutoff_hz / sample_rate;
+        let (sin, cos) = w0.sin_cos();
+        let alpha = sin / (2.0 * FRAC_1_SQRT_2);
+        let a0 = 1.0 + alpha;
+        Self {
+            b0: (1.0 + cos) / 2.0 / a0,
+            b1: -(1.0 + cos) / a0,
+            b2: (1.0 + cos) / 2.0 / a0,
+            a1: -2.0 * cos / a0,
+            a2: (1.0 - alpha) / a0,
+            states: vec![BiquadState::default(); channels.max(1) as usize],
+        }
+    }
+}
+
+impl SampleProcessor for HighPassFilter {
+    fn process(&mut self, samples: &mut [f32]) {
+        let channels = self.states.len();
+        for (index, sample) in samples.iter_mut().enumerate() {
+            let input = if sample.is_finite() { *sample } else { 0.0 };
+            let state = &mut self.states[index % channels];
+            let output = self.b0 * input + self.b1 * state.x1 + self.b2 * state.x2
+                - self.a1 * state.y1
+                - self.a2 * state.y2;
+            state.x2 = state.x1;
+            state.x1 = input;
+            state.y2 = state.y1;
+            state.y1 = output;
+            *sample = output;
+        }
+    }
+
+    fn reset(&mut self) {
+        self.states.fill(BiquadState::default());
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct NoiseGate {
+    frame_len: usize,
+    noise_floor: f32,
+    gain: f32,
+}
+
+impl NoiseGate {
+    pub fn new(sample_rate: u32, channels: u16) -> Self {
+        let samples_per_second = sample_rate.max(1) as f32 * channels.max(1) as f32;
+        Self {
+            frame_len: ((samples_per_second * GATE_FRAME) as usize).max(1),
+            noise_floor: 0.0,
+            gain: 1.0,
+        }
+    }
+
+    pub fn noise_floor(&self) -> f32 {
+        self.noise_floor
+    }
+
+    fn process_frame(&mut self, frame: &mut [f32]) {
+        let sum: f32 = frame.iter().map(|sample| sample * sample).sum();
+        let rms = (sum / frame.len() as f32).sqrt();
+        if !rms.is_finite() {
+            return;
+        }
+
+        if self.noise_floor == 0.0 || rms < self.noise_floor {
+            self.noise_floor = rms;
+        } else {
+            self.noise_floor += (rms - self.noise_floor) * GATE_FLOOR_RISE;
+        }
+
+        let target = if self.noise_floor > 0.0 {
+            ((rms / self.noise_floor - 1.0) / (GATE_OPEN_RATIO - 1.0)).clamp(GATE_MIN_GAIN, 1.0)
+        } else {
+            GATE_MIN_GAIN
+        };
+        self.gain += (target - self.gain) * GATE_SMOOTHING;
+        for sample in frame.iter_mut() {
+            *sample *= self.gain;
+        }
+    }
+}
+
+impl SampleProcessor for NoiseGate {
+    fn process(&mut self, samples: &mut [f32]) {
+        for frame in samples.chunks_mut(self.frame_len) {
+            self.process_frame(frame);
+        }
+    }
+
+    fn reset(&mut self) {
+        self.noise_floor = 0.0;
+        self.gain = 1.0;
+    }
+}
+
+#[derive(Default)]
+pub struct ProcessorChain {
+    processors: Vec<Box<dyn SampleProcessor>>,
+}
+
+impl ProcessorChain {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn noise_reduction(sample_rate: u32, channels: u16) -> Self {
+        Self::new()
+            .with_processor(HighPassFilter::new(
+                HIGH_PASS_CUTOFF_HZ,
+                sample_rate,
+                channels,
+            ))
+            .with_processor(NoiseGate::new(sample_rate, channels))
+    }
+
+    pub fn with_processor(mut self, processor: impl SampleProcessor + 'static) -> Self {
+        self.processors.push(Box::new(processor));
+        self
+    }
+
+    pub fn len(&self) -> usize {
+        self.processors.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.processors.is_empty()
+    }
+}
+
+impl SampleProcessor for ProcessorChain {
+    fn process(&mut self, samples: &mut [f32]) {
+        for processor in &mut self.processors {
+            processor.process(samples);
+        }
+    }
+
+    fn reset(&mut self) {
+        for processor in &mut self.processors {
+            processor.reset();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{HighPassFilter, NoiseGate, ProcessorChain, SampleProcessor, HIGH_PASS_CUTOFF_HZ};
+    use std::f32::consts::PI;
+
+    const RATE: u32 = 16_000;
+
+    fn rms(samples: &[f32]) -> f32 {
+        let sum: f32 = samples.iter().map(|sample| sample * sample).sum();
+        (sum / samples.len() as f32).sqrt()
+    }
+
+    fn sine(frequency: f32, amplitude: f32, seconds: f32) -> Vec<f32> {
+        (0..(RATE as f32 * seconds) as usize)
+            .map(|index| amplitude * (2.0 * PI * frequency * index as f32 / RATE as f32).sin())
+            .collect()
+    }
+
+    fn filtered(signal: &[f32]) -> Vec<f32> {
+        let mut filter = HighPassFilter::new(HIGH_PASS_CUTOFF_HZ, RATE, 1);
+        let mut samples = signal.to_vec();
+        filter.process(&mut samples);
+        samples
+    }
+
+    #[test]
+    fn high_pass_removes_dc_offset() {
+        let signal = vec![0.5; RATE as usize];
+        let output = filtered(&signal);
+
+        let tail = &output[RATE as usize / 2..];
+        assert!(rms(tail) < 0.001);
+    }
+
+    #[test]
+    fn high_pass_attenuates_rumble_and_keeps_speech_band() {
+        let rumble = sine(20.0, 0.5, 1.0);
+        let voice = sine(1_000.0, 0.5, 1.0);
+        let half = RATE as usize / 2;
+
+        let rumble_ratio = rms(&filtered(&rumble)[half..]) / rms(&rumble[half..]);
+        let voice_ratio = rms(&filtered(&voice)[half..]) / rms(&voice[half..]);
+
+        assert!(rumble_ratio < 0.1, "rumble ratio {rumble_ratio}");
+        assert!(voice_ratio > 0.95, "voice ratio {voice_ratio}");
+    }
+
+    #[test]
+    fn high_pass_filters_interleaved_channels_independently() {
+        let mut filter = HighPassFilter::new(HIGH_PASS_CUTOFF_HZ, RATE, 2);
+        let mono = filtered(&sine(1_000.0, 0.5, 0.1));
+        let mut stereo: Vec<f32> = sine(1_000.0, 0.5, 0.1)
+            .into_iter()
+            .flat_map(|sample| [sample, 0.25])
+            .collect();
+
+        filter.process(&mut stereo);
+
+        let left: Vec<f32> = stereo.iter().step_by(2).copied().collect();
+        assert_eq!(left, mono);
+    }
+
+    #[test]
+    fn noise_gate_suppresses_steady_noise_floor() {
+        let mut gate = NoiseGate::new(RATE, 1);
+        let mut noise = sine(3_000.0, 0.01, 1.0);
+        let before = rms(&noise[RATE as usize / 2..]);
+
+        gate.process(&mut noise);
+
+        assert!(rms(&noise[RATE as usize / 2..]) < before * 0.2);
+    }
+
+    #[test]
+    fn noise_gate_keeps_speech_above_floor() {
+        let mut gate = NoiseGate::new(RATE, 1);
+        let mut noise = sine(3_000.0, 0.01, 0.5);
+        gate.process(&mut noise);
+
+        let mut voice = sine(1_000.0, 0.5, 0.5);
+        let before = rms(&voice[RATE as usize / 4..]);
+        gate.process(&mut voice);
+
+        assert!(rms(&voice[RATE as usize / 4..]) > before * 0.9);
+    }
+
+    #[test]
+    fn reset_forgets_previous_noise_estimate() {
+        let mut chain = ProcessorChain::noise_reduction(RATE, 1);
+        assert_eq!(chain.len(), 2);
+        let mut first = vec![0.25; RATE as usize];
+        chain.process(&mut first);
+
+        chain.reset();
+        let mut fresh = ProcessorChain::noise_reduction(RATE, 1);
+        let signal = sine(440.0, 0.2, 0.2);
+        let (mut reused, mut expected) = (signal.clone(), signal);
+        chain.process(&mut reused);
+        fresh.process(&mut expected);
+
+        assert_eq!(reused, expected);
+    }
+}
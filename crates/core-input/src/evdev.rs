@@ -0,0 +1,539 @@
+use std::{
+    collections::HashSet,
+    fs::File,
+    io::{ErrorKind, Read},
+    os::fd::AsRawFd,
+    path::{Path, PathBuf},
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        mpsc, Arc, Mutex,
+    },
+    thread::JoinHandle,
+    time::{Duration, Instant},
+};
+
+use crate::hotkeys::{
+    spawn_key_listener, HotkeyActionEvent, HotkeyError, HotkeyKey, HotkeyListenerHandle,
+    HotkeyManager, MouseButton,
+};
+
+pub const EVDEV_INPUT_DIR: &str = "/dev/input";
+pub const EVDEV_RESCAN_INTERVAL: Duration = Duration::from_secs(2);
+
+const EV_KEY: u16 = 1;
+const TIMEVAL_SIZE: usize = 2 * std::mem::size_of::<std::ffi::c_long>();
+const INPUT_EVENT_SIZE: usize = TIMEVAL_SIZE + 8;
+const STOP_POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+pub struct EvdevHotkeyListener {
+    manager: Arc<Mutex<HotkeyManager>>,
+    input_dir: PathBuf,
+    rescan_interval: Duration,
+}
+
+impl EvdevHotkeyListener {
+    pub fn new(manager: Arc<Mutex<HotkeyManager>>) -> Self {
+        Self {
+            manager,
+            input_dir: PathBuf::from(EVDEV_INPUT_DIR),
+            rescan_interval: EVDEV_RESCAN_INTERVAL,
+        }
+    }
+
+    pub fn with_input_dir(mut self, input_dir: impl Into<PathBuf>) -> Self {
+        self.input_dir = input_dir.into();
+        self
+    }
+
+    pub fn with_rescan_interval(mut self, rescan_interval: Duration) -> Self {
+        self.rescan_interval = rescan_interval;
+        self
+    }
+
+    pub fn start(
+        &self,
+    ) -> Result<(HotkeyListenerHandle, mpsc::Receiver<HotkeyActionEvent>), HotkeyError> {
+        if !self.input_dir.is_dir() {
+            return Err(HotkeyError::Listener(format!(
+                "input device directory {} not found",
+                self.input_dir.display()
+            )));
+        }
+        let input_dir = self.input_dir.clone();
+        let rescan_interval = self.rescan_interval;
+        let (sender, receiver) = mpsc::channel();
+        let handle = spawn_key_listener(
+            Arc::clone(&self.manager),
+            sender,
+            move |mut handler, stopped| {
+                let (key_sender, key_receiver) = mpsc::channel();
+                let open_devices = Arc::new(Mutex::new(HashSet::new()));
+                let mut denied = HashSet::new();
+                let mut readers = Vec::new();
+                while !stopped.load(Ordering::SeqCst) {
+                    readers.retain(|reader: &JoinHandle<()>| !reader.is_finished());
+                    scan_devices(
+                        &input_dir,
+                        &open_devices,
+                        &mut denied,
+                        &key_sender,
+                        &stopped,
+                        &mut readers,
+                    );
+                    let rescan_at = Instant::now() + rescan_interval;
+                    while !stopped.load(Ordering::SeqCst) {
+                        let remaining = rescan_at.saturating_duration_since(Instant::now());
+                        if remaining.is_zero() {
+                            break;
+                        }
+                        if let Ok(event) =
+                            key_receiver.recv_timeout(remaining.min(STOP_POLL_INTERVAL))
+                        {
+                            handler(event);
+                        }
+                    }
+                }
+                for reader in readers {
+                    let _ = reader.join();
+                }
+                Ok(())
+            },
+        );
+        Ok((handle, receiver))
+    }
+}
+
+struct StoppableReader<R> {
+    inner: R,
+    stopped: Arc<AtomicBool>,
+}
+
+impl<R: Read + AsRawFd> Read for StoppableReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        loop {
+            if self.stopped.load(Ordering::SeqCst) {
+                return Ok(0);
+            }
+            let mut fd = libc::pollfd {
+                fd: self.inner.as_raw_fd(),
+                events: libc::POLLIN,
+                revents: 0,
+            };
+            // SAFETY: `fd` is a single initialised pollfd that outlives the call.
+            let ready =
+                unsafe { libc::poll(&mut fd, 1, STOP_POLL_INTERVAL.as_millis() as libc::c_int) };
+            if ready > 0 {
+                return self.inner.read(buf);
+            }
+            if ready < 0 {
+                let err = std::io::Error::last_os_error();
+                if err.kind() != ErrorKind::Interrupted {
+                    return Err(err);
+                }
+            }
+        }
+    }
+}
+
+fn scan_devices(
+    input_dir: &Path,
+    open_devices: &Arc<Mutex<HashSet<PathBuf>>>,
+    denied: &mut HashSet<PathBuf>,
+    sender: &mpsc::Sender<(HotkeyKey, bool)>,
+    stopped: &Arc<AtomicBool>,
+    readers: &mut Vec<JoinHandle<()>>,
+) {
+    let entries = match std::fs::read_dir(input_dir) {
+        Ok(entries) => entries,
+        Err(err) => {
+            log::warn!("failed to scan {}: {err}", input_dir.display());
+            return;
+        }
+    };
+    for path in entries.flatten().map(|entry| entry.path()) {
+        let is_event_device = path
+            .file_name()
+            .and_then(|name| name.to_str())
+            .is_some_and(|name| name.starts_with("event"));
+        if !is_event_device || denied.contains(&path) {
+            continue;
+        }
+        let Ok(mut open) = open_devices.lock() else {
+            return;
+        };
+        if open.contains(&path) {
+            continue;
+        }
+        let file = match File::open(&path) {
+            Ok(file) => file,
+            Err(err) => {
+                if err.kind() == ErrorKind::PermissionDenied {
+                    log::warn!("no permission to read {}; skipping it", path.display());
+                    denied.insert(path);
+                } else {
+                    log::warn!("failed to open {}: {err}", path.display());
+                }
+                continue;
+            }
+        };
+        log::info!("reading hotkeys from {}", path.display());
+        open.insert(path.clone());
+        let open_devices = Arc::clone(open_devices);
+        let sender = sender.clone();
+        let reader = StoppableReader {
+            inner: file,
+            stopped: Arc::clone(stopped),
+        };
+        readers.push(std::thread::spawn(move || {
+            if let Err(err) = read_key_events(reader, &sender) {
+                log::info!("stopped reading {}: {err}", path.display());
+            }
+            if let Ok(mut open) = open_devices.lock() {
+                open.remove(&path);
+            }
+        }));
+    }
+}
+
+fn read_key_events(
+    mut reader: impl Read,
+    sender: &mpsc::Sender<(HotkeyKey, bool)>,
+) -> std::io::Result<()> {
+    let mut buffer = [0u8; INPUT_EVENT_SIZE];
+    loop {
+        match reader.read_exact(&mut buffer) {
+            Ok(()) => {}
+            Err(err) if err.kind() == ErrorKind::UnexpectedEof => return Ok(()),
+            Err(err) => return Err(err),
+        }
+        let Some(event) = decode_event(&buffer) else {
+            continue;
+        };
+        if sender.send(event).is_err() {
+            return Ok(());
+        }
+    }
+}
+
+fn decode_event(buffer: &[u8; INPUT_EVENT_SIZE]) -> Option<(HotkeyKey, bool)> {
+    let event_type = u16::from_ne_bytes([buffer[TIMEVAL_SIZE], buffer[TIMEVAL_SIZE + 1]]);
+    let code = u16::from_ne_bytes([buffer[TIMEVAL_SIZE + 2], buffer[TIMEVAL_SIZE + 3]]);
+    let value = i32::from_ne_bytes([
+        buffer[TIMEVAL_SIZE + 4],
+        buffer[TIMEVAL_SIZE + 5],
+        buffer[TIMEVAL_SIZE + 6],
+        buffer[TIMEVAL_SIZE + 7],
+    ]);
+    if event_type != EV_KEY {
+        return None;
+    }
+    let pressed = match value {
+        0 => false,
+        1 => true,
+        _ => return None,
+    };
+    map_keycode(code).map(|key| (key, pressed))
+}
+
+fn map_keycode(code: u16) -> Option<HotkeyKey> {
+    let key = match code {
+        1 => HotkeyKey::Escape,
+        2 => HotkeyKey::Digit1,
+        3 => HotkeyKey::Digit2,
+        4 => HotkeyKey::Digit3,
+        5 => HotkeyKey::Digit4,
+        6 => HotkeyKey::Digit5,
+        7 => HotkeyKey::Digit6,
+        8 => HotkeyKey::Digit7,
+        9 => HotkeyKey::Digit8,
+        10 => HotkeyKey::Digit9,
+        11 => HotkeyKey::Digit0,
+        14 => HotkeyKey::Backspace,
+        15 => HotkeyKey::Tab,
+        16 => HotkeyKey::Q,
+        17 => HotkeyKey::W,
+        18 => HotkeyKey::E,
+        19 => HotkeyKey::R,
+        20 => HotkeyKey::T,
+        21 => HotkeyKey::Y,
+        22 => HotkeyKey::U,
+        23 => HotkeyKey::I,
+        24 => HotkeyKey::O,
+        25 => HotkeyKey::P,
+        28 => HotkeyKey::Enter,
+        29 => HotkeyKey::CtrlLeft,
+        30 => HotkeyKey::A,
+        31 => HotkeyKey::S,
+        32 => HotkeyKey::D,
+        33 => HotkeyKey::F,
+        34 => HotkeyKey::G,
+        35 => HotkeyKey::H,
+        36 => HotkeyKey::J,
+        37 => HotkeyKey::K,
+        38 => HotkeyKey::L,
+        39 => HotkeyKey::Semicolon,
+        41 => HotkeyKey::Grave,
+        42 => HotkeyKey::ShiftLeft,
+        44 => HotkeyKey::Z,
+        45 => HotkeyKey::X,
+        46 => HotkeyKey::C,
+        47 => HotkeyKey::V,
+        48 => HotkeyKey::B,
+        49 => HotkeyKey::N,
+        50 => HotkeyKey::M,
+        51 => HotkeyKey::Comma,
+        52 => HotkeyKey::Period,
+        53 => HotkeyKey::Slash,
+        54 => HotkeyKey::ShiftRight,
+        55 => HotkeyKey::NumpadMultiply,
+        56 => HotkeyKey::AltLeft,
+        57 => HotkeyKey::Space,
+        59 => HotkeyKey::F1,
+        60 => HotkeyKey::F2,
+        61 => HotkeyKey::F3,
+        62 => HotkeyKey::F4,
+        63 => HotkeyKey::F5,
+        64 => HotkeyKey::F6,
+        65 => HotkeyKey::F7,
+        66 => HotkeyKey::F8,
+        67 => HotkeyKey::F9,
+        68 => HotkeyKey::F10,
+        71 => HotkeyKey::Numpad7,
+        72 => HotkeyKey::Numpad8,
+        73 => HotkeyKey::Numpad9,
+        74 => HotkeyKey::NumpadSubtract,
+        75 => HotkeyKey::Numpad4,
+        76 => HotkeyKey::Numpad5,
+        77 => HotkeyKey::Numpad6,
+        78 => HotkeyKey::NumpadAdd,
+        79 => HotkeyKey::Numpad1,
+        80 => HotkeyKey::Numpad2,
+        81 => HotkeyKey::Numpad3,
+        82 => HotkeyKey::Numpad0,
+        83 => HotkeyKey::NumpadDecimal,
+        87 => HotkeyKey::F11,
+        88 => HotkeyKey::F12,
+        96 => HotkeyKey::NumpadEnter,
+        97 => HotkeyKey::CtrlRight,
+        98 => HotkeyKey::NumpadDivide,
+        100 => HotkeyKey::AltRight,
+        102 => HotkeyKey::Home,
+        103 => HotkeyKey::Up,
+        104 => HotkeyKey::PageUp,
+        105 => HotkeyKey::Left,
+        106 => HotkeyKey::Right,
+        107 => HotkeyKey::End,
+        108 => HotkeyKey::Down,
+        109 => HotkeyKey::PageDown,
+        110 => HotkeyKey::Insert,
+        111 => HotkeyKey::Delete,
+        119 => HotkeyKey::Pause,
+        125 => HotkeyKey::MetaLeft,
+        126 => HotkeyKey::MetaRight,
+        272 => HotkeyKey::Mouse(MouseButton::Left),
+        273 => HotkeyKey::Mouse(MouseButton::Right),
+        274 => HotkeyKey::Mouse(MouseButton::Middle),
+        // Side buttons use the X11 button numbers so a saved "mouse8" means the
+        // same button under both listeners.
+        275 => HotkeyKey::Mouse(MouseButton::Other(8)),
+        276 => HotkeyKey::Mouse(MouseButton::Other(9)),
+        _ => return None,
+    };
+    Some(key)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        decode_event, map_keycode, read_key_events, scan_devices, EvdevHotkeyListener,
+        StoppableReader, INPUT_EVENT_SIZE, TIMEVAL_SIZE,
+    };
+    use crate::hotkeys::{
+        spawn_key_listener, Hotkey, HotkeyError, HotkeyKey, HotkeyManager, HotkeyModifiers,
+        HotkeyState, HotkeyTrigger, MouseButton,
+    };
+    use std::collections::HashSet;
+    use std::os::unix::net::UnixStream;
+    use std::sync::atomic::{AtomicBool, Ordering};
+    use std::sync::{mpsc, Arc, Mutex};
+    use std::time::{Duration, Instant};
+
+    fn raw_event(event_type: u16, code: u16, value: i32) -> Vec<u8> {
+        let mut bytes = vec![0u8; TIMEVAL_SIZE];
+        bytes.extend_from_slice(&event_type.to_ne_bytes());
+        bytes.extend_from_slice(&code.to_ne_bytes());
+        bytes.extend_from_slice(&value.to_ne_bytes());
+        assert_eq!(bytes.len(), INPUT_EVENT_SIZE);
+        bytes
+    }
+
+    fn key_stream(events: &[(u16, u16, i32)]) -> Vec<u8> {
+        events
+            .iter()
+            .flat_map(|(event_type, code, value)| raw_event(*event_type, *code, *value))
+            .collect()
+    }
+
+    #[test]
+    fn map_keycode_covers_letters_modifiers_and_buttons() {
+        for (code, expected) in [
+            (30, HotkeyKey::A),
+            (67, HotkeyKey::F9),
+            (57, HotkeyKey::Space),
+            (29, HotkeyKey::CtrlLeft),
+            (100, HotkeyKey::AltRight),
+            (125, HotkeyKey::MetaLeft),
+            (82, HotkeyKey::Numpad0),
+            (275, HotkeyKey::Mouse(MouseButton::Other(8))),
+        ] {
+            assert_eq!(map_keycode(code), Some(expected), "{code}");
+        }
+        assert_eq!(map_keycode(58), None);
+    }
+
+    #[test]
+    fn decode_event_ignores_repeats_and_non_key_events() {
+        let decode = |event_type, code, value| {
+            let bytes: [u8; INPUT_EVENT_SIZE] = raw_event(event_type, code, value)
+                .try_into()
+                .expect("event size");
+            decode_event(&bytes)
+        };
+        assert_eq!(decode(1, 67, 1), Some((HotkeyKey::F9, true)));
+        assert_eq!(decode(1, 67, 0), Some((HotkeyKey::F9, false)));
+        assert_eq!(decode(1, 67, 2), None);
+        assert_eq!(decode(0, 0, 0), None);
+        assert_eq!(decode(2, 0, 5), None);
+    }
+
+    #[test]
+    fn fake_event_stream_drives_hotkey_actions() {
+        let manager = Arc::new(Mutex::new(HotkeyManager::new()));
+        let hotkey = Hotkey {
+            key: HotkeyKey::K,
+            modifiers: HotkeyModifiers {
+                ctrl: true,
+                ..HotkeyModifiers::none()
+            },
+        };
+        {
+            let mut manager = manager.lock().expect("manager");
+            manager.register_with_trigger(hotkey, HotkeyTrigger::Pressed, "ptt");
+            manager.register_with_trigger(hotkey, HotkeyTrigger::Released, "ptt");
+        }
+        let stream = key_stream(&[
+            (1, 37, 1),
+            (1, 37, 0),
+            (1, 29, 1),
+            (0, 0, 0),
+            (1, 37, 1),
+            (1, 37, 2),
+            (1, 37, 2),
+            (1, 37, 0),
+            (1, 29, 0),
+        ]);
+
+        let (sender, receiver) = mpsc::channel();
+        let handle = spawn_key_listener(manager, sender, move |mut handler, _stopped| {
+            let (key_sender, key_receiver) = mpsc::channel();
+            read_key_events(stream.as_slice(), &key_sender).expect("read");
+            drop(key_sender);
+            for event in key_receiver {
+                handler(event);
+            }
+            Ok(())
+        });
+        handle.join().expect("listener join");
+
+        let events = receiver
+            .try_iter()
+            .map(|event| (event.action, event.hotkey, event.state))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            events,
+            vec![
+                ("ptt".to_string(), hotkey, HotkeyState::Pressed),
+                ("ptt".to_string(), hotkey, HotkeyState::Released),
+            ]
+        );
+    }
+
+    #[test]
+    fn scan_reads_event_devices_and_skips_failures() {
+        let dir = std::env::temp_dir().join(format!("openwhisperai-evdev-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(dir.join("event1")).expect("unreadable device");
+        std::fs::write(dir.join("event0"), key_stream(&[(1, 67, 1), (1, 67, 0)])).expect("device");
+        std::fs::write(dir.join("mouse0"), key_stream(&[(1, 30, 1)])).expect("other node");
+
+        let open_devices = Arc::new(Mutex::new(HashSet::new()));
+        let mut denied = HashSet::new();
+        let mut readers = Vec::new();
+        let (sender, receiver) = mpsc::channel();
+        let stopped = Arc::new(AtomicBool::new(false));
+        scan_devices(
+            &dir,
+            &open_devices,
+            &mut denied,
+            &sender,
+            &stopped,
+            &mut readers,
+        );
+        drop(sender);
+
+        assert_eq!(
+            receiver.iter().collect::<Vec<_>>(),
+            vec![(HotkeyKey::F9, true), (HotkeyKey::F9, false)]
+        );
+        for reader in readers {
+            reader.join().expect("reader join");
+        }
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn stopped_reader_returns_without_input() {
+        let (device, _writer) = UnixStream::pair().expect("socket pair");
+        let stopped = Arc::new(AtomicBool::new(false));
+        let reader = StoppableReader {
+            inner: device,
+            stopped: Arc::clone(&stopped),
+        };
+        let (sender, receiver) = mpsc::channel();
+        let thread = std::thread::spawn(move || read_key_events(reader, &sender));
+
+        stopped.store(true, Ordering::SeqCst);
+
+        thread.join().expect("reader join").expect("clean stop");
+        assert!(receiver.try_recv().is_err());
+    }
+
+    #[test]
+    fn stopping_the_listener_ends_its_thread() {
+        let dir =
+            std::env::temp_dir().join(format!("openwhisperai-evdev-stop-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).expect("input dir");
+        let manager = Arc::new(Mutex::new(HotkeyManager::new()));
+        let (handle, _receiver) = EvdevHotkeyListener::new(manager)
+            .with_input_dir(&dir)
+            .with_rescan_interval(Duration::from_secs(60))
+            .start()
+            .expect("start");
+
+        let started = Instant::now();
+        handle.stop();
+        handle.join().expect("listener join");
+
+        assert!(started.elapsed() < Duration::from_secs(5));
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn missing_input_dir_fails_to_start() {
+        let manager = Arc::new(Mutex::new(HotkeyManager::new()));
+        let result = EvdevHotkeyListener::new(manager)
+            .with_input_dir(std::env::temp_dir().join("openwhisperai-no-such-input"))
+            .start();
+        assert!(matches!(result, Err(HotkeyError::Listener(_))));
+    }
+}
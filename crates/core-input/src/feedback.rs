@@ -0,0 +1,192 @@
+use crate::audio::{AudioError, SampleFormat};
+use cpal::traits::{DeviceTrait, HostTrait, StreamTrait};
+use log::warn;
+use std::f32::consts::TAU;
+use std::time::Duration;
+
+const TONE_AMPLITUDE: f32 = 0.2;
+const TONE_FADE: Duration = Duration::from_millis(5);
+const TONE_TAIL: Duration = Duration::from_millis(50);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum FeedbackTone {
+    Start,
+    Stop,
+    Error,
+}
+
+impl FeedbackTone {
+    fn notes(self) -> &'static [(f32, u64)] {
+        match self {
+            FeedbackTone::Start => &[(660.0, 60), (880.0, 60)],
+            FeedbackTone::Stop => &[(880.0, 60), (660.0, 60)],
+            FeedbackTone::Error => &[(220.0, 90), (0.0, 40), (220.0, 90)],
+        }
+    }
+
+    pub fn render(self, sample_rate: u32) -> Vec<f32> {
+        self.notes()
+            .iter()
+            .flat_map(|(frequency_hz, duration_ms)| {
+                sine_blip(
+                    *frequency_hz,
+                    Duration::from_millis(*duration_ms),
+                    sample_rate,
+                )
+            })
+            .collect()
+    }
+}
+
+pub fn sine_blip(frequency_hz: f32, duration: Duration, sample_rate: u32) -> Vec<f32> {
+    let len = (duration.as_secs_f64() * f64::from(sample_rate)) as usize;
+    let fade =
+        ((TONE_FADE.as_secs_f64() * f64::from(sample_rate)) as usize).clamp(1, len.max(2) / 2);
+    (0..len)
+        .map(|index| {
+            let time = index as f32 / sample_rate as f32;
+            let edge = index.min(len - 1 - index);
+            let envelope = (edge as f32 / fade as f32).min(1.0);
+            (TAU * frequency_hz * time).sin() * TONE_AMPLITUDE * envelope
+        })
+        .collect()
+}
+
+pub trait TonePlayer: Send + Sync {
+    fn play(&self, tone: FeedbackTone) -> Result<(), AudioError>;
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+pub struct CpalTonePlayer;
+
+impl TonePlayer for CpalTonePlayer {
+    fn play(&self, tone: FeedbackTone) -> Result<(), AudioError> {
+        let device = cpal::default_host()
+            .default_output_device()
+            .ok_or(AudioError::NoOutputDevice)?;
+        let config = device
+            .default_output_config()
+            .map_err(|err| AudioError::Backend(err.to_string()))?;
+        let sample_rate = config.sample_rate().0;
+        let channels = usize::from(config.channels());
+        let samples = tone.render(sample_rate);
+        let length = Duration::from_secs_f64(samples.len() as f64 / f64::from(sample_rate));
+        let mut samples = samples.into_iter();
+        let stream_config = config.config();
+        let error_callback = |err: cpal::StreamError| warn!("feedback tone stream error: {err}");
+
+        let stream = match SampleFormat::from(config.sample_format()) {
+            SampleFormat::F32 => device.build_output_stream(
+                &stream_config,
+                move |data: &mut [f32], _| fill_frames(data, channels, &mut samples, |s| s),
+                error_callback,
+                None,
+            ),
+            SampleFormat::I16 => device.build_output_stream(
+                &stream_config,
+                move |data: &mut [i16], _| {
+                    fill_frames(data, channels, &mut samples, f32_to_i16_sample)
+                },
+                error_callback,
+                None,
+            ),
+            SampleFormat::U16 => device.build_output_stream(
+                &stream_config,
+                move |data: &mut [u16], _| {
+                    fill_frames(data, channels, &mut samples, f32_to_u16_sample)
+                },
+                error_callback,
+                None,
+            ),
+            SampleFormat::Unsupported => {
+                return Err(AudioError::Backend("unsupported sample format".to_string()))
+            }
+        }
+        .map_err(|err| AudioError::Backend(err.to_string()))?;
+        stream
+            .play()
+            .map_err(|err| AudioError::Stream(err.to_string()))?;
+        std::thread::sleep(length + TONE_TAIL);
+        Ok(())
+    }
+}
+
+fn fill_frames<T: Copy>(
+    data: &mut [T],
+    channels: usize,
+    samples: &mut impl Iterator<Item = f32>,
+    convert: impl Fn(f32) -> T,
+) {
+    for frame in data.chunks_mut(channels.max(1)) {
+        frame.fill(convert(samples.next().unwrap_or(0.0)));
+    }
+}
+
+fn f32_to_i16_sample(sample: f32) -> i16 {
+    (sample.clamp(-1.0, 1.0) * f32::from(i16::MAX)) as i16
+}
+
+fn f32_to_u16_sample(sample: f32) -> u16 {
+    ((sample.clamp(-1.0, 1.0) + 1.0) * 0.5 * f32::from(u16::MAX)) as u16
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn peak(samples: &[f32]) -> f32 {
+        samples
+            .iter()
+            .fold(0.0, |peak, sample| peak.max(sample.abs()))
+    }
+
+    #[test]
+    fn sine_blip_has_requested_length_and_amplitude() {
+        let blip = sine_blip(440.0, Duration::from_millis(100), 48_000);
+
+        assert_eq!(blip.len(), 4_800);
+        assert!(peak(&blip) <= TONE_AMPLITUDE);
+        assert!(peak(&blip) > TONE_AMPLITUDE * 0.95);
+    }
+
+    #[test]
+    fn sine_blip_fades_in_and_out() {
+        let blip = sine_blip(440.0, Duration::from_millis(100), 48_000);
+
+        assert_eq!(blip[0], 0.0);
+        assert!(blip[blip.len() - 1].abs() < 1e-6);
+        assert!(peak(&blip[..48]) < peak(&blip[2_000..2_200]));
+    }
+
+    #[test]
+    fn short_blips_do_not_panic() {
+        assert!(sine_blip(440.0, Duration::ZERO, 48_000).is_empty());
+        assert_eq!(sine_blip(440.0, Duration::from_micros(20), 48_000).len(), 0);
+        assert_eq!(sine_blip(440.0, Duration::from_millis(1), 1_000).len(), 1);
+    }
+
+    #[test]
+    fn tones_are_distinct() {
+        let start = FeedbackTone::Start.render(16_000);
+        let stop = FeedbackTone::Stop.render(16_000);
+        let error = FeedbackTone::Error.render(16_000);
+
+        assert_eq!(start.len(), 1_920);
+        assert_eq!(stop.len(), start.len());
+        assert_ne!(start, stop);
+        assert_eq!(error.len(), 3_520);
+        assert!(error[1_440..2_080].iter().all(|sample| *sample == 0.0));
+    }
+
+    #[test]
+    fn frames_repeat_each_sample_across_channels() {
+        let mut data = [0i16; 6];
+        let mut samples = [1.0, -1.0].into_iter();
+
+        fill_frames(&mut data, 2, &mut samples, f32_to_i16_sample);
+
+        assert_eq!(data, [i16::MAX, i16::MAX, -i16::MAX, -i16::MAX, 0, 0]);
+        assert_eq!(f32_to_u16_sample(-1.0), 0);
+        assert_eq!(f32_to_u16_sample(1.0), u16::MAX);
+    }
+}
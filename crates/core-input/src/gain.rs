@@ -0,0 +1,152 @@
+pub const AGC_TARGET_DBFS: f32 = -20.0;
+pub const AGC_MIN_GAIN_DB: f32 = -12.0;
+pub const AGC_MAX_GAIN_DB: f32 = 24.0;
+const AGC_NOISE_FLOOR_DBFS: f32 = -60.0;
+const AGC_SMOOTHING: f32 = 0.2;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum GainMode {
+    Fixed { gain_db: f32 },
+    Automatic,
+}
+
+impl Default for GainMode {
+    fn default() -> Self {
+        Self::Fixed { gain_db: 0.0 }
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct GainStage {
+    mode: GainMode,
+    agc_gain_db: f32,
+}
+
+impl GainStage {
+    pub fn new(mode: GainMode) -> Self {
+        Self {
+            mode,
+            agc_gain_db: 0.0,
+        }
+    }
+
+    pub fn mode(&self) -> GainMode {
+        self.mode
+    }
+
+    pub fn set_mode(&mut self, mode: GainMode) {
+        self.mode = mode;
+        self.reset();
+    }
+
+    pub fn gain_db(&self) -> f32 {
+        match self.mode {
+            GainMode::Fixed { gain_db } => gain_db,
+            GainMode::Automatic => self.agc_gain_db,
+        }
+    }
+
+    pub fn process(&mut self, samples: &[f32]) -> Vec<f32> {
+        if self.mode == GainMode::Automatic {
+            self.adapt(samples);
+        }
+        let gain = db_to_linear(self.gain_db());
+        if gain == 1.0 {
+            return samples.to_vec();
+        }
+        samples
+            .iter()
+            .map(|sample| (sample * gain).clamp(-1.0, 1.0))
+            .collect()
+    }
+
+    pub fn reset(&mut self) {
+        self.agc_gain_db = 0.0;
+    }
+
+    fn adapt(&mut self, samples: &[f32]) {
+        let finite = samples.iter().filter(|sample| sample.is_finite());
+        let (sum, count) = finite.fold((0.0_f32, 0_u32), |(sum, count), sample| {
+            (sum + sample * sample, count + 1)
+        });
+        if count == 0 {
+            return;
+        }
+        let rms = (sum / count as f32).sqrt();
+        if rms <= 0.0 {
+            return;
+        }
+        let rms_dbfs = 20.0 * rms.log10();
+        if rms_dbfs < AGC_NOISE_FLOOR_DBFS {
+            return;
+        }
+        let desired = (AGC_TARGET_DBFS - rms_dbfs).clamp(AGC_MIN_GAIN_DB, AGC_MAX_GAIN_DB);
+        self.agc_gain_db += (desired - self.agc_gain_db) * AGC_SMOOTHING;
+    }
+}
+
+fn db_to_linear(db: f32) -> f32 {
+    if db.is_finite() {
+        10_f32.powf(db / 20.0)
+    } else {
+        1.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{GainMode, GainStage, AGC_MAX_GAIN_DB, AGC_TARGET_DBFS};
+    use approx::assert_relative_eq;
+
+    fn rms_dbfs(samples: &[f32]) -> f32 {
+        let sum: f32 = samples.iter().map(|sample| sample * sample).sum();
+        20.0 * (sum / samples.len() as f32).sqrt().log10()
+    }
+
+    #[test]
+    fn unity_gain_passes_samples_through() {
+        let mut stage = GainStage::default();
+        assert_eq!(stage.process(&[0.25, -0.5, 1.5]), vec![0.25, -0.5, 1.5]);
+    }
+
+    #[test]
+    fn fixed_gain_scales_and_clamps() {
+        let mut stage = GainStage::new(GainMode::Fixed { gain_db: 6.0 });
+        let processed = stage.process(&[0.1, -0.2, 0.75]);
+
+        assert_relative_eq!(processed[0], 0.1995, epsilon = 1e-3);
+        assert_relative_eq!(processed[1], -0.399, epsilon = 1e-3);
+        assert_eq!(processed[2], 1.0);
+    }
+
+    #[test]
+    fn automatic_gain_converges_towards_target() {
+        let mut stage = GainStage::new(GainMode::Automatic);
+        let quiet = vec![0.01_f32; 480];
+
+        let mut processed = Vec::new();
+        for _ in 0..40 {
+            processed = stage.process(&quiet);
+        }
+
+        assert_relative_eq!(rms_dbfs(&processed), AGC_TARGET_DBFS, epsilon = 0.5);
+    }
+
+    #[test]
+    fn automatic_gain_is_bounded_and_ignores_noise_floor() {
+        let mut stage = GainStage::new(GainMode::Automatic);
+        for _ in 0..100 {
+            stage.process(&[0.0001; 480]);
+        }
+        assert_eq!(stage.gain_db(), 0.0);
+
+        for _ in 0..100 {
+            stage.process(&[0.002; 480]);
+        }
+        assert!(stage.gain_db() <= AGC_MAX_GAIN_DB);
+        assert_relative_eq!(stage.gain_db(), AGC_MAX_GAIN_DB, epsilon = 0.01);
+
+        stage.reset();
+        assert_eq!(stage.gain_db(), 0.0);
+    }
+}
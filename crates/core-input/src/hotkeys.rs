@@ -1,6 +1,13 @@
 use std::{
-    collections::HashMap,
-    sync::{mpsc, Arc, Mutex},
+    borrow::Cow,
+    collections::{HashMap, HashSet},
+    fmt,
+    str::FromStr,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        mpsc, Arc, Mutex, OnceLock,
+    },
+    time::{Duration, Instant},
 };
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
@@ -52,6 +59,319 @@ pub enum HotkeyKey {
     Right,
     Up,
     Down,
+    Digit0,
+    Digit1,
+    Digit2,
+    Digit3,
+    Digit4,
+    Digit5,
+    Digit6,
+    Digit7,
+    Digit8,
+    Digit9,
+    Numpad0,
+    Numpad1,
+    Numpad2,
+    Numpad3,
+    Numpad4,
+    Numpad5,
+    Numpad6,
+    Numpad7,
+    Numpad8,
+    Numpad9,
+    NumpadAdd,
+    NumpadSubtract,
+    NumpadMultiply,
+    NumpadDivide,
+    NumpadDecimal,
+    NumpadEnter,
+    Comma,
+    Period,
+    Slash,
+    Semicolon,
+    Grave,
+    Insert,
+    Delete,
+    Home,
+    End,
+    PageUp,
+    PageDown,
+    Pause,
+    CtrlLeft,
+    CtrlRight,
+    AltLeft,
+    AltRight,
+    ShiftLeft,
+    ShiftRight,
+    MetaLeft,
+    MetaRight,
+    Mouse(MouseButton),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum MouseButton {
+    Left,
+    Right,
+    Middle,
+    Other(u8),
+}
+
+impl HotkeyKey {
+    pub const ALL: [HotkeyKey; 96] = [
+        HotkeyKey::A,
+        HotkeyKey::B,
+        HotkeyKey::C,
+        HotkeyKey::D,
+        HotkeyKey::E,
+        HotkeyKey::F,
+        HotkeyKey::G,
+        HotkeyKey::H,
+        HotkeyKey::I,
+        HotkeyKey::J,
+        HotkeyKey::K,
+        HotkeyKey::L,
+        HotkeyKey::M,
+        HotkeyKey::N,
+        HotkeyKey::O,
+        HotkeyKey::P,
+        HotkeyKey::Q,
+        HotkeyKey::R,
+        HotkeyKey::S,
+        HotkeyKey::T,
+        HotkeyKey::U,
+        HotkeyKey::V,
+        HotkeyKey::W,
+        HotkeyKey::X,
+        HotkeyKey::Y,
+        HotkeyKey::Z,
+        HotkeyKey::F1,
+        HotkeyKey::F2,
+        HotkeyKey::F3,
+        HotkeyKey::F4,
+        HotkeyKey::F5,
+        HotkeyKey::F6,
+        HotkeyKey::F7,
+        HotkeyKey::F8,
+        HotkeyKey::F9,
+        HotkeyKey::F10,
+        HotkeyKey::F11,
+        HotkeyKey::F12,
+        HotkeyKey::Space,
+        HotkeyKey::Enter,
+        HotkeyKey::Escape,
+        HotkeyKey::Tab,
+        HotkeyKey::Backspace,
+        HotkeyKey::Left,
+        HotkeyKey::Right,
+        HotkeyKey::Up,
+        HotkeyKey::Down,
+        HotkeyKey::Digit0,
+        HotkeyKey::Digit1,
+        HotkeyKey::Digit2,
+        HotkeyKey::Digit3,
+        HotkeyKey::Digit4,
+        HotkeyKey::Digit5,
+        HotkeyKey::Digit6,
+        HotkeyKey::Digit7,
+        HotkeyKey::Digit8,
+        HotkeyKey::Digit9,
+        HotkeyKey::Numpad0,
+        HotkeyKey::Numpad1,
+        HotkeyKey::Numpad2,
+        HotkeyKey::Numpad3,
+        HotkeyKey::Numpad4,
+        HotkeyKey::Numpad5,
+        HotkeyKey::Numpad6,
+        HotkeyKey::Numpad7,
+        HotkeyKey::Numpad8,
+        HotkeyKey::Numpad9,
+        HotkeyKey::NumpadAdd,
+        HotkeyKey::NumpadSubtract,
+        HotkeyKey::NumpadMultiply,
+        HotkeyKey::NumpadDivide,
+        HotkeyKey::NumpadDecimal,
+        HotkeyKey::NumpadEnter,
+        HotkeyKey::Comma,
+        HotkeyKey::Period,
+        HotkeyKey::Slash,
+        HotkeyKey::Semicolon,
+        HotkeyKey::Grave,
+        HotkeyKey::Insert,
+        HotkeyKey::Delete,
+        HotkeyKey::Home,
+        HotkeyKey::End,
+        HotkeyKey::PageUp,
+        HotkeyKey::PageDown,
+        HotkeyKey::Pause,
+        HotkeyKey::CtrlLeft,
+        HotkeyKey::CtrlRight,
+        HotkeyKey::AltLeft,
+        HotkeyKey::AltRight,
+        HotkeyKey::ShiftLeft,
+        HotkeyKey::ShiftRight,
+        HotkeyKey::MetaLeft,
+        HotkeyKey::MetaRight,
+        HotkeyKey::Mouse(MouseButton::Left),
+        HotkeyKey::Mouse(MouseButton::Right),
+        HotkeyKey::Mouse(MouseButton::Middle),
+    ];
+
+    pub fn is_modifier(self) -> bool {
+        matches!(
+            self,
+            HotkeyKey::CtrlLeft
+                | HotkeyKey::CtrlRight
+                | HotkeyKey::AltLeft
+                | HotkeyKey::AltRight
+                | HotkeyKey::ShiftLeft
+                | HotkeyKey::ShiftRight
+                | HotkeyKey::MetaLeft
+                | HotkeyKey::MetaRight
+        )
+    }
+
+    pub fn is_click(self) -> bool {
+        matches!(
+            self,
+            HotkeyKey::Mouse(MouseButton::Left) | HotkeyKey::Mouse(MouseButton::Right)
+        )
+    }
+
+    pub fn name(self) -> Cow<'static, str> {
+        let name = match self {
+            HotkeyKey::A => "a",
+            HotkeyKey::B => "b",
+            HotkeyKey::C => "c",
+            HotkeyKey::D => "d",
+            HotkeyKey::E => "e",
+            HotkeyKey::F => "f",
+            HotkeyKey::G => "g",
+            HotkeyKey::H => "h",
+            HotkeyKey::I => "i",
+            HotkeyKey::J => "j",
+            HotkeyKey::K => "k",
+            HotkeyKey::L => "l",
+            HotkeyKey::M => "m",
+            HotkeyKey::N => "n",
+            HotkeyKey::O => "o",
+            HotkeyKey::P => "p",
+            HotkeyKey::Q => "q",
+            HotkeyKey::R => "r",
+            HotkeyKey::S => "s",
+            HotkeyKey::T => "t",
+            HotkeyKey::U => "u",
+            HotkeyKey::V => "v",
+            HotkeyKey::W => "w",
+            HotkeyKey::X => "x",
+            HotkeyKey::Y => "y",
+            HotkeyKey::Z => "z",
+            HotkeyKey::F1 => "f1",
+            HotkeyKey::F2 => "f2",
+            HotkeyKey::F3 => "f3",
+            HotkeyKey::F4 => "f4",
+            HotkeyKey::F5 => "f5",
+            HotkeyKey::F6 => "f6",
+            HotkeyKey::F7 => "f7",
+            HotkeyKey::F8 => "f8",
+            HotkeyKey::F9 => "f9",
+            HotkeyKey::F10 => "f10",
+            HotkeyKey::F11 => "f11",
+            HotkeyKey::F12 => "f12",
+            HotkeyKey::Space => "space",
+            HotkeyKey::Enter => "enter",
+            HotkeyKey::Escape => "escape",
+            HotkeyKey::Tab => "tab",
+            HotkeyKey::Backspace => "backspace",
+            HotkeyKey::Left => "left",
+            HotkeyKey::Right => "right",
+            HotkeyKey::Up => "up",
+            HotkeyKey::Down => "down",
+            HotkeyKey::Digit0 => "0",
+            HotkeyKey::Digit1 => "1",
+            HotkeyKey::Digit2 => "2",
+            HotkeyKey::Digit3 => "3",
+            HotkeyKey::Digit4 => "4",
+            HotkeyKey::Digit5 => "5",
+            HotkeyKey::Digit6 => "6",
+            HotkeyKey::Digit7 => "7",
+            HotkeyKey::Digit8 => "8",
+            HotkeyKey::Digit9 => "9",
+            HotkeyKey::Numpad0 => "numpad0",
+            HotkeyKey::Numpad1 => "numpad1",
+            HotkeyKey::Numpad2 => "numpad2",
+            HotkeyKey::Numpad3 => "numpad3",
+            HotkeyKey::Numpad4 => "numpad4",
+            HotkeyKey::Numpad5 => "numpad5",
+            HotkeyKey::Numpad6 => "numpad6",
+            HotkeyKey::Numpad7 => "numpad7",
+            HotkeyKey::Numpad8 => "numpad8",
+            HotkeyKey::Numpad9 => "numpad9",
+            HotkeyKey::NumpadAdd => "numpad_add",
+            HotkeyKey::NumpadSubtract => "numpad_subtract",
+            HotkeyKey::NumpadMultiply => "numpad_multiply",
+            HotkeyKey::NumpadDivide => "numpad_divide",
+            HotkeyKey::NumpadDecimal => "numpad_decimal",
+            HotkeyKey::NumpadEnter => "numpad_enter",
+            HotkeyKey::Comma => "comma",
+            HotkeyKey::Period => "period",
+            HotkeyKey::Slash => "slash",
+            HotkeyKey::Semicolon => "semicolon",
+            HotkeyKey::Grave => "grave",
+            HotkeyKey::Insert => "insert",
+            HotkeyKey::Delete => "delete",
+            HotkeyKey::Home => "home",
+            HotkeyKey::End => "end",
+            HotkeyKey::PageUp => "page_up",
+            HotkeyKey::PageDown => "page_down",
+            HotkeyKey::Pause => "pause",
+            HotkeyKey::CtrlLeft => "ctrl_left",
+            HotkeyKey::CtrlRight => "ctrl_right",
+            HotkeyKey::AltLeft => "alt_left",
+            HotkeyKey::AltRight => "alt_right",
+            HotkeyKey::ShiftLeft => "shift_left",
+            HotkeyKey::ShiftRight => "shift_right",
+            HotkeyKey::MetaLeft => "meta_left",
+            HotkeyKey::MetaRight => "meta_right",
+            HotkeyKey::Mouse(MouseButton::Left) => "mouse_left",
+            HotkeyKey::Mouse(MouseButton::Right) => "mouse_right",
+            HotkeyKey::Mouse(MouseButton::Middle) => "mouse_middle",
+            HotkeyKey::Mouse(MouseButton::Other(code)) => return format!("mouse{code}").into(),
+        };
+        name.into()
+    }
+}
+
+impl fmt::Display for HotkeyKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.name())
+    }
+}
+
+impl FromStr for HotkeyKey {
+    type Err = HotkeyParseError;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let lowered = value.trim().to_ascii_lowercase();
+        let name = match lowered.as_str() {
+            "," => "comma",
+            "." => "period",
+            "/" => "slash",
+            ";" => "semicolon",
+            "`" => "grave",
+            "return" => "enter",
+            "esc" => "escape",
+            "pageup" => "page_up",
+            "pagedown" => "page_down",
+            other => other,
+        };
+        if let Some(key) = Self::ALL.into_iter().find(|key| key.name() == name) {
+            return Ok(key);
+        }
+        name.strip_prefix("mouse")
+            .and_then(|code| code.trim_start_matches('_').parse::<u8>().ok())
+            .map(|code| HotkeyKey::Mouse(MouseButton::Other(code)))
+            .ok_or_else(|| HotkeyParseError::UnknownKey(value.trim().to_string()))
+    }
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
@@ -71,6 +391,25 @@ impl HotkeyModifiers {
             meta: false,
         }
     }
+
+    pub fn without_key(self, key: HotkeyKey) -> Self {
+        match key {
+            HotkeyKey::CtrlLeft | HotkeyKey::CtrlRight => Self {
+                ctrl: false,
+                ..self
+            },
+            HotkeyKey::AltLeft | HotkeyKey::AltRight => Self { alt: false, ..self },
+            HotkeyKey::ShiftLeft | HotkeyKey::ShiftRight => Self {
+                shift: false,
+                ..self
+            },
+            HotkeyKey::MetaLeft | HotkeyKey::MetaRight => Self {
+                meta: false,
+                ..self
+            },
+            _ => self,
+        }
+    }
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
@@ -79,6 +418,53 @@ pub struct Hotkey {
     pub modifiers: HotkeyModifiers,
 }
 
+impl fmt::Display for Hotkey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (held, name) in [
+            (self.modifiers.ctrl, "ctrl"),
+            (self.modifiers.alt, "alt"),
+            (self.modifiers.shift, "shift"),
+            (self.modifiers.meta, "meta"),
+        ] {
+            if held {
+                write!(f, "{name}+")?;
+            }
+        }
+        write!(f, "{}", self.key)
+    }
+}
+
+impl FromStr for Hotkey {
+    type Err = HotkeyParseError;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        if value.trim().is_empty() {
+            return Err(HotkeyParseError::Empty);
+        }
+        let mut modifiers = HotkeyModifiers::none();
+        let mut key = None;
+        for part in value.split('+') {
+            match part.trim().to_ascii_lowercase().as_str() {
+                "ctrl" | "control" => modifiers.ctrl = true,
+                "alt" | "option" => modifiers.alt = true,
+                "shift" => modifiers.shift = true,
+                "meta" | "super" | "cmd" | "command" | "win" => modifiers.meta = true,
+                _ => {
+                    let parsed = part.parse::<HotkeyKey>()?;
+                    if key.replace(parsed).is_some() {
+                        return Err(HotkeyParseError::MultipleKeys(value.to_string()));
+                    }
+                }
+            }
+        }
+        let key = key.ok_or_else(|| HotkeyParseError::MissingKey(value.to_string()))?;
+        Ok(Hotkey {
+            key,
+            modifiers: modifiers.without_key(key),
+        })
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum HotkeyState {
     Pressed,
@@ -89,6 +475,19 @@ pub enum HotkeyState {
 pub enum HotkeyTrigger {
     Pressed,
     Released,
+    Held { min_ms: u64 },
+    DoubleTap { window_ms: u64 },
+}
+
+impl HotkeyTrigger {
+    fn order(self) -> (u8, u64) {
+        match self {
+            HotkeyTrigger::Pressed => (0, 0),
+            HotkeyTrigger::Released => (1, 0),
+            HotkeyTrigger::Held { min_ms } => (2, min_ms),
+            HotkeyTrigger::DoubleTap { window_ms } => (3, window_ms),
+        }
+    }
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
@@ -102,6 +501,7 @@ pub struct HotkeyEvent {
 pub struct HotkeyBinding {
     pub action: String,
     pub trigger: HotkeyTrigger,
+    pub enabled: bool,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -111,17 +511,76 @@ pub struct HotkeyActionEvent {
     pub state: HotkeyState,
 }
 
-#[derive(Debug, thiserror::Error)]
+#[derive(Debug, Clone, thiserror::Error)]
 pub enum HotkeyError {
     #[error("hotkey listener error: {0}")]
     Listener(String),
+    #[error("global shortcuts portal error: {0}")]
+    Portal(String),
+    #[error("global input grab is unsupported: {0}")]
+    GrabUnsupported(String),
     #[error("hotkey manager lock was poisoned")]
     ManagerLockPoisoned,
+    #[error("hotkey capture timed out")]
+    CaptureTimedOut,
+    #[error("hotkey capture was cancelled")]
+    CaptureCancelled,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum HotkeyParseError {
+    #[error("hotkey is empty")]
+    Empty,
+    #[error("unknown hotkey key '{0}'")]
+    UnknownKey(String),
+    #[error("hotkey '{0}' has no key")]
+    MissingKey(String),
+    #[error("hotkey '{0}' has more than one key")]
+    MultipleKeys(String),
 }
 
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[error("hotkey is already bound to '{existing_action}'")]
+pub struct ConflictError {
+    pub existing_action: String,
+}
+
+pub const DEFAULT_SEQUENCE_TIMEOUT: Duration = Duration::from_millis(1000);
+
 #[derive(Debug, Default)]
 pub struct HotkeyManager {
     bindings: HashMap<Hotkey, Vec<HotkeyBinding>>,
+    sequences: HashMap<Vec<Hotkey>, String>,
+    sequence_timeout: Option<Duration>,
+    capture: Option<mpsc::Sender<Hotkey>>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum SequenceMatch<'a> {
+    Complete(&'a str),
+    Partial,
+    Mismatch,
+}
+
+#[derive(Debug)]
+pub struct HotkeyCaptureSession {
+    receiver: mpsc::Receiver<Hotkey>,
+    timeout: Duration,
+}
+
+impl HotkeyCaptureSession {
+    pub fn timeout(&self) -> Duration {
+        self.timeout
+    }
+
+    pub fn wait(self) -> Result<Hotkey, HotkeyError> {
+        self.receiver
+            .recv_timeout(self.timeout)
+            .map_err(|err| match err {
+                mpsc::RecvTimeoutError::Timeout => HotkeyError::CaptureTimedOut,
+                mpsc::RecvTimeoutError::Disconnected => HotkeyError::CaptureCancelled,
+            })
+    }
 }
 
 impl HotkeyManager {
@@ -129,6 +588,15 @@ impl HotkeyManager {
         Self::default()
     }
 
+    pub fn with_sequence_timeout(mut self, timeout: Duration) -> Self {
+        self.sequence_timeout = Some(timeout);
+        self
+    }
+
+    pub fn sequence_timeout(&self) -> Duration {
+        self.sequence_timeout.unwrap_or(DEFAULT_SEQUENCE_TIMEOUT)
+    }
+
     pub fn register(&mut self, hotkey: Hotkey, action: impl Into<String>) -> Option<HotkeyBinding> {
         self.register_with_trigger(hotkey, HotkeyTrigger::Pressed, action)
     }
@@ -143,6 +611,7 @@ impl HotkeyManager {
         let binding = HotkeyBinding {
             action: action.into(),
             trigger,
+            enabled: true,
         };
 
         if let Some(index) = bindings
@@ -158,12 +627,95 @@ impl HotkeyManager {
         None
     }
 
+    pub fn try_register_with_trigger(
+        &mut self,
+        hotkey: Hotkey,
+        trigger: HotkeyTrigger,
+        action: impl Into<String>,
+    ) -> Result<(), ConflictError> {
+        let action = action.into();
+        if let Some(existing) = self.would_conflict(&hotkey, trigger) {
+            if existing != action {
+                return Err(ConflictError {
+                    existing_action: existing.to_string(),
+                });
+            }
+        }
+        self.register_with_trigger(hotkey, trigger, action);
+        Ok(())
+    }
+
+    pub fn register_sequence(
+        &mut self,
+        steps: &[Hotkey],
+        action: impl Into<String>,
+    ) -> Option<String> {
+        if steps.is_empty() {
+            return None;
+        }
+        self.sequences.insert(steps.to_vec(), action.into())
+    }
+
+    pub fn unregister_sequence(&mut self, steps: &[Hotkey]) -> Option<String> {
+        self.sequences.remove(steps)
+    }
+
+    pub fn would_conflict(&self, hotkey: &Hotkey, trigger: HotkeyTrigger) -> Option<&str> {
+        self.bindings.get(hotkey).and_then(|bindings| {
+            bindings
+                .iter()
+                .find(|binding| binding.trigger == trigger)
+                .map(|binding| binding.action.as_str())
+        })
+    }
+
+    pub fn begin_capture(&mut self, timeout: Duration) -> HotkeyCaptureSession {
+        let (sender, receiver) = mpsc::channel();
+        self.capture = Some(sender);
+        HotkeyCaptureSession { receiver, timeout }
+    }
+
+    fn deliver_capture(&mut self, hotkey: Hotkey) -> bool {
+        self.capture
+            .take()
+            .is_some_and(|sender| sender.send(hotkey).is_ok())
+    }
+
     pub fn unregister(&mut self, hotkey: &Hotkey) -> Option<HotkeyBinding> {
         self.bindings
             .remove(hotkey)
             .and_then(|mut bindings| bindings.pop())
     }
 
+    pub fn bindings(&self) -> Vec<(Hotkey, HotkeyBinding)> {
+        let mut listed: Vec<(Hotkey, HotkeyBinding)> = self
+            .bindings
+            .iter()
+            .flat_map(|(hotkey, bindings)| {
+                bindings
+                    .iter()
+                    .map(move |binding| (*hotkey, binding.clone()))
+            })
+            .collect();
+        listed
+            .sort_by_cached_key(|(hotkey, binding)| (hotkey.to_string(), binding.trigger.order()));
+        listed
+    }
+
+    pub fn set_enabled(&mut self, hotkey: &Hotkey, trigger: HotkeyTrigger, enabled: bool) -> bool {
+        match self.bindings.get_mut(hotkey).and_then(|bindings| {
+            bindings
+                .iter_mut()
+                .find(|binding| binding.trigger == trigger)
+        }) {
+            Some(binding) => {
+                binding.enabled = enabled;
+                true
+            }
+            None => false,
+        }
+    }
+
     pub fn resolve(&self, event: &HotkeyEvent) -> Option<&str> {
         let hotkey = Hotkey {
             key: event.key,
@@ -172,12 +724,70 @@ impl HotkeyManager {
         self.bindings.get(&hotkey).and_then(|bindings| {
             bindings
                 .iter()
+                .filter(|binding| binding.enabled)
                 .find(|binding| trigger_matches(binding.trigger, event.state))
                 .map(|binding| binding.action.as_str())
         })
     }
 }
 
+impl HotkeyManager {
+    fn held_bindings(&self, hotkey: &Hotkey) -> Vec<(Duration, &str)> {
+        self.bindings
+            .get(hotkey)
+            .into_iter()
+            .flatten()
+            .filter(|binding| binding.enabled)
+            .filter_map(|binding| match binding.trigger {
+                HotkeyTrigger::Held { min_ms } => {
+                    Some((Duration::from_millis(min_ms), binding.action.as_str()))
+                }
+                _ => None,
+            })
+            .collect()
+    }
+
+    fn is_bound(&self, hotkey: &Hotkey) -> bool {
+        let has_binding = self
+            .bindings
+            .get(hotkey)
+            .is_some_and(|bindings| bindings.iter().any(|binding| binding.enabled));
+        has_binding
+            || self
+                .sequences
+                .keys()
+                .any(|sequence| sequence.first() == Some(hotkey))
+    }
+
+    fn match_sequence(&self, steps: &[Hotkey]) -> SequenceMatch<'_> {
+        if let Some(action) = self.sequences.get(steps) {
+            return SequenceMatch::Complete(action);
+        }
+        if self
+            .sequences
+            .keys()
+            .any(|sequence| sequence.len() > steps.len() && sequence.starts_with(steps))
+        {
+            SequenceMatch::Partial
+        } else {
+            SequenceMatch::Mismatch
+        }
+    }
+
+    fn double_tap_binding(&self, hotkey: &Hotkey) -> Option<(Duration, &str)> {
+        self.bindings
+            .get(hotkey)?
+            .iter()
+            .filter(|binding| binding.enabled)
+            .find_map(|binding| match binding.trigger {
+                HotkeyTrigger::DoubleTap { window_ms } => {
+                    Some((Duration::from_millis(window_ms), binding.action.as_str()))
+                }
+                _ => None,
+            })
+    }
+}
+
 fn trigger_matches(trigger: HotkeyTrigger, state: HotkeyState) -> bool {
     matches!(
         (trigger, state),
@@ -188,9 +798,18 @@ fn trigger_matches(trigger: HotkeyTrigger, state: HotkeyState) -> bool {
 
 pub struct HotkeyListenerHandle {
     join_handle: std::thread::JoinHandle<Result<(), HotkeyError>>,
+    stopped: Arc<AtomicBool>,
 }
 
 impl HotkeyListenerHandle {
+    pub fn stop(&self) {
+        self.stopped.store(true, Ordering::SeqCst);
+    }
+
+    pub fn is_stopped(&self) -> bool {
+        self.stopped.load(Ordering::SeqCst)
+    }
+
     pub fn join(self) -> Result<(), HotkeyError> {
         match self.join_handle.join() {
             Ok(result) => result,
@@ -201,6 +820,13 @@ impl HotkeyListenerHandle {
     }
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ListenerMode {
+    #[default]
+    Listen,
+    Grab,
+}
+
 pub struct GlobalHotkeyListener {
     manager: Arc<Mutex<HotkeyManager>>,
 }
@@ -210,141 +836,640 @@ impl GlobalHotkeyListener {
         Self { manager }
     }
 
+    /// Mode the process-wide rdev thread was started in, or `None` before
+    /// the first start.
+    pub fn running_mode() -> Option<ListenerMode> {
+        RDEV_MODE.lock().ok().and_then(|running| *running)
+    }
+
     pub fn start(
         &self,
+        mode: ListenerMode,
+    ) -> Result<(HotkeyListenerHandle, mpsc::Receiver<HotkeyActionEvent>), HotkeyError> {
+        self.start_with(mode, listen_rdev)
+    }
+
+    fn start_with(
+        &self,
+        mode: ListenerMode,
+        backend: fn(ListenerMode, EventHandler<rdev::Event, bool>) -> Result<(), HotkeyError>,
     ) -> Result<(HotkeyListenerHandle, mpsc::Receiver<HotkeyActionEvent>), HotkeyError> {
         let (sender, receiver) = mpsc::channel();
+        let (ready, startup) = mpsc::channel();
         let manager = Arc::clone(&self.manager);
 
-        let handle = spawn_listener(manager, sender, |mut handler| {
-            rdev::listen(move |event| handler(event))
-                .map_err(|error| HotkeyError::Listener(format!("{error:?}")))
-        });
+        let handle = match mode {
+            ListenerMode::Listen => spawn_listener(manager, sender, move |mut handler| {
+                let result = backend(
+                    ListenerMode::Listen,
+                    Box::new(move |event| {
+                        handler(event);
+                        false
+                    }),
+                );
+                let _ = ready.send(result.clone());
+                result
+            }),
+            ListenerMode::Grab => spawn_grab_listener(manager, sender, move |consume| {
+                let result = backend(ListenerMode::Grab, consume);
+                let _ = ready.send(result.clone());
+                result
+            }),
+        };
 
+        let started = startup.recv().unwrap_or_else(|_| {
+            Err(HotkeyError::Listener(
+                "hotkey listener exited before starting".to_string(),
+            ))
+        });
+        if let Err(error) = started {
+            handle.stop();
+            return Err(error);
+        }
         Ok((handle, receiver))
     }
 }
 
+type EventHandler<E, R = ()> = Box<dyn FnMut(E) -> R + Send>;
+
+struct SharedListener<E, R = ()> {
+    handler: Mutex<Option<EventHandler<E, R>>>,
+}
+
+impl<E, R: Default> SharedListener<E, R> {
+    const fn new() -> Self {
+        Self {
+            handler: Mutex::new(None),
+        }
+    }
+
+    fn replace(&self, handler: Option<EventHandler<E, R>>) {
+        if let Ok(mut current) = self.handler.lock() {
+            *current = handler;
+        }
+    }
+
+    fn dispatch(&self, event: E) -> R {
+        match self.handler.lock() {
+            Ok(mut handler) => handler
+                .as_mut()
+                .map(|handler| handler(event))
+                .unwrap_or_default(),
+            Err(_) => R::default(),
+        }
+    }
+}
+
+// rdev::listen and rdev::grab never return, so a single thread serves the
+// whole process and every start swaps in its own handler, dropping the old
+// one. The handler reports whether a grabbed event should be swallowed. The
+// thread cannot be restarted, so its mode is fixed for the process lifetime.
+static RDEV_LISTENER: SharedListener<rdev::Event, bool> = SharedListener::new();
+static RDEV_MODE: Mutex<Option<ListenerMode>> = Mutex::new(None);
+static RDEV_FAILURE: OnceLock<String> = OnceLock::new();
+
+// rdev reports no readiness, so a thread that has not failed within this
+// window is treated as listening.
+const RDEV_STARTUP_TIMEOUT: Duration = Duration::from_millis(200);
+
+fn listen_rdev(
+    mode: ListenerMode,
+    handler: EventHandler<rdev::Event, bool>,
+) -> Result<(), HotkeyError> {
+    if let Some(failure) = RDEV_FAILURE.get() {
+        return Err(HotkeyError::Listener(failure.clone()));
+    }
+    let mut running = RDEV_MODE
+        .lock()
+        .map_err(|_| HotkeyError::Listener("rdev listener state poisoned".to_string()))?;
+    RDEV_LISTENER.replace(Some(handler));
+    if let Some(current) = *running {
+        if current != mode {
+            log::warn!("hotkey listener is running in {current:?} mode; {mode:?} needs a restart");
+        }
+        return Ok(());
+    }
+    *running = Some(mode);
+    drop(running);
+
+    let (failed, failure) = mpsc::channel();
+    std::thread::spawn(move || {
+        let result = run_rdev(mode, grab_rdev, || {
+            rdev::listen(|event| {
+                RDEV_LISTENER.dispatch(event);
+            })
+            .map_err(|error| HotkeyError::Listener(format!("{error:?}")))
+        });
+        if let Err(error) = result {
+            log::warn!("global hotkey listener failed: {error}");
+            let _ = RDEV_FAILURE.set(error.to_string());
+            RDEV_LISTENER.replace(None);
+            let _ = failed.send(error);
+        }
+    });
+    match failure.recv_timeout(RDEV_STARTUP_TIMEOUT) {
+        Ok(error) => Err(error),
+        Err(_) => Ok(()),
+    }
+}
+
+fn run_rdev(
+    mode: ListenerMode,
+    grab: impl FnOnce() -> Result<(), HotkeyError>,
+    listen: impl FnOnce() -> Result<(), HotkeyError>,
+) -> Result<(), HotkeyError> {
+    if mode == ListenerMode::Grab {
+        match grab() {
+            Err(HotkeyError::GrabUnsupported(reason)) => {
+                log::warn!("hotkey grab unavailable ({reason}); falling back to listen");
+            }
+            result => return result,
+        }
+    }
+    listen()
+}
+
+#[cfg(feature = "grab")]
+fn grab_rdev() -> Result<(), HotkeyError> {
+    rdev::grab(|event| (!RDEV_LISTENER.dispatch(event.clone())).then_some(event))
+        .map_err(|error| HotkeyError::GrabUnsupported(format!("{error:?}")))
+}
+
+#[cfg(not(feature = "grab"))]
+fn grab_rdev() -> Result<(), HotkeyError> {
+    Err(HotkeyError::GrabUnsupported(
+        "core-input is built without the grab feature".to_string(),
+    ))
+}
+
+type Clock = Arc<dyn Fn() -> Instant + Send + Sync>;
+
+const HOLD_POLL_INTERVAL: Duration = Duration::from_millis(10);
+
 fn spawn_listener(
     manager: Arc<Mutex<HotkeyManager>>,
     sender: mpsc::Sender<HotkeyActionEvent>,
     listen: impl FnOnce(Box<dyn FnMut(rdev::Event) + Send>) -> Result<(), HotkeyError> + Send + 'static,
 ) -> HotkeyListenerHandle {
-    let join_handle = std::thread::spawn(move || {
-        let mut modifiers = ModifierState::default();
-        let mut pressed_keys: HashMap<HotkeyKey, HotkeyModifiers> = HashMap::new();
-        let mut handler = move |event: rdev::Event| match event.event_type {
-            rdev::EventType::KeyPress(key) => {
-                if modifiers.update(key, true) {
-                    return;
-                }
+    spawn_listener_with_clock(
+        manager,
+        sender,
+        Arc::new(Instant::now),
+        ListenerState::handle,
+        listen,
+    )
+}
 
-                if let Some(mapped) = map_key(key) {
-                    let modifiers_snapshot = modifiers.as_modifiers();
-                    if matches!(pressed_keys.get(&mapped), Some(existing) if *existing == modifiers_snapshot)
-                    {
-                        return;
-                    }
-                    pressed_keys.insert(mapped, modifiers_snapshot);
-
-                    let event = HotkeyEvent {
-                        key: mapped,
-                        modifiers: modifiers_snapshot,
-                        state: HotkeyState::Pressed,
-                    };
-
-                    if let Ok(manager) = manager.lock() {
-                        if let Some(action) = manager.resolve(&event) {
-                            let _ = sender.send(HotkeyActionEvent {
-                                action: action.to_string(),
-                                hotkey: Hotkey {
-                                    key: event.key,
-                                    modifiers: event.modifiers,
-                                },
-                                state: event.state,
-                            });
-                        }
-                    }
-                }
-            }
-            rdev::EventType::KeyRelease(key) => {
-                modifiers.update(key, false);
+fn spawn_grab_listener(
+    manager: Arc<Mutex<HotkeyManager>>,
+    sender: mpsc::Sender<HotkeyActionEvent>,
+    grab: impl FnOnce(EventHandler<rdev::Event, bool>) -> Result<(), HotkeyError> + Send + 'static,
+) -> HotkeyListenerHandle {
+    spawn_listener_with_clock(
+        manager,
+        sender,
+        Arc::new(Instant::now),
+        ListenerState::handle_grab,
+        grab,
+    )
+}
 
-                if let Some(mapped) = map_key(key) {
-                    if pressed_keys.remove(&mapped).is_none() {
-                        return;
-                    }
+pub(crate) fn spawn_shortcut_listener(
+    manager: Arc<Mutex<HotkeyManager>>,
+    sender: mpsc::Sender<HotkeyActionEvent>,
+    listen: impl FnOnce(Box<dyn FnMut((Hotkey, HotkeyState)) + Send>) -> Result<(), HotkeyError>
+        + Send
+        + 'static,
+) -> HotkeyListenerHandle {
+    spawn_listener_with_clock(
+        manager,
+        sender,
+        Arc::new(Instant::now),
+        ListenerState::handle_shortcut,
+        listen,
+    )
+}
 
-                    let event = HotkeyEvent {
-                        key: mapped,
-                        modifiers: modifiers.as_modifiers(),
-                        state: HotkeyState::Released,
-                    };
-
-                    if let Ok(manager) = manager.lock() {
-                        if let Some(action) = manager.resolve(&event) {
-                            let _ = sender.send(HotkeyActionEvent {
-                                action: action.to_string(),
-                                hotkey: Hotkey {
-                                    key: event.key,
-                                    modifiers: event.modifiers,
-                                },
-                                state: event.state,
-                            });
-                        }
-                    }
-                }
+#[cfg(all(target_os = "linux", feature = "evdev"))]
+pub(crate) fn spawn_key_listener(
+    manager: Arc<Mutex<HotkeyManager>>,
+    sender: mpsc::Sender<HotkeyActionEvent>,
+    listen: impl FnOnce(Box<dyn FnMut((HotkeyKey, bool)) + Send>, Arc<AtomicBool>) -> Result<(), HotkeyError>
+        + Send
+        + 'static,
+) -> HotkeyListenerHandle {
+    let stopped = Arc::new(AtomicBool::new(false));
+    let listen_stopped = Arc::clone(&stopped);
+    spawn_listener_with_stop_flag(
+        manager,
+        sender,
+        Arc::new(Instant::now),
+        stopped,
+        ListenerState::handle_key,
+        move |handler| listen(handler, listen_stopped),
+    )
+}
+
+fn spawn_listener_with_clock<E: Send + 'static, R: Default + 'static>(
+    manager: Arc<Mutex<HotkeyManager>>,
+    sender: mpsc::Sender<HotkeyActionEvent>,
+    clock: Clock,
+    dispatch: fn(&mut ListenerState, E) -> R,
+    listen: impl FnOnce(EventHandler<E, R>) -> Result<(), HotkeyError> + Send + 'static,
+) -> HotkeyListenerHandle {
+    spawn_listener_with_stop_flag(
+        manager,
+        sender,
+        clock,
+        Arc::new(AtomicBool::new(false)),
+        dispatch,
+        listen,
+    )
+}
+
+fn spawn_listener_with_stop_flag<E: Send + 'static, R: Default + 'static>(
+    manager: Arc<Mutex<HotkeyManager>>,
+    sender: mpsc::Sender<HotkeyActionEvent>,
+    clock: Clock,
+    stopped: Arc<AtomicBool>,
+    dispatch: fn(&mut ListenerState, E) -> R,
+    listen: impl FnOnce(EventHandler<E, R>) -> Result<(), HotkeyError> + Send + 'static,
+) -> HotkeyListenerHandle {
+    let listener_stopped = Arc::clone(&stopped);
+    let join_handle = std::thread::spawn(move || {
+        let state = Arc::new(Mutex::new(ListenerState {
+            manager,
+            sender,
+            clock,
+            modifiers: ModifierState::default(),
+            pressed_keys: HashMap::new(),
+            suppressed_keys: HashSet::new(),
+            consumed_keys: HashSet::new(),
+            last_taps: HashMap::new(),
+            sequence: SequenceProgress::default(),
+        }));
+
+        let ticker = Arc::downgrade(&state);
+        let ticker_stopped = Arc::clone(&listener_stopped);
+        std::thread::spawn(move || loop {
+            if ticker_stopped.load(Ordering::SeqCst) {
+                break;
             }
-            _ => {}
-        };
+            {
+                let Some(shared) = ticker.upgrade() else {
+                    break;
+                };
+                if let Ok(mut state) = shared.lock() {
+                    state.poll_holds();
+                };
+            }
+            std::thread::sleep(HOLD_POLL_INTERVAL);
+        });
 
-        listen(Box::new(move |event| handler(event)))
+        listen(Box::new(move |event| {
+            if listener_stopped.load(Ordering::SeqCst) {
+                return R::default();
+            }
+            match state.lock() {
+                Ok(mut state) => dispatch(&mut state, event),
+                Err(_) => R::default(),
+            }
+        }))
     });
 
-    HotkeyListenerHandle { join_handle }
+    HotkeyListenerHandle {
+        join_handle,
+        stopped,
+    }
+}
+
+struct PressRecord {
+    modifiers: HotkeyModifiers,
+    at: Instant,
+    held_actions: Vec<String>,
+}
+
+struct ListenerState {
+    manager: Arc<Mutex<HotkeyManager>>,
+    sender: mpsc::Sender<HotkeyActionEvent>,
+    clock: Clock,
+    modifiers: ModifierState,
+    pressed_keys: HashMap<HotkeyKey, PressRecord>,
+    suppressed_keys: HashSet<HotkeyKey>,
+    consumed_keys: HashSet<HotkeyKey>,
+    last_taps: HashMap<Hotkey, Instant>,
+    sequence: SequenceProgress,
 }
 
 #[derive(Default)]
-struct ModifierState {
-    ctrl: bool,
-    alt: bool,
-    shift: bool,
-    meta: bool,
+struct SequenceProgress {
+    steps: Vec<Hotkey>,
+    at: Option<Instant>,
 }
 
-impl ModifierState {
-    fn update(&mut self, key: rdev::Key, pressed: bool) -> bool {
-        match key {
-            rdev::Key::ControlLeft | rdev::Key::ControlRight => {
-                self.ctrl = pressed;
-                true
+impl SequenceProgress {
+    fn advance(&mut self, manager: &HotkeyManager, hotkey: Hotkey, now: Instant) -> Option<String> {
+        let expired = self
+            .at
+            .is_none_or(|at| now.saturating_duration_since(at) > manager.sequence_timeout());
+        if expired {
+            self.steps.clear();
+        }
+        let mut steps = self.steps.clone();
+        steps.push(hotkey);
+        let mut outcome = manager.match_sequence(&steps);
+        if outcome == SequenceMatch::Mismatch && !self.steps.is_empty() {
+            if hotkey.key.is_modifier() {
+                return None;
             }
-            rdev::Key::ShiftLeft | rdev::Key::ShiftRight => {
-                self.shift = pressed;
-                true
+            steps = vec![hotkey];
+            outcome = manager.match_sequence(&steps);
+        }
+        match outcome {
+            SequenceMatch::Complete(action) => {
+                self.steps.clear();
+                self.at = None;
+                Some(action.to_string())
             }
-            rdev::Key::Alt | rdev::Key::AltGr => {
-                self.alt = pressed;
-                true
+            SequenceMatch::Partial => {
+                self.steps = steps;
+                self.at = Some(now);
+                None
             }
-            rdev::Key::MetaLeft | rdev::Key::MetaRight => {
-                self.meta = pressed;
-                true
+            SequenceMatch::Mismatch => {
+                self.steps.clear();
+                self.at = None;
+                None
             }
-            _ => false,
         }
     }
+}
 
-    fn as_modifiers(&self) -> HotkeyModifiers {
-        HotkeyModifiers {
-            ctrl: self.ctrl,
-            alt: self.alt,
-            shift: self.shift,
-            meta: self.meta,
+impl ListenerState {
+    fn handle(&mut self, event: rdev::Event) {
+        self.poll_holds();
+        match event.event_type {
+            rdev::EventType::KeyPress(key) => {
+                let modifiers = self.modifiers.as_modifiers();
+                self.modifiers.update(key, true);
+                if let Some(mapped) = map_key(key) {
+                    self.press(mapped, modifiers);
+                }
+            }
+            rdev::EventType::KeyRelease(key) => {
+                self.modifiers.update(key, false);
+                if let Some(mapped) = map_key(key) {
+                    self.release(mapped, self.modifiers.as_modifiers());
+                }
+            }
+            rdev::EventType::ButtonPress(button) => {
+                self.press(map_button(button), self.modifiers.as_modifiers());
+            }
+            rdev::EventType::ButtonRelease(button) => {
+                self.release(map_button(button), self.modifiers.as_modifiers());
+            }
+            _ => {}
+        }
+    }
+
+    fn handle_grab(&mut self, event: rdev::Event) -> bool {
+        let (key, pressed) = match event.event_type {
+            rdev::EventType::KeyPress(key) => (map_key(key), true),
+            rdev::EventType::KeyRelease(key) => (map_key(key), false),
+            rdev::EventType::ButtonPress(button) => (Some(map_button(button)), true),
+            rdev::EventType::ButtonRelease(button) => (Some(map_button(button)), false),
+            _ => (None, false),
+        };
+        self.handle(event);
+        let Some(key) = key else {
+            return false;
+        };
+        if pressed {
+            self.consumed_keys.contains(&key)
+        } else {
+            self.consumed_keys.remove(&key)
+        }
+    }
+
+    #[cfg(all(target_os = "linux", feature = "evdev"))]
+    fn handle_key(&mut self, (key, pressed): (HotkeyKey, bool)) {
+        self.poll_holds();
+        if pressed {
+            let modifiers = self.modifiers.as_modifiers();
+            self.modifiers.update_key(key, true);
+            self.press(key, modifiers);
+        } else {
+            self.modifiers.update_key(key, false);
+            self.release(key, self.modifiers.as_modifiers());
+        }
+    }
+
+    fn handle_shortcut(&mut self, (hotkey, state): (Hotkey, HotkeyState)) {
+        self.poll_holds();
+        match state {
+            HotkeyState::Pressed => self.press(hotkey.key, hotkey.modifiers),
+            HotkeyState::Released => self.release(hotkey.key, hotkey.modifiers),
+        }
+    }
+
+    fn press(&mut self, mapped: HotkeyKey, modifiers: HotkeyModifiers) {
+        if self
+            .pressed_keys
+            .get(&mapped)
+            .is_some_and(|record| record.modifiers == modifiers)
+        {
+            return;
+        }
+        let now = (self.clock)();
+        self.pressed_keys.insert(
+            mapped,
+            PressRecord {
+                modifiers,
+                at: now,
+                held_actions: Vec::new(),
+            },
+        );
+
+        let hotkey = Hotkey {
+            key: mapped,
+            modifiers,
+        };
+        let Ok(mut manager) = self.manager.lock() else {
+            return;
+        };
+        self.consumed_keys.remove(&mapped);
+        if !mapped.is_modifier() && !mapped.is_click() && manager.deliver_capture(hotkey) {
+            self.suppressed_keys.insert(mapped);
+            self.consumed_keys.insert(mapped);
+            return;
+        }
+        if let Some(action) = self.sequence.advance(&manager, hotkey, now) {
+            self.suppressed_keys.insert(mapped);
+            self.consumed_keys.insert(mapped);
+            send_action(&self.sender, &action, hotkey, HotkeyState::Pressed);
+            return;
+        }
+        if manager.is_bound(&hotkey) {
+            self.consumed_keys.insert(mapped);
+        }
+
+        let event = HotkeyEvent {
+            key: mapped,
+            modifiers,
+            state: HotkeyState::Pressed,
+        };
+        if let Some(action) = manager.resolve(&event) {
+            send_action(&self.sender, action, hotkey, HotkeyState::Pressed);
+        }
+        if let Some((window, action)) = manager.double_tap_binding(&hotkey) {
+            let tapped = self
+                .last_taps
+                .get(&hotkey)
+                .is_some_and(|at| now.saturating_duration_since(*at) <= window);
+            if tapped {
+                self.last_taps.remove(&hotkey);
+                send_action(&self.sender, action, hotkey, HotkeyState::Pressed);
+            }
+        }
+    }
+
+    fn release(&mut self, mapped: HotkeyKey, modifiers: HotkeyModifiers) {
+        let Some(record) = self.pressed_keys.remove(&mapped) else {
+            return;
+        };
+        if self.suppressed_keys.remove(&mapped) {
+            return;
+        }
+
+        let pressed = Hotkey {
+            key: mapped,
+            modifiers: record.modifiers,
+        };
+        if !record.held_actions.is_empty() {
+            for action in &record.held_actions {
+                send_action(&self.sender, action, pressed, HotkeyState::Released);
+            }
+            return;
+        }
+
+        let event = HotkeyEvent {
+            key: mapped,
+            modifiers,
+            state: HotkeyState::Released,
+        };
+        if let Ok(manager) = self.manager.lock() {
+            if let Some(action) = manager.resolve(&event) {
+                let hotkey = Hotkey {
+                    key: event.key,
+                    modifiers: event.modifiers,
+                };
+                send_action(&self.sender, action, hotkey, HotkeyState::Released);
+            }
+        }
+        self.last_taps.insert(pressed, (self.clock)());
+    }
+
+    fn poll_holds(&mut self) {
+        if self.pressed_keys.is_empty() {
+            return;
+        }
+        let now = (self.clock)();
+        let Ok(manager) = self.manager.lock() else {
+            return;
+        };
+        for (key, record) in self.pressed_keys.iter_mut() {
+            if self.suppressed_keys.contains(key) {
+                continue;
+            }
+            let hotkey = Hotkey {
+                key: *key,
+                modifiers: record.modifiers,
+            };
+            let held_for = now.saturating_duration_since(record.at);
+            for (min_held, action) in manager.held_bindings(&hotkey) {
+                if held_for >= min_held && !record.held_actions.iter().any(|held| held == action) {
+                    record.held_actions.push(action.to_string());
+                    send_action(&self.sender, action, hotkey, HotkeyState::Pressed);
+                }
+            }
         }
     }
 }
 
+fn send_action(
+    sender: &mpsc::Sender<HotkeyActionEvent>,
+    action: &str,
+    hotkey: Hotkey,
+    state: HotkeyState,
+) {
+    let _ = sender.send(HotkeyActionEvent {
+        action: action.to_string(),
+        hotkey,
+        state,
+    });
+}
+
+#[derive(Default)]
+struct ModifierState {
+    ctrl: bool,
+    alt: bool,
+    shift: bool,
+    meta: bool,
+}
+
+impl ModifierState {
+    fn update(&mut self, key: rdev::Key, pressed: bool) -> bool {
+        match key {
+            rdev::Key::ControlLeft | rdev::Key::ControlRight => {
+                self.ctrl = pressed;
+                true
+            }
+            rdev::Key::ShiftLeft | rdev::Key::ShiftRight => {
+                self.shift = pressed;
+                true
+            }
+            rdev::Key::Alt | rdev::Key::AltGr => {
+                self.alt = pressed;
+                true
+            }
+            rdev::Key::MetaLeft | rdev::Key::MetaRight => {
+                self.meta = pressed;
+                true
+            }
+            _ => false,
+        }
+    }
+
+    #[cfg(all(target_os = "linux", feature = "evdev"))]
+    fn update_key(&mut self, key: HotkeyKey, pressed: bool) {
+        match key {
+            HotkeyKey::CtrlLeft | HotkeyKey::CtrlRight => self.ctrl = pressed,
+            HotkeyKey::AltLeft | HotkeyKey::AltRight => self.alt = pressed,
+            HotkeyKey::ShiftLeft | HotkeyKey::ShiftRight => self.shift = pressed,
+            HotkeyKey::MetaLeft | HotkeyKey::MetaRight => self.meta = pressed,
+            _ => {}
+        }
+    }
+
+    fn as_modifiers(&self) -> HotkeyModifiers {
+        HotkeyModifiers {
+            ctrl: self.ctrl,
+            alt: self.alt,
+            shift: self.shift,
+            meta: self.meta,
+        }
+    }
+}
+
+fn map_button(button: rdev::Button) -> HotkeyKey {
+    HotkeyKey::Mouse(match button {
+        rdev::Button::Left => MouseButton::Left,
+        rdev::Button::Right => MouseButton::Right,
+        rdev::Button::Middle => MouseButton::Middle,
+        rdev::Button::Unknown(code) => MouseButton::Other(code),
+    })
+}
+
 fn map_key(key: rdev::Key) -> Option<HotkeyKey> {
     match key {
         rdev::Key::KeyA => Some(HotkeyKey::A),
@@ -394,6 +1519,52 @@ fn map_key(key: rdev::Key) -> Option<HotkeyKey> {
         rdev::Key::RightArrow => Some(HotkeyKey::Right),
         rdev::Key::UpArrow => Some(HotkeyKey::Up),
         rdev::Key::DownArrow => Some(HotkeyKey::Down),
+        rdev::Key::Num0 => Some(HotkeyKey::Digit0),
+        rdev::Key::Num1 => Some(HotkeyKey::Digit1),
+        rdev::Key::Num2 => Some(HotkeyKey::Digit2),
+        rdev::Key::Num3 => Some(HotkeyKey::Digit3),
+        rdev::Key::Num4 => Some(HotkeyKey::Digit4),
+        rdev::Key::Num5 => Some(HotkeyKey::Digit5),
+        rdev::Key::Num6 => Some(HotkeyKey::Digit6),
+        rdev::Key::Num7 => Some(HotkeyKey::Digit7),
+        rdev::Key::Num8 => Some(HotkeyKey::Digit8),
+        rdev::Key::Num9 => Some(HotkeyKey::Digit9),
+        rdev::Key::Kp0 => Some(HotkeyKey::Numpad0),
+        rdev::Key::Kp1 => Some(HotkeyKey::Numpad1),
+        rdev::Key::Kp2 => Some(HotkeyKey::Numpad2),
+        rdev::Key::Kp3 => Some(HotkeyKey::Numpad3),
+        rdev::Key::Kp4 => Some(HotkeyKey::Numpad4),
+        rdev::Key::Kp5 => Some(HotkeyKey::Numpad5),
+        rdev::Key::Kp6 => Some(HotkeyKey::Numpad6),
+        rdev::Key::Kp7 => Some(HotkeyKey::Numpad7),
+        rdev::Key::Kp8 => Some(HotkeyKey::Numpad8),
+        rdev::Key::Kp9 => Some(HotkeyKey::Numpad9),
+        rdev::Key::KpPlus => Some(HotkeyKey::NumpadAdd),
+        rdev::Key::KpMinus => Some(HotkeyKey::NumpadSubtract),
+        rdev::Key::KpMultiply => Some(HotkeyKey::NumpadMultiply),
+        rdev::Key::KpDivide => Some(HotkeyKey::NumpadDivide),
+        rdev::Key::KpDelete => Some(HotkeyKey::NumpadDecimal),
+        rdev::Key::KpReturn => Some(HotkeyKey::NumpadEnter),
+        rdev::Key::Comma => Some(HotkeyKey::Comma),
+        rdev::Key::Dot => Some(HotkeyKey::Period),
+        rdev::Key::Slash => Some(HotkeyKey::Slash),
+        rdev::Key::SemiColon => Some(HotkeyKey::Semicolon),
+        rdev::Key::BackQuote => Some(HotkeyKey::Grave),
+        rdev::Key::Insert => Some(HotkeyKey::Insert),
+        rdev::Key::Delete => Some(HotkeyKey::Delete),
+        rdev::Key::Home => Some(HotkeyKey::Home),
+        rdev::Key::End => Some(HotkeyKey::End),
+        rdev::Key::PageUp => Some(HotkeyKey::PageUp),
+        rdev::Key::PageDown => Some(HotkeyKey::PageDown),
+        rdev::Key::Pause => Some(HotkeyKey::Pause),
+        rdev::Key::ControlLeft => Some(HotkeyKey::CtrlLeft),
+        rdev::Key::ControlRight => Some(HotkeyKey::CtrlRight),
+        rdev::Key::Alt => Some(HotkeyKey::AltLeft),
+        rdev::Key::AltGr => Some(HotkeyKey::AltRight),
+        rdev::Key::ShiftLeft => Some(HotkeyKey::ShiftLeft),
+        rdev::Key::ShiftRight => Some(HotkeyKey::ShiftRight),
+        rdev::Key::MetaLeft => Some(HotkeyKey::MetaLeft),
+        rdev::Key::MetaRight => Some(HotkeyKey::MetaRight),
         _ => None,
     }
 }
@@ -401,11 +1572,14 @@ fn map_key(key: rdev::Key) -> Option<HotkeyKey> {
 #[cfg(test)]
 mod tests {
     use super::{
-        spawn_listener, Hotkey, HotkeyError, HotkeyEvent, HotkeyKey, HotkeyManager,
-        HotkeyModifiers, HotkeyState, HotkeyTrigger,
+        map_key, run_rdev, spawn_grab_listener, spawn_listener, spawn_listener_with_clock, Clock,
+        ConflictError, GlobalHotkeyListener, Hotkey, HotkeyError, HotkeyEvent, HotkeyKey,
+        HotkeyManager, HotkeyModifiers, HotkeyParseError, HotkeyState, HotkeyTrigger, ListenerMode,
+        ListenerState, MouseButton, SharedListener,
     };
+    use std::sync::atomic::{AtomicBool, Ordering};
     use std::sync::{mpsc, Arc, Mutex};
-    use std::time::SystemTime;
+    use std::time::{Duration, Instant, SystemTime};
 
     #[test]
     fn hotkey_manager_resolves_event() {
@@ -490,6 +1664,104 @@ mod tests {
         assert_eq!(manager.resolve(&released_event), Some("release-only"));
     }
 
+    #[test]
+    fn hotkey_manager_lists_and_disables_bindings() {
+        let mut manager = HotkeyManager::new();
+        let ptt = Hotkey {
+            key: HotkeyKey::Space,
+            modifiers: HotkeyModifiers {
+                ctrl: true,
+                ..HotkeyModifiers::none()
+            },
+        };
+        let cancel = Hotkey {
+            key: HotkeyKey::Escape,
+            modifiers: HotkeyModifiers::none(),
+        };
+        manager.register_with_trigger(ptt, HotkeyTrigger::Released, "ptt");
+        manager.register_with_trigger(ptt, HotkeyTrigger::Pressed, "ptt");
+        manager.register(cancel, "cancel");
+
+        let listed = manager
+            .bindings()
+            .into_iter()
+            .map(|(hotkey, binding)| (hotkey.to_string(), binding.trigger, binding.enabled))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            listed,
+            vec![
+                ("ctrl+space".to_string(), HotkeyTrigger::Pressed, true),
+                ("ctrl+space".to_string(), HotkeyTrigger::Released, true),
+                ("escape".to_string(), HotkeyTrigger::Pressed, true),
+            ]
+        );
+
+        let pressed = HotkeyEvent {
+            key: HotkeyKey::Space,
+            modifiers: ptt.modifiers,
+            state: HotkeyState::Pressed,
+        };
+        let released = HotkeyEvent {
+            state: HotkeyState::Released,
+            ..pressed
+        };
+        assert!(manager.set_enabled(&ptt, HotkeyTrigger::Pressed, false));
+        assert_eq!(manager.resolve(&pressed), None);
+        assert_eq!(manager.resolve(&released), Some("ptt"));
+        assert!(!manager.bindings()[0].1.enabled);
+
+        assert!(manager.set_enabled(&ptt, HotkeyTrigger::Pressed, true));
+        assert_eq!(manager.resolve(&pressed), Some("ptt"));
+        assert!(!manager.set_enabled(&cancel, HotkeyTrigger::Released, false));
+    }
+
+    #[test]
+    fn hotkey_manager_reports_conflicts_instead_of_replacing() {
+        let mut manager = HotkeyManager::new();
+        let hotkey = Hotkey {
+            key: HotkeyKey::F9,
+            modifiers: HotkeyModifiers::none(),
+        };
+        manager.register(hotkey, "toggle-capture");
+
+        assert_eq!(
+            manager.would_conflict(&hotkey, HotkeyTrigger::Pressed),
+            Some("toggle-capture")
+        );
+        assert_eq!(
+            manager.would_conflict(&hotkey, HotkeyTrigger::Released),
+            None
+        );
+        assert_eq!(
+            manager.try_register_with_trigger(hotkey, HotkeyTrigger::Pressed, "ptt"),
+            Err(ConflictError {
+                existing_action: "toggle-capture".to_string(),
+            })
+        );
+        assert_eq!(
+            manager.try_register_with_trigger(hotkey, HotkeyTrigger::Pressed, "toggle-capture"),
+            Ok(())
+        );
+        assert_eq!(
+            manager.try_register_with_trigger(hotkey, HotkeyTrigger::Released, "ptt"),
+            Ok(())
+        );
+
+        let pressed = HotkeyEvent {
+            key: HotkeyKey::F9,
+            modifiers: HotkeyModifiers::none(),
+            state: HotkeyState::Pressed,
+        };
+        assert_eq!(manager.resolve(&pressed), Some("toggle-capture"));
+
+        let replaced = manager.register(hotkey, "ptt");
+        assert_eq!(
+            replaced.map(|binding| binding.action),
+            Some("toggle-capture".to_string())
+        );
+        assert_eq!(manager.resolve(&pressed), Some("ptt"));
+    }
+
     #[test]
     fn hotkey_manager_supports_multiple_triggers() {
         let mut manager = HotkeyManager::new();
@@ -516,6 +1788,37 @@ mod tests {
         assert_eq!(manager.resolve(&released_event), Some("stop"));
     }
 
+    #[test]
+    fn map_key_covers_numpad_punctuation_and_navigation() {
+        for (key, expected) in [
+            (rdev::Key::Num0, HotkeyKey::Digit0),
+            (rdev::Key::Num9, HotkeyKey::Digit9),
+            (rdev::Key::Kp0, HotkeyKey::Numpad0),
+            (rdev::Key::Kp7, HotkeyKey::Numpad7),
+            (rdev::Key::KpPlus, HotkeyKey::NumpadAdd),
+            (rdev::Key::KpMinus, HotkeyKey::NumpadSubtract),
+            (rdev::Key::KpMultiply, HotkeyKey::NumpadMultiply),
+            (rdev::Key::KpDivide, HotkeyKey::NumpadDivide),
+            (rdev::Key::KpDelete, HotkeyKey::NumpadDecimal),
+            (rdev::Key::KpReturn, HotkeyKey::NumpadEnter),
+            (rdev::Key::Comma, HotkeyKey::Comma),
+            (rdev::Key::Dot, HotkeyKey::Period),
+            (rdev::Key::Slash, HotkeyKey::Slash),
+            (rdev::Key::SemiColon, HotkeyKey::Semicolon),
+            (rdev::Key::BackQuote, HotkeyKey::Grave),
+            (rdev::Key::Insert, HotkeyKey::Insert),
+            (rdev::Key::Delete, HotkeyKey::Delete),
+            (rdev::Key::Home, HotkeyKey::Home),
+            (rdev::Key::End, HotkeyKey::End),
+            (rdev::Key::PageUp, HotkeyKey::PageUp),
+            (rdev::Key::PageDown, HotkeyKey::PageDown),
+            (rdev::Key::Pause, HotkeyKey::Pause),
+        ] {
+            assert_eq!(map_key(key), Some(expected), "{key:?}");
+        }
+        assert_eq!(map_key(rdev::Key::Unknown(0)), None);
+    }
+
     #[test]
     fn hotkey_listener_propagates_listen_error() {
         let manager = Arc::new(Mutex::new(HotkeyManager::new()));
@@ -570,6 +1873,183 @@ mod tests {
         assert_eq!(result[0].state, HotkeyState::Pressed);
     }
 
+    #[test]
+    fn stopped_listener_ignores_further_events() {
+        let manager = Arc::new(Mutex::new(HotkeyManager::new()));
+        let hotkey = Hotkey {
+            key: HotkeyKey::F9,
+            modifiers: HotkeyModifiers::none(),
+        };
+        {
+            let mut manager = manager.lock().expect("manager");
+            manager.register_with_trigger(hotkey, HotkeyTrigger::Pressed, "ptt");
+            manager.register_with_trigger(hotkey, HotkeyTrigger::Released, "ptt");
+        }
+
+        let (event_tx, event_rx) = mpsc::channel::<rdev::EventType>();
+        let (sender, receiver) = mpsc::channel();
+        let handle = spawn_listener(manager, sender, move |mut handler| {
+            for event_type in event_rx {
+                handler(rdev::Event {
+                    time: SystemTime::now(),
+                    name: None,
+                    event_type,
+                });
+            }
+            Ok(())
+        });
+
+        event_tx
+            .send(rdev::EventType::KeyPress(rdev::Key::F9))
+            .expect("send");
+        let first = receiver
+            .recv_timeout(Duration::from_secs(1))
+            .expect("pressed");
+        assert_eq!(first.state, HotkeyState::Pressed);
+
+        handle.stop();
+        assert!(handle.is_stopped());
+        for event_type in [
+            rdev::EventType::KeyRelease(rdev::Key::F9),
+            rdev::EventType::KeyPress(rdev::Key::F9),
+            rdev::EventType::KeyRelease(rdev::Key::F9),
+        ] {
+            event_tx.send(event_type).expect("send");
+        }
+        drop(event_tx);
+        handle.join().expect("listener join");
+
+        assert_eq!(receiver.try_iter().count(), 0);
+    }
+
+    #[test]
+    fn restarted_listener_replaces_the_shared_handler() {
+        let manager = Arc::new(Mutex::new(HotkeyManager::new()));
+        let hotkey = Hotkey {
+            key: HotkeyKey::F9,
+            modifiers: HotkeyModifiers::none(),
+        };
+        manager.lock().expect("manager").register_with_trigger(
+            hotkey,
+            HotkeyTrigger::Pressed,
+            "ptt",
+        );
+        let shared = Arc::new(SharedListener::new());
+        let start = |sender| {
+            let shared = Arc::clone(&shared);
+            spawn_listener(Arc::clone(&manager), sender, move |handler| {
+                shared.replace(Some(handler));
+                Ok(())
+            })
+        };
+        let press = || rdev::Event {
+            time: SystemTime::now(),
+            name: None,
+            event_type: rdev::EventType::KeyPress(rdev::Key::F9),
+        };
+
+        let (first_sender, first_receiver) = mpsc::channel();
+        let first = start(first_sender);
+        first.join().expect("listener join");
+        shared.dispatch(press());
+        assert!(first_receiver.try_recv().is_ok());
+
+        let (second_sender, second_receiver) = mpsc::channel();
+        let second = start(second_sender);
+        second.join().expect("listener join");
+        shared.dispatch(press());
+
+        assert_eq!(
+            first_receiver.recv_timeout(Duration::from_secs(1)),
+            Err(mpsc::RecvTimeoutError::Disconnected)
+        );
+        assert!(second_receiver.try_recv().is_ok());
+    }
+
+    #[test]
+    fn grab_listener_consumes_bound_keys_only() {
+        let manager = Arc::new(Mutex::new(HotkeyManager::new()));
+        manager.lock().expect("manager").register_with_trigger(
+            Hotkey {
+                key: HotkeyKey::F9,
+                modifiers: HotkeyModifiers::none(),
+            },
+            HotkeyTrigger::Pressed,
+            "toggle-capture",
+        );
+        let (sender, receiver) = mpsc::channel();
+        let passed = Arc::new(Mutex::new(Vec::new()));
+        let passed_events = Arc::clone(&passed);
+
+        let handle = spawn_grab_listener(manager, sender, move |mut consume| {
+            for event_type in [
+                rdev::EventType::KeyPress(rdev::Key::KeyA),
+                rdev::EventType::KeyPress(rdev::Key::F9),
+                rdev::EventType::KeyPress(rdev::Key::F9),
+                rdev::EventType::KeyRelease(rdev::Key::F9),
+                rdev::EventType::KeyRelease(rdev::Key::KeyA),
+                rdev::EventType::KeyPress(rdev::Key::F10),
+            ] {
+                let event = rdev::Event {
+                    time: SystemTime::now(),
+                    name: None,
+                    event_type,
+                };
+                if !consume(event) {
+                    passed_events.lock().expect("passed").push(event_type);
+                }
+            }
+            Ok(())
+        });
+        handle.join().expect("listener join");
+
+        assert_eq!(
+            *passed.lock().expect("passed"),
+            vec![
+                rdev::EventType::KeyPress(rdev::Key::KeyA),
+                rdev::EventType::KeyRelease(rdev::Key::KeyA),
+                rdev::EventType::KeyPress(rdev::Key::F10),
+            ]
+        );
+        let action = receiver.try_recv().expect("action");
+        assert_eq!(action.action, "toggle-capture");
+        assert!(receiver.try_recv().is_err());
+    }
+
+    #[test]
+    fn unsupported_grab_falls_back_to_listen() {
+        let listened = AtomicBool::new(false);
+        let result = run_rdev(
+            ListenerMode::Grab,
+            || Err(HotkeyError::GrabUnsupported("no access".to_string())),
+            || {
+                listened.store(true, Ordering::SeqCst);
+                Ok(())
+            },
+        );
+
+        assert!(result.is_ok());
+        assert!(listened.load(Ordering::SeqCst));
+    }
+
+    #[test]
+    fn start_returns_backend_failures() {
+        let listener = GlobalHotkeyListener::new(Arc::new(Mutex::new(HotkeyManager::new())));
+
+        for mode in [ListenerMode::Listen, ListenerMode::Grab] {
+            let result = listener.start_with(mode, |_, _| {
+                Err(HotkeyError::Listener("permission denied".to_string()))
+            });
+            assert!(matches!(
+                result,
+                Err(HotkeyError::Listener(message)) if message == "permission denied"
+            ));
+        }
+        assert!(listener
+            .start_with(ListenerMode::Listen, |_, _| Ok(()))
+            .is_ok());
+    }
+
     #[test]
     fn hotkey_listener_allows_modifier_variants() {
         let manager = Arc::new(Mutex::new(HotkeyManager::new()));
@@ -641,4 +2121,501 @@ mod tests {
         assert_eq!(result[0].action, "plain");
         assert_eq!(result[1].action, "ctrl");
     }
+
+    #[test]
+    fn modifier_key_can_be_ptt_key_and_combo_modifier() {
+        let manager = Arc::new(Mutex::new(HotkeyManager::new()));
+        let ptt = Hotkey {
+            key: HotkeyKey::CtrlRight,
+            modifiers: HotkeyModifiers::none(),
+        };
+        let combo = Hotkey {
+            key: HotkeyKey::K,
+            modifiers: HotkeyModifiers {
+                ctrl: true,
+                ..HotkeyModifiers::none()
+            },
+        };
+        {
+            let mut manager = manager.lock().expect("manager");
+            manager.register_with_trigger(ptt, HotkeyTrigger::Pressed, "ptt");
+            manager.register_with_trigger(ptt, HotkeyTrigger::Released, "ptt");
+            manager.register(combo, "combo");
+        }
+
+        let (sender, receiver) = mpsc::channel();
+        let handle = spawn_listener(manager, sender, |mut handler| {
+            for event_type in [
+                rdev::EventType::KeyPress(rdev::Key::ControlRight),
+                rdev::EventType::KeyPress(rdev::Key::ControlRight),
+                rdev::EventType::KeyPress(rdev::Key::KeyK),
+                rdev::EventType::KeyRelease(rdev::Key::KeyK),
+                rdev::EventType::KeyRelease(rdev::Key::ControlRight),
+                rdev::EventType::KeyPress(rdev::Key::ControlLeft),
+                rdev::EventType::KeyPress(rdev::Key::KeyK),
+                rdev::EventType::KeyRelease(rdev::Key::KeyK),
+                rdev::EventType::KeyRelease(rdev::Key::ControlLeft),
+            ] {
+                handler(rdev::Event {
+                    time: SystemTime::now(),
+                    name: None,
+                    event_type,
+                });
+            }
+            Ok(())
+        });
+
+        handle.join().expect("listener join");
+
+        let result = receiver
+            .try_iter()
+            .map(|event| (event.action, event.state))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            result,
+            vec![
+                ("ptt".to_string(), HotkeyState::Pressed),
+                ("combo".to_string(), HotkeyState::Pressed),
+                ("ptt".to_string(), HotkeyState::Released),
+                ("combo".to_string(), HotkeyState::Pressed),
+            ]
+        );
+    }
+
+    #[test]
+    fn modifiers_without_key_drop_the_keys_own_flag() {
+        let all = HotkeyModifiers {
+            ctrl: true,
+            alt: true,
+            shift: true,
+            meta: true,
+        };
+
+        assert!(!all.without_key(HotkeyKey::CtrlRight).ctrl);
+        assert!(!all.without_key(HotkeyKey::AltLeft).alt);
+        assert!(!all.without_key(HotkeyKey::ShiftRight).shift);
+        assert!(!all.without_key(HotkeyKey::MetaLeft).meta);
+        assert_eq!(all.without_key(HotkeyKey::Space), all);
+    }
+
+    #[test]
+    fn hotkey_strings_roundtrip_for_every_key() {
+        let modifier_sets = [
+            HotkeyModifiers::none(),
+            HotkeyModifiers {
+                ctrl: true,
+                alt: true,
+                ..HotkeyModifiers::none()
+            },
+            HotkeyModifiers {
+                ctrl: true,
+                alt: true,
+                shift: true,
+                meta: true,
+            },
+        ];
+        for key in HotkeyKey::ALL {
+            assert_eq!(key.to_string().parse::<HotkeyKey>(), Ok(key));
+            assert_eq!(key.name().to_uppercase().parse::<HotkeyKey>(), Ok(key));
+            for modifiers in modifier_sets {
+                let hotkey = Hotkey {
+                    key,
+                    modifiers: modifiers.without_key(key),
+                };
+                assert_eq!(hotkey.to_string().parse::<Hotkey>(), Ok(hotkey), "{hotkey}");
+            }
+        }
+    }
+
+    #[test]
+    fn hotkey_parsing_normalizes_modifier_order_and_case() {
+        let hotkey: Hotkey = " Alt + CTRL + Space ".parse().expect("hotkey");
+        assert_eq!(hotkey.to_string(), "ctrl+alt+space");
+        assert_eq!(
+            "cmd+shift+,"
+                .parse::<Hotkey>()
+                .map(|hotkey| hotkey.to_string()),
+            Ok("shift+meta+comma".to_string())
+        );
+        assert_eq!(
+            "ctrl+ctrl_right"
+                .parse::<Hotkey>()
+                .map(|hotkey| hotkey.to_string()),
+            Ok("ctrl_right".to_string())
+        );
+        assert_eq!("Numpad0".parse::<HotkeyKey>(), Ok(HotkeyKey::Numpad0));
+        assert_eq!("esc".parse::<HotkeyKey>(), Ok(HotkeyKey::Escape));
+    }
+
+    #[test]
+    fn hotkey_parsing_reports_descriptive_errors() {
+        assert_eq!("  ".parse::<Hotkey>(), Err(HotkeyParseError::Empty));
+        assert_eq!(
+            "ctrl+hyper".parse::<Hotkey>(),
+            Err(HotkeyParseError::UnknownKey("hyper".to_string()))
+        );
+        assert_eq!(
+            "ctrl+shift".parse::<Hotkey>(),
+            Err(HotkeyParseError::MissingKey("ctrl+shift".to_string()))
+        );
+        assert_eq!(
+            "a+b".parse::<Hotkey>(),
+            Err(HotkeyParseError::MultipleKeys("a+b".to_string()))
+        );
+        assert_eq!(
+            "volume_up"
+                .parse::<HotkeyKey>()
+                .map_err(|err| err.to_string()),
+            Err("unknown hotkey key 'volume_up'".to_string())
+        );
+    }
+
+    #[test]
+    fn capture_session_takes_the_next_key_over_bindings() {
+        let manager = Arc::new(Mutex::new(HotkeyManager::new()));
+        let hotkey = Hotkey {
+            key: HotkeyKey::F9,
+            modifiers: HotkeyModifiers {
+                ctrl: true,
+                ..HotkeyModifiers::none()
+            },
+        };
+        let session = {
+            let mut manager = manager.lock().expect("manager");
+            manager.register_with_trigger(hotkey, HotkeyTrigger::Pressed, "ptt");
+            manager.register_with_trigger(hotkey, HotkeyTrigger::Released, "ptt");
+            manager.begin_capture(Duration::from_secs(1))
+        };
+
+        let (sender, receiver) = mpsc::channel();
+        let handle = spawn_listener(manager, sender, |mut handler| {
+            for event_type in [
+                rdev::EventType::KeyPress(rdev::Key::ControlLeft),
+                rdev::EventType::KeyPress(rdev::Key::F9),
+                rdev::EventType::KeyRelease(rdev::Key::F9),
+                rdev::EventType::KeyPress(rdev::Key::F9),
+                rdev::EventType::KeyRelease(rdev::Key::F9),
+                rdev::EventType::KeyRelease(rdev::Key::ControlLeft),
+            ] {
+                handler(rdev::Event {
+                    time: SystemTime::now(),
+                    name: None,
+                    event_type,
+                });
+            }
+            Ok(())
+        });
+        handle.join().expect("listener join");
+
+        assert_eq!(session.wait().expect("captured"), hotkey);
+        let result = receiver
+            .try_iter()
+            .map(|event| event.state)
+            .collect::<Vec<_>>();
+        assert_eq!(result, vec![HotkeyState::Pressed, HotkeyState::Released]);
+    }
+
+    #[test]
+    fn mouse_buttons_parse_by_name_and_code() {
+        let side = HotkeyKey::Mouse(MouseButton::Other(4));
+        assert_eq!("mouse4".parse::<HotkeyKey>(), Ok(side));
+        assert_eq!("Mouse_4".parse::<HotkeyKey>(), Ok(side));
+        assert_eq!(
+            "mouse_middle".parse::<HotkeyKey>(),
+            Ok(HotkeyKey::Mouse(MouseButton::Middle))
+        );
+        assert_eq!(side.to_string(), "mouse4");
+        assert_eq!(
+            "ctrl+mouse9"
+                .parse::<Hotkey>()
+                .map(|hotkey| hotkey.to_string()),
+            Ok("ctrl+mouse9".to_string())
+        );
+        assert_eq!(
+            "mouse256".parse::<HotkeyKey>(),
+            Err(HotkeyParseError::UnknownKey("mouse256".to_string()))
+        );
+    }
+
+    #[test]
+    fn hotkey_listener_fires_registered_mouse_buttons_only() {
+        let side = Hotkey {
+            key: HotkeyKey::Mouse(MouseButton::Other(8)),
+            modifiers: HotkeyModifiers::none(),
+        };
+        let mut manager = HotkeyManager::new();
+        manager.register_with_trigger(side, HotkeyTrigger::Pressed, "ptt");
+        manager.register_with_trigger(side, HotkeyTrigger::Released, "ptt");
+
+        let result = run_timed_events(
+            manager,
+            vec![
+                (0, rdev::EventType::ButtonPress(rdev::Button::Left)),
+                (0, rdev::EventType::ButtonRelease(rdev::Button::Left)),
+                (0, rdev::EventType::ButtonPress(rdev::Button::Unknown(8))),
+                (0, rdev::EventType::ButtonPress(rdev::Button::Unknown(8))),
+                (0, rdev::EventType::ButtonRelease(rdev::Button::Unknown(8))),
+                (0, rdev::EventType::ButtonPress(rdev::Button::Right)),
+                (0, rdev::EventType::ButtonRelease(rdev::Button::Right)),
+            ],
+        );
+
+        assert_eq!(
+            result,
+            vec![
+                ("ptt".to_string(), HotkeyState::Pressed),
+                ("ptt".to_string(), HotkeyState::Released),
+            ]
+        );
+    }
+
+    #[test]
+    fn capture_session_skips_plain_clicks() {
+        let mut manager = HotkeyManager::new();
+        let session = manager.begin_capture(Duration::from_secs(1));
+
+        run_timed_events(
+            manager,
+            vec![
+                (0, rdev::EventType::ButtonPress(rdev::Button::Left)),
+                (0, rdev::EventType::ButtonRelease(rdev::Button::Left)),
+                (0, rdev::EventType::ButtonPress(rdev::Button::Middle)),
+                (0, rdev::EventType::ButtonRelease(rdev::Button::Middle)),
+            ],
+        );
+
+        assert_eq!(
+            session.wait().expect("captured"),
+            Hotkey {
+                key: HotkeyKey::Mouse(MouseButton::Middle),
+                modifiers: HotkeyModifiers::none(),
+            }
+        );
+    }
+
+    #[test]
+    fn capture_session_times_out_and_stops_suppressing() {
+        let mut manager = HotkeyManager::new();
+        let session = manager.begin_capture(Duration::from_millis(10));
+        assert_eq!(session.timeout(), Duration::from_millis(10));
+        assert!(matches!(session.wait(), Err(HotkeyError::CaptureTimedOut)));
+
+        let hotkey = Hotkey {
+            key: HotkeyKey::A,
+            modifiers: HotkeyModifiers::none(),
+        };
+        assert!(!manager.deliver_capture(hotkey));
+
+        let replaced = manager.begin_capture(Duration::from_secs(1));
+        let _ = manager.begin_capture(Duration::from_secs(1));
+        assert!(matches!(
+            replaced.wait(),
+            Err(HotkeyError::CaptureCancelled)
+        ));
+    }
+
+    fn run_timed_events(
+        manager: HotkeyManager,
+        steps: Vec<(u64, rdev::EventType)>,
+    ) -> Vec<(String, HotkeyState)> {
+        let now = Arc::new(Mutex::new(Instant::now()));
+        let clock: Clock = {
+            let now = Arc::clone(&now);
+            Arc::new(move || *now.lock().expect("clock"))
+        };
+        let (sender, receiver) = mpsc::channel();
+        let handle = spawn_listener_with_clock(
+            Arc::new(Mutex::new(manager)),
+            sender,
+            clock,
+            ListenerState::handle,
+            move |mut handler| {
+                for (advance_ms, event_type) in steps {
+                    *now.lock().expect("clock") += Duration::from_millis(advance_ms);
+                    handler(rdev::Event {
+                        time: SystemTime::now(),
+                        name: None,
+                        event_type,
+                    });
+                }
+                Ok(())
+            },
+        );
+        handle.join().expect("listener join");
+        receiver
+            .try_iter()
+            .map(|event| (event.action, event.state))
+            .collect()
+    }
+
+    fn plain(key: HotkeyKey) -> Hotkey {
+        Hotkey {
+            key,
+            modifiers: HotkeyModifiers::none(),
+        }
+    }
+
+    fn sequence_manager() -> HotkeyManager {
+        let mut manager = HotkeyManager::new().with_sequence_timeout(Duration::from_millis(500));
+        manager.register_sequence(&[plain(HotkeyKey::F9), plain(HotkeyKey::T)], "translate");
+        manager.register(plain(HotkeyKey::T), "letter-t");
+        manager.register(plain(HotkeyKey::K), "letter-k");
+        manager
+    }
+
+    #[test]
+    fn completed_sequence_fires_its_action_only() {
+        let result = run_timed_events(
+            sequence_manager(),
+            vec![
+                (0, rdev::EventType::KeyPress(rdev::Key::F9)),
+                (50, rdev::EventType::KeyRelease(rdev::Key::F9)),
+                (200, rdev::EventType::KeyPress(rdev::Key::KeyT)),
+                (50, rdev::EventType::KeyRelease(rdev::Key::KeyT)),
+            ],
+        );
+        assert_eq!(
+            result,
+            vec![("translate".to_string(), HotkeyState::Pressed)]
+        );
+    }
+
+    #[test]
+    fn expired_or_mismatched_sequence_falls_back_to_single_keys() {
+        let result = run_timed_events(
+            sequence_manager(),
+            vec![
+                (0, rdev::EventType::KeyPress(rdev::Key::F9)),
+                (50, rdev::EventType::KeyRelease(rdev::Key::F9)),
+                (600, rdev::EventType::KeyPress(rdev::Key::KeyT)),
+                (50, rdev::EventType::KeyRelease(rdev::Key::KeyT)),
+                (0, rdev::EventType::KeyPress(rdev::Key::F9)),
+                (50, rdev::EventType::KeyRelease(rdev::Key::F9)),
+                (50, rdev::EventType::KeyPress(rdev::Key::KeyK)),
+                (50, rdev::EventType::KeyRelease(rdev::Key::KeyK)),
+                (50, rdev::EventType::KeyPress(rdev::Key::KeyT)),
+                (50, rdev::EventType::KeyRelease(rdev::Key::KeyT)),
+            ],
+        );
+        assert_eq!(
+            result,
+            vec![
+                ("letter-t".to_string(), HotkeyState::Pressed),
+                ("letter-k".to_string(), HotkeyState::Pressed),
+                ("letter-t".to_string(), HotkeyState::Pressed),
+            ]
+        );
+    }
+
+    #[test]
+    fn sequence_prefix_keeps_its_standalone_binding() {
+        let mut manager = sequence_manager();
+        manager.register_with_trigger(plain(HotkeyKey::F9), HotkeyTrigger::Pressed, "ptt");
+        manager.register_with_trigger(plain(HotkeyKey::F9), HotkeyTrigger::Released, "ptt");
+
+        let result = run_timed_events(
+            manager,
+            vec![
+                (0, rdev::EventType::KeyPress(rdev::Key::F9)),
+                (50, rdev::EventType::KeyRelease(rdev::Key::F9)),
+                (100, rdev::EventType::KeyPress(rdev::Key::KeyT)),
+                (50, rdev::EventType::KeyRelease(rdev::Key::KeyT)),
+            ],
+        );
+        assert_eq!(
+            result,
+            vec![
+                ("ptt".to_string(), HotkeyState::Pressed),
+                ("ptt".to_string(), HotkeyState::Released),
+                ("translate".to_string(), HotkeyState::Pressed),
+            ]
+        );
+    }
+
+    fn tap_and_hold_manager() -> HotkeyManager {
+        let hotkey = Hotkey {
+            key: HotkeyKey::F9,
+            modifiers: HotkeyModifiers::none(),
+        };
+        let mut manager = HotkeyManager::new();
+        manager.register_with_trigger(hotkey, HotkeyTrigger::Released, "toggle");
+        manager.register_with_trigger(hotkey, HotkeyTrigger::Held { min_ms: 300 }, "hold");
+        manager.register_with_trigger(
+            hotkey,
+            HotkeyTrigger::DoubleTap { window_ms: 250 },
+            "double",
+        );
+        manager
+    }
+
+    #[test]
+    fn short_tap_fires_release_binding_only() {
+        let events = run_timed_events(
+            tap_and_hold_manager(),
+            vec![
+                (0, rdev::EventType::KeyPress(rdev::Key::F9)),
+                (120, rdev::EventType::KeyRelease(rdev::Key::F9)),
+            ],
+        );
+
+        assert_eq!(events, vec![("toggle".to_string(), HotkeyState::Released)]);
+    }
+
+    #[test]
+    fn hold_past_threshold_fires_held_binding_instead_of_release() {
+        let events = run_timed_events(
+            tap_and_hold_manager(),
+            vec![
+                (0, rdev::EventType::KeyPress(rdev::Key::F9)),
+                (350, rdev::EventType::KeyPress(rdev::Key::F9)),
+                (400, rdev::EventType::KeyRelease(rdev::Key::F9)),
+            ],
+        );
+
+        assert_eq!(
+            events,
+            vec![
+                ("hold".to_string(), HotkeyState::Pressed),
+                ("hold".to_string(), HotkeyState::Released),
+            ]
+        );
+    }
+
+    #[test]
+    fn double_tap_fires_only_inside_window() {
+        let inside = run_timed_events(
+            tap_and_hold_manager(),
+            vec![
+                (0, rdev::EventType::KeyPress(rdev::Key::F9)),
+                (50, rdev::EventType::KeyRelease(rdev::Key::F9)),
+                (100, rdev::EventType::KeyPress(rdev::Key::F9)),
+                (50, rdev::EventType::KeyRelease(rdev::Key::F9)),
+            ],
+        );
+        assert_eq!(
+            inside,
+            vec![
+                ("toggle".to_string(), HotkeyState::Released),
+                ("double".to_string(), HotkeyState::Pressed),
+                ("toggle".to_string(), HotkeyState::Released),
+            ]
+        );
+
+        let outside = run_timed_events(
+            tap_and_hold_manager(),
+            vec![
+                (0, rdev::EventType::KeyPress(rdev::Key::F9)),
+                (50, rdev::EventType::KeyRelease(rdev::Key::F9)),
+                (400, rdev::EventType::KeyPress(rdev::Key::F9)),
+                (50, rdev::EventType::KeyRelease(rdev::Key::F9)),
+            ],
+        );
+        assert_eq!(
+            outside,
+            vec![
+                ("toggle".to_string(), HotkeyState::Released),
+                ("toggle".to_string(), HotkeyState::Released),
+            ]
+        );
+    }
 }
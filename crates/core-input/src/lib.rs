@@ -1,14 +1,49 @@
 mod audio;
+mod dsp;
+#[cfg(all(target_os = "linux", feature = "evdev"))]
+mod evdev;
+mod feedback;
+mod gain;
 mod hotkeys;
 mod meter;
+mod mix;
+mod portal;
 mod ptt;
+mod vad;
 
 pub use audio::CpalAudioBackend;
-pub use audio::{AudioBackend, AudioCaptureService, AudioDevice, AudioError, AudioStream};
+pub use audio::{
+    is_monitor_device, AudioBackend, AudioCaptureService, AudioDevice, AudioError, AudioStream,
+    DeviceChangeEvent, DeviceWatcher, DeviceWatcherHandle, SampleFormat, SelectedDevice,
+    StreamFormat, StreamRequest,
+};
+pub use audio_resample::{
+    downmix_to_mono, resample_linear, resample_to_16k_mono, Resampler, SPEECH_SAMPLE_RATE,
+};
+pub use dsp::{HighPassFilter, NoiseGate, ProcessorChain, SampleProcessor, HIGH_PASS_CUTOFF_HZ};
+#[cfg(all(target_os = "linux", feature = "evdev"))]
+pub use evdev::{EvdevHotkeyListener, EVDEV_INPUT_DIR, EVDEV_RESCAN_INTERVAL};
+pub use feedback::{sine_blip, CpalTonePlayer, FeedbackTone, TonePlayer};
+pub use gain::{GainMode, GainStage, AGC_MAX_GAIN_DB, AGC_MIN_GAIN_DB, AGC_TARGET_DBFS};
 pub use hotkeys::HotkeyListenerHandle;
 pub use hotkeys::{
-    GlobalHotkeyListener, Hotkey, HotkeyActionEvent, HotkeyBinding, HotkeyError, HotkeyEvent,
-    HotkeyKey, HotkeyManager, HotkeyModifiers, HotkeyState, HotkeyTrigger,
+    ConflictError, GlobalHotkeyListener, Hotkey, HotkeyActionEvent, HotkeyBinding,
+    HotkeyCaptureSession, HotkeyError, HotkeyEvent, HotkeyKey, HotkeyManager, HotkeyModifiers,
+    HotkeyParseError, HotkeyState, HotkeyTrigger, ListenerMode, MouseButton,
+    DEFAULT_SEQUENCE_TIMEOUT,
+};
+pub use meter::{
+    LevelHistory, LevelMeter, LevelReading, MeterBallistics, LEVEL_HISTORY_CAPACITY,
+    LEVEL_HISTORY_INTERVAL,
+};
+pub use mix::{
+    MixInput, MultiDeviceCapture, MultiDeviceStream, MIX_DEVICE_PREFIX, MIX_JITTER_BUFFER,
+};
+#[cfg(all(target_os = "linux", feature = "wayland-portal"))]
+pub use portal::DbusShortcutPortal;
+pub use portal::{PortalHotkeyListener, PortalShortcut, PortalSignal, ShortcutPortal};
+pub use ptt::{
+    CaptureAutoStopped, CaptureChunk, CaptureLimits, ChunkConfig, OverflowPolicy, PttCaptureError,
+    PttCaptureService, PTT_STREAM_REQUEST,
 };
-pub use meter::{LevelMeter, LevelReading};
-pub use ptt::{PttCaptureError, PttCaptureService};
+pub use vad::{VadConfig, VadDetector, VAD_MIN_CAPTURE};
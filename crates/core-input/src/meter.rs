@@ -1,8 +1,16 @@
+use std::collections::VecDeque;
+use std::time::Duration;
+
+pub const LEVEL_HISTORY_INTERVAL: Duration = Duration::from_millis(50);
+pub const LEVEL_HISTORY_CAPACITY: usize = 600;
+
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub struct LevelReading {
     pub rms: f32,
     pub peak: f32,
     pub clipped: bool,
+    pub smoothed_rms: f32,
+    pub peak_hold: f32,
 }
 
 impl LevelReading {
@@ -11,6 +19,8 @@ impl LevelReading {
             rms: 0.0,
             peak: 0.0,
             clipped: false,
+            smoothed_rms: 0.0,
+            peak_hold: 0.0,
         }
     }
 
@@ -21,20 +31,69 @@ impl LevelReading {
     pub fn peak_dbfs(&self) -> f32 {
         to_dbfs(self.peak)
     }
+
+    pub fn smoothed_rms_dbfs(&self) -> f32 {
+        to_dbfs(self.smoothed_rms)
+    }
+
+    pub fn peak_hold_dbfs(&self) -> f32 {
+        to_dbfs(self.peak_hold)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MeterBallistics {
+    pub attack: Duration,
+    pub decay: Duration,
+    pub hold: Duration,
+}
+
+impl Default for MeterBallistics {
+    fn default() -> Self {
+        Self {
+            attack: Duration::from_millis(10),
+            decay: Duration::from_millis(300),
+            hold: Duration::from_secs(1),
+        }
+    }
 }
 
 #[derive(Debug, Clone)]
 pub struct LevelMeter {
     reading: LevelReading,
+    ballistics: MeterBallistics,
+    samples_per_second: f64,
+    since_peak: Duration,
 }
 
 impl LevelMeter {
     pub fn new() -> Self {
         Self {
             reading: LevelReading::silence(),
+            ballistics: MeterBallistics::default(),
+            samples_per_second: 16_000.0,
+            since_peak: Duration::ZERO,
         }
     }
 
+    pub fn with_ballistics(mut self, ballistics: MeterBallistics) -> Self {
+        self.ballistics = ballistics;
+        self
+    }
+
+    pub fn with_sample_rate(mut self, sample_rate: u32, channels: u16) -> Self {
+        self.set_sample_rate(sample_rate, channels);
+        self
+    }
+
+    pub fn set_sample_rate(&mut self, sample_rate: u32, channels: u16) {
+        self.samples_per_second = sample_rate.max(1) as f64 * channels.max(1) as f64;
+    }
+
+    pub fn ballistics(&self) -> MeterBallistics {
+        self.ballistics
+    }
+
     pub fn update(&mut self, samples: &[f32]) {
         if samples.is_empty() {
             return;
@@ -71,7 +130,36 @@ impl LevelMeter {
             return;
         }
 
-        self.reading = LevelReading { rms, peak, clipped };
+        let elapsed = Duration::from_secs_f64(samples.len() as f64 / self.samples_per_second);
+        let previous = self.reading;
+        let time_constant = if rms > previous.smoothed_rms {
+            self.ballistics.attack
+        } else {
+            self.ballistics.decay
+        };
+        let smoothed_rms = previous.smoothed_rms
+            + (rms - previous.smoothed_rms) * smoothing(elapsed, time_constant);
+
+        let peak_hold = if peak >= previous.peak_hold {
+            self.since_peak = Duration::ZERO;
+            peak
+        } else {
+            let held_before = self.since_peak;
+            self.since_peak += elapsed;
+            let decaying = self
+                .since_peak
+                .saturating_sub(self.ballistics.hold.max(held_before));
+            let retained = 1.0 - smoothing(decaying, self.ballistics.decay);
+            (previous.peak_hold * retained).max(peak)
+        };
+
+        self.reading = LevelReading {
+            rms,
+            peak,
+            clipped,
+            smoothed_rms,
+            peak_hold,
+        };
     }
 
     pub fn reading(&self) -> LevelReading {
@@ -80,6 +168,7 @@ impl LevelMeter {
 
     pub fn reset(&mut self) {
         self.reading = LevelReading::silence();
+        self.since_peak = Duration::ZERO;
     }
 }
 
@@ -89,6 +178,116 @@ impl Default for LevelMeter {
     }
 }
 
+#[derive(Debug, Clone)]
+pub struct LevelHistory {
+    interval: Duration,
+    interval_samples: usize,
+    capacity: usize,
+    entries: VecDeque<LevelReading>,
+    bucket_len: usize,
+    bucket_sum: f32,
+    bucket_peak: f32,
+    bucket_clipped: bool,
+}
+
+impl LevelHistory {
+    pub fn new(interval: Duration, capacity: usize, sample_rate: u32, channels: u16) -> Self {
+        let mut history = Self {
+            interval,
+            interval_samples: 1,
+            capacity: capacity.max(1),
+            entries: VecDeque::new(),
+            bucket_len: 0,
+            bucket_sum: 0.0,
+            bucket_peak: 0.0,
+            bucket_clipped: false,
+        };
+        history.set_sample_rate(sample_rate, channels);
+        history
+    }
+
+    pub fn set_sample_rate(&mut self, sample_rate: u32, channels: u16) {
+        let samples_per_second = sample_rate.max(1) as f64 * channels.max(1) as f64;
+        self.interval_samples =
+            ((self.interval.as_secs_f64() * samples_per_second).round() as usize).max(1);
+    }
+
+    pub fn interval_samples(&self) -> usize {
+        self.interval_samples
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    pub fn push(&mut self, samples: &[f32]) {
+        for &sample in samples {
+            if sample.is_finite() {
+                let magnitude = sample.abs();
+                self.bucket_peak = self.bucket_peak.max(magnitude);
+                self.bucket_clipped |= magnitude >= 1.0;
+                self.bucket_sum += sample * sample;
+            }
+            self.bucket_len += 1;
+            if self.bucket_len >= self.interval_samples {
+                self.close_bucket();
+            }
+        }
+    }
+
+    pub fn snapshot(&self) -> Vec<LevelReading> {
+        self.entries.iter().copied().collect()
+    }
+
+    pub fn clear(&mut self) {
+        self.entries.clear();
+        self.reset_bucket();
+    }
+
+    fn close_bucket(&mut self) {
+        let rms = (self.bucket_sum / self.bucket_len as f32).sqrt();
+        self.entries.push_back(LevelReading {
+            rms,
+            peak: self.bucket_peak,
+            clipped: self.bucket_clipped,
+            smoothed_rms: rms,
+            peak_hold: self.bucket_peak,
+        });
+        if self.entries.len() > self.capacity {
+            self.entries.pop_front();
+        }
+        self.reset_bucket();
+    }
+
+    fn reset_bucket(&mut self) {
+        self.bucket_len = 0;
+        self.bucket_sum = 0.0;
+        self.bucket_peak = 0.0;
+        self.bucket_clipped = false;
+    }
+}
+
+impl Default for LevelHistory {
+    fn default() -> Self {
+        Self::new(LEVEL_HISTORY_INTERVAL, LEVEL_HISTORY_CAPACITY, 16_000, 1)
+    }
+}
+
+fn smoothing(elapsed: Duration, time_constant: Duration) -> f32 {
+    if time_constant.is_zero() {
+        return 1.0;
+    }
+    1.0 - (-elapsed.as_secs_f64() / time_constant.as_secs_f64()).exp() as f32
+}
+
 fn to_dbfs(value: f32) -> f32 {
     if !value.is_finite() || value <= 0.0 {
         f32::NEG_INFINITY
@@ -99,8 +298,13 @@ fn to_dbfs(value: f32) -> f32 {
 
 #[cfg(test)]
 mod tests {
-    use super::{LevelMeter, LevelReading};
+    use super::{LevelHistory, LevelMeter, LevelReading, MeterBallistics};
     use approx::assert_relative_eq;
+    use std::time::Duration;
+
+    fn block(value: f32, ms: usize) -> Vec<f32> {
+        vec![value; 16 * ms]
+    }
 
     #[test]
     fn meter_reports_silence_for_empty_samples() {
@@ -151,6 +355,8 @@ mod tests {
             rms: f32::NAN,
             peak: f32::NAN,
             clipped: false,
+            smoothed_rms: f32::NAN,
+            peak_hold: f32::NAN,
         };
         let rms_dbfs = reading.rms_dbfs();
         let peak_dbfs = reading.peak_dbfs();
@@ -159,4 +365,115 @@ mod tests {
         assert!(peak_dbfs.is_infinite());
         assert!(peak_dbfs.is_sign_negative());
     }
+
+    #[test]
+    fn smoothed_rms_attacks_fast_and_decays_slowly() {
+        let mut meter = LevelMeter::new();
+        meter.update(&block(0.5, 50));
+        let attacked = meter.reading().smoothed_rms;
+        assert!(attacked > 0.49, "attack {attacked}");
+
+        meter.update(&block(0.0, 100));
+        let decayed = meter.reading();
+        assert_eq!(decayed.rms, 0.0);
+        assert_relative_eq!(
+            decayed.smoothed_rms,
+            attacked * (-100.0_f32 / 300.0).exp(),
+            epsilon = 1e-4
+        );
+    }
+
+    #[test]
+    fn smoothing_follows_samples_processed_not_update_count() {
+        let mut chunked = LevelMeter::new();
+        chunked.update(&block(0.5, 50));
+        for _ in 0..10 {
+            chunked.update(&block(0.0, 10));
+        }
+
+        let mut single = LevelMeter::new();
+        single.update(&block(0.5, 50));
+        single.update(&block(0.0, 100));
+
+        assert_relative_eq!(
+            chunked.reading().smoothed_rms,
+            single.reading().smoothed_rms,
+            epsilon = 1e-4
+        );
+    }
+
+    #[test]
+    fn peak_hold_waits_before_decaying() {
+        let ballistics = MeterBallistics {
+            hold: Duration::from_millis(200),
+            ..MeterBallistics::default()
+        };
+        let mut meter = LevelMeter::new().with_ballistics(ballistics);
+        meter.update(&[0.8]);
+        meter.update(&block(0.1, 150));
+        assert_eq!(meter.reading().peak_hold, 0.8);
+
+        meter.update(&block(0.1, 350));
+        let held = meter.reading().peak_hold;
+        assert_relative_eq!(held, 0.8 * (-300.0_f32 / 300.0).exp(), epsilon = 1e-3);
+
+        meter.update(&block(0.1, 5_000));
+        assert_relative_eq!(meter.reading().peak_hold, 0.1, epsilon = 1e-6);
+
+        meter.update(&[0.9]);
+        assert_eq!(meter.reading().peak_hold, 0.9);
+    }
+
+    #[test]
+    fn sample_rate_scales_elapsed_time() {
+        let ballistics = MeterBallistics {
+            hold: Duration::from_millis(100),
+            ..MeterBallistics::default()
+        };
+        let mut meter = LevelMeter::new()
+            .with_ballistics(ballistics)
+            .with_sample_rate(48_000, 2);
+        meter.update(&[0.8]);
+        meter.update(&vec![0.0; 96 * 90]);
+        assert_eq!(meter.reading().peak_hold, 0.8);
+
+        meter.reset();
+        assert_eq!(meter.reading(), LevelReading::silence());
+    }
+
+    #[test]
+    fn history_buckets_by_sample_count() {
+        let mut history = LevelHistory::default();
+        assert_eq!(history.interval_samples(), 800);
+
+        history.push(&block(0.5, 30));
+        assert!(history.is_empty());
+        history.push(&block(0.5, 20));
+        history.push(&block(1.0, 60));
+
+        let snapshot = history.snapshot();
+        assert_eq!(snapshot.len(), 2);
+        assert_relative_eq!(snapshot[0].rms, 0.5, epsilon = 1e-6);
+        assert!(!snapshot[0].clipped);
+        assert_relative_eq!(snapshot[1].peak, 1.0, epsilon = 1e-6);
+        assert!(snapshot[1].clipped);
+
+        let stereo = LevelHistory::new(Duration::from_millis(50), 4, 48_000, 2);
+        assert_eq!(stereo.interval_samples(), 4_800);
+    }
+
+    #[test]
+    fn history_evicts_oldest_beyond_capacity() {
+        let mut history = LevelHistory::new(Duration::from_millis(10), 3, 16_000, 1);
+        for level in [0.1, 0.2, 0.3, 0.4, 0.5] {
+            history.push(&block(level, 10));
+        }
+
+        let peaks: Vec<f32> = history.snapshot().iter().map(|r| r.peak).collect();
+        assert_eq!(peaks, vec![0.3, 0.4, 0.5]);
+        assert_eq!(history.len(), history.capacity());
+
+        history.clear();
+        assert!(history.snapshot().is_empty());
+    }
 }
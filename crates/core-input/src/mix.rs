@@ -0,0 +1,769 @@
+use crate::audio::{
+    resolve_device_index, AudioBackend, AudioDevice, AudioError, AudioStream, SampleFormat,
+    StreamFormat, StreamRequest,
+};
+use audio_resample::{downmix_to_mono, SPEECH_SAMPLE_RATE};
+use log::{info, warn};
+use std::collections::VecDeque;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+pub const MIX_DEVICE_PREFIX: &str = "mix:";
+pub const MIX_JITTER_BUFFER: Duration = Duration::from_millis(100);
+
+type SampleCallback = Box<dyn FnMut(&[f32]) + Send>;
+type ErrorCallback = Box<dyn FnMut(AudioError) + Send>;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct MixInput {
+    pub device_id: String,
+    pub gain_db: f32,
+}
+
+impl MixInput {
+    pub fn new(device_id: impl Into<String>) -> Self {
+        Self {
+            device_id: device_id.into(),
+            gain_db: 0.0,
+        }
+    }
+
+    pub fn with_gain_db(mut self, gain_db: f32) -> Self {
+        self.gain_db = gain_db;
+        self
+    }
+}
+
+struct StreamResampler {
+    step: f64,
+    position: f64,
+    previous: Option<f32>,
+}
+
+impl StreamResampler {
+    fn new(src_rate: u32, dst_rate: u32) -> Self {
+        Self {
+            step: src_rate.max(1) as f64 / dst_rate.max(1) as f64,
+            position: 0.0,
+            previous: None,
+        }
+    }
+
+    fn process(&mut self, input: &[f32]) -> Vec<f32> {
+        if self.step == 1.0 || input.is_empty() {
+            return input.to_vec();
+        }
+        let mut source = Vec::with_capacity(input.len() + 1);
+        source.extend(self.previous);
+        source.extend_from_slice(input);
+
+        let mut output = Vec::new();
+        while self.position + 1.0 < source.len() as f64 {
+            let index = self.position.floor() as usize;
+            let frac = (self.position - index as f64) as f32;
+            output.push(source[index] + (source[index + 1] - source[index]) * frac);
+            self.position += self.step;
+        }
+        self.position -= (source.len() - 1) as f64;
+        self.previous = source.last().copied();
+        output
+    }
+}
+
+struct MixChannel {
+    channels: u16,
+    gain: f32,
+    resampler: StreamResampler,
+    queue: VecDeque<f32>,
+    active: bool,
+}
+
+struct Mixer {
+    output_rate: u32,
+    jitter_limit: usize,
+    inputs: Vec<MixChannel>,
+    on_samples: SampleCallback,
+    on_error: ErrorCallback,
+}
+
+impl Mixer {
+    fn new(output_rate: u32, on_samples: SampleCallback, on_error: ErrorCallback) -> Self {
+        Self {
+            output_rate,
+            jitter_limit: (MIX_JITTER_BUFFER.as_secs_f64() * output_rate as f64).ceil() as usize,
+            inputs: Vec::new(),
+            on_samples,
+            on_error,
+        }
+    }
+
+    fn add_input(&mut self, format: StreamFormat, gain_db: f32) -> usize {
+        let gain = if gain_db.is_finite() {
+            10_f32.powf(gain_db / 20.0)
+        } else {
+            1.0
+        };
+        self.inputs.push(MixChannel {
+            channels: format.channels,
+            gain,
+            resampler: StreamResampler::new(format.sample_rate, self.output_rate),
+            queue: VecDeque::new(),
+            active: true,
+        });
+        self.inputs.len() - 1
+    }
+
+    fn configure(&mut self, index: usize, format: StreamFormat) {
+        if let Some(channel) = self.inputs.get_mut(index) {
+            channel.channels = format.channels;
+            channel.resampler = StreamResampler::new(format.sample_rate, self.output_rate);
+        }
+    }
+
+    fn push(&mut self, index: usize, samples: &[f32]) {
+        let Some(channel) = self.inputs.get_mut(index).filter(|channel| channel.active) else {
+            return;
+        };
+        let mono = downmix_to_mono(samples.to_vec(), channel.channels);
+        let gain = channel.gain;
+        let resampled = channel.resampler.process(&mono);
+        channel
+            .queue
+            .extend(resampled.iter().map(|sample| sample * gain));
+        self.drain();
+    }
+
+    fn drain(&mut self) {
+        let lengths: Vec<usize> = self
+            .inputs
+            .iter()
+            .filter(|channel| channel.active)
+            .map(|channel| channel.queue.len())
+            .collect();
+        let (Some(ready), Some(longest)) = (lengths.iter().min(), lengths.iter().max()) else {
+            return;
+        };
+        let count = (*ready).max(longest.saturating_sub(self.jitter_limit));
+        if count == 0 {
+            return;
+        }
+
+        let mut mixed = vec![0.0_f32; count];
+        for channel in self.inputs.iter_mut().filter(|channel| channel.active) {
+            for slot in mixed.iter_mut() {
+                *slot += channel.queue.pop_front().unwrap_or(0.0);
+            }
+        }
+        let active = lengths.len() as f32;
+        for slot in mixed.iter_mut() {
+            *slot /= active;
+        }
+        (self.on_samples)(&mixed);
+    }
+
+    fn drop_input(&mut self, index: usize) {
+        if let Some(channel) = self.inputs.get_mut(index) {
+            channel.active = false;
+            channel.queue.clear();
+        }
+    }
+
+    fn fail(&mut self, index: usize, err: AudioError) {
+        if self.inputs.get(index).is_none_or(|channel| !channel.active) {
+            return;
+        }
+        self.drop_input(index);
+        if self.inputs.iter().any(|channel| channel.active) {
+            warn!("mix input {index} dropped out: {err}");
+            self.drain();
+        } else {
+            (self.on_error)(err);
+        }
+    }
+}
+
+pub struct MultiDeviceStream<S: AudioStream> {
+    streams: Vec<(usize, S)>,
+    mixer: Option<Arc<Mutex<Mixer>>>,
+    format: StreamFormat,
+}
+
+impl<S: AudioStream> MultiDeviceStream<S> {
+    fn single(stream: S) -> Self {
+        let format = stream.format();
+        Self {
+            streams: vec![(0, stream)],
+            mixer: None,
+            format,
+        }
+    }
+
+    pub fn is_mixed(&self) -> bool {
+        self.mixer.is_some()
+    }
+
+    pub fn input_count(&self) -> usize {
+        self.streams.len()
+    }
+}
+
+impl<S: AudioStream> AudioStream for MultiDeviceStream<S> {
+    fn start(&self) -> Result<(), AudioError> {
+        let Some(mixer) = &self.mixer else {
+            return self
+                .streams
+                .iter()
+                .try_for_each(|(_, stream)| stream.start());
+        };
+        let mut last_error = None;
+        for (index, stream) in &self.streams {
+            if let Err(err) = stream.start() {
+                warn!("mix input {index} failed to start: {err}");
+                if let Ok(mut mixer) = mixer.lock() {
+                    mixer.drop_input(*index);
+                }
+                last_error = Some(err);
+            }
+        }
+        let any_active = mixer
+            .lock()
+            .map(|mixer| mixer.inputs.iter().any(|channel| channel.active))
+            .unwrap_or(false);
+        match last_error {
+            Some(err) if !any_active => Err(err),
+            _ => Ok(()),
+        }
+    }
+
+    fn stop(&self) -> Result<(), AudioError> {
+        let mut first_error = None;
+        for (_, stream) in &self.streams {
+            if let Err(err) = stream.stop() {
+                first_error.get_or_insert(err);
+            }
+        }
+        first_error.map_or(Ok(()), Err)
+    }
+
+    fn format(&self) -> StreamFormat {
+        self.format
+    }
+}
+
+pub struct MultiDeviceCapture<B: AudioBackend> {
+    inner: B,
+    inputs: Mutex<Vec<MixInput>>,
+}
+
+impl<B: AudioBackend> MultiDeviceCapture<B> {
+    pub fn new(inner: B) -> Self {
+        Self {
+            inner,
+            inputs: Mutex::new(Vec::new()),
+        }
+    }
+
+    pub fn with_inputs(self, inputs: Vec<MixInput>) -> Self {
+        self.set_inputs(inputs);
+        self
+    }
+
+    pub fn set_inputs(&self, inputs: Vec<MixInput>) {
+        if let Ok(mut current) = self.inputs.lock() {
+            *current = inputs;
+        }
+    }
+
+    pub fn inputs(&self) -> Vec<MixInput> {
+        self.inputs
+            .lock()
+            .map(|inputs| inputs.clone())
+            .unwrap_or_default()
+    }
+
+    pub fn inner(&self) -> &B {
+        &self.inner
+    }
+
+    pub fn mix_device(&self) -> Option<AudioDevice> {
+        let inputs = self.inputs();
+        if inputs.len() < 2 {
+            return None;
+        }
+        let ids: Vec<&str> = inputs
+            .iter()
+            .map(|input| input.device_id.as_str())
+            .collect();
+        Some(AudioDevice {
+            id: format!("{MIX_DEVICE_PREFIX}{}", ids.join("+")),
+            name: format!("Mix ({})", ids.join(" + ")),
+            sample_rate: SPEECH_SAMPLE_RATE,
+            channels: 1,
+            sample_format: SampleFormat::F32,
+        })
+    }
+
+    fn build_mixed_stream(
+        &self,
+        request: &StreamRequest,
+        on_samples: SampleCallback,
+        on_error: ErrorCallback,
+    ) -> Result<MultiDeviceStream<B::Stream>, AudioError> {
+        let devices = self.inner.list_input_devices()?;
+        let ids: Vec<String> = devices.iter().map(|device| device.id.clone()).collect();
+        let output_rate = request.sample_rate.unwrap_or(SPEECH_SAMPLE_RATE);
+        let mixer = Arc::new(Mutex::new(Mixer::new(output_rate, on_samples, on_error)));
+
+        let mut streams = Vec::new();
+        for input in self.inputs() {
+            let Some(device) = resolve_device_index(&ids, &input.device_id).map(|i| &devices[i])
+            else {
+                warn!("mix input {:?} not found; skipping", input.device_id);
+                continue;
+            };
+            let index = match mixer.lock() {
+                Ok(mut mixer) => mixer.add_input(StreamFormat::from(device), input.gain_db),
+                Err(_) => continue,
+            };
+            let sample_mixer = Arc::clone(&mixer);
+            let error_mixer = Arc::clone(&mixer);
+            let built = self.inner.build_input_stream(
+                device,
+                request,
+                Box::new(move |samples| {
+                    if let Ok(mut mixer) = sample_mixer.lock() {
+                        mixer.push(index, samples);
+                    }
+                }),
+                Box::new(move |err| {
+                    if let Ok(mut mixer) = error_mixer.lock() {
+                        mixer.fail(index, err);
+                    }
+                }),
+            );
+            match built {
+                Ok(stream) => {
+                    if let Ok(mut mixer) = mixer.lock() {
+                        mixer.configure(index, stream.format());
+                    }
+                    info!("mixing input {} as channel {index}", device.name);
+                    streams.push((index, stream));
+                }
+                Err(err) => {
+                    warn!("mix input {} unavailable: {err}", device.name);
+                    if let Ok(mut mixer) = mixer.lock() {
+                        mixer.drop_input(index);
+                    }
+                }
+            }
+        }
+
+        if streams.is_empty() {
+            return Err(AudioError::NoInputDevice);
+        }
+        Ok(MultiDeviceStream {
+            streams,
+            mixer: Some(mixer),
+            format: StreamFormat {
+                sample_rate: output_rate,
+                channels: 1,
+                sample_format: SampleFormat::F32,
+            },
+        })
+    }
+}
+
+impl<B: AudioBackend> AudioBackend for MultiDeviceCapture<B> {
+    type Stream = MultiDeviceStream<B::Stream>;
+
+    fn list_input_devices(&self) -> Result<Vec<AudioDevice>, AudioError> {
+        let mut devices = self.inner.list_input_devices()?;
+        devices.extend(self.mix_device());
+        Ok(devices)
+    }
+
+    fn default_input_device(&self) -> Result<Option<AudioDevice>, AudioError> {
+        match self.mix_device() {
+            Some(device) => Ok(Some(device)),
+            None => self.inner.default_input_device(),
+        }
+    }
+
+    fn list_loopback_devices(&self) -> Result<Vec<AudioDevice>, AudioError> {
+        self.inner.list_loopback_devices()
+    }
+
+    fn build_input_stream(
+        &self,
+        device: &AudioDevice,
+        request: &StreamRequest,
+        on_samples: SampleCallback,
+        on_error: ErrorCallback,
+    ) -> Result<Self::Stream, AudioError> {
+        if device.id.starts_with(MIX_DEVICE_PREFIX) {
+            return self.build_mixed_stream(request, on_samples, on_error);
+        }
+        self.inner
+            .build_input_stream(device, request, on_samples, on_error)
+            .map(MultiDeviceStream::single)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        ErrorCallback, MixInput, MultiDeviceCapture, SampleCallback, StreamResampler,
+        MIX_DEVICE_PREFIX,
+    };
+    use crate::audio::{
+        AudioBackend, AudioCaptureService, AudioDevice, AudioError, AudioStream, SampleFormat,
+        StreamFormat, StreamRequest,
+    };
+    use crate::hotkeys::{Hotkey, HotkeyActionEvent, HotkeyKey, HotkeyModifiers, HotkeyState};
+    use crate::ptt::PttCaptureService;
+    use approx::assert_relative_eq;
+    use std::collections::HashMap;
+    use std::sync::{
+        atomic::{AtomicBool, Ordering},
+        mpsc, Arc, Mutex,
+    };
+    use std::time::Duration;
+
+    #[derive(Clone)]
+    struct MockStreamController {
+        running: Arc<AtomicBool>,
+        callback: Arc<Mutex<Option<SampleCallback>>>,
+        on_error: Arc<Mutex<Option<ErrorCallback>>>,
+    }
+
+    impl MockStreamController {
+        fn push_samples(&self, samples: &[f32]) {
+            if !self.running.load(Ordering::SeqCst) {
+                return;
+            }
+            if let Some(handler) = self.callback.lock().expect("callback lock").as_mut() {
+                handler(samples);
+            }
+        }
+
+        fn fail(&self, err: AudioError) {
+            if let Some(handler) = self.on_error.lock().expect("error lock").as_mut() {
+                handler(err);
+            }
+        }
+    }
+
+    struct MockStream {
+        controller: MockStreamController,
+        format: StreamFormat,
+    }
+
+    impl AudioStream for MockStream {
+        fn start(&self) -> Result<(), AudioError> {
+            self.controller.running.store(true, Ordering::SeqCst);
+            Ok(())
+        }
+
+        fn stop(&self) -> Result<(), AudioError> {
+            self.controller.running.store(false, Ordering::SeqCst);
+            Ok(())
+        }
+
+        fn format(&self) -> StreamFormat {
+            self.format
+        }
+    }
+
+    #[derive(Clone)]
+    struct MockAudioBackend {
+        devices: Vec<AudioDevice>,
+        controllers: Arc<Mutex<HashMap<String, MockStreamController>>>,
+    }
+
+    impl MockAudioBackend {
+        fn new(devices: Vec<AudioDevice>) -> Self {
+            Self {
+                devices,
+                controllers: Arc::new(Mutex::new(HashMap::new())),
+            }
+        }
+
+        fn controller(&self, id: &str) -> MockStreamController {
+            self.controllers
+                .lock()
+                .expect("controllers lock")
+                .get(id)
+                .cloned()
+                .expect("controller ready")
+        }
+    }
+
+    impl AudioBackend for MockAudioBackend {
+        type Stream = MockStream;
+
+        fn list_input_devices(&self) -> Result<Vec<AudioDevice>, AudioError> {
+            Ok(self.devices.clone())
+        }
+
+        fn default_input_device(&self) -> Result<Option<AudioDevice>, AudioError> {
+            Ok(self.devices.first().cloned())
+        }
+
+        fn build_input_stream(
+            &self,
+            device: &AudioDevice,
+            _request: &StreamRequest,
+            on_samples: SampleCallback,
+            on_error: ErrorCallback,
+        ) -> Result<Self::Stream, AudioError> {
+            let controller = MockStreamController {
+                running: Arc::new(AtomicBool::new(false)),
+                callback: Arc::new(Mutex::new(Some(on_samples))),
+                on_error: Arc::new(Mutex::new(Some(on_error))),
+            };
+            self.controllers
+                .lock()
+                .expect("controllers lock")
+                .insert(device.id.clone(), controller.clone());
+            Ok(MockStream {
+                controller,
+                format: StreamFormat::from(device),
+            })
+        }
+    }
+
+    fn device(id: &str, sample_rate: u32, channels: u16) -> AudioDevice {
+        AudioDevice {
+            id: id.to_string(),
+            name: id.to_string(),
+            sample_rate,
+            channels,
+            sample_format: SampleFormat::F32,
+        }
+    }
+
+    const SPEECH: StreamRequest = StreamRequest {
+        sample_rate: Some(16_000),
+        channels: Some(1),
+    };
+
+    fn mixed_service(
+        backend: &MockAudioBackend,
+        inputs: Vec<MixInput>,
+    ) -> (
+        AudioCaptureService<MultiDeviceCapture<MockAudioBackend>>,
+        mpsc::Receiver<Vec<f32>>,
+    ) {
+        let capture = MultiDeviceCapture::new(backend.clone()).with_inputs(inputs);
+        let mut service = AudioCaptureService::new(capture);
+        service.set_stream_request(SPEECH);
+        let (sender, receiver) = mpsc::channel();
+        service
+            .start_with_callback(move |samples| {
+                let _ = sender.send(samples.to_vec());
+            })
+            .expect("start mixed capture");
+        (service, receiver)
+    }
+
+    fn collect(receiver: &mpsc::Receiver<Vec<f32>>) -> Vec<f32> {
+        receiver.try_iter().flatten().collect()
+    }
+
+    fn ramp(start: usize, len: usize) -> Vec<f32> {
+        (start..start + len)
+            .map(|index| index as f32 / 1_000.0)
+            .collect()
+    }
+
+    #[test]
+    fn lists_a_virtual_mix_device_when_configured() {
+        let backend = MockAudioBackend::new(vec![device("Headset", 16_000, 1)]);
+        let capture = MultiDeviceCapture::new(backend);
+        assert!(capture.mix_device().is_none());
+        assert_eq!(capture.list_input_devices().unwrap().len(), 1);
+
+        capture.set_inputs(vec![MixInput::new("Headset"), MixInput::new("Desk")]);
+        let mix = capture.default_input_device().unwrap().expect("mix device");
+        assert_eq!(mix.id, format!("{MIX_DEVICE_PREFIX}Headset+Desk"));
+        assert_eq!(capture.list_input_devices().unwrap().len(), 2);
+    }
+
+    #[test]
+    fn aligns_interleaved_buffers_with_different_cadence() {
+        let backend = MockAudioBackend::new(vec![
+            device("Headset", 16_000, 1),
+            device("Desk", 16_000, 2),
+        ]);
+        let (service, receiver) = mixed_service(
+            &backend,
+            vec![MixInput::new("Headset"), MixInput::new("Desk")],
+        );
+        assert_eq!(service.selected_device().unwrap().sample_rate, 16_000);
+        let headset = backend.controller("Headset");
+        let desk = backend.controller("Desk");
+        let stereo = |mono: Vec<f32>| -> Vec<f32> {
+            mono.into_iter()
+                .flat_map(|sample| [sample, sample])
+                .collect()
+        };
+
+        headset.push_samples(&ramp(0, 100));
+        assert!(collect(&receiver).is_empty());
+        desk.push_samples(&stereo(ramp(0, 40)));
+        headset.push_samples(&ramp(100, 60));
+        desk.push_samples(&stereo(ramp(40, 120)));
+
+        let mixed = collect(&receiver);
+        assert_eq!(mixed.len(), 160);
+        for (sample, expected) in mixed.iter().zip(ramp(0, 160)) {
+            assert_relative_eq!(*sample, expected, epsilon = 1e-6);
+        }
+    }
+
+    #[test]
+    fn averages_inputs_after_per_device_gain() {
+        let backend = MockAudioBackend::new(vec![
+            device("Headset", 16_000, 1),
+            device("Desk", 16_000, 1),
+        ]);
+        let (_service, receiver) = mixed_service(
+            &backend,
+            vec![
+                MixInput::new("Headset"),
+                MixInput::new("Desk").with_gain_db(-6.0206),
+            ],
+        );
+
+        backend.controller("Headset").push_samples(&[0.2; 64]);
+        backend.controller("Desk").push_samples(&[0.8; 64]);
+
+        let mixed = collect(&receiver);
+        assert_eq!(mixed.len(), 64);
+        assert!(mixed.iter().all(|sample| (sample - 0.3).abs() < 1e-4));
+    }
+
+    #[test]
+    fn resamples_each_input_to_the_output_rate() {
+        let backend = MockAudioBackend::new(vec![
+            device("Headset", 16_000, 1),
+            device("Desk", 48_000, 1),
+        ]);
+        let (_service, receiver) = mixed_service(
+            &backend,
+            vec![MixInput::new("Headset"), MixInput::new("Desk")],
+        );
+
+        for _ in 0..4 {
+            backend.controller("Desk").push_samples(&[0.5; 480]);
+            backend.controller("Headset").push_samples(&[0.5; 160]);
+        }
+
+        let mixed = collect(&receiver);
+        assert!((636..=640).contains(&mixed.len()), "len {}", mixed.len());
+        assert!(mixed.iter().all(|sample| (sample - 0.5).abs() < 1e-6));
+    }
+
+    #[test]
+    fn stalled_input_is_padded_after_jitter_buffer() {
+        let backend = MockAudioBackend::new(vec![
+            device("Headset", 16_000, 1),
+            device("Desk", 16_000, 1),
+        ]);
+        let (_service, receiver) = mixed_service(
+            &backend,
+            vec![MixInput::new("Headset"), MixInput::new("Desk")],
+        );
+
+        backend.controller("Headset").push_samples(&[0.4; 1_600]);
+        assert!(collect(&receiver).is_empty());
+        backend.controller("Headset").push_samples(&[0.4; 400]);
+
+        let mixed = collect(&receiver);
+        assert_eq!(mixed.len(), 400);
+        assert!(mixed.iter().all(|sample| (sample - 0.2).abs() < 1e-6));
+    }
+
+    #[test]
+    fn failed_input_drops_out_without_stopping_capture() {
+        let backend = MockAudioBackend::new(vec![
+            device("Headset", 16_000, 1),
+            device("Desk", 16_000, 1),
+        ]);
+        let capture = MultiDeviceCapture::new(backend.clone())
+            .with_inputs(vec![MixInput::new("Headset"), MixInput::new("Desk")]);
+        let mut service = AudioCaptureService::new(capture);
+        let errors = service.error_feed().expect("error feed");
+        service.set_stream_request(SPEECH);
+        let (sender, receiver) = mpsc::channel();
+        service
+            .start_with_callback(move |samples| {
+                let _ = sender.send(samples.to_vec());
+            })
+            .expect("start mixed capture");
+
+        backend.controller("Headset").push_samples(&[0.4; 32]);
+        backend
+            .controller("Desk")
+            .fail(AudioError::Stream("unplugged".to_string()));
+        backend.controller("Headset").push_samples(&[0.4; 32]);
+
+        let mixed = collect(&receiver);
+        assert_eq!(mixed.len(), 64);
+        assert!(mixed.iter().all(|sample| (sample - 0.4).abs() < 1e-6));
+        assert!(errors.try_recv().is_err());
+
+        backend
+            .controller("Headset")
+            .fail(AudioError::Stream("unplugged".to_string()));
+        assert!(matches!(
+            errors.recv_timeout(Duration::from_millis(50)),
+            Ok(AudioError::Stream(_))
+        ));
+    }
+
+    #[test]
+    fn ptt_capture_runs_over_mixed_input() {
+        let backend = MockAudioBackend::new(vec![
+            device("Headset", 16_000, 1),
+            device("Desk", 16_000, 1),
+        ]);
+        let capture = MultiDeviceCapture::new(backend.clone())
+            .with_inputs(vec![MixInput::new("Headset"), MixInput::new("Desk")]);
+        let mut service = PttCaptureService::new(capture, "ptt");
+        service.start().expect("start capture");
+        let event = |state| HotkeyActionEvent {
+            action: "ptt".to_string(),
+            hotkey: Hotkey {
+                key: HotkeyKey::F9,
+                modifiers: HotkeyModifiers::none(),
+            },
+            state,
+        };
+
+        service
+            .handle_hotkey_action(&event(HotkeyState::Pressed))
+            .expect("activate capture");
+        backend.controller("Headset").push_samples(&[0.6; 8]);
+        backend.controller("Desk").push_samples(&[0.2; 8]);
+
+        let captured = service.take_audio().expect("take audio");
+        assert_eq!(captured.len(), 8);
+        assert!(captured.iter().all(|sample| (sample - 0.4).abs() < 1e-6));
+    }
+
+    #[test]
+    fn stream_resampler_carries_phase_across_buffers() {
+        let mut chunked = StreamResampler::new(48_000, 16_000);
+        let input = ramp(0, 960);
+        let output: Vec<f32> = input
+            .chunks(97)
+            .flat_map(|chunk| chunked.process(chunk))
+            .collect();
+
+        assert_eq!(output.len(), 320);
+        for (index, sample) in output.iter().enumerate() {
+            assert_relative_eq!(*sample, (index * 3) as f32 / 1_000.0, epsilon = 1e-5);
+        }
+    }
+}
@@ -0,0 +1,580 @@
+use std::sync::{mpsc, Arc, Mutex};
+
+use crate::hotkeys::{
+    spawn_shortcut_listener, Hotkey, HotkeyActionEvent, HotkeyError, HotkeyKey,
+    HotkeyListenerHandle, HotkeyManager, HotkeyState,
+};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PortalShortcut {
+    pub id: String,
+    pub description: String,
+    pub preferred_trigger: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PortalSignal {
+    Activated(String),
+    Deactivated(String),
+}
+
+pub trait ShortcutPortal: Send + 'static {
+    fn bind(&mut self, shortcuts: &[PortalShortcut]) -> Result<(), HotkeyError>;
+
+    fn listen(self, handler: Box<dyn FnMut(PortalSignal) + Send>) -> Result<(), HotkeyError>
+    where
+        Self: Sized;
+}
+
+pub struct PortalHotkeyListener {
+    manager: Arc<Mutex<HotkeyManager>>,
+}
+
+impl PortalHotkeyListener {
+    pub fn new(manager: Arc<Mutex<HotkeyManager>>) -> Self {
+        Self { manager }
+    }
+
+    pub fn start<P: ShortcutPortal>(
+        &self,
+        mut portal: P,
+    ) -> Result<(HotkeyListenerHandle, mpsc::Receiver<HotkeyActionEvent>), HotkeyError> {
+        let shortcuts = {
+            let manager = self
+                .manager
+                .lock()
+                .map_err(|_| HotkeyError::ManagerLockPoisoned)?;
+            portal_shortcuts(&manager)
+        };
+        portal.bind(&shortcuts)?;
+
+        let (sender, receiver) = mpsc::channel();
+        let handle = spawn_shortcut_listener(Arc::clone(&self.manager), sender, |mut handler| {
+            portal.listen(Box::new(move |signal| {
+                let (id, state) = match signal {
+                    PortalSignal::Activated(id) => (id, HotkeyState::Pressed),
+                    PortalSignal::Deactivated(id) => (id, HotkeyState::Released),
+                };
+                match id.parse::<Hotkey>() {
+                    Ok(hotkey) => handler((hotkey, state)),
+                    Err(err) => log::warn!("ignoring unknown portal shortcut '{id}': {err}"),
+                }
+            }))
+        });
+
+        Ok((handle, receiver))
+    }
+}
+
+fn portal_shortcuts(manager: &HotkeyManager) -> Vec<PortalShortcut> {
+    // The session is bound once, so bindings that are disabled right now
+    // (like the cancel hotkey outside a capture) are registered too; the
+    // listener ignores them until they are enabled.
+    let mut shortcuts: Vec<PortalShortcut> = Vec::new();
+    for (hotkey, binding) in manager.bindings() {
+        let id = hotkey.to_string();
+        if let Some(existing) = shortcuts.iter_mut().find(|shortcut| shortcut.id == id) {
+            if !existing
+                .description
+                .split(", ")
+                .any(|action| action == binding.action)
+            {
+                existing.description = format!("{}, {}", existing.description, binding.action);
+            }
+            continue;
+        }
+        let Some(preferred_trigger) = preferred_trigger(&hotkey) else {
+            log::warn!("hotkey '{hotkey}' cannot be bound through the shortcuts portal");
+            continue;
+        };
+        shortcuts.push(PortalShortcut {
+            id,
+            description: binding.action,
+            preferred_trigger,
+        });
+    }
+    shortcuts
+}
+
+fn preferred_trigger(hotkey: &Hotkey) -> Option<String> {
+    let mut trigger = String::new();
+    for (held, name) in [
+        (hotkey.modifiers.ctrl, "CTRL"),
+        (hotkey.modifiers.alt, "ALT"),
+        (hotkey.modifiers.shift, "SHIFT"),
+        (hotkey.modifiers.meta, "LOGO"),
+    ] {
+        if held {
+            trigger.push_str(name);
+            trigger.push('+');
+        }
+    }
+    trigger.push_str(&keysym_name(hotkey.key)?);
+    Some(trigger)
+}
+
+fn keysym_name(key: HotkeyKey) -> Option<String> {
+    let name = match key {
+        HotkeyKey::F1 => "F1",
+        HotkeyKey::F2 => "F2",
+        HotkeyKey::F3 => "F3",
+        HotkeyKey::F4 => "F4",
+        HotkeyKey::F5 => "F5",
+        HotkeyKey::F6 => "F6",
+        HotkeyKey::F7 => "F7",
+        HotkeyKey::F8 => "F8",
+        HotkeyKey::F9 => "F9",
+        HotkeyKey::F10 => "F10",
+        HotkeyKey::F11 => "F11",
+        HotkeyKey::F12 => "F12",
+        HotkeyKey::Space => "space",
+        HotkeyKey::Enter => "Return",
+        HotkeyKey::Escape => "Escape",
+        HotkeyKey::Tab => "Tab",
+        HotkeyKey::Backspace => "BackSpace",
+        HotkeyKey::Left => "Left",
+        HotkeyKey::Right => "Right",
+        HotkeyKey::Up => "Up",
+        HotkeyKey::Down => "Down",
+        HotkeyKey::Numpad0 => "KP_0",
+        HotkeyKey::Numpad1 => "KP_1",
+        HotkeyKey::Numpad2 => "KP_2",
+        HotkeyKey::Numpad3 => "KP_3",
+        HotkeyKey::Numpad4 => "KP_4",
+        HotkeyKey::Numpad5 => "KP_5",
+        HotkeyKey::Numpad6 => "KP_6",
+        HotkeyKey::Numpad7 => "KP_7",
+        HotkeyKey::Numpad8 => "KP_8",
+        HotkeyKey::Numpad9 => "KP_9",
+        HotkeyKey::NumpadAdd => "KP_Add",
+        HotkeyKey::NumpadSubtract => "KP_Subtract",
+        HotkeyKey::NumpadMultiply => "KP_Multiply",
+        HotkeyKey::NumpadDivide => "KP_Divide",
+        HotkeyKey::NumpadDecimal => "KP_Decimal",
+        HotkeyKey::NumpadEnter => "KP_Enter",
+        HotkeyKey::Insert => "Insert",
+        HotkeyKey::Delete => "Delete",
+        HotkeyKey::Home => "Home",
+        HotkeyKey::End => "End",
+        HotkeyKey::PageUp => "Page_Up",
+        HotkeyKey::PageDown => "Page_Down",
+        HotkeyKey::Pause => "Pause",
+        HotkeyKey::CtrlLeft => "Control_L",
+        HotkeyKey::CtrlRight => "Control_R",
+        HotkeyKey::AltLeft => "Alt_L",
+        HotkeyKey::AltRight => "Alt_R",
+        HotkeyKey::ShiftLeft => "Shift_L",
+        HotkeyKey::ShiftRight => "Shift_R",
+        HotkeyKey::MetaLeft => "Super_L",
+        HotkeyKey::MetaRight => "Super_R",
+        HotkeyKey::Mouse(_) => return None,
+        other => return Some(other.name().into_owned()),
+    };
+    Some(name.to_string())
+}
+
+#[cfg(all(target_os = "linux", feature = "wayland-portal"))]
+pub use dbus::DbusShortcutPortal;
+
+#[cfg(all(target_os = "linux", feature = "wayland-portal"))]
+mod dbus {
+    use std::collections::HashMap;
+    use std::sync::atomic::{AtomicU64, Ordering};
+
+    use zbus::blocking::{Connection, Proxy};
+    use zbus::zvariant::{OwnedObjectPath, OwnedValue, Value};
+    use zbus::Message;
+
+    use super::{PortalShortcut, PortalSignal, ShortcutPortal};
+    use crate::hotkeys::HotkeyError;
+
+    const PORTAL_DESTINATION: &str = "org.freedesktop.portal.Desktop";
+    const PORTAL_PATH: &str = "/org/freedesktop/portal/desktop";
+    const SHORTCUTS_INTERFACE: &str = "org.freedesktop.portal.GlobalShortcuts";
+    const REQUEST_INTERFACE: &str = "org.freedesktop.portal.Request";
+
+    static NEXT_TOKEN: AtomicU64 = AtomicU64::new(0);
+
+    type PortalResults = HashMap<String, OwnedValue>;
+
+    pub struct DbusShortcutPortal {
+        connection: Connection,
+        session: Option<OwnedObjectPath>,
+    }
+
+    impl DbusShortcutPortal {
+        pub fn connect() -> Result<Self, HotkeyError> {
+            let connection = Connection::session().map_err(portal_error)?;
+            let portal = Self {
+                connection,
+                session: None,
+            };
+            portal
+                .shortcuts()?
+                .get_property::<u32>("version")
+                .map_err(portal_error)?;
+            Ok(portal)
+        }
+
+        fn shortcuts(&self) -> Result<Proxy<'static>, HotkeyError> {
+            Proxy::new(
+                &self.connection,
+                PORTAL_DESTINATION,
+                PORTAL_PATH,
+                SHORTCUTS_INTERFACE,
+            )
+            .map_err(portal_error)
+        }
+
+        fn request(
+            &self,
+            method: &str,
+            token: &str,
+            call: impl FnOnce(&Proxy<'static>) -> zbus::Result<Message>,
+        ) -> Result<PortalResults, HotkeyError> {
+            let sender = self
+                .connection
+                .unique_name()
+                .ok_or_else(|| HotkeyError::Portal("no unique bus name".to_string()))?
+                .trim_start_matches(':')
+                .replace('.', "_");
+            let path = format!("{PORTAL_PATH}/request/{sender}/{token}");
+            let request = Proxy::new(
+                &self.connection,
+                PORTAL_DESTINATION,
+                path.as_str(),
+                REQUEST_INTERFACE,
+            )
+            .map_err(portal_error)?;
+            let mut responses = request.receive_signal("Response").map_err(portal_error)?;
+
+            call(&self.shortcuts()?).map_err(portal_error)?;
+
+            let message = responses
+                .next()
+                .ok_or_else(|| HotkeyError::Portal(format!("{method} got no response")))?;
+            let (code, results): (u32, PortalResults) =
+                message.body().deserialize().map_err(portal_error)?;
+            match code {
+                0 => Ok(results),
+                1 => Err(HotkeyError::Portal(format!("{method} was cancelled"))),
+                _ => Err(HotkeyError::Portal(format!("{method} failed"))),
+            }
+        }
+
+        fn create_session(&self) -> Result<OwnedObjectPath, HotkeyError> {
+            let token = next_token();
+            let options = HashMap::from([
+                ("handle_token", Value::from(token.as_str())),
+                ("session_handle_token", Value::from(token.as_str())),
+            ]);
+            let results = self.request("CreateSession", &token, |proxy| {
+                proxy.call_method("CreateSession", &(options,))
+            })?;
+            let handle = results
+                .get("session_handle")
+                .cloned()
+                .ok_or_else(|| HotkeyError::Portal("missing session handle".to_string()))?;
+            let handle = String::try_from(handle).map_err(portal_error)?;
+            OwnedObjectPath::try_from(handle).map_err(portal_error)
+        }
+    }
+
+    impl ShortcutPortal for DbusShortcutPortal {
+        fn bind(&mut self, shortcuts: &[PortalShortcut]) -> Result<(), HotkeyError> {
+            let session = self.create_session()?;
+            let token = next_token();
+            let shortcuts: Vec<(&str, HashMap<&str, Value>)> = shortcuts
+                .iter()
+                .map(|shortcut| {
+                    (
+                        shortcut.id.as_str(),
+                        HashMap::from([
+                            ("description", Value::from(shortcut.description.as_str())),
+                            (
+                                "preferred_trigger",
+                                Value::from(shortcut.preferred_trigger.as_str()),
+                            ),
+                        ]),
+                    )
+                })
+                .collect();
+            let options = HashMap::from([("handle_token", Value::from(token.as_str()))]);
+            self.request("BindShortcuts", &token, |proxy| {
+                proxy.call_method("BindShortcuts", &(&session, shortcuts, "", options))
+            })?;
+            self.session = Some(session);
+            Ok(())
+        }
+
+        fn listen(
+            self,
+            mut handler: Box<dyn FnMut(PortalSignal) + Send>,
+        ) -> Result<(), HotkeyError> {
+            let session = self
+                .session
+                .clone()
+                .ok_or_else(|| HotkeyError::Portal("shortcuts were not bound".to_string()))?;
+            let signals = self
+                .shortcuts()?
+                .receive_all_signals()
+                .map_err(portal_error)?;
+            for message in signals {
+                let header = message.header();
+                let Some(member) = header.member() else {
+                    continue;
+                };
+                let member = member.to_string();
+                if member != "Activated" && member != "Deactivated" {
+                    continue;
+                }
+                let Ok((handle, id, _timestamp, _options)) =
+                    message
+                        .body()
+                        .deserialize::<(OwnedObjectPath, String, u64, PortalResults)>()
+                else {
+                    continue;
+                };
+                if handle != session {
+                    continue;
+                }
+                handler(if member == "Activated" {
+                    PortalSignal::Activated(id)
+                } else {
+                    PortalSignal::Deactivated(id)
+                });
+            }
+            Err(HotkeyError::Portal("portal connection closed".to_string()))
+        }
+    }
+
+    fn next_token() -> String {
+        format!(
+            "openwhisperai_{}_{}",
+            std::process::id(),
+            NEXT_TOKEN.fetch_add(1, Ordering::Relaxed)
+        )
+    }
+
+    fn portal_error(err: impl std::fmt::Display) -> HotkeyError {
+        HotkeyError::Portal(err.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        keysym_name, portal_shortcuts, PortalHotkeyListener, PortalShortcut, PortalSignal,
+        ShortcutPortal,
+    };
+    use crate::hotkeys::{
+        Hotkey, HotkeyError, HotkeyKey, HotkeyManager, HotkeyModifiers, HotkeyState, HotkeyTrigger,
+        MouseButton,
+    };
+    use std::sync::{mpsc, Arc, Mutex};
+    use std::time::Duration;
+
+    struct MockPortal {
+        bound: Arc<Mutex<Vec<PortalShortcut>>>,
+        signals: Vec<PortalSignal>,
+    }
+
+    impl ShortcutPortal for MockPortal {
+        fn bind(&mut self, shortcuts: &[PortalShortcut]) -> Result<(), HotkeyError> {
+            *self.bound.lock().expect("bound") = shortcuts.to_vec();
+            Ok(())
+        }
+
+        fn listen(
+            self,
+            mut handler: Box<dyn FnMut(PortalSignal) + Send>,
+        ) -> Result<(), HotkeyError> {
+            for signal in self.signals {
+                handler(signal);
+            }
+            Ok(())
+        }
+    }
+
+    struct UnavailablePortal;
+
+    impl ShortcutPortal for UnavailablePortal {
+        fn bind(&mut self, _shortcuts: &[PortalShortcut]) -> Result<(), HotkeyError> {
+            Err(HotkeyError::Portal("no such interface".to_string()))
+        }
+
+        fn listen(self, _handler: Box<dyn FnMut(PortalSignal) + Send>) -> Result<(), HotkeyError> {
+            Ok(())
+        }
+    }
+
+    fn ctrl_k() -> Hotkey {
+        Hotkey {
+            key: HotkeyKey::K,
+            modifiers: HotkeyModifiers {
+                ctrl: true,
+                ..HotkeyModifiers::none()
+            },
+        }
+    }
+
+    #[test]
+    fn portal_signals_become_hotkey_action_events() {
+        let manager = Arc::new(Mutex::new(HotkeyManager::new()));
+        {
+            let mut manager = manager.lock().expect("manager");
+            manager.register_with_trigger(ctrl_k(), HotkeyTrigger::Pressed, "ptt");
+            manager.register_with_trigger(ctrl_k(), HotkeyTrigger::Released, "ptt");
+        }
+        let bound = Arc::new(Mutex::new(Vec::new()));
+        let portal = MockPortal {
+            bound: Arc::clone(&bound),
+            signals: vec![
+                PortalSignal::Activated("ctrl+k".to_string()),
+                PortalSignal::Activated("ctrl+k".to_string()),
+                PortalSignal::Deactivated("ctrl+k".to_string()),
+                PortalSignal::Activated("bogus".to_string()),
+            ],
+        };
+
+        let (handle, receiver) = PortalHotkeyListener::new(manager)
+            .start(portal)
+            .expect("start");
+        handle.join().expect("listener join");
+
+        assert_eq!(
+            *bound.lock().expect("bound"),
+            vec![PortalShortcut {
+                id: "ctrl+k".to_string(),
+                description: "ptt".to_string(),
+                preferred_trigger: "CTRL+k".to_string(),
+            }]
+        );
+        let events = receiver.try_iter().collect::<Vec<_>>();
+        assert_eq!(events.len(), 2);
+        assert!(events.iter().all(|event| event.hotkey == ctrl_k()));
+        assert_eq!(events[0].state, HotkeyState::Pressed);
+        assert_eq!(events[1].state, HotkeyState::Released);
+    }
+
+    #[test]
+    fn unavailable_portal_fails_to_start() {
+        let manager = Arc::new(Mutex::new(HotkeyManager::new()));
+        let result = PortalHotkeyListener::new(manager).start(UnavailablePortal);
+        assert!(matches!(result, Err(HotkeyError::Portal(_))));
+    }
+
+    #[test]
+    fn portal_shortcuts_keep_disabled_and_skip_mouse_bindings() {
+        let mut manager = HotkeyManager::new();
+        let f9 = Hotkey {
+            key: HotkeyKey::F9,
+            modifiers: HotkeyModifiers::none(),
+        };
+        let mouse = Hotkey {
+            key: HotkeyKey::Mouse(MouseButton::Other(8)),
+            modifiers: HotkeyModifiers::none(),
+        };
+        manager.register_with_trigger(f9, HotkeyTrigger::Released, "ptt-toggle");
+        manager.register_with_trigger(f9, HotkeyTrigger::Held { min_ms: 300 }, "ptt");
+        manager.register(mouse, "ptt");
+        manager.register(ctrl_k(), "overlay");
+        manager.set_enabled(&ctrl_k(), HotkeyTrigger::Pressed, false);
+
+        let shortcuts = portal_shortcuts(&manager);
+
+        assert_eq!(
+            shortcuts,
+            vec![
+                PortalShortcut {
+                    id: "ctrl+k".to_string(),
+                    description: "overlay".to_string(),
+                    preferred_trigger: "CTRL+k".to_string(),
+                },
+                PortalShortcut {
+                    id: "f9".to_string(),
+                    description: "ptt-toggle, ptt".to_string(),
+                    preferred_trigger: "F9".to_string(),
+                },
+            ]
+        );
+        assert_eq!(keysym_name(HotkeyKey::PageUp).as_deref(), Some("Page_Up"));
+        assert_eq!(keysym_name(HotkeyKey::Digit4).as_deref(), Some("4"));
+    }
+
+    struct ChannelPortal(mpsc::Receiver<PortalSignal>);
+
+    impl ShortcutPortal for ChannelPortal {
+        fn bind(&mut self, _shortcuts: &[PortalShortcut]) -> Result<(), HotkeyError> {
+            Ok(())
+        }
+
+        fn listen(
+            self,
+            mut handler: Box<dyn FnMut(PortalSignal) + Send>,
+        ) -> Result<(), HotkeyError> {
+            for signal in self.0 {
+                handler(signal);
+            }
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn disabled_portal_shortcuts_fire_once_enabled() {
+        let manager = Arc::new(Mutex::new(HotkeyManager::new()));
+        {
+            let mut manager = manager.lock().expect("manager");
+            manager.register(ctrl_k(), "ptt-cancel");
+            manager.set_enabled(&ctrl_k(), HotkeyTrigger::Pressed, false);
+        }
+        let (signal_tx, signal_rx) = mpsc::channel::<PortalSignal>();
+        let (handle, receiver) = PortalHotkeyListener::new(Arc::clone(&manager))
+            .start(ChannelPortal(signal_rx))
+            .expect("start");
+        let press = |signal_tx: &mpsc::Sender<PortalSignal>| {
+            for signal in [
+                PortalSignal::Activated("ctrl+k".to_string()),
+                PortalSignal::Deactivated("ctrl+k".to_string()),
+            ] {
+                signal_tx.send(signal).expect("send");
+            }
+        };
+
+        press(&signal_tx);
+        assert!(receiver.recv_timeout(Duration::from_millis(100)).is_err());
+
+        manager
+            .lock()
+            .expect("manager")
+            .set_enabled(&ctrl_k(), HotkeyTrigger::Pressed, true);
+        press(&signal_tx);
+        let event = receiver
+            .recv_timeout(Duration::from_secs(1))
+            .expect("cancel event");
+        assert_eq!(event.action, "ptt-cancel");
+
+        handle.stop();
+        drop(signal_tx);
+        handle.join().expect("listener join");
+    }
+
+    #[test]
+    fn portal_listener_stops_emitting_after_stop() {
+        let manager = Arc::new(Mutex::new(HotkeyManager::new()));
+        manager.lock().expect("manager").register(ctrl_k(), "ptt");
+        let (signal_tx, signal_rx) = mpsc::channel::<PortalSignal>();
+
+        let (handle, receiver) = PortalHotkeyListener::new(manager)
+            .start(ChannelPortal(signal_rx))
+            .expect("start");
+        handle.stop();
+        signal_tx
+            .send(PortalSignal::Activated("ctrl+k".to_string()))
+            .expect("send");
+        drop(signal_tx);
+        handle.join().expect("listener join");
+
+        assert_eq!(receiver.try_iter().count(), 0);
+    }
+}
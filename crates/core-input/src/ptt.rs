@@ -1,11 +1,16 @@
-use crate::audio::{AudioBackend, AudioCaptureService, AudioError};
+use crate::audio::{AudioBackend, AudioCaptureService, AudioError, StreamRequest};
+use crate::dsp::{ProcessorChain, SampleProcessor};
+use crate::gain::{GainMode, GainStage};
 use crate::hotkeys::{HotkeyActionEvent, HotkeyState};
-use crate::meter::{LevelMeter, LevelReading};
-use log::info;
+use crate::meter::{LevelHistory, LevelMeter, LevelReading};
+use crate::vad::{VadConfig, VadDetector};
+use log::{info, warn};
+use std::collections::VecDeque;
 use std::sync::{
     atomic::{AtomicBool, Ordering},
     mpsc, Arc, Mutex,
 };
+use std::time::Duration;
 
 #[derive(Debug, thiserror::Error)]
 pub enum PttCaptureError {
@@ -15,29 +20,318 @@ pub enum PttCaptureError {
     BufferLockPoisoned,
     #[error("level meter lock was poisoned")]
     MeterLockPoisoned,
+    #[error("capture buffer is full")]
+    BufferFull,
 }
 
+pub const PTT_STREAM_REQUEST: StreamRequest = StreamRequest {
+    sample_rate: Some(16_000),
+    channels: Some(1),
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum OverflowPolicy {
+    #[default]
+    StopCapture,
+    DropOldest,
+    DropNewest,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CaptureLimits {
+    pub max_duration: Duration,
+    pub min_duration: Duration,
+    pub silence_auto_stop: bool,
+    pub silence_threshold_dbfs: f32,
+    pub silence_duration: Duration,
+    pub overflow_policy: OverflowPolicy,
+    pub pre_roll: Duration,
+    pub chunking: Option<ChunkConfig>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ChunkConfig {
+    pub length: Duration,
+    pub overlap: Duration,
+}
+
+impl Default for ChunkConfig {
+    fn default() -> Self {
+        Self {
+            length: Duration::from_secs(5),
+            overlap: Duration::from_millis(500),
+        }
+    }
+}
+
+impl Default for CaptureLimits {
+    fn default() -> Self {
+        Self {
+            max_duration: Duration::from_secs(120),
+            min_duration: Duration::from_millis(300),
+            silence_auto_stop: false,
+            silence_threshold_dbfs: -45.0,
+            silence_duration: Duration::from_millis(1500),
+            overflow_policy: OverflowPolicy::StopCapture,
+            pre_roll: Duration::from_millis(500),
+            chunking: None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CaptureAutoStopped {
+    pub action: String,
+    pub silence: Duration,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct CaptureChunk {
+    pub action: String,
+    pub index: usize,
+    pub overlap: usize,
+    pub samples: Vec<f32>,
+}
+
+#[derive(Debug, Default)]
+struct CaptureBuffer {
+    samples: VecDeque<f32>,
+    max_samples: usize,
+    policy: OverflowPolicy,
+    overflowed: bool,
+    pre_roll: VecDeque<f32>,
+    pre_roll_samples: usize,
+    vad: Option<VadDetector>,
+    chunk_samples: Option<(usize, usize)>,
+    chunk_end: usize,
+    chunk_index: usize,
+}
+
+impl CaptureBuffer {
+    fn push_pre_roll(&mut self, incoming: &[f32]) {
+        let incoming = &incoming[incoming.len().saturating_sub(self.pre_roll_samples)..];
+        let excess = (self.pre_roll.len() + incoming.len()).saturating_sub(self.pre_roll_samples);
+        self.pre_roll.drain(..excess);
+        self.pre_roll.extend(incoming);
+    }
+
+    fn begin_capture(&mut self) {
+        self.samples = std::mem::take(&mut self.pre_roll);
+        let excess = self.samples.len().saturating_sub(self.max_samples);
+        self.samples.drain(..excess);
+        self.overflowed = false;
+        self.chunk_end = 0;
+        self.chunk_index = 0;
+        if let Some(vad) = self.vad.as_mut() {
+            vad.reset();
+        }
+    }
+
+    fn next_chunk(&mut self, action: &str) -> Option<CaptureChunk> {
+        let (length, overlap) = self.chunk_samples?;
+        let end = self.chunk_end + length;
+        if self.samples.len() < end {
+            return None;
+        }
+        let start = self.chunk_end.saturating_sub(overlap);
+        let chunk = CaptureChunk {
+            action: action.to_string(),
+            index: self.chunk_index,
+            overlap: self.chunk_end - start,
+            samples: self.samples.range(start..end).copied().collect(),
+        };
+        self.chunk_end = end;
+        self.chunk_index += 1;
+        Some(chunk)
+    }
+
+    fn detect_silence(&mut self, incoming: &[f32]) -> Option<Duration> {
+        let vad = self.vad.as_mut()?;
+        if !vad.push(incoming) {
+            return None;
+        }
+        let trailing = vad.silent_samples().saturating_sub(vad.window_samples());
+        let keep = self.samples.len().saturating_sub(trailing);
+        self.samples.truncate(keep);
+        Some(vad.silence())
+    }
+
+    fn push(&mut self, incoming: &[f32]) -> bool {
+        let free = self.max_samples.saturating_sub(self.samples.len());
+        if incoming.len() <= free {
+            self.samples.extend(incoming);
+            return true;
+        }
+
+        self.overflowed = true;
+        match self.policy {
+            OverflowPolicy::StopCapture => {
+                self.samples.extend(&incoming[..free]);
+                false
+            }
+            OverflowPolicy::DropNewest => {
+                self.samples.extend(&incoming[..free]);
+                true
+            }
+            OverflowPolicy::DropOldest => {
+                let incoming = &incoming[incoming.len().saturating_sub(self.max_samples)..];
+                let excess = (self.samples.len() + incoming.len()).saturating_sub(self.max_samples);
+                self.samples.drain(..excess);
+                self.samples.extend(incoming);
+                self.chunk_end = self.chunk_end.saturating_sub(excess);
+                true
+            }
+        }
+    }
+
+    fn clear(&mut self) {
+        self.samples.clear();
+        self.pre_roll.clear();
+        self.overflowed = false;
+    }
+
+    fn take(&mut self) -> Vec<f32> {
+        self.overflowed = false;
+        Vec::from(std::mem::take(&mut self.samples))
+    }
+}
+
+type SharedProcessor = Arc<Mutex<Option<Box<dyn SampleProcessor>>>>;
+
 pub struct PttCaptureService<B: AudioBackend> {
     action: String,
+    limits: CaptureLimits,
     audio: AudioCaptureService<B>,
-    buffer: Arc<Mutex<Vec<f32>>>,
+    buffer: Arc<Mutex<CaptureBuffer>>,
     capture_active: Arc<AtomicBool>,
     meter: Arc<Mutex<LevelMeter>>,
+    history: Arc<Mutex<LevelHistory>>,
+    gain: Arc<Mutex<GainStage>>,
+    noise_reduction: bool,
+    processor: SharedProcessor,
     level_sender: mpsc::Sender<LevelReading>,
     level_receiver: Option<mpsc::Receiver<LevelReading>>,
+    auto_stop_sender: mpsc::Sender<CaptureAutoStopped>,
+    auto_stop_receiver: Option<mpsc::Receiver<CaptureAutoStopped>>,
+    chunk_sender: mpsc::Sender<CaptureChunk>,
+    chunk_receiver: Option<mpsc::Receiver<CaptureChunk>>,
 }
 
 impl<B: AudioBackend> PttCaptureService<B> {
     pub fn new(backend: B, action: impl Into<String>) -> Self {
         let (level_sender, level_receiver) = mpsc::channel();
+        let (auto_stop_sender, auto_stop_receiver) = mpsc::channel();
+        let (chunk_sender, chunk_receiver) = mpsc::channel();
         Self {
             action: action.into(),
+            limits: CaptureLimits::default(),
             audio: AudioCaptureService::new(backend),
-            buffer: Arc::new(Mutex::new(Vec::new())),
+            buffer: Arc::new(Mutex::new(CaptureBuffer::default())),
             capture_active: Arc::new(AtomicBool::new(false)),
             meter: Arc::new(Mutex::new(LevelMeter::new())),
+            history: Arc::new(Mutex::new(LevelHistory::default())),
+            gain: Arc::new(Mutex::new(GainStage::default())),
+            noise_reduction: false,
+            processor: Arc::new(Mutex::new(None)),
             level_sender,
             level_receiver: Some(level_receiver),
+            auto_stop_sender,
+            auto_stop_receiver: Some(auto_stop_receiver),
+            chunk_sender,
+            chunk_receiver: Some(chunk_receiver),
+        }
+    }
+
+    pub fn limits(&self) -> CaptureLimits {
+        self.limits
+    }
+
+    pub fn set_limits(&mut self, limits: CaptureLimits) {
+        self.limits = limits;
+        self.refresh_stream_config();
+    }
+
+    fn refresh_stream_config(&self) {
+        let (sample_rate, channels) = self
+            .audio
+            .selected_device()
+            .map(|device| (device.sample_rate, device.channels))
+            .unwrap_or((
+                PTT_STREAM_REQUEST.sample_rate.unwrap_or(16_000),
+                PTT_STREAM_REQUEST.channels.unwrap_or(1),
+            ));
+        let per_second = sample_rate as f64 * channels.max(1) as f64;
+        let max_samples = (self.limits.max_duration.as_secs_f64() * per_second).ceil() as usize;
+        let pre_roll_samples = (self.limits.pre_roll.as_secs_f64() * per_second).ceil() as usize;
+        let frame = channels.max(1) as usize;
+        let frames = |duration: Duration| {
+            (duration.as_secs_f64() * per_second / frame as f64).ceil() as usize * frame
+        };
+        let chunk_samples = self.limits.chunking.and_then(|chunking| {
+            let length = frames(chunking.length);
+            (length > 0).then(|| (length, frames(chunking.overlap).min(length)))
+        });
+        if let Ok(mut buffer) = self.buffer.lock() {
+            buffer.max_samples = max_samples.max(1);
+            buffer.policy = self.limits.overflow_policy;
+            buffer.pre_roll_samples = pre_roll_samples;
+            buffer.chunk_samples = chunk_samples;
+            let excess = buffer.pre_roll.len().saturating_sub(pre_roll_samples);
+            buffer.pre_roll.drain(..excess);
+            buffer.vad = self.limits.silence_auto_stop.then(|| {
+                let config = VadConfig {
+                    threshold_dbfs: self.limits.silence_threshold_dbfs,
+                    hangover: self.limits.silence_duration,
+                    ..VadConfig::default()
+                };
+                VadDetector::new(config, sample_rate, channels)
+            });
+        }
+        if let Ok(mut meter) = self.meter.lock() {
+            meter.set_sample_rate(sample_rate, channels);
+        }
+        if let Ok(mut history) = self.history.lock() {
+            history.set_sample_rate(sample_rate, channels);
+        }
+        if let Ok(mut processor) = self.processor.lock() {
+            *processor = self.noise_reduction.then(|| {
+                Box::new(ProcessorChain::noise_reduction(sample_rate, channels))
+                    as Box<dyn SampleProcessor>
+            });
+        }
+    }
+
+    pub fn check_capacity(&self) -> Result<(), PttCaptureError> {
+        let buffer = self
+            .buffer
+            .lock()
+            .map_err(|_| PttCaptureError::BufferLockPoisoned)?;
+        if buffer.overflowed && buffer.policy == OverflowPolicy::StopCapture {
+            return Err(PttCaptureError::BufferFull);
+        }
+        Ok(())
+    }
+
+    pub fn noise_reduction(&self) -> bool {
+        self.noise_reduction
+    }
+
+    pub fn set_noise_reduction(&mut self, enabled: bool) {
+        if self.noise_reduction != enabled {
+            self.noise_reduction = enabled;
+            self.refresh_stream_config();
+        }
+    }
+
+    pub fn gain_mode(&self) -> GainMode {
+        self.gain.lock().map(|gain| gain.mode()).unwrap_or_default()
+    }
+
+    pub fn set_gain_mode(&mut self, mode: GainMode) {
+        if let Ok(mut gain) = self.gain.lock() {
+            if gain.mode() != mode {
+                gain.set_mode(mode);
+            }
         }
     }
 
@@ -66,26 +360,69 @@ impl<B: AudioBackend> PttCaptureService<B> {
                 .map_err(|_| PttCaptureError::MeterLockPoisoned)?;
             meter.reset();
         }
+        if let Ok(mut gain) = self.gain.lock() {
+            gain.reset();
+        }
 
+        self.audio.set_stream_request(PTT_STREAM_REQUEST);
         let buffer = Arc::clone(&self.buffer);
         let meter = Arc::clone(&self.meter);
+        let history = Arc::clone(&self.history);
+        let gain = Arc::clone(&self.gain);
+        let processor = Arc::clone(&self.processor);
         let capture_active = Arc::clone(&self.capture_active);
         let level_sender = self.level_sender.clone();
+        let auto_stop_sender = self.auto_stop_sender.clone();
+        let chunk_sender = self.chunk_sender.clone();
+        let action = self.action.clone();
 
         self.audio
             .start_with_callback(move |samples| {
+                let mut samples = samples.to_vec();
+                if let Ok(mut processor) = processor.lock() {
+                    if let Some(processor) = processor.as_mut() {
+                        processor.process(&mut samples);
+                    }
+                }
+                let samples = match gain.lock() {
+                    Ok(mut gain) => gain.process(&samples),
+                    Err(_) => samples,
+                };
+                let samples = samples.as_slice();
                 if let Ok(mut meter) = meter.lock() {
                     meter.update(samples);
                     let _ = level_sender.send(meter.reading());
                 }
 
-                if capture_active.load(Ordering::SeqCst) {
-                    if let Ok(mut buffer) = buffer.lock() {
-                        buffer.extend_from_slice(samples);
+                if let Ok(mut buffer) = buffer.lock() {
+                    let active = capture_active.load(Ordering::SeqCst);
+                    if active {
+                        if let Ok(mut history) = history.lock() {
+                            history.push(samples);
+                        }
+                    }
+                    if !active {
+                        buffer.push_pre_roll(samples);
+                    } else if !buffer.push(samples) {
+                        capture_active.store(false, Ordering::SeqCst);
+                        warn!("ptt capture buffer full; capture stopped");
+                    } else if let Some(silence) = buffer.detect_silence(samples) {
+                        capture_active.store(false, Ordering::SeqCst);
+                        info!("ptt capture auto-stopped after {silence:?} of silence");
+                        let _ = auto_stop_sender.send(CaptureAutoStopped {
+                            action: action.clone(),
+                            silence,
+                        });
+                    }
+                    if capture_active.load(Ordering::SeqCst) {
+                        while let Some(chunk) = buffer.next_chunk(&action) {
+                            let _ = chunk_sender.send(chunk);
+                        }
                     }
                 }
             })
             .map_err(PttCaptureError::from)?;
+        self.refresh_stream_config();
         info!("ptt capture started");
         Ok(())
     }
@@ -98,13 +435,34 @@ impl<B: AudioBackend> PttCaptureService<B> {
         Ok(())
     }
 
+    pub fn pause(&mut self) -> Result<(), PttCaptureError> {
+        self.capture_active.store(false, Ordering::SeqCst);
+        self.audio.pause().map_err(PttCaptureError::from)?;
+        info!("ptt capture paused");
+        Ok(())
+    }
+
+    pub fn resume(&mut self) -> Result<(), PttCaptureError> {
+        {
+            let mut buffer = self
+                .buffer
+                .lock()
+                .map_err(|_| PttCaptureError::BufferLockPoisoned)?;
+            buffer.clear();
+        }
+        self.audio.resume().map_err(PttCaptureError::from)?;
+        self.refresh_stream_config();
+        info!("ptt capture resumed");
+        Ok(())
+    }
+
     pub fn take_audio(&self) -> Result<Vec<f32>, PttCaptureError> {
         info!("ptt capture take audio");
         let mut buffer = self
             .buffer
             .lock()
             .map_err(|_| PttCaptureError::BufferLockPoisoned)?;
-        let data = std::mem::take(&mut *buffer);
+        let data = buffer.take();
         info!("ptt capture took {} samples", data.len());
         Ok(data)
     }
@@ -113,6 +471,18 @@ impl<B: AudioBackend> PttCaptureService<B> {
         self.level_receiver.take()
     }
 
+    pub fn auto_stop_feed(&mut self) -> Option<mpsc::Receiver<CaptureAutoStopped>> {
+        self.auto_stop_receiver.take()
+    }
+
+    pub fn chunk_feed(&mut self) -> Option<mpsc::Receiver<CaptureChunk>> {
+        self.chunk_receiver.take()
+    }
+
+    pub fn error_feed(&mut self) -> Option<mpsc::Receiver<AudioError>> {
+        self.audio.error_feed()
+    }
+
     pub fn level(&self) -> Result<LevelReading, PttCaptureError> {
         let meter = self
             .meter
@@ -121,6 +491,14 @@ impl<B: AudioBackend> PttCaptureService<B> {
         Ok(meter.reading())
     }
 
+    pub fn level_history(&self) -> Result<Vec<LevelReading>, PttCaptureError> {
+        let history = self
+            .history
+            .lock()
+            .map_err(|_| PttCaptureError::MeterLockPoisoned)?;
+        Ok(history.snapshot())
+    }
+
     pub fn handle_hotkey_action(
         &mut self,
         event: &HotkeyActionEvent,
@@ -132,12 +510,23 @@ impl<B: AudioBackend> PttCaptureService<B> {
         match event.state {
             HotkeyState::Pressed => {
                 info!("ptt capture hotkey pressed");
-                self.capture_active.store(true, Ordering::SeqCst);
                 let mut buffer = self
                     .buffer
                     .lock()
                     .map_err(|_| PttCaptureError::BufferLockPoisoned)?;
-                buffer.clear();
+                if let Ok(mut processor) = self.processor.lock() {
+                    if let Some(processor) = processor.as_mut() {
+                        processor.reset();
+                    }
+                }
+                buffer.begin_capture();
+                if let Ok(mut history) = self.history.lock() {
+                    history.clear();
+                    let (head, tail) = buffer.samples.as_slices();
+                    history.push(head);
+                    history.push(tail);
+                }
+                self.capture_active.store(true, Ordering::SeqCst);
             }
             HotkeyState::Released => {
                 info!("ptt capture hotkey released");
@@ -151,8 +540,14 @@ impl<B: AudioBackend> PttCaptureService<B> {
 
 #[cfg(test)]
 mod tests {
-    use super::{PttCaptureError, PttCaptureService};
-    use crate::audio::{AudioBackend, AudioDevice, AudioError, AudioStream};
+    use super::{
+        CaptureLimits, ChunkConfig, GainMode, OverflowPolicy, PttCaptureError, PttCaptureService,
+        PTT_STREAM_REQUEST,
+    };
+    use crate::audio::{
+        AudioBackend, AudioDevice, AudioError, AudioStream, SampleFormat, StreamFormat,
+        StreamRequest,
+    };
     use crate::hotkeys::{Hotkey, HotkeyActionEvent, HotkeyKey, HotkeyModifiers, HotkeyState};
     use std::sync::{
         atomic::{AtomicBool, Ordering},
@@ -182,6 +577,7 @@ mod tests {
 
     struct MockStream {
         controller: MockStreamController,
+        format: StreamFormat,
     }
 
     impl AudioStream for MockStream {
@@ -194,12 +590,17 @@ mod tests {
             self.controller.running.store(false, Ordering::SeqCst);
             Ok(())
         }
+
+        fn format(&self) -> StreamFormat {
+            self.format
+        }
     }
 
     #[derive(Clone)]
     struct MockAudioBackend {
         devices: Vec<AudioDevice>,
         controller: Arc<Mutex<Option<MockStreamController>>>,
+        last_request: Arc<Mutex<Option<StreamRequest>>>,
     }
 
     impl MockAudioBackend {
@@ -207,6 +608,7 @@ mod tests {
             Self {
                 devices,
                 controller: Arc::new(Mutex::new(None)),
+                last_request: Arc::new(Mutex::new(None)),
             }
         }
 
@@ -228,8 +630,10 @@ mod tests {
 
         fn build_input_stream(
             &self,
-            _device: &AudioDevice,
+            device: &AudioDevice,
+            request: &StreamRequest,
             on_samples: Box<dyn FnMut(&[f32]) + Send>,
+            _on_error: Box<dyn FnMut(AudioError) + Send>,
         ) -> Result<Self::Stream, AudioError> {
             let controller = MockStreamController {
                 running: Arc::new(AtomicBool::new(false)),
@@ -239,8 +643,14 @@ mod tests {
             if let Ok(mut stored) = self.controller.lock() {
                 *stored = Some(controller.clone());
             }
+            if let Ok(mut last_request) = self.last_request.lock() {
+                *last_request = Some(*request);
+            }
 
-            Ok(MockStream { controller })
+            Ok(MockStream {
+                controller,
+                format: StreamFormat::from(device),
+            })
         }
     }
 
@@ -262,6 +672,7 @@ mod tests {
             name: "Mock".to_string(),
             sample_rate: 48_000,
             channels: 2,
+            sample_format: SampleFormat::F32,
         }]);
         let controller_handle = backend.controller.clone();
         let mut service = PttCaptureService::new(backend, "ptt");
@@ -282,7 +693,7 @@ mod tests {
         controller.push_samples(&[0.25, -0.25, 0.5]);
 
         let captured = service.take_audio().expect("take audio");
-        assert_eq!(captured, vec![0.25, -0.25, 0.5]);
+        assert_eq!(captured, vec![0.1, 0.2, 0.25, -0.25, 0.5]);
 
         service
             .handle_hotkey_action(&hotkey_event(HotkeyState::Released))
@@ -298,6 +709,7 @@ mod tests {
             name: "Mock".to_string(),
             sample_rate: 48_000,
             channels: 2,
+            sample_format: SampleFormat::F32,
         }]);
         let controller_handle = backend.controller.clone();
         let mut service = PttCaptureService::new(backend, "ptt");
@@ -317,6 +729,368 @@ mod tests {
         assert!(reading.peak > 0.0);
     }
 
+    #[test]
+    fn ptt_capture_records_level_history_while_active() {
+        let backend = MockAudioBackend::new(vec![AudioDevice {
+            id: "0:Mock".to_string(),
+            name: "Mock".to_string(),
+            sample_rate: 1_000,
+            channels: 1,
+            sample_format: SampleFormat::F32,
+        }]);
+        let controller_handle = backend.controller.clone();
+        let mut service = PttCaptureService::new(backend, "ptt");
+        service.set_limits(CaptureLimits {
+            pre_roll: Duration::ZERO,
+            ..CaptureLimits::default()
+        });
+        service.start().expect("start capture");
+        let controller = controller_handle
+            .lock()
+            .ok()
+            .and_then(|value| value.clone())
+            .expect("controller ready");
+
+        controller.push_samples(&[0.9; 100]);
+        service
+            .handle_hotkey_action(&hotkey_event(HotkeyState::Pressed))
+            .expect("activate capture");
+        controller.push_samples(&[0.5; 120]);
+        service
+            .handle_hotkey_action(&hotkey_event(HotkeyState::Released))
+            .expect("deactivate capture");
+        controller.push_samples(&[0.9; 100]);
+
+        let history = service.level_history().expect("level history");
+        assert_eq!(history.len(), 2);
+        assert!(history.iter().all(|reading| reading.peak == 0.5));
+
+        service
+            .handle_hotkey_action(&hotkey_event(HotkeyState::Pressed))
+            .expect("activate capture");
+        assert!(service.level_history().expect("level history").is_empty());
+    }
+
+    #[test]
+    fn ptt_capture_requests_speech_stream_format() {
+        let backend = MockAudioBackend::new(vec![AudioDevice {
+            id: "0:Mock".to_string(),
+            name: "Mock".to_string(),
+            sample_rate: 48_000,
+            channels: 2,
+            sample_format: SampleFormat::F32,
+        }]);
+        let last_request = backend.last_request.clone();
+        let mut service = PttCaptureService::new(backend, "ptt");
+
+        service.start().expect("start capture");
+
+        assert_eq!(
+            *last_request.lock().expect("lock"),
+            Some(PTT_STREAM_REQUEST)
+        );
+        let selected = service.audio().selected_device().expect("device selected");
+        assert_eq!((selected.sample_rate, selected.channels), (48_000, 2));
+    }
+
+    #[test]
+    fn ptt_capture_keeps_configured_limits() {
+        let mut service = PttCaptureService::new(MockAudioBackend::new(Vec::new()), "ptt");
+        assert_eq!(service.limits(), CaptureLimits::default());
+
+        let limits = CaptureLimits {
+            max_duration: Duration::from_secs(30),
+            min_duration: Duration::from_millis(150),
+            silence_auto_stop: true,
+            silence_threshold_dbfs: -50.0,
+            silence_duration: Duration::from_millis(800),
+            overflow_policy: OverflowPolicy::DropOldest,
+            pre_roll: Duration::from_millis(250),
+            chunking: Some(ChunkConfig::default()),
+        };
+        service.set_limits(limits);
+
+        assert_eq!(service.limits(), limits);
+    }
+
+    fn overflow_capture(policy: OverflowPolicy) -> PttCaptureService<MockAudioBackend> {
+        let backend = MockAudioBackend::new(vec![AudioDevice {
+            id: "0:Mock".to_string(),
+            name: "Mock".to_string(),
+            sample_rate: 4_000,
+            channels: 1,
+            sample_format: SampleFormat::F32,
+        }]);
+        let controller_handle = backend.controller.clone();
+        let mut service = PttCaptureService::new(backend, "ptt");
+        service.set_limits(CaptureLimits {
+            max_duration: Duration::from_millis(1),
+            overflow_policy: policy,
+            ..CaptureLimits::default()
+        });
+        service.start().expect("start capture");
+        service
+            .handle_hotkey_action(&hotkey_event(HotkeyState::Pressed))
+            .expect("activate capture");
+
+        let controller = controller_handle
+            .lock()
+            .ok()
+            .and_then(|value| value.clone())
+            .expect("controller ready");
+        controller.push_samples(&[1.0, 2.0, 3.0]);
+        controller.push_samples(&[4.0, 5.0, 6.0]);
+        controller.push_samples(&[7.0]);
+        service
+    }
+
+    #[test]
+    fn ptt_capture_stops_when_buffer_fills() {
+        let service = overflow_capture(OverflowPolicy::StopCapture);
+
+        assert!(matches!(
+            service.check_capacity(),
+            Err(PttCaptureError::BufferFull)
+        ));
+        assert_eq!(
+            service.take_audio().expect("take audio"),
+            vec![1.0, 2.0, 3.0, 4.0]
+        );
+        assert!(service.check_capacity().is_ok());
+    }
+
+    #[test]
+    fn ptt_capture_drops_oldest_samples_when_full() {
+        let service = overflow_capture(OverflowPolicy::DropOldest);
+
+        assert!(service.check_capacity().is_ok());
+        assert_eq!(
+            service.take_audio().expect("take audio"),
+            vec![4.0, 5.0, 6.0, 7.0]
+        );
+    }
+
+    #[test]
+    fn ptt_capture_drops_newest_samples_when_full() {
+        let service = overflow_capture(OverflowPolicy::DropNewest);
+
+        assert!(service.check_capacity().is_ok());
+        assert_eq!(
+            service.take_audio().expect("take audio"),
+            vec![1.0, 2.0, 3.0, 4.0]
+        );
+    }
+
+    #[test]
+    fn ptt_capture_prepends_pre_roll_window() {
+        let backend = MockAudioBackend::new(vec![AudioDevice {
+            id: "0:Mock".to_string(),
+            name: "Mock".to_string(),
+            sample_rate: 4_000,
+            channels: 1,
+            sample_format: SampleFormat::F32,
+        }]);
+        let controller_handle = backend.controller.clone();
+        let mut service = PttCaptureService::new(backend, "ptt");
+        service.set_limits(CaptureLimits {
+            pre_roll: Duration::from_millis(1),
+            ..CaptureLimits::default()
+        });
+        service.start().expect("start capture");
+        let controller = controller_handle
+            .lock()
+            .ok()
+            .and_then(|value| value.clone())
+            .expect("controller ready");
+
+        controller.push_samples(&[1.0, 2.0, 3.0]);
+        controller.push_samples(&[4.0, 5.0, 6.0]);
+        service
+            .handle_hotkey_action(&hotkey_event(HotkeyState::Pressed))
+            .expect("activate capture");
+        controller.push_samples(&[7.0]);
+
+        assert_eq!(
+            service.take_audio().expect("take audio"),
+            vec![3.0, 4.0, 5.0, 6.0, 7.0]
+        );
+
+        service
+            .handle_hotkey_action(&hotkey_event(HotkeyState::Released))
+            .expect("deactivate capture");
+        controller.push_samples(&[8.0]);
+        service
+            .handle_hotkey_action(&hotkey_event(HotkeyState::Pressed))
+            .expect("activate capture");
+
+        assert_eq!(service.take_audio().expect("take audio"), vec![8.0]);
+    }
+
+    #[test]
+    fn ptt_capture_without_pre_roll_starts_empty() {
+        let backend = MockAudioBackend::new(vec![AudioDevice {
+            id: "0:Mock".to_string(),
+            name: "Mock".to_string(),
+            sample_rate: 48_000,
+            channels: 2,
+            sample_format: SampleFormat::F32,
+        }]);
+        let controller_handle = backend.controller.clone();
+        let mut service = PttCaptureService::new(backend, "ptt");
+        service.set_limits(CaptureLimits {
+            pre_roll: Duration::ZERO,
+            ..CaptureLimits::default()
+        });
+        service.start().expect("start capture");
+        let controller = controller_handle
+            .lock()
+            .ok()
+            .and_then(|value| value.clone())
+            .expect("controller ready");
+
+        controller.push_samples(&[0.1, 0.2]);
+        service
+            .handle_hotkey_action(&hotkey_event(HotkeyState::Pressed))
+            .expect("activate capture");
+        controller.push_samples(&[0.3]);
+
+        assert_eq!(service.take_audio().expect("take audio"), vec![0.3]);
+    }
+
+    fn silence_capture(
+        silence_auto_stop: bool,
+    ) -> (PttCaptureService<MockAudioBackend>, MockStreamController) {
+        let backend = MockAudioBackend::new(vec![AudioDevice {
+            id: "0:Mock".to_string(),
+            name: "Mock".to_string(),
+            sample_rate: 1_000,
+            channels: 1,
+            sample_format: SampleFormat::F32,
+        }]);
+        let controller_handle = backend.controller.clone();
+        let mut service = PttCaptureService::new(backend, "ptt");
+        service.set_limits(CaptureLimits {
+            silence_auto_stop,
+            silence_duration: Duration::from_millis(200),
+            ..CaptureLimits::default()
+        });
+        service.start().expect("start capture");
+        service
+            .handle_hotkey_action(&hotkey_event(HotkeyState::Pressed))
+            .expect("activate capture");
+        let controller = controller_handle
+            .lock()
+            .ok()
+            .and_then(|value| value.clone())
+            .expect("controller ready");
+        (service, controller)
+    }
+
+    #[test]
+    fn ptt_capture_auto_stops_on_trailing_silence() {
+        let (mut service, controller) = silence_capture(true);
+        let receiver = service.auto_stop_feed().expect("auto stop feed");
+
+        controller.push_samples(&[0.4; 1_000]);
+        assert!(receiver.try_recv().is_err());
+        controller.push_samples(&[0.0; 1_000]);
+
+        let event = receiver
+            .recv_timeout(Duration::from_millis(50))
+            .expect("auto stop event");
+        assert_eq!(event.action, "ptt");
+        assert!(event.silence >= Duration::from_millis(200));
+
+        controller.push_samples(&[0.4; 10]);
+        let captured = service.take_audio().expect("take audio");
+        assert!(captured.len() < 2_000);
+        assert!(captured[..1_000].iter().all(|sample| *sample == 0.4));
+    }
+
+    #[test]
+    fn ptt_capture_keeps_silence_when_auto_stop_disabled() {
+        let (mut service, controller) = silence_capture(false);
+        let receiver = service.auto_stop_feed().expect("auto stop feed");
+
+        controller.push_samples(&[0.4; 1_000]);
+        controller.push_samples(&[0.0; 1_000]);
+
+        assert!(receiver.try_recv().is_err());
+        assert_eq!(service.take_audio().expect("take audio").len(), 2_000);
+    }
+
+    #[test]
+    fn ptt_capture_applies_input_gain_before_meter_and_buffer() {
+        let backend = MockAudioBackend::new(vec![AudioDevice {
+            id: "0:Mock".to_string(),
+            name: "Mock".to_string(),
+            sample_rate: 48_000,
+            channels: 2,
+            sample_format: SampleFormat::F32,
+        }]);
+        let controller_handle = backend.controller.clone();
+        let mut service = PttCaptureService::new(backend, "ptt");
+        let receiver = service.level_feed().expect("level feed");
+        service.set_gain_mode(GainMode::Fixed { gain_db: 12.0 });
+        service.start().expect("start capture");
+        service
+            .handle_hotkey_action(&hotkey_event(HotkeyState::Pressed))
+            .expect("activate capture");
+        let controller = controller_handle
+            .lock()
+            .ok()
+            .and_then(|value| value.clone())
+            .expect("controller ready");
+
+        controller.push_samples(&[0.1, -0.5]);
+
+        let reading = receiver
+            .recv_timeout(Duration::from_millis(50))
+            .expect("level reading");
+        assert!(reading.clipped);
+        assert_eq!(reading.peak, 1.0);
+        let captured = service.take_audio().expect("take audio");
+        assert!((captured[0] - 0.398).abs() < 1e-3);
+        assert_eq!(captured[1], -1.0);
+        assert_eq!(service.gain_mode(), GainMode::Fixed { gain_db: 12.0 });
+    }
+
+    #[test]
+    fn ptt_capture_filters_samples_when_noise_reduction_enabled() {
+        let backend = MockAudioBackend::new(vec![AudioDevice {
+            id: "0:Mock".to_string(),
+            name: "Mock".to_string(),
+            sample_rate: 16_000,
+            channels: 1,
+            sample_format: SampleFormat::F32,
+        }]);
+        let controller_handle = backend.controller.clone();
+        let mut service = PttCaptureService::new(backend, "ptt");
+        service.set_noise_reduction(true);
+        service.start().expect("start capture");
+        service
+            .handle_hotkey_action(&hotkey_event(HotkeyState::Pressed))
+            .expect("activate capture");
+        let controller = controller_handle
+            .lock()
+            .ok()
+            .and_then(|value| value.clone())
+            .expect("controller ready");
+
+        controller.push_samples(&[0.5; 16_000]);
+
+        let captured = service.take_audio().expect("take audio");
+        assert_eq!(captured.len(), 16_000);
+        assert!(captured[8_000..].iter().all(|sample| sample.abs() < 0.01));
+
+        service.set_noise_reduction(false);
+        service
+            .handle_hotkey_action(&hotkey_event(HotkeyState::Pressed))
+            .expect("activate capture");
+        controller.push_samples(&[0.5; 4]);
+        assert_eq!(service.take_audio().expect("take audio"), vec![0.5; 4]);
+    }
+
     #[test]
     fn ptt_capture_ignores_unrelated_actions() {
         let backend = MockAudioBackend::new(vec![AudioDevice {
@@ -324,6 +1098,7 @@ mod tests {
             name: "Mock".to_string(),
             sample_rate: 48_000,
             channels: 2,
+            sample_format: SampleFormat::F32,
         }]);
         let mut service = PttCaptureService::new(backend, "ptt");
         service.start().expect("start capture");
@@ -348,6 +1123,7 @@ mod tests {
             name: "Mock".to_string(),
             sample_rate: 48_000,
             channels: 2,
+            sample_format: SampleFormat::F32,
         }]);
         let mut service = PttCaptureService::new(backend, "ptt");
 
@@ -368,6 +1144,7 @@ mod tests {
             name: "Mock".to_string(),
             sample_rate: 48_000,
             channels: 2,
+            sample_format: SampleFormat::F32,
         }]);
         let controller_handle = backend.controller.clone();
         let mut service = PttCaptureService::new(backend, "ptt");
@@ -383,4 +1160,49 @@ mod tests {
             .expect("controller ready");
         controller.push_samples(&[0.5]);
     }
+
+    #[test]
+    fn ptt_capture_emits_overlapping_chunks() {
+        let backend = MockAudioBackend::new(vec![AudioDevice {
+            id: "0:Mock".to_string(),
+            name: "Mock".to_string(),
+            sample_rate: 1_000,
+            channels: 1,
+            sample_format: SampleFormat::F32,
+        }]);
+        let controller_handle = backend.controller.clone();
+        let mut service = PttCaptureService::new(backend, "ptt");
+        service.set_limits(CaptureLimits {
+            chunking: Some(ChunkConfig {
+                length: Duration::from_millis(100),
+                overlap: Duration::from_millis(20),
+            }),
+            ..CaptureLimits::default()
+        });
+        let receiver = service.chunk_feed().expect("chunk feed");
+        service.start().expect("start capture");
+        service
+            .handle_hotkey_action(&hotkey_event(HotkeyState::Pressed))
+            .expect("activate capture");
+        let controller = controller_handle
+            .lock()
+            .ok()
+            .and_then(|value| value.clone())
+            .expect("controller ready");
+
+        let samples: Vec<f32> = (0..250).map(|index| index as f32 / 1_000.0).collect();
+        for block in samples.chunks(30) {
+            controller.push_samples(block);
+        }
+
+        let chunks: Vec<_> = receiver.try_iter().collect();
+        assert_eq!(chunks.len(), 2);
+        assert_eq!(chunks[0].index, 0);
+        assert_eq!(chunks[0].overlap, 0);
+        assert_eq!(chunks[0].samples, samples[..100].to_vec());
+        assert_eq!(chunks[1].index, 1);
+        assert_eq!(chunks[1].overlap, 20);
+        assert_eq!(chunks[1].samples, samples[80..200].to_vec());
+        assert_eq!(service.take_audio().expect("take audio"), samples);
+    }
 }
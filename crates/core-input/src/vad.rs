@@ -0,0 +1,194 @@
+use std::collections::VecDeque;
+use std::time::Duration;
+
+pub const VAD_MIN_CAPTURE: Duration = Duration::from_secs(1);
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct VadConfig {
+    pub threshold_dbfs: f32,
+    pub window: Duration,
+    pub hangover: Duration,
+}
+
+impl Default for VadConfig {
+    fn default() -> Self {
+        Self {
+            threshold_dbfs: -45.0,
+            window: Duration::from_millis(30),
+            hangover: Duration::from_millis(1500),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct VadDetector {
+    config: VadConfig,
+    samples_per_second: f64,
+    threshold_rms: f64,
+    window_len: usize,
+    hangover_len: usize,
+    min_capture_len: usize,
+    window: VecDeque<f64>,
+    sum: f64,
+    elapsed: usize,
+    silent_run: usize,
+}
+
+impl VadDetector {
+    pub fn new(config: VadConfig, sample_rate: u32, channels: u16) -> Self {
+        let samples_per_second = (sample_rate.max(1) as f64) * (channels.max(1) as f64);
+        let samples_for =
+            |duration: Duration| (duration.as_secs_f64() * samples_per_second).ceil() as usize;
+        Self {
+            config,
+            samples_per_second,
+            threshold_rms: 10_f64.powf(config.threshold_dbfs as f64 / 20.0),
+            window_len: samples_for(config.window).max(1),
+            hangover_len: samples_for(config.hangover).max(1),
+            min_capture_len: samples_for(VAD_MIN_CAPTURE),
+            window: VecDeque::new(),
+            sum: 0.0,
+            elapsed: 0,
+            silent_run: 0,
+        }
+    }
+
+    pub fn config(&self) -> VadConfig {
+        self.config
+    }
+
+    pub fn push(&mut self, samples: &[f32]) -> bool {
+        for &sample in samples {
+            let sample = if sample.is_finite() { sample } else { 0.0 };
+            let square = (sample as f64) * (sample as f64);
+            self.window.push_back(square);
+            self.sum += square;
+            if self.window.len() > self.window_len {
+                if let Some(oldest) = self.window.pop_front() {
+                    self.sum -= oldest;
+                }
+            }
+            self.elapsed += 1;
+            if self.is_silent() {
+                self.silent_run += 1;
+            } else {
+                self.silent_run = 0;
+            }
+        }
+        self.should_stop()
+    }
+
+    pub fn is_silent(&self) -> bool {
+        if self.window.is_empty() {
+            return true;
+        }
+        let rms = (self.sum.max(0.0) / self.window.len() as f64).sqrt();
+        rms < self.threshold_rms
+    }
+
+    pub fn should_stop(&self) -> bool {
+        self.elapsed >= self.min_capture_len && self.silent_run >= self.hangover_len
+    }
+
+    pub fn window_samples(&self) -> usize {
+        self.window_len
+    }
+
+    pub fn silent_samples(&self) -> usize {
+        self.silent_run
+    }
+
+    pub fn silence(&self) -> Duration {
+        Duration::from_secs_f64(self.silent_run as f64 / self.samples_per_second)
+    }
+
+    pub fn reset(&mut self) {
+        self.window.clear();
+        self.sum = 0.0;
+        self.elapsed = 0;
+        self.silent_run = 0;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{VadConfig, VadDetector};
+    use std::time::Duration;
+
+    const RATE: u32 = 16_000;
+
+    fn speech(ms: u32) -> Vec<f32> {
+        (0..RATE / 1000 * ms)
+            .map(|index| if index % 2 == 0 { 0.3 } else { -0.3 })
+            .collect()
+    }
+
+    fn silence(ms: u32) -> Vec<f32> {
+        vec![0.0005; (RATE / 1000 * ms) as usize]
+    }
+
+    #[test]
+    fn stops_after_silence_tail_exceeds_hangover() {
+        let mut vad = VadDetector::new(VadConfig::default(), RATE, 1);
+
+        assert!(!vad.push(&speech(1_200)));
+        assert!(!vad.is_silent());
+        assert!(!vad.push(&silence(1_400)));
+        assert!(vad.push(&silence(200)));
+        assert!(vad.silence() >= Duration::from_millis(1_500));
+    }
+
+    #[test]
+    fn speech_resets_the_silence_run() {
+        let mut vad = VadDetector::new(VadConfig::default(), RATE, 1);
+
+        assert!(!vad.push(&speech(1_000)));
+        assert!(!vad.push(&silence(1_000)));
+        assert!(!vad.push(&speech(100)));
+        assert_eq!(vad.silent_samples(), 0);
+        assert!(!vad.push(&silence(1_000)));
+        assert!(vad.push(&silence(600)));
+    }
+
+    #[test]
+    fn never_stops_within_first_second() {
+        let config = VadConfig {
+            hangover: Duration::from_millis(200),
+            ..VadConfig::default()
+        };
+        let mut vad = VadDetector::new(config, RATE, 1);
+
+        assert!(!vad.push(&silence(999)));
+        assert!(vad.silence() >= Duration::from_millis(200));
+        assert!(vad.push(&silence(1)));
+    }
+
+    #[test]
+    fn durations_account_for_interleaved_channels() {
+        let config = VadConfig {
+            hangover: Duration::from_millis(500),
+            ..VadConfig::default()
+        };
+        let mut vad = VadDetector::new(config, RATE, 2);
+
+        assert!(!vad.push(&silence(1_000)));
+        assert!(!vad.push(&silence(999)));
+        assert!(vad.push(&silence(1)));
+    }
+
+    #[test]
+    fn threshold_is_compared_in_dbfs() {
+        let config = VadConfig {
+            threshold_dbfs: -6.0,
+            ..VadConfig::default()
+        };
+        let mut vad = VadDetector::new(config, RATE, 1);
+
+        vad.push(&speech(50));
+        assert!(vad.is_silent());
+
+        vad.reset();
+        vad.push(&[0.9; 1_000]);
+        assert!(!vad.is_silent());
+    }
+}
@@ -0,0 +1,190 @@
+use std::io::ErrorKind;
+use std::process::Command;
+
+use shared_types::PasteShortcut;
+
+use crate::{wtype_paste_args, xdotool_paste_args, Clipboard, ClipboardError};
+
+pub struct ArboardClipboard {
+    inner: arboard::Clipboard,
+    paste_shortcut: PasteShortcut,
+}
+
+impl ArboardClipboard {
+    pub fn new() -> Result<Self, ClipboardError> {
+        let inner = arboard::Clipboard::new().map_err(map_arboard_error)?;
+        Ok(Self {
+            inner,
+            paste_shortcut: PasteShortcut::default(),
+        })
+    }
+
+    pub fn with_paste_shortcut(mut self, shortcut: PasteShortcut) -> Self {
+        self.paste_shortcut = shortcut;
+        self
+    }
+}
+
+impl Clipboard for ArboardClipboard {
+    fn get_text(&mut self) -> Result<Option<String>, ClipboardError> {
+        match self.inner.get_text() {
+            Ok(text) => Ok(Some(text)),
+            Err(arboard::Error::ContentNotAvailable) => Ok(None),
+            Err(err) => Err(map_arboard_error(err)),
+        }
+    }
+
+    fn set_text(&mut self, text: &str) -> Result<(), ClipboardError> {
+        self.inner
+            .set_text(text.to_string())
+            .map_err(map_arboard_error)
+    }
+
+    fn clear(&mut self) -> Result<(), ClipboardError> {
+        self.inner.clear().map_err(map_arboard_error)
+    }
+
+    #[cfg(target_os = "linux")]
+    fn get_primary(&mut self) -> Result<Option<String>, ClipboardError> {
+        use arboard::{GetExtLinux, LinuxClipboardKind};
+
+        match self
+            .inner
+            .get()
+            .clipboard(LinuxClipboardKind::Primary)
+            .text()
+        {
+            Ok(text) => Ok(Some(text)),
+            Err(arboard::Error::ContentNotAvailable) => Ok(None),
+            Err(err) => Err(map_arboard_error(err)),
+        }
+    }
+
+    #[cfg(target_os = "linux")]
+    fn set_primary(&mut self, text: &str) -> Result<(), ClipboardError> {
+        use arboard::{LinuxClipboardKind, SetExtLinux};
+
+        self.inner
+            .set()
+            .clipboard(LinuxClipboardKind::Primary)
+            .text(text.to_string())
+            .map_err(map_arboard_error)
+    }
+
+    #[cfg(target_os = "linux")]
+    fn clear_primary(&mut self) -> Result<(), ClipboardError> {
+        use arboard::{ClearExtLinux, LinuxClipboardKind};
+
+        self.inner
+            .clear_with()
+            .clipboard(LinuxClipboardKind::Primary)
+            .map_err(map_arboard_error)
+    }
+
+    fn set_html(&mut self, html: &str, alt_text: &str) -> Result<(), ClipboardError> {
+        self.inner
+            .set_html(html, Some(alt_text))
+            .map_err(map_arboard_error)
+    }
+
+    fn paste(&mut self) -> Result<(), ClipboardError> {
+        let wayland = std::env::var_os("WAYLAND_DISPLAY").is_some();
+        for (cmd, args) in paste_command_candidates(wayland, self.paste_shortcut) {
+            match Command::new(cmd).args(&args).output() {
+                Ok(output) if output.status.success() => return Ok(()),
+                Ok(output) => {
+                    let stderr = String::from_utf8_lossy(&output.stderr);
+                    return Err(ClipboardError::new(format!(
+                        "command `{}` exited with {}: {}",
+                        cmd,
+                        output.status,
+                        stderr.trim()
+                    )));
+                }
+                Err(err) if err.kind() == ErrorKind::NotFound => continue,
+                Err(err) => {
+                    return Err(ClipboardError::new(format!(
+                        "failed to run `{}`: {}",
+                        cmd, err
+                    )))
+                }
+            }
+        }
+
+        Err(ClipboardError::new(
+            "missing paste helper: install xdotool (X11) or wtype (Wayland)",
+        ))
+    }
+}
+
+fn paste_command_candidates(
+    wayland: bool,
+    shortcut: PasteShortcut,
+) -> Vec<(&'static str, Vec<String>)> {
+    let wtype = ("wtype", wtype_paste_args(shortcut));
+    let xdotool = ("xdotool", xdotool_paste_args(shortcut));
+    if wayland {
+        vec![wtype, xdotool]
+    } else {
+        vec![xdotool, wtype]
+    }
+}
+
+fn map_arboard_error(err: arboard::Error) -> ClipboardError {
+    match err {
+        arboard::Error::ContentNotAvailable => ClipboardError::new("clipboard is empty"),
+        arboard::Error::ClipboardNotSupported => {
+            ClipboardError::new("clipboard is not supported in this environment")
+        }
+        arboard::Error::ClipboardOccupied => {
+            ClipboardError::new("clipboard is occupied by another application")
+        }
+        arboard::Error::ConversionFailure => {
+            ClipboardError::new("clipboard contents could not be converted to text")
+        }
+        arboard::Error::Unknown { description } => ClipboardError::new(description),
+        other => ClipboardError::new(other.to_string()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn prefers_wtype_on_wayland() {
+        let wayland = paste_command_candidates(true, PasteShortcut::CtrlV);
+        let x11 = paste_command_candidates(false, PasteShortcut::CtrlV);
+
+        assert_eq!(wayland.first().map(|(cmd, _)| *cmd), Some("wtype"));
+        assert_eq!(x11.first().map(|(cmd, _)| *cmd), Some("xdotool"));
+    }
+
+    #[test]
+    fn paste_commands_use_the_requested_combo() {
+        let candidates = paste_command_candidates(false, PasteShortcut::CtrlShiftV);
+
+        assert_eq!(
+            candidates[0],
+            ("xdotool", xdotool_paste_args(PasteShortcut::CtrlShiftV))
+        );
+        assert_eq!(
+            candidates[1],
+            ("wtype", wtype_paste_args(PasteShortcut::CtrlShiftV))
+        );
+    }
+
+    #[test]
+    fn maps_arboard_errors_to_messages() {
+        assert_eq!(
+            map_arboard_error(arboard::Error::ClipboardOccupied),
+            ClipboardError::new("clipboard is occupied by another application")
+        );
+        assert_eq!(
+            map_arboard_error(arboard::Error::Unknown {
+                description: "boom".to_string(),
+            }),
+            ClipboardError::new("boom")
+        );
+    }
+}
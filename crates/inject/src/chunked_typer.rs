@@ -0,0 +1,190 @@
+use std::time::Duration;
+
+use crate::{Sleeper, ThreadSleeper, Typer, TypingError};
+
+pub const DEFAULT_CHUNK_SIZE: usize = 200;
+pub const DEFAULT_CHUNK_PAUSE: Duration = Duration::from_millis(25);
+
+pub struct ChunkedTyper<T> {
+    inner: T,
+    chunk_size: usize,
+    pause: Duration,
+    sleeper: Box<dyn Sleeper + Send>,
+}
+
+impl<T: Typer> ChunkedTyper<T> {
+    pub fn new(inner: T) -> Self {
+        Self {
+            inner,
+            chunk_size: DEFAULT_CHUNK_SIZE,
+            pause: DEFAULT_CHUNK_PAUSE,
+            sleeper: Box::new(ThreadSleeper),
+        }
+    }
+
+    pub fn with_chunk_size(mut self, chunk_size: usize) -> Self {
+        self.chunk_size = chunk_size.max(1);
+        self
+    }
+
+    pub fn with_pause(mut self, pause: Duration) -> Self {
+        self.pause = pause;
+        self
+    }
+
+    pub fn with_sleeper(mut self, sleeper: impl Sleeper + Send + 'static) -> Self {
+        self.sleeper = Box::new(sleeper);
+        self
+    }
+
+    pub fn into_inner(self) -> T {
+        self.inner
+    }
+}
+
+impl<T: Typer> Typer for ChunkedTyper<T> {
+    fn type_text(&mut self, text: &str) -> Result<(), TypingError> {
+        for (index, chunk) in split_chunks(text, self.chunk_size).into_iter().enumerate() {
+            if index > 0 && !self.pause.is_zero() {
+                self.sleeper.sleep(self.pause);
+            }
+            if self.inner.type_text(chunk).is_err() {
+                self.inner.type_text(chunk)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+fn split_chunks(text: &str, max_chars: usize) -> Vec<&str> {
+    let mut chunks = Vec::new();
+    let mut rest = text;
+
+    while !rest.is_empty() {
+        let limit = match rest.char_indices().nth(max_chars) {
+            Some((index, _)) => index,
+            None => {
+                chunks.push(rest);
+                break;
+            }
+        };
+
+        let split = rest[..limit]
+            .char_indices()
+            .rev()
+            .find(|(_, ch)| ch.is_whitespace())
+            .map(|(index, ch)| index + ch.len_utf8())
+            .unwrap_or(limit);
+
+        let (chunk, tail) = rest.split_at(split);
+        chunks.push(chunk);
+        rest = tail;
+    }
+
+    chunks
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::{Arc, Mutex};
+
+    #[derive(Default)]
+    struct MockTyper {
+        typed: Vec<String>,
+        failures_left: usize,
+    }
+
+    impl Typer for MockTyper {
+        fn type_text(&mut self, text: &str) -> Result<(), TypingError> {
+            if self.failures_left > 0 {
+                self.failures_left -= 1;
+                return Err(TypingError::new("typing failed"));
+            }
+            self.typed.push(text.to_string());
+            Ok(())
+        }
+    }
+
+    #[derive(Clone, Default)]
+    struct MockSleeper {
+        slept: Arc<Mutex<Vec<Duration>>>,
+    }
+
+    impl Sleeper for MockSleeper {
+        fn sleep(&mut self, duration: Duration) {
+            self.slept.lock().unwrap().push(duration);
+        }
+    }
+
+    #[test]
+    fn splits_on_whitespace_and_preserves_text() {
+        let text = "one two  three\nfour   five";
+        let chunks = split_chunks(text, 8);
+
+        assert_eq!(chunks, vec!["one two ", " three\n", "four   ", "five"]);
+        assert_eq!(chunks.concat(), text);
+        assert!(chunks.iter().all(|chunk| chunk.chars().count() <= 8));
+    }
+
+    #[test]
+    fn splits_long_words_on_char_boundaries() {
+        let text = "héllø wörld 日本語テキスト";
+        for size in 1..8 {
+            let chunks = split_chunks(text, size);
+            assert_eq!(chunks.concat(), text);
+            assert!(chunks
+                .iter()
+                .all(|chunk| !chunk.is_empty() && chunk.chars().count() <= size));
+        }
+    }
+
+    #[test]
+    fn pauses_between_chunks() {
+        let sleeper = MockSleeper::default();
+        let mut typer = ChunkedTyper::new(MockTyper::default())
+            .with_chunk_size(4)
+            .with_pause(Duration::from_millis(10))
+            .with_sleeper(sleeper.clone());
+
+        typer.type_text("ab cd ef").unwrap();
+        let inner = typer.into_inner();
+
+        assert_eq!(inner.typed, vec!["ab ", "cd ", "ef"]);
+        assert_eq!(
+            *sleeper.slept.lock().unwrap(),
+            vec![Duration::from_millis(10), Duration::from_millis(10)]
+        );
+    }
+
+    #[test]
+    fn retries_failed_chunk_once() {
+        let inner = MockTyper {
+            failures_left: 1,
+            ..Default::default()
+        };
+        let mut typer = ChunkedTyper::new(inner)
+            .with_chunk_size(3)
+            .with_sleeper(MockSleeper::default());
+
+        typer.type_text("abcdef").unwrap();
+
+        assert_eq!(typer.into_inner().typed, vec!["abc", "def"]);
+    }
+
+    #[test]
+    fn surfaces_error_after_second_failure() {
+        let inner = MockTyper {
+            failures_left: 2,
+            ..Default::default()
+        };
+        let mut typer = ChunkedTyper::new(inner)
+            .with_chunk_size(3)
+            .with_sleeper(MockSleeper::default());
+
+        let result = typer.type_text("abcdef");
+
+        assert_eq!(result, Err(TypingError::new("typing failed")));
+        assert!(typer.into_inner().typed.is_empty());
+    }
+}
@@ -0,0 +1,198 @@
+use std::fmt;
+use std::io::{self, ErrorKind, Write};
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+
+use crate::ClipboardError;
+
+const YDOTOOL_SOCKET_NAME: &str = ".ydotool_socket";
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CommandOutput {
+    pub success: bool,
+    pub status: String,
+    pub stdout: String,
+    pub stderr: String,
+}
+
+pub trait CommandRunner {
+    fn run(
+        &mut self,
+        program: &str,
+        args: &[String],
+        stdin: Option<&str>,
+    ) -> io::Result<CommandOutput>;
+
+    fn path_exists(&self, path: &Path) -> bool {
+        path.exists()
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SystemCommandRunner;
+
+impl CommandRunner for SystemCommandRunner {
+    fn run(
+        &mut self,
+        program: &str,
+        args: &[String],
+        stdin: Option<&str>,
+    ) -> io::Result<CommandOutput> {
+        let Some(input) = stdin else {
+            let output = Command::new(program).args(args).output()?;
+            return Ok(CommandOutput {
+                success: output.status.success(),
+                status: output.status.to_string(),
+                stdout: String::from_utf8_lossy(&output.stdout).into_owned(),
+                stderr: String::from_utf8_lossy(&output.stderr).trim().to_string(),
+            });
+        };
+        // wl-copy forks a daemon that keeps inherited pipes open until the
+        // selection changes, so only stdin is piped here.
+        let mut child = Command::new(program)
+            .args(args)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .spawn()?;
+        // Close the pipe and reap the child before reporting a write error
+        // such as EPIPE, so a failed write never leaves a zombie behind.
+        let written = match child.stdin.take() {
+            Some(mut pipe) => pipe.write_all(input.as_bytes()),
+            None => Ok(()),
+        };
+        let status = child.wait()?;
+        written?;
+        Ok(CommandOutput {
+            success: status.success(),
+            status: status.to_string(),
+            stdout: String::new(),
+            stderr: String::new(),
+        })
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DisplayServer {
+    X11,
+    Wayland,
+}
+
+impl DisplayServer {
+    pub fn detect() -> Self {
+        if std::env::var_os("WAYLAND_DISPLAY").is_some() {
+            Self::Wayland
+        } else {
+            Self::X11
+        }
+    }
+}
+
+impl fmt::Display for DisplayServer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::X11 => write!(f, "X11"),
+            Self::Wayland => write!(f, "Wayland"),
+        }
+    }
+}
+
+pub fn ydotool_socket_path() -> PathBuf {
+    if let Some(path) = std::env::var_os("YDOTOOL_SOCKET") {
+        return PathBuf::from(path);
+    }
+    std::env::var_os("XDG_RUNTIME_DIR")
+        .map(PathBuf::from)
+        .unwrap_or_else(std::env::temp_dir)
+        .join(YDOTOOL_SOCKET_NAME)
+}
+
+pub fn wl_copy<R: CommandRunner>(runner: &mut R, text: &str) -> Result<(), ClipboardError> {
+    match runner.run("wl-copy", &[], Some(text)) {
+        Ok(output) if output.success => Ok(()),
+        Ok(output) => Err(ClipboardError::new(format!(
+            "command `wl-copy` exited with {}: {}",
+            output.status, output.stderr
+        ))),
+        Err(err) if err.kind() == ErrorKind::NotFound => Err(ClipboardError::new(
+            "missing clipboard helper: install wl-clipboard to copy on Wayland",
+        )),
+        Err(err) => Err(ClipboardError::new(format!(
+            "failed to run `wl-copy`: {err}"
+        ))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct ScriptedRunner {
+        result: Option<io::Result<CommandOutput>>,
+        calls: Vec<(String, Option<String>)>,
+    }
+
+    impl ScriptedRunner {
+        fn returning(result: io::Result<CommandOutput>) -> Self {
+            Self {
+                result: Some(result),
+                calls: Vec::new(),
+            }
+        }
+    }
+
+    impl CommandRunner for ScriptedRunner {
+        fn run(
+            &mut self,
+            program: &str,
+            _args: &[String],
+            stdin: Option<&str>,
+        ) -> io::Result<CommandOutput> {
+            self.calls
+                .push((program.to_string(), stdin.map(str::to_string)));
+            self.result.take().expect("single call")
+        }
+    }
+
+    #[test]
+    fn wl_copy_pipes_text_through_stdin() {
+        let mut runner = ScriptedRunner::returning(Ok(CommandOutput {
+            success: true,
+            status: "exit status: 0".to_string(),
+            stdout: String::new(),
+            stderr: String::new(),
+        }));
+
+        wl_copy(&mut runner, "hello").unwrap();
+
+        assert_eq!(
+            runner.calls,
+            vec![("wl-copy".to_string(), Some("hello".to_string()))]
+        );
+    }
+
+    #[test]
+    fn wl_copy_reports_missing_helper() {
+        let mut runner = ScriptedRunner::returning(Err(io::Error::from(ErrorKind::NotFound)));
+
+        let err = wl_copy(&mut runner, "hello").unwrap_err();
+
+        assert_eq!(
+            err.message(),
+            "missing clipboard helper: install wl-clipboard to copy on Wayland"
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn system_runner_reports_write_errors_after_the_child_exits() {
+        // `true` never reads stdin, so writing more than a pipe buffer fails.
+        let input = "x".repeat(1 << 20);
+
+        let err = SystemCommandRunner
+            .run("true", &[], Some(&input))
+            .unwrap_err();
+
+        assert_eq!(err.kind(), ErrorKind::BrokenPipe);
+    }
+}
@@ -0,0 +1,355 @@
+use std::io::ErrorKind;
+use std::path::{Path, PathBuf};
+
+use crate::command_runner::{
+    ydotool_socket_path, CommandRunner, DisplayServer, SystemCommandRunner,
+};
+use crate::{Typer, TypingError};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TypeCommand {
+    pub program: String,
+    pub args: Vec<String>,
+    pub socket: Option<PathBuf>,
+}
+
+impl TypeCommand {
+    pub fn new(program: impl Into<String>, args: &[&str]) -> Self {
+        Self {
+            program: program.into(),
+            args: args.iter().map(|arg| arg.to_string()).collect(),
+            socket: None,
+        }
+    }
+
+    pub fn wtype() -> Self {
+        Self::new("wtype", &["--"])
+    }
+
+    pub fn xdotool() -> Self {
+        Self::new("xdotool", &["type", "--clearmodifiers", "--"])
+    }
+
+    pub fn ydotool(socket: impl Into<PathBuf>) -> Self {
+        Self::new("ydotool", &["type", "--"]).with_socket(socket)
+    }
+
+    pub fn with_socket(mut self, socket: impl Into<PathBuf>) -> Self {
+        self.socket = Some(socket.into());
+        self
+    }
+
+    fn args_with(&self, text: &str) -> Vec<String> {
+        let mut args = self.args.clone();
+        args.push(text.to_string());
+        args
+    }
+}
+
+pub struct CommandTyper<R = SystemCommandRunner> {
+    candidates: Vec<TypeCommand>,
+    display: Option<DisplayServer>,
+    runner: R,
+}
+
+impl CommandTyper {
+    pub fn new() -> Self {
+        Self::for_display(DisplayServer::detect(), &ydotool_socket_path())
+    }
+
+    pub fn for_display(display: DisplayServer, ydotool_socket: &Path) -> Self {
+        Self {
+            candidates: default_candidates(display, ydotool_socket),
+            display: Some(display),
+            runner: SystemCommandRunner,
+        }
+    }
+
+    pub fn with_candidates(candidates: Vec<TypeCommand>) -> Self {
+        Self {
+            candidates,
+            display: None,
+            runner: SystemCommandRunner,
+        }
+    }
+}
+
+impl<R> CommandTyper<R> {
+    pub fn with_runner<T: CommandRunner>(self, runner: T) -> CommandTyper<T> {
+        CommandTyper {
+            candidates: self.candidates,
+            display: self.display,
+            runner,
+        }
+    }
+
+    pub fn candidates(&self) -> &[TypeCommand] {
+        &self.candidates
+    }
+
+    pub fn display(&self) -> Option<DisplayServer> {
+        self.display
+    }
+}
+
+impl Default for CommandTyper {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<R: CommandRunner> Typer for CommandTyper<R> {
+    fn type_text(&mut self, text: &str) -> Result<(), TypingError> {
+        let mut tried = Vec::new();
+
+        for candidate in &self.candidates {
+            if let Some(socket) = &candidate.socket {
+                if !self.runner.path_exists(socket) {
+                    tried.push(format!(
+                        "{} (daemon socket {} not found)",
+                        candidate.program,
+                        socket.display()
+                    ));
+                    continue;
+                }
+            }
+            let output = match self
+                .runner
+                .run(&candidate.program, &candidate.args_with(text), None)
+            {
+                Ok(output) => output,
+                Err(err) if err.kind() == ErrorKind::NotFound => {
+                    tried.push(candidate.program.clone());
+                    continue;
+                }
+                Err(err) => {
+                    return Err(TypingError::new(format!(
+                        "failed to run `{}`: {}",
+                        candidate.program, err
+                    )))
+                }
+            };
+
+            if output.success {
+                return Ok(());
+            }
+
+            return Err(TypingError::new(format!(
+                "command `{}` exited with {}: {}",
+                candidate.program, output.status, output.stderr
+            )));
+        }
+
+        if tried.is_empty() {
+            return Err(TypingError::new("no typing helper configured"));
+        }
+        let display = self
+            .display
+            .map(|display| format!(" on {display}"))
+            .unwrap_or_default();
+        Err(TypingError::new(format!(
+            "missing typing helper{display}: tried {}; install one to enable direct write",
+            tried.join(", ")
+        )))
+    }
+}
+
+fn default_candidates(display: DisplayServer, ydotool_socket: &Path) -> Vec<TypeCommand> {
+    let ydotool = TypeCommand::ydotool(ydotool_socket);
+    match display {
+        DisplayServer::Wayland => vec![TypeCommand::wtype(), TypeCommand::xdotool(), ydotool],
+        DisplayServer::X11 => vec![TypeCommand::xdotool(), TypeCommand::wtype(), ydotool],
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::command_runner::CommandOutput;
+    use std::collections::HashSet;
+    use std::io;
+
+    const SOCKET: &str = "/run/user/1000/.ydotool_socket";
+
+    #[derive(Default)]
+    struct FakeSystem {
+        installed: HashSet<&'static str>,
+        sockets: HashSet<PathBuf>,
+        attempts: Vec<String>,
+    }
+
+    impl FakeSystem {
+        fn with(installed: &[&'static str], ydotool_socket: bool) -> Self {
+            Self {
+                installed: installed.iter().copied().collect(),
+                sockets: ydotool_socket
+                    .then(|| PathBuf::from(SOCKET))
+                    .into_iter()
+                    .collect(),
+                attempts: Vec::new(),
+            }
+        }
+    }
+
+    impl CommandRunner for &mut FakeSystem {
+        fn run(
+            &mut self,
+            program: &str,
+            args: &[String],
+            _stdin: Option<&str>,
+        ) -> io::Result<CommandOutput> {
+            self.attempts.push(format!("{program} {}", args.join(" ")));
+            if !self.installed.contains(program) {
+                return Err(io::Error::from(ErrorKind::NotFound));
+            }
+            Ok(CommandOutput {
+                success: true,
+                status: "exit status: 0".to_string(),
+                stdout: String::new(),
+                stderr: String::new(),
+            })
+        }
+
+        fn path_exists(&self, path: &Path) -> bool {
+            self.sockets.contains(path)
+        }
+    }
+
+    fn type_on(display: DisplayServer, system: &mut FakeSystem) -> Result<(), TypingError> {
+        CommandTyper::for_display(display, Path::new(SOCKET))
+            .with_runner(system)
+            .type_text("hello")
+    }
+
+    #[test]
+    fn prefers_wtype_on_wayland_and_xdotool_on_x11() {
+        let programs = |display| {
+            default_candidates(display, Path::new(SOCKET))
+                .into_iter()
+                .map(|candidate| candidate.program)
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(
+            programs(DisplayServer::Wayland),
+            vec!["wtype", "xdotool", "ydotool"]
+        );
+        assert_eq!(
+            programs(DisplayServer::X11),
+            vec!["xdotool", "wtype", "ydotool"]
+        );
+        assert_eq!(
+            TypeCommand::xdotool().args,
+            vec!["type", "--clearmodifiers", "--"]
+        );
+    }
+
+    #[test]
+    fn x11_types_with_xdotool_first() {
+        let mut system = FakeSystem::with(&["xdotool", "wtype", "ydotool"], true);
+
+        type_on(DisplayServer::X11, &mut system).unwrap();
+
+        assert_eq!(
+            system.attempts,
+            vec!["xdotool type --clearmodifiers -- hello"]
+        );
+    }
+
+    #[test]
+    fn leading_dash_text_is_not_parsed_as_an_option() {
+        let mut system = FakeSystem::with(&["xdotool"], false);
+
+        CommandTyper::for_display(DisplayServer::X11, Path::new(SOCKET))
+            .with_runner(&mut system)
+            .type_text("-5 degrees")
+            .unwrap();
+
+        assert_eq!(
+            system.attempts,
+            vec!["xdotool type --clearmodifiers -- -5 degrees"]
+        );
+    }
+
+    #[test]
+    fn wayland_types_with_wtype_when_installed() {
+        let mut system = FakeSystem::with(&["wtype", "ydotool"], true);
+
+        type_on(DisplayServer::Wayland, &mut system).unwrap();
+
+        assert_eq!(system.attempts, vec!["wtype -- hello"]);
+    }
+
+    #[test]
+    fn wayland_falls_back_to_ydotool_when_daemon_is_running() {
+        let mut system = FakeSystem::with(&["ydotool"], true);
+
+        type_on(DisplayServer::Wayland, &mut system).unwrap();
+
+        assert_eq!(
+            system.attempts,
+            vec![
+                "wtype -- hello",
+                "xdotool type --clearmodifiers -- hello",
+                "ydotool type -- hello"
+            ]
+        );
+    }
+
+    #[test]
+    fn ydotool_is_skipped_without_its_daemon_socket() {
+        let mut system = FakeSystem::with(&["ydotool"], false);
+
+        let err = type_on(DisplayServer::Wayland, &mut system).unwrap_err();
+
+        assert_eq!(system.attempts.len(), 2);
+        assert_eq!(
+            err.message(),
+            format!(
+                "missing typing helper on Wayland: tried wtype, xdotool, ydotool \
+                 (daemon socket {SOCKET} not found); install one to enable direct write"
+            )
+        );
+    }
+
+    #[test]
+    fn names_missing_helpers_when_none_installed() {
+        let mut typer = CommandTyper::with_candidates(vec![
+            TypeCommand::new("openwhisperai-missing-wtype", &["--"]),
+            TypeCommand::new("openwhisperai-missing-xdotool", &["type"]),
+        ]);
+
+        let err = typer.type_text("hello").unwrap_err();
+
+        assert_eq!(
+            err.message(),
+            "missing typing helper: tried openwhisperai-missing-wtype, \
+             openwhisperai-missing-xdotool; install one to enable direct write"
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn skips_missing_helper_and_uses_next_candidate() {
+        let mut typer = CommandTyper::with_candidates(vec![
+            TypeCommand::new("openwhisperai-missing-wtype", &["--"]),
+            TypeCommand::new("sh", &["-c", "test \"$1\" = hello", "sh"]),
+        ]);
+
+        assert!(typer.type_text("hello").is_ok());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn reports_non_zero_exit_with_stderr() {
+        let mut typer = CommandTyper::with_candidates(vec![
+            TypeCommand::new("sh", &["-c", "echo 'no display' >&2; exit 3", "sh"]),
+            TypeCommand::new("true", &[]),
+        ]);
+
+        let err = typer.type_text("hello").unwrap_err();
+
+        assert!(err.message().starts_with("command `sh` exited with"));
+        assert!(err.message().ends_with("no display"));
+    }
+}
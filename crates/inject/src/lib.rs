@@ -1,20 +1,222 @@
+#[cfg(feature = "arboard-backend")]
+mod arboard_clipboard;
+mod chunked_typer;
+mod command_runner;
+mod command_typer;
+mod paste_shortcut;
+mod queue;
+mod secret;
+mod transform;
+
+#[cfg(feature = "arboard-backend")]
+pub use arboard_clipboard::ArboardClipboard;
+pub use chunked_typer::{ChunkedTyper, DEFAULT_CHUNK_PAUSE, DEFAULT_CHUNK_SIZE};
+pub use command_runner::{
+    wl_copy, ydotool_socket_path, CommandOutput, CommandRunner, DisplayServer, SystemCommandRunner,
+};
+pub use command_typer::{CommandTyper, TypeCommand};
+pub use paste_shortcut::{wtype_paste_args, xdotool_paste_args};
+pub use queue::InjectionQueue;
+pub use secret::SecretString;
+pub use transform::{
+    AsciiDashes, AsciiQuotes, CollapseWhitespace, NormalizeNfc, StripTrailingNewline, TextTransform,
+};
+
+use std::fmt;
+use std::time::{Duration, Instant};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ClipboardErrorKind {
+    Failed,
+    PrimaryUnsupported,
+    HtmlUnsupported,
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
-pub struct ClipboardError(pub &'static str);
+pub struct ClipboardError {
+    kind: ClipboardErrorKind,
+    message: String,
+}
+
+impl ClipboardError {
+    pub fn new(message: impl Into<String>) -> Self {
+        Self {
+            kind: ClipboardErrorKind::Failed,
+            message: message.into(),
+        }
+    }
+
+    pub fn primary_unsupported() -> Self {
+        Self {
+            kind: ClipboardErrorKind::PrimaryUnsupported,
+            message: "primary selection is not supported by this backend".to_string(),
+        }
+    }
+
+    pub fn html_unsupported() -> Self {
+        Self {
+            kind: ClipboardErrorKind::HtmlUnsupported,
+            message: "html clipboard content is not supported by this backend".to_string(),
+        }
+    }
+
+    pub fn kind(&self) -> ClipboardErrorKind {
+        self.kind
+    }
+
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+
+    pub fn is_primary_unsupported(&self) -> bool {
+        self.kind == ClipboardErrorKind::PrimaryUnsupported
+    }
+
+    pub fn is_html_unsupported(&self) -> bool {
+        self.kind == ClipboardErrorKind::HtmlUnsupported
+    }
+}
+
+impl fmt::Display for ClipboardError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "clipboard error: {}", self.message)
+    }
+}
+
+impl std::error::Error for ClipboardError {}
+
+impl From<&str> for ClipboardError {
+    fn from(message: &str) -> Self {
+        Self::new(message)
+    }
+}
+
+impl From<String> for ClipboardError {
+    fn from(message: String) -> Self {
+        Self::new(message)
+    }
+}
 
 #[derive(Debug, Clone, PartialEq, Eq)]
-pub struct TypingError(pub &'static str);
+pub struct TypingError(pub String);
+
+impl TypingError {
+    pub fn new(message: impl Into<String>) -> Self {
+        Self(message.into())
+    }
+
+    pub fn message(&self) -> &str {
+        &self.0
+    }
+}
+
+impl fmt::Display for TypingError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "typing error: {}", self.0)
+    }
+}
+
+impl std::error::Error for TypingError {}
+
+impl From<&str> for TypingError {
+    fn from(message: &str) -> Self {
+        Self::new(message)
+    }
+}
+
+impl From<String> for TypingError {
+    fn from(message: String) -> Self {
+        Self(message)
+    }
+}
 
 pub trait Clipboard {
     fn get_text(&mut self) -> Result<Option<String>, ClipboardError>;
     fn set_text(&mut self, text: &str) -> Result<(), ClipboardError>;
     fn clear(&mut self) -> Result<(), ClipboardError>;
     fn paste(&mut self) -> Result<(), ClipboardError>;
+
+    fn get_primary(&mut self) -> Result<Option<String>, ClipboardError> {
+        Err(ClipboardError::primary_unsupported())
+    }
+
+    fn set_primary(&mut self, _text: &str) -> Result<(), ClipboardError> {
+        Err(ClipboardError::primary_unsupported())
+    }
+
+    fn clear_primary(&mut self) -> Result<(), ClipboardError> {
+        Err(ClipboardError::primary_unsupported())
+    }
+
+    fn set_html(&mut self, _html: &str, _alt_text: &str) -> Result<(), ClipboardError> {
+        Err(ClipboardError::html_unsupported())
+    }
 }
 
 pub trait Typer {
     fn type_text(&mut self, text: &str) -> Result<(), TypingError>;
 }
 
+pub trait Sleeper {
+    fn sleep(&mut self, duration: Duration);
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ThreadSleeper;
+
+impl Sleeper for ThreadSleeper {
+    fn sleep(&mut self, duration: Duration) {
+        std::thread::sleep(duration);
+    }
+}
+
+pub trait Clock {
+    fn now(&mut self) -> Duration;
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct MonotonicClock {
+    origin: Instant,
+}
+
+impl Default for MonotonicClock {
+    fn default() -> Self {
+        Self {
+            origin: Instant::now(),
+        }
+    }
+}
+
+impl Clock for MonotonicClock {
+    fn now(&mut self) -> Duration {
+        self.origin.elapsed()
+    }
+}
+
+pub const VERIFY_BACKOFF: Duration = Duration::from_millis(10);
+
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct InjectOptions {
+    /// Overrides the injector's default restore delay for this call.
+    pub restore_delay: Option<Duration>,
+    pub verify_retries: Option<u8>,
+    pub also_set_primary: bool,
+    pub secure: bool,
+    pub clear_after: Option<Duration>,
+    pub paste_retries: u8,
+    pub paste_retry_delay: Duration,
+}
+
+impl InjectOptions {
+    fn secure_ttl(&self) -> Option<Duration> {
+        if self.secure {
+            self.clear_after
+        } else {
+            None
+        }
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum InjectOutcome {
     Clipboard,
@@ -25,17 +227,54 @@ pub enum InjectOutcome {
 pub enum ClipboardRestore {
     NotAttempted,
     Restored,
+    SkippedExternalChange,
+    ClearScheduled(Duration),
+    ClearedAfterTtl,
+    Failed(ClipboardError),
+}
+
+enum PrimaryReplace {
+    Skipped,
+    Replaced(Option<SecretString>),
     Failed(ClipboardError),
 }
 
+struct PendingClear {
+    text: SecretString,
+    due: Duration,
+}
+
+impl PendingClear {
+    fn settle(mut self) {
+        self.text.wipe();
+    }
+}
+
+impl Drop for PendingClear {
+    fn drop(&mut self) {
+        if !self.text.as_str().is_empty() {
+            log::warn!(
+                "dropping a scheduled clipboard clear; the injected text stays on the clipboard"
+            );
+        }
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct InjectResult {
     pub outcome: InjectOutcome,
     pub restore: ClipboardRestore,
+    pub primary_restore: ClipboardRestore,
+    pub set_duration: Duration,
+    pub paste_duration: Duration,
+    pub typing_duration: Option<Duration>,
+    pub attempts: u16,
+    pub paste_attempts: u16,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum InjectError {
+    ClipboardRead(ClipboardError),
     ClipboardSet(ClipboardError),
     ClipboardPaste(ClipboardError),
     Typing {
@@ -44,9 +283,128 @@ pub enum InjectError {
     },
 }
 
+impl fmt::Display for InjectError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            InjectError::ClipboardRead(err) => {
+                write!(f, "failed to read clipboard: {}", err.message())
+            }
+            InjectError::ClipboardSet(err) => {
+                write!(f, "failed to set clipboard: {}", err.message())
+            }
+            InjectError::ClipboardPaste(err) => {
+                write!(f, "failed to paste clipboard: {}", err.message())
+            }
+            InjectError::Typing {
+                source,
+                clipboard: Some(clipboard),
+            } => write!(
+                f,
+                "typing fallback failed: {} (clipboard: {})",
+                source.0,
+                clipboard.message()
+            ),
+            InjectError::Typing {
+                source,
+                clipboard: None,
+            } => write!(f, "typing fallback failed: {}", source.0),
+        }
+    }
+}
+
+impl std::error::Error for InjectError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            InjectError::ClipboardRead(err)
+            | InjectError::ClipboardSet(err)
+            | InjectError::ClipboardPaste(err) => Some(err),
+            InjectError::Typing { source, .. } => Some(source),
+        }
+    }
+}
+
+impl From<TypingError> for InjectError {
+    fn from(source: TypingError) -> Self {
+        InjectError::Typing {
+            source,
+            clipboard: None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum RestorePolicy {
+    Always,
+    Never,
+    #[default]
+    OnlyIfUnchanged,
+}
+
 pub struct Injector<C, T> {
     clipboard: C,
     typer: T,
+    sleeper: Box<dyn Sleeper + Send>,
+    clock: Box<dyn Clock + Send>,
+    restore_policy: RestorePolicy,
+    typing_fallback: bool,
+    restore_delay: Duration,
+    transforms: Vec<Box<dyn TextTransform + Send>>,
+    pending_clear: Option<PendingClear>,
+}
+
+pub struct InjectorBuilder<C, T> {
+    clipboard: C,
+    typer: T,
+    restore_policy: RestorePolicy,
+    typing_fallback: bool,
+    restore_delay: Duration,
+    transforms: Vec<Box<dyn TextTransform + Send>>,
+}
+
+impl<C, T> InjectorBuilder<C, T>
+where
+    C: Clipboard,
+    T: Typer,
+{
+    pub fn new(clipboard: C, typer: T) -> Self {
+        Self {
+            clipboard,
+            typer,
+            restore_policy: RestorePolicy::default(),
+            typing_fallback: true,
+            restore_delay: Duration::ZERO,
+            transforms: Vec::new(),
+        }
+    }
+
+    pub fn restore_policy(mut self, policy: RestorePolicy) -> Self {
+        self.restore_policy = policy;
+        self
+    }
+
+    pub fn typing_fallback(mut self, enabled: bool) -> Self {
+        self.typing_fallback = enabled;
+        self
+    }
+
+    pub fn restore_delay(mut self, delay: Duration) -> Self {
+        self.restore_delay = delay;
+        self
+    }
+
+    pub fn transform(mut self, transform: impl TextTransform + Send + 'static) -> Self {
+        self.transforms.push(Box::new(transform));
+        self
+    }
+
+    pub fn build(self) -> Injector<C, T> {
+        let mut injector = Injector::new(self.clipboard, self.typer);
+        injector.restore_policy = self.restore_policy;
+        injector.typing_fallback = self.typing_fallback;
+        injector.restore_delay = self.restore_delay;
+        injector.transforms = self.transforms;
+        injector
+    }
 }
 
 impl<C, T> Injector<C, T>
@@ -55,45 +413,342 @@ where
     T: Typer,
 {
     pub fn new(clipboard: C, typer: T) -> Self {
-        Self { clipboard, typer }
+        Self {
+            clipboard,
+            typer,
+            sleeper: Box::new(ThreadSleeper),
+            clock: Box::new(MonotonicClock::default()),
+            restore_policy: RestorePolicy::default(),
+            typing_fallback: true,
+            restore_delay: Duration::ZERO,
+            transforms: Vec::new(),
+            pending_clear: None,
+        }
+    }
+
+    pub fn builder(clipboard: C, typer: T) -> InjectorBuilder<C, T> {
+        InjectorBuilder::new(clipboard, typer)
+    }
+
+    pub fn with_sleeper(mut self, sleeper: impl Sleeper + Send + 'static) -> Self {
+        self.sleeper = Box::new(sleeper);
+        self
+    }
+
+    pub fn with_transform(mut self, transform: impl TextTransform + Send + 'static) -> Self {
+        self.transforms.push(Box::new(transform));
+        self
+    }
+
+    /// Runs the transform chain, wiping each intermediate copy of the text.
+    pub fn apply_transforms(&self, text: &str) -> SecretString {
+        self.transforms.iter().fold(
+            SecretString::from(text.to_string()),
+            |current, transform| SecretString::from(transform.apply(current.as_str())),
+        )
+    }
+
+    pub fn with_clock(mut self, clock: impl Clock + Send + 'static) -> Self {
+        self.clock = Box::new(clock);
+        self
+    }
+
+    pub fn restore_policy(&self) -> RestorePolicy {
+        self.restore_policy
     }
 
     pub fn into_parts(self) -> (C, T) {
         (self.clipboard, self.typer)
     }
 
+    pub fn clear_due_in(&mut self) -> Option<Duration> {
+        let due = self.pending_clear.as_ref()?.due;
+        Some(due.saturating_sub(self.clock.now()))
+    }
+
+    pub fn clear_pending(&mut self) -> Option<ClipboardRestore> {
+        let due = self.pending_clear.as_ref()?.due;
+        if self.clock.now() < due {
+            return None;
+        }
+        let pending = self.pending_clear.take()?;
+        let changed = self.clipboard.get_text().is_ok_and(|current| {
+            let current = current.map(SecretString::from);
+            current.as_ref().map(SecretString::as_str) != Some(pending.text.as_str())
+        });
+        pending.settle();
+        if changed {
+            return Some(ClipboardRestore::SkippedExternalChange);
+        }
+        Some(match self.clipboard.clear() {
+            Ok(()) => ClipboardRestore::ClearedAfterTtl,
+            Err(err) => ClipboardRestore::Failed(err),
+        })
+    }
+
     pub fn inject_text(&mut self, text: &str) -> Result<InjectResult, InjectError> {
-        let previous = match self.clipboard.get_text() {
-            Ok(value) => value,
-            Err(err) => {
-                return self
-                    .typer
-                    .type_text(text)
-                    .map(|()| InjectResult {
-                        outcome: InjectOutcome::TypedFallback,
-                        restore: ClipboardRestore::NotAttempted,
-                    })
-                    .map_err(|typing_err| InjectError::Typing {
-                        source: typing_err,
-                        clipboard: Some(err),
-                    });
+        self.inject_text_with_options(text, &InjectOptions::default())
+    }
+
+    pub fn inject_text_with_options(
+        &mut self,
+        text: &str,
+        options: &InjectOptions,
+    ) -> Result<InjectResult, InjectError> {
+        if self.transforms.is_empty() {
+            return self.inject_prepared(text, None, options);
+        }
+        let transformed = self.apply_transforms(text);
+        self.inject_prepared(transformed.as_str(), None, options)
+    }
+
+    pub fn inject_html(
+        &mut self,
+        html: &str,
+        plain_fallback: &str,
+    ) -> Result<InjectResult, InjectError> {
+        let options = InjectOptions::default();
+        if self.transforms.is_empty() {
+            return self.inject_prepared(plain_fallback, Some(html), &options);
+        }
+        let transformed = self.apply_transforms(plain_fallback);
+        self.inject_prepared(transformed.as_str(), Some(html), &options)
+    }
+
+    fn inject_prepared(
+        &mut self,
+        text: &str,
+        html: Option<&str>,
+        options: &InjectOptions,
+    ) -> Result<InjectResult, InjectError> {
+        let mut timings = Timings::default();
+        let ttl = options.secure_ttl();
+
+        let previous = if self.restore_policy == RestorePolicy::Never || ttl.is_some() {
+            None
+        } else {
+            match self.clipboard.get_text() {
+                Ok(value) => Some(value.map(SecretString::from)),
+                Err(err) => {
+                    if !self.typing_fallback {
+                        return Err(InjectError::ClipboardRead(err));
+                    }
+                    return self
+                        .timed_type(text, &mut timings)
+                        .map(|()| {
+                            timings.finish(
+                                InjectOutcome::TypedFallback,
+                                ClipboardRestore::NotAttempted,
+                                ClipboardRestore::NotAttempted,
+                            )
+                        })
+                        .map_err(|typing_err| InjectError::Typing {
+                            source: typing_err,
+                            clipboard: Some(err),
+                        });
+                }
+            }
+        };
+
+        let started = self.clock.now();
+        let set_result = match html {
+            Some(html) => self.set_html_or_text(html, text, options, &mut timings),
+            None => self.set_and_verify(text, options, &mut timings),
+        };
+        timings.set = self.clock.now().saturating_sub(started);
+        if let Err(err) = set_result {
+            let restore = match previous {
+                Some(previous) => restore_outcome(restore_clipboard(&mut self.clipboard, previous)),
+                None => ClipboardRestore::NotAttempted,
+            };
+            if !self.typing_fallback {
+                return Err(InjectError::ClipboardSet(err));
             }
+            return self.typing_fallback_after_restore(text, err, restore, timings);
+        }
+
+        let previous_primary = if options.also_set_primary {
+            self.replace_primary(text)
+        } else {
+            PrimaryReplace::Skipped
         };
 
-        if let Err(err) = self.clipboard.set_text(text) {
-            let restore_result = restore_clipboard(&mut self.clipboard, previous);
-            return self.typing_fallback_after_restore(text, err, restore_result);
+        let started = self.clock.now();
+        let paste_result = self.paste_with_retries(options, &mut timings);
+        timings.paste = self.clock.now().saturating_sub(started);
+
+        match paste_result {
+            Ok(()) => {
+                let restore_delay = options.restore_delay.unwrap_or(self.restore_delay);
+                if previous.is_some() && !restore_delay.is_zero() {
+                    self.sleeper.sleep(restore_delay);
+                }
+                let restore = self.finish_restore(text, previous, ttl);
+                let primary_restore = self.restore_primary(previous_primary);
+                Ok(timings.finish(InjectOutcome::Clipboard, restore, primary_restore))
+            }
+            Err(paste_err) => {
+                if !self.typing_fallback {
+                    self.finish_restore(text, previous, ttl);
+                    self.restore_primary(previous_primary);
+                    return Err(InjectError::ClipboardPaste(paste_err));
+                }
+                self.typing_fallback_with_restore(
+                    text,
+                    paste_err,
+                    previous,
+                    previous_primary,
+                    ttl,
+                    timings,
+                )
+            }
         }
+    }
 
-        match self.clipboard.paste() {
+    fn set_html_or_text(
+        &mut self,
+        html: &str,
+        text: &str,
+        options: &InjectOptions,
+        timings: &mut Timings,
+    ) -> Result<(), ClipboardError> {
+        match self.clipboard.set_html(html, text) {
             Ok(()) => {
-                let restore_result = restore_clipboard(&mut self.clipboard, previous);
-                Ok(InjectResult {
-                    outcome: InjectOutcome::Clipboard,
-                    restore: restore_outcome(restore_result),
-                })
+                timings.attempts += 1;
+                Ok(())
+            }
+            Err(err) if err.is_html_unsupported() => self.set_and_verify(text, options, timings),
+            Err(err) => {
+                timings.attempts += 1;
+                Err(err)
+            }
+        }
+    }
+
+    fn set_and_verify(
+        &mut self,
+        text: &str,
+        options: &InjectOptions,
+        timings: &mut Timings,
+    ) -> Result<(), ClipboardError> {
+        timings.attempts += 1;
+        self.clipboard.set_text(text)?;
+        let Some(retries) = options.verify_retries else {
+            return Ok(());
+        };
+
+        for attempt in 0..=u32::from(retries) {
+            if attempt > 0 {
+                self.sleeper.sleep(VERIFY_BACKOFF * attempt);
+                timings.attempts += 1;
+                self.clipboard.set_text(text)?;
+            }
+            if let Ok(Some(current)) = self.clipboard.get_text() {
+                let current = SecretString::from(current);
+                if current.as_str() == text {
+                    return Ok(());
+                }
+            }
+        }
+
+        Err(ClipboardError::new(
+            "clipboard contents did not match after set",
+        ))
+    }
+
+    fn paste_with_retries(
+        &mut self,
+        options: &InjectOptions,
+        timings: &mut Timings,
+    ) -> Result<(), ClipboardError> {
+        timings.paste_attempts += 1;
+        let mut result = self.clipboard.paste();
+        for _ in 0..options.paste_retries {
+            if result.is_ok() {
+                break;
+            }
+            if !options.paste_retry_delay.is_zero() {
+                self.sleeper.sleep(options.paste_retry_delay);
+            }
+            timings.paste_attempts += 1;
+            result = self.clipboard.paste();
+        }
+        result
+    }
+
+    fn timed_type(&mut self, text: &str, timings: &mut Timings) -> Result<(), TypingError> {
+        let started = self.clock.now();
+        let result = self.typer.type_text(text);
+        timings.typing = Some(self.clock.now().saturating_sub(started));
+        result
+    }
+
+    fn finish_restore(
+        &mut self,
+        text: &str,
+        previous: Option<Option<SecretString>>,
+        ttl: Option<Duration>,
+    ) -> ClipboardRestore {
+        let Some(ttl) = ttl else {
+            return self.restore_after_injection(text, previous);
+        };
+
+        if let Some(superseded) = self.pending_clear.take() {
+            superseded.settle();
+        }
+        self.pending_clear = Some(PendingClear {
+            text: SecretString::from(text.to_string()),
+            due: self.clock.now().saturating_add(ttl),
+        });
+        ClipboardRestore::ClearScheduled(ttl)
+    }
+
+    fn restore_after_injection(
+        &mut self,
+        text: &str,
+        previous: Option<Option<SecretString>>,
+    ) -> ClipboardRestore {
+        let Some(previous) = previous else {
+            return ClipboardRestore::NotAttempted;
+        };
+
+        if self.restore_policy == RestorePolicy::OnlyIfUnchanged {
+            if let Ok(current) = self.clipboard.get_text() {
+                let current = current.map(SecretString::from);
+                if current.as_ref().map(SecretString::as_str) != Some(text) {
+                    return ClipboardRestore::SkippedExternalChange;
+                }
+            }
+        }
+        restore_outcome(restore_clipboard(&mut self.clipboard, previous))
+    }
+
+    fn replace_primary(&mut self, text: &str) -> PrimaryReplace {
+        let previous = if self.restore_policy == RestorePolicy::Never {
+            None
+        } else {
+            match self.clipboard.get_primary() {
+                Ok(previous) => Some(previous.map(SecretString::from)),
+                Err(err) if err.is_primary_unsupported() => return PrimaryReplace::Skipped,
+                Err(err) => return PrimaryReplace::Failed(err),
+            }
+        };
+        match (self.clipboard.set_primary(text), previous) {
+            (Err(err), _) if err.is_primary_unsupported() => PrimaryReplace::Skipped,
+            (Err(err), _) => PrimaryReplace::Failed(err),
+            (Ok(()), Some(previous)) => PrimaryReplace::Replaced(previous),
+            (Ok(()), None) => PrimaryReplace::Skipped,
+        }
+    }
+
+    fn restore_primary(&mut self, previous: PrimaryReplace) -> ClipboardRestore {
+        match previous {
+            PrimaryReplace::Skipped => ClipboardRestore::NotAttempted,
+            PrimaryReplace::Failed(err) => ClipboardRestore::Failed(err),
+            PrimaryReplace::Replaced(Some(value)) => {
+                restore_outcome(self.clipboard.set_primary(value.as_str()))
             }
-            Err(paste_err) => self.typing_fallback_with_restore(text, paste_err, previous),
+            PrimaryReplace::Replaced(None) => restore_outcome(self.clipboard.clear_primary()),
         }
     }
 
@@ -101,18 +756,19 @@ where
         &mut self,
         text: &str,
         clipboard_error: ClipboardError,
-        previous: Option<String>,
+        previous: Option<Option<SecretString>>,
+        previous_primary: PrimaryReplace,
+        ttl: Option<Duration>,
+        mut timings: Timings,
     ) -> Result<InjectResult, InjectError> {
-        let typing_result = self.typer.type_text(text);
-        let restore_result = restore_clipboard(&mut self.clipboard, previous);
+        let typing_result = self.timed_type(text, &mut timings);
+        let restore = self.finish_restore(text, previous, ttl);
+        let primary_restore = self.restore_primary(previous_primary);
 
         match typing_result {
-            Ok(()) => Ok(InjectResult {
-                outcome: InjectOutcome::TypedFallback,
-                restore: restore_outcome(restore_result),
-            }),
+            Ok(()) => Ok(timings.finish(InjectOutcome::TypedFallback, restore, primary_restore)),
             Err(typing_err) => {
-                if let Err(restore_err) = restore_result {
+                if let ClipboardRestore::Failed(restore_err) = restore {
                     return Err(InjectError::Typing {
                         source: typing_err,
                         clipboard: Some(restore_err),
@@ -131,15 +787,17 @@ where
         &mut self,
         text: &str,
         clipboard_error: ClipboardError,
-        restore_result: Result<(), ClipboardError>,
+        restore: ClipboardRestore,
+        mut timings: Timings,
     ) -> Result<InjectResult, InjectError> {
-        let typing_result = self.typer.type_text(text);
+        let typing_result = self.timed_type(text, &mut timings);
 
         match typing_result {
-            Ok(()) => Ok(InjectResult {
-                outcome: InjectOutcome::TypedFallback,
-                restore: restore_outcome(restore_result),
-            }),
+            Ok(()) => Ok(timings.finish(
+                InjectOutcome::TypedFallback,
+                restore,
+                ClipboardRestore::NotAttempted,
+            )),
             Err(typing_err) => Err(InjectError::Typing {
                 source: typing_err,
                 clipboard: Some(clipboard_error),
@@ -148,12 +806,41 @@ where
     }
 }
 
+#[derive(Debug, Default)]
+struct Timings {
+    set: Duration,
+    paste: Duration,
+    typing: Option<Duration>,
+    attempts: u16,
+    paste_attempts: u16,
+}
+
+impl Timings {
+    fn finish(
+        self,
+        outcome: InjectOutcome,
+        restore: ClipboardRestore,
+        primary_restore: ClipboardRestore,
+    ) -> InjectResult {
+        InjectResult {
+            outcome,
+            restore,
+            primary_restore,
+            set_duration: self.set,
+            paste_duration: self.paste,
+            typing_duration: self.typing,
+            attempts: self.attempts,
+            paste_attempts: self.paste_attempts,
+        }
+    }
+}
+
 fn restore_clipboard<C: Clipboard>(
     clipboard: &mut C,
-    previous: Option<String>,
+    previous: Option<SecretString>,
 ) -> Result<(), ClipboardError> {
     match previous {
-        Some(value) => clipboard.set_text(&value),
+        Some(value) => clipboard.set_text(value.as_str()),
         None => clipboard.clear(),
     }
 }
@@ -168,6 +855,7 @@ fn restore_outcome(result: Result<(), ClipboardError>) -> ClipboardRestore {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use std::sync::{Arc, Mutex};
 
     #[derive(Debug, PartialEq, Eq)]
     enum Op {
@@ -175,6 +863,10 @@ mod tests {
         Set(String),
         Clear,
         Paste,
+        GetPrimary,
+        SetPrimary(String),
+        ClearPrimary,
+        SetHtml(String, String),
     }
 
     struct MockClipboard {
@@ -184,6 +876,12 @@ mod tests {
         fail_set: bool,
         fail_paste: bool,
         fail_clear: bool,
+        ignore_sets: usize,
+        primary: Option<Option<String>>,
+        fail_set_primary: bool,
+        copy_on_paste: Option<String>,
+        paste_failures: usize,
+        html_supported: bool,
     }
 
     impl MockClipboard {
@@ -195,6 +893,12 @@ mod tests {
                 fail_set: false,
                 fail_paste: false,
                 fail_clear: false,
+                ignore_sets: 0,
+                primary: None,
+                fail_set_primary: false,
+                copy_on_paste: None,
+                paste_failures: 0,
+                html_supported: false,
             }
         }
     }
@@ -203,7 +907,7 @@ mod tests {
         fn get_text(&mut self) -> Result<Option<String>, ClipboardError> {
             self.ops.push(Op::Get);
             if self.fail_get {
-                return Err(ClipboardError("get failed"));
+                return Err(ClipboardError::new("get failed"));
             }
             Ok(self.content.clone())
         }
@@ -211,7 +915,11 @@ mod tests {
         fn set_text(&mut self, text: &str) -> Result<(), ClipboardError> {
             self.ops.push(Op::Set(text.to_string()));
             if self.fail_set {
-                return Err(ClipboardError("set failed"));
+                return Err(ClipboardError::new("set failed"));
+            }
+            if self.ignore_sets > 0 {
+                self.ignore_sets -= 1;
+                return Ok(());
             }
             self.content = Some(text.to_string());
             Ok(())
@@ -220,7 +928,7 @@ mod tests {
         fn clear(&mut self) -> Result<(), ClipboardError> {
             self.ops.push(Op::Clear);
             if self.fail_clear {
-                return Err(ClipboardError("clear failed"));
+                return Err(ClipboardError::new("clear failed"));
             }
             self.content = None;
             Ok(())
@@ -228,23 +936,103 @@ mod tests {
 
         fn paste(&mut self) -> Result<(), ClipboardError> {
             self.ops.push(Op::Paste);
+            if let Some(copied) = self.copy_on_paste.take() {
+                self.content = Some(copied);
+            }
             if self.fail_paste {
-                return Err(ClipboardError("paste failed"));
+                return Err(ClipboardError::new("paste failed"));
+            }
+            if self.paste_failures > 0 {
+                self.paste_failures -= 1;
+                return Err(ClipboardError::new("window not focused"));
             }
             Ok(())
         }
-    }
 
-    #[derive(Default)]
-    struct MockTyper {
-        typed: Vec<String>,
-        fail: bool,
-    }
+        fn get_primary(&mut self) -> Result<Option<String>, ClipboardError> {
+            self.ops.push(Op::GetPrimary);
+            self.primary
+                .clone()
+                .ok_or_else(ClipboardError::primary_unsupported)
+        }
 
-    impl Typer for MockTyper {
-        fn type_text(&mut self, text: &str) -> Result<(), TypingError> {
-            if self.fail {
-                return Err(TypingError("typing failed"));
+        fn set_html(&mut self, html: &str, alt_text: &str) -> Result<(), ClipboardError> {
+            if !self.html_supported {
+                return Err(ClipboardError::html_unsupported());
+            }
+            if self.fail_set {
+                return Err(ClipboardError::new("set failed"));
+            }
+            self.ops
+                .push(Op::SetHtml(html.to_string(), alt_text.to_string()));
+            self.content = Some(alt_text.to_string());
+            Ok(())
+        }
+
+        fn set_primary(&mut self, text: &str) -> Result<(), ClipboardError> {
+            self.ops.push(Op::SetPrimary(text.to_string()));
+            if self.primary.is_none() {
+                return Err(ClipboardError::primary_unsupported());
+            }
+            if self.fail_set_primary {
+                return Err(ClipboardError::new("set primary failed"));
+            }
+            self.primary = Some(Some(text.to_string()));
+            Ok(())
+        }
+
+        fn clear_primary(&mut self) -> Result<(), ClipboardError> {
+            self.ops.push(Op::ClearPrimary);
+            if self.primary.is_none() {
+                return Err(ClipboardError::primary_unsupported());
+            }
+            self.primary = Some(None);
+            Ok(())
+        }
+    }
+
+    #[derive(Clone, Default)]
+    struct MockSleeper {
+        slept: Arc<Mutex<Vec<Duration>>>,
+    }
+
+    impl Sleeper for MockSleeper {
+        fn sleep(&mut self, duration: Duration) {
+            self.slept.lock().unwrap().push(duration);
+        }
+    }
+
+    struct StepClock {
+        now: Duration,
+        step: Duration,
+    }
+
+    impl StepClock {
+        fn new(step_ms: u64) -> Self {
+            Self {
+                now: Duration::ZERO,
+                step: Duration::from_millis(step_ms),
+            }
+        }
+    }
+
+    impl Clock for StepClock {
+        fn now(&mut self) -> Duration {
+            self.now += self.step;
+            self.now
+        }
+    }
+
+    #[derive(Default)]
+    struct MockTyper {
+        typed: Vec<String>,
+        fail: bool,
+    }
+
+    impl Typer for MockTyper {
+        fn type_text(&mut self, text: &str) -> Result<(), TypingError> {
+            if self.fail {
+                return Err(TypingError::new("typing failed"));
             }
             self.typed.push(text.to_string());
             Ok(())
@@ -269,6 +1057,7 @@ mod tests {
                 Op::Get,
                 Op::Set("new".to_string()),
                 Op::Paste,
+                Op::Get,
                 Op::Set("old".to_string()),
             ]
         );
@@ -294,6 +1083,7 @@ mod tests {
                 Op::Get,
                 Op::Set("typed".to_string()),
                 Op::Paste,
+                Op::Get,
                 Op::Set("stash".to_string()),
             ]
         );
@@ -313,7 +1103,7 @@ mod tests {
         assert_eq!(outcome.outcome, InjectOutcome::TypedFallback);
         assert_eq!(
             outcome.restore,
-            ClipboardRestore::Failed(ClipboardError("set failed"))
+            ClipboardRestore::Failed(ClipboardError::new("set failed"))
         );
         assert_eq!(clipboard.content, Some("keep".to_string()));
         assert_eq!(
@@ -361,7 +1151,13 @@ mod tests {
         assert_eq!(clipboard.content, None);
         assert_eq!(
             clipboard.ops,
-            vec![Op::Get, Op::Set("alpha".to_string()), Op::Paste, Op::Clear,]
+            vec![
+                Op::Get,
+                Op::Set("alpha".to_string()),
+                Op::Paste,
+                Op::Get,
+                Op::Clear,
+            ]
         );
     }
 
@@ -378,12 +1174,18 @@ mod tests {
         assert_eq!(outcome.outcome, InjectOutcome::Clipboard);
         assert_eq!(
             outcome.restore,
-            ClipboardRestore::Failed(ClipboardError("clear failed"))
+            ClipboardRestore::Failed(ClipboardError::new("clear failed"))
         );
         assert_eq!(clipboard.content, Some("alpha".to_string()));
         assert_eq!(
             clipboard.ops,
-            vec![Op::Get, Op::Set("alpha".to_string()), Op::Paste, Op::Clear,]
+            vec![
+                Op::Get,
+                Op::Set("alpha".to_string()),
+                Op::Paste,
+                Op::Get,
+                Op::Clear,
+            ]
         );
     }
 
@@ -408,20 +1210,22 @@ mod tests {
     fn returns_typing_error_when_get_text_and_typing_fail() {
         let mut clipboard = MockClipboard::new(Some("keep".to_string()));
         clipboard.fail_get = true;
-        let mut typer = MockTyper::default();
-        typer.fail = true;
+        let typer = MockTyper {
+            fail: true,
+            ..Default::default()
+        };
         let mut injector = Injector::new(clipboard, typer);
 
         let result = injector.inject_text("typed");
         let (clipboard, typer) = injector.into_parts();
 
-        assert!(matches!(
+        assert_eq!(
             result,
             Err(InjectError::Typing {
-                source: TypingError("typing failed"),
-                clipboard: Some(ClipboardError("get failed")),
+                source: TypingError::new("typing failed"),
+                clipboard: Some(ClipboardError::new("get failed")),
             })
-        ));
+        );
         assert_eq!(clipboard.content, Some("keep".to_string()));
         assert_eq!(clipboard.ops, vec![Op::Get]);
         assert!(typer.typed.is_empty());
@@ -431,20 +1235,22 @@ mod tests {
     fn restores_clipboard_when_set_fails_and_typing_fails() {
         let mut clipboard = MockClipboard::new(Some("stash".to_string()));
         clipboard.fail_set = true;
-        let mut typer = MockTyper::default();
-        typer.fail = true;
+        let typer = MockTyper {
+            fail: true,
+            ..Default::default()
+        };
         let mut injector = Injector::new(clipboard, typer);
 
         let result = injector.inject_text("fallback");
         let (clipboard, typer) = injector.into_parts();
 
-        assert!(matches!(
+        assert_eq!(
             result,
             Err(InjectError::Typing {
-                source: TypingError("typing failed"),
-                clipboard: Some(ClipboardError("set failed")),
+                source: TypingError::new("typing failed"),
+                clipboard: Some(ClipboardError::new("set failed")),
             })
-        ));
+        );
         assert_eq!(clipboard.content, Some("stash".to_string()));
         assert_eq!(
             clipboard.ops,
@@ -461,20 +1267,22 @@ mod tests {
     fn restores_clipboard_when_paste_fails_and_typing_fails() {
         let mut clipboard = MockClipboard::new(Some("stash".to_string()));
         clipboard.fail_paste = true;
-        let mut typer = MockTyper::default();
-        typer.fail = true;
+        let typer = MockTyper {
+            fail: true,
+            ..Default::default()
+        };
         let mut injector = Injector::new(clipboard, typer);
 
         let result = injector.inject_text("typed");
         let (clipboard, typer) = injector.into_parts();
 
-        assert!(matches!(
+        assert_eq!(
             result,
             Err(InjectError::Typing {
-                source: TypingError("typing failed"),
-                clipboard: Some(ClipboardError("paste failed")),
+                source: TypingError::new("typing failed"),
+                clipboard: Some(ClipboardError::new("paste failed")),
             })
-        ));
+        );
         assert_eq!(clipboard.content, Some("stash".to_string()));
         assert_eq!(
             clipboard.ops,
@@ -482,9 +1290,894 @@ mod tests {
                 Op::Get,
                 Op::Set("typed".to_string()),
                 Op::Paste,
+                Op::Get,
                 Op::Set("stash".to_string()),
             ]
         );
         assert!(typer.typed.is_empty());
     }
+
+    #[test]
+    fn waits_before_restoring_after_successful_paste() {
+        let clipboard = MockClipboard::new(Some("old".to_string()));
+        let typer = MockTyper::default();
+        let sleeper = MockSleeper::default();
+        let mut injector = Injector::new(clipboard, typer).with_sleeper(sleeper.clone());
+        let options = InjectOptions {
+            restore_delay: Some(Duration::from_millis(150)),
+            ..Default::default()
+        };
+
+        let outcome = injector.inject_text_with_options("new", &options).unwrap();
+        let (clipboard, _) = injector.into_parts();
+
+        assert_eq!(outcome.outcome, InjectOutcome::Clipboard);
+        assert_eq!(outcome.restore, ClipboardRestore::Restored);
+        assert_eq!(
+            *sleeper.slept.lock().unwrap(),
+            vec![Duration::from_millis(150)]
+        );
+        assert_eq!(
+            clipboard.ops,
+            vec![
+                Op::Get,
+                Op::Set("new".to_string()),
+                Op::Paste,
+                Op::Get,
+                Op::Set("old".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn skips_restore_delay_when_paste_fails() {
+        let mut clipboard = MockClipboard::new(None);
+        clipboard.fail_paste = true;
+        let typer = MockTyper::default();
+        let sleeper = MockSleeper::default();
+        let mut injector = Injector::new(clipboard, typer).with_sleeper(sleeper.clone());
+        let options = InjectOptions {
+            restore_delay: Some(Duration::from_millis(150)),
+            ..Default::default()
+        };
+
+        let outcome = injector
+            .inject_text_with_options("typed", &options)
+            .unwrap();
+        let (clipboard, _) = injector.into_parts();
+
+        assert_eq!(outcome.outcome, InjectOutcome::TypedFallback);
+        assert!(sleeper.slept.lock().unwrap().is_empty());
+        assert_eq!(
+            clipboard.ops,
+            vec![
+                Op::Get,
+                Op::Set("typed".to_string()),
+                Op::Paste,
+                Op::Get,
+                Op::Clear
+            ]
+        );
+    }
+
+    #[test]
+    fn inject_text_does_not_sleep() {
+        let clipboard = MockClipboard::new(Some("old".to_string()));
+        let typer = MockTyper::default();
+        let sleeper = MockSleeper::default();
+        let mut injector = Injector::new(clipboard, typer).with_sleeper(sleeper.clone());
+
+        injector.inject_text("new").unwrap();
+
+        assert!(sleeper.slept.lock().unwrap().is_empty());
+    }
+
+    #[test]
+    fn retries_set_until_clipboard_accepts_value() {
+        let mut clipboard = MockClipboard::new(Some("old".to_string()));
+        clipboard.ignore_sets = 1;
+        let typer = MockTyper::default();
+        let sleeper = MockSleeper::default();
+        let mut injector = Injector::new(clipboard, typer).with_sleeper(sleeper.clone());
+        let options = InjectOptions {
+            verify_retries: Some(2),
+            ..Default::default()
+        };
+
+        let outcome = injector.inject_text_with_options("new", &options).unwrap();
+        let (clipboard, typer) = injector.into_parts();
+
+        assert_eq!(outcome.outcome, InjectOutcome::Clipboard);
+        assert_eq!(outcome.restore, ClipboardRestore::Restored);
+        assert_eq!(clipboard.content, Some("old".to_string()));
+        assert_eq!(
+            clipboard.ops,
+            vec![
+                Op::Get,
+                Op::Set("new".to_string()),
+                Op::Get,
+                Op::Set("new".to_string()),
+                Op::Get,
+                Op::Paste,
+                Op::Get,
+                Op::Set("old".to_string()),
+            ]
+        );
+        assert_eq!(*sleeper.slept.lock().unwrap(), vec![VERIFY_BACKOFF]);
+        assert!(typer.typed.is_empty());
+    }
+
+    #[test]
+    fn falls_back_to_typing_when_verification_keeps_failing() {
+        let mut clipboard = MockClipboard::new(Some("old".to_string()));
+        clipboard.ignore_sets = 5;
+        let typer = MockTyper::default();
+        let sleeper = MockSleeper::default();
+        let mut injector = Injector::new(clipboard, typer).with_sleeper(sleeper.clone());
+        let options = InjectOptions {
+            verify_retries: Some(1),
+            ..Default::default()
+        };
+
+        let outcome = injector.inject_text_with_options("new", &options).unwrap();
+        let (clipboard, typer) = injector.into_parts();
+
+        assert_eq!(outcome.outcome, InjectOutcome::TypedFallback);
+        assert_eq!(
+            clipboard.ops,
+            vec![
+                Op::Get,
+                Op::Set("new".to_string()),
+                Op::Get,
+                Op::Set("new".to_string()),
+                Op::Get,
+                Op::Set("old".to_string()),
+            ]
+        );
+        assert!(!clipboard.ops.contains(&Op::Paste));
+        assert_eq!(typer.typed, vec!["new".to_string()]);
+    }
+
+    #[test]
+    fn max_verify_retries_count_every_attempt() {
+        let mut clipboard = MockClipboard::new(Some("old".to_string()));
+        clipboard.ignore_sets = usize::MAX;
+        let sleeper = MockSleeper::default();
+        let mut injector =
+            Injector::new(clipboard, MockTyper::default()).with_sleeper(sleeper.clone());
+        let options = InjectOptions {
+            verify_retries: Some(u8::MAX),
+            ..Default::default()
+        };
+
+        let outcome = injector.inject_text_with_options("new", &options).unwrap();
+        let (_, typer) = injector.into_parts();
+
+        assert_eq!(outcome.outcome, InjectOutcome::TypedFallback);
+        assert_eq!(outcome.attempts, u16::from(u8::MAX) + 1);
+        assert_eq!(sleeper.slept.lock().unwrap().len(), usize::from(u8::MAX));
+        assert_eq!(typer.typed, vec!["new".to_string()]);
+    }
+
+    #[test]
+    fn preserves_backend_error_messages_through_inject_error() {
+        struct FailingClipboard;
+
+        impl Clipboard for FailingClipboard {
+            fn get_text(&mut self) -> Result<Option<String>, ClipboardError> {
+                Err(ClipboardError::new(format!(
+                    "X11 server connection timed out after {}ms",
+                    500
+                )))
+            }
+
+            fn set_text(&mut self, _text: &str) -> Result<(), ClipboardError> {
+                unreachable!()
+            }
+
+            fn clear(&mut self) -> Result<(), ClipboardError> {
+                unreachable!()
+            }
+
+            fn paste(&mut self) -> Result<(), ClipboardError> {
+                unreachable!()
+            }
+        }
+
+        let typer = MockTyper {
+            fail: true,
+            ..Default::default()
+        };
+        let mut injector = Injector::new(FailingClipboard, typer);
+
+        let err = injector.inject_text("secret").unwrap_err();
+
+        match &err {
+            InjectError::Typing {
+                clipboard: Some(clipboard),
+                ..
+            } => assert_eq!(
+                clipboard.message(),
+                "X11 server connection timed out after 500ms"
+            ),
+            other => panic!("unexpected error: {other:?}"),
+        }
+        assert_eq!(
+            err.to_string(),
+            "typing fallback failed: typing failed (clipboard: X11 server connection timed out after 500ms)"
+        );
+        let source = std::error::Error::source(&err).unwrap();
+        assert_eq!(source.to_string(), "typing error: typing failed");
+    }
+
+    #[test]
+    fn sets_and_restores_both_selections() {
+        let mut clipboard = MockClipboard::new(Some("old".to_string()));
+        clipboard.primary = Some(Some("selected".to_string()));
+        let typer = MockTyper::default();
+        let mut injector = Injector::new(clipboard, typer);
+        let options = InjectOptions {
+            also_set_primary: true,
+            ..Default::default()
+        };
+
+        let outcome = injector.inject_text_with_options("new", &options).unwrap();
+        let (clipboard, _) = injector.into_parts();
+
+        assert_eq!(outcome.outcome, InjectOutcome::Clipboard);
+        assert_eq!(outcome.restore, ClipboardRestore::Restored);
+        assert_eq!(outcome.primary_restore, ClipboardRestore::Restored);
+        assert_eq!(clipboard.content, Some("old".to_string()));
+        assert_eq!(clipboard.primary, Some(Some("selected".to_string())));
+        assert_eq!(
+            clipboard.ops,
+            vec![
+                Op::Get,
+                Op::Set("new".to_string()),
+                Op::GetPrimary,
+                Op::SetPrimary("new".to_string()),
+                Op::Paste,
+                Op::Get,
+                Op::Set("old".to_string()),
+                Op::SetPrimary("selected".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn restores_both_selections_after_typed_fallback() {
+        let mut clipboard = MockClipboard::new(None);
+        clipboard.primary = Some(None);
+        clipboard.fail_paste = true;
+        let typer = MockTyper::default();
+        let mut injector = Injector::new(clipboard, typer);
+        let options = InjectOptions {
+            also_set_primary: true,
+            ..Default::default()
+        };
+
+        let outcome = injector.inject_text_with_options("new", &options).unwrap();
+        let (clipboard, typer) = injector.into_parts();
+
+        assert_eq!(outcome.outcome, InjectOutcome::TypedFallback);
+        assert_eq!(outcome.primary_restore, ClipboardRestore::Restored);
+        assert_eq!(clipboard.primary, Some(None));
+        assert_eq!(
+            clipboard.ops,
+            vec![
+                Op::Get,
+                Op::Set("new".to_string()),
+                Op::GetPrimary,
+                Op::SetPrimary("new".to_string()),
+                Op::Paste,
+                Op::Get,
+                Op::Clear,
+                Op::ClearPrimary,
+            ]
+        );
+        assert_eq!(typer.typed, vec!["new".to_string()]);
+    }
+
+    #[test]
+    fn primary_failure_does_not_abort_clipboard_paste() {
+        let mut clipboard = MockClipboard::new(Some("old".to_string()));
+        clipboard.primary = Some(Some("selected".to_string()));
+        clipboard.fail_set_primary = true;
+        let typer = MockTyper::default();
+        let mut injector = Injector::new(clipboard, typer);
+        let options = InjectOptions {
+            also_set_primary: true,
+            ..Default::default()
+        };
+
+        let outcome = injector.inject_text_with_options("new", &options).unwrap();
+        let (clipboard, typer) = injector.into_parts();
+
+        assert_eq!(outcome.outcome, InjectOutcome::Clipboard);
+        assert_eq!(outcome.restore, ClipboardRestore::Restored);
+        assert_eq!(
+            outcome.primary_restore,
+            ClipboardRestore::Failed(ClipboardError::new("set primary failed"))
+        );
+        assert_eq!(clipboard.primary, Some(Some("selected".to_string())));
+        assert!(typer.typed.is_empty());
+    }
+
+    #[test]
+    fn unsupported_primary_is_not_fatal() {
+        let clipboard = MockClipboard::new(Some("old".to_string()));
+        let typer = MockTyper::default();
+        let mut injector = Injector::new(clipboard, typer);
+        let options = InjectOptions {
+            also_set_primary: true,
+            ..Default::default()
+        };
+
+        let outcome = injector.inject_text_with_options("new", &options).unwrap();
+        let (clipboard, _) = injector.into_parts();
+
+        assert_eq!(outcome.outcome, InjectOutcome::Clipboard);
+        assert_eq!(outcome.primary_restore, ClipboardRestore::NotAttempted);
+        assert_eq!(
+            clipboard.ops,
+            vec![
+                Op::Get,
+                Op::Set("new".to_string()),
+                Op::GetPrimary,
+                Op::Paste,
+                Op::Get,
+                Op::Set("old".to_string()),
+            ]
+        );
+        assert!(ClipboardError::primary_unsupported().is_primary_unsupported());
+        assert!(
+            !ClipboardError::new("primary selection is not supported by this backend")
+                .is_primary_unsupported()
+        );
+    }
+
+    #[test]
+    fn reports_phase_durations_for_clipboard_paste() {
+        let clipboard = MockClipboard::new(Some("old".to_string()));
+        let typer = MockTyper::default();
+        let mut injector = Injector::new(clipboard, typer).with_clock(StepClock::new(5));
+
+        let outcome = injector.inject_text("new").unwrap();
+
+        assert_eq!(outcome.outcome, InjectOutcome::Clipboard);
+        assert_eq!(outcome.set_duration, Duration::from_millis(5));
+        assert_eq!(outcome.paste_duration, Duration::from_millis(5));
+        assert_eq!(outcome.typing_duration, None);
+        assert_eq!(outcome.attempts, 1);
+    }
+
+    #[test]
+    fn reports_typing_duration_and_attempts_for_fallback() {
+        let mut clipboard = MockClipboard::new(Some("old".to_string()));
+        clipboard.ignore_sets = 5;
+        let typer = MockTyper::default();
+        let mut injector = Injector::new(clipboard, typer)
+            .with_clock(StepClock::new(7))
+            .with_sleeper(MockSleeper::default());
+        let options = InjectOptions {
+            verify_retries: Some(2),
+            ..Default::default()
+        };
+
+        let outcome = injector.inject_text_with_options("new", &options).unwrap();
+
+        assert_eq!(outcome.outcome, InjectOutcome::TypedFallback);
+        assert_eq!(outcome.attempts, 3);
+        assert_eq!(outcome.set_duration, Duration::from_millis(7));
+        assert_eq!(outcome.paste_duration, Duration::ZERO);
+        assert_eq!(outcome.typing_duration, Some(Duration::from_millis(7)));
+    }
+
+    #[test]
+    fn skips_restore_when_user_copies_during_injection() {
+        let mut clipboard = MockClipboard::new(Some("old".to_string()));
+        clipboard.copy_on_paste = Some("user copy".to_string());
+        let typer = MockTyper::default();
+        let mut injector = Injector::new(clipboard, typer);
+
+        let outcome = injector.inject_text("new").unwrap();
+        let (clipboard, _) = injector.into_parts();
+
+        assert_eq!(outcome.outcome, InjectOutcome::Clipboard);
+        assert_eq!(outcome.restore, ClipboardRestore::SkippedExternalChange);
+        assert_eq!(clipboard.content, Some("user copy".to_string()));
+        assert_eq!(
+            clipboard.ops,
+            vec![Op::Get, Op::Set("new".to_string()), Op::Paste, Op::Get]
+        );
+    }
+
+    #[test]
+    fn skips_restore_after_typed_fallback_when_clipboard_changed() {
+        let mut clipboard = MockClipboard::new(None);
+        clipboard.copy_on_paste = Some("user copy".to_string());
+        clipboard.fail_paste = true;
+        let typer = MockTyper::default();
+        let mut injector = Injector::new(clipboard, typer);
+
+        let outcome = injector.inject_text("new").unwrap();
+        let (clipboard, typer) = injector.into_parts();
+
+        assert_eq!(outcome.outcome, InjectOutcome::TypedFallback);
+        assert_eq!(outcome.restore, ClipboardRestore::SkippedExternalChange);
+        assert_eq!(clipboard.content, Some("user copy".to_string()));
+        assert!(!clipboard.ops.contains(&Op::Clear));
+        assert_eq!(typer.typed, vec!["new".to_string()]);
+    }
+
+    #[test]
+    fn builder_always_policy_restores_over_external_change() {
+        let mut clipboard = MockClipboard::new(Some("old".to_string()));
+        clipboard.copy_on_paste = Some("user copy".to_string());
+        let mut injector = Injector::builder(clipboard, MockTyper::default())
+            .restore_policy(RestorePolicy::Always)
+            .build();
+
+        let outcome = injector.inject_text("new").unwrap();
+        let (clipboard, _) = injector.into_parts();
+
+        assert_eq!(outcome.restore, ClipboardRestore::Restored);
+        assert_eq!(clipboard.content, Some("old".to_string()));
+        assert_eq!(
+            clipboard.ops,
+            vec![
+                Op::Get,
+                Op::Set("new".to_string()),
+                Op::Paste,
+                Op::Set("old".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn builder_never_policy_skips_snapshot_and_restore() {
+        let mut clipboard = MockClipboard::new(Some("old".to_string()));
+        clipboard.fail_get = true;
+        let sleeper = MockSleeper::default();
+        let mut injector = Injector::builder(clipboard, MockTyper::default())
+            .restore_policy(RestorePolicy::Never)
+            .restore_delay(Duration::from_millis(100))
+            .build()
+            .with_sleeper(sleeper.clone());
+
+        let outcome = injector.inject_text("new").unwrap();
+        let (clipboard, typer) = injector.into_parts();
+
+        assert_eq!(outcome.outcome, InjectOutcome::Clipboard);
+        assert_eq!(outcome.restore, ClipboardRestore::NotAttempted);
+        assert_eq!(clipboard.content, Some("new".to_string()));
+        assert_eq!(clipboard.ops, vec![Op::Set("new".to_string()), Op::Paste]);
+        assert!(sleeper.slept.lock().unwrap().is_empty());
+        assert!(typer.typed.is_empty());
+    }
+
+    #[test]
+    fn builder_only_if_unchanged_policy_uses_restore_delay() {
+        let clipboard = MockClipboard::new(Some("old".to_string()));
+        let sleeper = MockSleeper::default();
+        let mut injector = Injector::builder(clipboard, MockTyper::default())
+            .restore_policy(RestorePolicy::OnlyIfUnchanged)
+            .restore_delay(Duration::from_millis(100))
+            .build()
+            .with_sleeper(sleeper.clone());
+
+        let outcome = injector.inject_text("new").unwrap();
+        let (clipboard, _) = injector.into_parts();
+
+        assert_eq!(RestorePolicy::default(), RestorePolicy::OnlyIfUnchanged);
+        assert_eq!(outcome.restore, ClipboardRestore::Restored);
+        assert_eq!(clipboard.content, Some("old".to_string()));
+        assert_eq!(
+            *sleeper.slept.lock().unwrap(),
+            vec![Duration::from_millis(100)]
+        );
+    }
+
+    #[test]
+    fn per_call_restore_delay_overrides_builder_default() {
+        let clipboard = MockClipboard::new(Some("old".to_string()));
+        let sleeper = MockSleeper::default();
+        let mut injector = Injector::builder(clipboard, MockTyper::default())
+            .restore_delay(Duration::from_millis(100))
+            .build()
+            .with_sleeper(sleeper.clone());
+        let options = InjectOptions {
+            restore_delay: Some(Duration::from_millis(250)),
+            ..Default::default()
+        };
+
+        injector.inject_text_with_options("new", &options).unwrap();
+        injector
+            .inject_text_with_options("newer", &InjectOptions::default())
+            .unwrap();
+
+        assert_eq!(
+            *sleeper.slept.lock().unwrap(),
+            vec![Duration::from_millis(250), Duration::from_millis(100)]
+        );
+    }
+
+    #[test]
+    fn builder_without_typing_fallback_reports_paste_error() {
+        let mut clipboard = MockClipboard::new(Some("old".to_string()));
+        clipboard.fail_paste = true;
+        let mut injector = Injector::builder(clipboard, MockTyper::default())
+            .typing_fallback(false)
+            .build();
+
+        let result = injector.inject_text("new");
+        let (clipboard, typer) = injector.into_parts();
+
+        assert_eq!(
+            result,
+            Err(InjectError::ClipboardPaste(ClipboardError::new(
+                "paste failed"
+            )))
+        );
+        assert_eq!(clipboard.content, Some("old".to_string()));
+        assert!(typer.typed.is_empty());
+    }
+
+    #[test]
+    fn builder_without_typing_fallback_reports_read_error() {
+        let mut clipboard = MockClipboard::new(Some("old".to_string()));
+        clipboard.fail_get = true;
+        let mut injector = Injector::builder(clipboard, MockTyper::default())
+            .typing_fallback(false)
+            .build();
+
+        let result = injector.inject_text("new");
+        let (clipboard, typer) = injector.into_parts();
+
+        assert_eq!(
+            result,
+            Err(InjectError::ClipboardRead(ClipboardError::new(
+                "get failed"
+            )))
+        );
+        assert_eq!(clipboard.ops, vec![Op::Get]);
+        assert!(typer.typed.is_empty());
+    }
+
+    #[test]
+    fn secure_mode_clears_clipboard_after_ttl_instead_of_restoring() {
+        let clipboard = MockClipboard::new(Some("old".to_string()));
+        let sleeper = MockSleeper::default();
+        let mut injector =
+            Injector::new(clipboard, MockTyper::default()).with_sleeper(sleeper.clone());
+        let options = InjectOptions {
+            secure: true,
+            clear_after: Some(Duration::from_secs(30)),
+            ..Default::default()
+        };
+
+        let outcome = injector
+            .inject_text_with_options("p@ssw0rd", &options)
+            .unwrap();
+
+        assert_eq!(outcome.outcome, InjectOutcome::Clipboard);
+        assert_eq!(
+            outcome.restore,
+            ClipboardRestore::ClearScheduled(Duration::from_secs(30))
+        );
+        assert!(sleeper.slept.lock().unwrap().is_empty());
+        assert_eq!(injector.clear_pending(), None);
+
+        let mut injector = injector.with_clock(StepClock::new(31_000));
+        assert_eq!(
+            injector.clear_pending(),
+            Some(ClipboardRestore::ClearedAfterTtl)
+        );
+        assert_eq!(injector.clear_pending(), None);
+        let (clipboard, _) = injector.into_parts();
+
+        assert_eq!(clipboard.content, None);
+        assert_eq!(
+            clipboard.ops,
+            vec![
+                Op::Set("p@ssw0rd".to_string()),
+                Op::Paste,
+                Op::Get,
+                Op::Clear
+            ]
+        );
+    }
+
+    #[test]
+    fn secure_clear_skips_clipboard_changed_after_injection() {
+        let clipboard = MockClipboard::new(Some("old".to_string()));
+        let mut injector =
+            Injector::new(clipboard, MockTyper::default()).with_clock(StepClock::new(1_000));
+        let options = InjectOptions {
+            secure: true,
+            clear_after: Some(Duration::from_secs(1)),
+            ..Default::default()
+        };
+
+        injector
+            .inject_text_with_options("p@ssw0rd", &options)
+            .unwrap();
+        injector.clipboard.content = Some("copied later".to_string());
+
+        assert_eq!(
+            injector.clear_pending(),
+            Some(ClipboardRestore::SkippedExternalChange)
+        );
+        let (clipboard, _) = injector.into_parts();
+        assert_eq!(clipboard.content, Some("copied later".to_string()));
+        assert!(!clipboard.ops.contains(&Op::Clear));
+    }
+
+    #[test]
+    fn secure_mode_without_ttl_restores_previous_clipboard() {
+        let clipboard = MockClipboard::new(Some("old".to_string()));
+        let mut injector = Injector::new(clipboard, MockTyper::default());
+        let options = InjectOptions {
+            secure: true,
+            ..Default::default()
+        };
+
+        let outcome = injector
+            .inject_text_with_options("token", &options)
+            .unwrap();
+        let (clipboard, _) = injector.into_parts();
+
+        assert_eq!(outcome.restore, ClipboardRestore::Restored);
+        assert_eq!(clipboard.content, Some("old".to_string()));
+    }
+
+    #[test]
+    fn clear_after_is_ignored_outside_secure_mode() {
+        let clipboard = MockClipboard::new(Some("old".to_string()));
+        let sleeper = MockSleeper::default();
+        let mut injector =
+            Injector::new(clipboard, MockTyper::default()).with_sleeper(sleeper.clone());
+        let options = InjectOptions {
+            clear_after: Some(Duration::from_secs(30)),
+            ..Default::default()
+        };
+
+        let outcome = injector.inject_text_with_options("text", &options).unwrap();
+
+        assert_eq!(outcome.restore, ClipboardRestore::Restored);
+        assert!(sleeper.slept.lock().unwrap().is_empty());
+    }
+
+    #[test]
+    fn retries_transient_paste_failure_before_typing() {
+        let mut clipboard = MockClipboard::new(Some("old".to_string()));
+        clipboard.paste_failures = 1;
+        let sleeper = MockSleeper::default();
+        let mut injector =
+            Injector::new(clipboard, MockTyper::default()).with_sleeper(sleeper.clone());
+        let options = InjectOptions {
+            paste_retries: 2,
+            paste_retry_delay: Duration::from_millis(50),
+            ..Default::default()
+        };
+
+        let outcome = injector.inject_text_with_options("new", &options).unwrap();
+        let (clipboard, typer) = injector.into_parts();
+
+        assert_eq!(outcome.outcome, InjectOutcome::Clipboard);
+        assert_eq!(outcome.paste_attempts, 2);
+        assert_eq!(outcome.restore, ClipboardRestore::Restored);
+        assert_eq!(
+            clipboard.ops,
+            vec![
+                Op::Get,
+                Op::Set("new".to_string()),
+                Op::Paste,
+                Op::Paste,
+                Op::Get,
+                Op::Set("old".to_string()),
+            ]
+        );
+        assert_eq!(
+            *sleeper.slept.lock().unwrap(),
+            vec![Duration::from_millis(50)]
+        );
+        assert!(typer.typed.is_empty());
+    }
+
+    #[test]
+    fn falls_back_to_typing_after_exhausting_paste_retries() {
+        let mut clipboard = MockClipboard::new(Some("old".to_string()));
+        clipboard.fail_paste = true;
+        let mut injector =
+            Injector::new(clipboard, MockTyper::default()).with_sleeper(MockSleeper::default());
+        let options = InjectOptions {
+            paste_retries: 2,
+            ..Default::default()
+        };
+
+        let outcome = injector.inject_text_with_options("new", &options).unwrap();
+        let (clipboard, typer) = injector.into_parts();
+
+        assert_eq!(outcome.outcome, InjectOutcome::TypedFallback);
+        assert_eq!(outcome.paste_attempts, 3);
+        assert_eq!(
+            clipboard.ops.iter().filter(|op| **op == Op::Paste).count(),
+            3
+        );
+        assert_eq!(typer.typed, vec!["new".to_string()]);
+    }
+
+    #[test]
+    fn max_paste_retries_terminate() {
+        let mut clipboard = MockClipboard::new(Some("old".to_string()));
+        clipboard.fail_paste = true;
+        let mut injector =
+            Injector::new(clipboard, MockTyper::default()).with_sleeper(MockSleeper::default());
+        let options = InjectOptions {
+            paste_retries: u8::MAX,
+            ..Default::default()
+        };
+
+        let outcome = injector.inject_text_with_options("new", &options).unwrap();
+        let (clipboard, typer) = injector.into_parts();
+
+        assert_eq!(outcome.outcome, InjectOutcome::TypedFallback);
+        assert_eq!(outcome.paste_attempts, u16::from(u8::MAX) + 1);
+        assert_eq!(
+            clipboard.ops.iter().filter(|op| **op == Op::Paste).count(),
+            usize::from(u8::MAX) + 1
+        );
+        assert_eq!(typer.typed, vec!["new".to_string()]);
+    }
+
+    #[test]
+    fn sets_transformed_text_on_clipboard() {
+        let clipboard = MockClipboard::new(None);
+        let mut injector = Injector::builder(clipboard, MockTyper::default())
+            .transform(NormalizeNfc)
+            .transform(AsciiQuotes)
+            .transform(StripTrailingNewline)
+            .build();
+
+        injector
+            .inject_text("\u{201C}cafe\u{301}\u{201D}\n")
+            .unwrap();
+        let (clipboard, _) = injector.into_parts();
+
+        assert_eq!(clipboard.ops[1], Op::Set("\"caf\u{e9}\"".to_string()));
+    }
+
+    #[test]
+    fn types_transformed_text_in_fallback() {
+        let mut clipboard = MockClipboard::new(None);
+        clipboard.fail_paste = true;
+        let mut injector = Injector::new(clipboard, MockTyper::default())
+            .with_transform(AsciiQuotes)
+            .with_transform(CollapseWhitespace);
+
+        let outcome = injector.inject_text("it\u{2019}s  \u{a0}done").unwrap();
+        let (clipboard, typer) = injector.into_parts();
+
+        assert_eq!(outcome.outcome, InjectOutcome::TypedFallback);
+        assert_eq!(clipboard.ops[1], Op::Set("it's done".to_string()));
+        assert_eq!(typer.typed, vec!["it's done".to_string()]);
+    }
+
+    #[test]
+    fn secure_mode_runs_the_transform_chain() {
+        let clipboard = MockClipboard::new(Some("old".to_string()));
+        let mut injector = Injector::new(clipboard, MockTyper::default())
+            .with_transform(NormalizeNfc)
+            .with_transform(AsciiQuotes)
+            .with_transform(CollapseWhitespace)
+            .with_transform(StripTrailingNewline);
+        let options = InjectOptions {
+            secure: true,
+            clear_after: Some(Duration::from_secs(30)),
+            ..Default::default()
+        };
+
+        let transformed = injector.apply_transforms("\u{201c}cafe\u{301}\u{201d}  key\n");
+        assert!(transformed == *"\"caf\u{e9}\" key");
+        assert_eq!(format!("{transformed:?}"), "SecretString(<redacted>)");
+
+        let outcome = injector
+            .inject_text_with_options("\u{201c}cafe\u{301}\u{201d}  key\n", &options)
+            .unwrap();
+        let (clipboard, _) = injector.into_parts();
+
+        assert_eq!(
+            outcome.restore,
+            ClipboardRestore::ClearScheduled(Duration::from_secs(30))
+        );
+        assert_eq!(
+            clipboard.ops,
+            vec![Op::Set("\"caf\u{e9}\" key".to_string()), Op::Paste]
+        );
+    }
+
+    #[test]
+    fn injects_html_and_restores_plain_snapshot() {
+        let mut clipboard = MockClipboard::new(Some("old".to_string()));
+        clipboard.html_supported = true;
+        let mut injector = Injector::new(clipboard, MockTyper::default());
+
+        let outcome = injector
+            .inject_html("<p>one</p><p>two</p>", "one\n\ntwo")
+            .unwrap();
+        let (clipboard, typer) = injector.into_parts();
+
+        assert_eq!(outcome.outcome, InjectOutcome::Clipboard);
+        assert_eq!(outcome.restore, ClipboardRestore::Restored);
+        assert_eq!(clipboard.content, Some("old".to_string()));
+        assert_eq!(
+            clipboard.ops,
+            vec![
+                Op::Get,
+                Op::SetHtml("<p>one</p><p>two</p>".to_string(), "one\n\ntwo".to_string()),
+                Op::Paste,
+                Op::Get,
+                Op::Set("old".to_string()),
+            ]
+        );
+        assert!(typer.typed.is_empty());
+    }
+
+    #[test]
+    fn html_falls_back_to_plain_text_when_unsupported() {
+        let clipboard = MockClipboard::new(None);
+        let mut injector = Injector::new(clipboard, MockTyper::default());
+
+        let outcome = injector.inject_html("<b>hi</b>", "hi").unwrap();
+        let (clipboard, _) = injector.into_parts();
+
+        assert_eq!(outcome.outcome, InjectOutcome::Clipboard);
+        assert_eq!(outcome.restore, ClipboardRestore::Restored);
+        assert_eq!(outcome.attempts, 1);
+        assert_eq!(
+            clipboard.ops,
+            vec![
+                Op::Get,
+                Op::Set("hi".to_string()),
+                Op::Paste,
+                Op::Get,
+                Op::Clear,
+            ]
+        );
+    }
+
+    #[test]
+    fn html_set_failure_is_reported_instead_of_retried_as_text() {
+        let mut clipboard = MockClipboard::new(Some("old".to_string()));
+        clipboard.html_supported = true;
+        clipboard.fail_set = true;
+        let mut injector = Injector::new(clipboard, MockTyper::default());
+
+        let outcome = injector.inject_html("<b>hi</b>", "hi").unwrap();
+        let (clipboard, typer) = injector.into_parts();
+
+        assert_eq!(outcome.outcome, InjectOutcome::TypedFallback);
+        assert_eq!(outcome.attempts, 1);
+        assert!(!clipboard.ops.contains(&Op::Set("hi".to_string())));
+        assert_eq!(typer.typed, vec!["hi".to_string()]);
+        assert!(ClipboardError::html_unsupported().is_html_unsupported());
+        assert!(!ClipboardError::new("set failed").is_html_unsupported());
+    }
+
+    #[test]
+    fn html_paste_failure_types_plain_fallback() {
+        let mut clipboard = MockClipboard::new(Some("old".to_string()));
+        clipboard.html_supported = true;
+        clipboard.fail_paste = true;
+        let mut injector = Injector::new(clipboard, MockTyper::default());
+
+        let outcome = injector.inject_html("<b>hi</b>", "hi").unwrap();
+        let (clipboard, typer) = injector.into_parts();
+
+        assert_eq!(outcome.outcome, InjectOutcome::TypedFallback);
+        assert_eq!(outcome.restore, ClipboardRestore::Restored);
+        assert_eq!(clipboard.content, Some("old".to_string()));
+        assert_eq!(typer.typed, vec!["hi".to_string()]);
+    }
 }
@@ -0,0 +1,74 @@
+use shared_types::PasteShortcut;
+
+fn modifiers(shortcut: PasteShortcut) -> &'static [&'static str] {
+    match shortcut {
+        PasteShortcut::CtrlV => &["ctrl"],
+        PasteShortcut::CtrlShiftV => &["ctrl", "shift"],
+        PasteShortcut::ShiftInsert => &["shift"],
+    }
+}
+
+fn key(shortcut: PasteShortcut) -> &'static str {
+    match shortcut {
+        PasteShortcut::CtrlV | PasteShortcut::CtrlShiftV => "v",
+        PasteShortcut::ShiftInsert => "Insert",
+    }
+}
+
+pub fn xdotool_paste_args(shortcut: PasteShortcut) -> Vec<String> {
+    let mut combo: Vec<&str> = modifiers(shortcut).to_vec();
+    combo.push(key(shortcut));
+    vec![
+        "key".to_string(),
+        "--clearmodifiers".to_string(),
+        combo.join("+"),
+    ]
+}
+
+pub fn wtype_paste_args(shortcut: PasteShortcut) -> Vec<String> {
+    let modifiers = modifiers(shortcut);
+    let press = modifiers.iter().flat_map(|modifier| ["-M", modifier]);
+    let release = modifiers.iter().rev().flat_map(|modifier| ["-m", modifier]);
+    press
+        .chain(["-k", key(shortcut)])
+        .chain(release)
+        .map(str::to_string)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn xdotool_args_join_the_combo() {
+        assert_eq!(
+            xdotool_paste_args(PasteShortcut::CtrlV),
+            vec!["key", "--clearmodifiers", "ctrl+v"]
+        );
+        assert_eq!(
+            xdotool_paste_args(PasteShortcut::CtrlShiftV),
+            vec!["key", "--clearmodifiers", "ctrl+shift+v"]
+        );
+        assert_eq!(
+            xdotool_paste_args(PasteShortcut::ShiftInsert),
+            vec!["key", "--clearmodifiers", "shift+Insert"]
+        );
+    }
+
+    #[test]
+    fn wtype_args_release_modifiers_in_reverse() {
+        assert_eq!(
+            wtype_paste_args(PasteShortcut::CtrlV),
+            vec!["-M", "ctrl", "-k", "v", "-m", "ctrl"]
+        );
+        assert_eq!(
+            wtype_paste_args(PasteShortcut::CtrlShiftV),
+            vec!["-M", "ctrl", "-M", "shift", "-k", "v", "-m", "shift", "-m", "ctrl"]
+        );
+        assert_eq!(
+            wtype_paste_args(PasteShortcut::ShiftInsert),
+            vec!["-M", "shift", "-k", "Insert", "-m", "shift"]
+        );
+    }
+}
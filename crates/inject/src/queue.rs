@@ -0,0 +1,306 @@
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError, Sender};
+use std::sync::Arc;
+use std::thread::{self, JoinHandle};
+use std::time::Duration;
+
+use crate::{
+    Clipboard, ClipboardRestore, InjectError, InjectOptions, InjectResult, Injector, Typer,
+};
+
+type InjectReply = Sender<Result<InjectResult, InjectError>>;
+
+enum QueueMessage {
+    Inject {
+        text: String,
+        options: InjectOptions,
+        generation: u64,
+        reply: InjectReply,
+    },
+    Drain(Sender<()>),
+}
+
+pub struct InjectionQueue {
+    sender: Option<Sender<QueueMessage>>,
+    generation: Arc<AtomicU64>,
+    handle: Option<JoinHandle<()>>,
+}
+
+impl InjectionQueue {
+    pub fn new<C, T>(injector: Injector<C, T>, gap: Duration) -> Self
+    where
+        C: Clipboard + Send + 'static,
+        T: Typer + Send + 'static,
+    {
+        let (sender, receiver) = mpsc::channel();
+        let generation = Arc::new(AtomicU64::new(0));
+        let worker_generation = Arc::clone(&generation);
+        let handle = thread::spawn(move || {
+            run_worker(injector, receiver, worker_generation, gap);
+        });
+
+        Self {
+            sender: Some(sender),
+            generation,
+            handle: Some(handle),
+        }
+    }
+
+    pub fn enqueue(&self, text: impl Into<String>) -> Receiver<Result<InjectResult, InjectError>> {
+        self.enqueue_with_options(text, InjectOptions::default())
+    }
+
+    pub fn enqueue_with_options(
+        &self,
+        text: impl Into<String>,
+        options: InjectOptions,
+    ) -> Receiver<Result<InjectResult, InjectError>> {
+        let (reply, receiver) = mpsc::channel();
+        if let Some(sender) = &self.sender {
+            let _ = sender.send(QueueMessage::Inject {
+                text: text.into(),
+                options,
+                generation: self.generation.load(Ordering::SeqCst),
+                reply,
+            });
+        }
+        receiver
+    }
+
+    pub fn drain(&self) {
+        let Some(sender) = &self.sender else {
+            return;
+        };
+        let (ack, done) = mpsc::channel();
+        if sender.send(QueueMessage::Drain(ack)).is_ok() {
+            let _ = done.recv();
+        }
+    }
+
+    pub fn cancel_pending(&self) {
+        self.generation.fetch_add(1, Ordering::SeqCst);
+    }
+}
+
+impl Drop for InjectionQueue {
+    fn drop(&mut self) {
+        self.cancel_pending();
+        self.sender.take();
+        if let Some(handle) = self.handle.take() {
+            let _ = handle.join();
+        }
+    }
+}
+
+fn run_worker<C, T>(
+    mut injector: Injector<C, T>,
+    receiver: Receiver<QueueMessage>,
+    generation: Arc<AtomicU64>,
+    gap: Duration,
+) where
+    C: Clipboard,
+    T: Typer,
+{
+    let mut injected_before = false;
+    loop {
+        // Wake up for a scheduled secure clear even when no jobs arrive.
+        let message = match injector.clear_due_in() {
+            Some(due) => match receiver.recv_timeout(due) {
+                Ok(message) => message,
+                Err(RecvTimeoutError::Timeout) => {
+                    clear_pending(&mut injector);
+                    continue;
+                }
+                Err(RecvTimeoutError::Disconnected) => break,
+            },
+            None => match receiver.recv() {
+                Ok(message) => message,
+                Err(_) => break,
+            },
+        };
+        match message {
+            QueueMessage::Inject {
+                text,
+                options,
+                generation: job_generation,
+                reply,
+            } => {
+                if job_generation != generation.load(Ordering::SeqCst) {
+                    continue;
+                }
+                if injected_before && !gap.is_zero() {
+                    thread::sleep(gap);
+                }
+                let result = injector.inject_text_with_options(&text, &options);
+                injected_before = true;
+                let _ = reply.send(result);
+            }
+            QueueMessage::Drain(ack) => {
+                let _ = ack.send(());
+            }
+        }
+    }
+    clear_pending(&mut injector);
+}
+
+fn clear_pending<C: Clipboard, T: Typer>(injector: &mut Injector<C, T>) {
+    if let Some(ClipboardRestore::Failed(err)) = injector.clear_pending() {
+        log::warn!(
+            "failed to clear the clipboard after its TTL: {}",
+            err.message()
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{ClipboardError, InjectOutcome, TypingError};
+    use std::sync::Mutex;
+
+    #[derive(Clone, Default)]
+    struct SharedClipboard {
+        sets: Arc<Mutex<Vec<String>>>,
+        content: Arc<Mutex<Option<String>>>,
+    }
+
+    impl Clipboard for SharedClipboard {
+        fn get_text(&mut self) -> Result<Option<String>, ClipboardError> {
+            Ok(self.content.lock().unwrap().clone())
+        }
+
+        fn set_text(&mut self, text: &str) -> Result<(), ClipboardError> {
+            self.sets.lock().unwrap().push(text.to_string());
+            *self.content.lock().unwrap() = Some(text.to_string());
+            Ok(())
+        }
+
+        fn clear(&mut self) -> Result<(), ClipboardError> {
+            *self.content.lock().unwrap() = None;
+            Ok(())
+        }
+
+        fn paste(&mut self) -> Result<(), ClipboardError> {
+            Ok(())
+        }
+    }
+
+    struct GatedClipboard {
+        inner: SharedClipboard,
+        started: Sender<()>,
+        gate: Receiver<()>,
+    }
+
+    impl Clipboard for GatedClipboard {
+        fn get_text(&mut self) -> Result<Option<String>, ClipboardError> {
+            self.inner.get_text()
+        }
+
+        fn set_text(&mut self, text: &str) -> Result<(), ClipboardError> {
+            self.inner.set_text(text)
+        }
+
+        fn clear(&mut self) -> Result<(), ClipboardError> {
+            self.inner.clear()
+        }
+
+        fn paste(&mut self) -> Result<(), ClipboardError> {
+            let _ = self.started.send(());
+            let _ = self.gate.recv();
+            Ok(())
+        }
+    }
+
+    struct NoopTyper;
+
+    impl Typer for NoopTyper {
+        fn type_text(&mut self, _text: &str) -> Result<(), TypingError> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn injects_in_submission_order() {
+        let clipboard = SharedClipboard::default();
+        let sets = Arc::clone(&clipboard.sets);
+        let queue = InjectionQueue::new(Injector::new(clipboard, NoopTyper), Duration::ZERO);
+
+        let receivers: Vec<_> = ["first", "second", "third"]
+            .into_iter()
+            .map(|text| queue.enqueue(text))
+            .collect();
+        queue.drain();
+
+        for receiver in receivers {
+            let result = receiver.recv().unwrap().unwrap();
+            assert_eq!(result.outcome, InjectOutcome::Clipboard);
+        }
+        assert_eq!(*sets.lock().unwrap(), vec!["first", "second", "third"]);
+    }
+
+    #[test]
+    fn cancel_pending_skips_queued_jobs() {
+        let shared = SharedClipboard::default();
+        let sets = Arc::clone(&shared.sets);
+        let (started_tx, started_rx) = mpsc::channel();
+        let (gate_tx, gate_rx) = mpsc::channel();
+        let clipboard = GatedClipboard {
+            inner: shared,
+            started: started_tx,
+            gate: gate_rx,
+        };
+        let queue = InjectionQueue::new(Injector::new(clipboard, NoopTyper), Duration::ZERO);
+
+        let first = queue.enqueue("first");
+        started_rx.recv().unwrap();
+        let second = queue.enqueue("second");
+        let third = queue.enqueue("third");
+        queue.cancel_pending();
+        gate_tx.send(()).unwrap();
+        queue.drain();
+
+        assert!(first.recv().unwrap().is_ok());
+        assert!(second.recv().is_err());
+        assert!(third.recv().is_err());
+        assert_eq!(*sets.lock().unwrap(), vec!["first".to_string()]);
+
+        let fourth = queue.enqueue("fourth");
+        gate_tx.send(()).unwrap();
+        assert!(fourth.recv().unwrap().is_ok());
+    }
+
+    #[test]
+    fn worker_clears_secure_text_once_its_ttl_passes() {
+        let clipboard = SharedClipboard::default();
+        let content = Arc::clone(&clipboard.content);
+        let queue = InjectionQueue::new(Injector::new(clipboard, NoopTyper), Duration::ZERO);
+        let options = InjectOptions {
+            secure: true,
+            clear_after: Some(Duration::from_millis(20)),
+            ..Default::default()
+        };
+
+        let result = queue.enqueue_with_options("p@ssw0rd", options);
+        assert_eq!(
+            result.recv().unwrap().unwrap().restore,
+            ClipboardRestore::ClearScheduled(Duration::from_millis(20))
+        );
+        let deadline = std::time::Instant::now() + Duration::from_secs(5);
+        while content.lock().unwrap().is_some() && std::time::Instant::now() < deadline {
+            thread::sleep(Duration::from_millis(5));
+        }
+
+        assert_eq!(*content.lock().unwrap(), None);
+    }
+
+    #[test]
+    fn drop_shuts_down_worker() {
+        let clipboard = SharedClipboard::default();
+        let queue = InjectionQueue::new(Injector::new(clipboard, NoopTyper), Duration::ZERO);
+        let receiver = queue.enqueue("text");
+        queue.drain();
+        drop(queue);
+
+        assert!(receiver.recv().unwrap().is_ok());
+    }
+}
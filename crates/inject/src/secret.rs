@@ -0,0 +1,96 @@
+use std::fmt;
+use std::sync::atomic::{compiler_fence, Ordering};
+
+#[derive(Default)]
+pub struct SecretString(String);
+
+impl SecretString {
+    pub fn new(value: String) -> Self {
+        Self(value)
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    pub fn wipe(&mut self) {
+        // SAFETY: zero bytes are valid UTF-8, so the string stays well-formed.
+        let bytes = unsafe { self.0.as_mut_vec() };
+        // Extend over the spare capacity too; it can hold bytes from earlier
+        // contents and resizing within capacity never reallocates.
+        bytes.resize(bytes.capacity(), 0);
+        for byte in bytes.iter_mut() {
+            // SAFETY: `byte` is a valid, aligned, exclusive reference into the buffer.
+            unsafe { std::ptr::write_volatile(byte, 0) };
+        }
+        compiler_fence(Ordering::SeqCst);
+        bytes.clear();
+    }
+}
+
+impl From<String> for SecretString {
+    fn from(value: String) -> Self {
+        Self::new(value)
+    }
+}
+
+impl PartialEq<str> for SecretString {
+    fn eq(&self, other: &str) -> bool {
+        self.0 == other
+    }
+}
+
+impl fmt::Debug for SecretString {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("SecretString(<redacted>)")
+    }
+}
+
+impl Drop for SecretString {
+    fn drop(&mut self) {
+        self.wipe();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn wipe_zeroes_buffer_in_place() {
+        let mut secret = SecretString::from("hunter2".to_string());
+        let ptr = secret.0.as_ptr();
+        let len = secret.0.len();
+
+        secret.wipe();
+
+        assert!(secret.as_str().is_empty());
+        assert_eq!(secret.0.as_ptr(), ptr);
+        let wiped = unsafe { std::slice::from_raw_parts(ptr, len) };
+        assert!(wiped.iter().all(|byte| *byte == 0));
+    }
+
+    #[test]
+    fn wipe_zeroes_spare_capacity() {
+        let mut value = String::with_capacity(32);
+        value.push_str("hunter2-and-more");
+        value.truncate(6);
+        let mut secret = SecretString::from(value);
+        let ptr = secret.0.as_ptr();
+        let capacity = secret.0.capacity();
+
+        secret.wipe();
+
+        assert_eq!(secret.0.capacity(), capacity);
+        let wiped = unsafe { std::slice::from_raw_parts(ptr, capacity) };
+        assert!(wiped.iter().all(|byte| *byte == 0));
+    }
+
+    #[test]
+    fn debug_output_is_redacted() {
+        let secret = SecretString::from("api-key".to_string());
+
+        assert_eq!(format!("{secret:?}"), "SecretString(<redacted>)");
+        assert!(secret == *"api-key");
+    }
+}
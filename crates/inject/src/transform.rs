@@ -0,0 +1,125 @@
+use unicode_normalization::UnicodeNormalization;
+
+pub trait TextTransform {
+    fn apply(&self, text: &str) -> String;
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+pub struct NormalizeNfc;
+
+impl TextTransform for NormalizeNfc {
+    fn apply(&self, text: &str) -> String {
+        text.nfc().collect()
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+pub struct AsciiQuotes;
+
+impl TextTransform for AsciiQuotes {
+    fn apply(&self, text: &str) -> String {
+        text.chars()
+            .map(|ch| match ch {
+                '\u{2018}' | '\u{2019}' | '\u{201A}' | '\u{201B}' | '\u{2032}' => '\'',
+                '\u{201C}' | '\u{201D}' | '\u{201E}' | '\u{201F}' | '\u{2033}' | '\u{00AB}'
+                | '\u{00BB}' => '"',
+                other => other,
+            })
+            .collect()
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+pub struct AsciiDashes;
+
+impl TextTransform for AsciiDashes {
+    fn apply(&self, text: &str) -> String {
+        let mut output = String::with_capacity(text.len());
+        for ch in text.chars() {
+            match ch {
+                '\u{2010}' | '\u{2011}' | '\u{2012}' | '\u{2013}' | '\u{2212}' => output.push('-'),
+                '\u{2014}' | '\u{2015}' => output.push_str("--"),
+                '\u{2026}' => output.push_str("..."),
+                other => output.push(other),
+            }
+        }
+        output
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+pub struct CollapseWhitespace;
+
+impl TextTransform for CollapseWhitespace {
+    fn apply(&self, text: &str) -> String {
+        let mut output = String::with_capacity(text.len());
+        let mut pending_space = false;
+        for ch in text.chars() {
+            if ch == '\n' {
+                pending_space = false;
+                output.push(ch);
+            } else if ch.is_whitespace() {
+                pending_space = true;
+            } else {
+                if pending_space && !output.is_empty() && !output.ends_with('\n') {
+                    output.push(' ');
+                }
+                pending_space = false;
+                output.push(ch);
+            }
+        }
+        output
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+pub struct StripTrailingNewline;
+
+impl TextTransform for StripTrailingNewline {
+    fn apply(&self, text: &str) -> String {
+        text.trim_end_matches(['\n', '\r']).to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn composes_combining_characters() {
+        let decomposed = "cafe\u{301} nai\u{308}ve";
+
+        assert_eq!(NormalizeNfc.apply(decomposed), "caf\u{e9} na\u{ef}ve");
+    }
+
+    #[test]
+    fn replaces_curly_quotes() {
+        let text = "\u{201C}It\u{2019}s fine,\u{201D} she said \u{2018}twice\u{2019}";
+
+        assert_eq!(AsciiQuotes.apply(text), "\"It's fine,\" she said 'twice'");
+    }
+
+    #[test]
+    fn replaces_dashes_and_ellipsis() {
+        assert_eq!(
+            AsciiDashes.apply("wait\u{2014}what\u{2026} 1\u{2013}2"),
+            "wait--what... 1-2"
+        );
+    }
+
+    #[test]
+    fn collapses_whitespace_but_keeps_newlines() {
+        let text = "  hello \u{a0}\t world\n  next   line ";
+
+        assert_eq!(CollapseWhitespace.apply(text), "hello world\nnext line");
+    }
+
+    #[test]
+    fn strips_trailing_newlines_only() {
+        assert_eq!(
+            StripTrailingNewline.apply("line one\nline two\r\n\n"),
+            "line one\nline two"
+        );
+        assert_eq!(StripTrailingNewline.apply("\nkeep"), "\nkeep");
+    }
+}
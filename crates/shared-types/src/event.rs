@@ -0,0 +1,100 @@
+use serde::{Deserialize, Serialize};
+use std::{
+    sync::atomic::{AtomicU64, Ordering},
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct EventEnvelope<T> {
+    pub seq: u64,
+    pub emitted_at_ms: u128,
+    pub payload: T,
+}
+
+#[derive(Debug, Default)]
+pub struct EventSequencer {
+    last: AtomicU64,
+}
+
+impl EventSequencer {
+    pub const fn new() -> Self {
+        Self {
+            last: AtomicU64::new(0),
+        }
+    }
+
+    pub fn next_seq(&self) -> u64 {
+        self.last.fetch_add(1, Ordering::SeqCst) + 1
+    }
+
+    pub fn current(&self) -> u64 {
+        self.last.load(Ordering::SeqCst)
+    }
+
+    pub fn wrap<T>(&self, payload: T) -> EventEnvelope<T> {
+        let emitted_at_ms = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_millis();
+        EventEnvelope {
+            seq: self.next_seq(),
+            emitted_at_ms,
+            payload,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::PttState;
+    use std::{sync::Arc, thread};
+
+    #[test]
+    fn sequence_starts_at_one_and_tracks_current() {
+        let sequencer = EventSequencer::new();
+        assert_eq!(sequencer.current(), 0);
+
+        assert_eq!(sequencer.wrap("a").seq, 1);
+        assert_eq!(sequencer.wrap("b").seq, 2);
+        assert_eq!(sequencer.current(), 2);
+    }
+
+    #[test]
+    fn sequence_is_unique_and_monotonic_across_threads() {
+        let sequencer = Arc::new(EventSequencer::new());
+        let handles: Vec<_> = (0..4)
+            .map(|_| {
+                let sequencer = Arc::clone(&sequencer);
+                thread::spawn(move || (0..250).map(|_| sequencer.next_seq()).collect::<Vec<_>>())
+            })
+            .collect();
+
+        let mut all = Vec::new();
+        for handle in handles {
+            let seqs = handle.join().unwrap();
+            assert!(seqs.windows(2).all(|pair| pair[0] < pair[1]));
+            all.extend(seqs);
+        }
+        all.sort_unstable();
+        assert_eq!(all, (1..=1000).collect::<Vec<_>>());
+        assert_eq!(sequencer.current(), 1000);
+    }
+
+    #[test]
+    fn envelope_serde_shape_is_stable() {
+        let envelope = EventEnvelope {
+            seq: 42,
+            emitted_at_ms: 1_700_000_000_000,
+            payload: PttState::Armed,
+        };
+
+        let json = serde_json::to_string(&envelope).unwrap();
+        assert_eq!(
+            json,
+            r#"{"seq":42,"emitted_at_ms":1700000000000,"payload":"armed"}"#
+        );
+        let decoded: EventEnvelope<PttState> = serde_json::from_str(&json).unwrap();
+        assert_eq!(decoded, envelope);
+    }
+}
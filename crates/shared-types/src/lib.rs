@@ -1,4 +1,27 @@
+mod event;
+mod migration;
+mod model_status;
+mod transcript;
+mod validation;
+
+pub use event::{EventEnvelope, EventSequencer};
+pub use migration::{
+    migrate_from_value, settings_schema_version, MigrationError, SETTINGS_SCHEMA_VERSION,
+};
+pub use model_status::{format_bytes, format_eta};
+pub use transcript::{
+    TranscriptEntry, TranscriptHistory, TranscriptMetrics, TranscriptSegment, TranscriptionStats,
+    DEFAULT_TRANSCRIPT_HISTORY_CAPACITY, TRANSCRIPTION_STATS_WINDOW,
+};
+pub use validation::{
+    is_reserved_whisper_arg, is_supported_language, SettingsValidationError, MAX_INPUT_GAIN_DB,
+    MAX_LATENCY_MS, MAX_LEVEL_INTERVAL_MS, MAX_MIN_RECORDING_MS, MAX_RECORDING_SECONDS,
+    MAX_SILENCE_THRESHOLD_DBFS, MIN_INPUT_GAIN_DB, MIN_LATENCY_MS, MIN_LEVEL_INTERVAL_MS,
+    MIN_RECORDING_SECONDS, MIN_SILENCE_THRESHOLD_DBFS, RESERVED_WHISPER_ARGS, SUPPORTED_LANGUAGES,
+};
+
 use serde::{Deserialize, Serialize};
+use std::{fmt, str::FromStr};
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 #[serde(rename_all = "snake_case")]
@@ -9,6 +32,29 @@ pub enum BackendState {
     Error { message: String },
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(from = "TimedStateRepr")]
+pub struct TimedState {
+    pub state: BackendState,
+    pub since_ms: u64,
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum TimedStateRepr {
+    Timed { state: BackendState, since_ms: u64 },
+    Bare(BackendState),
+}
+
+impl From<TimedStateRepr> for TimedState {
+    fn from(repr: TimedStateRepr) -> Self {
+        match repr {
+            TimedStateRepr::Timed { state, since_ms } => Self { state, since_ms },
+            TimedStateRepr::Bare(state) => Self { state, since_ms: 0 },
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 #[serde(rename_all = "snake_case")]
 pub enum BackendEvent {
@@ -35,7 +81,7 @@ pub enum PttState {
     Idle,
     Armed,
     Capturing,
-    Processing,
+    Processing { model: String, started_ms: u64 },
     Error { message: String },
 }
 
@@ -43,6 +89,10 @@ pub enum PttState {
 pub struct PttLevel {
     pub rms: f32,
     pub peak: f32,
+    #[serde(default)]
+    pub smoothed_rms: f32,
+    #[serde(default)]
+    pub peak_hold: f32,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
@@ -61,6 +111,54 @@ pub enum OutputMode {
     UiOnly,
     Clipboard,
     DirectWrite,
+    Paste,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum PttMode {
+    #[default]
+    HoldToTalk,
+    Toggle,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum AudioSource {
+    #[default]
+    Microphone,
+    SystemAudio,
+    Both,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct WordReplacement {
+    pub from: String,
+    pub to: String,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum PasteShortcut {
+    #[default]
+    CtrlV,
+    CtrlShiftV,
+    ShiftInsert,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct PasteProfile {
+    pub window_class: String,
+    pub shortcut: PasteShortcut,
+}
+
+impl PasteProfile {
+    pub fn new(window_class: &str, shortcut: PasteShortcut) -> Self {
+        Self {
+            window_class: window_class.to_string(),
+            shortcut,
+        }
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Default)]
@@ -113,14 +211,132 @@ pub enum OverlayPosition {
     Docked,
     Floating,
     Compact,
+    Custom {
+        x: i32,
+        y: i32,
+        anchor: OverlayAnchor,
+    },
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum OverlayAnchor {
+    #[default]
+    TopLeft,
+    TopRight,
+    BottomLeft,
+    BottomRight,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct HotkeyConfig {
+    pub key: String,
+    #[serde(default)]
+    pub ctrl: bool,
+    #[serde(default)]
+    pub alt: bool,
+    #[serde(default)]
+    pub shift: bool,
+    #[serde(default)]
+    pub meta: bool,
+}
+
+impl Default for HotkeyConfig {
+    fn default() -> Self {
+        Self {
+            key: "space".to_string(),
+            ctrl: true,
+            alt: true,
+            shift: false,
+            meta: false,
+        }
+    }
+}
+
+impl HotkeyConfig {
+    pub fn normalized(&self) -> Self {
+        Self {
+            key: self.key.trim().to_ascii_lowercase(),
+            ..self.clone()
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(untagged)]
+pub enum InputDeviceSelection {
+    Single(String),
+    Mixed(Vec<String>),
+}
+
+impl InputDeviceSelection {
+    pub fn devices(&self) -> Vec<&str> {
+        match self {
+            Self::Single(device) => vec![device.as_str()],
+            Self::Mixed(devices) => devices.iter().map(String::as_str).collect(),
+        }
+    }
+
+    pub fn is_default(&self) -> bool {
+        self.devices() == ["default"]
+    }
+
+    pub fn is_mixed(&self) -> bool {
+        self.devices().len() > 1
+    }
+
+    pub fn is_blank(&self) -> bool {
+        let devices = self.devices();
+        devices.is_empty() || devices.iter().any(|device| device.trim().is_empty())
+    }
+}
+
+impl Default for InputDeviceSelection {
+    fn default() -> Self {
+        Self::Single("default".to_string())
+    }
+}
+
+impl From<&str> for InputDeviceSelection {
+    fn from(device: &str) -> Self {
+        Self::Single(device.to_string())
+    }
+}
+
+impl From<String> for InputDeviceSelection {
+    fn from(device: String) -> Self {
+        Self::Single(device)
+    }
+}
+
+impl From<Vec<String>> for InputDeviceSelection {
+    fn from(devices: Vec<String>) -> Self {
+        Self::Mixed(devices)
+    }
+}
+
+impl PartialEq<&str> for InputDeviceSelection {
+    fn eq(&self, other: &&str) -> bool {
+        matches!(self, Self::Single(device) if device == other)
+    }
+}
+
+impl fmt::Display for InputDeviceSelection {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.devices().join(" + "))
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 #[serde(rename_all = "snake_case")]
 pub struct AppSettings {
-    pub input_device: String,
+    #[serde(default)]
+    pub schema_version: u32,
+    pub input_device: InputDeviceSelection,
     pub noise_reduction: bool,
     pub auto_language: bool,
+    #[serde(default)]
+    pub language: Option<String>,
     pub latency_ms: u16,
     pub auto_export: bool,
     #[serde(default)]
@@ -128,34 +344,166 @@ pub struct AppSettings {
     pub overlay_position: OverlayPosition,
     pub show_timestamps: bool,
     pub auto_punctuation: bool,
+    #[serde(default)]
+    pub hotkey: HotkeyConfig,
+    #[serde(default)]
+    pub ptt_mode: PttMode,
+    #[serde(default = "default_cancel_hotkey")]
+    pub cancel_hotkey: HotkeyConfig,
+    #[serde(default = "default_max_recording_seconds")]
+    pub max_recording_seconds: u32,
+    #[serde(default)]
+    pub silence_auto_stop: bool,
+    #[serde(default = "default_silence_threshold_dbfs")]
+    pub silence_threshold_dbfs: f32,
+    #[serde(default = "default_min_recording_ms")]
+    pub min_recording_ms: u32,
+    #[serde(default = "default_level_interval_ms")]
+    pub level_interval_ms: u32,
+    #[serde(default)]
+    pub input_gain_db: f32,
+    #[serde(default)]
+    pub auto_gain: bool,
+    #[serde(default)]
+    pub save_recordings: bool,
+    #[serde(default)]
+    pub audio_source: AudioSource,
+    #[serde(default)]
+    pub translate: bool,
+    #[serde(default)]
+    pub custom_vocabulary: Vec<String>,
+    #[serde(default)]
+    pub whisper_threads: Option<u8>,
+    #[serde(default)]
+    pub whisper_extra_args: Vec<String>,
+    #[serde(default)]
+    pub partial_transcription: bool,
+    #[serde(default)]
+    pub spoken_punctuation: bool,
+    #[serde(default)]
+    pub replacements: Vec<WordReplacement>,
+    #[serde(default = "default_restore_clipboard")]
+    pub restore_clipboard: bool,
+    #[serde(default = "default_paste_profiles")]
+    pub paste_profiles: Vec<PasteProfile>,
+    #[serde(default)]
+    pub sound_feedback: bool,
+    #[serde(default)]
+    pub notify_on_complete: bool,
+    #[serde(default)]
+    pub active_model: Option<String>,
+    #[serde(default)]
+    pub consume_hotkey: bool,
+}
+
+fn default_max_recording_seconds() -> u32 {
+    120
+}
+
+fn default_silence_threshold_dbfs() -> f32 {
+    -45.0
+}
+
+fn default_min_recording_ms() -> u32 {
+    300
+}
+
+fn default_level_interval_ms() -> u32 {
+    50
+}
+
+fn default_cancel_hotkey() -> HotkeyConfig {
+    HotkeyConfig {
+        key: "escape".to_string(),
+        ctrl: false,
+        alt: false,
+        shift: false,
+        meta: false,
+    }
+}
+
+fn default_restore_clipboard() -> bool {
+    true
+}
+
+pub fn default_paste_profiles() -> Vec<PasteProfile> {
+    let terminals = [
+        "*terminal*",
+        "*konsole*",
+        "kitty",
+        "alacritty",
+        "foot",
+        "*wezterm*",
+        "*tilix*",
+        "terminator",
+        "*ghostty*",
+    ];
+    terminals
+        .into_iter()
+        .map(|class| PasteProfile::new(class, PasteShortcut::CtrlShiftV))
+        .chain(
+            ["xterm", "*rxvt*"]
+                .into_iter()
+                .map(|class| PasteProfile::new(class, PasteShortcut::ShiftInsert)),
+        )
+        .collect()
 }
 
 impl Default for AppSettings {
     fn default() -> Self {
         Self {
-            input_device: "default".to_string(),
+            schema_version: SETTINGS_SCHEMA_VERSION,
+            input_device: InputDeviceSelection::default(),
             noise_reduction: true,
             auto_language: false,
+            language: None,
             latency_ms: 600,
             auto_export: true,
             output_mode: OutputMode::Clipboard,
             overlay_position: OverlayPosition::Docked,
             show_timestamps: true,
             auto_punctuation: true,
+            hotkey: HotkeyConfig::default(),
+            ptt_mode: PttMode::HoldToTalk,
+            cancel_hotkey: default_cancel_hotkey(),
+            max_recording_seconds: default_max_recording_seconds(),
+            silence_auto_stop: false,
+            silence_threshold_dbfs: default_silence_threshold_dbfs(),
+            min_recording_ms: default_min_recording_ms(),
+            level_interval_ms: default_level_interval_ms(),
+            input_gain_db: 0.0,
+            auto_gain: false,
+            save_recordings: false,
+            audio_source: AudioSource::Microphone,
+            translate: false,
+            custom_vocabulary: Vec::new(),
+            whisper_threads: None,
+            whisper_extra_args: Vec::new(),
+            partial_transcription: false,
+            spoken_punctuation: false,
+            replacements: Vec::new(),
+            restore_clipboard: default_restore_clipboard(),
+            paste_profiles: default_paste_profiles(),
+            sound_feedback: false,
+            notify_on_complete: false,
+            active_model: None,
+            consume_hotkey: false,
         }
     }
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Default)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default)]
 #[serde(rename_all = "snake_case")]
 pub struct SettingsUpdate {
     #[serde(default)]
-    pub input_device: Option<String>,
+    pub input_device: Option<InputDeviceSelection>,
     #[serde(default)]
     pub noise_reduction: Option<bool>,
     #[serde(default)]
     pub auto_language: Option<bool>,
     #[serde(default)]
+    pub language: Option<String>,
+    #[serde(default)]
     pub latency_ms: Option<u16>,
     #[serde(default)]
     pub auto_export: Option<bool>,
@@ -167,16 +515,73 @@ pub struct SettingsUpdate {
     pub show_timestamps: Option<bool>,
     #[serde(default)]
     pub auto_punctuation: Option<bool>,
+    #[serde(default)]
+    pub hotkey: Option<HotkeyConfig>,
+    #[serde(default)]
+    pub ptt_mode: Option<PttMode>,
+    #[serde(default)]
+    pub cancel_hotkey: Option<HotkeyConfig>,
+    #[serde(default)]
+    pub max_recording_seconds: Option<u32>,
+    #[serde(default)]
+    pub silence_auto_stop: Option<bool>,
+    #[serde(default)]
+    pub silence_threshold_dbfs: Option<f32>,
+    #[serde(default)]
+    pub min_recording_ms: Option<u32>,
+    #[serde(default)]
+    pub level_interval_ms: Option<u32>,
+    #[serde(default)]
+    pub input_gain_db: Option<f32>,
+    #[serde(default)]
+    pub auto_gain: Option<bool>,
+    #[serde(default)]
+    pub save_recordings: Option<bool>,
+    #[serde(default)]
+    pub audio_source: Option<AudioSource>,
+    #[serde(default)]
+    pub translate: Option<bool>,
+    #[serde(default)]
+    pub custom_vocabulary: Option<Vec<String>>,
+    /// `Some(0)` clears the override so whisper picks its own thread count.
+    #[serde(default)]
+    pub whisper_threads: Option<u8>,
+    #[serde(default)]
+    pub whisper_extra_args: Option<Vec<String>>,
+    #[serde(default)]
+    pub partial_transcription: Option<bool>,
+    #[serde(default)]
+    pub spoken_punctuation: Option<bool>,
+    #[serde(default)]
+    pub replacements: Option<Vec<WordReplacement>>,
+    #[serde(default)]
+    pub restore_clipboard: Option<bool>,
+    #[serde(default)]
+    pub paste_profiles: Option<Vec<PasteProfile>>,
+    #[serde(default)]
+    pub sound_feedback: Option<bool>,
+    #[serde(default)]
+    pub notify_on_complete: Option<bool>,
+    #[serde(default)]
+    pub active_model: Option<String>,
+    #[serde(default)]
+    pub consume_hotkey: Option<bool>,
 }
 
 impl AppSettings {
     pub fn apply_update(&self, update: SettingsUpdate) -> Self {
         Self {
+            schema_version: self.schema_version,
             input_device: update
                 .input_device
                 .unwrap_or_else(|| self.input_device.clone()),
             noise_reduction: update.noise_reduction.unwrap_or(self.noise_reduction),
             auto_language: update.auto_language.unwrap_or(self.auto_language),
+            language: match update.language {
+                Some(code) if code.trim().is_empty() => None,
+                Some(code) => Some(code),
+                None => self.language.clone(),
+            },
             latency_ms: update.latency_ms.unwrap_or(self.latency_ms),
             auto_export: update.auto_export.unwrap_or(self.auto_export),
             output_mode: update
@@ -187,18 +592,77 @@ impl AppSettings {
                 .unwrap_or_else(|| self.overlay_position.clone()),
             show_timestamps: update.show_timestamps.unwrap_or(self.show_timestamps),
             auto_punctuation: update.auto_punctuation.unwrap_or(self.auto_punctuation),
+            hotkey: update.hotkey.unwrap_or_else(|| self.hotkey.clone()),
+            ptt_mode: update.ptt_mode.unwrap_or(self.ptt_mode),
+            cancel_hotkey: update
+                .cancel_hotkey
+                .unwrap_or_else(|| self.cancel_hotkey.clone()),
+            max_recording_seconds: update
+                .max_recording_seconds
+                .unwrap_or(self.max_recording_seconds),
+            silence_auto_stop: update.silence_auto_stop.unwrap_or(self.silence_auto_stop),
+            silence_threshold_dbfs: update
+                .silence_threshold_dbfs
+                .unwrap_or(self.silence_threshold_dbfs),
+            min_recording_ms: update.min_recording_ms.unwrap_or(self.min_recording_ms),
+            level_interval_ms: update.level_interval_ms.unwrap_or(self.level_interval_ms),
+            input_gain_db: update.input_gain_db.unwrap_or(self.input_gain_db),
+            auto_gain: update.auto_gain.unwrap_or(self.auto_gain),
+            save_recordings: update.save_recordings.unwrap_or(self.save_recordings),
+            audio_source: update.audio_source.unwrap_or(self.audio_source),
+            translate: update.translate.unwrap_or(self.translate),
+            custom_vocabulary: update
+                .custom_vocabulary
+                .unwrap_or_else(|| self.custom_vocabulary.clone()),
+            whisper_threads: match update.whisper_threads {
+                Some(0) => None,
+                Some(threads) => Some(threads),
+                None => self.whisper_threads,
+            },
+            whisper_extra_args: update
+                .whisper_extra_args
+                .unwrap_or_else(|| self.whisper_extra_args.clone()),
+            partial_transcription: update
+                .partial_transcription
+                .unwrap_or(self.partial_transcription),
+            spoken_punctuation: update.spoken_punctuation.unwrap_or(self.spoken_punctuation),
+            replacements: update
+                .replacements
+                .unwrap_or_else(|| self.replacements.clone()),
+            restore_clipboard: update.restore_clipboard.unwrap_or(self.restore_clipboard),
+            paste_profiles: update
+                .paste_profiles
+                .unwrap_or_else(|| self.paste_profiles.clone()),
+            sound_feedback: update.sound_feedback.unwrap_or(self.sound_feedback),
+            notify_on_complete: update.notify_on_complete.unwrap_or(self.notify_on_complete),
+            active_model: match update.active_model {
+                Some(model) if model.trim().is_empty() => None,
+                Some(model) => Some(model),
+                None => self.active_model.clone(),
+            },
+            consume_hotkey: update.consume_hotkey.unwrap_or(self.consume_hotkey),
         }
     }
 }
 
-#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
 pub struct AppVersion {
     pub major: u8,
     pub minor: u8,
     pub patch: u8,
 }
 
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum ParseVersionError {
+    #[error("version '{input}' must have exactly three components, found {found}")]
+    ComponentCount { input: String, found: usize },
+    #[error("version '{input}' has invalid component '{component}'")]
+    InvalidComponent { input: String, component: String },
+}
+
 impl AppVersion {
+    pub const CURRENT: AppVersion = AppVersion::parse_const(env!("CARGO_PKG_VERSION"));
+
     pub const fn new(major: u8, minor: u8, patch: u8) -> Self {
         Self {
             major,
@@ -208,15 +672,82 @@ impl AppVersion {
     }
 
     pub fn as_string(&self) -> String {
-        format!("{}.{}.{}", self.major, self.minor, self.patch)
+        self.to_string()
+    }
+
+    pub fn is_newer_than(&self, other: &AppVersion) -> bool {
+        self > other
+    }
+
+    const fn parse_const(input: &str) -> Self {
+        let bytes = input.as_bytes();
+        let mut parts = [0u16; 3];
+        let mut part = 0;
+        let mut digits = 0;
+        let mut index = 0;
+        while index < bytes.len() {
+            let byte = bytes[index];
+            if byte == b'-' || byte == b'+' {
+                break;
+            }
+            if byte == b'.' {
+                assert!(digits > 0 && part < 2, "malformed crate version");
+                part += 1;
+                digits = 0;
+            } else {
+                assert!(byte.is_ascii_digit(), "malformed crate version");
+                parts[part] = parts[part] * 10 + (byte - b'0') as u16;
+                assert!(parts[part] <= u8::MAX as u16, "crate version out of range");
+                digits += 1;
+            }
+            index += 1;
+        }
+        assert!(digits > 0 && part == 2, "malformed crate version");
+        Self::new(parts[0] as u8, parts[1] as u8, parts[2] as u8)
+    }
+}
+
+impl fmt::Display for AppVersion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}.{}.{}", self.major, self.minor, self.patch)
+    }
+}
+
+impl FromStr for AppVersion {
+    type Err = ParseVersionError;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        let components: Vec<&str> = input.trim().split('.').collect();
+        if components.len() != 3 {
+            return Err(ParseVersionError::ComponentCount {
+                input: input.to_string(),
+                found: components.len(),
+            });
+        }
+        let mut parsed = [0u8; 3];
+        for (slot, component) in parsed.iter_mut().zip(&components) {
+            let numeric = !component.is_empty() && component.bytes().all(|b| b.is_ascii_digit());
+            *slot = match component.parse() {
+                Ok(value) if numeric => value,
+                _ => {
+                    return Err(ParseVersionError::InvalidComponent {
+                        input: input.to_string(),
+                        component: component.to_string(),
+                    })
+                }
+            };
+        }
+        Ok(Self::new(parsed[0], parsed[1], parsed[2]))
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::{
-        AppSettings, AppVersion, OverlayPosition, PttCommand, PttEvent, PttLevel, PttState,
-        SettingsUpdate,
+        default_paste_profiles, AppSettings, AppVersion, AudioSource, BackendState, HotkeyConfig,
+        InputDeviceSelection, OutputMode, OverlayAnchor, OverlayPosition, ParseVersionError,
+        PasteProfile, PasteShortcut, PttCommand, PttEvent, PttLevel, PttMode, PttState,
+        SettingsUpdate, TimedState,
     };
 
     #[test]
@@ -233,11 +764,64 @@ mod tests {
         assert_eq!(decoded, version);
     }
 
+    #[test]
+    fn version_parses_and_displays() {
+        let version: AppVersion = "1.10.0".parse().expect("parse version");
+        assert_eq!(version, AppVersion::new(1, 10, 0));
+        assert_eq!(version.to_string(), "1.10.0");
+    }
+
+    #[test]
+    fn version_ordering_is_numeric() {
+        let parse = |input: &str| input.parse::<AppVersion>().expect("parse version");
+
+        assert!(parse("1.10.0") > parse("1.9.0"));
+        assert!(parse("2.0.0") > parse("1.255.255"));
+        assert!(parse("0.1.10") > parse("0.1.9"));
+        assert!(parse("1.2.3").is_newer_than(&parse("1.2.2")));
+        assert!(!parse("1.2.3").is_newer_than(&parse("1.2.3")));
+        assert!(!parse("0.9.9").is_newer_than(&parse("1.0.0")));
+    }
+
+    #[test]
+    fn version_rejects_malformed_inputs() {
+        for (input, found) in [("1.2", 2), ("1.2.3.4", 4), ("", 1)] {
+            assert_eq!(
+                input.parse::<AppVersion>(),
+                Err(ParseVersionError::ComponentCount {
+                    input: input.to_string(),
+                    found,
+                })
+            );
+        }
+        for (input, component) in [
+            ("1.x.3", "x"),
+            ("1..3", ""),
+            ("1.2.-3", "-3"),
+            ("1.2.+3", "+3"),
+            ("1.2.256", "256"),
+        ] {
+            assert_eq!(
+                input.parse::<AppVersion>(),
+                Err(ParseVersionError::InvalidComponent {
+                    input: input.to_string(),
+                    component: component.to_string(),
+                })
+            );
+        }
+    }
+
+    #[test]
+    fn current_version_matches_crate_version() {
+        let parsed: AppVersion = env!("CARGO_PKG_VERSION").parse().expect("parse version");
+        assert_eq!(AppVersion::CURRENT, parsed);
+    }
+
     #[test]
     fn settings_update_merges_fields() {
         let settings = AppSettings::default();
         let update = SettingsUpdate {
-            input_device: Some("USB Mic".to_string()),
+            input_device: Some("USB Mic".into()),
             latency_ms: Some(900),
             overlay_position: Some(OverlayPosition::Floating),
             ..SettingsUpdate::default()
@@ -265,6 +849,8 @@ mod tests {
             level: PttLevel {
                 rms: 0.2,
                 peak: 0.8,
+                smoothed_rms: 0.15,
+                peak_hold: 0.9,
             },
         };
         let json = serde_json::to_string(&event).expect("serialize ptt event");
@@ -273,6 +859,14 @@ mod tests {
         assert!(json.contains("level"));
     }
 
+    #[test]
+    fn ptt_level_defaults_smoothed_fields() {
+        let level: PttLevel =
+            serde_json::from_str(r#"{"rms":0.2,"peak":0.8}"#).expect("deserialize ptt level");
+        assert_eq!(level.smoothed_rms, 0.0);
+        assert_eq!(level.peak_hold, 0.0);
+    }
+
     #[test]
     fn ptt_state_roundtrips_json() {
         let state = PttState::Capturing;
@@ -280,4 +874,410 @@ mod tests {
         let decoded: PttState = serde_json::from_str(&json).expect("deserialize ptt state");
         assert_eq!(decoded, state);
     }
+
+    #[test]
+    fn ptt_state_error_uses_snake_case_tag() {
+        let state = PttState::Error {
+            message: "no input device".to_string(),
+        };
+        let json = serde_json::to_string(&state).expect("serialize ptt state");
+        assert_eq!(json, r#"{"error":{"message":"no input device"}}"#);
+        let decoded: PttState = serde_json::from_str(&json).expect("deserialize ptt state");
+        assert_eq!(decoded, state);
+    }
+
+    #[test]
+    fn ptt_state_processing_carries_model_and_start() {
+        let state = PttState::Processing {
+            model: "base".to_string(),
+            started_ms: 1_700_000_000_000,
+        };
+        let json = serde_json::to_string(&state).expect("serialize ptt state");
+        assert_eq!(
+            json,
+            r#"{"processing":{"model":"base","started_ms":1700000000000}}"#
+        );
+        let decoded: PttState = serde_json::from_str(&json).expect("deserialize ptt state");
+        assert_eq!(decoded, state);
+    }
+
+    #[test]
+    fn timed_state_roundtrips_json() {
+        let timed = TimedState {
+            state: BackendState::Error {
+                message: "boom".to_string(),
+            },
+            since_ms: 1_700_000_000_000,
+        };
+
+        let value = serde_json::to_value(&timed).expect("serialize timed state");
+        assert_eq!(
+            value,
+            serde_json::json!({
+                "state": { "error": { "message": "boom" } },
+                "since_ms": 1_700_000_000_000_u64,
+            })
+        );
+        let decoded: TimedState = serde_json::from_value(value).expect("deserialize timed state");
+        assert_eq!(decoded, timed);
+    }
+
+    #[test]
+    fn timed_state_accepts_bare_state() {
+        let decoded: TimedState =
+            serde_json::from_str("\"recording\"").expect("deserialize bare state");
+
+        assert_eq!(
+            decoded,
+            TimedState {
+                state: BackendState::Recording,
+                since_ms: 0,
+            }
+        );
+    }
+
+    #[test]
+    fn output_mode_roundtrips_json() {
+        for (mode, expected) in [
+            (OutputMode::UiOnly, "\"ui_only\""),
+            (OutputMode::Clipboard, "\"clipboard\""),
+            (OutputMode::DirectWrite, "\"direct_write\""),
+            (OutputMode::Paste, "\"paste\""),
+        ] {
+            let json = serde_json::to_string(&mode).expect("serialize output mode");
+            assert_eq!(json, expected);
+            let decoded: OutputMode = serde_json::from_str(&json).expect("deserialize output mode");
+            assert_eq!(decoded, mode);
+        }
+    }
+
+    #[test]
+    fn input_device_accepts_string_or_list() {
+        let single: InputDeviceSelection =
+            serde_json::from_str("\"USB Mic\"").expect("deserialize single device");
+        assert_eq!(single, "USB Mic");
+        assert!(!single.is_mixed());
+        assert_eq!(serde_json::to_string(&single).unwrap(), "\"USB Mic\"");
+
+        let mixed: InputDeviceSelection =
+            serde_json::from_str(r#"["Headset", "Desk Mic"]"#).expect("deserialize device list");
+        assert_eq!(mixed.devices(), vec!["Headset", "Desk Mic"]);
+        assert!(mixed.is_mixed());
+        assert_eq!(mixed.to_string(), "Headset + Desk Mic");
+        assert!(InputDeviceSelection::default().is_default());
+    }
+
+    #[test]
+    fn overlay_position_keeps_unit_variant_strings() {
+        for (position, expected) in [
+            (OverlayPosition::Docked, "\"docked\""),
+            (OverlayPosition::Floating, "\"floating\""),
+            (OverlayPosition::Compact, "\"compact\""),
+        ] {
+            let json = serde_json::to_string(&position).expect("serialize overlay position");
+            assert_eq!(json, expected);
+            let decoded: OverlayPosition =
+                serde_json::from_str(&json).expect("deserialize overlay position");
+            assert_eq!(decoded, position);
+        }
+    }
+
+    #[test]
+    fn overlay_position_custom_uses_tagged_object() {
+        let position = OverlayPosition::Custom {
+            x: -24,
+            y: 48,
+            anchor: OverlayAnchor::BottomRight,
+        };
+
+        let value = serde_json::to_value(&position).expect("serialize custom position");
+        assert_eq!(
+            value,
+            serde_json::json!({ "custom": { "x": -24, "y": 48, "anchor": "bottom_right" } })
+        );
+        let decoded: OverlayPosition =
+            serde_json::from_str(r#"{"custom":{"x":-24,"y":48,"anchor":"bottom_right"}}"#)
+                .expect("deserialize custom position");
+        assert_eq!(decoded, position);
+        assert!(serde_json::from_str::<OverlayPosition>(
+            r#"{"custom":{"x":0,"y":0,"anchor":"center"}}"#
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn settings_update_applies_custom_overlay_position() {
+        let position = OverlayPosition::Custom {
+            x: 12,
+            y: 12,
+            anchor: OverlayAnchor::TopRight,
+        };
+        let update = SettingsUpdate {
+            overlay_position: Some(position.clone()),
+            ..SettingsUpdate::default()
+        };
+
+        let merged = AppSettings::default().apply_update(update);
+        assert_eq!(merged.overlay_position, position);
+    }
+
+    #[test]
+    fn audio_source_roundtrips_json() {
+        for (source, expected) in [
+            (AudioSource::Microphone, "\"microphone\""),
+            (AudioSource::SystemAudio, "\"system_audio\""),
+            (AudioSource::Both, "\"both\""),
+        ] {
+            let json = serde_json::to_string(&source).expect("serialize audio source");
+            assert_eq!(json, expected);
+            let decoded: AudioSource =
+                serde_json::from_str(&json).expect("deserialize audio source");
+            assert_eq!(decoded, source);
+        }
+
+        let merged = AppSettings::default().apply_update(SettingsUpdate {
+            audio_source: Some(AudioSource::Both),
+            ..SettingsUpdate::default()
+        });
+        assert_eq!(merged.audio_source, AudioSource::Both);
+    }
+
+    #[test]
+    fn settings_update_applies_output_mode() {
+        let settings = AppSettings::default();
+        let update = SettingsUpdate {
+            output_mode: Some(OutputMode::DirectWrite),
+            ..SettingsUpdate::default()
+        };
+
+        let merged = settings.apply_update(update);
+        assert_eq!(merged.output_mode, OutputMode::DirectWrite);
+        assert_eq!(merged.input_device, settings.input_device);
+    }
+
+    #[test]
+    fn settings_update_applies_ptt_mode() {
+        let update: SettingsUpdate =
+            serde_json::from_str(r#"{"ptt_mode":"toggle"}"#).expect("deserialize update");
+
+        let merged = AppSettings::default().apply_update(update);
+        assert_eq!(merged.ptt_mode, PttMode::Toggle);
+        assert_eq!(
+            merged.apply_update(SettingsUpdate::default()).ptt_mode,
+            PttMode::Toggle
+        );
+    }
+
+    #[test]
+    fn settings_without_output_mode_still_deserialize() {
+        let json = r#"{
+            "input_device": "default",
+            "noise_reduction": true,
+            "auto_language": false,
+            "latency_ms": 600,
+            "auto_export": true,
+            "overlay_position": "docked",
+            "show_timestamps": true,
+            "auto_punctuation": true
+        }"#;
+
+        let settings: AppSettings = serde_json::from_str(json).expect("deserialize old settings");
+        assert_eq!(settings.output_mode, OutputMode::default());
+        assert_eq!(settings.ptt_mode, PttMode::HoldToTalk);
+        assert_eq!(settings.cancel_hotkey.key, "escape");
+        assert_eq!(settings.latency_ms, 600);
+        assert_eq!(settings.max_recording_seconds, 120);
+        assert!(!settings.silence_auto_stop);
+        assert_eq!(settings.silence_threshold_dbfs, -45.0);
+        assert_eq!(settings.min_recording_ms, 300);
+        assert_eq!(settings.level_interval_ms, 50);
+        assert_eq!(settings.input_gain_db, 0.0);
+        assert!(!settings.auto_gain);
+        assert!(!settings.save_recordings);
+        assert_eq!(settings.audio_source, AudioSource::Microphone);
+        assert!(!settings.translate);
+        assert!(settings.custom_vocabulary.is_empty());
+        assert_eq!(settings.whisper_threads, None);
+        assert!(settings.whisper_extra_args.is_empty());
+        assert!(!settings.partial_transcription);
+        assert!(!settings.spoken_punctuation);
+        assert!(settings.replacements.is_empty());
+        assert!(settings.restore_clipboard);
+        assert_eq!(settings.paste_profiles, default_paste_profiles());
+        assert_eq!(settings.active_model, None);
+        assert!(!settings.sound_feedback);
+        assert!(!settings.notify_on_complete);
+        assert!(!settings.consume_hotkey);
+    }
+
+    #[test]
+    fn settings_update_selects_and_clears_active_model() {
+        let selected = AppSettings::default().apply_update(SettingsUpdate {
+            active_model: Some("small".to_string()),
+            ..SettingsUpdate::default()
+        });
+        assert_eq!(selected.active_model.as_deref(), Some("small"));
+
+        let kept = selected.apply_update(SettingsUpdate::default());
+        assert_eq!(kept.active_model.as_deref(), Some("small"));
+        let cleared = kept.apply_update(SettingsUpdate {
+            active_model: Some(" ".to_string()),
+            ..SettingsUpdate::default()
+        });
+        assert_eq!(cleared.active_model, None);
+    }
+
+    #[test]
+    fn settings_update_replaces_word_replacements() {
+        let replacement = crate::WordReplacement {
+            from: "open whisper".to_string(),
+            to: "OpenWhisperAI".to_string(),
+        };
+        let merged = AppSettings::default().apply_update(SettingsUpdate {
+            replacements: Some(vec![replacement.clone()]),
+            ..SettingsUpdate::default()
+        });
+        assert_eq!(merged.replacements, vec![replacement]);
+
+        let kept = merged.apply_update(SettingsUpdate::default());
+        assert_eq!(kept.replacements, merged.replacements);
+        let cleared = kept.apply_update(SettingsUpdate {
+            replacements: Some(Vec::new()),
+            ..SettingsUpdate::default()
+        });
+        assert!(cleared.replacements.is_empty());
+    }
+
+    #[test]
+    fn settings_update_replaces_paste_profiles() {
+        let profile = PasteProfile::new("code", PasteShortcut::CtrlShiftV);
+        let merged = AppSettings::default().apply_update(SettingsUpdate {
+            paste_profiles: Some(vec![profile.clone()]),
+            ..SettingsUpdate::default()
+        });
+        assert_eq!(merged.paste_profiles, vec![profile]);
+
+        let kept = merged.apply_update(SettingsUpdate::default());
+        assert_eq!(kept.paste_profiles, merged.paste_profiles);
+        let json = serde_json::to_value(&kept.paste_profiles[0]).expect("serialize");
+        assert_eq!(json["shortcut"], "ctrl_shift_v");
+    }
+
+    #[test]
+    fn settings_update_toggles_clipboard_restore() {
+        let disabled = AppSettings::default().apply_update(SettingsUpdate {
+            restore_clipboard: Some(false),
+            output_mode: Some(OutputMode::Paste),
+            ..SettingsUpdate::default()
+        });
+        assert!(!disabled.restore_clipboard);
+        assert_eq!(disabled.output_mode, OutputMode::Paste);
+        assert!(
+            !disabled
+                .apply_update(SettingsUpdate::default())
+                .restore_clipboard
+        );
+    }
+
+    #[test]
+    fn settings_update_merges_recording_limits() {
+        let settings = AppSettings::default();
+        let update = SettingsUpdate {
+            silence_auto_stop: Some(true),
+            silence_threshold_dbfs: Some(-38.5),
+            ..SettingsUpdate::default()
+        };
+
+        let merged = settings.apply_update(update);
+        assert!(merged.silence_auto_stop);
+        assert_eq!(merged.silence_threshold_dbfs, -38.5);
+        assert_eq!(merged.max_recording_seconds, settings.max_recording_seconds);
+        assert_eq!(merged.min_recording_ms, settings.min_recording_ms);
+
+        let merged = merged.apply_update(SettingsUpdate {
+            max_recording_seconds: Some(30),
+            min_recording_ms: Some(150),
+            ..SettingsUpdate::default()
+        });
+        assert_eq!(merged.max_recording_seconds, 30);
+        assert_eq!(merged.min_recording_ms, 150);
+        assert!(merged.silence_auto_stop);
+    }
+
+    #[test]
+    fn settings_update_merges_input_gain() {
+        let merged = AppSettings::default().apply_update(SettingsUpdate {
+            input_gain_db: Some(9.5),
+            ..SettingsUpdate::default()
+        });
+        assert_eq!(merged.input_gain_db, 9.5);
+        assert!(!merged.auto_gain);
+
+        let merged = merged.apply_update(SettingsUpdate {
+            auto_gain: Some(true),
+            ..SettingsUpdate::default()
+        });
+        assert_eq!(merged.input_gain_db, 9.5);
+        assert!(merged.auto_gain);
+    }
+
+    #[test]
+    fn settings_update_sets_and_clears_whisper_threads() {
+        let merged = AppSettings::default().apply_update(SettingsUpdate {
+            whisper_threads: Some(6),
+            ..SettingsUpdate::default()
+        });
+        assert_eq!(merged.whisper_threads, Some(6));
+        let unchanged = merged.apply_update(SettingsUpdate::default());
+        assert_eq!(unchanged.whisper_threads, Some(6));
+        let cleared = unchanged.apply_update(SettingsUpdate {
+            whisper_threads: Some(0),
+            ..SettingsUpdate::default()
+        });
+        assert_eq!(cleared.whisper_threads, None);
+    }
+
+    #[test]
+    fn settings_update_sets_and_clears_language() {
+        let settings = AppSettings::default();
+        let update = SettingsUpdate {
+            language: Some("de".to_string()),
+            ..SettingsUpdate::default()
+        };
+
+        let merged = settings.apply_update(update);
+        assert_eq!(merged.language.as_deref(), Some("de"));
+        let unchanged = merged.apply_update(SettingsUpdate::default());
+        assert_eq!(unchanged.language.as_deref(), Some("de"));
+        let cleared = unchanged.apply_update(SettingsUpdate {
+            language: Some(String::new()),
+            ..SettingsUpdate::default()
+        });
+        assert_eq!(cleared.language, None);
+    }
+
+    #[test]
+    fn settings_update_replaces_hotkey() {
+        let settings = AppSettings::default();
+        let hotkey = HotkeyConfig {
+            key: "k".to_string(),
+            ctrl: true,
+            alt: false,
+            shift: true,
+            meta: false,
+        };
+        let update = SettingsUpdate {
+            hotkey: Some(hotkey.clone()),
+            ..SettingsUpdate::default()
+        };
+
+        let merged = settings.apply_update(update);
+        assert_eq!(merged.hotkey, hotkey);
+
+        let json = serde_json::to_value(&merged).expect("serialize settings");
+        assert_eq!(
+            json["hotkey"],
+            serde_json::json!({ "key": "k", "ctrl": true, "alt": false, "shift": true, "meta": false })
+        );
+    }
 }
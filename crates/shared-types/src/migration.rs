@@ -0,0 +1,199 @@
+use serde_json::{Map, Value};
+
+use crate::AppSettings;
+
+pub const SETTINGS_SCHEMA_VERSION: u32 = 1;
+
+const V0_KEY_RENAMES: &[(&str, &str)] = &[
+    ("inputDevice", "input_device"),
+    ("noiseReduction", "noise_reduction"),
+    ("autoLanguage", "auto_language"),
+    ("latencyMs", "latency_ms"),
+    ("latency", "latency_ms"),
+    ("autoExport", "auto_export"),
+    ("outputMode", "output_mode"),
+    ("overlayPosition", "overlay_position"),
+    ("showTimestamps", "show_timestamps"),
+    ("autoPunctuation", "auto_punctuation"),
+];
+
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum MigrationError {
+    #[error("settings file must contain a JSON object")]
+    NotAnObject,
+    #[error("invalid schema_version value: {0}")]
+    InvalidVersion(String),
+    #[error("settings schema version {found} is newer than the supported version {supported}")]
+    UnsupportedVersion { found: u32, supported: u32 },
+    #[error("invalid settings: {0}")]
+    Invalid(String),
+}
+
+pub fn settings_schema_version(value: &Value) -> Result<u32, MigrationError> {
+    match value.get("schema_version") {
+        None => Ok(0),
+        Some(version) => version
+            .as_u64()
+            .and_then(|version| u32::try_from(version).ok())
+            .ok_or_else(|| MigrationError::InvalidVersion(version.to_string())),
+    }
+}
+
+pub fn migrate_from_value(value: Value) -> Result<AppSettings, MigrationError> {
+    let version = settings_schema_version(&value)?;
+    let Value::Object(mut fields) = value else {
+        return Err(MigrationError::NotAnObject);
+    };
+
+    if version > SETTINGS_SCHEMA_VERSION {
+        return Err(MigrationError::UnsupportedVersion {
+            found: version,
+            supported: SETTINGS_SCHEMA_VERSION,
+        });
+    }
+    if version < 1 {
+        migrate_v0(&mut fields);
+    }
+
+    let mut merged = match serde_json::to_value(AppSettings::default()) {
+        Ok(Value::Object(defaults)) => defaults,
+        _ => Map::new(),
+    };
+    merged.extend(fields);
+    merged.insert(
+        "schema_version".to_string(),
+        Value::from(SETTINGS_SCHEMA_VERSION),
+    );
+
+    serde_json::from_value(Value::Object(merged))
+        .map_err(|err| MigrationError::Invalid(err.to_string()))
+}
+
+fn migrate_v0(fields: &mut Map<String, Value>) {
+    for (legacy, current) in V0_KEY_RENAMES {
+        if let Some(value) = fields.remove(*legacy) {
+            fields.entry(current.to_string()).or_insert(value);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{OutputMode, OverlayAnchor, OverlayPosition};
+
+    const V0_SETTINGS: &str = r#"{
+        "inputDevice": "USB Mic",
+        "noise_reduction": false,
+        "latency": 900,
+        "overlayPosition": "compact",
+        "show_timestamps": false
+    }"#;
+
+    #[test]
+    fn migrates_v0_file_with_legacy_keys_and_missing_fields() {
+        let value: Value = serde_json::from_str(V0_SETTINGS).unwrap();
+
+        let settings = migrate_from_value(value).unwrap();
+
+        assert_eq!(settings.schema_version, SETTINGS_SCHEMA_VERSION);
+        assert_eq!(settings.input_device, "USB Mic");
+        assert!(!settings.noise_reduction);
+        assert_eq!(settings.latency_ms, 900);
+        assert_eq!(settings.overlay_position, OverlayPosition::Compact);
+        assert!(!settings.show_timestamps);
+        assert_eq!(settings.output_mode, OutputMode::Clipboard);
+        assert_eq!(
+            settings.auto_punctuation,
+            AppSettings::default().auto_punctuation
+        );
+    }
+
+    #[test]
+    fn current_version_keys_win_over_legacy_aliases() {
+        let value = serde_json::json!({
+            "input_device": "Built-in",
+            "inputDevice": "Old Mic",
+        });
+
+        let settings = migrate_from_value(value).unwrap();
+
+        assert_eq!(settings.input_device, "Built-in");
+    }
+
+    #[test]
+    fn current_version_roundtrips_unchanged() {
+        let settings = AppSettings {
+            latency_ms: 750,
+            ..AppSettings::default()
+        };
+        let value = serde_json::to_value(&settings).unwrap();
+
+        assert_eq!(migrate_from_value(value).unwrap(), settings);
+    }
+
+    #[test]
+    fn existing_overlay_positions_load_unchanged() {
+        for (raw, expected) in [
+            ("docked", OverlayPosition::Docked),
+            ("floating", OverlayPosition::Floating),
+            ("compact", OverlayPosition::Compact),
+        ] {
+            let value = serde_json::json!({
+                "schema_version": SETTINGS_SCHEMA_VERSION,
+                "overlay_position": raw,
+            });
+
+            let settings = migrate_from_value(value).unwrap();
+
+            assert_eq!(settings.overlay_position, expected);
+        }
+    }
+
+    #[test]
+    fn custom_overlay_position_survives_migration() {
+        let settings = AppSettings {
+            overlay_position: OverlayPosition::Custom {
+                x: 40,
+                y: -8,
+                anchor: OverlayAnchor::BottomLeft,
+            },
+            ..AppSettings::default()
+        };
+        let value = serde_json::to_value(&settings).unwrap();
+
+        assert_eq!(migrate_from_value(value).unwrap(), settings);
+    }
+
+    #[test]
+    fn future_version_fails_loudly() {
+        let value = serde_json::json!({
+            "schema_version": SETTINGS_SCHEMA_VERSION + 1,
+            "input_device": "default",
+        });
+
+        assert_eq!(
+            migrate_from_value(value),
+            Err(MigrationError::UnsupportedVersion {
+                found: SETTINGS_SCHEMA_VERSION + 1,
+                supported: SETTINGS_SCHEMA_VERSION,
+            })
+        );
+    }
+
+    #[test]
+    fn rejects_malformed_documents() {
+        assert_eq!(
+            migrate_from_value(serde_json::json!([1, 2])),
+            Err(MigrationError::NotAnObject)
+        );
+        assert!(matches!(
+            migrate_from_value(serde_json::json!({ "schema_version": "one" })),
+            Err(MigrationError::InvalidVersion(_))
+        ));
+        assert!(matches!(
+            migrate_from_value(serde_json::json!({ "latency_ms": "fast" })),
+            Err(MigrationError::Invalid(_))
+        ));
+    }
+}
@@ -0,0 +1,257 @@
+use crate::{ModelInstallStatus, ModelStatusItem, ModelStatusPayload};
+
+const BYTE_UNITS: [&str; 7] = ["B", "KiB", "MiB", "GiB", "TiB", "PiB", "EiB"];
+
+impl ModelInstallStatus {
+    pub fn is_pending(&self) -> bool {
+        matches!(self, Self::Downloading | Self::Queued | Self::Pending)
+    }
+}
+
+impl ModelStatusPayload {
+    pub fn merge(&mut self, update: ModelStatusItem) {
+        match self.models.iter_mut().find(|item| item.id == update.id) {
+            Some(existing) => *existing = update,
+            None => self.models.push(update),
+        }
+        self.refresh_queue_count();
+    }
+
+    pub fn apply_progress(
+        &mut self,
+        id: &str,
+        downloaded_bytes: u64,
+        total_bytes: u64,
+        speed_bytes_per_sec: u64,
+        eta_seconds: u64,
+    ) -> bool {
+        let Some(item) = self.models.iter_mut().find(|item| item.id == id) else {
+            return false;
+        };
+        item.downloaded_bytes = downloaded_bytes;
+        item.total_bytes = total_bytes;
+        item.speed_bytes_per_sec = speed_bytes_per_sec;
+        item.eta_seconds = eta_seconds;
+        item.progress = if total_bytes > 0 {
+            (downloaded_bytes as f64 / total_bytes as f64 * 100.0).min(100.0) as f32
+        } else {
+            0.0
+        };
+        if !matches!(
+            item.status,
+            ModelInstallStatus::Ready | ModelInstallStatus::Installed
+        ) {
+            item.status = ModelInstallStatus::Downloading;
+        }
+        self.refresh_queue_count();
+        true
+    }
+
+    pub fn refresh_queue_count(&mut self) {
+        self.queue_count = self
+            .models
+            .iter()
+            .filter(|item| item.status.is_pending())
+            .count();
+    }
+}
+
+impl ModelStatusItem {
+    pub fn downloaded_display(&self) -> String {
+        format_bytes(self.downloaded_bytes)
+    }
+
+    pub fn total_display(&self) -> String {
+        format_bytes(self.total_bytes)
+    }
+
+    pub fn eta_display(&self) -> String {
+        if self.total_bytes > 0 && self.downloaded_bytes >= self.total_bytes {
+            return "0s".to_string();
+        }
+        if self.eta_seconds > 0 {
+            return format_eta(self.eta_seconds as f64);
+        }
+        let remaining = self.total_bytes.saturating_sub(self.downloaded_bytes);
+        if remaining == 0 || self.speed_bytes_per_sec == 0 {
+            return "--".to_string();
+        }
+        format_eta(remaining as f64 / self.speed_bytes_per_sec as f64)
+    }
+
+    pub fn percent(&self) -> f32 {
+        let percent = if self.progress > 0.0 {
+            self.progress
+        } else if self.total_bytes > 0 {
+            (self.downloaded_bytes as f64 / self.total_bytes as f64 * 100.0) as f32
+        } else {
+            0.0
+        };
+        if percent.is_nan() {
+            return 0.0;
+        }
+        percent.clamp(0.0, 100.0)
+    }
+}
+
+pub fn format_bytes(bytes: u64) -> String {
+    let mut value = bytes as f64;
+    let mut unit = 0;
+    while value >= 1024.0 && unit < BYTE_UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+    if unit == 0 {
+        format!("{bytes} B")
+    } else {
+        format!("{value:.1} {}", BYTE_UNITS[unit])
+    }
+}
+
+pub fn format_eta(seconds: f64) -> String {
+    if !seconds.is_finite() || seconds <= 0.0 {
+        return "0s".to_string();
+    }
+    if seconds < 1.0 {
+        return "<1s".to_string();
+    }
+    let total = seconds.round() as u64;
+    let (hours, minutes, secs) = (total / 3600, total % 3600 / 60, total % 60);
+    match (hours, minutes) {
+        (0, 0) => format!("{secs}s"),
+        (0, _) => format!("{minutes}m {secs}s"),
+        _ => format!("{hours}h {minutes}m"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn item(downloaded_bytes: u64, total_bytes: u64) -> ModelStatusItem {
+        ModelStatusItem {
+            id: "base".to_string(),
+            name: "Base".to_string(),
+            status: ModelInstallStatus::Downloading,
+            total_bytes,
+            downloaded_bytes,
+            speed_bytes_per_sec: 0,
+            eta_seconds: 0,
+            progress: 0.0,
+            active: false,
+        }
+    }
+
+    #[test]
+    fn formats_bytes_with_binary_units() {
+        assert_eq!(format_bytes(0), "0 B");
+        assert_eq!(format_bytes(1023), "1023 B");
+        assert_eq!(format_bytes(1024), "1.0 KiB");
+        assert_eq!(format_bytes(1_503_238_554), "1.4 GiB");
+        assert_eq!(format_bytes(u64::MAX), "16.0 EiB");
+    }
+
+    #[test]
+    fn displays_byte_counts() {
+        let status = item(147_000_000, 1_500_000_000);
+
+        assert_eq!(status.downloaded_display(), "140.2 MiB");
+        assert_eq!(status.total_display(), "1.4 GiB");
+    }
+
+    #[test]
+    fn formats_eta() {
+        assert_eq!(format_eta(192.0), "3m 12s");
+        assert_eq!(format_eta(42.4), "42s");
+        assert_eq!(format_eta(3_725.0), "1h 2m");
+        assert_eq!(format_eta(0.3), "<1s");
+        assert_eq!(format_eta(0.0), "0s");
+    }
+
+    #[test]
+    fn eta_display_prefers_reported_eta_then_speed() {
+        let mut status = item(0, 1_000);
+        assert_eq!(status.eta_display(), "--");
+
+        status.speed_bytes_per_sec = 4_000;
+        assert_eq!(status.eta_display(), "<1s");
+
+        status.eta_seconds = 192;
+        assert_eq!(status.eta_display(), "3m 12s");
+
+        status.downloaded_bytes = 1_000;
+        assert_eq!(status.eta_display(), "0s");
+    }
+
+    fn payload() -> ModelStatusPayload {
+        let mut ready = item(10, 10);
+        ready.id = "tiny".to_string();
+        ready.status = ModelInstallStatus::Ready;
+        ModelStatusPayload {
+            models: vec![ready, item(0, 1_000)],
+            active_model: Some("tiny".to_string()),
+            queue_count: 1,
+        }
+    }
+
+    #[test]
+    fn merge_appends_unknown_id() {
+        let mut payload = payload();
+        let mut small = item(0, 0);
+        small.id = "small".to_string();
+        small.status = ModelInstallStatus::Queued;
+
+        payload.merge(small);
+
+        assert_eq!(payload.models.len(), 3);
+        assert_eq!(payload.models[2].id, "small");
+        assert_eq!(payload.queue_count, 2);
+        assert_eq!(payload.active_model.as_deref(), Some("tiny"));
+    }
+
+    #[test]
+    fn merge_updates_known_id_in_place() {
+        let mut payload = payload();
+        let mut base = item(1_000, 1_000);
+        base.status = ModelInstallStatus::Ready;
+
+        payload.merge(base.clone());
+
+        assert_eq!(payload.models.len(), 2);
+        assert_eq!(payload.models[1], base);
+        assert_eq!(payload.models[0].id, "tiny");
+        assert_eq!(payload.queue_count, 0);
+    }
+
+    #[test]
+    fn apply_progress_updates_single_item() {
+        let mut payload = payload();
+        let untouched = payload.models[0].clone();
+
+        assert!(payload.apply_progress("base", 250, 1_000, 50, 15));
+        assert!(!payload.apply_progress("missing", 1, 2, 3, 4));
+
+        let base = &payload.models[1];
+        assert_eq!(base.downloaded_bytes, 250);
+        assert_eq!(base.speed_bytes_per_sec, 50);
+        assert_eq!(base.eta_seconds, 15);
+        assert_eq!(base.percent(), 25.0);
+        assert_eq!(base.status, ModelInstallStatus::Downloading);
+        assert_eq!(payload.models[0], untouched);
+        assert_eq!(payload.queue_count, 1);
+    }
+
+    #[test]
+    fn percent_falls_back_to_byte_counts() {
+        assert_eq!(item(0, 0).percent(), 0.0);
+        assert_eq!(item(250, 1_000).percent(), 25.0);
+        assert_eq!(item(u64::MAX, u64::MAX).percent(), 100.0);
+        assert_eq!(item(2_000, 1_000).percent(), 100.0);
+
+        let mut reported = item(250, 1_000);
+        reported.progress = 140.0;
+        assert_eq!(reported.percent(), 100.0);
+        reported.progress = 60.0;
+        assert_eq!(reported.percent(), 60.0);
+    }
+}
@@ -0,0 +1,341 @@
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+
+use crate::OutputMode;
+
+pub const DEFAULT_TRANSCRIPT_HISTORY_CAPACITY: usize = 50;
+pub const TRANSCRIPTION_STATS_WINDOW: usize = 20;
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct TranscriptSegment {
+    pub start_ms: u64,
+    pub end_ms: u64,
+    pub text: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct TranscriptMetrics {
+    pub audio_seconds: f32,
+    pub wall_ms: u64,
+    pub rtf: f32,
+    pub model: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct TranscriptionStats {
+    pub model: String,
+    pub samples: usize,
+    pub avg_audio_seconds: f32,
+    pub avg_wall_ms: u64,
+    pub avg_rtf: f32,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct TranscriptEntry {
+    pub id: u64,
+    pub text: String,
+    pub created_at_ms: u64,
+    pub duration_ms: u64,
+    #[serde(default)]
+    pub model: Option<String>,
+    pub chars: usize,
+    #[serde(default)]
+    pub output_mode: OutputMode,
+    #[serde(default)]
+    pub recording_path: Option<PathBuf>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub segments: Vec<TranscriptSegment>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub metrics: Option<TranscriptMetrics>,
+}
+
+impl TranscriptEntry {
+    pub fn new(
+        text: impl Into<String>,
+        created_at_ms: u64,
+        duration_ms: u64,
+        model: Option<String>,
+        output_mode: OutputMode,
+    ) -> Self {
+        let text = text.into();
+        Self {
+            id: 0,
+            chars: text.chars().count(),
+            text,
+            created_at_ms,
+            duration_ms,
+            model,
+            output_mode,
+            recording_path: None,
+            segments: Vec::new(),
+            metrics: None,
+        }
+    }
+
+    pub fn with_recording_path(mut self, path: impl Into<PathBuf>) -> Self {
+        self.recording_path = Some(path.into());
+        self
+    }
+
+    pub fn with_segments(mut self, segments: Vec<TranscriptSegment>) -> Self {
+        self.segments = segments;
+        self
+    }
+
+    pub fn with_metrics(mut self, metrics: TranscriptMetrics) -> Self {
+        self.metrics = Some(metrics);
+        self
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct TranscriptHistory {
+    capacity: usize,
+    next_id: u64,
+    entries: Vec<TranscriptEntry>,
+}
+
+impl Default for TranscriptHistory {
+    fn default() -> Self {
+        Self::new(DEFAULT_TRANSCRIPT_HISTORY_CAPACITY)
+    }
+}
+
+impl TranscriptHistory {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity: capacity.max(1),
+            next_id: 1,
+            entries: Vec::new(),
+        }
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    pub fn push(&mut self, mut entry: TranscriptEntry) -> &TranscriptEntry {
+        entry.id = self.next_id;
+        self.next_id += 1;
+        let capacity = self.capacity.max(1);
+        if self.entries.len() >= capacity {
+            let overflow = self.entries.len() + 1 - capacity;
+            self.entries.drain(..overflow);
+        }
+        self.entries.push(entry);
+        self.entries.last().expect("entry was just pushed")
+    }
+
+    pub fn latest(&self) -> Option<&TranscriptEntry> {
+        self.entries.last()
+    }
+
+    pub fn list(&self) -> Vec<TranscriptEntry> {
+        self.entries.iter().rev().cloned().collect()
+    }
+
+    pub fn clear(&mut self) {
+        self.entries.clear();
+    }
+
+    pub fn stats(&self, window: usize) -> Vec<TranscriptionStats> {
+        let mut grouped: Vec<(String, Vec<&TranscriptMetrics>)> = Vec::new();
+        for metrics in self
+            .entries
+            .iter()
+            .rev()
+            .filter_map(|entry| entry.metrics.as_ref())
+        {
+            match grouped
+                .iter_mut()
+                .find(|(model, _)| *model == metrics.model)
+            {
+                Some((_, samples)) if samples.len() >= window => {}
+                Some((_, samples)) => samples.push(metrics),
+                None if window > 0 => grouped.push((metrics.model.clone(), vec![metrics])),
+                None => {}
+            }
+        }
+        let mut stats: Vec<TranscriptionStats> = grouped
+            .into_iter()
+            .map(|(model, samples)| {
+                let count = samples.len();
+                TranscriptionStats {
+                    model,
+                    samples: count,
+                    avg_audio_seconds: samples.iter().map(|item| item.audio_seconds).sum::<f32>()
+                        / count as f32,
+                    avg_wall_ms: samples.iter().map(|item| item.wall_ms).sum::<u64>()
+                        / count as u64,
+                    avg_rtf: samples.iter().map(|item| item.rtf).sum::<f32>() / count as f32,
+                }
+            })
+            .collect();
+        stats.sort_by(|left, right| left.model.cmp(&right.model));
+        stats
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(text: &str) -> TranscriptEntry {
+        TranscriptEntry::new(text, 1_700_000_000_000, 1_250, None, OutputMode::Clipboard)
+    }
+
+    #[test]
+    fn push_assigns_ids_and_lists_newest_first() {
+        let mut history = TranscriptHistory::new(5);
+
+        history.push(entry("first"));
+        history.push(entry("second"));
+
+        let listed = history.list();
+        assert_eq!(listed[0].text, "second");
+        assert_eq!(listed[0].id, 2);
+        assert_eq!(listed[1].text, "first");
+        assert_eq!(listed[1].id, 1);
+    }
+
+    #[test]
+    fn evicts_oldest_entries_past_capacity() {
+        let mut history = TranscriptHistory::new(2);
+
+        history.push(entry("one"));
+        history.push(entry("two"));
+        history.push(entry("three"));
+
+        let texts: Vec<_> = history.list().into_iter().map(|item| item.text).collect();
+        assert_eq!(texts, vec!["three", "two"]);
+        assert_eq!(history.latest().map(|item| item.id), Some(3));
+    }
+
+    #[test]
+    fn clear_keeps_id_sequence() {
+        let mut history = TranscriptHistory::new(2);
+        history.push(entry("one"));
+
+        history.clear();
+        let pushed = history.push(entry("two")).id;
+
+        assert_eq!(history.len(), 1);
+        assert_eq!(pushed, 2);
+    }
+
+    #[test]
+    fn entry_keeps_recording_path() {
+        let item = entry("hi").with_recording_path("/tmp/recording.wav");
+        let value = serde_json::to_value(&item).unwrap();
+
+        assert_eq!(value["recording_path"], "/tmp/recording.wav");
+        let decoded: TranscriptEntry = serde_json::from_value(value).unwrap();
+        assert_eq!(decoded, item);
+    }
+
+    #[test]
+    fn entry_serializes_segments_when_present() {
+        let item = entry("hi there").with_segments(vec![TranscriptSegment {
+            start_ms: 0,
+            end_ms: 1_200,
+            text: "hi there".to_string(),
+        }]);
+        let value = serde_json::to_value(&item).unwrap();
+
+        assert_eq!(
+            value["segments"],
+            serde_json::json!([{ "start_ms": 0, "end_ms": 1_200, "text": "hi there" }])
+        );
+        let decoded: TranscriptEntry = serde_json::from_value(value).unwrap();
+        assert_eq!(decoded, item);
+    }
+
+    fn timed(text: &str, model: &str, wall_ms: u64) -> TranscriptEntry {
+        entry(text).with_metrics(TranscriptMetrics {
+            audio_seconds: 2.0,
+            wall_ms,
+            rtf: wall_ms as f32 / 2_000.0,
+            model: model.to_string(),
+        })
+    }
+
+    #[test]
+    fn stats_average_recent_metrics_per_model() {
+        let mut history = TranscriptHistory::new(10);
+        history.push(timed("old", "base", 4_000));
+        history.push(timed("a", "base", 1_000));
+        history.push(entry("untimed"));
+        history.push(timed("b", "tiny", 200));
+        history.push(timed("c", "base", 500));
+
+        let stats = history.stats(2);
+
+        assert_eq!(
+            stats,
+            vec![
+                TranscriptionStats {
+                    model: "base".to_string(),
+                    samples: 2,
+                    avg_audio_seconds: 2.0,
+                    avg_wall_ms: 750,
+                    avg_rtf: 0.375,
+                },
+                TranscriptionStats {
+                    model: "tiny".to_string(),
+                    samples: 1,
+                    avg_audio_seconds: 2.0,
+                    avg_wall_ms: 200,
+                    avg_rtf: 0.1,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn entry_omits_missing_metrics() {
+        let value = serde_json::to_value(entry("hi")).unwrap();
+        assert!(value.get("metrics").is_none());
+
+        let item = timed("hi", "base", 1_000);
+        let decoded: TranscriptEntry =
+            serde_json::from_value(serde_json::to_value(&item).unwrap()).unwrap();
+        assert_eq!(decoded, item);
+    }
+
+    #[test]
+    fn entry_counts_chars_not_bytes() {
+        assert_eq!(entry("café").chars, 4);
+    }
+
+    #[test]
+    fn entry_serde_roundtrip() {
+        let mut item = entry("hello world");
+        item.id = 7;
+        item.model = Some("base".to_string());
+
+        let value = serde_json::to_value(&item).unwrap();
+        assert_eq!(
+            value,
+            serde_json::json!({
+                "id": 7,
+                "text": "hello world",
+                "created_at_ms": 1_700_000_000_000_u64,
+                "duration_ms": 1_250,
+                "model": "base",
+                "chars": 11,
+                "output_mode": "clipboard",
+                "recording_path": null,
+            })
+        );
+        let decoded: TranscriptEntry = serde_json::from_value(value).unwrap();
+        assert_eq!(decoded, item);
+    }
+}
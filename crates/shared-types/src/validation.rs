@@ -0,0 +1,553 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{AppSettings, SettingsUpdate, SETTINGS_SCHEMA_VERSION};
+
+pub const MIN_LATENCY_MS: u16 = 50;
+pub const MAX_LATENCY_MS: u16 = 5000;
+pub const MIN_RECORDING_SECONDS: u32 = 1;
+pub const MAX_RECORDING_SECONDS: u32 = 600;
+pub const MAX_MIN_RECORDING_MS: u32 = 5000;
+pub const MIN_LEVEL_INTERVAL_MS: u32 = 10;
+pub const MAX_LEVEL_INTERVAL_MS: u32 = 1000;
+pub const MIN_SILENCE_THRESHOLD_DBFS: f32 = -90.0;
+pub const MAX_SILENCE_THRESHOLD_DBFS: f32 = 0.0;
+pub const MIN_INPUT_GAIN_DB: f32 = -24.0;
+pub const MAX_INPUT_GAIN_DB: f32 = 24.0;
+pub const RESERVED_WHISPER_ARGS: &[&str] = &[
+    "-m",
+    "--model",
+    "-f",
+    "--file",
+    "-of",
+    "--output-file",
+    "-l",
+    "--language",
+    "-t",
+    "--threads",
+    "--prompt",
+    "-h",
+    "--help",
+];
+
+pub const SUPPORTED_LANGUAGES: &[&str] = &[
+    "af", "am", "ar", "as", "az", "ba", "be", "bg", "bn", "bo", "br", "bs", "ca", "cs", "cy", "da",
+    "de", "el", "en", "es", "et", "eu", "fa", "fi", "fo", "fr", "gl", "gu", "ha", "he", "hi", "hr",
+    "ht", "hu", "hy", "id", "is", "it", "ja", "jw", "ka", "kk", "km", "kn", "ko", "la", "lb", "ln",
+    "lo", "lt", "lv", "mg", "mi", "mk", "ml", "mn", "mr", "ms", "mt", "my", "ne", "nl", "nn", "no",
+    "oc", "pa", "pl", "ps", "pt", "ro", "ru", "sa", "sd", "si", "sk", "sl", "sn", "so", "sq", "sr",
+    "su", "sv", "sw", "ta", "te", "tg", "th", "tk", "tl", "tr", "tt", "uk", "ur", "uz", "vi", "yi",
+    "yo", "zh",
+];
+
+pub fn is_supported_language(code: &str) -> bool {
+    SUPPORTED_LANGUAGES.contains(&code)
+}
+
+pub fn is_reserved_whisper_arg(arg: &str) -> bool {
+    let flag = arg.split_once('=').map_or(arg, |(flag, _)| flag);
+    RESERVED_WHISPER_ARGS.contains(&flag.trim())
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, thiserror::Error)]
+#[serde(tag = "field", rename_all = "snake_case")]
+pub enum SettingsValidationError {
+    #[error("latency_ms must be between {min} and {max}, got {value}")]
+    LatencyMs { value: u16, min: u16, max: u16 },
+    #[error("input_device must not be empty")]
+    InputDevice,
+    #[error("hotkey key must not be empty")]
+    Hotkey,
+    #[error("cancel_hotkey must be set and differ from the push-to-talk hotkey")]
+    CancelHotkey,
+    #[error("cancel_hotkey key '{value}' is not a known key")]
+    UnknownCancelHotkey { value: String },
+    #[error("language '{value}' is not a supported ISO 639-1 code")]
+    Language { value: String },
+    #[error("schema_version {value} is newer than the supported version {supported}")]
+    SchemaVersion { value: u32, supported: u32 },
+    #[error("max_recording_seconds must be between {min} and {max}, got {value}")]
+    MaxRecordingSeconds { value: u32, min: u32, max: u32 },
+    #[error("min_recording_ms must be at most {max}, got {value}")]
+    MinRecordingMs { value: u32, max: u32 },
+    #[error("level_interval_ms must be between {min} and {max}, got {value}")]
+    LevelIntervalMs { value: u32, min: u32, max: u32 },
+    #[error("silence_threshold_dbfs must be between {min} and {max}, got {value}")]
+    SilenceThresholdDbfs { value: f32, min: f32, max: f32 },
+    #[error("input_gain_db must be between {min} and {max}, got {value}")]
+    InputGainDb { value: f32, min: f32, max: f32 },
+    #[error("whisper_threads must be at least 1")]
+    WhisperThreads,
+    #[error("whisper_extra_args must not include '{value}'")]
+    WhisperExtraArgs { value: String },
+    #[error("replacement for '{to}' must have a phrase to match")]
+    Replacements { to: String },
+    #[error("paste profiles must have a window class pattern")]
+    PasteProfiles,
+}
+
+impl AppSettings {
+    pub fn validate(&self) -> Result<(), Vec<SettingsValidationError>> {
+        let mut errors = Vec::new();
+        if !(MIN_LATENCY_MS..=MAX_LATENCY_MS).contains(&self.latency_ms) {
+            errors.push(SettingsValidationError::LatencyMs {
+                value: self.latency_ms,
+                min: MIN_LATENCY_MS,
+                max: MAX_LATENCY_MS,
+            });
+        }
+        if self.input_device.is_blank() {
+            errors.push(SettingsValidationError::InputDevice);
+        }
+        if self.hotkey.key.trim().is_empty() {
+            errors.push(SettingsValidationError::Hotkey);
+        }
+        let cancel_hotkey = self.cancel_hotkey.normalized();
+        if cancel_hotkey.key.is_empty() || cancel_hotkey == self.hotkey.normalized() {
+            errors.push(SettingsValidationError::CancelHotkey);
+        }
+        if let Some(language) = &self.language {
+            if !is_supported_language(language) {
+                errors.push(SettingsValidationError::Language {
+                    value: language.clone(),
+                });
+            }
+        }
+        if !(MIN_RECORDING_SECONDS..=MAX_RECORDING_SECONDS).contains(&self.max_recording_seconds) {
+            errors.push(SettingsValidationError::MaxRecordingSeconds {
+                value: self.max_recording_seconds,
+                min: MIN_RECORDING_SECONDS,
+                max: MAX_RECORDING_SECONDS,
+            });
+        }
+        let max_min_recording_ms =
+            MAX_MIN_RECORDING_MS.min(self.max_recording_seconds.saturating_mul(1000));
+        if self.min_recording_ms > max_min_recording_ms {
+            errors.push(SettingsValidationError::MinRecordingMs {
+                value: self.min_recording_ms,
+                max: max_min_recording_ms,
+            });
+        }
+        if !(MIN_LEVEL_INTERVAL_MS..=MAX_LEVEL_INTERVAL_MS).contains(&self.level_interval_ms) {
+            errors.push(SettingsValidationError::LevelIntervalMs {
+                value: self.level_interval_ms,
+                min: MIN_LEVEL_INTERVAL_MS,
+                max: MAX_LEVEL_INTERVAL_MS,
+            });
+        }
+        if !(MIN_SILENCE_THRESHOLD_DBFS..=MAX_SILENCE_THRESHOLD_DBFS)
+            .contains(&self.silence_threshold_dbfs)
+        {
+            errors.push(SettingsValidationError::SilenceThresholdDbfs {
+                value: self.silence_threshold_dbfs,
+                min: MIN_SILENCE_THRESHOLD_DBFS,
+                max: MAX_SILENCE_THRESHOLD_DBFS,
+            });
+        }
+        if !(MIN_INPUT_GAIN_DB..=MAX_INPUT_GAIN_DB).contains(&self.input_gain_db) {
+            errors.push(SettingsValidationError::InputGainDb {
+                value: self.input_gain_db,
+                min: MIN_INPUT_GAIN_DB,
+                max: MAX_INPUT_GAIN_DB,
+            });
+        }
+        if self.whisper_threads == Some(0) {
+            errors.push(SettingsValidationError::WhisperThreads);
+        }
+        for arg in &self.whisper_extra_args {
+            if is_reserved_whisper_arg(arg) {
+                errors.push(SettingsValidationError::WhisperExtraArgs { value: arg.clone() });
+            }
+        }
+        for replacement in &self.replacements {
+            if replacement.from.trim().is_empty() {
+                errors.push(SettingsValidationError::Replacements {
+                    to: replacement.to.clone(),
+                });
+            }
+        }
+        if self
+            .paste_profiles
+            .iter()
+            .any(|profile| profile.window_class.trim().is_empty())
+        {
+            errors.push(SettingsValidationError::PasteProfiles);
+        }
+        if self.schema_version > SETTINGS_SCHEMA_VERSION {
+            errors.push(SettingsValidationError::SchemaVersion {
+                value: self.schema_version,
+                supported: SETTINGS_SCHEMA_VERSION,
+            });
+        }
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
+    }
+}
+
+impl SettingsUpdate {
+    pub fn validated_apply(
+        self,
+        current: &AppSettings,
+    ) -> Result<AppSettings, Vec<SettingsValidationError>> {
+        let next = current.apply_update(self);
+        next.validate()?;
+        Ok(next)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::HotkeyConfig;
+
+    #[test]
+    fn default_settings_are_valid() {
+        assert_eq!(AppSettings::default().validate(), Ok(()));
+    }
+
+    #[test]
+    fn rejects_latency_outside_bounds() {
+        for latency_ms in [0, MIN_LATENCY_MS - 1, MAX_LATENCY_MS + 1] {
+            let settings = AppSettings {
+                latency_ms,
+                ..AppSettings::default()
+            };
+
+            assert_eq!(
+                settings.validate(),
+                Err(vec![SettingsValidationError::LatencyMs {
+                    value: latency_ms,
+                    min: MIN_LATENCY_MS,
+                    max: MAX_LATENCY_MS,
+                }])
+            );
+        }
+    }
+
+    #[test]
+    fn rejects_blank_input_device() {
+        let settings = AppSettings {
+            input_device: "  ".into(),
+            ..AppSettings::default()
+        };
+
+        assert_eq!(
+            settings.validate(),
+            Err(vec![SettingsValidationError::InputDevice])
+        );
+    }
+
+    #[test]
+    fn rejects_blank_or_empty_device_lists() {
+        for devices in [vec![], vec!["USB Mic".to_string(), " ".to_string()]] {
+            let settings = AppSettings {
+                input_device: devices.into(),
+                ..AppSettings::default()
+            };
+
+            assert_eq!(
+                settings.validate(),
+                Err(vec![SettingsValidationError::InputDevice])
+            );
+        }
+    }
+
+    #[test]
+    fn rejects_blank_hotkey_key() {
+        let mut settings = AppSettings::default();
+        settings.hotkey.key = String::new();
+
+        assert_eq!(
+            settings.validate(),
+            Err(vec![SettingsValidationError::Hotkey])
+        );
+    }
+
+    #[test]
+    fn rejects_empty_cancel_hotkey() {
+        let mut settings = AppSettings::default();
+        settings.cancel_hotkey.key = " ".to_string();
+
+        assert_eq!(
+            settings.validate(),
+            Err(vec![SettingsValidationError::CancelHotkey])
+        );
+    }
+
+    #[test]
+    fn rejects_cancel_hotkey_matching_the_ptt_hotkey() {
+        let mut settings = AppSettings::default();
+        settings.cancel_hotkey = HotkeyConfig {
+            key: " Space ".to_string(),
+            ..settings.hotkey.clone()
+        };
+
+        assert_eq!(
+            settings.validate(),
+            Err(vec![SettingsValidationError::CancelHotkey])
+        );
+
+        settings.cancel_hotkey.shift = true;
+        assert_eq!(settings.validate(), Ok(()));
+    }
+
+    #[test]
+    fn rejects_zero_whisper_threads() {
+        let settings = AppSettings {
+            whisper_threads: Some(0),
+            ..AppSettings::default()
+        };
+
+        assert_eq!(
+            settings.validate(),
+            Err(vec![SettingsValidationError::WhisperThreads])
+        );
+    }
+
+    #[test]
+    fn accepts_known_language_and_rejects_unknown() {
+        let mut settings = AppSettings {
+            language: Some("fr".to_string()),
+            ..AppSettings::default()
+        };
+        assert_eq!(settings.validate(), Ok(()));
+
+        for code in ["xx", "FR", "french"] {
+            settings.language = Some(code.to_string());
+            assert_eq!(
+                settings.validate(),
+                Err(vec![SettingsValidationError::Language {
+                    value: code.to_string(),
+                }])
+            );
+        }
+    }
+
+    #[test]
+    fn rejects_recording_limits_outside_bounds() {
+        let settings = AppSettings {
+            max_recording_seconds: 0,
+            min_recording_ms: 300,
+            silence_threshold_dbfs: 3.0,
+            ..AppSettings::default()
+        };
+
+        assert_eq!(
+            settings.validate(),
+            Err(vec![
+                SettingsValidationError::MaxRecordingSeconds {
+                    value: 0,
+                    min: MIN_RECORDING_SECONDS,
+                    max: MAX_RECORDING_SECONDS,
+                },
+                SettingsValidationError::MinRecordingMs { value: 300, max: 0 },
+                SettingsValidationError::SilenceThresholdDbfs {
+                    value: 3.0,
+                    min: MIN_SILENCE_THRESHOLD_DBFS,
+                    max: MAX_SILENCE_THRESHOLD_DBFS,
+                },
+            ])
+        );
+    }
+
+    #[test]
+    fn min_recording_must_fit_inside_max_recording() {
+        let settings = AppSettings {
+            max_recording_seconds: 2,
+            min_recording_ms: 2_500,
+            ..AppSettings::default()
+        };
+        assert_eq!(
+            settings.validate(),
+            Err(vec![SettingsValidationError::MinRecordingMs {
+                value: 2_500,
+                max: 2_000,
+            }])
+        );
+
+        let settings = AppSettings {
+            min_recording_ms: MAX_MIN_RECORDING_MS + 1,
+            ..AppSettings::default()
+        };
+        assert!(settings.validate().is_err());
+    }
+
+    #[test]
+    fn level_interval_must_be_within_bounds() {
+        for value in [MIN_LEVEL_INTERVAL_MS - 1, MAX_LEVEL_INTERVAL_MS + 1] {
+            let settings = AppSettings {
+                level_interval_ms: value,
+                ..AppSettings::default()
+            };
+            assert_eq!(
+                settings.validate(),
+                Err(vec![SettingsValidationError::LevelIntervalMs {
+                    value,
+                    min: MIN_LEVEL_INTERVAL_MS,
+                    max: MAX_LEVEL_INTERVAL_MS,
+                }])
+            );
+        }
+    }
+
+    #[test]
+    fn rejects_non_finite_silence_threshold() {
+        let settings = AppSettings {
+            silence_threshold_dbfs: f32::NAN,
+            ..AppSettings::default()
+        };
+
+        assert!(matches!(
+            settings.validate().unwrap_err().as_slice(),
+            [SettingsValidationError::SilenceThresholdDbfs { .. }]
+        ));
+    }
+
+    #[test]
+    fn rejects_input_gain_outside_bounds() {
+        for input_gain_db in [MIN_INPUT_GAIN_DB - 0.5, MAX_INPUT_GAIN_DB + 0.5, f32::NAN] {
+            let settings = AppSettings {
+                input_gain_db,
+                ..AppSettings::default()
+            };
+
+            assert!(matches!(
+                settings.validate().unwrap_err().as_slice(),
+                [SettingsValidationError::InputGainDb { .. }]
+            ));
+        }
+
+        let settings = AppSettings {
+            input_gain_db: MAX_INPUT_GAIN_DB,
+            ..AppSettings::default()
+        };
+        assert_eq!(settings.validate(), Ok(()));
+    }
+
+    #[test]
+    fn rejects_reserved_whisper_args() {
+        let settings = AppSettings {
+            whisper_extra_args: vec![
+                "-bs".to_string(),
+                "5".to_string(),
+                "-of=/tmp/out".to_string(),
+                "-l".to_string(),
+                "de".to_string(),
+                "--prompt=hello".to_string(),
+            ],
+            ..AppSettings::default()
+        };
+
+        assert_eq!(
+            settings.validate().unwrap_err(),
+            vec![
+                SettingsValidationError::WhisperExtraArgs {
+                    value: "-of=/tmp/out".to_string(),
+                },
+                SettingsValidationError::WhisperExtraArgs {
+                    value: "-l".to_string(),
+                },
+                SettingsValidationError::WhisperExtraArgs {
+                    value: "--prompt=hello".to_string(),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn rejects_future_schema_version() {
+        let settings = AppSettings {
+            schema_version: SETTINGS_SCHEMA_VERSION + 1,
+            ..AppSettings::default()
+        };
+
+        assert!(matches!(
+            settings.validate().unwrap_err().as_slice(),
+            [SettingsValidationError::SchemaVersion { .. }]
+        ));
+    }
+
+    #[test]
+    fn partially_invalid_update_is_rejected_whole() {
+        let current = AppSettings::default();
+        let update = SettingsUpdate {
+            auto_export: Some(false),
+            latency_ms: Some(10),
+            input_device: Some(String::new().into()),
+            ..SettingsUpdate::default()
+        };
+
+        let errors = update.validated_apply(&current).unwrap_err();
+
+        assert_eq!(errors.len(), 2);
+        assert!(current.auto_export);
+    }
+
+    #[test]
+    fn valid_update_is_applied() {
+        let update = SettingsUpdate {
+            latency_ms: Some(MAX_LATENCY_MS),
+            ..SettingsUpdate::default()
+        };
+
+        let next = update.validated_apply(&AppSettings::default()).unwrap();
+
+        assert_eq!(next.latency_ms, MAX_LATENCY_MS);
+    }
+
+    #[test]
+    fn blank_replacement_phrases_are_rejected() {
+        let settings = AppSettings {
+            replacements: vec![crate::WordReplacement {
+                from: "  ".to_string(),
+                to: "OpenWhisperAI".to_string(),
+            }],
+            ..AppSettings::default()
+        };
+
+        assert_eq!(
+            settings.validate(),
+            Err(vec![SettingsValidationError::Replacements {
+                to: "OpenWhisperAI".to_string()
+            }])
+        );
+    }
+
+    #[test]
+    fn blank_paste_profile_patterns_are_rejected() {
+        let settings = AppSettings {
+            paste_profiles: vec![crate::PasteProfile::new(
+                " ",
+                crate::PasteShortcut::CtrlShiftV,
+            )],
+            ..AppSettings::default()
+        };
+
+        assert_eq!(
+            settings.validate(),
+            Err(vec![SettingsValidationError::PasteProfiles])
+        );
+    }
+
+    #[test]
+    fn errors_serialize_with_field_tag() {
+        let value = serde_json::to_value(SettingsValidationError::LatencyMs {
+            value: 0,
+            min: MIN_LATENCY_MS,
+            max: MAX_LATENCY_MS,
+        })
+        .unwrap();
+
+        assert_eq!(
+            value,
+            serde_json::json!({ "field": "latency_ms", "value": 0, "min": 50, "max": 5000 })
+        );
+        assert_eq!(
+            serde_json::to_value(SettingsValidationError::InputDevice).unwrap(),
+            serde_json::json!({ "field": "input_device" })
+        );
+    }
+}
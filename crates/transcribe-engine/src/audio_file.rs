@@ -0,0 +1,149 @@
+use crate::engine::EngineError;
+use audio_resample::resample_to_16k_mono;
+use std::path::Path;
+use std::time::Duration;
+
+pub const MAX_FILE_DURATION: Duration = Duration::from_secs(4 * 60 * 60);
+// Hard ceiling on the decoded mono buffer; `force` only lifts the duration limit.
+pub const MAX_DECODED_BYTES: u64 = 1 << 30;
+
+pub fn decode_audio_file(path: &Path, force: bool) -> Result<Vec<f32>, EngineError> {
+    let extension = path
+        .extension()
+        .and_then(|ext| ext.to_str())
+        .map(str::to_ascii_lowercase)
+        .unwrap_or_default();
+    match extension.as_str() {
+        "wav" | "wave" => decode_wav(path, force),
+        _ => Err(EngineError::UnsupportedFormat(path.display().to_string())),
+    }
+}
+
+fn decode_wav(path: &Path, force: bool) -> Result<Vec<f32>, EngineError> {
+    let decode_error = |err: hound::Error| EngineError::Decode(err.to_string());
+    let mut reader = hound::WavReader::open(path).map_err(decode_error)?;
+    let spec = reader.spec();
+    if spec.sample_rate == 0 || spec.channels == 0 {
+        return Err(EngineError::Decode("invalid wav header".to_string()));
+    }
+    let frames = u64::from(reader.duration());
+    let seconds = frames / u64::from(spec.sample_rate);
+    if !force && seconds > MAX_FILE_DURATION.as_secs() {
+        return Err(EngineError::AudioTooLong {
+            seconds,
+            limit: MAX_FILE_DURATION.as_secs(),
+        });
+    }
+    let bytes = frames.saturating_mul(std::mem::size_of::<f32>() as u64);
+    if bytes > MAX_DECODED_BYTES {
+        return Err(EngineError::AudioTooLarge {
+            bytes,
+            limit: MAX_DECODED_BYTES,
+        });
+    }
+    let mut mono = Vec::with_capacity(frames as usize);
+    match spec.sample_format {
+        hound::SampleFormat::Float => {
+            downmix_into(&mut mono, reader.samples::<f32>(), spec.channels).map_err(decode_error)?
+        }
+        hound::SampleFormat::Int => {
+            let scale = (1_i64 << (spec.bits_per_sample.clamp(1, 32) - 1)) as f32;
+            let samples = reader
+                .samples::<i32>()
+                .map(|sample| sample.map(|value| value as f32 / scale));
+            downmix_into(&mut mono, samples, spec.channels).map_err(decode_error)?
+        }
+    }
+    Ok(resample_to_16k_mono(mono, spec.sample_rate, 1))
+}
+
+fn downmix_into(
+    mono: &mut Vec<f32>,
+    samples: impl Iterator<Item = Result<f32, hound::Error>>,
+    channels: u16,
+) -> Result<(), hound::Error> {
+    let channels = usize::from(channels);
+    let (mut sum, mut count) = (0.0, 0);
+    for sample in samples {
+        sum += sample?;
+        count += 1;
+        if count == channels {
+            mono.push(sum / channels as f32);
+            (sum, count) = (0.0, 0);
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn write_fixture(path: &Path, sample_rate: u32, channels: u16, frames: usize) {
+        let spec = hound::WavSpec {
+            channels,
+            sample_rate,
+            bits_per_sample: 16,
+            sample_format: hound::SampleFormat::Int,
+        };
+        let mut writer = hound::WavWriter::create(path, spec).expect("create wav");
+        for frame in 0..frames {
+            let value = ((frame as f32 / 20.0).sin() * 8_000.0) as i16;
+            for _ in 0..channels {
+                writer.write_sample(value).expect("write sample");
+            }
+        }
+        writer.finalize().expect("finalize wav");
+    }
+
+    #[test]
+    fn decodes_stereo_wav_to_16k_mono() {
+        let dir = tempfile::tempdir().expect("create tempdir");
+        let path = dir.path().join("clip.wav");
+        write_fixture(&path, 48_000, 2, 48_000);
+
+        let audio = decode_audio_file(&path, false).expect("decode");
+
+        assert!((audio.len() as i64 - 16_000).abs() <= 2);
+        assert!(audio.iter().all(|sample| sample.abs() <= 1.0));
+        assert!(audio.iter().any(|sample| sample.abs() > 0.1));
+    }
+
+    #[test]
+    fn rejects_headers_that_would_decode_past_the_byte_cap_even_when_forced() {
+        let dir = tempfile::tempdir().expect("create tempdir");
+        let path = dir.path().join("huge.wav");
+        write_fixture(&path, 48_000, 1, 16);
+        let mut bytes = std::fs::read(&path).expect("read wav");
+        let data_len = u32::MAX - 65;
+        bytes[4..8].copy_from_slice(&(data_len + 36).to_le_bytes());
+        bytes[40..44].copy_from_slice(&data_len.to_le_bytes());
+        std::fs::write(&path, bytes).expect("write wav");
+
+        let err = decode_audio_file(&path, true).unwrap_err();
+
+        assert!(matches!(
+            err,
+            EngineError::AudioTooLarge {
+                limit: MAX_DECODED_BYTES,
+                ..
+            }
+        ));
+    }
+
+    #[test]
+    fn rejects_unsupported_extensions() {
+        let err = decode_audio_file(Path::new("/tmp/clip.ogg"), false).unwrap_err();
+        assert!(matches!(err, EngineError::UnsupportedFormat(_)));
+    }
+
+    #[test]
+    fn reports_decode_errors_for_invalid_wav() {
+        let dir = tempfile::tempdir().expect("create tempdir");
+        let path = dir.path().join("broken.wav");
+        std::fs::write(&path, b"not a wav").expect("write file");
+
+        let err = decode_audio_file(&path, false).unwrap_err();
+        assert!(matches!(err, EngineError::Decode(_)));
+    }
+}
@@ -1,14 +1,86 @@
-use log::warn;
+use crate::engine::{Segment, TranscriptionResult};
+use log::{info, warn};
+use serde::Deserialize;
+use shared_types::is_reserved_whisper_arg;
+use std::collections::HashSet;
 use std::env;
+use std::ffi::{OsStr, OsString};
+use std::io::{Cursor, Read, Seek, Write};
 use std::path::{Path, PathBuf};
-use std::process::Command;
+use std::process::{Child, Command, Output, Stdio};
+use std::sync::{Mutex, OnceLock};
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, Instant};
 
 #[derive(Debug, thiserror::Error)]
+#[non_exhaustive]
 pub enum BindingError {
     #[error("whisper.cpp bindings unavailable")]
     Unavailable,
-    #[error("failed to initialize whisper.cpp context")]
-    InitFailed,
+    #[error("failed to load whisper model: {stderr}")]
+    ModelLoadFailed { stderr: String },
+    #[error("whisper model format is not supported: {stderr}")]
+    UnsupportedModel { stderr: String },
+    #[error("whisper.cpp ran out of memory: {stderr}")]
+    OutOfMemory { stderr: String },
+    #[error("whisper.cpp failed ({status}): {stderr}")]
+    ProcessFailed { status: String, stderr: String },
+    #[error("whisper.cpp i/o error: {0}")]
+    IoError(String),
+    #[error("whisper.cpp produced no output")]
+    OutputMissing,
+    #[error("whisper transcription failed: {0}")]
+    Transcribe(String),
+    #[error("whisper.cpp timed out after {millis}ms")]
+    Timeout { millis: u64 },
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TranscribeOptions {
+    pub language: Option<String>,
+    pub translate: bool,
+    pub initial_prompt: Option<String>,
+    pub threads: Option<u8>,
+    pub extra_args: Vec<String>,
+    pub timeout: Option<Duration>,
+    pub partial: bool,
+}
+
+impl TranscribeOptions {
+    pub fn with_language(mut self, language: Option<String>) -> Self {
+        self.language = language;
+        self
+    }
+
+    pub fn with_translate(mut self, translate: bool) -> Self {
+        self.translate = translate;
+        self
+    }
+
+    pub fn with_initial_prompt(mut self, prompt: Option<String>) -> Self {
+        self.initial_prompt = prompt;
+        self
+    }
+
+    pub fn with_threads(mut self, threads: Option<u8>) -> Self {
+        self.threads = threads;
+        self
+    }
+
+    pub fn with_extra_args(mut self, extra_args: Vec<String>) -> Self {
+        self.extra_args = extra_args;
+        self
+    }
+
+    pub fn with_timeout(mut self, timeout: Option<Duration>) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    pub fn with_partial(mut self, partial: bool) -> Self {
+        self.partial = partial;
+        self
+    }
 }
 
 pub trait WhisperBindings {
@@ -16,17 +88,109 @@ pub trait WhisperBindings {
 
     fn init_from_file(path: &Path) -> Result<Self::Context, BindingError>;
     fn transcribe(context: &Self::Context, audio: &[f32]) -> Result<String, BindingError>;
+
+    fn transcribe_with_language(
+        context: &Self::Context,
+        audio: &[f32],
+        language: Option<&str>,
+    ) -> Result<String, BindingError> {
+        let _ = language;
+        Self::transcribe(context, audio)
+    }
+
+    fn transcribe_segments(
+        context: &Self::Context,
+        audio: &[f32],
+        language: Option<&str>,
+    ) -> Result<TranscriptionResult, BindingError> {
+        Self::transcribe_with_language(context, audio, language).map(TranscriptionResult::from_text)
+    }
+
+    fn transcribe_with_options(
+        context: &Self::Context,
+        audio: &[f32],
+        options: &TranscribeOptions,
+    ) -> Result<TranscriptionResult, BindingError> {
+        Self::transcribe_segments(context, audio, options.language.as_deref())
+    }
+
+    fn detect_language(
+        context: &Self::Context,
+        audio: &[f32],
+    ) -> Result<(String, f32), BindingError> {
+        let _ = (context, audio);
+        Err(BindingError::Unavailable)
+    }
 }
 
 pub struct WhisperCppBindings;
 
 const WHISPER_SAMPLE_RATE: u32 = 16_000;
+const DEFAULT_CLI_TIMEOUT: Duration = Duration::from_secs(120);
+const CLI_TIMEOUT_PER_AUDIO_SECOND: u32 = 2;
+const CLI_POLL_INTERVAL: Duration = Duration::from_millis(50);
+const STDERR_TAIL_LINES: usize = 5;
+const OUT_OF_MEMORY_ERRORS: [&str; 4] = [
+    "out of memory",
+    "failed to allocate",
+    "bad_alloc",
+    "cannot allocate memory",
+];
+const UNSUPPORTED_MODEL_ERRORS: [&str; 4] = [
+    "bad magic",
+    "unknown ggml type",
+    "invalid ftype",
+    "unsupported model",
+];
+const MODEL_LOAD_ERRORS: [&str; 3] = [
+    "failed to load model",
+    "failed to initialize whisper context",
+    "invalid model",
+];
 const WHISPER_BITS_PER_SAMPLE: u16 = 16;
+const DETECTED_LANGUAGE_MARKER: &str = "auto-detected language:";
+const STDIN_INPUT: &str = "-";
+const STDIN_REJECTED_ERRORS: [&str; 3] =
+    ["input file not found", "failed to open", "failed to read"];
 
-fn resolve_whisper_bin() -> std::ffi::OsString {
+static STDIN_UNSUPPORTED: OnceLock<Mutex<HashSet<OsString>>> = OnceLock::new();
+
+fn resolve_whisper_bin() -> OsString {
     std::env::var_os("WHISPER_CPP_BIN").unwrap_or_else(|| "whisper".into())
 }
 
+#[derive(Deserialize)]
+struct CliJsonOutput {
+    transcription: Vec<CliJsonSegment>,
+}
+
+#[derive(Deserialize)]
+struct CliJsonSegment {
+    offsets: CliJsonOffsets,
+    text: String,
+}
+
+#[derive(Deserialize)]
+struct CliJsonOffsets {
+    from: u64,
+    to: u64,
+}
+
+fn parse_cli_json(output: &str) -> Option<Vec<Segment>> {
+    let output = serde_json::from_str::<CliJsonOutput>(output).ok()?;
+    Some(
+        output
+            .transcription
+            .into_iter()
+            .map(|segment| Segment {
+                start_ms: segment.offsets.from,
+                end_ms: segment.offsets.to,
+                text: segment.text.trim().to_string(),
+            })
+            .collect(),
+    )
+}
+
 fn parse_cli_output(output: &str) -> String {
     let mut parts = Vec::new();
     for line in output.lines() {
@@ -48,38 +212,158 @@ fn parse_cli_output(output: &str) -> String {
     parts.join(" ").trim().to_string()
 }
 
-fn write_wav(path: &Path, audio: &[f32]) -> Result<(), BindingError> {
+fn parse_cli_segments(output: &str) -> Vec<Segment> {
+    output
+        .lines()
+        .filter_map(|line| {
+            let (range, text) = line.trim().strip_prefix('[')?.split_once(']')?;
+            let (start, end) = range.split_once("-->")?;
+            let text = text.trim();
+            if text.is_empty() {
+                return None;
+            }
+            Some(Segment {
+                start_ms: parse_cli_timestamp(start)?,
+                end_ms: parse_cli_timestamp(end)?,
+                text: text.to_string(),
+            })
+        })
+        .collect()
+}
+
+fn parse_cli_timestamp(value: &str) -> Option<u64> {
+    let (clock, millis) = value.trim().split_once(['.', ','])?;
+    let mut seconds = 0u64;
+    for part in clock.split(':') {
+        seconds = seconds * 60 + part.parse::<u64>().ok()?;
+    }
+    Some(seconds * 1000 + millis.parse::<u64>().ok()?)
+}
+
+fn cli_language_arg(language: Option<&str>) -> &str {
+    match language {
+        Some(code) if !code.trim().is_empty() => code.trim(),
+        _ => "auto",
+    }
+}
+
+pub(crate) fn sanitize_prompt(prompt: &str) -> Option<String> {
+    let prompt = prompt
+        .split(char::is_control)
+        .flat_map(str::split_whitespace)
+        .collect::<Vec<_>>()
+        .join(" ");
+    (!prompt.is_empty()).then_some(prompt)
+}
+
+pub fn write_wav(path: &Path, audio: &[f32]) -> Result<(), BindingError> {
+    let file = std::fs::File::create(path).map_err(|err| BindingError::IoError(err.to_string()))?;
+    encode_wav(std::io::BufWriter::new(file), audio)
+}
+
+fn wav_bytes(audio: &[f32]) -> Result<Vec<u8>, BindingError> {
+    let mut cursor = Cursor::new(Vec::new());
+    encode_wav(&mut cursor, audio)?;
+    Ok(cursor.into_inner())
+}
+
+fn encode_wav<W: Write + Seek>(writer: W, audio: &[f32]) -> Result<(), BindingError> {
     let spec = hound::WavSpec {
         channels: 1,
         sample_rate: WHISPER_SAMPLE_RATE,
         bits_per_sample: WHISPER_BITS_PER_SAMPLE,
         sample_format: hound::SampleFormat::Int,
     };
-    let mut writer = hound::WavWriter::create(path, spec).map_err(|_| BindingError::InitFailed)?;
+    let wav_error = |err: hound::Error| BindingError::IoError(err.to_string());
+    let mut writer = hound::WavWriter::new(writer, spec).map_err(wav_error)?;
     for sample in audio {
         let scaled = (sample.clamp(-1.0, 1.0) * i16::MAX as f32) as i16;
-        writer
-            .write_sample(scaled)
-            .map_err(|_| BindingError::InitFailed)?;
+        writer.write_sample(scaled).map_err(wav_error)?;
     }
-    writer.finalize().map_err(|_| BindingError::InitFailed)
+    writer.finalize().map_err(wav_error)
 }
 
-fn run_whisper_cli_with_bin(
-    bin: &std::ffi::OsStr,
+fn cli_args(
     model_path: &Path,
-    audio: &[f32],
-) -> Result<String, BindingError> {
-    let bin_path = Path::new(bin);
-    let bin_dir = bin_path.parent();
-    let temp_dir = tempfile::tempdir().map_err(|_| BindingError::InitFailed)?;
-    let wav_path = temp_dir.path().join("audio.wav");
-    write_wav(&wav_path, audio)?;
-    let output_prefix = temp_dir.path().join("whisper-output");
+    wav_path: &Path,
+    output_prefix: &Path,
+    options: &TranscribeOptions,
+) -> Vec<OsString> {
+    cli_args_with_output(
+        model_path,
+        wav_path.as_os_str(),
+        Some(output_prefix),
+        options,
+    )
+}
 
+fn cli_args_with_output(
+    model_path: &Path,
+    input: &OsStr,
+    output_prefix: Option<&Path>,
+    options: &TranscribeOptions,
+) -> Vec<OsString> {
+    let mut args: Vec<OsString> = vec![
+        "-m".into(),
+        model_path.into(),
+        "-f".into(),
+        input.into(),
+        "-l".into(),
+        cli_language_arg(options.language.as_deref()).into(),
+    ];
+    if options.translate {
+        args.push("--translate".into());
+    }
+    if options.partial {
+        args.push("-mc".into());
+        args.push("0".into());
+    } else if let Some(prompt) = options.initial_prompt.as_deref().and_then(sanitize_prompt) {
+        args.push("--prompt".into());
+        args.push(prompt.into());
+    }
+    if let Some(threads) = options.threads.filter(|threads| *threads > 0) {
+        args.push("-t".into());
+        args.push(threads.to_string().into());
+    }
+    if let Some(output_prefix) = output_prefix {
+        args.extend([
+            "-oj".into(),
+            "-otxt".into(),
+            "-of".into(),
+            output_prefix.into(),
+        ]);
+    }
+    args.extend(
+        filter_extra_args(&options.extra_args)
+            .into_iter()
+            .map(Into::into),
+    );
+    args
+}
+
+fn filter_extra_args(extra_args: &[String]) -> Vec<String> {
+    let mut filtered = Vec::new();
+    let mut args = extra_args.iter().map(|arg| arg.trim()).peekable();
+    while let Some(arg) = args.next() {
+        if arg.is_empty() {
+            continue;
+        }
+        if is_reserved_whisper_arg(arg) {
+            warn!("ignoring reserved whisper cli argument '{arg}'");
+            if !arg.contains('=') {
+                args.next_if(|value| !value.starts_with('-'));
+            }
+            continue;
+        }
+        filtered.push(arg.to_string());
+    }
+    filtered
+}
+
+fn whisper_command(bin: &OsStr) -> Command {
     let mut command = Command::new(bin);
     if cfg!(target_os = "linux") {
-        if let Some(dir) = bin_dir {
+        if let Some(dir) = Path::new(bin).parent() {
             let current = env::var_os("LD_LIBRARY_PATH").unwrap_or_default();
             let mut value = dir.as_os_str().to_os_string();
             if !current.is_empty() {
@@ -89,25 +373,115 @@ fn run_whisper_cli_with_bin(
             command.env("LD_LIBRARY_PATH", value);
         }
     }
+    command
+}
 
-    let output = command
-        .arg("-m")
-        .arg(model_path)
-        .arg("-f")
-        .arg(&wav_path)
-        .arg("-l")
-        .arg("auto")
-        .arg("-otxt")
-        .arg("-of")
-        .arg(&output_prefix)
-        .output()
-        .map_err(|err| {
-            if err.kind() == std::io::ErrorKind::NotFound {
-                BindingError::Unavailable
-            } else {
-                BindingError::InitFailed
+fn stdin_enabled(bin: &OsStr) -> bool {
+    let configured = env::var("WHISPER_CPP_STDIN")
+        .map(|value| !matches!(value.trim(), "0" | "false" | "off"))
+        .unwrap_or(true);
+    configured
+        && !STDIN_UNSUPPORTED
+            .get_or_init(Default::default)
+            .lock()
+            .unwrap_or_else(|err| err.into_inner())
+            .contains(bin)
+}
+
+fn mark_stdin_unsupported(bin: &OsStr) {
+    STDIN_UNSUPPORTED
+        .get_or_init(Default::default)
+        .lock()
+        .unwrap_or_else(|err| err.into_inner())
+        .insert(bin.to_os_string());
+}
+
+fn stdin_rejected(err: &BindingError) -> bool {
+    let BindingError::ProcessFailed { stderr, .. } = err else {
+        return false;
+    };
+    let stderr = stderr.to_lowercase();
+    STDIN_REJECTED_ERRORS
+        .iter()
+        .any(|pattern| stderr.contains(pattern))
+}
+
+fn run_whisper_cli_with_bin(
+    bin: &OsStr,
+    model_path: &Path,
+    audio: &[f32],
+    options: &TranscribeOptions,
+) -> Result<TranscriptionResult, BindingError> {
+    if stdin_enabled(bin) {
+        let started = Instant::now();
+        match run_whisper_cli_stdin(bin, model_path, audio, options) {
+            Err(err) if stdin_rejected(&err) => {
+                warn!("whisper cli rejected audio on stdin ({err}); falling back to temp files");
+                mark_stdin_unsupported(bin);
             }
-        })?;
+            result => {
+                info!(
+                    "whisper cli (stdin) finished in {}ms",
+                    started.elapsed().as_millis()
+                );
+                return result;
+            }
+        }
+    }
+    let started = Instant::now();
+    let result = run_whisper_cli_files(bin, model_path, audio, options);
+    info!(
+        "whisper cli (temp files) finished in {}ms",
+        started.elapsed().as_millis()
+    );
+    result
+}
+
+fn run_whisper_cli_stdin(
+    bin: &OsStr,
+    model_path: &Path,
+    audio: &[f32],
+    options: &TranscribeOptions,
+) -> Result<TranscriptionResult, BindingError> {
+    let wav = wav_bytes(audio)?;
+    let mut command = whisper_command(bin);
+    command.args(cli_args_with_output(
+        model_path,
+        OsStr::new(STDIN_INPUT),
+        None,
+        options,
+    ));
+    let output = output_with_timeout(&mut command, Some(wav), cli_timeout(options, audio.len()))?;
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    if !output.status.success() {
+        return Err(cli_failure(&output.status.to_string(), &stderr));
+    }
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let segments = parse_cli_segments(&stdout);
+    if !segments.is_empty() {
+        return Ok(TranscriptionResult::from_segments(segments));
+    }
+    let text = parse_cli_output(&stdout);
+    if text.is_empty() {
+        return Err(BindingError::OutputMissing);
+    }
+    Ok(TranscriptionResult::from_text(text))
+}
+
+fn run_whisper_cli_files(
+    bin: &OsStr,
+    model_path: &Path,
+    audio: &[f32],
+    options: &TranscribeOptions,
+) -> Result<TranscriptionResult, BindingError> {
+    let temp_dir = tempfile::tempdir().map_err(|err| BindingError::IoError(err.to_string()))?;
+    let wav_path = temp_dir.path().join("audio.wav");
+    write_wav(&wav_path, audio)?;
+    let output_prefix = temp_dir.path().join("whisper-output");
+
+    let mut command = whisper_command(bin);
+    command.args(cli_args(model_path, &wav_path, &output_prefix, options));
+    let output = output_with_timeout(&mut command, None, cli_timeout(options, audio.len()))?;
 
     if !output.status.success() {
         let stdout = String::from_utf8_lossy(&output.stdout);
@@ -118,23 +492,216 @@ fn run_whisper_cli_with_bin(
             stdout.trim(),
             stderr.trim()
         );
-        return Err(BindingError::InitFailed);
+        return Err(cli_failure(&output.status.to_string(), &stderr));
+    }
+
+    let json_path = output_prefix.with_extension("json");
+    if let Some(segments) = std::fs::read_to_string(&json_path)
+        .ok()
+        .and_then(|contents| parse_cli_json(&contents))
+    {
+        return Ok(TranscriptionResult::from_segments(segments));
     }
 
+    let stdout = String::from_utf8_lossy(&output.stdout);
     let output_path = output_prefix.with_extension("txt");
-    if let Ok(contents) = std::fs::read_to_string(&output_path) {
-        let trimmed = contents.trim();
-        if !trimmed.is_empty() {
-            return Ok(trimmed.to_string());
+    let text = match std::fs::read_to_string(&output_path) {
+        Ok(contents) if !contents.trim().is_empty() => contents.trim().to_string(),
+        Ok(_) => parse_cli_output(&stdout),
+        Err(_) => {
+            let text = parse_cli_output(&stdout);
+            if text.is_empty() {
+                return Err(BindingError::OutputMissing);
+            }
+            text
+        }
+    };
+    Ok(TranscriptionResult {
+        text,
+        segments: parse_cli_segments(&stdout),
+        ..TranscriptionResult::default()
+    })
+}
+
+fn detect_language_with_bin(
+    bin: &OsStr,
+    model_path: &Path,
+    audio: &[f32],
+) -> Result<(String, f32), BindingError> {
+    let temp_dir = tempfile::tempdir().map_err(|err| BindingError::IoError(err.to_string()))?;
+    let wav_path = temp_dir.path().join("audio.wav");
+    write_wav(&wav_path, audio)?;
+
+    let mut command = whisper_command(bin);
+    command
+        .arg("-m")
+        .arg(model_path)
+        .arg("-f")
+        .arg(&wav_path)
+        .args(["-l", "auto", "--detect-language"]);
+    let timeout = cli_timeout(&TranscribeOptions::default(), audio.len());
+    let output = output_with_timeout(&mut command, None, timeout)?;
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    if !output.status.success() {
+        return Err(cli_failure(&output.status.to_string(), &stderr));
+    }
+    parse_detected_language(&stderr)
+        .or_else(|| parse_detected_language(&String::from_utf8_lossy(&output.stdout)))
+        .ok_or(BindingError::OutputMissing)
+}
+
+fn parse_detected_language(output: &str) -> Option<(String, f32)> {
+    let line = output
+        .lines()
+        .find_map(|line| line.split_once(DETECTED_LANGUAGE_MARKER))?
+        .1;
+    let (language, rest) = line.trim().split_once(' ').unwrap_or((line.trim(), ""));
+    if language.is_empty() {
+        return None;
+    }
+    let probability = rest
+        .split_once('=')
+        .and_then(|(_, value)| value.trim().trim_end_matches(')').trim().parse().ok())
+        .unwrap_or(0.0);
+    Some((language.to_string(), probability))
+}
+
+fn cli_failure(status: &str, stderr: &str) -> BindingError {
+    let stderr = stderr_tail(stderr);
+    let lowered = stderr.to_lowercase();
+    let matches = |patterns: &[&str]| patterns.iter().any(|pattern| lowered.contains(pattern));
+    if matches(&OUT_OF_MEMORY_ERRORS) {
+        BindingError::OutOfMemory { stderr }
+    } else if matches(&UNSUPPORTED_MODEL_ERRORS) {
+        BindingError::UnsupportedModel { stderr }
+    } else if matches(&MODEL_LOAD_ERRORS) {
+        BindingError::ModelLoadFailed { stderr }
+    } else {
+        BindingError::ProcessFailed {
+            status: status.to_string(),
+            stderr,
         }
     }
+}
+
+fn stderr_tail(stderr: &str) -> String {
+    let lines: Vec<&str> = stderr
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .collect();
+    lines[lines.len().saturating_sub(STDERR_TAIL_LINES)..].join("\n")
+}
 
-    Ok(String::new())
+fn cli_timeout(options: &TranscribeOptions, samples: usize) -> Duration {
+    options.timeout.unwrap_or_else(|| {
+        let audio = Duration::from_secs_f64(samples as f64 / f64::from(WHISPER_SAMPLE_RATE));
+        DEFAULT_CLI_TIMEOUT.max(audio * CLI_TIMEOUT_PER_AUDIO_SECOND)
+    })
+}
+
+fn output_with_timeout(
+    command: &mut Command,
+    input: Option<Vec<u8>>,
+    timeout: Duration,
+) -> Result<Output, BindingError> {
+    command
+        .stdin(if input.is_some() {
+            Stdio::piped()
+        } else {
+            Stdio::null()
+        })
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped());
+    #[cfg(unix)]
+    std::os::unix::process::CommandExt::process_group(command, 0);
+    let mut child = command.spawn().map_err(|err| {
+        if err.kind() == std::io::ErrorKind::NotFound {
+            BindingError::Unavailable
+        } else {
+            BindingError::IoError(err.to_string())
+        }
+    })?;
+    if let (Some(mut stdin), Some(input)) = (child.stdin.take(), input) {
+        thread::spawn(move || {
+            let _ = stdin.write_all(&input);
+        });
+    }
+    let stdout = read_pipe(child.stdout.take());
+    let stderr = read_pipe(child.stderr.take());
+    let deadline = Instant::now() + timeout;
+    let status = loop {
+        match child.try_wait() {
+            Ok(Some(status)) => break status,
+            Ok(None) if Instant::now() >= deadline => {
+                warn!("whisper cli still running after {timeout:?}; killing it");
+                kill_process_group(&mut child);
+                let _ = child.wait();
+                return Err(BindingError::Timeout {
+                    millis: timeout.as_millis() as u64,
+                });
+            }
+            Ok(None) => thread::sleep(CLI_POLL_INTERVAL),
+            Err(err) => {
+                kill_process_group(&mut child);
+                return Err(BindingError::IoError(err.to_string()));
+            }
+        }
+    };
+    Ok(Output {
+        status,
+        stdout: stdout.join().unwrap_or_default(),
+        stderr: stderr.join().unwrap_or_default(),
+    })
+}
+
+fn read_pipe<R: Read + Send + 'static>(pipe: Option<R>) -> JoinHandle<Vec<u8>> {
+    thread::spawn(move || {
+        let mut buffer = Vec::new();
+        if let Some(mut pipe) = pipe {
+            let _ = pipe.read_to_end(&mut buffer);
+        }
+        buffer
+    })
+}
+
+#[cfg(unix)]
+fn kill_process_group(child: &mut Child) {
+    let running = matches!(child.try_wait(), Ok(None));
+    match i32::try_from(child.id()) {
+        Ok(pid) if pid > 0 && running => {
+            // SAFETY: kill(2) takes no pointers. The child was spawned as the
+            // leader of its own process group and has not been reaped, so
+            // -pid names that group rather than ours or a recycled pid.
+            unsafe {
+                libc::kill(-pid, libc::SIGKILL);
+            }
+        }
+        _ => {}
+    }
+    let _ = child.kill();
+}
+
+#[cfg(not(unix))]
+fn kill_process_group(child: &mut Child) {
+    let _ = child.kill();
+}
+
+fn transcribe_with_cli(
+    model_path: &Path,
+    audio: &[f32],
+    options: &TranscribeOptions,
+) -> Result<TranscriptionResult, BindingError> {
+    let bin = resolve_whisper_bin();
+    run_whisper_cli_with_bin(bin.as_os_str(), model_path, audio, options)
 }
 
-fn transcribe_with_cli(model_path: &Path, audio: &[f32]) -> Result<String, BindingError> {
+fn detect_language_with_cli(
+    model_path: &Path,
+    audio: &[f32],
+) -> Result<(String, f32), BindingError> {
     let bin = resolve_whisper_bin();
-    run_whisper_cli_with_bin(bin.as_os_str(), model_path, audio)
+    detect_language_with_bin(bin.as_os_str(), model_path, audio)
 }
 
 #[cfg(feature = "whisper-ffi")]
@@ -173,9 +740,11 @@ impl WhisperBindings for WhisperCppBindings {
 
     fn init_from_file(path: &Path) -> Result<Self::Context, BindingError> {
         let c_path = std::ffi::CString::new(path.to_string_lossy().as_bytes())
-            .map_err(|_| BindingError::InitFailed)?;
+            .map_err(|err| BindingError::IoError(err.to_string()))?;
         let ctx = unsafe { ffi::whisper_init_from_file(c_path.as_ptr()) };
-        let ctx = std::ptr::NonNull::new(ctx).ok_or(BindingError::InitFailed)?;
+        let ctx = std::ptr::NonNull::new(ctx).ok_or_else(|| BindingError::ModelLoadFailed {
+            stderr: format!("whisper_init_from_file failed for {}", path.display()),
+        })?;
         Ok(WhisperContext {
             ctx,
             model_path: path.to_path_buf(),
@@ -183,7 +752,39 @@ impl WhisperBindings for WhisperCppBindings {
     }
 
     fn transcribe(context: &Self::Context, audio: &[f32]) -> Result<String, BindingError> {
-        transcribe_with_cli(&context.model_path, audio)
+        Self::transcribe_with_language(context, audio, None)
+    }
+
+    fn transcribe_with_language(
+        context: &Self::Context,
+        audio: &[f32],
+        language: Option<&str>,
+    ) -> Result<String, BindingError> {
+        Self::transcribe_segments(context, audio, language).map(|result| result.text)
+    }
+
+    fn transcribe_segments(
+        context: &Self::Context,
+        audio: &[f32],
+        language: Option<&str>,
+    ) -> Result<TranscriptionResult, BindingError> {
+        let options = TranscribeOptions::default().with_language(language.map(str::to_string));
+        Self::transcribe_with_options(context, audio, &options)
+    }
+
+    fn transcribe_with_options(
+        context: &Self::Context,
+        audio: &[f32],
+        options: &TranscribeOptions,
+    ) -> Result<TranscriptionResult, BindingError> {
+        transcribe_with_cli(&context.model_path, audio, options)
+    }
+
+    fn detect_language(
+        context: &Self::Context,
+        audio: &[f32],
+    ) -> Result<(String, f32), BindingError> {
+        detect_language_with_cli(&context.model_path, audio)
     }
 }
 
@@ -203,7 +804,39 @@ impl WhisperBindings for WhisperCppBindings {
     }
 
     fn transcribe(context: &Self::Context, audio: &[f32]) -> Result<String, BindingError> {
-        transcribe_with_cli(&context.model_path, audio)
+        Self::transcribe_with_language(context, audio, None)
+    }
+
+    fn transcribe_with_language(
+        context: &Self::Context,
+        audio: &[f32],
+        language: Option<&str>,
+    ) -> Result<String, BindingError> {
+        Self::transcribe_segments(context, audio, language).map(|result| result.text)
+    }
+
+    fn transcribe_segments(
+        context: &Self::Context,
+        audio: &[f32],
+        language: Option<&str>,
+    ) -> Result<TranscriptionResult, BindingError> {
+        let options = TranscribeOptions::default().with_language(language.map(str::to_string));
+        Self::transcribe_with_options(context, audio, &options)
+    }
+
+    fn transcribe_with_options(
+        context: &Self::Context,
+        audio: &[f32],
+        options: &TranscribeOptions,
+    ) -> Result<TranscriptionResult, BindingError> {
+        transcribe_with_cli(&context.model_path, audio, options)
+    }
+
+    fn detect_language(
+        context: &Self::Context,
+        audio: &[f32],
+    ) -> Result<(String, f32), BindingError> {
+        detect_language_with_cli(&context.model_path, audio)
     }
 }
 
@@ -219,6 +852,75 @@ mod tests {
         assert_eq!(parse_cli_output(output), "Hello world");
     }
 
+    #[test]
+    fn parse_cli_json_reads_segment_offsets() {
+        let output = r#"{
+            "systeminfo": "AVX = 1 | AVX2 = 1",
+            "model": {"type": "base", "multilingual": true},
+            "params": {"model": "ggml-base.bin", "language": "en", "translate": false},
+            "result": {"language": "en"},
+            "transcription": [
+                {
+                    "timestamps": {"from": "00:00:00,000", "to": "00:00:02,480"},
+                    "offsets": {"from": 0, "to": 2480},
+                    "text": " Hello there."
+                },
+                {
+                    "timestamps": {"from": "00:00:02,480", "to": "00:00:05,120"},
+                    "offsets": {"from": 2480, "to": 5120},
+                    "text": " How are you?"
+                }
+            ]
+        }"#;
+
+        let result = TranscriptionResult::from_segments(parse_cli_json(output).expect("json"));
+
+        assert_eq!(result.text, "Hello there. How are you?");
+        assert_eq!(
+            result.segments,
+            vec![
+                Segment {
+                    start_ms: 0,
+                    end_ms: 2480,
+                    text: "Hello there.".to_string(),
+                },
+                Segment {
+                    start_ms: 2480,
+                    end_ms: 5120,
+                    text: "How are you?".to_string(),
+                },
+            ]
+        );
+        assert!(parse_cli_json("Hello there.").is_none());
+    }
+
+    #[test]
+    fn parse_cli_segments_reads_timestamped_text() {
+        let output = "\
+[00:00:00.000 --> 00:00:01.500]   Hello
+[00:00:01.500 --> 00:01:02.040]   world
+whisper_print_timings: total time = 812.00 ms
+[00:01:02.040 --> 00:01:03.000]
+";
+
+        assert_eq!(
+            parse_cli_segments(output),
+            vec![
+                Segment {
+                    start_ms: 0,
+                    end_ms: 1500,
+                    text: "Hello".to_string(),
+                },
+                Segment {
+                    start_ms: 1500,
+                    end_ms: 62_040,
+                    text: "world".to_string(),
+                },
+            ]
+        );
+        assert_eq!(parse_cli_timestamp("00:01.000"), Some(1000));
+    }
+
     #[test]
     fn write_wav_encodes_pcm16() {
         let dir = tempfile::tempdir().expect("tempdir");
@@ -231,6 +933,16 @@ mod tests {
         assert_eq!(bytes.len(), 44 + 4);
     }
 
+    #[test]
+    fn write_wav_reports_unwritable_path() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let wav_path = dir.path().join("missing").join("sample.wav");
+
+        let result = write_wav(&wav_path, &[0.0]);
+
+        assert!(matches!(result, Err(BindingError::IoError(_))));
+    }
+
     #[test]
     fn run_whisper_cli_reads_output_file() {
         let dir = tempfile::tempdir().expect("tempdir");
@@ -256,8 +968,430 @@ mod tests {
 
         let model_path = dir.path().join("model.bin");
         fs::write(&model_path, "model").expect("write model");
-        let result = run_whisper_cli_with_bin(bin_path.as_os_str(), &model_path, &[0.0, 0.1])
-            .expect("transcribe");
-        assert_eq!(result, "mock transcript");
+        let result = run_whisper_cli_files(
+            bin_path.as_os_str(),
+            &model_path,
+            &[0.0, 0.1],
+            &TranscribeOptions::default(),
+        )
+        .expect("transcribe");
+        assert_eq!(result.text, "mock transcript");
+    }
+
+    #[test]
+    fn language_arg_defaults_to_auto() {
+        assert_eq!(cli_language_arg(Some("de")), "de");
+        assert_eq!(cli_language_arg(None), "auto");
+        assert_eq!(cli_language_arg(Some(" ")), "auto");
+    }
+
+    #[test]
+    fn run_whisper_cli_passes_language() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let bin_path = dir.path().join("whisper-mock");
+        let script = "#!/bin/sh\n".to_string()
+            + "out=\"\"\n"
+            + "lang=\"\"\n"
+            + "while [ \"$#\" -gt 0 ]; do\n"
+            + "  case \"$1\" in\n"
+            + "    -of) shift; out=\"$1\" ;;\n"
+            + "    -l) shift; lang=\"$1\" ;;\n"
+            + "  esac\n"
+            + "  shift\n"
+            + "done\n"
+            + "printf \"%s\" \"$lang\" > \"${out}.txt\"\n";
+        fs::write(&bin_path, script).expect("write script");
+        let mut perms = fs::metadata(&bin_path).expect("metadata").permissions();
+        perms.set_mode(0o755);
+        fs::set_permissions(&bin_path, perms).expect("set perms");
+        let model_path = dir.path().join("model.bin");
+        fs::write(&model_path, "model").expect("write model");
+
+        let run = |language: Option<&str>| {
+            let options = TranscribeOptions::default().with_language(language.map(str::to_string));
+            run_whisper_cli_files(bin_path.as_os_str(), &model_path, &[0.0], &options)
+                .expect("transcribe")
+                .text
+        };
+        assert_eq!(run(Some("fr")), "fr");
+        assert_eq!(run(None), "auto");
+    }
+
+    #[test]
+    fn cli_args_append_translate_when_requested() {
+        let args = |options: &TranscribeOptions| {
+            cli_args(
+                Path::new("model.bin"),
+                Path::new("audio.wav"),
+                Path::new("out"),
+                options,
+            )
+            .into_iter()
+            .map(|arg| arg.to_string_lossy().into_owned())
+            .collect::<Vec<_>>()
+        };
+
+        let plain = TranscribeOptions::default().with_language(Some("de".to_string()));
+        assert_eq!(
+            args(&plain),
+            [
+                "-m",
+                "model.bin",
+                "-f",
+                "audio.wav",
+                "-l",
+                "de",
+                "-oj",
+                "-otxt",
+                "-of",
+                "out"
+            ]
+        );
+        assert_eq!(
+            args(&plain.with_translate(true)),
+            [
+                "-m",
+                "model.bin",
+                "-f",
+                "audio.wav",
+                "-l",
+                "de",
+                "--translate",
+                "-oj",
+                "-otxt",
+                "-of",
+                "out"
+            ]
+        );
+    }
+
+    #[test]
+    fn cli_args_pass_initial_prompt_as_single_argument() {
+        let options = TranscribeOptions::default().with_initial_prompt(Some(
+            "Kubernetes, \"OpenWhisperAI\", $HOME;\n rm -rf\0".to_string(),
+        ));
+
+        let args = cli_args(
+            Path::new("model.bin"),
+            Path::new("audio.wav"),
+            Path::new("out"),
+            &options,
+        );
+        let prompt_index = args
+            .iter()
+            .position(|arg| arg == "--prompt")
+            .expect("prompt flag");
+
+        assert_eq!(
+            args[prompt_index + 1],
+            "Kubernetes, \"OpenWhisperAI\", $HOME; rm -rf"
+        );
+        let partial = cli_args(
+            Path::new("m"),
+            Path::new("a"),
+            Path::new("o"),
+            &options.clone().with_partial(true),
+        );
+        assert!(!partial.iter().any(|arg| arg == "--prompt"));
+        assert!(partial
+            .windows(2)
+            .any(|pair| pair[0] == "-mc" && pair[1] == "0"));
+        let blank = TranscribeOptions::default().with_initial_prompt(Some(" \n ".to_string()));
+        assert!(
+            !cli_args(Path::new("m"), Path::new("a"), Path::new("o"), &blank)
+                .iter()
+                .any(|arg| arg == "--prompt")
+        );
+    }
+
+    #[test]
+    fn extra_args_drop_reserved_flags_and_their_values() {
+        let extra_args = [
+            "-bs",
+            "5",
+            "-m",
+            "/tmp/other.bin",
+            "--no-timestamps",
+            "-of=/tmp/x",
+            "-t",
+            "8",
+            "-f",
+        ]
+        .map(str::to_string);
+
+        assert_eq!(
+            filter_extra_args(&extra_args),
+            ["-bs", "5", "--no-timestamps"]
+        );
+        assert!(is_reserved_whisper_arg("--model=/tmp/other.bin"));
+        assert!(!is_reserved_whisper_arg("-fa"));
+    }
+
+    #[test]
+    fn cli_args_place_threads_before_outputs_and_extra_args_last() {
+        let options = TranscribeOptions::default()
+            .with_threads(Some(12))
+            .with_extra_args(vec!["--no-timestamps".to_string(), "-m".to_string()]);
+
+        let args = cli_args(
+            Path::new("model.bin"),
+            Path::new("audio.wav"),
+            Path::new("out"),
+            &options,
+        )
+        .into_iter()
+        .map(|arg| arg.to_string_lossy().into_owned())
+        .collect::<Vec<_>>();
+
+        assert_eq!(
+            args,
+            [
+                "-m",
+                "model.bin",
+                "-f",
+                "audio.wav",
+                "-l",
+                "auto",
+                "-t",
+                "12",
+                "-oj",
+                "-otxt",
+                "-of",
+                "out",
+                "--no-timestamps"
+            ]
+        );
+        let auto_threads = TranscribeOptions::default().with_threads(Some(0));
+        assert!(!cli_args(
+            Path::new("m"),
+            Path::new("a"),
+            Path::new("o"),
+            &auto_threads
+        )
+        .iter()
+        .any(|arg| arg == "-t"));
+    }
+
+    #[test]
+    fn cli_timeout_scales_with_audio_length() {
+        let options = TranscribeOptions::default();
+        assert_eq!(cli_timeout(&options, 16_000), DEFAULT_CLI_TIMEOUT);
+        assert_eq!(
+            cli_timeout(&options, 16_000 * 600),
+            Duration::from_secs(1200)
+        );
+        let options = options.with_timeout(Some(Duration::from_secs(5)));
+        assert_eq!(cli_timeout(&options, 16_000 * 600), Duration::from_secs(5));
+    }
+
+    #[test]
+    fn run_whisper_cli_kills_hung_process() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let bin_path = dir.path().join("whisper-hang");
+        fs::write(&bin_path, "#!/bin/sh\nsleep 30\n").expect("write script");
+        let mut perms = fs::metadata(&bin_path).expect("metadata").permissions();
+        perms.set_mode(0o755);
+        fs::set_permissions(&bin_path, perms).expect("set perms");
+
+        let model_path = dir.path().join("model.bin");
+        fs::write(&model_path, "model").expect("write model");
+        let started = Instant::now();
+        let result = run_whisper_cli_with_bin(
+            bin_path.as_os_str(),
+            &model_path,
+            &[0.0, 0.1],
+            &TranscribeOptions::default().with_timeout(Some(Duration::from_millis(200))),
+        );
+
+        assert!(matches!(result, Err(BindingError::Timeout { millis: 200 })));
+        assert!(started.elapsed() < Duration::from_secs(10));
+    }
+
+    fn write_mock_cli(dir: &Path, script: &str) -> (PathBuf, PathBuf) {
+        let bin_path = dir.join("whisper-mock");
+        fs::write(&bin_path, format!("#!/bin/sh\n{script}")).expect("write script");
+        let mut perms = fs::metadata(&bin_path).expect("metadata").permissions();
+        perms.set_mode(0o755);
+        fs::set_permissions(&bin_path, perms).expect("set perms");
+        let model_path = dir.join("model.bin");
+        fs::write(&model_path, "model").expect("write model");
+        (bin_path, model_path)
+    }
+
+    fn run_mock_cli(script: &str) -> Result<TranscriptionResult, BindingError> {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let (bin_path, model_path) = write_mock_cli(dir.path(), script);
+        run_whisper_cli_with_bin(
+            bin_path.as_os_str(),
+            &model_path,
+            &[0.0, 0.1],
+            &TranscribeOptions::default(),
+        )
+    }
+
+    #[test]
+    fn run_whisper_cli_reports_model_load_stderr() {
+        let result = run_mock_cli(
+            "echo 'whisper_init: loading model' >&2\n\
+             echo 'error: failed to load model from model.bin' >&2\nexit 1\n",
+        );
+
+        let err = result.expect_err("model load failure");
+        assert!(matches!(err, BindingError::ModelLoadFailed { .. }));
+        assert!(err
+            .to_string()
+            .contains("error: failed to load model from model.bin"));
+    }
+
+    #[test]
+    fn run_whisper_cli_reports_exit_status_and_stderr_tail() {
+        let result =
+            run_mock_cli("for i in 1 2 3 4 5 6 7; do echo \"line $i\" >&2; done\nexit 3\n");
+
+        match result {
+            Err(BindingError::ProcessFailed { status, stderr }) => {
+                assert!(status.contains('3'));
+                assert_eq!(stderr, "line 3\nline 4\nline 5\nline 6\nline 7");
+            }
+            other => panic!("unexpected result: {other:?}"),
+        }
+    }
+
+    #[test]
+    fn run_whisper_cli_reports_missing_output() {
+        let result = run_mock_cli("exit 0\n");
+
+        assert!(matches!(result, Err(BindingError::OutputMissing)));
+    }
+
+    #[test]
+    fn parse_detected_language_reads_code_and_probability() {
+        let stderr = "whisper_init_from_file: loading model\n\
+                      whisper_full_with_state: auto-detected language: fr (p = 0.912345)\n";
+        let (language, probability) = parse_detected_language(stderr).expect("language");
+        assert_eq!(language, "fr");
+        assert!((probability - 0.912345).abs() < 1e-6);
+        assert_eq!(parse_detected_language("no language here"), None);
+    }
+
+    #[test]
+    fn detect_language_runs_cli_in_detect_mode() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let (bin_path, model_path) = write_mock_cli(
+            dir.path(),
+            "case \"$*\" in *--detect-language*) ;; *) exit 2 ;; esac\n\
+             echo 'whisper_lang_auto_detect: auto-detected language: de (p = 0.75)' >&2\n",
+        );
+
+        let (language, probability) =
+            detect_language_with_bin(bin_path.as_os_str(), &model_path, &[0.0; 160])
+                .expect("detect language");
+
+        assert_eq!(language, "de");
+        assert_eq!(probability, 0.75);
+    }
+
+    #[test]
+    fn run_whisper_cli_streams_wav_on_stdin() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let (bin_path, model_path) = write_mock_cli(
+            dir.path(),
+            "case \"$*\" in *\"-f -\"*) ;; *) exit 9 ;; esac\n\
+             case \"$*\" in *-of*) exit 8 ;; esac\n\
+             bytes=$(wc -c | tr -d ' ')\n\
+             echo \"[00:00:00.000 --> 00:00:01.500]   got $bytes bytes\"\n",
+        );
+
+        let result = run_whisper_cli_with_bin(
+            bin_path.as_os_str(),
+            &model_path,
+            &[0.0; 100],
+            &TranscribeOptions::default(),
+        )
+        .expect("transcribe");
+
+        assert_eq!(result.text, "got 244 bytes");
+        assert_eq!(result.segments.len(), 1);
+        assert_eq!(result.segments[0].end_ms, 1_500);
+        assert!(stdin_enabled(bin_path.as_os_str()));
+    }
+
+    #[test]
+    fn run_whisper_cli_falls_back_to_temp_files_when_stdin_is_rejected() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let (bin_path, model_path) = write_mock_cli(
+            dir.path(),
+            "case \"$*\" in *\"-f -\"*) echo \"error: input file not found '-'\" >&2; exit 2 ;; esac\n\
+             out=\"\"\n\
+             while [ \"$#\" -gt 0 ]; do\n\
+               if [ \"$1\" = \"-of\" ]; then shift; out=\"$1\"; fi\n\
+               shift\n\
+             done\n\
+             printf '%s' 'from file' > \"${out}.txt\"\n",
+        );
+
+        let result = run_whisper_cli_with_bin(
+            bin_path.as_os_str(),
+            &model_path,
+            &[0.0, 0.1],
+            &TranscribeOptions::default(),
+        )
+        .expect("transcribe");
+
+        assert_eq!(result.text, "from file");
+        assert!(!stdin_enabled(bin_path.as_os_str()));
+    }
+
+    #[test]
+    fn stdin_failures_that_are_not_rejections_are_reported() {
+        assert!(!stdin_rejected(&BindingError::OutputMissing));
+        assert!(stdin_rejected(&BindingError::ProcessFailed {
+            status: "exit status: 2".to_string(),
+            stderr: "error: failed to read audio data as wav".to_string(),
+        }));
+        assert!(!stdin_rejected(&BindingError::ProcessFailed {
+            status: "exit status: 3".to_string(),
+            stderr: "segmentation fault".to_string(),
+        }));
+        assert!(!stdin_rejected(&BindingError::Timeout { millis: 1_000 }));
+    }
+
+    #[test]
+    fn cli_failure_classifies_known_whisper_errors() {
+        let classify = |stderr: &str| cli_failure("exit status: 1", stderr);
+
+        assert!(matches!(
+            classify(
+                "whisper_init_from_file_with_params_no_state: loading model from 'ggml-base.bin'\n\
+                 whisper_model_load: tensor 'decoder.blocks.5.mlp.0.weight' has wrong size in model file\n\
+                 error: failed to initialize whisper context"
+            ),
+            BindingError::ModelLoadFailed { .. }
+        ));
+        assert!(matches!(
+            classify(
+                "whisper_model_load: invalid model data (bad magic)\nerror: failed to load model"
+            ),
+            BindingError::UnsupportedModel { .. }
+        ));
+        assert!(matches!(
+            classify("whisper_model_load: unknown ggml type 41 for tensor encoder.conv1.weight"),
+            BindingError::UnsupportedModel { .. }
+        ));
+        assert!(matches!(
+            classify("ggml_backend_cpu_buffer_type_alloc_buffer: failed to allocate buffer of size 1550 MB"),
+            BindingError::OutOfMemory { .. }
+        ));
+        assert!(matches!(
+            classify("terminate called after throwing an instance of 'std::bad_alloc'"),
+            BindingError::OutOfMemory { .. }
+        ));
+        match classify("whisper_full: something unexpected") {
+            BindingError::ProcessFailed { status, stderr } => {
+                assert_eq!(status, "exit status: 1");
+                assert_eq!(stderr, "whisper_full: something unexpected");
+            }
+            other => panic!("unexpected classification: {other:?}"),
+        }
     }
 }
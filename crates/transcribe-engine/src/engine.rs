@@ -1,10 +1,77 @@
-use crate::bindings::{BindingError, WhisperBindings, WhisperCppBindings};
+use crate::audio_file::decode_audio_file;
+use crate::bindings::{BindingError, TranscribeOptions, WhisperBindings, WhisperCppBindings};
 use crate::model::{FsDownloader, ModelDownloader, ModelError, ModelId, ModelManager};
 use std::marker::PhantomData;
+use std::path::Path;
+use std::time::{Duration, Instant};
+
+const METRICS_SAMPLE_RATE: f32 = 16_000.0;
 
 #[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Segment {
+    pub start_ms: u64,
+    pub end_ms: u64,
+    pub text: String,
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct TranscriptionMetrics {
+    pub audio_seconds: f32,
+    pub wall_ms: u64,
+    pub rtf: f32,
+    pub model: String,
+}
+
+impl TranscriptionMetrics {
+    pub fn measure(model: impl Into<String>, samples: usize, elapsed: Duration) -> Self {
+        let audio_seconds = samples as f32 / METRICS_SAMPLE_RATE;
+        let rtf = if audio_seconds > 0.0 {
+            elapsed.as_secs_f32() / audio_seconds
+        } else {
+            0.0
+        };
+        Self {
+            audio_seconds,
+            wall_ms: elapsed.as_millis() as u64,
+            rtf,
+            model: model.into(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
 pub struct TranscriptionResult {
     pub text: String,
+    pub segments: Vec<Segment>,
+    pub metrics: TranscriptionMetrics,
+}
+
+impl TranscriptionResult {
+    pub fn from_text(text: impl Into<String>) -> Self {
+        Self {
+            text: text.into(),
+            ..Self::default()
+        }
+    }
+
+    pub fn with_metrics(mut self, metrics: TranscriptionMetrics) -> Self {
+        self.metrics = metrics;
+        self
+    }
+
+    pub fn from_segments(segments: Vec<Segment>) -> Self {
+        let text = segments
+            .iter()
+            .map(|segment| segment.text.trim())
+            .filter(|text| !text.is_empty())
+            .collect::<Vec<_>>()
+            .join(" ");
+        Self {
+            text,
+            segments,
+            ..Self::default()
+        }
+    }
 }
 
 #[derive(Debug, thiserror::Error)]
@@ -15,10 +82,27 @@ pub enum EngineError {
     Binding(#[from] BindingError),
     #[error("audio buffer is empty")]
     EmptyAudio,
+    #[error("unsupported audio file format: {0}")]
+    UnsupportedFormat(String),
+    #[error("failed to decode audio file: {0}")]
+    Decode(String),
+    #[error("audio file is {seconds}s long, which exceeds the {limit}s limit")]
+    AudioTooLong { seconds: u64, limit: u64 },
+    #[error("audio file would decode to {bytes} bytes, which exceeds the {limit} byte limit")]
+    AudioTooLarge { bytes: u64, limit: u64 },
 }
 
 pub trait TranscriptionEngine {
     fn transcribe(&self, audio: &[f32]) -> Result<TranscriptionResult, EngineError>;
+
+    fn transcribe_with_options(
+        &self,
+        audio: &[f32],
+        options: &TranscribeOptions,
+    ) -> Result<TranscriptionResult, EngineError> {
+        let _ = options;
+        self.transcribe(audio)
+    }
 }
 
 pub struct TranscriptionPipeline<
@@ -44,6 +128,30 @@ impl<B: WhisperBindings, D: ModelDownloader> TranscriptionPipeline<B, D> {
         model_id: ModelId,
         audio: &[f32],
     ) -> Result<TranscriptionResult, EngineError> {
+        self.transcribe_with_options(model_id, audio, &TranscribeOptions::default())
+    }
+
+    pub fn transcribe_with_options(
+        &self,
+        model_id: ModelId,
+        audio: &[f32],
+        options: &TranscribeOptions,
+    ) -> Result<TranscriptionResult, EngineError> {
+        if audio.is_empty() {
+            return Err(EngineError::EmptyAudio);
+        }
+        let model_path = self
+            .manager
+            .ensure_model_cached(&model_id, &self.downloader)?;
+        let context = B::init_from_file(&model_path)?;
+        timed_transcription::<B>(&context, &model_id, audio, options)
+    }
+
+    pub fn detect_language(
+        &self,
+        model_id: ModelId,
+        audio: &[f32],
+    ) -> Result<(String, f32), EngineError> {
         if audio.is_empty() {
             return Err(EngineError::EmptyAudio);
         }
@@ -51,16 +159,45 @@ impl<B: WhisperBindings, D: ModelDownloader> TranscriptionPipeline<B, D> {
             .manager
             .ensure_model_cached(&model_id, &self.downloader)?;
         let context = B::init_from_file(&model_path)?;
-        let text = B::transcribe(&context, audio)?;
-        Ok(TranscriptionResult { text })
+        Ok(B::detect_language(&context, audio)?)
+    }
+
+    pub fn transcribe_file(
+        &self,
+        model_id: ModelId,
+        path: &Path,
+    ) -> Result<TranscriptionResult, EngineError> {
+        self.transcribe_file_with_options(model_id, path, &TranscribeOptions::default(), false)
+    }
+
+    pub fn transcribe_file_with_options(
+        &self,
+        model_id: ModelId,
+        path: &Path,
+        options: &TranscribeOptions,
+        force: bool,
+    ) -> Result<TranscriptionResult, EngineError> {
+        let audio = decode_audio_file(path, force)?;
+        self.transcribe_with_options(model_id, &audio, options)
     }
 }
 
+fn timed_transcription<B: WhisperBindings>(
+    context: &B::Context,
+    model_id: &ModelId,
+    audio: &[f32],
+    options: &TranscribeOptions,
+) -> Result<TranscriptionResult, EngineError> {
+    let started = Instant::now();
+    let result = B::transcribe_with_options(context, audio, options)?;
+    let metrics =
+        TranscriptionMetrics::measure(model_id.display_name(), audio.len(), started.elapsed());
+    Ok(result.with_metrics(metrics))
+}
+
 pub struct WhisperCppEngine<B: WhisperBindings = WhisperCppBindings> {
     _marker: PhantomData<B>,
-    #[allow(dead_code)]
     model_id: ModelId,
-    #[allow(dead_code)]
     context: B::Context,
 }
 
@@ -84,11 +221,18 @@ impl<B: WhisperBindings> WhisperCppEngine<B> {
 
 impl<B: WhisperBindings> TranscriptionEngine for WhisperCppEngine<B> {
     fn transcribe(&self, audio: &[f32]) -> Result<TranscriptionResult, EngineError> {
+        self.transcribe_with_options(audio, &TranscribeOptions::default())
+    }
+
+    fn transcribe_with_options(
+        &self,
+        audio: &[f32],
+        options: &TranscribeOptions,
+    ) -> Result<TranscriptionResult, EngineError> {
         if audio.is_empty() {
             return Err(EngineError::EmptyAudio);
         }
-        let text = B::transcribe(&self.context, audio)?;
-        Ok(TranscriptionResult { text })
+        timed_transcription::<B>(&self.context, &self.model_id, audio, options)
     }
 }
 
@@ -119,16 +263,20 @@ impl<B: WhisperBindings> TranscriptionWrapper<B> {
 
 impl<B: WhisperBindings> TranscriptionEngine for TranscriptionWrapper<B> {
     fn transcribe(&self, audio: &[f32]) -> Result<TranscriptionResult, EngineError> {
+        self.transcribe_with_options(audio, &TranscribeOptions::default())
+    }
+
+    fn transcribe_with_options(
+        &self,
+        audio: &[f32],
+        options: &TranscribeOptions,
+    ) -> Result<TranscriptionResult, EngineError> {
         if audio.is_empty() {
             return Err(EngineError::EmptyAudio);
         }
-        let empty_result = || {
-            Ok(TranscriptionResult {
-                text: String::new(),
-            })
-        };
+        let empty_result = || Ok(TranscriptionResult::default());
         match &self.engine {
-            Some(engine) => match engine.transcribe(audio) {
+            Some(engine) => match engine.transcribe_with_options(audio, options) {
                 Err(EngineError::Binding(BindingError::Unavailable)) => empty_result(),
                 other => other,
             },
@@ -185,6 +333,52 @@ mod tests {
         }
     }
 
+    #[test]
+    fn engine_reports_real_time_factor() {
+        struct SlowBindings;
+
+        impl WhisperBindings for SlowBindings {
+            type Context = ();
+
+            fn init_from_file(_path: &std::path::Path) -> Result<Self::Context, BindingError> {
+                Ok(())
+            }
+
+            fn transcribe(
+                _context: &Self::Context,
+                _audio: &[f32],
+            ) -> Result<String, BindingError> {
+                std::thread::sleep(Duration::from_millis(100));
+                Ok("slow".to_string())
+            }
+        }
+
+        let dir = tempfile::tempdir().expect("create tempdir");
+        let mut manager = ModelManager::new(dir.path());
+        let id = ModelId::Custom("slow".to_string());
+        manager.register_model(ModelSpec::new(id.clone(), "slow.bin").with_size(1));
+        manager.write_model_bytes(&id, &[0u8]).expect("write model");
+        let engine =
+            WhisperCppEngine::<SlowBindings>::with_bindings(&manager, id).expect("load engine");
+
+        let result = engine.transcribe(&[0.0; 8_000]).expect("transcribe");
+
+        let metrics = result.metrics;
+        assert_eq!(metrics.model, "slow");
+        assert_eq!(metrics.audio_seconds, 0.5);
+        assert!(metrics.wall_ms >= 100 && metrics.wall_ms < 1_000);
+        let expected_rtf = metrics.wall_ms as f32 / 500.0;
+        assert!((metrics.rtf - expected_rtf).abs() < 0.01);
+    }
+
+    #[test]
+    fn metrics_handle_empty_audio() {
+        let metrics = TranscriptionMetrics::measure("base", 0, Duration::from_millis(20));
+        assert_eq!(metrics.audio_seconds, 0.0);
+        assert_eq!(metrics.rtf, 0.0);
+        assert_eq!(metrics.wall_ms, 20);
+    }
+
     #[test]
     fn engine_loads_with_mock_bindings() {
         let dir = tempfile::tempdir().expect("create tempdir");
@@ -298,6 +492,168 @@ mod tests {
         assert_eq!(result.text, "mock transcript");
     }
 
+    #[test]
+    fn pipeline_forwards_transcribe_options() {
+        struct OptionBindings;
+
+        impl WhisperBindings for OptionBindings {
+            type Context = MockContext;
+
+            fn init_from_file(path: &std::path::Path) -> Result<Self::Context, BindingError> {
+                Ok(MockContext {
+                    _path: path.to_path_buf(),
+                })
+            }
+
+            fn transcribe(
+                _context: &Self::Context,
+                _audio: &[f32],
+            ) -> Result<String, BindingError> {
+                Ok("plain".to_string())
+            }
+
+            fn transcribe_with_options(
+                _context: &Self::Context,
+                _audio: &[f32],
+                options: &TranscribeOptions,
+            ) -> Result<TranscriptionResult, BindingError> {
+                Ok(TranscriptionResult::from_text(format!(
+                    "{}:{}",
+                    options.language.as_deref().unwrap_or("auto"),
+                    options.translate
+                )))
+            }
+        }
+
+        let dir = tempfile::tempdir().expect("create tempdir");
+        let mut manager = ModelManager::new(dir.path());
+        let spec = ModelSpec::new(ModelId::Custom("options".to_string()), "options.bin")
+            .with_download_url("file://mock")
+            .with_size(1);
+        manager.register_model(spec);
+        let pipeline =
+            TranscriptionPipeline::<OptionBindings, _>::new(manager, MockDownloader::new(vec![0]));
+        let options = TranscribeOptions::default()
+            .with_language(Some("fr".to_string()))
+            .with_translate(true);
+
+        let translated = pipeline
+            .transcribe_with_options(ModelId::Custom("options".to_string()), &[0.1], &options)
+            .expect("transcribe");
+        let default = pipeline
+            .transcribe(ModelId::Custom("options".to_string()), &[0.1])
+            .expect("transcribe");
+
+        assert_eq!(translated.text, "fr:true");
+        assert_eq!(default.text, "auto:false");
+    }
+
+    #[test]
+    fn pipeline_transcribes_wav_files_at_16k_mono() {
+        struct LengthBindings;
+
+        impl WhisperBindings for LengthBindings {
+            type Context = MockContext;
+
+            fn init_from_file(path: &std::path::Path) -> Result<Self::Context, BindingError> {
+                Ok(MockContext {
+                    _path: path.to_path_buf(),
+                })
+            }
+
+            fn transcribe(_context: &Self::Context, audio: &[f32]) -> Result<String, BindingError> {
+                Ok(audio.len().to_string())
+            }
+        }
+
+        let dir = tempfile::tempdir().expect("create tempdir");
+        let wav_path = dir.path().join("meeting.wav");
+        let spec = hound::WavSpec {
+            channels: 2,
+            sample_rate: 44_100,
+            bits_per_sample: 16,
+            sample_format: hound::SampleFormat::Int,
+        };
+        let mut writer = hound::WavWriter::create(&wav_path, spec).expect("create wav");
+        for _ in 0..44_100 * 2 * 2 {
+            writer.write_sample(1_000_i16).expect("write sample");
+        }
+        writer.finalize().expect("finalize wav");
+
+        let mut manager = ModelManager::new(dir.path().join("models"));
+        let spec = ModelSpec::new(ModelId::Custom("file".to_string()), "file.bin")
+            .with_download_url("file://mock")
+            .with_size(1);
+        manager.register_model(spec);
+        let pipeline =
+            TranscriptionPipeline::<LengthBindings, _>::new(manager, MockDownloader::new(vec![0]));
+
+        let result = pipeline
+            .transcribe_file(ModelId::Custom("file".to_string()), &wav_path)
+            .expect("transcribe file");
+
+        let samples: i64 = result.text.parse().expect("sample count");
+        assert!((samples - 32_000).abs() <= 2, "got {samples} samples");
+        assert_eq!(result.metrics.model, "file");
+    }
+
+    #[test]
+    fn pipeline_detects_language_through_bindings() {
+        struct DetectBindings;
+
+        impl WhisperBindings for DetectBindings {
+            type Context = MockContext;
+
+            fn init_from_file(path: &std::path::Path) -> Result<Self::Context, BindingError> {
+                Ok(MockContext {
+                    _path: path.to_path_buf(),
+                })
+            }
+
+            fn transcribe(
+                _context: &Self::Context,
+                _audio: &[f32],
+            ) -> Result<String, BindingError> {
+                panic!("detection must not run a full transcription");
+            }
+
+            fn detect_language(
+                _context: &Self::Context,
+                _audio: &[f32],
+            ) -> Result<(String, f32), BindingError> {
+                Ok(("fr".to_string(), 0.9))
+            }
+        }
+
+        let dir = tempfile::tempdir().expect("create tempdir");
+        let mut manager = ModelManager::new(dir.path());
+        let spec = ModelSpec::new(ModelId::Custom("detect".to_string()), "detect.bin")
+            .with_download_url("file://mock")
+            .with_size(1);
+        manager.register_model(spec);
+        let pipeline =
+            TranscriptionPipeline::<DetectBindings, _>::new(manager, MockDownloader::new(vec![0]));
+
+        let detected = pipeline
+            .detect_language(ModelId::Custom("detect".to_string()), &[0.1; 160])
+            .expect("detect");
+        assert_eq!(detected, ("fr".to_string(), 0.9));
+        assert!(matches!(
+            pipeline.detect_language(ModelId::Custom("detect".to_string()), &[]),
+            Err(EngineError::EmptyAudio)
+        ));
+        let mut manager = ModelManager::new(dir.path());
+        manager.register_model(
+            ModelSpec::new(ModelId::Custom("detect".to_string()), "detect.bin").with_size(1),
+        );
+        let unsupported =
+            TranscriptionPipeline::<MockBindings, _>::new(manager, MockDownloader::new(vec![0]));
+        assert!(matches!(
+            unsupported.detect_language(ModelId::Custom("detect".to_string()), &[0.1]),
+            Err(EngineError::Binding(BindingError::Unavailable))
+        ));
+    }
+
     #[test]
     fn pipeline_reuses_cached_model() {
         let dir = tempfile::tempdir().expect("create tempdir");
@@ -404,7 +760,9 @@ mod tests {
             type Context = MockContext;
 
             fn init_from_file(_path: &std::path::Path) -> Result<Self::Context, BindingError> {
-                Err(BindingError::InitFailed)
+                Err(BindingError::ModelLoadFailed {
+                    stderr: "bad magic".to_string(),
+                })
             }
 
             fn transcribe(
@@ -430,7 +788,7 @@ mod tests {
         );
         assert!(matches!(
             result,
-            Err(EngineError::Binding(BindingError::InitFailed))
+            Err(EngineError::Binding(BindingError::ModelLoadFailed { .. }))
         ));
     }
 }
@@ -1,13 +1,24 @@
+mod audio_file;
 mod bindings;
 mod engine;
 mod model;
+#[cfg(feature = "whisper-native")]
+mod native;
+mod subtitles;
 
-pub use bindings::{BindingError, WhisperBindings, WhisperCppBindings};
+pub use audio_file::{decode_audio_file, MAX_DECODED_BYTES, MAX_FILE_DURATION};
+pub use bindings::{
+    write_wav, BindingError, TranscribeOptions, WhisperBindings, WhisperCppBindings,
+};
 pub use engine::{
-    EngineError, TranscriptionEngine, TranscriptionPipeline, TranscriptionResult,
-    TranscriptionWrapper, WhisperCppEngine,
+    EngineError, Segment, TranscriptionEngine, TranscriptionMetrics, TranscriptionPipeline,
+    TranscriptionResult, TranscriptionWrapper, WhisperCppEngine,
 };
 pub use model::{
-    AutoDownloader, FsDownloader, HttpDownloader, ModelDownloader, ModelError, ModelId,
-    ModelManager, ModelSpec,
+    standard_model_checksum, AutoDownloader, DownloadProgress, FreeSpace, FsDownloader,
+    HttpDownloader, InstalledModel, ModelDiskUsage, ModelDownloader, ModelError, ModelId,
+    ModelManager, ModelSpec, PartialDownload, Quantization, RetryPolicy, StandardModel,
+    SystemFreeSpace, VerificationReport,
 };
+#[cfg(feature = "whisper-native")]
+pub use native::{WhisperNativeBindings, WhisperNativeContext};
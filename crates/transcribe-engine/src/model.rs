@@ -1,8 +1,13 @@
+use fs2::FileExt;
+use serde::Deserialize;
 use sha2::{Digest, Sha256};
-use std::collections::HashMap;
-use std::fs::File;
-use std::io::{Read, Write};
+use std::collections::{HashMap, HashSet};
+use std::fs::{File, OpenOptions};
+use std::io::{Read, Seek, SeekFrom, Write};
 use std::path::{Component, Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex, OnceLock};
+use std::time::{Duration, SystemTime};
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub enum ModelId {
@@ -11,10 +16,140 @@ pub enum ModelId {
     Small,
     Medium,
     Large,
+    Quantized {
+        base: StandardModel,
+        english: bool,
+        quant: Quantization,
+    },
     Custom(String),
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum StandardModel {
+    Tiny,
+    Base,
+    Small,
+    Medium,
+    Large,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Quantization {
+    Q5_0,
+    Q5_1,
+    Q8_0,
+}
+
+impl StandardModel {
+    pub const ALL: [StandardModel; 5] = [
+        StandardModel::Tiny,
+        StandardModel::Base,
+        StandardModel::Small,
+        StandardModel::Medium,
+        StandardModel::Large,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            StandardModel::Tiny => "tiny",
+            StandardModel::Base => "base",
+            StandardModel::Small => "small",
+            StandardModel::Medium => "medium",
+            StandardModel::Large => "large",
+        }
+    }
+
+    pub fn has_english_variant(self) -> bool {
+        self != StandardModel::Large
+    }
+
+    /// Quantized files published for this model. whisper.cpp only ships
+    /// quantized large models under their large-v2/v3 names, so `Large` has none.
+    pub fn quantizations(self) -> &'static [Quantization] {
+        match self {
+            StandardModel::Tiny | StandardModel::Base | StandardModel::Small => {
+                &[Quantization::Q5_1, Quantization::Q8_0]
+            }
+            StandardModel::Medium => &[Quantization::Q5_0, Quantization::Q8_0],
+            StandardModel::Large => &[],
+        }
+    }
+
+    fn from_name(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|model| model.name() == name)
+    }
+}
+
+impl From<StandardModel> for ModelId {
+    fn from(model: StandardModel) -> Self {
+        match model {
+            StandardModel::Tiny => ModelId::Tiny,
+            StandardModel::Base => ModelId::Base,
+            StandardModel::Small => ModelId::Small,
+            StandardModel::Medium => ModelId::Medium,
+            StandardModel::Large => ModelId::Large,
+        }
+    }
+}
+
+impl Quantization {
+    pub const ALL: [Quantization; 3] = [Quantization::Q5_0, Quantization::Q5_1, Quantization::Q8_0];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Quantization::Q5_0 => "q5_0",
+            Quantization::Q5_1 => "q5_1",
+            Quantization::Q8_0 => "q8_0",
+        }
+    }
+
+    fn from_name(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|quant| quant.name() == name)
+    }
+}
+
 impl ModelId {
+    pub fn from_name(name: &str) -> Self {
+        let name = name.trim().to_ascii_lowercase();
+        if let Some(model) = StandardModel::from_name(&name) {
+            return model.into();
+        }
+        let quantized = name.rsplit_once('-').and_then(|(model, quant)| {
+            let (model, english) = match model.strip_suffix(".en") {
+                Some(model) => (model, true),
+                None => (model, false),
+            };
+            let base = StandardModel::from_name(model)?;
+            let quant = Quantization::from_name(quant)?;
+            let published = base.quantizations().contains(&quant);
+            (published && (!english || base.has_english_variant())).then_some(ModelId::Quantized {
+                base,
+                english,
+                quant,
+            })
+        });
+        quantized.unwrap_or(ModelId::Custom(name))
+    }
+
+    pub fn quantized_variants() -> Vec<ModelId> {
+        let mut variants = Vec::new();
+        for base in StandardModel::ALL {
+            for english in [false, true] {
+                if english && !base.has_english_variant() {
+                    continue;
+                }
+                for &quant in base.quantizations() {
+                    variants.push(ModelId::Quantized {
+                        base,
+                        english,
+                        quant,
+                    });
+                }
+            }
+        }
+        variants
+    }
+
     pub fn display_name(&self) -> String {
         match self {
             ModelId::Tiny => "tiny".to_string(),
@@ -22,16 +157,31 @@ impl ModelId {
             ModelId::Small => "small".to_string(),
             ModelId::Medium => "medium".to_string(),
             ModelId::Large => "large".to_string(),
+            ModelId::Quantized {
+                base,
+                english,
+                quant,
+            } => {
+                let language = if *english { ".en" } else { "" };
+                format!("{}{language}-{}", base.name(), quant.name())
+            }
             ModelId::Custom(name) => name.clone(),
         }
     }
+
+    pub fn ggml_filename(&self) -> Option<String> {
+        match self {
+            ModelId::Custom(_) => None,
+            other => Some(format!("ggml-{}.bin", other.display_name())),
+        }
+    }
 }
 
 #[derive(Debug, Clone)]
 pub struct ModelSpec {
     pub id: ModelId,
     pub filename: String,
-    pub download_url: Option<String>,
+    pub download_urls: Vec<String>,
     pub sha256: Option<String>,
     pub size_bytes: Option<u64>,
 }
@@ -41,14 +191,23 @@ impl ModelSpec {
         Self {
             id,
             filename: filename.into(),
-            download_url: None,
+            download_urls: Vec::new(),
             sha256: None,
             size_bytes: None,
         }
     }
 
     pub fn with_download_url(mut self, url: impl Into<String>) -> Self {
-        self.download_url = Some(url.into());
+        self.download_urls.push(url.into());
+        self
+    }
+
+    pub fn with_download_urls<I, S>(mut self, urls: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.download_urls.extend(urls.into_iter().map(Into::into));
         self
     }
 
@@ -75,21 +234,337 @@ pub enum ModelError {
     MissingDownloadUrl(String),
     #[error("failed to download model from {0}")]
     DownloadFailed(String),
+    #[error("model download from {url} returned HTTP {status}")]
+    HttpStatus { url: String, status: u16 },
+    #[error("all model download mirrors failed: {}", format_attempts(attempts))]
+    AllMirrorsFailed { attempts: Vec<(String, String)> },
     #[error("model size mismatch: expected {expected} bytes, got {actual} bytes")]
     SizeMismatch { expected: u64, actual: u64 },
     #[error("model checksum mismatch: expected {expected}, got {actual}")]
     ChecksumMismatch { expected: String, actual: String },
+    #[error("model download was cancelled")]
+    Cancelled,
+    #[error("model '{0}' is already being downloaded")]
+    DownloadInProgress(String),
+    #[error("invalid model manifest: {0}")]
+    InvalidManifest(String),
+    #[error("not a ggml model file: {0}")]
+    NotAModel(String),
+    #[error("a model named '{0}' already exists")]
+    ModelExists(String),
+    #[error("not enough disk space: {required} bytes required, {available} bytes available")]
+    InsufficientDiskSpace { required: u64, available: u64 },
     #[error("io error while handling model file")]
     Io(#[from] std::io::Error),
 }
 
+const DOWNLOAD_CHUNK_SIZE: usize = 64 * 1024;
+const DISK_SPACE_MARGIN: u64 = 64 * 1024 * 1024;
+const STALE_DOWNLOAD_AGE: Duration = Duration::from_secs(60 * 60);
+const DEFAULT_CONNECT_TIMEOUT: Duration = Duration::from_secs(15);
+const DEFAULT_READ_TIMEOUT: Duration = Duration::from_secs(30);
+const DEFAULT_USER_AGENT: &str = concat!("OpenWhisperAI/", env!("CARGO_PKG_VERSION"));
+const GGML_MAGIC: [u8; 4] = *b"lmgg";
+const GGUF_MAGIC: [u8; 4] = *b"GGUF";
+
+const STANDARD_MODEL_CHECKSUMS: [(&str, u64, &str); 4] = [
+    (
+        "ggml-tiny.bin",
+        77_691_713,
+        "be07e048e1e599ad46341c8d2a135645097a538221678b7acdd1b1919c6e1b21",
+    ),
+    (
+        "ggml-base.bin",
+        147_951_465,
+        "60ed5bc3dd14eea856493d334349b405782ddcaf0028d4b5df4088345fba2efe",
+    ),
+    (
+        "ggml-small.bin",
+        487_601_967,
+        "1be3a9b2063867b937e64e2ec7483364a79917e157fa98c5d94b5c1fffea987b",
+    ),
+    (
+        "ggml-medium.bin",
+        1_533_763_059,
+        "6c14d5adee5f86394037b4e4e8b59f1673b6cee10e3cf0b11bbdbee79c156208",
+    ),
+];
+
+pub fn standard_model_checksum(filename: &str) -> Option<(u64, &'static str)> {
+    STANDARD_MODEL_CHECKSUMS
+        .iter()
+        .find(|(name, _, _)| *name == filename)
+        .map(|(_, size_bytes, sha256)| (*size_bytes, *sha256))
+}
+
+#[derive(Debug, Deserialize)]
+struct ManifestEntry {
+    id: String,
+    filename: String,
+    url: Option<String>,
+    sha256: Option<String>,
+    size_bytes: Option<u64>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InstalledModel {
+    pub path: PathBuf,
+    pub filename: String,
+    pub size_bytes: u64,
+    pub registered: Option<ModelId>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ModelDiskUsage {
+    pub per_model: Vec<(ModelId, u64)>,
+    pub temp_bytes: u64,
+    pub total: u64,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VerificationReport {
+    pub path: PathBuf,
+    pub size_bytes: u64,
+    pub expected_size_bytes: Option<u64>,
+    pub sha256: String,
+    pub expected_sha256: Option<String>,
+}
+
+impl VerificationReport {
+    pub fn size_matches(&self) -> bool {
+        self.expected_size_bytes
+            .is_none_or(|expected| expected == self.size_bytes)
+    }
+
+    pub fn checksum_matches(&self) -> bool {
+        self.expected_sha256
+            .as_ref()
+            .is_none_or(|expected| expected.eq_ignore_ascii_case(&self.sha256))
+    }
+
+    pub fn is_valid(&self) -> bool {
+        self.size_matches() && self.checksum_matches()
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DownloadProgress {
+    pub downloaded_bytes: u64,
+    pub total_bytes: Option<u64>,
+}
+
+impl ModelError {
+    pub fn is_transient(&self) -> bool {
+        match self {
+            ModelError::DownloadFailed(_) => true,
+            ModelError::HttpStatus { status, .. } => {
+                *status >= 500 || *status == 408 || *status == 429
+            }
+            _ => false,
+        }
+    }
+}
+
+fn format_attempts(attempts: &[(String, String)]) -> String {
+    attempts
+        .iter()
+        .map(|(url, error)| format!("{url} ({error})"))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RetryPolicy {
+    pub attempts: u32,
+    pub initial_backoff: Duration,
+}
+
+impl RetryPolicy {
+    fn backoff(&self, attempt: u32) -> Duration {
+        self.initial_backoff
+            .saturating_mul(2u32.saturating_pow(attempt.saturating_sub(1)))
+    }
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            attempts: 3,
+            initial_backoff: Duration::from_millis(500),
+        }
+    }
+}
+
+pub trait FreeSpace: Send + Sync {
+    fn available_space(&self, path: &Path) -> std::io::Result<u64>;
+}
+
+pub struct SystemFreeSpace;
+
+impl FreeSpace for SystemFreeSpace {
+    fn available_space(&self, path: &Path) -> std::io::Result<u64> {
+        fs2::available_space(path)
+    }
+}
+
 pub trait ModelDownloader {
     fn download(&self, url: &str) -> Result<Vec<u8>, ModelError>;
+
+    fn download_with_progress(
+        &self,
+        url: &str,
+        progress: &mut dyn FnMut(DownloadProgress),
+    ) -> Result<Vec<u8>, ModelError> {
+        let bytes = self.download(url)?;
+        let len = bytes.len() as u64;
+        progress(DownloadProgress {
+            downloaded_bytes: len,
+            total_bytes: Some(len),
+        });
+        Ok(bytes)
+    }
+
+    fn download_to(
+        &self,
+        url: &str,
+        partial: &mut PartialDownload,
+        progress: &mut dyn FnMut(DownloadProgress),
+    ) -> Result<(), ModelError> {
+        let bytes = self.download_with_progress(url, progress)?;
+        partial.restart()?;
+        partial.write_all(&bytes)?;
+        Ok(())
+    }
+}
+
+fn active_downloads() -> &'static Mutex<HashSet<PathBuf>> {
+    static ACTIVE: OnceLock<Mutex<HashSet<PathBuf>>> = OnceLock::new();
+    ACTIVE.get_or_init(|| Mutex::new(HashSet::new()))
+}
+
+struct DownloadLock {
+    path: PathBuf,
+    file: File,
+}
+
+impl DownloadLock {
+    fn acquire(path: &Path, id: &ModelId) -> Result<Self, ModelError> {
+        let in_progress = || ModelError::DownloadInProgress(id.display_name());
+        {
+            let mut active = active_downloads()
+                .lock()
+                .unwrap_or_else(|poisoned| poisoned.into_inner());
+            if !active.insert(path.to_path_buf()) {
+                return Err(in_progress());
+            }
+        }
+        let locked = OpenOptions::new()
+            .write(true)
+            .create(true)
+            .truncate(false)
+            .open(path.with_extension("lock"))
+            .and_then(|file| file.try_lock_exclusive().map(|()| file));
+        match locked {
+            Ok(file) => Ok(Self {
+                path: path.to_path_buf(),
+                file,
+            }),
+            Err(err) => {
+                active_downloads()
+                    .lock()
+                    .unwrap_or_else(|poisoned| poisoned.into_inner())
+                    .remove(path);
+                if err.kind() == fs2::lock_contended_error().kind() {
+                    Err(in_progress())
+                } else {
+                    Err(ModelError::Io(err))
+                }
+            }
+        }
+    }
+}
+
+impl Drop for DownloadLock {
+    fn drop(&mut self) {
+        let _ = FileExt::unlock(&self.file);
+        active_downloads()
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .remove(&self.path);
+    }
+}
+
+pub struct PartialDownload {
+    file: File,
+    len: u64,
+    cancel: Option<Arc<AtomicBool>>,
+}
+
+impl PartialDownload {
+    pub fn open(path: &Path) -> std::io::Result<Self> {
+        let mut file = OpenOptions::new()
+            .read(true)
+            .write(true)
+            .create(true)
+            .truncate(false)
+            .open(path)?;
+        let len = file.seek(SeekFrom::End(0))?;
+        Ok(Self {
+            file,
+            len,
+            cancel: None,
+        })
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.cancel
+            .as_ref()
+            .is_some_and(|cancel| cancel.load(Ordering::SeqCst))
+    }
+
+    pub fn len(&self) -> u64 {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn restart(&mut self) -> std::io::Result<()> {
+        self.file.set_len(0)?;
+        self.file.seek(SeekFrom::Start(0))?;
+        self.len = 0;
+        Ok(())
+    }
+}
+
+impl Write for PartialDownload {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        if self.is_cancelled() {
+            return Err(std::io::Error::other("download cancelled"));
+        }
+        let written = self.file.write(buf)?;
+        self.len += written as u64;
+        Ok(written)
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        self.file.flush()
+    }
 }
 
 pub struct FsDownloader;
 
-pub struct HttpDownloader;
+#[derive(Clone)]
+pub struct HttpDownloader {
+    agent: ureq::Agent,
+    direct_agent: ureq::Agent,
+    connect_timeout: Duration,
+    read_timeout: Duration,
+    user_agent: String,
+    proxy: Option<String>,
+    no_proxy: Vec<String>,
+}
 
 pub struct AutoDownloader;
 
@@ -100,46 +575,415 @@ impl ModelDownloader for FsDownloader {
     }
 }
 
+impl HttpDownloader {
+    pub fn new() -> Self {
+        Self {
+            agent: ureq::Agent::new(),
+            direct_agent: ureq::Agent::new(),
+            connect_timeout: DEFAULT_CONNECT_TIMEOUT,
+            read_timeout: DEFAULT_READ_TIMEOUT,
+            user_agent: DEFAULT_USER_AGENT.to_string(),
+            proxy: None,
+            no_proxy: Vec::new(),
+        }
+        .rebuild()
+    }
+
+    pub fn from_env() -> Self {
+        let var = |name: &str| std::env::var(name).ok();
+        Self::new()
+            .with_proxy(proxy_from_env(var))
+            .with_no_proxy(no_proxy_from_env(var))
+    }
+
+    pub fn with_connect_timeout(mut self, timeout: Duration) -> Self {
+        self.connect_timeout = timeout;
+        self.rebuild()
+    }
+
+    pub fn with_read_timeout(mut self, timeout: Duration) -> Self {
+        self.read_timeout = timeout;
+        self.rebuild()
+    }
+
+    pub fn with_user_agent(mut self, user_agent: impl Into<String>) -> Self {
+        self.user_agent = user_agent.into();
+        self.rebuild()
+    }
+
+    pub fn with_proxy(mut self, proxy: Option<String>) -> Self {
+        self.proxy = proxy;
+        self.rebuild()
+    }
+
+    /// Hosts reached directly even when a proxy is set, in `NO_PROXY` syntax.
+    pub fn with_no_proxy(mut self, no_proxy: Option<String>) -> Self {
+        self.no_proxy = no_proxy
+            .iter()
+            .flat_map(|value| value.split(','))
+            .map(|entry| entry.trim().trim_start_matches('.').to_ascii_lowercase())
+            .filter(|entry| !entry.is_empty())
+            .collect();
+        self
+    }
+
+    fn rebuild(mut self) -> Self {
+        let builder = || {
+            ureq::AgentBuilder::new()
+                .timeout_connect(self.connect_timeout)
+                .timeout_read(self.read_timeout)
+                .user_agent(&self.user_agent)
+        };
+        self.direct_agent = builder().build();
+        self.agent = match self.proxy.as_deref().map(ureq::Proxy::new) {
+            Some(Ok(proxy)) => builder().proxy(proxy).build(),
+            Some(Err(err)) => {
+                let proxy = self.proxy.take().unwrap_or_default();
+                log::warn!(
+                    "ignoring invalid download proxy {}: {err}",
+                    redact_userinfo(&proxy)
+                );
+                self.direct_agent.clone()
+            }
+            None => self.direct_agent.clone(),
+        };
+        self
+    }
+
+    fn proxy_for(&self, url: &str) -> Option<&str> {
+        let proxy = self.proxy.as_deref()?;
+        let bypass = url_host(url).is_some_and(|host| {
+            let host = host.to_ascii_lowercase();
+            self.no_proxy.iter().any(|entry| {
+                entry == "*"
+                    || host == *entry
+                    || host
+                        .strip_suffix(entry.as_str())
+                        .is_some_and(|prefix| prefix.ends_with('.'))
+            })
+        });
+        (!bypass).then_some(proxy)
+    }
+
+    fn agent_for(&self, url: &str) -> &ureq::Agent {
+        if self.proxy_for(url).is_some() {
+            &self.agent
+        } else {
+            &self.direct_agent
+        }
+    }
+
+    fn get(&self, url: &str) -> Result<ureq::Response, ModelError> {
+        self.agent_for(url)
+            .get(url)
+            .call()
+            .map_err(|err| self.request_error(url, err))
+    }
+
+    fn request_error(&self, url: &str, err: ureq::Error) -> ModelError {
+        let transport = match err {
+            ureq::Error::Status(status, _) => {
+                return ModelError::HttpStatus {
+                    url: url.to_string(),
+                    status,
+                }
+            }
+            ureq::Error::Transport(transport) => transport,
+        };
+        let proxy = self.proxy_for(url);
+        let via_proxy = matches!(
+            transport.kind(),
+            ureq::ErrorKind::ProxyConnect
+                | ureq::ErrorKind::ProxyUnauthorized
+                | ureq::ErrorKind::InvalidProxyUrl
+        ) || (proxy.is_some()
+            && transport.kind() == ureq::ErrorKind::ConnectionFailed);
+        let message = if let (true, Some(proxy)) = (via_proxy, proxy) {
+            format!(
+                "{url}: proxy {} failed: {transport}",
+                redact_userinfo(proxy)
+            )
+        } else if is_timeout(&transport) && transport.kind() == ureq::ErrorKind::ConnectionFailed {
+            format!(
+                "{url}: connection timed out after {}s",
+                self.connect_timeout.as_secs_f32()
+            )
+        } else if is_timeout(&transport) {
+            self.read_timeout_message(url)
+        } else {
+            url.to_string()
+        };
+        ModelError::DownloadFailed(message)
+    }
+
+    fn read_error(&self, url: &str, err: std::io::Error) -> ModelError {
+        if matches!(
+            err.kind(),
+            std::io::ErrorKind::TimedOut | std::io::ErrorKind::WouldBlock
+        ) {
+            return ModelError::DownloadFailed(self.read_timeout_message(url));
+        }
+        ModelError::DownloadFailed(url.to_string())
+    }
+
+    fn read_timeout_message(&self, url: &str) -> String {
+        format!(
+            "{url}: no data received for {}s",
+            self.read_timeout.as_secs_f32()
+        )
+    }
+}
+
+impl Default for HttpDownloader {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn proxy_from_env(var: impl Fn(&str) -> Option<String>) -> Option<String> {
+    ["HTTPS_PROXY", "https_proxy", "HTTP_PROXY", "http_proxy"]
+        .into_iter()
+        .filter_map(var)
+        .map(|value| value.trim().to_string())
+        .find(|value| !value.is_empty())
+}
+
+fn no_proxy_from_env(var: impl Fn(&str) -> Option<String>) -> Option<String> {
+    ["NO_PROXY", "no_proxy"].into_iter().find_map(var)
+}
+
+fn url_authority(url: &str) -> (&str, &str) {
+    let (scheme, rest) = match url.find("://") {
+        Some(index) => url.split_at(index + 3),
+        None => ("", url),
+    };
+    let end = rest.find(['/', '?', '#']).unwrap_or(rest.len());
+    (scheme, &rest[..end])
+}
+
+fn url_host(url: &str) -> Option<&str> {
+    let (_, authority) = url_authority(url);
+    let host = authority.rsplit('@').next()?;
+    let host = match host.strip_prefix('[') {
+        Some(ipv6) => ipv6.split(']').next()?,
+        None => host.split(':').next()?,
+    };
+    (!host.is_empty()).then_some(host)
+}
+
+fn redact_userinfo(url: &str) -> String {
+    let (scheme, authority) = url_authority(url);
+    match authority.rsplit_once('@') {
+        Some((_, host)) => {
+            let path = &url[scheme.len() + authority.len()..];
+            format!("{scheme}***@{host}{path}")
+        }
+        None => url.to_string(),
+    }
+}
+
+fn is_timeout(err: &ureq::Transport) -> bool {
+    let mut source = std::error::Error::source(err);
+    while let Some(err) = source {
+        if let Some(io) = err.downcast_ref::<std::io::Error>() {
+            if matches!(
+                io.kind(),
+                std::io::ErrorKind::TimedOut | std::io::ErrorKind::WouldBlock
+            ) {
+                return true;
+            }
+        }
+        source = err.source();
+    }
+    false
+}
+
 impl ModelDownloader for HttpDownloader {
     fn download(&self, url: &str) -> Result<Vec<u8>, ModelError> {
-        let response = ureq::get(url)
-            .call()
-            .map_err(|_| ModelError::DownloadFailed(url.to_string()))?;
-        let mut reader = response.into_reader();
-        let mut bytes = Vec::new();
-        reader
-            .read_to_end(&mut bytes)
-            .map_err(|_| ModelError::DownloadFailed(url.to_string()))?;
-        Ok(bytes)
+        self.download_with_progress(url, &mut |_| {})
+    }
+
+    fn download_with_progress(
+        &self,
+        url: &str,
+        progress: &mut dyn FnMut(DownloadProgress),
+    ) -> Result<Vec<u8>, ModelError> {
+        let response = self.get(url)?;
+        let total_bytes = response
+            .header("Content-Length")
+            .and_then(|value| value.trim().parse::<u64>().ok());
+        read_with_progress(response.into_reader(), total_bytes, progress)
+            .map_err(|err| self.read_error(url, err))
+    }
+
+    fn download_to(
+        &self,
+        url: &str,
+        partial: &mut PartialDownload,
+        progress: &mut dyn FnMut(DownloadProgress),
+    ) -> Result<(), ModelError> {
+        let offset = partial.len();
+        let mut request = self.agent_for(url).get(url);
+        if offset > 0 {
+            request = request.set("Range", &format!("bytes={offset}-"));
+        }
+        let response = match request.call() {
+            Ok(response) => response,
+            Err(ureq::Error::Status(416, _)) if offset > 0 => self.get(url)?,
+            Err(err) => return Err(self.request_error(url, err)),
+        };
+        if response.status() != 206 {
+            partial.restart()?;
+        }
+        let start = partial.len();
+        let total_bytes = response
+            .header("Content-Length")
+            .and_then(|value| value.trim().parse::<u64>().ok())
+            .map(|len| start + len);
+        copy_with_progress(
+            response.into_reader(),
+            partial,
+            start,
+            total_bytes,
+            progress,
+        )
+        .map_err(|err| self.read_error(url, err))?;
+        Ok(())
     }
 }
 
 impl ModelDownloader for AutoDownloader {
     fn download(&self, url: &str) -> Result<Vec<u8>, ModelError> {
+        self.download_with_progress(url, &mut |_| {})
+    }
+
+    fn download_with_progress(
+        &self,
+        url: &str,
+        progress: &mut dyn FnMut(DownloadProgress),
+    ) -> Result<Vec<u8>, ModelError> {
+        if url.starts_with("http://") || url.starts_with("https://") {
+            return HttpDownloader::from_env().download_with_progress(url, progress);
+        }
+        FsDownloader.download_with_progress(url, progress)
+    }
+
+    fn download_to(
+        &self,
+        url: &str,
+        partial: &mut PartialDownload,
+        progress: &mut dyn FnMut(DownloadProgress),
+    ) -> Result<(), ModelError> {
         if url.starts_with("http://") || url.starts_with("https://") {
-            return HttpDownloader.download(url);
+            return HttpDownloader::from_env().download_to(url, partial, progress);
         }
-        FsDownloader.download(url)
+        FsDownloader.download_to(url, partial, progress)
+    }
+}
+
+fn read_with_progress(
+    reader: impl Read,
+    total_bytes: Option<u64>,
+    progress: &mut dyn FnMut(DownloadProgress),
+) -> std::io::Result<Vec<u8>> {
+    let mut bytes = Vec::new();
+    copy_with_progress(reader, &mut bytes, 0, total_bytes, progress)?;
+    Ok(bytes)
+}
+
+fn copy_with_progress(
+    mut reader: impl Read,
+    sink: &mut dyn Write,
+    start: u64,
+    total_bytes: Option<u64>,
+    progress: &mut dyn FnMut(DownloadProgress),
+) -> std::io::Result<()> {
+    let mut downloaded_bytes = start;
+    let mut buffer = vec![0u8; DOWNLOAD_CHUNK_SIZE];
+    loop {
+        let read_bytes = match reader.read(&mut buffer) {
+            Ok(0) => break,
+            Ok(read_bytes) => read_bytes,
+            Err(err) if err.kind() == std::io::ErrorKind::Interrupted => continue,
+            Err(err) => return Err(err),
+        };
+        sink.write_all(&buffer[..read_bytes])?;
+        downloaded_bytes += read_bytes as u64;
+        progress(DownloadProgress {
+            downloaded_bytes,
+            total_bytes,
+        });
     }
+    sink.flush()
 }
 
 pub struct ModelManager {
     root: PathBuf,
     registry: HashMap<ModelId, ModelSpec>,
+    cancel: Option<Arc<AtomicBool>>,
+    retry: RetryPolicy,
+    free_space: Box<dyn FreeSpace>,
+    verified: Mutex<HashMap<PathBuf, FileStamp>>,
 }
 
+type FileStamp = (u64, SystemTime);
+
 impl ModelManager {
     pub fn new(root: impl Into<PathBuf>) -> Self {
         Self {
             root: root.into(),
             registry: HashMap::new(),
+            cancel: None,
+            retry: RetryPolicy::default(),
+            free_space: Box::new(SystemFreeSpace),
+            verified: Mutex::new(HashMap::new()),
         }
     }
 
+    pub fn with_cancellation(mut self, cancel: Arc<AtomicBool>) -> Self {
+        self.cancel = Some(cancel);
+        self
+    }
+
+    pub fn with_retry_policy(mut self, retry: RetryPolicy) -> Self {
+        self.retry = retry;
+        self
+    }
+
+    pub fn with_free_space(mut self, free_space: impl FreeSpace + 'static) -> Self {
+        self.free_space = Box::new(free_space);
+        self
+    }
+
     pub fn register_model(&mut self, spec: ModelSpec) {
         self.registry.insert(spec.id.clone(), spec);
     }
 
+    pub fn specs(&self) -> impl Iterator<Item = &ModelSpec> {
+        self.registry.values()
+    }
+
+    // Later entries replace earlier ones with the same id, including built-in models.
+    pub fn load_manifest(&mut self, path: &Path) -> Result<usize, ModelError> {
+        let contents = std::fs::read_to_string(path)?;
+        let entries: Vec<ManifestEntry> = serde_json::from_str(&contents)
+            .map_err(|err| ModelError::InvalidManifest(err.to_string()))?;
+        for entry in &entries {
+            validate_model_filename(&entry.filename)?;
+        }
+        let mut ids = HashSet::new();
+        for entry in entries {
+            let id = ModelId::from_name(&entry.id);
+            let mut spec = ModelSpec::new(id.clone(), entry.filename);
+            spec.download_urls.extend(entry.url);
+            spec.sha256 = entry.sha256;
+            spec.size_bytes = entry.size_bytes;
+            self.register_model(spec);
+            ids.insert(id);
+        }
+        Ok(ids.len())
+    }
+
     pub fn model_path(&self, id: &ModelId) -> Result<PathBuf, ModelError> {
         let spec = self
             .registry
@@ -156,14 +1000,63 @@ impl ModelManager {
             .ok_or_else(|| ModelError::UnregisteredModel(id.display_name()))?;
         validate_model_filename(&spec.filename)?;
         let path = self.root.join(&spec.filename);
-        verify_model_file(&path, spec)?;
+        self.verify_installed(&path, spec)?;
         Ok(path)
     }
 
+    pub fn verify(&self, id: &ModelId) -> Result<VerificationReport, ModelError> {
+        let spec = self
+            .registry
+            .get(id)
+            .ok_or_else(|| ModelError::UnregisteredModel(id.display_name()))?;
+        validate_model_filename(&spec.filename)?;
+        let path = self.root.join(&spec.filename);
+        if !path.exists() {
+            return Err(ModelError::MissingFile(path.display().to_string()));
+        }
+        let report = VerificationReport {
+            size_bytes: path.metadata()?.len(),
+            expected_size_bytes: spec.size_bytes,
+            sha256: sha256_hex_from_file(&path)?,
+            expected_sha256: spec.sha256.clone(),
+            path,
+        };
+        if let Ok(mut verified) = self.verified.lock() {
+            verified.remove(&report.path);
+        }
+        Ok(report)
+    }
+
+    fn verify_installed(&self, path: &Path, spec: &ModelSpec) -> Result<(), ModelError> {
+        let stamp = file_stamp(path);
+        let already_verified = stamp.is_some()
+            && self
+                .verified
+                .lock()
+                .is_ok_and(|verified| verified.get(path) == stamp.as_ref());
+        if already_verified {
+            return Ok(());
+        }
+        verify_model_file(path, spec)?;
+        if let (Some(stamp), Ok(mut verified)) = (stamp, self.verified.lock()) {
+            verified.insert(path.to_path_buf(), stamp);
+        }
+        Ok(())
+    }
+
     pub fn ensure_model_cached<D: ModelDownloader>(
         &self,
         id: &ModelId,
         downloader: &D,
+    ) -> Result<PathBuf, ModelError> {
+        self.ensure_model_cached_with_progress(id, downloader, &mut |_| {})
+    }
+
+    pub fn ensure_model_cached_with_progress<D: ModelDownloader>(
+        &self,
+        id: &ModelId,
+        downloader: &D,
+        progress: &mut dyn FnMut(DownloadProgress),
     ) -> Result<PathBuf, ModelError> {
         let spec = self
             .registry
@@ -171,7 +1064,7 @@ impl ModelManager {
             .ok_or_else(|| ModelError::UnregisteredModel(id.display_name()))?;
         validate_model_filename(&spec.filename)?;
         let path = self.root.join(&spec.filename);
-        match verify_model_file(&path, spec) {
+        match self.verify_installed(&path, spec) {
             Ok(()) => return Ok(path),
             Err(ModelError::MissingFile(_))
             | Err(ModelError::SizeMismatch { .. })
@@ -179,23 +1072,287 @@ impl ModelManager {
             Err(err) => return Err(err),
         }
 
-        let url = spec
-            .download_url
-            .as_ref()
-            .ok_or_else(|| ModelError::MissingDownloadUrl(id.display_name()))?;
-        let bytes = downloader.download(url)?;
-        verify_model_bytes(spec, &bytes)?;
+        if spec.download_urls.is_empty() {
+            return Err(ModelError::MissingDownloadUrl(id.display_name()));
+        }
 
         if let Some(parent) = path.parent() {
             std::fs::create_dir_all(parent)?;
         }
+        let _lock = DownloadLock::acquire(&path, id)?;
+        if verify_model_file(&path, spec).is_ok() {
+            return Ok(path);
+        }
         let tmp_path = path.with_extension("download");
-        let mut file = File::create(&tmp_path)?;
-        file.write_all(&bytes)?;
+        let mut partial = PartialDownload::open(&tmp_path)?;
+        partial.cancel = self.cancel.clone();
+        if spec
+            .size_bytes
+            .is_some_and(|expected| partial.len() >= expected)
+            && verify_model_file(&tmp_path, spec).is_err()
+        {
+            partial.restart()?;
+        }
+        if spec
+            .size_bytes
+            .is_none_or(|expected| partial.len() < expected)
+        {
+            self.check_disk_space(spec, partial.len())?;
+            self.download_from_mirrors(spec, downloader, &mut partial, &mut |update| {
+                progress(DownloadProgress {
+                    total_bytes: update.total_bytes.or(spec.size_bytes),
+                    ..update
+                })
+            })?;
+        }
+        drop(partial);
+
+        if let Err(err) = verify_model_file(&tmp_path, spec) {
+            let _ = std::fs::remove_file(&tmp_path);
+            return Err(err);
+        }
         replace_cached_model(&tmp_path, &path)?;
         Ok(path)
     }
 
+    fn check_disk_space(&self, spec: &ModelSpec, downloaded: u64) -> Result<(), ModelError> {
+        let Some(size_bytes) = spec.size_bytes else {
+            return Ok(());
+        };
+        let available = match self.free_space.available_space(&self.root) {
+            Ok(available) => available,
+            Err(err) => {
+                log::warn!("could not read free disk space: {err}");
+                return Ok(());
+            }
+        };
+        let required = size_bytes.saturating_sub(downloaded) + DISK_SPACE_MARGIN;
+        if available < required {
+            return Err(ModelError::InsufficientDiskSpace {
+                required,
+                available,
+            });
+        }
+        Ok(())
+    }
+
+    fn download_from_mirrors<D: ModelDownloader>(
+        &self,
+        spec: &ModelSpec,
+        downloader: &D,
+        partial: &mut PartialDownload,
+        progress: &mut dyn FnMut(DownloadProgress),
+    ) -> Result<(), ModelError> {
+        let mut attempts = Vec::new();
+        for url in &spec.download_urls {
+            for attempt in 0..self.retry.attempts.max(1) {
+                if attempt > 0 {
+                    std::thread::sleep(self.retry.backoff(attempt));
+                }
+                if partial.is_cancelled() {
+                    return Err(ModelError::Cancelled);
+                }
+                let err = match downloader.download_to(url, partial, progress) {
+                    Ok(()) => return Ok(()),
+                    Err(_) if partial.is_cancelled() => return Err(ModelError::Cancelled),
+                    Err(err) => err,
+                };
+                log::warn!(
+                    "model download attempt {} from {url} failed: {err}",
+                    attempt + 1
+                );
+                let transient = err.is_transient();
+                attempts.push((url.clone(), err.to_string()));
+                if !transient {
+                    break;
+                }
+            }
+        }
+        Err(ModelError::AllMirrorsFailed { attempts })
+    }
+
+    pub fn scan_installed(&self) -> Vec<InstalledModel> {
+        let Ok(entries) = std::fs::read_dir(&self.root) else {
+            return Vec::new();
+        };
+        let mut installed = entries
+            .filter_map(Result::ok)
+            .filter_map(|entry| {
+                let path = entry.path();
+                if path.extension().and_then(|ext| ext.to_str()) != Some("bin") {
+                    return None;
+                }
+                let metadata = entry
+                    .metadata()
+                    .ok()
+                    .filter(|metadata| metadata.is_file())?;
+                if !has_model_magic(&path) {
+                    return None;
+                }
+                let filename = entry.file_name().to_str()?.to_string();
+                let registered = self
+                    .registry
+                    .values()
+                    .find(|spec| spec.filename == filename)
+                    .map(|spec| spec.id.clone());
+                Some(InstalledModel {
+                    path,
+                    filename,
+                    size_bytes: metadata.len(),
+                    registered,
+                })
+            })
+            .collect::<Vec<_>>();
+        installed.sort_by(|a, b| a.filename.cmp(&b.filename));
+        installed
+    }
+
+    pub fn delete_model(&self, id: &ModelId) -> Result<u64, ModelError> {
+        let path = self.model_path(id)?;
+        let mut freed = 0;
+        for candidate in [path.with_extension("download"), path] {
+            match candidate.metadata() {
+                Ok(metadata) => {
+                    std::fs::remove_file(&candidate)?;
+                    freed += metadata.len();
+                }
+                Err(err) if err.kind() == std::io::ErrorKind::NotFound => {}
+                Err(err) => return Err(ModelError::Io(err)),
+            }
+        }
+        Ok(freed)
+    }
+
+    pub fn partial_download_size(&self, id: &ModelId) -> Result<Option<u64>, ModelError> {
+        let path = self.model_path(id)?.with_extension("download");
+        match path.metadata() {
+            Ok(metadata) => Ok(Some(metadata.len())),
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => Ok(None),
+            Err(err) => Err(ModelError::Io(err)),
+        }
+    }
+
+    pub fn import_model(&mut self, source: &Path, id: ModelId) -> Result<PathBuf, ModelError> {
+        let filename = match id.ggml_filename() {
+            Some(filename) => filename,
+            None => {
+                let name = id.display_name();
+                let safe = !name.is_empty()
+                    && !name.starts_with('.')
+                    && name
+                        .chars()
+                        .all(|ch| ch.is_ascii_alphanumeric() || matches!(ch, '-' | '_' | '.'));
+                if !safe {
+                    return Err(ModelError::InvalidFilename(name));
+                }
+                if self.registry.contains_key(&id) {
+                    return Err(ModelError::ModelExists(name));
+                }
+                format!("{name}.bin")
+            }
+        };
+        if !source.is_file() {
+            return Err(ModelError::MissingFile(source.display().to_string()));
+        }
+        if !has_model_magic(source) {
+            return Err(ModelError::NotAModel(source.display().to_string()));
+        }
+        let path = self.root.join(&filename);
+        if path.exists() {
+            return Err(ModelError::ModelExists(id.display_name()));
+        }
+        std::fs::create_dir_all(&self.root)?;
+        if std::fs::hard_link(source, &path).is_err() {
+            let tmp_path = path.with_extension("import");
+            if let Err(err) =
+                std::fs::copy(source, &tmp_path).and_then(|_| std::fs::rename(&tmp_path, &path))
+            {
+                let _ = std::fs::remove_file(&tmp_path);
+                return Err(ModelError::Io(err));
+            }
+        }
+        self.register_model(ModelSpec::new(id, filename));
+        Ok(path)
+    }
+
+    pub fn cleanup_temp_files(&self) -> Result<u64, ModelError> {
+        let now = SystemTime::now();
+        let active = active_downloads()
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .iter()
+            .map(|path| path.with_extension("download"))
+            .collect::<HashSet<_>>();
+        let mut reclaimed = 0;
+        for (path, metadata) in self.temp_files()? {
+            let stale = metadata
+                .modified()
+                .ok()
+                .and_then(|modified| now.duration_since(modified).ok())
+                .is_some_and(|age| age >= STALE_DOWNLOAD_AGE);
+            if !stale || active.contains(&path) {
+                continue;
+            }
+            match std::fs::remove_file(&path) {
+                Ok(()) => reclaimed += metadata.len(),
+                Err(err) if err.kind() == std::io::ErrorKind::NotFound => {}
+                Err(err) => return Err(ModelError::Io(err)),
+            }
+        }
+        Ok(reclaimed)
+    }
+
+    pub fn disk_usage(&self) -> Result<ModelDiskUsage, ModelError> {
+        let mut per_model = Vec::new();
+        for spec in self.registry.values() {
+            let Ok(path) = self.model_path(&spec.id) else {
+                continue;
+            };
+            if let Some(metadata) = path.metadata().ok().filter(|metadata| metadata.is_file()) {
+                per_model.push((spec.id.clone(), metadata.len()));
+            }
+        }
+        for installed in self.scan_installed() {
+            if installed.registered.is_some() {
+                continue;
+            }
+            let name = installed
+                .filename
+                .strip_suffix(".bin")
+                .unwrap_or(&installed.filename);
+            per_model.push((ModelId::Custom(name.to_string()), installed.size_bytes));
+        }
+        per_model.sort_by_key(|(id, _)| id.display_name());
+        let temp_bytes = self
+            .temp_files()?
+            .iter()
+            .map(|(_, metadata)| metadata.len())
+            .sum::<u64>();
+        let total = temp_bytes + per_model.iter().map(|(_, size)| size).sum::<u64>();
+        Ok(ModelDiskUsage {
+            per_model,
+            temp_bytes,
+            total,
+        })
+    }
+
+    fn temp_files(&self) -> Result<Vec<(PathBuf, std::fs::Metadata)>, ModelError> {
+        let entries = match std::fs::read_dir(&self.root) {
+            Ok(entries) => entries,
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(err) => return Err(ModelError::Io(err)),
+        };
+        Ok(entries
+            .filter_map(Result::ok)
+            .map(|entry| entry.path())
+            .filter(|path| path.extension().and_then(|ext| ext.to_str()) == Some("download"))
+            .filter_map(|path| {
+                let metadata = path.metadata().ok().filter(|metadata| metadata.is_file())?;
+                Some((path, metadata))
+            })
+            .collect())
+    }
+
     pub fn write_model_bytes(&self, id: &ModelId, bytes: &[u8]) -> Result<PathBuf, ModelError> {
         let path = self.model_path(id)?;
         if let Some(parent) = path.parent() {
@@ -224,6 +1381,18 @@ fn validate_model_filename(filename: &str) -> Result<(), ModelError> {
     Ok(())
 }
 
+fn has_model_magic(path: &Path) -> bool {
+    let mut magic = [0u8; 4];
+    File::open(path)
+        .and_then(|mut file| file.read_exact(&mut magic))
+        .is_ok_and(|()| magic == GGML_MAGIC || magic == GGUF_MAGIC)
+}
+
+fn file_stamp(path: &Path) -> Option<FileStamp> {
+    let metadata = path.metadata().ok()?;
+    Some((metadata.len(), metadata.modified().ok()?))
+}
+
 fn verify_model_file(path: &Path, spec: &ModelSpec) -> Result<(), ModelError> {
     if !path.exists() {
         return Err(ModelError::MissingFile(path.display().to_string()));
@@ -247,25 +1416,6 @@ fn verify_model_file(path: &Path, spec: &ModelSpec) -> Result<(), ModelError> {
     Ok(())
 }
 
-fn verify_model_bytes(spec: &ModelSpec, bytes: &[u8]) -> Result<(), ModelError> {
-    if let Some(expected) = spec.size_bytes {
-        let actual = bytes.len() as u64;
-        if actual != expected {
-            return Err(ModelError::SizeMismatch { expected, actual });
-        }
-    }
-    if let Some(expected) = spec.sha256.as_ref() {
-        let actual = sha256_hex(bytes);
-        if !expected.eq_ignore_ascii_case(&actual) {
-            return Err(ModelError::ChecksumMismatch {
-                expected: expected.clone(),
-                actual,
-            });
-        }
-    }
-    Ok(())
-}
-
 fn replace_cached_model(tmp_path: &Path, path: &Path) -> Result<(), ModelError> {
     match std::fs::rename(tmp_path, path) {
         Ok(()) => Ok(()),
@@ -281,6 +1431,7 @@ fn replace_cached_model(tmp_path: &Path, path: &Path) -> Result<(), ModelError>
     }
 }
 
+#[cfg(test)]
 fn sha256_hex(bytes: &[u8]) -> String {
     let mut hasher = Sha256::new();
     hasher.update(bytes);
@@ -304,27 +1455,147 @@ fn sha256_hex_from_file(path: &Path) -> Result<String, ModelError> {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use std::cell::Cell;
+    use std::cell::{Cell, RefCell};
 
     struct MockDownloader {
         bytes: Vec<u8>,
         calls: Cell<usize>,
     }
 
-    impl MockDownloader {
-        fn new(bytes: Vec<u8>) -> Self {
-            Self {
-                bytes,
-                calls: Cell::new(0),
-            }
-        }
+    impl MockDownloader {
+        fn new(bytes: Vec<u8>) -> Self {
+            Self {
+                bytes,
+                calls: Cell::new(0),
+            }
+        }
+    }
+
+    impl ModelDownloader for MockDownloader {
+        fn download(&self, _url: &str) -> Result<Vec<u8>, ModelError> {
+            self.calls.set(self.calls.get() + 1);
+            Ok(self.bytes.clone())
+        }
+    }
+
+    #[test]
+    fn quantized_model_names_round_trip() {
+        for id in ModelId::quantized_variants() {
+            assert_eq!(ModelId::from_name(&id.display_name()), id);
+        }
+        assert_eq!(
+            ModelId::from_name("Base.en-Q5_1"),
+            ModelId::Quantized {
+                base: StandardModel::Base,
+                english: true,
+                quant: Quantization::Q5_1,
+            }
+        );
+        assert_eq!(ModelId::from_name("medium"), ModelId::Medium);
+        assert_eq!(
+            ModelId::from_name("large.en-q5_0"),
+            ModelId::Custom("large.en-q5_0".to_string())
+        );
+        assert_eq!(
+            ModelId::from_name("large-q5_0"),
+            ModelId::Custom("large-q5_0".to_string())
+        );
+        assert_eq!(
+            ModelId::from_name("base-q3_k"),
+            ModelId::Custom("base-q3_k".to_string())
+        );
+        assert_eq!(
+            ModelId::from_name("base-q5_0"),
+            ModelId::Custom("base-q5_0".to_string())
+        );
+        assert_eq!(
+            ModelId::from_name("medium.en-q5_1"),
+            ModelId::Custom("medium.en-q5_1".to_string())
+        );
+    }
+
+    #[test]
+    fn quantized_models_use_ggml_filenames() {
+        let filenames = StandardModel::Medium
+            .quantizations()
+            .iter()
+            .map(|&quant| {
+                ModelId::Quantized {
+                    base: StandardModel::Medium,
+                    english: false,
+                    quant,
+                }
+                .ggml_filename()
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            filenames,
+            ["ggml-medium-q5_0.bin", "ggml-medium-q8_0.bin"].map(|name| Some(name.to_string()))
+        );
+        assert_eq!(
+            ModelId::Tiny.ggml_filename().as_deref(),
+            Some("ggml-tiny.bin")
+        );
+        assert_eq!(ModelId::Custom("mine".to_string()).ggml_filename(), None);
+        assert_eq!(ModelId::quantized_variants().len(), 16);
+    }
+
+    #[test]
+    fn manifest_registers_models_and_last_duplicate_wins() {
+        let dir = tempfile::tempdir().expect("create tempdir");
+        let manifest = dir.path().join("manifest.json");
+        std::fs::write(
+            &manifest,
+            r#"[
+                {"id": "finetune", "filename": "old.bin", "url": "https://models.invalid/old.bin"},
+                {"id": "finetune", "filename": "finetune.bin", "url": "https://models.invalid/finetune.bin",
+                 "sha256": "abc123", "size_bytes": 42},
+                {"id": "base", "filename": "ggml-base-custom.bin"}
+            ]"#,
+        )
+        .expect("write manifest");
+        let mut manager = ModelManager::new(dir.path());
+        manager.register_model(ModelSpec::new(ModelId::Base, "ggml-base.bin"));
+
+        assert_eq!(manager.load_manifest(&manifest).expect("load manifest"), 2);
+
+        let finetune = ModelId::Custom("finetune".to_string());
+        let spec = manager
+            .specs()
+            .find(|spec| spec.id == finetune)
+            .expect("manifest model");
+        assert_eq!(spec.filename, "finetune.bin");
+        assert_eq!(spec.download_urls, ["https://models.invalid/finetune.bin"]);
+        assert_eq!(spec.sha256.as_deref(), Some("abc123"));
+        assert_eq!(spec.size_bytes, Some(42));
+        assert!(manager
+            .model_path(&ModelId::Base)
+            .expect("base path")
+            .ends_with("ggml-base-custom.bin"));
     }
 
-    impl ModelDownloader for MockDownloader {
-        fn download(&self, _url: &str) -> Result<Vec<u8>, ModelError> {
-            self.calls.set(self.calls.get() + 1);
-            Ok(self.bytes.clone())
-        }
+    #[test]
+    fn manifest_rejects_malformed_json_and_unsafe_filenames() {
+        let dir = tempfile::tempdir().expect("create tempdir");
+        let manifest = dir.path().join("manifest.json");
+        let mut manager = ModelManager::new(dir.path());
+
+        std::fs::write(&manifest, r#"[{"id": "broken""#).expect("write manifest");
+        assert!(matches!(
+            manager.load_manifest(&manifest),
+            Err(ModelError::InvalidManifest(_))
+        ));
+
+        std::fs::write(
+            &manifest,
+            r#"[{"id": "ok", "filename": "ok.bin"}, {"id": "evil", "filename": "../evil.bin"}]"#,
+        )
+        .expect("write manifest");
+        assert!(matches!(
+            manager.load_manifest(&manifest),
+            Err(ModelError::InvalidFilename(_))
+        ));
+        assert_eq!(manager.specs().count(), 0);
     }
 
     #[test]
@@ -386,6 +1657,321 @@ mod tests {
         assert_eq!(downloader.calls.get(), 1);
     }
 
+    struct ChunkedDownloader {
+        bytes: Vec<u8>,
+        chunk: usize,
+    }
+
+    impl ModelDownloader for ChunkedDownloader {
+        fn download(&self, url: &str) -> Result<Vec<u8>, ModelError> {
+            self.download_with_progress(url, &mut |_| {})
+        }
+
+        fn download_with_progress(
+            &self,
+            _url: &str,
+            progress: &mut dyn FnMut(DownloadProgress),
+        ) -> Result<Vec<u8>, ModelError> {
+            let reader = ChunkedReader {
+                bytes: &self.bytes,
+                chunk: self.chunk,
+            };
+            Ok(read_with_progress(reader, None, progress)?)
+        }
+    }
+
+    struct ChunkedReader<'a> {
+        bytes: &'a [u8],
+        chunk: usize,
+    }
+
+    impl Read for ChunkedReader<'_> {
+        fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+            let len = self.chunk.min(buf.len()).min(self.bytes.len());
+            buf[..len].copy_from_slice(&self.bytes[..len]);
+            self.bytes = &self.bytes[len..];
+            Ok(len)
+        }
+    }
+
+    #[test]
+    fn model_manager_reports_download_progress() {
+        let dir = tempfile::tempdir().expect("create tempdir");
+        let mut manager = ModelManager::new(dir.path());
+        let bytes = (0..10u8).collect::<Vec<_>>();
+        let spec = ModelSpec::new(ModelId::Custom("progress".to_string()), "progress.bin")
+            .with_download_url("file://mock")
+            .with_size(bytes.len() as u64);
+        manager.register_model(spec);
+
+        let downloader = ChunkedDownloader { bytes, chunk: 4 };
+        let mut updates = Vec::new();
+        manager
+            .ensure_model_cached_with_progress(
+                &ModelId::Custom("progress".to_string()),
+                &downloader,
+                &mut |update| updates.push(update),
+            )
+            .expect("download model");
+
+        let downloaded = updates
+            .iter()
+            .map(|update| update.downloaded_bytes)
+            .collect::<Vec<_>>();
+        assert_eq!(downloaded, vec![4, 8, 10]);
+        assert!(updates.iter().all(|update| update.total_bytes == Some(10)));
+    }
+
+    #[test]
+    fn default_download_progress_reports_completion() {
+        let downloader = MockDownloader::new(b"whole".to_vec());
+        let mut updates = Vec::new();
+        downloader
+            .download_with_progress("file://mock", &mut |update| updates.push(update))
+            .expect("download");
+        assert_eq!(
+            updates,
+            vec![DownloadProgress {
+                downloaded_bytes: 5,
+                total_bytes: Some(5),
+            }]
+        );
+    }
+
+    const NO_RETRY: RetryPolicy = RetryPolicy {
+        attempts: 1,
+        initial_backoff: Duration::ZERO,
+    };
+
+    struct MirrorDownloader {
+        statuses: HashMap<&'static str, u16>,
+        calls: RefCell<Vec<String>>,
+    }
+
+    impl ModelDownloader for MirrorDownloader {
+        fn download(&self, url: &str) -> Result<Vec<u8>, ModelError> {
+            self.calls.borrow_mut().push(url.to_string());
+            match self.statuses.get(url) {
+                Some(&status) => Err(ModelError::HttpStatus {
+                    url: url.to_string(),
+                    status,
+                }),
+                None => Ok(b"mirrored".to_vec()),
+            }
+        }
+    }
+
+    #[test]
+    fn model_manager_falls_back_to_next_mirror() {
+        let dir = tempfile::tempdir().expect("create tempdir");
+        let retry = RetryPolicy {
+            attempts: 3,
+            initial_backoff: Duration::ZERO,
+        };
+        let mut manager = ModelManager::new(dir.path()).with_retry_policy(retry);
+        let id = ModelId::Custom("mirrored".to_string());
+        let spec = ModelSpec::new(id.clone(), "mirrored.bin")
+            .with_download_url("https://primary.invalid/model.bin")
+            .with_download_urls(["https://mirror.invalid/model.bin"]);
+        manager.register_model(spec);
+
+        let downloader = MirrorDownloader {
+            statuses: HashMap::from([("https://primary.invalid/model.bin", 404)]),
+            calls: RefCell::new(Vec::new()),
+        };
+        let path = manager
+            .ensure_model_cached(&id, &downloader)
+            .expect("mirror download");
+
+        assert_eq!(std::fs::read(path).expect("read model"), b"mirrored");
+        assert_eq!(
+            *downloader.calls.borrow(),
+            vec![
+                "https://primary.invalid/model.bin".to_string(),
+                "https://mirror.invalid/model.bin".to_string(),
+            ]
+        );
+    }
+
+    #[test]
+    fn model_manager_aggregates_failed_mirrors() {
+        let dir = tempfile::tempdir().expect("create tempdir");
+        let retry = RetryPolicy {
+            attempts: 2,
+            initial_backoff: Duration::ZERO,
+        };
+        let mut manager = ModelManager::new(dir.path()).with_retry_policy(retry);
+        let id = ModelId::Custom("unavailable".to_string());
+        let spec = ModelSpec::new(id.clone(), "unavailable.bin").with_download_urls([
+            "https://primary.invalid/model.bin",
+            "https://mirror.invalid/model.bin",
+        ]);
+        manager.register_model(spec);
+
+        let downloader = MirrorDownloader {
+            statuses: HashMap::from([
+                ("https://primary.invalid/model.bin", 503),
+                ("https://mirror.invalid/model.bin", 404),
+            ]),
+            calls: RefCell::new(Vec::new()),
+        };
+        let result = manager.ensure_model_cached(&id, &downloader);
+
+        let Err(ModelError::AllMirrorsFailed { attempts }) = result else {
+            panic!("expected aggregated mirror failure");
+        };
+        let urls = attempts
+            .iter()
+            .map(|(url, _)| url.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            urls,
+            vec![
+                "https://primary.invalid/model.bin",
+                "https://primary.invalid/model.bin",
+                "https://mirror.invalid/model.bin",
+            ]
+        );
+        assert!(attempts[0].1.contains("503"));
+        assert!(attempts[2].1.contains("404"));
+    }
+
+    struct SlowDownloader {
+        bytes: Vec<u8>,
+        chunk: usize,
+    }
+
+    impl ModelDownloader for SlowDownloader {
+        fn download(&self, _url: &str) -> Result<Vec<u8>, ModelError> {
+            Ok(self.bytes.clone())
+        }
+
+        fn download_to(
+            &self,
+            url: &str,
+            partial: &mut PartialDownload,
+            progress: &mut dyn FnMut(DownloadProgress),
+        ) -> Result<(), ModelError> {
+            let reader = ChunkedReader {
+                bytes: &self.bytes,
+                chunk: self.chunk,
+            };
+            copy_with_progress(reader, partial, 0, Some(self.bytes.len() as u64), progress)
+                .map_err(|_| ModelError::DownloadFailed(url.to_string()))
+        }
+    }
+
+    struct RangeDownloader {
+        bytes: Vec<u8>,
+        fail_after: Option<usize>,
+        offsets: RefCell<Vec<u64>>,
+    }
+
+    impl ModelDownloader for RangeDownloader {
+        fn download(&self, _url: &str) -> Result<Vec<u8>, ModelError> {
+            Ok(self.bytes.clone())
+        }
+
+        fn download_to(
+            &self,
+            url: &str,
+            partial: &mut PartialDownload,
+            _progress: &mut dyn FnMut(DownloadProgress),
+        ) -> Result<(), ModelError> {
+            let offset = partial.len();
+            self.offsets.borrow_mut().push(offset);
+            let end = self.fail_after.unwrap_or(self.bytes.len());
+            partial.write_all(&self.bytes[offset as usize..end])?;
+            if self.fail_after.is_some() {
+                return Err(ModelError::DownloadFailed(url.to_string()));
+            }
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn model_manager_keeps_partial_download_and_resumes() {
+        let dir = tempfile::tempdir().expect("create tempdir");
+        let mut manager = ModelManager::new(dir.path()).with_retry_policy(NO_RETRY);
+        let id = ModelId::Custom("resume".to_string());
+        let bytes = b"resumable whisper model".to_vec();
+        let spec = ModelSpec::new(id.clone(), "resume.bin")
+            .with_download_url("https://example.invalid/resume.bin")
+            .with_sha256(sha256_hex(&bytes))
+            .with_size(bytes.len() as u64);
+        manager.register_model(spec);
+        assert_eq!(manager.partial_download_size(&id).expect("size"), None);
+
+        let interrupted = RangeDownloader {
+            bytes: bytes.clone(),
+            fail_after: Some(10),
+            offsets: RefCell::new(Vec::new()),
+        };
+        let result = manager.ensure_model_cached(&id, &interrupted);
+        assert!(
+            matches!(result, Err(ModelError::AllMirrorsFailed { ref attempts }) if attempts.len() == 1)
+        );
+        assert_eq!(manager.partial_download_size(&id).expect("size"), Some(10));
+
+        let resumed = RangeDownloader {
+            bytes: bytes.clone(),
+            fail_after: None,
+            offsets: RefCell::new(Vec::new()),
+        };
+        let path = manager
+            .ensure_model_cached(&id, &resumed)
+            .expect("resume download");
+        assert_eq!(*resumed.offsets.borrow(), vec![10]);
+        assert_eq!(std::fs::read(path).expect("read model"), bytes);
+        assert_eq!(manager.partial_download_size(&id).expect("size"), None);
+    }
+
+    #[test]
+    fn cancelled_download_stops_streaming_and_keeps_partial() {
+        let dir = tempfile::tempdir().expect("create tempdir");
+        let cancel = Arc::new(AtomicBool::new(false));
+        let mut manager = ModelManager::new(dir.path()).with_cancellation(Arc::clone(&cancel));
+        let id = ModelId::Custom("cancel".to_string());
+        let bytes = (0..40u8).collect::<Vec<_>>();
+        let spec = ModelSpec::new(id.clone(), "cancel.bin")
+            .with_download_url("https://example.invalid/cancel.bin")
+            .with_size(bytes.len() as u64);
+        manager.register_model(spec);
+
+        let downloader = SlowDownloader { bytes, chunk: 4 };
+        let mut callbacks = 0;
+        let result = manager.ensure_model_cached_with_progress(&id, &downloader, &mut |_| {
+            callbacks += 1;
+            if callbacks == 2 {
+                cancel.store(true, Ordering::SeqCst);
+            }
+        });
+
+        assert!(matches!(result, Err(ModelError::Cancelled)));
+        assert_eq!(callbacks, 2);
+        assert_eq!(manager.partial_download_size(&id).expect("size"), Some(8));
+        assert!(!dir.path().join("cancel.bin").exists());
+    }
+
+    #[test]
+    fn model_manager_restarts_when_downloader_cannot_resume() {
+        let dir = tempfile::tempdir().expect("create tempdir");
+        let mut manager = ModelManager::new(dir.path());
+        let id = ModelId::Custom("restart".to_string());
+        let bytes = b"fresh whisper".to_vec();
+        let spec = ModelSpec::new(id.clone(), "restart.bin")
+            .with_download_url("file://mock")
+            .with_size(bytes.len() as u64);
+        manager.register_model(spec);
+        std::fs::write(dir.path().join("restart.download"), b"stale").expect("write partial");
+
+        let downloader = MockDownloader::new(bytes.clone());
+        let path = manager
+            .ensure_model_cached(&id, &downloader)
+            .expect("fresh download");
+        assert_eq!(std::fs::read(path).expect("read model"), bytes);
+    }
+
     #[test]
     fn model_manager_requires_download_url() {
         let dir = tempfile::tempdir().expect("create tempdir");
@@ -450,6 +2036,208 @@ mod tests {
         assert!(matches!(result, Err(ModelError::ChecksumMismatch { .. })));
     }
 
+    #[test]
+    fn model_manager_deletes_model_and_partial_download() {
+        let dir = tempfile::tempdir().expect("create tempdir");
+        let mut manager = ModelManager::new(dir.path());
+        let id = ModelId::Custom("delete".to_string());
+        manager.register_model(ModelSpec::new(id.clone(), "delete.bin"));
+        let path = manager
+            .write_model_bytes(&id, b"model")
+            .expect("write model");
+        std::fs::write(dir.path().join("delete.download"), b"part").expect("write partial");
+
+        assert_eq!(manager.delete_model(&id).expect("delete"), 9);
+        assert!(!path.exists());
+        assert_eq!(manager.partial_download_size(&id).expect("size"), None);
+        assert_eq!(manager.delete_model(&id).expect("delete again"), 0);
+    }
+
+    #[test]
+    fn model_manager_refuses_to_delete_outside_root() {
+        let dir = tempfile::tempdir().expect("create tempdir");
+        let mut manager = ModelManager::new(dir.path().join("models"));
+        let id = ModelId::Custom("escape".to_string());
+        manager.register_model(ModelSpec::new(id.clone(), "../escape.bin"));
+        let outside = dir.path().join("escape.bin");
+        std::fs::write(&outside, b"keep").expect("write outside file");
+
+        let result = manager.delete_model(&id);
+        assert!(matches!(result, Err(ModelError::InvalidFilename(_))));
+        assert!(outside.exists());
+    }
+
+    #[test]
+    fn verify_reports_size_mismatch_and_cache_redownloads() {
+        let dir = tempfile::tempdir().expect("create tempdir");
+        let mut manager = ModelManager::new(dir.path());
+        let id = ModelId::Custom("truncated".to_string());
+        let bytes = b"complete whisper model".to_vec();
+        let spec = ModelSpec::new(id.clone(), "truncated.bin")
+            .with_download_url("file://mock")
+            .with_sha256(sha256_hex(&bytes))
+            .with_size(bytes.len() as u64);
+        manager.register_model(spec);
+        manager
+            .write_model_bytes(&id, &bytes[..8])
+            .expect("write truncated model");
+
+        let report = manager.verify(&id).expect("verify");
+        assert!(!report.size_matches());
+        assert!(!report.checksum_matches());
+        assert!(!report.is_valid());
+
+        let downloader = MockDownloader::new(bytes.clone());
+        manager
+            .ensure_model_cached(&id, &downloader)
+            .expect("redownload model");
+        assert_eq!(downloader.calls.get(), 1);
+        assert!(manager.verify(&id).expect("verify again").is_valid());
+    }
+
+    #[test]
+    fn standard_models_have_known_checksums() {
+        for name in ["tiny", "base", "small", "medium"] {
+            let (size_bytes, sha256) =
+                standard_model_checksum(&format!("ggml-{name}.bin")).expect("known model");
+            assert!(size_bytes > 0);
+            assert_eq!(sha256.len(), 64);
+            assert!(sha256.chars().all(|ch| ch.is_ascii_hexdigit()));
+        }
+        assert!(standard_model_checksum("custom.bin").is_none());
+    }
+
+    struct CountingDownloader {
+        bytes: Vec<u8>,
+        calls: std::sync::atomic::AtomicUsize,
+    }
+
+    impl ModelDownloader for CountingDownloader {
+        fn download(&self, _url: &str) -> Result<Vec<u8>, ModelError> {
+            self.calls.fetch_add(1, Ordering::SeqCst);
+            std::thread::sleep(Duration::from_millis(100));
+            Ok(self.bytes.clone())
+        }
+    }
+
+    #[test]
+    fn concurrent_downloads_of_one_model_run_once() {
+        let dir = tempfile::tempdir().expect("create tempdir");
+        let id = ModelId::Custom("shared".to_string());
+        let bytes = b"shared whisper".to_vec();
+        let manager = || {
+            let mut manager = ModelManager::new(dir.path());
+            manager.register_model(
+                ModelSpec::new(id.clone(), "shared.bin")
+                    .with_download_url("file://mock")
+                    .with_size(bytes.len() as u64),
+            );
+            manager
+        };
+        let downloader = CountingDownloader {
+            bytes: bytes.clone(),
+            calls: std::sync::atomic::AtomicUsize::new(0),
+        };
+        let barrier = std::sync::Barrier::new(2);
+
+        let results = std::thread::scope(|scope| {
+            let handles = (0..2)
+                .map(|_| {
+                    scope.spawn(|| {
+                        let manager = manager();
+                        barrier.wait();
+                        manager.ensure_model_cached(&id, &downloader)
+                    })
+                })
+                .collect::<Vec<_>>();
+            handles
+                .into_iter()
+                .map(|handle| handle.join().expect("download thread"))
+                .collect::<Vec<_>>()
+        });
+
+        assert_eq!(downloader.calls.load(Ordering::SeqCst), 1);
+        assert!(results
+            .iter()
+            .all(|result| matches!(result, Ok(_) | Err(ModelError::DownloadInProgress(_)))));
+        assert!(results.iter().any(Result::is_ok));
+        assert_eq!(
+            std::fs::read(dir.path().join("shared.bin")).expect("read model"),
+            bytes
+        );
+    }
+
+    struct FixedFreeSpace(u64);
+
+    impl FreeSpace for FixedFreeSpace {
+        fn available_space(&self, _path: &Path) -> std::io::Result<u64> {
+            Ok(self.0)
+        }
+    }
+
+    #[test]
+    fn model_manager_checks_disk_space_before_download() {
+        let dir = tempfile::tempdir().expect("create tempdir");
+        let mut manager = ModelManager::new(dir.path()).with_free_space(FixedFreeSpace(1024));
+        let sized = ModelId::Custom("sized".to_string());
+        let unsized_id = ModelId::Custom("unsized".to_string());
+        manager.register_model(
+            ModelSpec::new(sized.clone(), "sized.bin")
+                .with_download_url("file://mock")
+                .with_size(4),
+        );
+        manager.register_model(
+            ModelSpec::new(unsized_id.clone(), "unsized.bin").with_download_url("file://mock"),
+        );
+
+        let downloader = MockDownloader::new(vec![1, 2, 3, 4]);
+        let result = manager.ensure_model_cached(&sized, &downloader);
+        assert!(matches!(
+            result,
+            Err(ModelError::InsufficientDiskSpace {
+                required,
+                available: 1024,
+            }) if required == 4 + DISK_SPACE_MARGIN
+        ));
+        assert_eq!(downloader.calls.get(), 0);
+
+        manager
+            .ensure_model_cached(&unsized_id, &downloader)
+            .expect("unknown size skips the check");
+        assert_eq!(downloader.calls.get(), 1);
+    }
+
+    #[test]
+    fn scan_lists_model_files_with_ggml_magic() {
+        let dir = tempfile::tempdir().expect("create tempdir");
+        let mut manager = ModelManager::new(dir.path());
+        manager.register_model(ModelSpec::new(ModelId::Base, "ggml-base.bin"));
+        std::fs::write(dir.path().join("ggml-base.bin"), b"lmggbase").expect("write base");
+        std::fs::write(dir.path().join("finetune.bin"), b"lmggtuned").expect("write custom");
+        std::fs::write(dir.path().join("garbage.bin"), b"not a model").expect("write junk");
+        std::fs::write(dir.path().join("notes.txt"), b"lmgg").expect("write notes");
+
+        let installed = manager.scan_installed();
+
+        let summary = installed
+            .iter()
+            .map(|model| {
+                (
+                    model.filename.as_str(),
+                    model.size_bytes,
+                    model.registered.clone(),
+                )
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            summary,
+            vec![
+                ("finetune.bin", 9, None),
+                ("ggml-base.bin", 8, Some(ModelId::Base)),
+            ]
+        );
+    }
+
     #[test]
     fn model_manager_rejects_path_traversal_filenames() {
         let dir = tempfile::tempdir().expect("create tempdir");
@@ -471,4 +2259,274 @@ mod tests {
         let result = manager.model_path(&ModelId::Custom("abs".to_string()));
         assert!(matches!(result, Err(ModelError::InvalidFilename(_))));
     }
+
+    fn serve_once(
+        response: &'static [u8],
+        delay: Duration,
+    ) -> (String, std::thread::JoinHandle<String>) {
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").expect("bind listener");
+        let url = format!("http://{}/model.bin", listener.local_addr().expect("addr"));
+        let handle = std::thread::spawn(move || {
+            let (mut stream, _) = listener.accept().expect("accept connection");
+            let mut request = [0u8; 1024];
+            let read = stream.read(&mut request).unwrap_or(0);
+            std::thread::sleep(delay);
+            let _ = stream.write_all(response);
+            String::from_utf8_lossy(&request[..read]).into_owned()
+        });
+        (url, handle)
+    }
+
+    #[test]
+    fn http_downloader_sends_user_agent() {
+        let (url, server) = serve_once(
+            b"HTTP/1.1 200 OK\r\nContent-Length: 5\r\nConnection: close\r\n\r\nhello",
+            Duration::ZERO,
+        );
+        let downloader = HttpDownloader::new().with_user_agent("whisper-test/1.0");
+
+        let bytes = downloader.download(&url).expect("download");
+        let request = server.join().expect("server thread");
+
+        assert_eq!(bytes, b"hello");
+        assert!(request
+            .to_ascii_lowercase()
+            .contains("user-agent: whisper-test/1.0"));
+    }
+
+    #[test]
+    fn http_downloader_read_timeout_mentions_limit() {
+        let (url, server) = serve_once(b"", Duration::from_millis(500));
+        let downloader = HttpDownloader::new().with_read_timeout(Duration::from_millis(100));
+
+        let result = downloader.download(&url);
+        server.join().expect("server thread");
+
+        match result {
+            Err(ModelError::DownloadFailed(message)) => {
+                assert!(message.contains("no data received for 0.1s"), "{message}")
+            }
+            other => panic!("expected timeout, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn http_downloader_reports_unreachable_proxy() {
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").expect("bind listener");
+        let proxy = format!("http://{}", listener.local_addr().expect("addr"));
+        drop(listener);
+        let downloader = HttpDownloader::new().with_proxy(Some(proxy.clone()));
+
+        let result = downloader.download("http://models.invalid/ggml-tiny.bin");
+
+        match result {
+            Err(ModelError::DownloadFailed(message)) => {
+                assert!(
+                    message.contains(&format!("proxy {proxy} failed")),
+                    "{message}"
+                )
+            }
+            other => panic!("expected proxy failure, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn proxy_errors_redact_credentials() {
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").expect("bind listener");
+        let addr = listener.local_addr().expect("addr");
+        drop(listener);
+        let downloader =
+            HttpDownloader::new().with_proxy(Some(format!("http://user:hunter2@{addr}")));
+
+        let result = downloader.download("http://models.invalid/ggml-tiny.bin");
+
+        match result {
+            Err(ModelError::DownloadFailed(message)) => {
+                assert!(
+                    message.contains(&format!("proxy http://***@{addr} failed")),
+                    "{message}"
+                );
+                assert!(!message.contains("hunter2"), "{message}");
+            }
+            other => panic!("expected proxy failure, got {other:?}"),
+        }
+        assert_eq!(
+            redact_userinfo("user:pass@proxy.lan:3128/path"),
+            "***@proxy.lan:3128/path"
+        );
+        assert_eq!(redact_userinfo("http://proxy.lan"), "http://proxy.lan");
+    }
+
+    #[test]
+    fn no_proxy_hosts_bypass_the_proxy() {
+        let downloader = HttpDownloader::new()
+            .with_proxy(Some("http://proxy.lan:3128".to_string()))
+            .with_no_proxy(Some("localhost, .internal.example,10.0.0.1".to_string()));
+
+        assert_eq!(
+            downloader.proxy_for("http://localhost:8080/model.bin"),
+            None
+        );
+        assert_eq!(
+            downloader.proxy_for("https://mirror.internal.example/model.bin"),
+            None
+        );
+        assert_eq!(
+            downloader.proxy_for("https://internal.example/model.bin"),
+            None
+        );
+        assert_eq!(downloader.proxy_for("http://user@10.0.0.1/model.bin"), None);
+        assert_eq!(
+            downloader.proxy_for("https://notinternal.example/model.bin"),
+            Some("http://proxy.lan:3128")
+        );
+        assert_eq!(
+            downloader.proxy_for("https://huggingface.co/model.bin"),
+            Some("http://proxy.lan:3128")
+        );
+
+        let everything = HttpDownloader::new()
+            .with_proxy(Some("http://proxy.lan:3128".to_string()))
+            .with_no_proxy(Some("*".to_string()));
+        assert_eq!(
+            everything.proxy_for("https://huggingface.co/model.bin"),
+            None
+        );
+    }
+
+    #[test]
+    fn proxy_from_env_prefers_https_and_skips_blank_values() {
+        let vars = HashMap::from([
+            ("HTTPS_PROXY", " "),
+            ("https_proxy", "http://secure.proxy:8443"),
+            ("HTTP_PROXY", "http://plain.proxy:8080"),
+        ]);
+        let lookup = |name: &str| vars.get(name).map(|value| value.to_string());
+
+        assert_eq!(
+            proxy_from_env(lookup).as_deref(),
+            Some("http://secure.proxy:8443")
+        );
+        assert_eq!(proxy_from_env(|_| None), None);
+    }
+
+    #[test]
+    fn cleanup_removes_only_stale_temp_files() {
+        let dir = tempfile::tempdir().expect("create tempdir");
+        let stale = dir.path().join("ggml-base.download");
+        let fresh = dir.path().join("ggml-tiny.download");
+        std::fs::write(&stale, vec![0u8; 12]).expect("write stale partial");
+        std::fs::write(&fresh, vec![0u8; 5]).expect("write fresh partial");
+        std::fs::write(dir.path().join("ggml-base.bin"), b"lmggdata").expect("write model");
+        File::options()
+            .write(true)
+            .open(&stale)
+            .and_then(|file| {
+                file.set_modified(SystemTime::now() - Duration::from_secs(2 * 60 * 60))
+            })
+            .expect("age stale partial");
+        let manager = ModelManager::new(dir.path());
+
+        let reclaimed = manager.cleanup_temp_files().expect("cleanup");
+
+        assert_eq!(reclaimed, 12);
+        assert!(!stale.exists());
+        assert!(fresh.exists());
+        assert!(dir.path().join("ggml-base.bin").exists());
+    }
+
+    #[test]
+    fn disk_usage_aggregates_models_and_temp_files() {
+        let dir = tempfile::tempdir().expect("create tempdir");
+        let mut manager = ModelManager::new(dir.path());
+        manager.register_model(ModelSpec::new(ModelId::Base, "ggml-base.bin"));
+        manager.register_model(ModelSpec::new(ModelId::Tiny, "ggml-tiny.bin"));
+        std::fs::write(dir.path().join("ggml-base.bin"), vec![0u8; 100]).expect("write base");
+        std::fs::write(dir.path().join("finetune.bin"), b"GGUFdata").expect("write unmanaged");
+        std::fs::write(dir.path().join("ggml-tiny.download"), vec![0u8; 7]).expect("write partial");
+
+        let usage = manager.disk_usage().expect("disk usage");
+
+        assert_eq!(
+            usage.per_model,
+            vec![
+                (ModelId::Base, 100),
+                (ModelId::Custom("finetune".to_string()), 8),
+            ]
+        );
+        assert_eq!(usage.temp_bytes, 7);
+        assert_eq!(usage.total, 115);
+    }
+
+    #[test]
+    fn disk_usage_of_missing_root_is_empty() {
+        let dir = tempfile::tempdir().expect("create tempdir");
+        let manager = ModelManager::new(dir.path().join("missing"));
+
+        assert_eq!(
+            manager.disk_usage().expect("disk usage"),
+            ModelDiskUsage::default()
+        );
+        assert_eq!(manager.cleanup_temp_files().expect("cleanup"), 0);
+    }
+
+    #[test]
+    fn import_model_links_file_and_registers_spec() {
+        let source_dir = tempfile::tempdir().expect("create source dir");
+        let source = source_dir.path().join("my-finetune.bin");
+        std::fs::write(&source, b"lmggweights").expect("write source");
+        let dir = tempfile::tempdir().expect("create tempdir");
+        let mut manager = ModelManager::new(dir.path().join("models"));
+        let id = ModelId::Custom("finetune-de".to_string());
+
+        let path = manager.import_model(&source, id.clone()).expect("import");
+
+        assert_eq!(path, dir.path().join("models").join("finetune-de.bin"));
+        assert_eq!(std::fs::read(&path).expect("read import"), b"lmggweights");
+        assert_eq!(manager.model_path(&id).expect("registered"), path);
+        assert_eq!(
+            manager
+                .scan_installed()
+                .into_iter()
+                .map(|installed| (installed.filename, installed.registered))
+                .collect::<Vec<_>>(),
+            vec![("finetune-de.bin".to_string(), Some(id))]
+        );
+    }
+
+    #[test]
+    fn import_model_rejects_files_without_ggml_magic() {
+        let dir = tempfile::tempdir().expect("create tempdir");
+        let source = dir.path().join("notes.txt");
+        std::fs::write(&source, b"hello world").expect("write source");
+        let mut manager = ModelManager::new(dir.path().join("models"));
+
+        let result = manager.import_model(&source, ModelId::Custom("notes".to_string()));
+
+        assert!(matches!(result, Err(ModelError::NotAModel(_))));
+        assert!(!dir.path().join("models").join("notes.bin").exists());
+    }
+
+    #[test]
+    fn import_model_rejects_name_collisions_and_unsafe_names() {
+        let dir = tempfile::tempdir().expect("create tempdir");
+        let source = dir.path().join("source.bin");
+        std::fs::write(&source, b"GGUFweights").expect("write source");
+        std::fs::write(dir.path().join("taken.bin"), b"GGUFother").expect("write existing");
+        std::fs::write(dir.path().join("ggml-base.bin"), b"lmggbase").expect("write base");
+        let mut manager = ModelManager::new(dir.path());
+        manager.register_model(ModelSpec::new(ModelId::Base, "ggml-base.bin"));
+
+        let taken = manager.import_model(&source, ModelId::Custom("taken".to_string()));
+        let base = manager.import_model(&source, ModelId::Base);
+        let unsafe_name = manager.import_model(&source, ModelId::Custom("../escape".to_string()));
+
+        assert!(matches!(taken, Err(ModelError::ModelExists(name)) if name == "taken"));
+        assert!(matches!(base, Err(ModelError::ModelExists(name)) if name == "base"));
+        assert!(matches!(unsafe_name, Err(ModelError::InvalidFilename(_))));
+        assert_eq!(
+            std::fs::read(dir.path().join("taken.bin")).expect("read"),
+            b"GGUFother"
+        );
+    }
 }
@@ -0,0 +1,221 @@
+use crate::bindings::{sanitize_prompt, BindingError, TranscribeOptions, WhisperBindings};
+use crate::engine::{Segment, TranscriptionResult};
+use log::warn;
+use std::path::Path;
+use whisper_rs::{FullParams, SamplingStrategy, WhisperContext, WhisperContextParameters};
+
+pub struct WhisperNativeBindings;
+
+pub struct WhisperNativeContext {
+    context: WhisperContext,
+}
+
+impl WhisperBindings for WhisperNativeBindings {
+    type Context = WhisperNativeContext;
+
+    fn init_from_file(path: &Path) -> Result<Self::Context, BindingError> {
+        let path = path.to_str().ok_or_else(|| BindingError::ModelLoadFailed {
+            stderr: format!("model path is not valid UTF-8: {}", path.display()),
+        })?;
+        let context = WhisperContext::new_with_params(path, WhisperContextParameters::default())
+            .map_err(|err| {
+                warn!("whisper-rs failed to load {path}: {err}");
+                BindingError::ModelLoadFailed {
+                    stderr: err.to_string(),
+                }
+            })?;
+        Ok(WhisperNativeContext { context })
+    }
+
+    fn transcribe(context: &Self::Context, audio: &[f32]) -> Result<String, BindingError> {
+        Self::transcribe_with_language(context, audio, None)
+    }
+
+    fn transcribe_with_language(
+        context: &Self::Context,
+        audio: &[f32],
+        language: Option<&str>,
+    ) -> Result<String, BindingError> {
+        Self::transcribe_segments(context, audio, language).map(|result| result.text)
+    }
+
+    fn transcribe_segments(
+        context: &Self::Context,
+        audio: &[f32],
+        language: Option<&str>,
+    ) -> Result<TranscriptionResult, BindingError> {
+        let options = TranscribeOptions::default().with_language(language.map(str::to_string));
+        Self::transcribe_with_options(context, audio, &options)
+    }
+
+    fn transcribe_with_options(
+        context: &Self::Context,
+        audio: &[f32],
+        options: &TranscribeOptions,
+    ) -> Result<TranscriptionResult, BindingError> {
+        let mut state = context
+            .context
+            .create_state()
+            .map_err(|err| BindingError::Transcribe(err.to_string()))?;
+        let language = native_language(options.language.as_deref());
+        let mut params = FullParams::new(SamplingStrategy::Greedy { best_of: 1 });
+        params.set_language(language);
+        params.set_translate(options.translate);
+        params.set_print_special(false);
+        params.set_print_progress(false);
+        params.set_print_realtime(false);
+        params.set_print_timestamps(false);
+        if options.partial {
+            params.set_no_context(true);
+        } else if let Some(prompt) = options.initial_prompt.as_deref().and_then(sanitize_prompt) {
+            params.set_initial_prompt(&prompt);
+        }
+        if let Some(threads) = options.threads.filter(|threads| *threads > 0) {
+            params.set_n_threads(i32::from(threads));
+        }
+        if !options.extra_args.is_empty() {
+            warn!("whisper extra arguments are ignored by the native backend");
+        }
+        state
+            .full(params, audio)
+            .map_err(|err| BindingError::Transcribe(err.to_string()))?;
+        let count = state
+            .full_n_segments()
+            .map_err(|err| BindingError::Transcribe(err.to_string()))?;
+        let mut segments = Vec::new();
+        for index in 0..count {
+            let text = state
+                .full_get_segment_text_lossy(index)
+                .map_err(|err| BindingError::Transcribe(err.to_string()))?;
+            let start = state
+                .full_get_segment_t0(index)
+                .map_err(|err| BindingError::Transcribe(err.to_string()))?;
+            let end = state
+                .full_get_segment_t1(index)
+                .map_err(|err| BindingError::Transcribe(err.to_string()))?;
+            segments.push(Segment {
+                start_ms: native_timestamp_ms(start),
+                end_ms: native_timestamp_ms(end),
+                text: text.trim().to_string(),
+            });
+        }
+        Ok(TranscriptionResult::from_segments(segments))
+    }
+
+    fn detect_language(
+        context: &Self::Context,
+        audio: &[f32],
+    ) -> Result<(String, f32), BindingError> {
+        let mut state = context
+            .context
+            .create_state()
+            .map_err(|err| BindingError::Transcribe(err.to_string()))?;
+        let threads = std::thread::available_parallelism()
+            .map(|threads| threads.get().min(4))
+            .unwrap_or(1);
+        state
+            .pcm_to_mel(audio, threads)
+            .map_err(|err| BindingError::Transcribe(err.to_string()))?;
+        let (id, probabilities) = state
+            .lang_detect(0, threads)
+            .map_err(|err| BindingError::Transcribe(err.to_string()))?;
+        let language = whisper_rs::get_lang_str(id)
+            .ok_or_else(|| BindingError::Transcribe(format!("unknown language id {id}")))?;
+        let probability = usize::try_from(id)
+            .ok()
+            .and_then(|index| probabilities.get(index).copied())
+            .unwrap_or(0.0);
+        Ok((language.to_string(), probability))
+    }
+}
+
+fn native_language(language: Option<&str>) -> Option<&str> {
+    match language.map(str::trim) {
+        Some("") | Some("auto") | None => None,
+        other => other,
+    }
+}
+
+fn native_timestamp_ms(value: i64) -> u64 {
+    u64::try_from(value).unwrap_or(0) * 10
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::env;
+    use std::path::PathBuf;
+
+    fn fixture(var: &str) -> PathBuf {
+        let path = env::var_os(var)
+            .map(PathBuf::from)
+            .unwrap_or_else(|| panic!("{var} is not set"));
+        assert!(path.exists(), "{var} points at a missing file");
+        path
+    }
+
+    fn read_clip(path: &Path) -> Vec<f32> {
+        let mut reader = hound::WavReader::open(path).expect("open clip");
+        reader
+            .samples::<i16>()
+            .map(|sample| f32::from(sample.expect("sample")) / f32::from(i16::MAX))
+            .collect()
+    }
+
+    fn normalize(text: &str) -> String {
+        text.chars()
+            .filter(|ch| ch.is_alphanumeric() || ch.is_whitespace())
+            .collect::<String>()
+            .to_lowercase()
+            .split_whitespace()
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+
+    #[test]
+    fn native_language_treats_auto_as_detect() {
+        assert_eq!(native_language(None), None);
+        assert_eq!(native_language(Some("auto")), None);
+        assert_eq!(native_language(Some(" ")), None);
+        assert_eq!(native_language(Some("fr")), Some("fr"));
+    }
+
+    #[test]
+    fn native_timestamps_are_converted_to_ms() {
+        assert_eq!(native_timestamp_ms(0), 0);
+        assert_eq!(native_timestamp_ms(152), 1520);
+        assert_eq!(native_timestamp_ms(-1), 0);
+    }
+
+    #[test]
+    #[ignore = "needs WHISPER_NATIVE_TEST_MODEL and WHISPER_NATIVE_TEST_CLIP"]
+    fn native_matches_cli_on_fixture_clip() {
+        let model = fixture("WHISPER_NATIVE_TEST_MODEL");
+        let clip = fixture("WHISPER_NATIVE_TEST_CLIP");
+        let audio = read_clip(&clip);
+        let context = WhisperNativeBindings::init_from_file(&model).expect("load model");
+        let native =
+            WhisperNativeBindings::transcribe_segments(&context, &audio, Some("en")).unwrap();
+        assert!(!native.segments.is_empty());
+
+        let cli_context = crate::WhisperCppBindings::init_from_file(&model).unwrap();
+        match crate::WhisperCppBindings::transcribe_segments(&cli_context, &audio, Some("en")) {
+            Ok(cli) => assert_eq!(normalize(&native.text), normalize(&cli.text)),
+            Err(BindingError::Unavailable) => {}
+            Err(err) => panic!("cli transcription failed: {err}"),
+        }
+    }
+
+    #[test]
+    #[ignore = "needs WHISPER_NATIVE_TEST_MODEL and WHISPER_NATIVE_TEST_CLIP"]
+    fn native_context_is_reused_across_transcriptions() {
+        let model = fixture("WHISPER_NATIVE_TEST_MODEL");
+        let clip = fixture("WHISPER_NATIVE_TEST_CLIP");
+        let audio = read_clip(&clip);
+        let context = WhisperNativeBindings::init_from_file(&model).expect("load model");
+        let first = WhisperNativeBindings::transcribe(&context, &audio).unwrap();
+        let second = WhisperNativeBindings::transcribe(&context, &audio).unwrap();
+        assert!(!first.is_empty());
+        assert_eq!(first, second);
+    }
+}
@@ -0,0 +1,123 @@
+use crate::engine::{Segment, TranscriptionResult};
+
+impl TranscriptionResult {
+    pub fn to_srt(&self) -> String {
+        subtitle_cues(&self.segments)
+            .enumerate()
+            .map(|(index, (segment, text))| {
+                format!(
+                    "{}\n{} --> {}\n{}\n",
+                    index + 1,
+                    subtitle_timestamp(segment.start_ms, ','),
+                    subtitle_timestamp(segment.end_ms, ','),
+                    escape_srt(&text)
+                )
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    pub fn to_vtt(&self) -> String {
+        let mut output = String::from("WEBVTT\n");
+        for (segment, text) in subtitle_cues(&self.segments) {
+            output.push_str(&format!(
+                "\n{} --> {}\n{}\n",
+                subtitle_timestamp(segment.start_ms, '.'),
+                subtitle_timestamp(segment.end_ms, '.'),
+                escape_vtt(&text)
+            ));
+        }
+        output
+    }
+}
+
+fn subtitle_cues(segments: &[Segment]) -> impl Iterator<Item = (&Segment, String)> {
+    segments.iter().filter_map(|segment| {
+        let text = segment
+            .text
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty())
+            .collect::<Vec<_>>()
+            .join("\n");
+        (!text.is_empty()).then_some((segment, text))
+    })
+}
+
+fn subtitle_timestamp(ms: u64, separator: char) -> String {
+    let hours = ms / 3_600_000;
+    let minutes = ms / 60_000 % 60;
+    let seconds = ms / 1_000 % 60;
+    let millis = ms % 1_000;
+    format!("{hours:02}:{minutes:02}:{seconds:02}{separator}{millis:03}")
+}
+
+fn escape_srt(text: &str) -> String {
+    let mut text = text.to_string();
+    while text.contains("-->") {
+        text = text.replace("-->", "->");
+    }
+    text
+}
+
+fn escape_vtt(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn segment(start_ms: u64, end_ms: u64, text: &str) -> Segment {
+        Segment {
+            start_ms,
+            end_ms,
+            text: text.to_string(),
+        }
+    }
+
+    fn fixture_result() -> TranscriptionResult {
+        TranscriptionResult::from_segments(vec![
+            segment(0, 2_500, " Hello there."),
+            segment(2_500, 4_000, "   "),
+            segment(3_599_250, 3_601_040, "Crossing the hour --> still going"),
+            segment(36_061_001, 36_065_999, "Tom & Jerry <live>"),
+        ])
+    }
+
+    #[test]
+    fn srt_matches_golden_fixture() {
+        assert_eq!(
+            fixture_result().to_srt(),
+            include_str!("../fixtures/segments.srt")
+        );
+    }
+
+    #[test]
+    fn vtt_matches_golden_fixture() {
+        assert_eq!(
+            fixture_result().to_vtt(),
+            include_str!("../fixtures/segments.vtt")
+        );
+    }
+
+    #[test]
+    fn empty_results_export_without_cues() {
+        let result = TranscriptionResult::default();
+        assert_eq!(result.to_srt(), "");
+        assert_eq!(result.to_vtt(), "WEBVTT\n");
+    }
+
+    #[test]
+    fn srt_text_never_contains_a_cue_arrow() {
+        assert_eq!(escape_srt("a ---> b ----> c"), "a -> b -> c");
+    }
+
+    #[test]
+    fn timestamps_use_format_separator() {
+        assert_eq!(subtitle_timestamp(3_723_004, ','), "01:02:03,004");
+        assert_eq!(subtitle_timestamp(3_723_004, '.'), "01:02:03.004");
+    }
+}
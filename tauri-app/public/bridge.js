@@ -63,6 +63,9 @@
     if (!state) return { status: "idle" };
     if (typeof state === "string") return { status: state };
     if (typeof state === "object") {
+      if ("since_ms" in state && "state" in state) {
+        return { ...normalizeBackendState(state.state), sinceMs: state.since_ms };
+      }
       if (state.status) {
         return { status: String(state.status), message: state.message };
       }
@@ -227,18 +230,27 @@
     updateHeroStats(latestModels, payload);
   };
 
+  const eventPayload = (event) => {
+    const data = event?.payload ?? event;
+    if (data && typeof data === "object" && typeof data.seq === "number" && "payload" in data) {
+      return data.payload;
+    }
+    return data;
+  };
+
   const installBridgeListeners = (listen) => {
     if (!listen) return;
     listen("backend-state", (event) => {
-      updateOverlayState(event?.payload ?? event);
+      updateOverlayState(eventPayload(event));
     });
     listen("backend-log", (event) => {
-      if (event?.payload?.message) {
-        setBackendLog(event.payload.message);
+      const entry = eventPayload(event);
+      if (entry?.message) {
+        setBackendLog(entry.message);
       }
     });
     listen("model-download-status", (event) => {
-      handleModelUpdate(event?.payload ?? event);
+      handleModelUpdate(eventPayload(event));
     });
   };
 
@@ -8,12 +8,16 @@ const hotkeyInput = document.querySelector("#hotkeyInput");
 const applyHotkey = document.querySelector("#applyHotkey");
 const hotkeyPreview = document.querySelector("#hotkeyPreview");
 const outputMode = document.querySelector("#outputMode");
+const restoreClipboard = document.querySelector("#restoreClipboard");
+const pttMode = document.querySelector("#pttMode");
+const consumeHotkey = document.querySelector("#consumeHotkey");
 const themeToggle = document.querySelector("#themeToggle");
 
 let invokeCommand = null;
 let pttState = "idle";
 let latestTranscript = "";
 let lastTranscriptFetch = "";
+let showTimestamps = false;
 
 const defaultHotkey = {
   key: "space",
@@ -26,6 +30,31 @@ function setTranscriptOutput(text) {
   transcriptOutput.textContent = text;
 }
 
+function formatSegmentTime(ms) {
+  const totalSeconds = Math.floor(ms / 1000);
+  const minutes = Math.floor(totalSeconds / 60);
+  const seconds = (totalSeconds % 60).toString().padStart(2, "0");
+  return `${minutes}:${seconds}`;
+}
+
+function formatTranscription(payload) {
+  const segments = Array.isArray(payload.segments) ? payload.segments : [];
+  if (!showTimestamps || segments.length === 0) {
+    return payload.text;
+  }
+  return segments
+    .map((segment) => `[${formatSegmentTime(segment.start_ms)}] ${segment.text}`)
+    .join("\n");
+}
+
+function languageName(code) {
+  try {
+    return new Intl.DisplayNames(["en"], { type: "language" }).of(code) || code;
+  } catch (error) {
+    return code;
+  }
+}
+
 function setStatus(message) {
   if (recordingHint) {
     recordingHint.textContent = message;
@@ -125,7 +154,7 @@ function normalizePttState(state) {
   if (typeof state === "object") {
     const [key] = Object.keys(state);
     if (!key) return { status: "idle" };
-    return { status: key, message: state[key]?.message };
+    return { status: key, message: state[key]?.message, model: state[key]?.model };
   }
   return { status: "idle" };
 }
@@ -137,7 +166,9 @@ function applyPttState(state) {
   if (startButton) startButton.disabled = isRecording;
   if (stopButton) stopButton.disabled = !isRecording;
   setPttStatus(pttState);
-  if (isRecording) {
+  if (pttState === "processing") {
+    setStatus(normalized.model ? `Transcribing with ${normalized.model}...` : "Transcribing...");
+  } else if (isRecording) {
     setStatus("Recording... Press hotkey again to stop.");
   } else if (pttState === "error") {
     setStatus(normalized.message || "Error");
@@ -146,6 +177,14 @@ function applyPttState(state) {
   }
 }
 
+function eventPayload(event) {
+  const data = event?.payload;
+  if (data && typeof data === "object" && typeof data.seq === "number" && "payload" in data) {
+    return data.payload;
+  }
+  return data;
+}
+
 async function refreshLastTranscript() {
   if (!invokeCommand) return;
   try {
@@ -219,23 +258,76 @@ async function initializeBackendBridge() {
     if (outputMode && settings?.output_mode) {
       outputMode.value = settings.output_mode;
     }
+    if (restoreClipboard && settings) {
+      restoreClipboard.checked = Boolean(settings.restore_clipboard);
+    }
+    if (pttMode && settings?.ptt_mode) {
+      pttMode.value = settings.ptt_mode;
+    }
+    if (consumeHotkey && settings) {
+      consumeHotkey.checked = Boolean(settings.consume_hotkey);
+    }
+    showTimestamps = Boolean(settings?.show_timestamps);
+    if (settings?.hotkey?.key) {
+      const { key, ctrl, alt, shift, meta } = settings.hotkey;
+      pendingHotkey = { key, modifiers: { ctrl, alt, shift, meta } };
+      if (hotkeyInput) {
+        hotkeyInput.value = formatHotkey(pendingHotkey);
+      }
+      if (hotkeyPreview) {
+        hotkeyPreview.textContent = `Current: ${formatHotkey(pendingHotkey)}`;
+      }
+    }
   } catch (error) {
     setStatus("Backend unavailable");
   }
 
   if (listen) {
     listen("ptt_state", (event) => {
-      applyPttState(event?.payload);
+      applyPttState(eventPayload(event));
     });
     listen("ptt_transcription", (event) => {
-      if (typeof event?.payload === "string") {
-        latestTranscript = event.payload;
-        setTranscriptOutput(event.payload || "(empty transcript)");
+      const payload = eventPayload(event);
+      if (payload && typeof payload.text === "string") {
+        latestTranscript = payload.text;
+        setTranscriptOutput(formatTranscription(payload) || "(empty transcript)");
+      }
+    });
+    listen("ptt_transcription_partial", (event) => {
+      const payload = eventPayload(event);
+      if (payload && typeof payload.text === "string") {
+        setTranscriptOutput(payload.text);
+      }
+    });
+    listen("ptt_language_detected", (event) => {
+      const payload = eventPayload(event);
+      if (payload && typeof payload.language === "string") {
+        setStatus(`Detected: ${languageName(payload.language)}`);
       }
     });
+    listen("file_transcription", (event) => {
+      const payload = eventPayload(event);
+      if (!payload || typeof payload.stage !== "string") {
+        return;
+      }
+      if (payload.stage === "completed" && payload.entry) {
+        latestTranscript = payload.entry.text;
+        setTranscriptOutput(formatTranscription(payload.entry) || "(empty transcript)");
+        setStatus("File transcribed");
+      } else if (payload.stage === "failed") {
+        setStatus(payload.error || "File transcription failed");
+      } else {
+        setStatus(`File ${payload.stage}...`);
+      }
+    });
+    listen("ptt_cancelled", (event) => {
+      applyPttState(eventPayload(event));
+      setStatus("Capture cancelled");
+    });
     listen("ptt_error", (event) => {
-      if (typeof event?.payload === "string") {
-        setStatus(event.payload);
+      const message = eventPayload(event);
+      if (typeof message === "string") {
+        setStatus(message);
       }
     });
   }
@@ -256,8 +348,19 @@ if (applyHotkey) {
       setStatus("Press a shortcut first");
       return;
     }
+    const setHotkey = (replace) =>
+      invokeCommand("ipc_ptt_set_hotkey", { payload: pendingHotkey, replace });
     try {
-      const next = await invokeCommand("ipc_ptt_set_hotkey", pendingHotkey);
+      let next;
+      try {
+        next = await setHotkey(false);
+      } catch (error) {
+        const message = String(error);
+        if (!message.includes("already bound") || !window.confirm(`${message}. Replace it?`)) {
+          throw error;
+        }
+        next = await setHotkey(true);
+      }
       pendingHotkey = next;
       if (hotkeyPreview) {
         hotkeyPreview.textContent = `Current: ${formatHotkey(next)}`;
@@ -284,6 +387,56 @@ if (outputMode) {
   });
 }
 
+if (restoreClipboard) {
+  restoreClipboard.addEventListener("change", async () => {
+    if (!invokeCommand) {
+      setStatus("IPC unavailable");
+      return;
+    }
+    try {
+      await invokeCommand("ipc_update_settings", {
+        update: { restore_clipboard: restoreClipboard.checked },
+      });
+      setStatus("Clipboard restore updated");
+    } catch (error) {
+      setStatus("Clipboard restore update failed");
+    }
+  });
+}
+
+if (pttMode) {
+  pttMode.addEventListener("change", async () => {
+    if (!invokeCommand) {
+      setStatus("IPC unavailable");
+      return;
+    }
+    try {
+      await invokeCommand("ipc_update_settings", { update: { ptt_mode: pttMode.value } });
+      setStatus("Hotkey mode updated");
+    } catch (error) {
+      setStatus("Hotkey mode update failed");
+    }
+  });
+}
+
+if (consumeHotkey) {
+  consumeHotkey.addEventListener("change", async () => {
+    if (!invokeCommand) {
+      setStatus("IPC unavailable");
+      return;
+    }
+    try {
+      await invokeCommand("ipc_update_settings", {
+        update: { consume_hotkey: consumeHotkey.checked },
+      });
+      const restart = await invokeCommand("ipc_hotkey_restart_required");
+      setStatus(restart ? "Hotkey consumption saved; restart to apply" : "Hotkey consumption updated");
+    } catch (error) {
+      setStatus("Hotkey consumption update failed");
+    }
+  });
+}
+
 if (themeToggle) {
   const storedTheme = localStorage.getItem("openwhisperai-theme");
   const initialTheme = storedTheme === "dark" ? "dark" : "light";
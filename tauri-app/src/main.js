@@ -161,6 +161,9 @@ function normalizeBackendState(state) {
   if (!state) return { status: "idle" };
   if (typeof state === "string") return { status: state };
   if (typeof state === "object") {
+    if ("since_ms" in state && "state" in state) {
+      return { ...normalizeBackendState(state.state), sinceMs: state.since_ms };
+    }
     const [key] = Object.keys(state);
     if (!key) return { status: "idle" };
     return { status: key, message: state[key]?.message };
@@ -197,8 +200,10 @@ async function initializeBackendBridge() {
 
   if (listen) {
     listen("backend-log", (event) => {
-      if (event?.payload?.message) {
-        setBackendLog(event.payload.message);
+      const data = event?.payload;
+      const entry = typeof data?.seq === "number" ? data.payload : data;
+      if (entry?.message) {
+        setBackendLog(entry.message);
       }
     });
   }